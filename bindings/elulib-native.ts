@@ -0,0 +1,3331 @@
+// Generated from the native commands by src-tauri/src/bindings.rs. Do not edit.
+// Regenerate with `npm run bindings`.
+import { decode } from "@msgpack/msgpack";
+import { invoke } from "@tauri-apps/api/core";
+import { listen as listenEvent } from "@tauri-apps/api/event";
+
+/**
+ * Accessibility preferences of the device
+ */
+export type AccessibilityStatus = { 
+/**
+ * A screen reader (VoiceOver / TalkBack) is running
+ */
+screenReaderActive: boolean; 
+/**
+ * The user asked for bold text (iOS) or a bold font weight (Android 12+)
+ */
+boldText: boolean; 
+/**
+ * The user asked to reduce motion (iOS) or to remove animations (Android)
+ */
+reduceMotion: boolean }
+
+/**
+ * Active policy, as returned to the frontend
+ */
+export type ActivePolicy = { 
+/**
+ * Signals currently raised
+ */
+signals: Signal[]; 
+/**
+ * Consequences currently applied
+ */
+consequences: Consequence[] }
+
+/**
+ * Result of a successful negotiation, returned by `negotiate_api`
+ */
+export type ApiNegotiation = { 
+/**
+ * Version the native layer answers with until the next page load
+ */
+version: number; 
+/**
+ * Latest version supported by the app
+ */
+latest: number; 
+/**
+ * Oldest version supported by the app
+ */
+oldest: number; 
+/**
+ * Version of the app
+ */
+appVersion: string }
+
+/**
+ * Payload of `lifecycle://background`
+ */
+export type AppBackground = null
+
+/**
+ * Payload of `lifecycle://foreground`
+ */
+export type AppForeground = null
+
+/**
+ * Payload of `lifecycle://resume`, emitted after `lifecycle://foreground`
+ */
+export type AppResumed = { 
+/**
+ * Time spent in background (seconds)
+ */
+away_secs: number }
+
+/**
+ * Snapshot of the frontend state
+ */
+export type AppStateSnapshot = { 
+/**
+ * Last route displayed by the frontend
+ */
+lastRoute: string | null; 
+/**
+ * Scroll offsets, keyed by route or element identifier
+ */
+scrollHints?: Partial<{ [key in string]: number }>; 
+/**
+ * Identifiers of drafts that were being edited
+ */
+pendingDraftIds?: string[]; 
+/**
+ * Time the snapshot was saved (seconds since the Unix epoch)
+ */
+savedAt?: number }
+
+/**
+ * Authenticator response of an authentication
+ */
+export type AssertionResponse = { 
+/**
+ * Client data (base64url)
+ */
+clientDataJSON: string; 
+/**
+ * Authenticator data (base64url)
+ */
+authenticatorData: string; 
+/**
+ * Assertion signature (base64url)
+ */
+signature: string; 
+/**
+ * User handle of the credential (base64url)
+ */
+userHandle?: string | null }
+
+/**
+ * Kind of attachment, selecting the viewer
+ */
+export type AttachmentKind = 
+/**
+ * PDF document
+ */
+"pdf" | 
+/**
+ * Image
+ */
+"image" | 
+/**
+ * Word processing, spreadsheet or presentation document
+ */
+"office" | 
+/**
+ * Plain text
+ */
+"text" | 
+/**
+ * ZIP archive
+ */
+"archive"
+
+/**
+ * Detected type of an attachment
+ */
+export type AttachmentType = { 
+/**
+ * Kind of attachment
+ */
+kind: AttachmentKind; 
+/**
+ * MIME type handed to the viewer
+ */
+mimeType: string }
+
+/**
+ * Attestation service that produced a token
+ */
+export type AttestationProvider = 
+/**
+ * Google Play Integrity API
+ */
+"play_integrity" | 
+/**
+ * Apple App Attest
+ */
+"app_attest"
+
+/**
+ * Authenticator response of a registration
+ */
+export type AttestationResponse = { 
+/**
+ * Client data (base64url)
+ */
+clientDataJSON: string; 
+/**
+ * Attestation object (base64url)
+ */
+attestationObject: string; 
+/**
+ * Transports of the new credential
+ */
+transports?: string[] }
+
+/**
+ * Attestation token to be verified by the backend
+ */
+export type AttestationToken = { 
+/**
+ * Service that produced the token
+ */
+provider: AttestationProvider; 
+/**
+ * Opaque attestation token (base64)
+ */
+token: string; 
+/**
+ * App Attest key identifier (iOS only)
+ */
+keyId: string | null; 
+/**
+ * Indicators found by the binary integrity self-check, reported by the
+ * app itself (filled in by `attest`)
+ */
+selfCheck: IntegrityIndicator[] }
+
+/**
+ * Payload of the `audio-level` event
+ */
+export type AudioLevel = { 
+/**
+ * Normalized input level, from `0.0` (silence) to `1.0` (peak)
+ */
+level: number }
+
+/**
+ * Exported log
+ */
+export type AuditExport = { 
+/**
+ * Records, oldest first
+ */
+records: AuditRecord[]; 
+/**
+ * Head anchored in the keychain, if any
+ */
+anchor: ChainHead | null; 
+/**
+ * Whether the chain and the anchor are intact
+ */
+verified: boolean; 
+/**
+ * Why verification failed
+ */
+error: string | null }
+
+/**
+ * A record of the log
+ */
+export type AuditRecord = { 
+/**
+ * Sequence number, starting at 0
+ */
+seq: number; 
+/**
+ * Time of the event (seconds since the Unix epoch)
+ */
+timestamp: number; 
+/**
+ * Event
+ */
+event: SecurityEvent; 
+/**
+ * Hash of the previous record (hex)
+ */
+prevHash: string; 
+/**
+ * Hash of this record (hex)
+ */
+hash: string }
+
+/**
+ * Authentication method of an attempt
+ */
+export type AuthMethod = 
+/**
+ * Native OAuth/OIDC login
+ */
+"oidc" | 
+/**
+ * Passkey assertion
+ */
+"passkey" | 
+/**
+ * Biometric unlock of a locked session
+ */
+"session_unlock"
+
+/**
+ * Session returned to the frontend
+ */
+export type AuthSession = { 
+/**
+ * Opaque session handle
+ */
+handle: string; 
+/**
+ * Expiry of the access token (seconds since Unix epoch)
+ */
+expiresAt: number | null; 
+/**
+ * Identity provider profile
+ */
+profile: string; 
+/**
+ * eIDAS level of the authentication, for profiles reporting it
+ */
+eidasLevel: EidasLevel | null }
+
+/**
+ * Options of a passkey authentication, as returned by the backend
+ */
+export type AuthenticationOptions = { 
+/**
+ * Server challenge (base64url)
+ */
+challenge: string; 
+/**
+ * Relying party identifier
+ */
+rpId: string; 
+/**
+ * Credentials accepted (any discoverable credential when empty)
+ */
+allowCredentials?: CredentialDescriptor[]; 
+/**
+ * Timeout hint, in milliseconds
+ */
+timeout?: number | null; 
+/**
+ * `required`, `preferred` or `discouraged`
+ */
+userVerification?: string | null }
+
+/**
+ * Passkey assertion, to send to the backend for verification
+ */
+export type AuthenticationResponse = { 
+/**
+ * Credential identifier (base64url)
+ */
+id: string; 
+/**
+ * Credential identifier (base64url)
+ */
+rawId: string; 
+/**
+ * Always `public-key`
+ */
+type: string; 
+/**
+ * Authenticator response
+ */
+response: AssertionResponse; 
+/**
+ * `platform` or `cross-platform`
+ */
+authenticatorAttachment?: string | null }
+
+/**
+ * Authenticator requirements of a registration
+ */
+export type AuthenticatorSelection = { 
+/**
+ * `required`, `preferred` or `discouraged`
+ */
+residentKey?: string | null; 
+/**
+ * `required`, `preferred` or `discouraged`
+ */
+userVerification?: string | null }
+
+/**
+ * Badge reset policy
+ */
+export type BadgeResetPolicy = { 
+/**
+ * Reset the badge automatically
+ */
+enabled: boolean; 
+/**
+ * Wait for the frontend to acknowledge the inbox before resetting
+ */
+requireInboxAck: boolean; 
+/**
+ * Channels whose delivered notifications are dismissed (all if empty)
+ */
+channels: string[] }
+
+/**
+ * Signature of a backend nonce
+ */
+export type BindingAssertion = { 
+/**
+ * Key identifier (base64url SHA-256 of the public key)
+ */
+keyId: string; 
+/**
+ * Public key (base64url SubjectPublicKeyInfo DER)
+ */
+publicKey: string; 
+/**
+ * Signature algorithm, always `ES256`
+ */
+algorithm: string; 
+/**
+ * Signature of `signed_message(nonce)` (base64url ASN.1 DER)
+ */
+signature: string; 
+/**
+ * Where the private key is stored
+ */
+storage: KeyStorage }
+
+/**
+ * A step recorded before a potential failure
+ */
+export type Breadcrumb = { 
+/**
+ * Time of the step (milliseconds since the Unix epoch)
+ */
+timestampMs: number; 
+/**
+ * Category (`lifecycle`, `navigation`, `auth`, ...)
+ */
+category: string; 
+/**
+ * Redacted description of the step
+ */
+message: string }
+
+/**
+ * Carrier and SIM information reported to the frontend
+ */
+export type CarrierInfo = { 
+/**
+ * The phone state permission is granted
+ */
+permissionGranted: boolean; 
+/**
+ * Name of the network operator (e.g. "Orange F")
+ */
+carrierName: string | null; 
+/**
+ * ISO 3166-1 country code of the network operator (lowercase)
+ */
+countryIso: string | null; 
+/**
+ * Mobile country code + mobile network code (e.g. "20801")
+ */
+mccMnc: string | null; 
+/**
+ * The device is roaming, if known
+ */
+roaming: boolean | null }
+
+/**
+ * Sequence number and hash of the last record
+ */
+export type ChainHead = { 
+/**
+ * Sequence number of the last record
+ */
+seq: number; 
+/**
+ * Hash of the last record (hex)
+ */
+hash: string }
+
+/**
+ * Outcome of a test notification on one delivery channel
+ */
+export type ChannelDelivery = { 
+/**
+ * Delivery channel: `native` (with its channel on Android), `badge` or
+ * `webview` (`notification://received` event)
+ */
+channel: string; 
+/**
+ * Error, if the delivery failed
+ */
+error: string | null }
+
+/**
+ * Why the clipboard was cleared, sent with the `clipboard://cleared` event
+ */
+export type ClearReason = 
+/**
+ * The clear delay elapsed
+ */
+"expired" | 
+/**
+ * The app went to the background
+ */
+"background"
+
+/**
+ * Payload of `clipboard://cleared`
+ */
+export type ClipboardCleared = { 
+/**
+ * Why the clipboard was cleared
+ */
+reason: ClearReason }
+
+/**
+ * Outcome of the sync of a collection
+ */
+export type CollectionResult = { 
+/**
+ * Collection name
+ */
+name: string; 
+/**
+ * Number of created or updated records
+ */
+upserted: number; 
+/**
+ * Number of deleted records
+ */
+deleted: number; 
+/**
+ * Error message, if the sync of the collection failed
+ */
+error: string | null }
+
+/**
+ * Error of a failed command
+ */
+export type CommandError = { 
+/**
+ * Stable error code
+ */
+code: ErrorCode; 
+/**
+ * Human-readable description, for logs
+ */
+message: string; 
+/**
+ * Whether the same call may succeed if retried later
+ */
+retryable: boolean }
+
+/**
+ * Metrics of all commands, as returned by `get_metrics`
+ */
+export type CommandMetrics = { 
+/**
+ * Upper bounds of the histogram buckets (milliseconds)
+ */
+bucketBoundsMs: number[]; 
+/**
+ * Metrics by command name, for commands invoked since launch
+ */
+commands: Partial<{ [key in string]: CommandStats }> }
+
+/**
+ * Envelope returned by migrated commands
+ * 
+ * Serialized for the API version of the page when sent to the webview.
+ */
+export type CommandResponse<T> = { 
+/**
+ * Whether the command succeeded
+ */
+ok: boolean; 
+/**
+ * Result of the command, if it succeeded
+ */
+data: T | null; 
+/**
+ * Error of the command, if it failed
+ */
+error: CommandError | null }
+
+/**
+ * Execution metrics of a command
+ */
+export type CommandStats = { 
+/**
+ * Number of invocations
+ */
+invocations: number; 
+/**
+ * Number of invocations returning an error
+ */
+failures: number; 
+/**
+ * Cumulated execution time (milliseconds)
+ */
+totalMs: number; 
+/**
+ * Longest execution time (milliseconds)
+ */
+maxMs: number; 
+/**
+ * Number of invocations per latency bucket (`COMMAND_METRICS_BUCKETS_MS`),
+ * the last bucket counting the invocations above the highest bound
+ */
+histogram: number[] }
+
+/**
+ * Payload of `connectivity://changed`, emitted when a connectivity check
+ * result differs from the previous one
+ */
+export type ConnectivityChanged = { 
+/**
+ * Whether the application server is reachable
+ */
+online: boolean }
+
+/**
+ * Reachability of the application server
+ */
+export type ConnectivityReport = { 
+/**
+ * The server answered a quick connection attempt
+ */
+reachable: boolean; 
+/**
+ * Error of the connection attempt, if any
+ */
+error: string | null; 
+/**
+ * Active network, if available
+ */
+network: NetworkInterfaces | null }
+
+/**
+ * Consequence applied by the policy
+ */
+export type Consequence = 
+/**
+ * Do not cache content in the encrypted database
+ */
+"block_offline_cache" | 
+/**
+ * Lock the session after a shorter inactivity delay
+ */
+"frequent_reauth" | 
+/**
+ * Do not release keychain values to the frontend
+ */
+"disable_keychain_export"
+
+/**
+ * Kind of crash
+ */
+export type CrashKind = 
+/**
+ * Rust panic
+ */
+"panic" | 
+/**
+ * Native crash (signal, uncaught exception), recorded as a minidump
+ */
+"native" | 
+/**
+ * Main thread stall ("app not responding"), detected by the watchdog
+ */
+"hang"
+
+/**
+ * Summary of a pending crash report, readable without decrypting it
+ */
+export type CrashSummary = { 
+/**
+ * Kind of crash
+ */
+kind: CrashKind; 
+/**
+ * Time of the crash (seconds since the Unix epoch)
+ */
+occurredAt: number }
+
+/**
+ * Existing credential, to exclude or allow
+ */
+export type CredentialDescriptor = { 
+/**
+ * Credential identifier (base64url)
+ */
+id: string; 
+/**
+ * Always `public-key`
+ */
+type: string; 
+/**
+ * Transport hints (`internal`, `hybrid`, ...)
+ */
+transports: string[] }
+
+/**
+ * Requested credential algorithm
+ */
+export type CredentialParameter = { 
+/**
+ * COSE algorithm identifier (`-7` for ES256)
+ */
+alg: number; 
+/**
+ * Always `public-key`
+ */
+type: string }
+
+/**
+ * A deep link into the application, emitted as `deeplink://open`
+ */
+export type DeepLink = { 
+/**
+ * The link, as received
+ */
+url: string; 
+/**
+ * Path (with query and fragment) to open in the frontend
+ */
+path: string; 
+/**
+ * Origin of the link
+ */
+source: DeepLinkSource }
+
+/**
+ * Origin of a deep link
+ */
+export type DeepLinkSource = 
+/**
+ * Custom scheme, universal link or app link
+ */
+"link" | 
+/**
+ * Tap on a notification
+ */
+"notification" | 
+/**
+ * Siri Shortcut or App Action (see `shortcuts`)
+ */
+"shortcut"
+
+/**
+ * General information about the device and the application
+ */
+export type DeviceInfo = { 
+/**
+ * Operating system (`ios`, `android`, ...)
+ */
+platform: string; 
+/**
+ * CPU architecture (`aarch64`, `x86_64`, ...)
+ */
+arch: string; 
+/**
+ * Application version
+ */
+appVersion: string; 
+/**
+ * `true` if running in an emulator or simulator
+ */
+isEmulator: boolean }
+
+/**
+ * Lock-screen security status of the device
+ */
+export type DeviceSecurityStatus = { 
+/**
+ * A passcode, PIN, pattern or password protects the lock screen
+ */
+passcodeSet: boolean; 
+/**
+ * At least one biometric credential (Face ID, Touch ID, fingerprint) is enrolled
+ */
+biometricsEnrolled: boolean }
+
+/**
+ * Report attached to the in-app support form
+ */
+export type DiagnosticsReport = 
+/**
+ * Device, storage and log settings
+ */
+({ 
+/**
+ * Time of the export (seconds since the Unix epoch)
+ */
+generatedAt: number; 
+/**
+ * Device and app version
+ */
+device: DeviceInfo; 
+/**
+ * Storage and memory, if available
+ */
+storage: StorageInfo | null; 
+/**
+ * Active log level
+ */
+logLevel: LogLevelSetting; 
+/**
+ * Disk space used by the logs
+ */
+logStorage: LogStorageUsage; 
+/**
+ * Breadcrumb trail, oldest first
+ */
+breadcrumbs: Breadcrumb[] }) & { 
+/**
+ * Runtime permission statuses, if available
+ */
+permissions: PermissionEntry[] | null; 
+/**
+ * Reachability of the application server
+ */
+connectivity: ConnectivityReport; 
+/**
+ * Keychain entries of the app
+ */
+keychain: KeychainStats; 
+/**
+ * Notification permission and channel
+ */
+notifications: NotificationState; 
+/**
+ * Root/jailbreak and tamper indicators of the device
+ */
+integrity: IntegrityReport; 
+/**
+ * Most recent pending crash report, if any
+ */
+lastCrash: CrashSummary | null }
+
+/**
+ * Download, as persisted and sent to the frontend
+ */
+export type Download = { 
+/**
+ * Download identifier
+ */
+id: string; 
+/**
+ * URL of the file
+ */
+url: string; 
+/**
+ * Destination, relative to the app file storage
+ */
+path: string; 
+/**
+ * Expected SHA-256 of the file (lowercase hex), if known
+ */
+sha256: string | null; 
+/**
+ * Size of the file, once known
+ */
+totalBytes: number | null; 
+/**
+ * Bytes downloaded so far
+ */
+downloadedBytes: number; 
+/**
+ * Status of the download
+ */
+status: DownloadStatus; 
+/**
+ * Error of a failed download
+ */
+error: string | null; 
+/**
+ * Entity tag of the file, to resume only if it did not change
+ */
+etag: string | null; 
+/**
+ * Last modification date of the file (`Last-Modified`), to resume only
+ * if it did not change when there is no strong entity tag
+ */
+lastModified: string | null; 
+/**
+ * Time the download was added (seconds since the Unix epoch)
+ */
+createdAt: number }
+
+/**
+ * Payload of the `download://progress` event
+ */
+export type DownloadProgress = { 
+/**
+ * Download identifier
+ */
+id: string; 
+/**
+ * Bytes downloaded so far
+ */
+downloadedBytes: number; 
+/**
+ * Size of the file, if known
+ */
+totalBytes: number | null }
+
+/**
+ * Status of a download
+ */
+export type DownloadStatus = 
+/**
+ * Waiting for a download slot
+ */
+"queued" | 
+/**
+ * Transferring
+ */
+"running" | 
+/**
+ * Paused by the user
+ */
+"paused" | 
+/**
+ * Downloaded and verified
+ */
+"completed" | 
+/**
+ * Failed (see `error`); can be resumed
+ */
+"failed"
+
+/**
+ * Stored draft of a form
+ */
+export type Draft = { 
+/**
+ * Form identifier
+ */
+formId: string; 
+/**
+ * Draft contents, as saved by the frontend
+ */
+payload: JsonValue; 
+/**
+ * When the draft was last saved (seconds since the Unix epoch)
+ */
+updatedAt: number }
+
+/**
+ * eIDAS assurance level of an authentication
+ */
+export type EidasLevel = 
+/**
+ * Low
+ */
+"eidas_1" | 
+/**
+ * Substantial
+ */
+"eidas_2" | 
+/**
+ * High
+ */
+"eidas_3"
+
+/**
+ * Backend environment
+ */
+export type Environment = 
+/**
+ * Production
+ */
+"prod" | 
+/**
+ * Pre-production, mirroring production data
+ */
+"preprod" | 
+/**
+ * Staging, for QA of unreleased features
+ */
+"staging"
+
+/**
+ * Environment status, returned by `environment_get`
+ */
+export type EnvironmentStatus = { 
+/**
+ * Environment in use
+ */
+environment: Environment; 
+/**
+ * Environment selected at build time
+ */
+buildEnvironment: Environment; 
+/**
+ * Environment applied at the next launch, if different
+ */
+pending: Environment | null; 
+/**
+ * Effective application URL (a runtime config file may override it)
+ */
+appUrl: string; 
+/**
+ * Effective connectivity host
+ */
+connectivityHost: string; 
+/**
+ * Push sender identifier
+ */
+pushSenderId: string }
+
+/**
+ * Stable error code of a failed command
+ */
+export type ErrorCode = 
+/**
+ * An argument failed validation (key or value length, ...)
+ */
+"INVALID_ARGUMENT" | 
+/**
+ * The operation is not allowed through this command
+ */
+"FORBIDDEN" | 
+/**
+ * The keystore plugin is not available
+ */
+"KEYCHAIN_UNAVAILABLE" | 
+/**
+ * The platform keychain/keystore operation failed
+ */
+"KEYCHAIN_FAILED" | 
+/**
+ * The keychain holds no value for the key
+ */
+"KEYCHAIN_NOT_FOUND" | 
+/**
+ * The server could not be reached in time
+ */
+"CONNECTIVITY_TIMEOUT" | 
+/**
+ * The connection to the server failed
+ */
+"CONNECTIVITY_FAILED" | 
+/**
+ * The platform notification API failed
+ */
+"NOTIFICATION_FAILED" | 
+/**
+ * The configuration is invalid or unavailable
+ */
+"CONFIG_INVALID" | 
+/**
+ * Unexpected internal error (I/O, serialization, runtime)
+ */
+"INTERNAL"
+
+/**
+ * Exported archive
+ */
+export type ExportedData = { 
+/**
+ * Name of the archive handed to the share sheet
+ */
+fileName: string; 
+/**
+ * Size of the archive (bytes)
+ */
+bytes: number; 
+/**
+ * Exported sections
+ */
+sections: string[]; 
+/**
+ * Sections that could not be read, with the error
+ */
+failed: Partial<{ [key in string]: string }> }
+
+/**
+ * Exported archive, as reported to the frontend
+ */
+export type ExportedLogs = { 
+/**
+ * Archive file name
+ */
+fileName: string; 
+/**
+ * Archive size (bytes)
+ */
+bytes: number }
+
+/**
+ * Payload of `feature://changed`: the flags whose value changed
+ */
+export type FeatureFlagsChanged = FlagChange[]
+
+/**
+ * File or directory of the app file storage
+ */
+export type FileEntry = { 
+/**
+ * Path relative to the storage directory (`annexes/deliberation-12.pdf`)
+ */
+path: string; 
+/**
+ * `true` for a directory
+ */
+isDir: boolean; 
+/**
+ * Size in bytes (0 for directories)
+ */
+size: number; 
+/**
+ * Last modification time (seconds since the Unix epoch)
+ */
+modifiedAt: number }
+
+/**
+ * A flag whose value changed, emitted with `feature://changed`
+ */
+export type FlagChange = { 
+/**
+ * Flag name
+ */
+name: string; 
+/**
+ * New value of the flag
+ */
+enabled: boolean }
+
+/**
+ * Image cache statistics
+ */
+export type ImageCacheStats = { 
+/**
+ * Number of cached images
+ */
+entries: number; 
+/**
+ * Total size of the cached images (bytes)
+ */
+totalBytes: number; 
+/**
+ * Maximum size of the cache (bytes)
+ */
+maxBytes: number; 
+/**
+ * Requests served from the cache since launch
+ */
+hits: number; 
+/**
+ * Requests downloading the image since launch
+ */
+misses: number; 
+/**
+ * Images evicted since launch
+ */
+evictions: number }
+
+/**
+ * Handle of a cached image, returned to the frontend
+ */
+export type ImageHandle = { 
+/**
+ * Local handle of the image
+ */
+handle: string; 
+/**
+ * Content type of the image
+ */
+contentType: string; 
+/**
+ * Size of the image (bytes)
+ */
+size: number; 
+/**
+ * The image was already cached
+ */
+cached: boolean }
+
+/**
+ * Install information reported to the frontend
+ */
+export type InstallInfo = { 
+/**
+ * This is the first launch since the app was installed
+ */
+firstLaunch: boolean; 
+/**
+ * Time of the first launch (seconds since the Unix epoch)
+ */
+installedAt: number; 
+/**
+ * Current app version
+ */
+currentVersion: string; 
+/**
+ * App version of the previous launch, if it differs from the current one
+ */
+previousVersion: string | null; 
+/**
+ * Source the app was installed from
+ */
+source: InstallSource; 
+/**
+ * Random identifier of this installation, reset on reinstall
+ */
+installationId: string }
+
+/**
+ * Source the app was installed from
+ */
+export type InstallSource = 
+/**
+ * Apple App Store
+ */
+"app_store" | 
+/**
+ * Apple TestFlight
+ */
+"test_flight" | 
+/**
+ * Google Play Store
+ */
+"play_store" | 
+/**
+ * Installed from outside of a store (APK, Xcode, enterprise MDM, ...)
+ */
+"sideload" | 
+/**
+ * Unknown source (desktop, development builds)
+ */
+"unknown"
+
+/**
+ * A single integrity finding
+ */
+export type IntegrityFinding = { 
+/**
+ * Kind of indicator
+ */
+indicator: IntegrityIndicator; 
+/**
+ * Path or mount point that triggered the finding
+ */
+detail: string }
+
+/**
+ * Kind of integrity indicator
+ */
+export type IntegrityIndicator = 
+/**
+ * An `su` binary was found
+ */
+"su_binary" | 
+/**
+ * Magisk files were found
+ */
+"magisk" | 
+/**
+ * The system partition is mounted read-write
+ */
+"writable_system_partition" | 
+/**
+ * Jailbreak tool files were found
+ */
+"jailbreak_files" | 
+/**
+ * The app was able to write outside of its sandbox
+ */
+"sandbox_escape" | 
+/**
+ * The app is not signed with the expected identity
+ */
+"signing_mismatch" | 
+/**
+ * The app bundle lost its code signature
+ */
+"bundle_modified" | 
+/**
+ * A debugger is attached to the app
+ */
+"debugger_attached"
+
+/**
+ * Result of the device integrity checks
+ */
+export type IntegrityReport = { 
+/**
+ * `true` if at least one indicator was found
+ */
+compromised: boolean; 
+/**
+ * Findings that triggered the `compromised` flag
+ */
+findings: IntegrityFinding[] }
+
+/**
+ * Payload of `integrity://tampered`: the findings of the self-check
+ */
+export type IntegrityTampered = IntegrityFinding[]
+
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+
+/**
+ * Where the device-binding key is stored
+ */
+export type KeyStorage = 
+/**
+ * Secure Enclave (iOS)
+ */
+"secure_enclave" | 
+/**
+ * StrongBox secure element (Android)
+ */
+"strong_box" | 
+/**
+ * Trusted execution environment (Android)
+ */
+"trusted_environment" | 
+/**
+ * Software keystore (emulators, devices without secure hardware)
+ */
+"software"
+
+/**
+ * Payload of `keyboard://changed`
+ */
+export type KeyboardChanged = { 
+/**
+ * Whether the software keyboard is shown
+ */
+visible: boolean; 
+/**
+ * Height of the keyboard over the webview (CSS pixels, 0 when hidden)
+ */
+height: number }
+
+/**
+ * Results of `run_keychain`
+ */
+export type KeychainBenchmark = { 
+/**
+ * Number of keys
+ */
+iterations: number; 
+/**
+ * Latency of a store
+ */
+store: LatencyStats; 
+/**
+ * Latency of a retrieve
+ */
+retrieve: LatencyStats; 
+/**
+ * Latency of a remove
+ */
+remove: LatencyStats; 
+/**
+ * Total time storing the values as one keychain item each, as separate
+ * commands do
+ */
+sequentialUs: number; 
+/**
+ * Total time storing the values together as a single keychain item
+ */
+batchUs: number }
+
+/**
+ * Keychain entries of the app (names only)
+ */
+export type KeychainStats = { 
+/**
+ * Number of entries checked
+ */
+checked: number; 
+/**
+ * Names of the entries present
+ */
+present: string[] }
+
+/**
+ * Language of the native strings
+ */
+export type Language = 
+/**
+ * French (default)
+ */
+"french" | 
+/**
+ * English
+ */
+"english"
+
+/**
+ * Latency distribution of an operation, in microseconds
+ */
+export type LatencyStats = { 
+/**
+ * Number of samples
+ */
+samples: number; 
+/**
+ * Fastest sample
+ */
+minUs: number; 
+/**
+ * Median sample
+ */
+medianUs: number; 
+/**
+ * 95th percentile
+ */
+p95Us: number; 
+/**
+ * Slowest sample
+ */
+maxUs: number }
+
+/**
+ * Notification that launched the app, returned by `get_launch_payload`
+ */
+export type LaunchPayload = { 
+/**
+ * Original data of the push notification
+ */
+payload: JsonValue; 
+/**
+ * Notification title
+ */
+title: string; 
+/**
+ * Notification body text
+ */
+body: string; 
+/**
+ * Deep link of the notification, if it has a valid one
+ */
+link: DeepLink | null; 
+/**
+ * When the notification was tapped (seconds since the Unix epoch)
+ */
+receivedAt: number }
+
+/**
+ * Current locale, payload of the `locale://changed` event
+ */
+export type LocaleInfo = { 
+/**
+ * System locale tag (`fr-FR`, `en-US`, ...)
+ */
+locale: string; 
+/**
+ * Language of the native strings
+ */
+language: Language }
+
+/**
+ * Why the session locked, sent with the `session://locked` event
+ */
+export type LockReason = 
+/**
+ * No user activity in foreground for the timeout
+ */
+"idle" | 
+/**
+ * The app spent longer than the timeout in background
+ */
+"background"
+
+/**
+ * Log level setting, as persisted and reported to the frontend
+ */
+export type LogLevelSetting = { 
+/**
+ * Level: `off`, `error`, `warn`, `info`, `debug` or `trace`
+ */
+level: string; 
+/**
+ * Module path the level applies to, or all modules if `None`
+ */
+moduleFilter?: string | null }
+
+/**
+ * Log file line parsed back into its parts
+ */
+export type LogLine = { 
+/**
+ * Time of the record (seconds since the Unix epoch)
+ */
+timestamp: number; 
+/**
+ * Module path of the record
+ */
+module: string; 
+/**
+ * Level (`ERROR`, `WARN`, `INFO`, `DEBUG`, `TRACE`)
+ */
+level: string; 
+/**
+ * Redacted message, possibly spanning several lines
+ */
+message: string }
+
+/**
+ * Log shipping status, as reported to the frontend
+ */
+export type LogShippingStatus = { 
+/**
+ * Log records are being shipped
+ */
+enabled: boolean; 
+/**
+ * The user opted in
+ */
+optedIn: boolean; 
+/**
+ * End of the support grant, if any (seconds since the Unix epoch)
+ */
+supportExpiresAt: number | null; 
+/**
+ * Records waiting for upload
+ */
+queuedRecords: number; 
+/**
+ * Records dropped because the queue was full, not reported yet
+ */
+droppedRecords: number }
+
+/**
+ * Disk space used by the log directory, as reported to the frontend
+ */
+export type LogStorageUsage = { 
+/**
+ * Total size of the log files (bytes)
+ */
+totalBytes: number; 
+/**
+ * Number of log files
+ */
+fileCount: number; 
+/**
+ * Maximum size of the log files under the rotation limits (bytes)
+ */
+maxBytes: number }
+
+/**
+ * Network interface details reported to the frontend
+ */
+export type NetworkInterfaces = { 
+/**
+ * Name of the active interface, if known
+ */
+activeInterface: string | null; 
+/**
+ * Transport of the active network
+ */
+transport: NetworkTransport; 
+/**
+ * Local IP address used to reach the application server, if any
+ */
+localIp: string | null; 
+/**
+ * A VPN is active
+ */
+vpnActive: boolean }
+
+/**
+ * Transport of the active network
+ */
+export type NetworkTransport = 
+/**
+ * Wi-Fi network
+ */
+"wifi" | 
+/**
+ * Cellular data network
+ */
+"cellular" | 
+/**
+ * Wired network
+ */
+"ethernet" | 
+/**
+ * Other or unknown transport
+ */
+"other" | 
+/**
+ * No active network
+ */
+"none"
+
+/**
+ * Payload of `notification://received`, emitted for every visible push
+ */
+export type NotificationReceived = { 
+/**
+ * Notification title
+ */
+title: string; 
+/**
+ * Notification body text
+ */
+body: string; 
+/**
+ * Link opened when the notification is tapped
+ */
+link: string | null; 
+/**
+ * Whether the user tapped the notification
+ */
+tapped: boolean }
+
+/**
+ * Notification permission and channel
+ */
+export type NotificationState = { 
+/**
+ * Notifications are allowed, if known
+ */
+permissionGranted: boolean | null; 
+/**
+ * Notification channel, on platforms with channels
+ */
+channel: string | null }
+
+/**
+ * Event queued while the page was not ready
+ */
+export type OutboxMessage = { 
+/**
+ * Identifier, acknowledged with `outbox_ack`
+ */
+id: number; 
+/**
+ * Channel the event was emitted on
+ */
+channel: string; 
+/**
+ * Event payload
+ */
+payload: JsonValue; 
+/**
+ * When the event was queued (seconds since the Unix epoch)
+ */
+createdAt: number }
+
+/**
+ * User account of a registration
+ */
+export type PasskeyUser = { 
+/**
+ * Opaque user handle (base64url)
+ */
+id: string; 
+/**
+ * Account name (e.g. an email)
+ */
+name: string; 
+/**
+ * Name displayed by the system sheet
+ */
+displayName: string }
+
+/**
+ * Runtime permissions managed by the application
+ */
+export type Permission = 
+/**
+ * Permission to display notifications
+ */
+"notifications" | 
+/**
+ * Permission to access the camera
+ */
+"camera" | 
+/**
+ * Permission to access the device location
+ */
+"location" | 
+/**
+ * Permission to record audio with the microphone
+ */
+"microphone" | 
+/**
+ * Permission to read the user's contacts
+ */
+"contacts" | 
+/**
+ * Permission to read the phone state (SIM and carrier details)
+ */
+"phone_state"
+
+/**
+ * Status of a single permission, as reported by `status_all()`
+ */
+export type PermissionEntry = { 
+/**
+ * The permission
+ */
+permission: Permission; 
+/**
+ * The current status of the permission
+ */
+status: PermissionStatus }
+
+/**
+ * Status of a runtime permission
+ */
+export type PermissionStatus = 
+/**
+ * The permission has been granted by the user
+ */
+"granted" | 
+/**
+ * The permission has not been granted, but can still be requested
+ */
+"denied" | 
+/**
+ * The permission was denied and the system will no longer prompt the user
+ * 
+ * The user must enable it manually from the system settings.
+ */
+"permanently_denied" | 
+/**
+ * The permission cannot be granted (parental controls, MDM policy, etc.)
+ */
+"restricted"
+
+/**
+ * Pin validation failure, sent with the pin-failure event
+ */
+export type PinFailure = { 
+/**
+ * Host whose certificate chain did not match
+ */
+host: string; 
+/**
+ * SPKI hashes of the presented chain, leaf first
+ */
+presented: string[] }
+
+/**
+ * Preference value
+ */
+export type PrefValue = 
+/**
+ * Boolean value
+ */
+boolean | 
+/**
+ * Integer value
+ */
+number | 
+/**
+ * String value
+ */
+string
+
+/**
+ * Prefetch status of a document
+ */
+export type PrefetchEntry = { 
+/**
+ * Document to prefetch
+ */
+item: PrefetchItem; 
+/**
+ * Download of the document, once started
+ */
+download: Download | null; 
+/**
+ * Vault identifier of the document, once downloaded (`vault_open`)
+ */
+vaultId: string | null; 
+/**
+ * Why the document is not downloaded (quota, invalid URL), if skipped
+ */
+skipped: string | null }
+
+/**
+ * Document to prefetch, as given by the frontend
+ */
+export type PrefetchItem = { 
+/**
+ * `https` URL of the document
+ */
+url: string; 
+/**
+ * Destination, relative to the app file storage
+ */
+path: string; 
+/**
+ * Size of the document (bytes), checked against the storage quota
+ */
+size: number | null; 
+/**
+ * Expected SHA-256 of the document (hex), if known
+ */
+sha256: string | null }
+
+/**
+ * Prefetch status, returned to the frontend
+ */
+export type PrefetchStatus = { 
+/**
+ * Whether the network and power conditions currently allow prefetching
+ */
+conditionsMet: boolean; 
+/**
+ * Status of each document
+ */
+entries: PrefetchEntry[] }
+
+/**
+ * Value of an event property
+ */
+export type PropValue = 
+/**
+ * Boolean value
+ */
+boolean | 
+/**
+ * Numeric value
+ */
+number | 
+/**
+ * Short identifier (screen name, feature name, ...)
+ */
+string
+
+/**
+ * Identity provider configuration, provided by the frontend
+ */
+export type ProviderConfig = { 
+/**
+ * Authorization endpoint (https)
+ */
+authorizationEndpoint: string; 
+/**
+ * Token endpoint (https)
+ */
+tokenEndpoint: string; 
+/**
+ * OAuth client identifier (public client, no secret)
+ */
+clientId: string; 
+/**
+ * Redirect URI, an `elulib://` link or an app link
+ */
+redirectUri: string; 
+/**
+ * Requested scopes
+ */
+scopes?: string[]; 
+/**
+ * Additional authorization request parameters
+ */
+extraParams?: Partial<{ [key in string]: string }>; 
+/**
+ * Identity provider profile (`oidc` when not set, `franceconnect`)
+ */
+profile?: string | null; 
+/**
+ * End-session (logout) endpoint (https)
+ */
+endSessionEndpoint?: string | null; 
+/**
+ * Redirect URI after logout, an `elulib://` link or an app link
+ */
+postLogoutRedirectUri?: string | null }
+
+/**
+ * Visible part of a push
+ */
+export type PushAlert = { 
+/**
+ * Notification title
+ */
+title: string; 
+/**
+ * Notification body text
+ */
+body: string }
+
+/**
+ * What the app did with a push, returned by `simulate_push`
+ */
+export type PushRouting = { 
+/**
+ * `sync`, `visible` or `ignored`
+ */
+kind: string; 
+/**
+ * Report of the sync started by a sync push
+ */
+sync: SyncReport | null; 
+/**
+ * Notification shown for a visible push
+ */
+notification: PushAlert | null; 
+/**
+ * Error showing the notification, if any
+ */
+notificationError: string | null; 
+/**
+ * Deep link routed when the notification was tapped
+ */
+deepLink: DeepLink | null }
+
+/**
+ * Rejection counters
+ */
+export type RateLimitMetrics = { 
+/**
+ * Rejected invocations by command
+ */
+rejected: Partial<{ [key in string]: number }>; 
+/**
+ * Invocations rejected by the global ceiling
+ */
+globalRejected: number }
+
+/**
+ * Metadata of a completed recording
+ */
+export type RecordingInfo = { 
+/**
+ * Absolute path of the `.m4a` file
+ */
+path: string; 
+/**
+ * Duration of the recording in milliseconds
+ */
+durationMs: number }
+
+/**
+ * Summary of a stopped recording
+ */
+export type RecordingSummary = { 
+/**
+ * Recording file
+ */
+path: string; 
+/**
+ * Number of recorded invokes
+ */
+invokes: number }
+
+/**
+ * Options of a passkey registration, as returned by the backend
+ */
+export type RegistrationOptions = { 
+/**
+ * Server challenge (base64url)
+ */
+challenge: string; 
+/**
+ * Relying party
+ */
+rp: RelyingParty; 
+/**
+ * User account
+ */
+user: PasskeyUser; 
+/**
+ * Acceptable algorithms, by preference
+ */
+pubKeyCredParams: CredentialParameter[]; 
+/**
+ * Timeout hint, in milliseconds
+ */
+timeout?: number | null; 
+/**
+ * Credentials already registered for the user
+ */
+excludeCredentials?: CredentialDescriptor[]; 
+/**
+ * Authenticator requirements
+ */
+authenticatorSelection?: AuthenticatorSelection; 
+/**
+ * Attestation conveyance (`none`, `direct`, ...)
+ */
+attestation?: string | null }
+
+/**
+ * New passkey, to send to the backend for verification
+ */
+export type RegistrationResponse = { 
+/**
+ * Credential identifier (base64url)
+ */
+id: string; 
+/**
+ * Credential identifier (base64url)
+ */
+rawId: string; 
+/**
+ * Always `public-key`
+ */
+type: string; 
+/**
+ * Authenticator response
+ */
+response: AttestationResponse; 
+/**
+ * `platform` or `cross-platform`
+ */
+authenticatorAttachment?: string | null }
+
+/**
+ * Relying party of a registration
+ */
+export type RelyingParty = { 
+/**
+ * Relying party identifier (a domain)
+ */
+id: string; 
+/**
+ * Display name
+ */
+name: string }
+
+/**
+ * Security event
+ */
+export type SecurityEvent = 
+/**
+ * Authentication attempt
+ */
+{ type: "auth_attempt"; method: AuthMethod; success: boolean } | 
+/**
+ * Runtime permission status change
+ */
+{ type: "permission_change"; permission: Permission; status: PermissionStatus } | 
+/**
+ * Binary integrity self-check failure
+ */
+{ type: "integrity_failure"; indicators: IntegrityIndicator[] } | 
+/**
+ * Webview TLS pin failure
+ */
+{ type: "pin_failure"; host: string }
+
+/**
+ * Payload of `session://locked`
+ */
+export type SessionLocked = { 
+/**
+ * Why the session locked
+ */
+reason: LockReason }
+
+/**
+ * Payload of `session://unlocked`
+ */
+export type SessionUnlocked = null
+
+/**
+ * Payload of `gesture://shake`
+ */
+export type ShakeDetected = null
+
+/**
+ * File shared with the app, payload of `share://received`
+ */
+export type SharedFile = { 
+/**
+ * Path in the app file storage (`shared/1700000000000-minutes.pdf`)
+ */
+path: string; 
+/**
+ * Name of the file, as shared
+ */
+name: string; 
+/**
+ * Detected MIME type
+ */
+mimeType: string; 
+/**
+ * Size in bytes
+ */
+size: number; 
+/**
+ * When the file was received (milliseconds since the Unix epoch)
+ */
+receivedAt: number }
+
+/**
+ * Screen that can be opened from a shortcut
+ */
+export type Shortcut = 
+/**
+ * Next meeting ("Ouvrir la prochaine séance")
+ */
+"next_meeting" | 
+/**
+ * Documents list ("Voir mes documents")
+ */
+"documents"
+
+/**
+ * Device trust signal
+ */
+export type Signal = 
+/**
+ * Root, jailbreak or tamper indicators were found
+ */
+"integrity_compromised" | 
+/**
+ * No passcode protects the lock screen
+ */
+"no_lock_screen" | 
+/**
+ * The attestation service rejected the last attestation request
+ */
+"attestation_failed"
+
+/**
+ * Payload of `sse://message`, emitted for every event of a stream
+ */
+export type SseMessage = { 
+/**
+ * Subscription id
+ */
+subscription: number; 
+/**
+ * Event type (`message` unless named by the server)
+ */
+event: string; 
+/**
+ * Event data, lines joined with `\n`
+ */
+data: string; 
+/**
+ * Event id, if the server sent one
+ */
+id: string | null }
+
+/**
+ * State of a subscription
+ */
+export type SseState = 
+/**
+ * Opening the stream
+ */
+"connecting" | 
+/**
+ * Receiving events
+ */
+"open" | 
+/**
+ * Waiting before the next connection attempt
+ */
+"reconnecting" | 
+/**
+ * Closed by `unsubscribe` or by the server
+ */
+"closed"
+
+/**
+ * Payload of `sse://state`, also returned by `sse_subscribe`
+ */
+export type SseStatus = { 
+/**
+ * Subscription id
+ */
+id: number; 
+/**
+ * Endpoint of the stream
+ */
+url: string; 
+/**
+ * Subscription state
+ */
+state: SseState; 
+/**
+ * Id of the last event received, sent back when reconnecting
+ */
+lastEventId: string | null; 
+/**
+ * Failed connection attempts since the last successful one
+ */
+attempt: number }
+
+/**
+ * Startup durations, in milliseconds since process start
+ */
+export type StartupMetrics = { 
+/**
+ * Time to configure the Tauri builder
+ */
+builderReadyMs: number | null; 
+/**
+ * Time to create the main webview
+ */
+webviewCreatedMs: number | null; 
+/**
+ * Time to the first page load
+ */
+firstPageLoadMs: number | null }
+
+/**
+ * Result of a step
+ */
+export type StepResult = { 
+/**
+ * Step
+ */
+step: WipeStep; 
+/**
+ * Error of a failed step
+ */
+error: string | null }
+
+/**
+ * Storage and memory information reported to the frontend
+ */
+export type StorageInfo = { 
+/**
+ * Total storage of the data volume (bytes)
+ */
+totalBytes: number | null; 
+/**
+ * Free storage of the data volume (bytes)
+ */
+freeBytes: number | null; 
+/**
+ * Size of the app cache directory (bytes)
+ */
+appCacheBytes: number; 
+/**
+ * Size of the app data directory (bytes)
+ */
+appDataBytes: number; 
+/**
+ * Total physical memory (bytes)
+ */
+memoryTotalBytes: number | null; 
+/**
+ * Memory currently available to the app (bytes)
+ */
+memoryAvailableBytes: number | null }
+
+/**
+ * Payload of the `sync://completed` event, also returned by `sync_now`
+ */
+export type SyncReport = { 
+/**
+ * What started the sync
+ */
+trigger: SyncTrigger; 
+/**
+ * Outcome of each synced collection
+ */
+collections: CollectionResult[]; 
+/**
+ * Time the sync completed (seconds since the Unix epoch)
+ */
+completedAt: number }
+
+/**
+ * What started a sync
+ */
+export type SyncTrigger = 
+/**
+ * Periodic sync while in foreground
+ */
+"schedule" | 
+/**
+ * Silent push notification
+ */
+"push" | 
+/**
+ * Background refresh task run by the OS
+ */
+"background" | 
+/**
+ * `sync_now` command
+ */
+"manual"
+
+/**
+ * Update availability, as reported by `check_app_update`
+ */
+export type UpdateInfo = { 
+/**
+ * Installed version
+ */
+currentVersion: string; 
+/**
+ * Latest version available in the store
+ */
+latestVersion: string; 
+/**
+ * `true` if a newer version is available
+ */
+updateAvailable: boolean; 
+/**
+ * `true` if the installed version is below the minimum supported version
+ */
+updateRequired: boolean; 
+/**
+ * Store page of the app, if known
+ */
+storeUrl: string | null }
+
+/**
+ * In-app update flow type
+ */
+export type UpdateMode = 
+/**
+ * Background download, installed on demand
+ */
+"flexible" | 
+/**
+ * Blocking full-screen update
+ */
+"immediate"
+
+/**
+ * Outcome of an in-app update flow
+ */
+export type UpdateOutcome = 
+/**
+ * The flexible update is downloaded and ready to install
+ */
+"downloaded" | 
+/**
+ * The update was installed
+ */
+"installed" | 
+/**
+ * The user declined or cancelled the update
+ */
+"cancelled" | 
+/**
+ * The update failed
+ */
+"failed"
+
+/**
+ * Download progress of an in-app update
+ */
+export type UpdateProgress = { 
+/**
+ * Bytes downloaded so far
+ */
+bytesDownloaded: number; 
+/**
+ * Total size of the update, in bytes
+ */
+totalBytes: number }
+
+/**
+ * Result of an in-app update flow, emitted as `update://result`
+ */
+export type UpdateResult = { 
+/**
+ * The update flow type
+ */
+mode: UpdateMode; 
+/**
+ * The outcome of the flow
+ */
+outcome: UpdateOutcome; 
+/**
+ * Error details, if the update failed
+ */
+error: string | null }
+
+/**
+ * Upload, as persisted and sent to the frontend
+ */
+export type Upload = { 
+/**
+ * Upload identifier
+ */
+id: string; 
+/**
+ * File to upload, relative to the app file storage
+ */
+path: string; 
+/**
+ * Target URL
+ */
+url: string; 
+/**
+ * HTTP method (`POST` or `PUT`)
+ */
+method: string; 
+/**
+ * Content type of the request body
+ */
+contentType: string; 
+/**
+ * Authorization sent with the upload
+ */
+auth: UploadAuth | null; 
+/**
+ * Wait for an unmetered network
+ */
+deferOnMetered: boolean; 
+/**
+ * Status of the upload
+ */
+status: UploadStatus; 
+/**
+ * Failed attempts so far
+ */
+attempts: number; 
+/**
+ * Earliest time of the next attempt (seconds since the Unix epoch)
+ */
+nextAttemptAt: number; 
+/**
+ * Status code of the last response
+ */
+responseStatus: number | null; 
+/**
+ * Error of the last failed attempt
+ */
+error: string | null; 
+/**
+ * Time the upload was enqueued (seconds since the Unix epoch)
+ */
+createdAt: number }
+
+/**
+ * Reference to the authorization sent with an upload
+ */
+export type UploadAuth = 
+/**
+ * Bearer session token (application server only)
+ */
+{ type: "session" } | 
+/**
+ * Bearer token stored in the keychain under `key` (application server only)
+ */
+{ type: "keychain"; key: string }
+
+/**
+ * Status of an upload
+ */
+export type UploadStatus = 
+/**
+ * Waiting to be uploaded
+ */
+"queued" | 
+/**
+ * Uploading
+ */
+"uploading" | 
+/**
+ * Failed, retried at `nextAttemptAt`
+ */
+"retrying" | 
+/**
+ * Uploaded
+ */
+"completed" | 
+/**
+ * Failed permanently (see `error`)
+ */
+"failed"
+
+/**
+ * Document stored in the vault, as sent to the frontend
+ */
+export type VaultItem = { 
+/**
+ * Vault identifier
+ */
+id: string; 
+/**
+ * File name of the document
+ */
+name: string; 
+/**
+ * Size of the document (bytes)
+ */
+size: number; 
+/**
+ * Time the document was stored (seconds since the Unix epoch)
+ */
+storedAt: number; 
+/**
+ * Time the document was last opened (seconds since the Unix epoch)
+ */
+lastOpenedAt: number | null; 
+/**
+ * Time after which the document is purged (seconds since the Unix epoch)
+ */
+expiresAt: number | null }
+
+/**
+ * Payload of `watch://mark-read`, posted when a meeting is marked as read
+ * from the watch
+ */
+export type WatchMarkRead = { 
+/**
+ * Identifier of the agenda record
+ */
+meetingId: string; 
+/**
+ * When the meeting was marked as read (seconds since the Unix epoch)
+ */
+readAt: number }
+
+/**
+ * Payload of `ws://message`, emitted for every message from the server
+ */
+export type WebSocketMessage = { 
+/**
+ * Text of the message, or its base64 encoding for a binary message
+ */
+data: string; 
+/**
+ * Whether the message was binary
+ */
+binary: boolean }
+
+/**
+ * State of the realtime connection
+ */
+export type WebSocketState = 
+/**
+ * Opening the connection
+ */
+"connecting" | 
+/**
+ * Connected
+ */
+"open" | 
+/**
+ * Waiting before the next connection attempt
+ */
+"reconnecting" | 
+/**
+ * Closed while the app is in background
+ */
+"suspended" | 
+/**
+ * Closed by `close`, or never opened
+ */
+"closed"
+
+/**
+ * Payload of `ws://state`, also returned by `ws_status`
+ */
+export type WebSocketStatus = { 
+/**
+ * Connection state
+ */
+state: WebSocketState; 
+/**
+ * Endpoint of the connection, if any
+ */
+url: string | null; 
+/**
+ * Failed connection attempts since the last successful one
+ */
+attempt: number; 
+/**
+ * Messages waiting to be sent
+ */
+queued: number }
+
+/**
+ * Verification report of the wipe
+ */
+export type WipeReport = { 
+/**
+ * Steps, in the order they ran
+ */
+steps: StepResult[]; 
+/**
+ * What remains after the wipe (files, stores, keychain keys)
+ */
+remaining: string[]; 
+/**
+ * Whether every step succeeded and nothing remains
+ */
+verified: boolean; 
+/**
+ * Time the wipe ended (seconds since the Unix epoch)
+ */
+completedAt: number }
+
+/**
+ * Step of the wipe
+ */
+export type WipeStep = 
+/**
+ * Upload, download, analytics and log shipping queues, ephemeral secrets
+ */
+"pending_queues" | 
+/**
+ * Encrypted database files
+ */
+"database" | 
+/**
+ * App file storage and document vault
+ */
+"file_storage" | 
+/**
+ * Asset cache, image cache and cache directory
+ */
+"caches" | 
+/**
+ * Preferences and restored app state
+ */
+"preferences" | 
+/**
+ * Keychain entries and device-binding key
+ */
+"keychain" | 
+/**
+ * Data and log directories
+ */
+"app_data"
+
+export const BINDINGS_VERSION = "0.1.0";
+
+export const commands = {
+  /**
+   * Store a value in the keychain
+   */
+  keychain_store: (key: string, value: string): Promise<CommandResponse<null>> =>
+    invoke<CommandResponse<null>>("keychain_store", { key, value }),
+  /**
+   * Retrieve a value from the keychain
+   */
+  keychain_retrieve: (key: string): Promise<CommandResponse<string>> =>
+    invoke<CommandResponse<string>>("keychain_retrieve", { key }),
+  /**
+   * Remove a value from the keychain
+   */
+  keychain_remove: (key: string): Promise<CommandResponse<null>> =>
+    invoke<CommandResponse<null>>("keychain_remove", { key }),
+  /**
+   * Check if a key exists in the keychain
+   */
+  keychain_exists: (key: string): Promise<CommandResponse<boolean>> =>
+    invoke<CommandResponse<boolean>>("keychain_exists", { key }),
+  /**
+   * Check connectivity to the application server
+   */
+  check_connectivity: (): Promise<CommandResponse<boolean>> =>
+    invoke<CommandResponse<boolean>>("check_connectivity"),
+  /**
+   * Perform a quick connectivity check without retries
+   */
+  check_connectivity_quick: (): Promise<CommandResponse<boolean>> =>
+    invoke<CommandResponse<boolean>>("check_connectivity_quick"),
+  /**
+   * Check the status of a runtime permission
+   */
+  permission_check: (permission: Permission): Promise<PermissionStatus> =>
+    invoke<PermissionStatus>("permission_check", { permission }),
+  /**
+   * Request a runtime permission from the user
+   */
+  permission_request: (permission: Permission): Promise<PermissionStatus> =>
+    invoke<PermissionStatus>("permission_request", { permission }),
+  /**
+   * Check the status of every runtime permission managed by the application
+   */
+  permission_status_all: (): Promise<PermissionEntry[]> =>
+    invoke<PermissionEntry[]>("permission_status_all"),
+  /**
+   * Start recording an audio note
+   */
+  start_recording: (): Promise<string> =>
+    invoke<string>("start_recording"),
+  /**
+   * Stop the audio recording in progress
+   */
+  stop_recording: (): Promise<RecordingInfo> =>
+    invoke<RecordingInfo>("stop_recording"),
+  /**
+   * Get the lock-screen security status of the device
+   */
+  device_security_status: (): Promise<DeviceSecurityStatus> =>
+    invoke<DeviceSecurityStatus>("device_security_status"),
+  /**
+   * Run the root / jailbreak detection heuristics
+   */
+  device_integrity: (): Promise<IntegrityReport> =>
+    invoke<IntegrityReport>("device_integrity"),
+  /**
+   * Get general information about the device and the application
+   */
+  device_info: (): Promise<DeviceInfo> =>
+    invoke<DeviceInfo>("device_info"),
+  /**
+   * Request a device attestation token for a backend-issued nonce
+   */
+  attest_device: (nonce: string): Promise<AttestationToken> =>
+    invoke<AttestationToken>("attest_device", { nonce }),
+  /**
+   * Get the accessibility preferences of the device
+   */
+  accessibility_status: (): Promise<AccessibilityStatus> =>
+    invoke<AccessibilityStatus>("accessibility_status"),
+  /**
+   * Get device storage, memory and app footprint information
+   */
+  storage_info: (): Promise<StorageInfo> =>
+    invoke<StorageInfo>("storage_info"),
+  /**
+   * Get details about the active network interface
+   */
+  network_interfaces: (): Promise<NetworkInterfaces> =>
+    invoke<NetworkInterfaces>("network_interfaces"),
+  /**
+   * Get the mobile carrier and SIM information
+   */
+  carrier_info: (): Promise<CarrierInfo> =>
+    invoke<CarrierInfo>("carrier_info"),
+  /**
+   * Get first-launch, install time, install source and previous version information
+   */
+  install_info: (): Promise<InstallInfo> =>
+    invoke<InstallInfo>("install_info"),
+  /**
+   * Update the app state snapshot persisted when the app goes to background
+   */
+  update_app_state: (snapshot: AppStateSnapshot): Promise<null> =>
+    invoke<null>("update_app_state", { snapshot }),
+  /**
+   * Get the app state snapshot restored from the previous session
+   */
+  get_restored_state: (): Promise<AppStateSnapshot | null> =>
+    invoke<AppStateSnapshot | null>("get_restored_state"),
+  /**
+   * Report user activity, postponing the inactivity auto-lock
+   */
+  session_activity: (): Promise<null> =>
+    invoke<null>("session_activity"),
+  /**
+   * Re-authenticate the user with biometrics or the device passcode and
+   * unlock the session
+   */
+  unlock_session: (): Promise<boolean> =>
+    invoke<boolean>("unlock_session"),
+  /**
+   * Check whether the session is locked
+   */
+  session_locked: (): Promise<boolean> =>
+    invoke<boolean>("session_locked"),
+  /**
+   * Check whether an app update is available or required
+   */
+  check_app_update: (): Promise<UpdateInfo> =>
+    invoke<UpdateInfo>("check_app_update"),
+  /**
+   * Start a flexible (background download) in-app update
+   */
+  start_flexible_update: (): Promise<null> =>
+    invoke<null>("start_flexible_update"),
+  /**
+   * Start an immediate (full-screen, blocking) in-app update
+   */
+  start_immediate_update: (): Promise<null> =>
+    invoke<null>("start_immediate_update"),
+  /**
+   * Install a downloaded flexible update and restart the app
+   */
+  complete_flexible_update: (): Promise<null> =>
+    invoke<null>("complete_flexible_update"),
+  /**
+   * Read a value from the remote config
+   */
+  get_config: (key: string): Promise<JsonValue | null> =>
+    invoke<JsonValue | null>("get_config", { key }),
+  /**
+   * Check whether a feature flag is enabled for this installation
+   */
+  feature_enabled: (name: string): Promise<boolean> =>
+    invoke<boolean>("feature_enabled", { name }),
+  /**
+   * Get the most recent crash report left by a previous session
+   */
+  get_last_crash: (): Promise<CrashSummary | null> =>
+    invoke<CrashSummary | null>("get_last_crash"),
+  /**
+   * Upload all pending crash reports
+   */
+  send_crash_reports: (): Promise<number> =>
+    invoke<number>("send_crash_reports"),
+  /**
+   * Delete all pending crash reports without sending them
+   */
+  discard_crash_reports: (): Promise<null> =>
+    invoke<null>("discard_crash_reports"),
+  /**
+   * Opt in or out of automatic crash report upload on next launch
+   */
+  set_crash_reporting_enabled: (enabled: boolean): Promise<null> =>
+    invoke<null>("set_crash_reporting_enabled", { enabled }),
+  /**
+   * Check whether the previous session ended with a crash
+   */
+  launched_after_crash: (): Promise<boolean> =>
+    invoke<boolean>("launched_after_crash"),
+  /**
+   * Track an analytics event
+   */
+  track_event: (name: string, props: Partial<{ [key in string]: PropValue }> | null): Promise<null> =>
+    invoke<null>("track_event", { name, props }),
+  /**
+   * Opt in or out of analytics
+   */
+  set_analytics_enabled: (enabled: boolean): Promise<null> =>
+    invoke<null>("set_analytics_enabled", { enabled }),
+  /**
+   * Check whether analytics are enabled (the user did not opt out)
+   */
+  analytics_enabled: (): Promise<boolean> =>
+    invoke<boolean>("analytics_enabled"),
+  /**
+   * Get the cold start durations of the current process
+   */
+  startup_metrics: (): Promise<StartupMetrics> =>
+    invoke<StartupMetrics>("startup_metrics"),
+  /**
+   * Acknowledge that the user saw the inbox
+   */
+  acknowledge_inbox: (): Promise<boolean> =>
+    invoke<boolean>("acknowledge_inbox"),
+  /**
+   * Get the badge reset policy
+   */
+  get_badge_reset_policy: (): Promise<BadgeResetPolicy> =>
+    invoke<BadgeResetPolicy>("get_badge_reset_policy"),
+  /**
+   * Configure the automatic badge reset
+   */
+  set_badge_reset_policy: (policy: BadgeResetPolicy): Promise<null> =>
+    invoke<null>("set_badge_reset_policy", { policy }),
+  /**
+   * Get the current system locale
+   */
+  current_locale: (): Promise<LocaleInfo> =>
+    invoke<LocaleInfo>("current_locale"),
+  /**
+   * Show a native notification
+   */
+  show_notification: (title: string, body: string, icon: string | null): Promise<CommandResponse<null>> =>
+    invoke<CommandResponse<null>>("show_notification", { title, body, icon }),
+  /**
+   * Request notification permissions
+   */
+  request_notification_permission: (): Promise<CommandResponse<boolean>> =>
+    invoke<CommandResponse<boolean>>("request_notification_permission"),
+  /**
+   * Check notification permission status
+   */
+  check_notification_permission: (): Promise<CommandResponse<boolean>> =>
+    invoke<CommandResponse<boolean>>("check_notification_permission"),
+  /**
+   * Check if notifications are supported
+   */
+  is_notification_supported: (): Promise<CommandResponse<boolean>> =>
+    invoke<CommandResponse<boolean>>("is_notification_supported"),
+  /**
+   * Retrieve the request signing secret of the current page
+   */
+  signing_handshake: (): Promise<string> =>
+    invoke<string>("signing_handshake"),
+  /**
+   * Execute a statement on the encrypted local database
+   */
+  db_execute: (sql: string, params: JsonValue[] | null): Promise<number> =>
+    invoke<number>("db_execute", { sql, params }),
+  /**
+   * Run a query on the encrypted local database
+   */
+  db_query: (sql: string, params: JsonValue[] | null): Promise<(Partial<{ [key in string]: JsonValue }>)[]> =>
+    invoke<(Partial<{ [key in string]: JsonValue }>)[]>("db_query", { sql, params }),
+  /**
+   * Claim the session token of the current page
+   */
+  claim_session_token: (): Promise<string | null> =>
+    invoke<string | null>("claim_session_token"),
+  /**
+   * Log in with an OAuth 2.0 / OpenID Connect provider
+   */
+  auth_login: (providerConfig: ProviderConfig): Promise<AuthSession> =>
+    invoke<AuthSession>("auth_login", { providerConfig }),
+  /**
+   * End a native login session
+   */
+  auth_logout: (handle: string): Promise<null> =>
+    invoke<null>("auth_logout", { handle }),
+  /**
+   * Copy text to the clipboard
+   */
+  clipboard_copy: (text: string, sensitive: boolean | null): Promise<null> =>
+    invoke<null>("clipboard_copy", { text, sensitive }),
+  /**
+   * Create a passkey with the platform authenticator
+   */
+  passkey_register: (options: RegistrationOptions): Promise<RegistrationResponse> =>
+    invoke<RegistrationResponse>("passkey_register", { options }),
+  /**
+   * Sign in with a passkey
+   */
+  passkey_authenticate: (options: AuthenticationOptions): Promise<AuthenticationResponse> =>
+    invoke<AuthenticationResponse>("passkey_authenticate", { options }),
+  /**
+   * Get the number of invocations rejected by the rate limiter
+   */
+  rate_limit_metrics: (): Promise<RateLimitMetrics> =>
+    invoke<RateLimitMetrics>("rate_limit_metrics"),
+  /**
+   * Export the security audit log
+   */
+  export_security_log: (): Promise<AuditExport> =>
+    invoke<AuditExport>("export_security_log"),
+  /**
+   * Sign a backend-issued nonce with the device-binding key
+   */
+  device_binding_assertion: (nonce: string): Promise<BindingAssertion> =>
+    invoke<BindingAssertion>("device_binding_assertion", { nonce }),
+  /**
+   * Get the active device policy
+   */
+  device_policy: (): Promise<ActivePolicy> =>
+    invoke<ActivePolicy>("device_policy"),
+  /**
+   * Keep a secret in memory for the current session
+   */
+  ephemeral_store: (key: string, value: string): Promise<null> =>
+    invoke<null>("ephemeral_store", { key, value }),
+  /**
+   * Get a secret kept with `ephemeral_store`
+   */
+  ephemeral_get: (key: string): Promise<string | null> =>
+    invoke<string | null>("ephemeral_get", { key }),
+  /**
+   * Get the disk space used by the log files
+   */
+  log_storage_usage: (): Promise<LogStorageUsage> =>
+    invoke<LogStorageUsage>("log_storage_usage"),
+  /**
+   * Change the log level at runtime
+   */
+  set_log_level: (level: string, moduleFilter: string | null): Promise<LogLevelSetting> =>
+    invoke<LogLevelSetting>("set_log_level", { level, moduleFilter }),
+  /**
+   * Get the active log level
+   */
+  get_log_level: (): Promise<LogLevelSetting> =>
+    invoke<LogLevelSetting>("get_log_level"),
+  /**
+   * Export the logs and open the share sheet
+   */
+  export_logs: (): Promise<ExportedLogs> =>
+    invoke<ExportedLogs>("export_logs"),
+  /**
+   * Enable or disable remote log shipping
+   */
+  set_log_shipping: (enabled: boolean, supportToken: string | null): Promise<LogShippingStatus> =>
+    invoke<LogShippingStatus>("set_log_shipping", { enabled, supportToken }),
+  /**
+   * Get the remote log shipping status
+   */
+  log_shipping_status: (): Promise<LogShippingStatus> =>
+    invoke<LogShippingStatus>("log_shipping_status"),
+  /**
+   * Generate the diagnostics report for the in-app support form
+   */
+  generate_diagnostics: (): Promise<DiagnosticsReport> =>
+    invoke<DiagnosticsReport>("generate_diagnostics"),
+  /**
+   * Record a breadcrumb
+   */
+  add_breadcrumb: (category: string, message: string): Promise<null> =>
+    invoke<null>("add_breadcrumb", { category, message }),
+  /**
+   * Read the most recent records of the current log file
+   */
+  read_recent_logs: (levelFilter: string | null, limit: number | null): Promise<LogLine[]> =>
+    invoke<LogLine[]>("read_recent_logs", { levelFilter, limit }),
+  /**
+   * Get the execution metrics of the commands invoked since launch
+   */
+  get_metrics: (): Promise<CommandMetrics> =>
+    invoke<CommandMetrics>("get_metrics"),
+  /**
+   * Clear the offline asset cache
+   */
+  clear_asset_cache: (): Promise<number> =>
+    invoke<number>("clear_asset_cache"),
+  /**
+   * Sync collections into the local database now
+   */
+  sync_now: (collections: string[] | null): Promise<SyncReport> =>
+    invoke<SyncReport>("sync_now", { collections }),
+  /**
+   * Write a file to the app file storage
+   */
+  file_write: (path: string, data: string): Promise<null> =>
+    invoke<null>("file_write", { path, data }),
+  /**
+   * Read a file from the app file storage
+   */
+  file_read: (path: string): Promise<string> =>
+    invoke<string>("file_read", { path }),
+  /**
+   * Delete a file, or a directory and its contents, from the app file storage
+   */
+  file_delete: (path: string): Promise<boolean> =>
+    invoke<boolean>("file_delete", { path }),
+  /**
+   * List a directory of the app file storage
+   */
+  file_list: (directory: string | null): Promise<FileEntry[]> =>
+    invoke<FileEntry[]>("file_list", { directory }),
+  /**
+   * Download a file into the app file storage
+   */
+  download_start: (url: string, path: string, sha256: string | null): Promise<Download> =>
+    invoke<Download>("download_start", { url, path, sha256 }),
+  /**
+   * Pause a download
+   */
+  download_pause: (id: string): Promise<null> =>
+    invoke<null>("download_pause", { id }),
+  /**
+   * Resume a paused or failed download, from where it stopped
+   */
+  download_resume: (id: string): Promise<null> =>
+    invoke<null>("download_resume", { id }),
+  /**
+   * Cancel a download and delete its partial file, or remove a completed
+   * download from the list (the downloaded file is kept)
+   */
+  download_cancel: (id: string): Promise<null> =>
+    invoke<null>("download_cancel", { id }),
+  /**
+   * List the downloads, oldest first
+   */
+  download_list: (): Promise<Download[]> =>
+    invoke<Download[]>("download_list"),
+  /**
+   * Enqueue a file of the app file storage for upload
+   */
+  upload_enqueue: (path: string, url: string, method: string | null, contentType: string | null, auth: UploadAuth | null, deferOnMetered: boolean | null): Promise<Upload> =>
+    invoke<Upload>("upload_enqueue", { path, url, method, contentType, auth, deferOnMetered }),
+  /**
+   * Retry a failed upload now
+   */
+  upload_retry: (id: string): Promise<Upload> =>
+    invoke<Upload>("upload_retry", { id }),
+  /**
+   * Remove an upload from the queue
+   */
+  upload_cancel: (id: string): Promise<null> =>
+    invoke<null>("upload_cancel", { id }),
+  /**
+   * List the uploads, oldest first
+   */
+  upload_list: (): Promise<Upload[]> =>
+    invoke<Upload[]>("upload_list"),
+  /**
+   * Cache an image (avatar, document thumbnail) on disk
+   */
+  cache_image: (url: string): Promise<ImageHandle> =>
+    invoke<ImageHandle>("cache_image", { url }),
+  /**
+   * Read a cached image as a `data:` URL, to use as `src` of an `<img>`
+   */
+  read_cached_image: (handle: string): Promise<string> =>
+    invoke<string>("read_cached_image", { handle }),
+  /**
+   * Get the image cache statistics
+   */
+  get_image_cache_stats: (): Promise<ImageCacheStats> =>
+    invoke<ImageCacheStats>("get_image_cache_stats"),
+  /**
+   * Clear the image cache
+   */
+  clear_image_cache: (): Promise<number> =>
+    invoke<number>("clear_image_cache"),
+  /**
+   * Get a preference
+   */
+  prefs_get: (key: string): Promise<PrefValue | null> =>
+    invoke<PrefValue | null>("prefs_get", { key }),
+  /**
+   * Set a preference
+   */
+  prefs_set: (key: string, value: PrefValue): Promise<null> =>
+    invoke<null>("prefs_set", { key, value }),
+  /**
+   * Remove a preference
+   */
+  prefs_remove: (key: string): Promise<boolean> =>
+    invoke<boolean>("prefs_remove", { key }),
+  /**
+   * Export the personal data stored by the app and present the share sheet
+   */
+  export_user_data: (): Promise<ExportedData> =>
+    invoke<ExportedData>("export_user_data"),
+  /**
+   * Wipe all local data (device decommissioning)
+   */
+  wipe_all_local_data: (keychainKeys: string[] | null): Promise<WipeReport> =>
+    invoke<WipeReport>("wipe_all_local_data", { keychainKeys }),
+  /**
+   * Open a file of the app file storage in the native viewer
+   */
+  open_attachment: (path: string): Promise<AttachmentType> =>
+    invoke<AttachmentType>("open_attachment", { path }),
+  /**
+   * Move a file of the app file storage into the encrypted document vault
+   */
+  vault_import: (path: string, expiresAt: number | null): Promise<VaultItem> =>
+    invoke<VaultItem>("vault_import", { path, expiresAt }),
+  /**
+   * List the documents of the vault
+   */
+  vault_list: (): Promise<VaultItem[]> =>
+    invoke<VaultItem[]>("vault_list"),
+  /**
+   * Decrypt a document of the vault to memory
+   */
+  vault_read: (id: string): Promise<string> =>
+    invoke<string>("vault_read", { id }),
+  /**
+   * Open a document of the vault in the native viewer
+   */
+  vault_open: (id: string): Promise<AttachmentType> =>
+    invoke<AttachmentType>("vault_open", { id }),
+  /**
+   * Remove a document from the vault
+   */
+  vault_remove: (id: string): Promise<boolean> =>
+    invoke<boolean>("vault_remove", { id }),
+  /**
+   * Set the documents to prefetch for the next session
+   */
+  prefetch_set: (items: PrefetchItem[]): Promise<PrefetchStatus> =>
+    invoke<PrefetchStatus>("prefetch_set", { items }),
+  /**
+   * Get the prefetch status
+   */
+  prefetch_status: (): Promise<PrefetchStatus> =>
+    invoke<PrefetchStatus>("prefetch_status"),
+  /**
+   * Save the draft of a form
+   */
+  draft_save: (formId: string, payload: JsonValue): Promise<Draft> =>
+    invoke<Draft>("draft_save", { formId, payload }),
+  /**
+   * Load the draft of a form
+   */
+  draft_load: (formId: string): Promise<Draft | null> =>
+    invoke<Draft | null>("draft_load", { formId }),
+  /**
+   * Delete the draft of a form, once it was submitted or discarded
+   */
+  draft_delete: (formId: string): Promise<boolean> =>
+    invoke<boolean>("draft_delete", { formId }),
+  /**
+   * Start recording the invokes to a file (debug builds only)
+   */
+  invoke_recording_start: (): Promise<null> =>
+    invoke<null>("invoke_recording_start"),
+  /**
+   * Stop recording the invokes (debug builds only)
+   */
+  invoke_recording_stop: (): Promise<RecordingSummary | null> =>
+    invoke<RecordingSummary | null>("invoke_recording_stop"),
+  /**
+   * Open the web inspector of the main window (debug builds only)
+   */
+  open_devtools: (): Promise<null> =>
+    invoke<null>("open_devtools"),
+  /**
+   * Reload the page of the main window (debug builds only)
+   */
+  reload_webview: (): Promise<null> =>
+    invoke<null>("reload_webview"),
+  /**
+   * Navigate the main window to a URL (debug builds only)
+   */
+  navigate: (url: string): Promise<null> =>
+    invoke<null>("navigate", { url }),
+  /**
+   * Get the backend environment and its effective endpoints
+   */
+  environment_get: (): Promise<EnvironmentStatus> =>
+    invoke<EnvironmentStatus>("environment_get"),
+  /**
+   * Select the backend environment of the next launch (debug builds only)
+   */
+  environment_set: (environment: Environment): Promise<EnvironmentStatus> =>
+    invoke<EnvironmentStatus>("environment_set", { environment }),
+  /**
+   * Simulate offline from the debug menu (debug builds only)
+   */
+  debug_set_offline: (offline: boolean): Promise<boolean> =>
+    invoke<boolean>("debug_set_offline", { offline }),
+  /**
+   * Fire a fake push from the debug menu (debug builds only)
+   */
+  debug_fake_push: (payload: JsonValue | null): Promise<SyncReport | null> =>
+    invoke<SyncReport | null>("debug_fake_push", { payload }),
+  /**
+   * Show a test notification on every delivery channel (debug builds only)
+   */
+  debug_test_notifications: (): Promise<ChannelDelivery[]> =>
+    invoke<ChannelDelivery[]>("debug_test_notifications"),
+  /**
+   * Corrupt a keychain entry from the debug menu (debug builds only)
+   */
+  debug_corrupt_keychain: (key: string): Promise<null> =>
+    invoke<null>("debug_corrupt_keychain", { key }),
+  /**
+   * Crash the app from the debug menu (debug builds only)
+   */
+  debug_crash: (): Promise<null> =>
+    invoke<null>("debug_crash"),
+  /**
+   * Feed a synthetic push through the push pipeline (debug builds only)
+   */
+  simulate_push: (payload: JsonValue, tapped: boolean | null): Promise<PushRouting> =>
+    invoke<PushRouting>("simulate_push", { payload, tapped }),
+  /**
+   * Time the device keychain (debug builds only)
+   */
+  benchmark_keychain: (iterations: number | null): Promise<KeychainBenchmark> =>
+    invoke<KeychainBenchmark>("benchmark_keychain", { iterations }),
+  /**
+   * Do nothing, to time the invoke round trip (debug builds only)
+   */
+  benchmark_ping: (): Promise<null> =>
+    invoke<null>("benchmark_ping"),
+  /**
+   * Open the realtime connection to the backend
+   */
+  ws_connect: (url: string): Promise<WebSocketStatus> =>
+    invoke<WebSocketStatus>("ws_connect", { url }),
+  /**
+   * Send a text message on the realtime connection
+   */
+  ws_send: (message: string): Promise<null> =>
+    invoke<null>("ws_send", { message }),
+  /**
+   * Close the realtime connection, until the next `ws_connect`
+   */
+  ws_close: (): Promise<null> =>
+    invoke<null>("ws_close"),
+  /**
+   * Get the state of the realtime connection
+   */
+  ws_status: (): Promise<WebSocketStatus> =>
+    invoke<WebSocketStatus>("ws_status"),
+  /**
+   * Run a query on the encrypted local database, with a MessagePack result
+   */
+  db_query_packed: (sql: string, params: JsonValue[] | null): Promise<(Partial<{ [key in string]: JsonValue }>)[]> =>
+    invoke<ArrayBuffer>("db_query_packed", { sql, params }).then((body) => decode(new Uint8Array(body)) as (Partial<{ [key in string]: JsonValue }>)[]),
+  /**
+   * Negotiate the native API version of the page
+   */
+  negotiate_api: (version: number): Promise<ApiNegotiation> =>
+    invoke<ApiNegotiation>("negotiate_api", { version }),
+  /**
+   * Signal the page is ready to receive events
+   */
+  outbox_ready: (): Promise<OutboxMessage[]> =>
+    invoke<OutboxMessage[]>("outbox_ready"),
+  /**
+   * Acknowledge queued events handled by the page
+   */
+  outbox_ack: (ids: number[]): Promise<number> =>
+    invoke<number>("outbox_ack", { ids }),
+  /**
+   * Subscribe to a Server-Sent Events stream
+   */
+  sse_subscribe: (url: string): Promise<SseStatus> =>
+    invoke<SseStatus>("sse_subscribe", { url }),
+  /**
+   * Close a Server-Sent Events subscription
+   */
+  sse_unsubscribe: (id: number): Promise<boolean> =>
+    invoke<boolean>("sse_unsubscribe", { id }),
+  /**
+   * Get the open Server-Sent Events subscriptions
+   */
+  sse_subscriptions: (): Promise<SseStatus[]> =>
+    invoke<SseStatus[]>("sse_subscriptions"),
+  /**
+   * Get the notification that launched the app
+   */
+  get_launch_payload: (): Promise<LaunchPayload | null> =>
+    invoke<LaunchPayload | null>("get_launch_payload"),
+  /**
+   * Donate a Siri Shortcut / App Action for the displayed screen
+   */
+  donate_shortcut: (shortcut: Shortcut): Promise<boolean> =>
+    invoke<boolean>("donate_shortcut", { shortcut }),
+};
+
+export type EventPayloads = {
+  "connectivity://changed": ConnectivityChanged;
+  "lifecycle://foreground": AppForeground;
+  "lifecycle://background": AppBackground;
+  "lifecycle://resume": AppResumed;
+  "notification://received": NotificationReceived;
+  "deeplink://open": DeepLink;
+  "sync://completed": SyncReport;
+  "download://progress": DownloadProgress;
+  "download://state": Download;
+  "upload://state": Upload;
+  "ws://state": WebSocketStatus;
+  "ws://message": WebSocketMessage;
+  "sse://state": SseStatus;
+  "sse://message": SseMessage;
+  "share://received": SharedFile;
+  "watch://mark-read": WatchMarkRead;
+  "keyboard://changed": KeyboardChanged;
+  "gesture://shake": ShakeDetected;
+  "accessibility-changed": AccessibilityStatus;
+  "audio-level": AudioLevel;
+  "locale://changed": LocaleInfo;
+  "session://locked": SessionLocked;
+  "session://unlocked": SessionUnlocked;
+  "clipboard://cleared": ClipboardCleared;
+  "policy://changed": ActivePolicy;
+  "integrity://tampered": IntegrityTampered;
+  "tls://pin-failure": PinFailure;
+  "wipe://completed": WipeReport;
+  "feature://changed": FeatureFlagsChanged;
+  "update://required": UpdateInfo;
+  "update://progress": UpdateProgress;
+  "update://result": UpdateResult;
+};
+
+export const listen = <E extends keyof EventPayloads>(
+  event: E,
+  handler: (payload: EventPayloads[E]) => void,
+) => listenEvent<EventPayloads[E]>(event, ({ payload }) => handler(payload));
@@ -0,0 +1,99 @@
+//! Keychain and bridge overhead benchmarks
+//!
+//! Run on the mock app (`create_test_app()`, mock keystore), so they measure
+//! the cost of the bridge and of the command code, not of the device
+//! keychain; use the on-device `benchmark_keychain` command for that.
+//!
+//! ```sh
+//! cargo bench --features test-harness --bench bridge
+//! ```
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use serde_json::json;
+
+use elulib_mobile::{benchmark, payload_encoding};
+
+const BATCH_SIZES: [u32; 3] = [1, 10, 50];
+
+/// Number of rows of the encoded document lists
+const ROW_COUNTS: [usize; 2] = [100, 1000];
+
+fn keychain(c: &mut Criterion) {
+    let app = elulib_mobile::create_test_app();
+    let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+        .build()
+        .expect("Failed to create benchmark webview");
+    let invoke = |command: &str, args| {
+        elulib_mobile::invoke_test_command(&webview, command, args).expect("Benchmark invoke rejected")
+    };
+
+    let mut group = c.benchmark_group("keychain");
+    group.bench_function("store", |b| {
+        b.iter(|| invoke("keychain_store", json!({ "key": "benchmark_store", "value": "benchmark-value" })))
+    });
+    invoke("keychain_store", json!({ "key": "benchmark_retrieve", "value": "benchmark-value" }));
+    group.bench_function("retrieve", |b| {
+        b.iter(|| invoke("keychain_retrieve", json!({ "key": "benchmark_retrieve" })))
+    });
+    group.bench_function("store_native", |b| {
+        b.iter(|| benchmark::store_one(app.handle(), "benchmark_native", "benchmark-value").unwrap())
+    });
+    group.finish();
+
+    // Invoke round trip of a command doing almost nothing
+    c.bench_function("invoke_round_trip", |b| {
+        b.iter(|| invoke("keychain_exists", json!({ "key": "benchmark_missing" })))
+    });
+
+    let mut group = c.benchmark_group("keychain_batch_vs_sequential");
+    for size in BATCH_SIZES {
+        let entries: Vec<(String, String)> = (0..size)
+            .map(|index| (benchmark::key(index), "benchmark-value".to_string()))
+            .collect();
+        // The benchmark namespace is reserved to the native layer
+        let command_keys: Vec<String> = (0..size).map(|index| format!("benchmark_{}", index)).collect();
+        group.bench_with_input(BenchmarkId::new("sequential_invokes", size), &command_keys, |b, keys| {
+            b.iter(|| {
+                for key in keys {
+                    invoke("keychain_store", json!({ "key": key, "value": "benchmark-value" }));
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("batch_native", size), &entries, |b, entries| {
+            b.iter_batched(
+                || entries.clone(),
+                |entries| benchmark::store_batch(app.handle(), &entries).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+// Encoding of a document list, as returned by `db_query` and `db_query_packed`
+fn payload_encoding(c: &mut Criterion) {
+    let mut group = c.benchmark_group("payload_encoding");
+    for count in ROW_COUNTS {
+        let rows: Vec<serde_json::Value> = (0..count)
+            .map(|index| {
+                json!({
+                    "id": format!("doc-{}", index),
+                    "title": "Procès-verbal de la séance du conseil municipal",
+                    "updated_at": 1_700_000_000 + index,
+                    "size": 482_133,
+                    "tags": ["conseil", "délibération"],
+                })
+            })
+            .collect();
+        group.bench_with_input(BenchmarkId::new("json", count), &rows, |b, rows| {
+            b.iter(|| serde_json::to_vec(rows).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("msgpack", count), &rows, |b, rows| {
+            b.iter(|| payload_encoding::encode(rows).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, keychain, payload_encoding);
+criterion_main!(benches);
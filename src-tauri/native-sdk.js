@@ -0,0 +1,129 @@
+// window.EluNative: promise-based access to the native commands and events.
+// Injected into every page by src/native_sdk.rs, which calls this function
+// with its configuration. Keep it dependency-free ES2017.
+(function (config) {
+  var internals = window.__TAURI_INTERNALS__;
+  if (!internals || window[config.global]) return;
+
+  var prefix = Math.random().toString(36).slice(2, 8);
+  var counter = 0;
+
+  function NativeError(command, requestId, code, message, retryable, retryAfterMs) {
+    var error = new Error(command + ' failed: ' + message);
+    error.name = 'NativeError';
+    error.command = command;
+    error.requestId = requestId;
+    error.code = code;
+    error.retryable = retryable;
+    error.retryAfterMs = retryAfterMs;
+    return error;
+  }
+
+  // Rejections are `AppError` objects (`{ code, message }`, numeric code),
+  // strings, or JSON strings for rate limited invocations
+  function rejection(command, requestId, reason) {
+    if (reason && reason.name === 'NativeError') return reason;
+    if (reason && typeof reason === 'object' && typeof reason.code === 'number') {
+      return NativeError(command, requestId, reason.code, String(reason.message), false);
+    }
+    var message = typeof reason === 'string' ? reason : String(reason && reason.message || reason);
+    try {
+      var parsed = JSON.parse(message);
+      if (parsed && parsed.error === 'rate_limited') {
+        return NativeError(command, requestId, 'RATE_LIMITED', 'rate limited', true, parsed.retryAfterMs);
+      }
+    } catch (_) {}
+    return NativeError(command, requestId, 'REJECTED', message, false);
+  }
+
+  // Commands resolving with a `CommandResponse` envelope settle with its data
+  function isEnvelope(value) {
+    return value !== null && typeof value === 'object' && typeof value.ok === 'boolean' && 'error' in value && 'data' in value;
+  }
+
+  function withTimeout(promise, command, requestId, timeoutMs) {
+    if (!(timeoutMs > 0)) return promise;
+    return new Promise(function (resolve, reject) {
+      var timer = setTimeout(function () {
+        reject(NativeError(command, requestId, 'TIMEOUT', 'no response after ' + timeoutMs + 'ms', false));
+      }, timeoutMs);
+      promise.then(
+        function (value) { clearTimeout(timer); resolve(value); },
+        function (reason) { clearTimeout(timer); reject(reason); }
+      );
+    });
+  }
+
+  function attempt(command, args, requestId, timeoutMs) {
+    var headers = {};
+    headers[config.requestIdHeader] = requestId;
+    var call = Promise.resolve().then(function () {
+      return internals.invoke(command, args || {}, { headers: headers });
+    });
+    return withTimeout(call, command, requestId, timeoutMs).then(
+      function (value) {
+        if (!isEnvelope(value)) return value;
+        if (value.ok) return value.data;
+        var error = value.error || {};
+        throw NativeError(command, requestId, error.code || 'INTERNAL', error.message || 'unknown error', !!error.retryable);
+      },
+      function (reason) { throw rejection(command, requestId, reason); }
+    );
+  }
+
+  function delay(ms) {
+    return new Promise(function (resolve) { setTimeout(resolve, ms); });
+  }
+
+  // Invoke a command; options: { timeoutMs, retries, requestId }
+  function invoke(command, args, options) {
+    options = options || {};
+    var requestId = options.requestId || prefix + '-' + (++counter);
+    var timeoutMs = options.timeoutMs === undefined ? config.timeoutMs : options.timeoutMs;
+    var retries = options.retries === undefined ? config.maxRetries : options.retries;
+    var run = function (retry) {
+      return attempt(command, args, requestId, timeoutMs).catch(function (error) {
+        if (!error.retryable || retry >= retries) throw error;
+        var wait = Math.max(error.retryAfterMs || 0, config.retryBaseDelayMs * Math.pow(2, retry));
+        return delay(wait).then(function () { return run(retry + 1); });
+      });
+    };
+    return run(0);
+  }
+
+  // Subscribe to a native event; resolves with the unsubscribe function
+  function on(event, handler) {
+    var callback = internals.transformCallback(function (message) { handler(message.payload); });
+    return internals
+      .invoke('plugin:event|listen', { event: event, target: { kind: 'Any' }, handler: callback })
+      .then(function (eventId) {
+        return function () {
+          internals.unregisterCallback(callback);
+          return internals.invoke('plugin:event|unlisten', { event: event, eventId: eventId });
+        };
+      });
+  }
+
+  // Receive the next occurrence of a native event
+  function once(event) {
+    return new Promise(function (resolve, reject) {
+      var unsubscribe;
+      var received;
+      on(event, function (payload) {
+        if (received) return;
+        received = true;
+        if (unsubscribe) unsubscribe();
+        resolve(payload);
+      }).then(function (stop) {
+        unsubscribe = stop;
+        if (received) stop();
+      }, reject);
+    });
+  }
+
+  Object.defineProperty(window, config.global, {
+    value: Object.freeze({ version: config.version, invoke: invoke, on: on, once: once }),
+    writable: false,
+    configurable: false,
+  });
+})
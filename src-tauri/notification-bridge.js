@@ -133,8 +133,11 @@
     // Implement requestPermission
     TauriNotification.requestPermission = function(callback) {
         const promise = invoke('request_notification_permission')
-            .then(granted => {
-                const permission = granted ? 'granted' : 'denied';
+            .then(response => {
+                if (!response.ok) {
+                    throw response.error;
+                }
+                const permission = response.data ? 'granted' : 'denied';
                 window.__TAURI_NOTIFICATION_PERMISSION__ = permission;
                 return permission;
             })
@@ -156,11 +159,14 @@
     // Function to show native notification via Tauri
     async function showNativeNotification(notification) {
         try {
-            await invoke('show_notification', {
+            const response = await invoke('show_notification', {
                 title: notification.title,
                 body: notification.body,
                 icon: notification.icon
             });
+            if (!response.ok) {
+                throw response.error;
+            }
             
             console.log('[Tauri Notification Bridge] Native notification shown:', notification.title);
         } catch (error) {
@@ -174,8 +180,8 @@
     
     // Initialize permission status
     invoke('is_notification_supported')
-        .then(supported => {
-            if (supported) {
+        .then(response => {
+            if (response.ok && response.data) {
                 // Request permission on initialization
                 TauriNotification.requestPermission().then(permission => {
                     console.log('[Tauri Notification Bridge] Notification permission:', permission);
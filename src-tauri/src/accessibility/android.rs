@@ -0,0 +1,33 @@
+/// Android-specific accessibility implementation
+///
+/// This module reads accessibility preferences using `AccessibilityManager`
+/// and the system settings from the Android SDK.
+///
+/// Note: This implementation provides the structure for Android accessibility.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::AccessibilityStatus;
+use crate::AppResult;
+
+/// Get the accessibility status on Android
+///
+/// # Returns
+///
+/// Returns the `AccessibilityStatus` of the device.
+pub fn status() -> AppResult<AccessibilityStatus> {
+    // TODO: Implement native Android lookup
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val am = context.getSystemService(Context.ACCESSIBILITY_SERVICE) as AccessibilityManager
+    // val screenReaderActive = am.isEnabled && am.isTouchExplorationEnabled
+    // val boldText = Build.VERSION.SDK_INT >= 31 &&
+    //     context.resources.configuration.fontWeightAdjustment > 0
+    // val reduceMotion = Settings.Global.getFloat(
+    //     context.contentResolver, Settings.Global.ANIMATOR_DURATION_SCALE, 1f) == 0f
+    // ```
+
+    // Placeholder: Return default preferences
+    // Replace this with actual native implementation
+    Ok(AccessibilityStatus::default())
+}
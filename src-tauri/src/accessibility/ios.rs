@@ -0,0 +1,30 @@
+/// iOS-specific accessibility implementation
+///
+/// This module reads accessibility preferences using `UIAccessibility`
+/// from the UIKit framework.
+///
+/// Note: This implementation provides the structure for iOS accessibility.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::AccessibilityStatus;
+use crate::AppResult;
+
+/// Get the accessibility status on iOS
+///
+/// # Returns
+///
+/// Returns the `AccessibilityStatus` of the device.
+pub fn status() -> AppResult<AccessibilityStatus> {
+    // TODO: Implement native iOS lookup using UIAccessibility
+    // Example Swift implementation:
+    // ```swift
+    // let screenReaderActive = UIAccessibility.isVoiceOverRunning
+    // let boldText = UIAccessibility.isBoldTextEnabled
+    // let reduceMotion = UIAccessibility.isReduceMotionEnabled
+    // ```
+
+    // Placeholder: Return default preferences
+    // Replace this with actual native implementation
+    Ok(AccessibilityStatus::default())
+}
@@ -0,0 +1,122 @@
+/// Accessibility status reporting
+///
+/// This module reports the accessibility preferences of the device (screen
+/// reader, bold text, reduced motion) so the frontend can adapt animations
+/// and labels.
+///
+/// Changes are detected by a background watcher that compares the current
+/// status with the last reported one and emits an `accessibility-changed`
+/// event to the webview when they differ.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+use crate::constants;
+use crate::events;
+use crate::AppResult;
+
+/// Accessibility preferences of the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessibilityStatus {
+    /// A screen reader (VoiceOver / TalkBack) is running
+    pub screen_reader_active: bool,
+    /// The user asked for bold text (iOS) or a bold font weight (Android 12+)
+    pub bold_text: bool,
+    /// The user asked to reduce motion (iOS) or to remove animations (Android)
+    pub reduce_motion: bool,
+}
+
+/// Get the current accessibility status of the device
+///
+/// # Returns
+///
+/// Returns the `AccessibilityStatus`, or an error if it cannot be
+/// determined.
+pub fn status() -> AppResult<AccessibilityStatus> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::status()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::status()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(AccessibilityStatus::default())
+    }
+}
+
+/// Tracks the last reported accessibility status to detect changes
+#[derive(Default)]
+pub struct AccessibilityWatcher {
+    last: Mutex<Option<AccessibilityStatus>>,
+}
+
+impl AccessibilityWatcher {
+    /// Record `current` and return it if it differs from the last recorded status
+    ///
+    /// The first call only records the status and never reports a change.
+    pub fn update(&self, current: AccessibilityStatus) -> Option<AccessibilityStatus> {
+        let mut last = self.last.lock().ok()?;
+        let previous = last.replace(current);
+        match previous {
+            Some(previous) if previous != current => Some(current),
+            _ => None,
+        }
+    }
+}
+
+/// Spawn a background task emitting `accessibility-changed` events
+///
+/// The status is polled every `ACCESSIBILITY_POLL_INTERVAL_SECS` seconds.
+pub fn spawn_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let watcher = AccessibilityWatcher::default();
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            constants::ACCESSIBILITY_POLL_INTERVAL_SECS,
+        ));
+        loop {
+            interval.tick().await;
+            let current = match status() {
+                Ok(current) => current,
+                Err(e) => {
+                    tracing::warn!("Failed to read accessibility status: {}", e);
+                    continue;
+                }
+            };
+            if let Some(changed) = watcher.update(current) {
+                tracing::info!("Accessibility status changed: {:?}", changed);
+                events::emit(&app, &changed);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watcher_reports_changes_only() {
+        let watcher = AccessibilityWatcher::default();
+        let initial = AccessibilityStatus::default();
+        let changed = AccessibilityStatus { reduce_motion: true, ..initial };
+
+        assert_eq!(watcher.update(initial), None, "First status should not be reported as a change");
+        assert_eq!(watcher.update(initial), None, "Unchanged status should not be reported");
+        assert_eq!(watcher.update(changed), Some(changed));
+        assert_eq!(watcher.update(changed), None);
+    }
+}
@@ -0,0 +1,429 @@
+/// Privacy-respecting analytics module
+///
+/// This module records product analytics events from both the frontend
+/// (`track_event` command) and native flows, so they no longer depend on
+/// webview-only analytics.
+///
+/// Privacy rules:
+/// - only events declared in `EVENT_SCHEMA` are accepted, with their declared
+///   properties only
+/// - property values are booleans, numbers or short identifiers
+///   (`[A-Za-z0-9_.:-]`, at most `ANALYTICS_MAX_VALUE_LENGTH` characters), so
+///   free-form text (names, emails, messages) cannot be sent
+/// - no user or device identifier is attached to events
+/// - the user can opt out; the opt-out is persisted and drops queued events
+///
+/// Events are buffered in memory, uploaded in batches of `ANALYTICS_BATCH_SIZE`
+/// (or every `ANALYTICS_FLUSH_INTERVAL_SECS`), and persisted to disk when the
+/// app goes to background or an upload fails, so they survive offline periods.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::http;
+use crate::{AppError, AppResult};
+
+/// Accepted events and their allowed properties
+pub const EVENT_SCHEMA: &[(&str, &[&str])] = &[
+    ("app_open", &["first_launch", "post_crash"]),
+    ("app_startup", &["builder_ready_ms", "webview_created_ms", "first_page_load_ms"]),
+    ("screen_view", &["screen"]),
+    ("feature_used", &["feature", "source"]),
+    ("notification_opened", &["category"]),
+    ("permission_result", &["permission", "status"]),
+    ("update_prompt", &["action", "required"]),
+    ("command_metrics", &["command", "invocations", "failures", "p50_ms", "p95_ms", "max_ms"]),
+];
+
+/// Value of an event property
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(untagged)]
+pub enum PropValue {
+    /// Boolean value
+    Bool(bool),
+    /// Numeric value
+    Number(f64),
+    /// Short identifier (screen name, feature name, ...)
+    Token(String),
+}
+
+/// A validated analytics event
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsEvent {
+    /// Event name, declared in `EVENT_SCHEMA`
+    pub name: String,
+    /// Event properties
+    pub props: BTreeMap<String, PropValue>,
+    /// Time of the event (seconds since the Unix epoch)
+    pub timestamp: u64,
+}
+
+/// Batch of events, as uploaded to `ANALYTICS_ENDPOINT`
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EventBatch<'a> {
+    app_version: &'a str,
+    platform: &'a str,
+    events: &'a [AnalyticsEvent],
+}
+
+/// Analytics settings persisted between launches
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AnalyticsSettings {
+    opted_out: bool,
+}
+
+/// Check an event against the schema
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the event is declared and all its properties are
+/// allowed and well-formed, or an error describing the violation.
+pub fn validate(name: &str, props: &BTreeMap<String, PropValue>) -> AppResult<()> {
+    let (_, allowed) = EVENT_SCHEMA
+        .iter()
+        .find(|(event, _)| *event == name)
+        .ok_or_else(|| AppError::InvalidArgument(format!("Unknown analytics event: {}", name)))?;
+
+    for (key, value) in props {
+        if !allowed.contains(&key.as_str()) {
+            return Err(AppError::Storage(format!("Property {} is not allowed for event {}", key, name)));
+        }
+        match value {
+            PropValue::Bool(_) => {}
+            PropValue::Number(number) if number.is_finite() => {}
+            PropValue::Number(_) => return Err(AppError::Storage(format!("Property {} is not a finite number", key))),
+            PropValue::Token(token) => {
+                let valid = !token.is_empty()
+                    && token.len() <= constants::ANALYTICS_MAX_VALUE_LENGTH
+                    && token
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | ':' | '-'));
+                if !valid {
+                    return Err(AppError::InvalidArgument(format!("Property {} must be a short identifier", key)));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Analytics state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct Analytics {
+    queue: Mutex<VecDeque<AnalyticsEvent>>,
+    opted_out: AtomicBool,
+    dir: Mutex<Option<PathBuf>>,
+}
+
+impl Analytics {
+    /// Returns `true` if the user did not opt out
+    pub fn is_enabled(&self) -> bool {
+        !self.opted_out.load(Ordering::SeqCst)
+    }
+
+    /// Validate and queue an event
+    ///
+    /// Events are silently dropped when the user opted out. The oldest events
+    /// are dropped when the queue holds `ANALYTICS_MAX_QUEUED_EVENTS`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of queued events, or an error if the event
+    /// does not match the schema.
+    pub fn track(&self, name: &str, props: BTreeMap<String, PropValue>) -> AppResult<usize> {
+        validate(name, &props)?;
+        if !self.is_enabled() {
+            return Ok(0);
+        }
+
+        let mut queue = self.queue.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        if queue.len() == constants::ANALYTICS_MAX_QUEUED_EVENTS {
+            queue.pop_front();
+        }
+        queue.push_back(AnalyticsEvent {
+            name: name.to_string(),
+            props,
+            timestamp: now_secs(),
+        });
+        Ok(queue.len())
+    }
+
+    /// Remove up to `ANALYTICS_BATCH_SIZE` events from the queue
+    fn take_batch(&self) -> Vec<AnalyticsEvent> {
+        match self.queue.lock() {
+            Ok(mut queue) => {
+                let count = queue.len().min(constants::ANALYTICS_BATCH_SIZE);
+                queue.drain(..count).collect()
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Put back events whose upload failed, ahead of newer events
+    fn requeue(&self, events: Vec<AnalyticsEvent>) {
+        if let Ok(mut queue) = self.queue.lock() {
+            for event in events.into_iter().rev() {
+                queue.push_front(event);
+            }
+            queue.truncate(constants::ANALYTICS_MAX_QUEUED_EVENTS);
+        }
+    }
+
+    /// Drop the queued events
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of dropped events.
+    pub fn discard_queued(&self) -> usize {
+        self.queue
+            .lock()
+            .map(|mut queue| queue.drain(..).count())
+            .unwrap_or(0)
+    }
+
+    /// Events queued for upload, oldest first
+    pub fn queued(&self) -> Vec<AnalyticsEvent> {
+        self.queue
+            .lock()
+            .map(|queue| queue.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents)
+        .map_err(|e| tracing::warn!("Ignoring invalid analytics file {}: {}", path.display(), e))
+        .ok()
+}
+
+fn write_json<T: serde::Serialize>(path: &Path, value: &T) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| AppError::Storage(format!("Failed to create data directory: {}", e)))?;
+    }
+    let contents =
+        serde_json::to_string(value).map_err(|e| AppError::Storage(format!("Failed to serialize analytics: {}", e)))?;
+    std::fs::write(path, contents).map_err(|e| AppError::Storage(format!("Failed to write analytics file: {}", e)))
+}
+
+/// Initialize analytics: restore the opt-out and queued events, and start
+/// the periodic upload
+pub fn init(app: &AppHandle) -> AppResult<()> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve data directory: {}", e)))?;
+    let analytics = app.state::<Analytics>();
+
+    let settings: AnalyticsSettings =
+        read_json(&dir.join(constants::ANALYTICS_SETTINGS_FILE)).unwrap_or_default();
+    analytics.opted_out.store(settings.opted_out, Ordering::SeqCst);
+
+    let queue_path = dir.join(constants::ANALYTICS_QUEUE_FILE);
+    let queued: Vec<AnalyticsEvent> = read_json(&queue_path).unwrap_or_default();
+    if !queued.is_empty() && analytics.is_enabled() {
+        tracing::debug!("Restored {} queued analytics event(s)", queued.len());
+        analytics.requeue(queued);
+    }
+    let _ = std::fs::remove_file(&queue_path);
+
+    if let Ok(mut stored) = analytics.dir.lock() {
+        *stored = Some(dir);
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(constants::ANALYTICS_FLUSH_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            flush(&app).await;
+        }
+    });
+    Ok(())
+}
+
+/// Track an event, uploading a batch in background once enough are queued
+pub fn track(app: &AppHandle, name: &str, props: BTreeMap<String, PropValue>) -> AppResult<()> {
+    let queued = app.state::<Analytics>().track(name, props)?;
+    if queued >= constants::ANALYTICS_BATCH_SIZE {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move { flush(&app).await });
+    }
+    Ok(())
+}
+
+/// Track the `app_open` event at startup
+pub fn track_app_open(app: &AppHandle, first_launch: bool, post_crash: bool) -> AppResult<()> {
+    let props = BTreeMap::from([
+        ("first_launch".to_string(), PropValue::Bool(first_launch)),
+        ("post_crash".to_string(), PropValue::Bool(post_crash)),
+    ]);
+    track(app, "app_open", props)
+}
+
+/// Upload all queued events, batch by batch
+///
+/// On failure, the remaining events are kept and persisted for a later attempt.
+pub async fn flush(app: &AppHandle) {
+    let analytics = app.state::<Analytics>();
+    let client = match http::client() {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::warn!("{}", e);
+            return;
+        }
+    };
+
+    loop {
+        let events = analytics.take_batch();
+        if events.is_empty() {
+            return;
+        }
+
+        let batch = EventBatch {
+            app_version: env!("CARGO_PKG_VERSION"),
+            platform: std::env::consts::OS,
+            events: &events,
+        };
+        let result = client
+            .post(http::endpoint(constants::ANALYTICS_ENDPOINT))
+            .json(&batch)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        if let Err(e) = result {
+            tracing::debug!("Analytics upload failed, keeping events for later: {}", e);
+            analytics.requeue(events);
+            if let Err(e) = persist(app) {
+                tracing::warn!("{}", e);
+            }
+            return;
+        }
+    }
+}
+
+/// Persist queued events, so they survive the process being killed
+pub fn persist(app: &AppHandle) -> AppResult<()> {
+    let analytics = app.state::<Analytics>();
+    let Some(dir) = analytics.dir.lock().map_err(|e| AppError::Storage(e.to_string()))?.clone() else {
+        return Ok(());
+    };
+    write_json(&dir.join(constants::ANALYTICS_QUEUE_FILE), &analytics.queued())
+}
+
+/// Opt in or out of analytics
+///
+/// Opting out drops all queued events.
+pub fn set_enabled(app: &AppHandle, enabled: bool) -> AppResult<()> {
+    let analytics = app.state::<Analytics>();
+    analytics.opted_out.store(!enabled, Ordering::SeqCst);
+    if !enabled {
+        if let Ok(mut queue) = analytics.queue.lock() {
+            queue.clear();
+        }
+    }
+
+    let dir = analytics
+        .dir
+        .lock()
+        .map_err(|e| AppError::Storage(e.to_string()))?
+        .clone()
+        .ok_or_else(|| AppError::Storage("Analytics not initialized".to_string()))?;
+    if !enabled {
+        let _ = std::fs::remove_file(dir.join(constants::ANALYTICS_QUEUE_FILE));
+    }
+    write_json(
+        &dir.join(constants::ANALYTICS_SETTINGS_FILE),
+        &AnalyticsSettings { opted_out: !enabled },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn props(entries: &[(&str, PropValue)]) -> BTreeMap<String, PropValue> {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_accepts_schema_events() {
+        assert!(validate("screen_view", &props(&[("screen", PropValue::Token("agenda".into()))])).is_ok());
+        assert!(validate("app_open", &props(&[("first_launch", PropValue::Bool(true))])).is_ok());
+        assert!(validate("app_open", &BTreeMap::new()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_event_and_property() {
+        assert!(validate("custom_event", &BTreeMap::new()).is_err());
+        assert!(validate("screen_view", &props(&[("email", PropValue::Bool(true))])).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_free_form_values() {
+        let screen = |value: &str| props(&[("screen", PropValue::Token(value.to_string()))]);
+        assert!(validate("screen_view", &screen("jean.dupont@example.com")).is_err());
+        assert!(validate("screen_view", &screen("Bonjour Jean")).is_err());
+        assert!(validate("screen_view", &screen("")).is_err());
+        assert!(validate("screen_view", &screen(&"a".repeat(65))).is_err());
+        assert!(validate("update_prompt", &props(&[("required", PropValue::Number(f64::NAN))])).is_err());
+    }
+
+    #[test]
+    fn test_track_respects_opt_out() {
+        let analytics = Analytics::default();
+        assert_eq!(analytics.track("app_open", BTreeMap::new()).unwrap(), 1);
+        analytics.opted_out.store(true, Ordering::SeqCst);
+        assert_eq!(analytics.track("app_open", BTreeMap::new()).unwrap(), 0);
+        assert!(analytics.track("unknown", BTreeMap::new()).is_err(), "Schema is checked even when opted out");
+    }
+
+    #[test]
+    fn test_queue_is_bounded() {
+        let analytics = Analytics::default();
+        for _ in 0..constants::ANALYTICS_MAX_QUEUED_EVENTS + 5 {
+            analytics.track("app_open", BTreeMap::new()).unwrap();
+        }
+        assert_eq!(analytics.queued().len(), constants::ANALYTICS_MAX_QUEUED_EVENTS);
+    }
+
+    #[test]
+    fn test_take_batch_and_requeue_keep_order() {
+        let analytics = Analytics::default();
+        for screen in ["a", "b", "c"] {
+            analytics
+                .track("screen_view", props(&[("screen", PropValue::Token(screen.into()))]))
+                .unwrap();
+        }
+
+        let batch = analytics.take_batch();
+        assert_eq!(batch.len(), 3);
+        analytics.track("app_open", BTreeMap::new()).unwrap();
+        analytics.requeue(batch);
+
+        let names: Vec<String> = analytics.queued().into_iter().map(|event| event.name).collect();
+        assert_eq!(names, ["screen_view", "screen_view", "screen_view", "app_open"]);
+    }
+}
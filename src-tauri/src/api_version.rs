@@ -0,0 +1,116 @@
+/// Native API versioning
+///
+/// The web application is deployed independently of the mobile builds, which
+/// users update slowly, so a page may expect another native API than the one
+/// of the installed app. The page declares the version it was written for
+/// with `negotiate_api(version)`, first thing when its bundle runs:
+/// - a version between `NATIVE_API_MIN_VERSION` and `NATIVE_API_VERSION` is
+///   accepted, and the native layer answers with the shapes of that version
+///   until the next page load
+/// - a newer version is refused with an error asking to update the app, an
+///   older one with an error asking to reload the web application
+///
+/// Pages that do not negotiate get the current version.
+///
+/// Versions:
+/// - 1: keychain, notification and connectivity commands resolve with their
+///   value and reject with the error message
+/// - 2: they resolve with a `CommandResponse` envelope
+///   (`{ ok, data, error }`, see `command_response`)
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::constants;
+use crate::{AppError, AppResult};
+
+/// API version negotiated by the current page (0 until it negotiates)
+static NEGOTIATED: AtomicU32 = AtomicU32::new(0);
+
+/// Result of a successful negotiation, returned by `negotiate_api`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiNegotiation {
+    /// Version the native layer answers with until the next page load
+    pub version: u32,
+    /// Latest version supported by the app
+    pub latest: u32,
+    /// Oldest version supported by the app
+    pub oldest: u32,
+    /// Version of the app
+    pub app_version: String,
+}
+
+/// Check that the app supports an API version
+///
+/// # Returns
+///
+/// Returns the negotiation result, or an error telling the user what to do:
+/// update the app (version too new) or reload the web application (version
+/// too old).
+pub fn check(version: u32) -> AppResult<ApiNegotiation> {
+    let supported = format!(
+        "this app supports native API versions {} to {}",
+        constants::NATIVE_API_MIN_VERSION,
+        constants::NATIVE_API_VERSION
+    );
+    if version > constants::NATIVE_API_VERSION {
+        return Err(AppError::InvalidArgument(format!(
+            "The web application requires native API version {} but {}: update the app from the store",
+            version, supported
+        )));
+    }
+    if version < constants::NATIVE_API_MIN_VERSION {
+        return Err(AppError::InvalidArgument(format!(
+            "The web application uses native API version {} but {}: reload the web application",
+            version, supported
+        )));
+    }
+    Ok(ApiNegotiation {
+        version,
+        latest: constants::NATIVE_API_VERSION,
+        oldest: constants::NATIVE_API_MIN_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+/// Negotiate the API version of the current page
+///
+/// A refused version leaves the negotiated version unchanged.
+pub fn negotiate(version: u32) -> AppResult<ApiNegotiation> {
+    let negotiation = check(version).map_err(|e| {
+        tracing::warn!("API negotiation refused: {}", e);
+        e
+    })?;
+    NEGOTIATED.store(version, Ordering::SeqCst);
+    tracing::info!("Native API version {} negotiated", version);
+    Ok(negotiation)
+}
+
+/// API version the native layer answers with
+pub fn current() -> u32 {
+    match NEGOTIATED.load(Ordering::SeqCst) {
+        0 => constants::NATIVE_API_VERSION,
+        version => version,
+    }
+}
+
+/// Forget the negotiated version, when a page starts loading
+pub fn reset() {
+    NEGOTIATED.store(0, Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_versions() {
+        for version in constants::NATIVE_API_MIN_VERSION..=constants::NATIVE_API_VERSION {
+            assert_eq!(check(version).map(|negotiation| negotiation.version).unwrap(), version);
+        }
+        let newer = check(constants::NATIVE_API_VERSION + 1).unwrap_err().to_string();
+        assert!(newer.contains("update the app"), "{}", newer);
+        let older = check(constants::NATIVE_API_MIN_VERSION - 1).unwrap_err().to_string();
+        assert!(older.contains("reload the web application"), "{}", older);
+    }
+}
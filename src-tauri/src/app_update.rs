@@ -0,0 +1,188 @@
+/// App update availability module
+///
+/// This module compares the installed app version with the version policy
+/// published by the backend (`APP_VERSION_ENDPOINT`), so the frontend can nudge
+/// users to update, or require it when the installed build is no longer
+/// supported.
+
+use std::cmp::Ordering;
+
+use crate::constants;
+use crate::http;
+use crate::{AppError, AppResult};
+
+/// Version policy published by the backend for a platform
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionPolicy {
+    /// Latest version available in the store
+    pub latest_version: String,
+    /// Oldest version still supported
+    pub minimum_version: String,
+    /// Store page of the app
+    #[serde(default)]
+    pub store_url: Option<String>,
+}
+
+/// Update availability, as reported by `check_app_update`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    /// Installed version
+    pub current_version: String,
+    /// Latest version available in the store
+    pub latest_version: String,
+    /// `true` if a newer version is available
+    pub update_available: bool,
+    /// `true` if the installed version is below the minimum supported version
+    pub update_required: bool,
+    /// Store page of the app, if known
+    pub store_url: Option<String>,
+}
+
+/// Compare two dotted version strings (`1.2.10` > `1.2.9`)
+///
+/// Missing components count as zero (`1.2` == `1.2.0`), and pre-release or
+/// build suffixes (`-beta.1`, `+42`) are ignored.
+///
+/// # Returns
+///
+/// Returns `None` if either version is not made of numeric components.
+pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    fn parse(version: &str) -> Option<Vec<u64>> {
+        let core = version
+            .trim()
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()?;
+        core.split('.').map(|part| part.parse().ok()).collect()
+    }
+
+    let a = parse(a)?;
+    let b = parse(b)?;
+    let len = a.len().max(b.len());
+    let component = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    Some(
+        (0..len)
+            .map(|i| component(&a, i).cmp(&component(&b, i)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal),
+    )
+}
+
+/// Evaluate the installed version against a version policy
+///
+/// # Returns
+///
+/// Returns the `UpdateInfo`, or an error if a version is malformed.
+pub fn evaluate(current_version: &str, policy: &VersionPolicy) -> AppResult<UpdateInfo> {
+    let against = |other: &str| {
+        compare_versions(current_version, other)
+            .ok_or_else(|| AppError::InvalidArgument(format!("Invalid version: {} / {}", current_version, other)))
+    };
+
+    Ok(UpdateInfo {
+        current_version: current_version.to_string(),
+        latest_version: policy.latest_version.clone(),
+        update_available: against(&policy.latest_version)? == Ordering::Less,
+        update_required: against(&policy.minimum_version)? == Ordering::Less,
+        store_url: policy.store_url.clone().or_else(default_store_url),
+    })
+}
+
+/// Store page used when the backend does not provide one
+fn default_store_url() -> Option<String> {
+    if cfg!(target_os = "android") {
+        Some(constants::PLAY_STORE_URL.to_string())
+    } else {
+        None
+    }
+}
+
+/// Fetch the version policy for the current platform
+///
+/// # Returns
+///
+/// Returns the `VersionPolicy`, or an error if the request fails.
+pub async fn fetch_policy() -> AppResult<VersionPolicy> {
+    let response = http::client()?
+        .get(http::endpoint(constants::APP_VERSION_ENDPOINT))
+        .query(&[("platform", std::env::consts::OS)])
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| AppError::Network(format!("Version check failed: {}", e)))?;
+
+    response
+        .json()
+        .await
+        .map_err(|e| AppError::Network(format!("Invalid version policy: {}", e)))
+}
+
+/// Check whether an app update is available or required
+///
+/// # Returns
+///
+/// Returns the `UpdateInfo`, or an error if the version policy could
+/// not be fetched.
+pub async fn check() -> AppResult<UpdateInfo> {
+    let policy = fetch_policy().await?;
+    let info = evaluate(env!("CARGO_PKG_VERSION"), &policy)?;
+    tracing::info!(
+        "App update check: current={}, latest={}, required={}",
+        info.current_version,
+        info.latest_version,
+        info.update_required
+    );
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(latest: &str, minimum: &str) -> VersionPolicy {
+        VersionPolicy {
+            latest_version: latest.to_string(),
+            minimum_version: minimum.to_string(),
+            store_url: Some("https://example.com/app".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.2.10", "1.2.9"), Some(Ordering::Greater));
+        assert_eq!(compare_versions("1.2", "1.2.0"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("v2.0.0-beta.1", "2.0.0"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("0.9.9", "1.0"), Some(Ordering::Less));
+        assert_eq!(compare_versions("1.x", "1.0"), None);
+        assert_eq!(compare_versions("", "1.0"), None);
+    }
+
+    #[test]
+    fn test_evaluate_up_to_date() {
+        let info = evaluate("1.4.0", &policy("1.4.0", "1.2.0")).unwrap();
+        assert!(!info.update_available);
+        assert!(!info.update_required);
+        assert_eq!(info.store_url.as_deref(), Some("https://example.com/app"));
+    }
+
+    #[test]
+    fn test_evaluate_update_available() {
+        let info = evaluate("1.3.0", &policy("1.4.0", "1.2.0")).unwrap();
+        assert!(info.update_available);
+        assert!(!info.update_required);
+    }
+
+    #[test]
+    fn test_evaluate_update_required() {
+        let info = evaluate("1.1.5", &policy("1.4.0", "1.2.0")).unwrap();
+        assert!(info.update_available);
+        assert!(info.update_required);
+    }
+
+    #[test]
+    fn test_evaluate_invalid_version() {
+        assert!(evaluate("1.0.0", &policy("latest", "1.0.0")).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+/// Encrypted diagnostic artifacts module
+///
+/// Crash reports and minidumps may carry personal data (log lines, app
+/// state, memory contents), so they are never kept in plaintext on disk:
+/// - the artifact key is derived with HKDF-SHA256 from the keychain master key
+///   (the database key, see `database::master_key`), so it is never stored
+/// - artifacts are sealed with AES-256-GCM into `<name>.enc` files:
+///   `ARTIFACT_MAGIC`, a random 96-bit nonce, then the ciphertext and tag;
+///   the file name is authenticated, so sealed files cannot be swapped
+/// - artifacts are only opened when the user explicitly shares or uploads
+///   them (`send_crash_reports`, opted-in crash upload)
+///
+/// The key is derived once at launch (`init`) and kept in memory, because the
+/// panic hook cannot reach the keychain. Artifacts written by native code
+/// (minidumps) are sealed on the next launch (`seal_file`).
+///
+/// Log files and the diagnostics export archive are not sealed: log lines are
+/// redacted when written and again when exported, and stay in the app-private
+/// log directory; the archive is written in plaintext for the share sheet
+/// only, and deleted before the next export and at the next launch
+/// (`diagnostics`).
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::hkdf::{Salt, HKDF_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use tauri::AppHandle;
+
+use crate::constants;
+use crate::database;
+use crate::{AppError, AppResult};
+
+/// Artifact key derived at launch
+static KEY: OnceLock<ArtifactKey> = OnceLock::new();
+
+/// Key sealing diagnostic artifacts
+pub struct ArtifactKey(LessSafeKey);
+
+impl std::fmt::Debug for ArtifactKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ArtifactKey(..)")
+    }
+}
+
+impl ArtifactKey {
+    /// Derive the artifact key from the master key
+    pub fn derive(master_key: &[u8]) -> AppResult<Self> {
+        let info = [constants::ARTIFACT_KEY_INFO.as_bytes()];
+        let prk = Salt::new(HKDF_SHA256, &[]).extract(master_key);
+        let okm = prk
+            .expand(&info, &AES_256_GCM)
+            .map_err(|_| AppError::Storage("Failed to derive artifact key".to_string()))?;
+        Ok(Self(LessSafeKey::new(UnboundKey::from(okm))))
+    }
+
+    /// Encrypt an artifact
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - File name of the sealed artifact, authenticated with it
+    /// * `plaintext` - Artifact contents
+    pub fn seal(&self, name: &str, plaintext: &[u8]) -> AppResult<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| AppError::Storage("Failed to generate artifact nonce".to_string()))?;
+
+        let mut in_out = plaintext.to_vec();
+        self.0
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::from(name.as_bytes()), &mut in_out)
+            .map_err(|_| AppError::Storage("Failed to encrypt artifact".to_string()))?;
+        Ok([constants::ARTIFACT_MAGIC, &nonce[..], &in_out].concat())
+    }
+
+    /// Decrypt an artifact sealed with `seal`
+    pub fn open(&self, name: &str, sealed: &[u8]) -> AppResult<Vec<u8>> {
+        let rest = sealed
+            .strip_prefix(constants::ARTIFACT_MAGIC)
+            .ok_or_else(|| AppError::Storage("Not an encrypted artifact".to_string()))?;
+        if rest.len() < NONCE_LEN {
+            return Err(AppError::Storage("Truncated encrypted artifact".to_string()));
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| AppError::Storage("Invalid artifact nonce".to_string()))?;
+
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = self
+            .0
+            .open_in_place(nonce, Aad::from(name.as_bytes()), &mut in_out)
+            .map_err(|_| AppError::Storage("Failed to decrypt artifact".to_string()))?;
+        Ok(plaintext.to_vec())
+    }
+
+    /// Write an artifact to `<path>.enc`
+    ///
+    /// # Returns
+    ///
+    /// Returns the path of the sealed file.
+    pub fn write(&self, path: &Path, plaintext: &[u8]) -> AppResult<PathBuf> {
+        let sealed_path = sealed_path(path);
+        let sealed = self.seal(&file_name(&sealed_path), plaintext)?;
+        std::fs::write(&sealed_path, sealed).map_err(|e| AppError::Storage(format!("Failed to write artifact: {}", e)))?;
+        Ok(sealed_path)
+    }
+
+    /// Read and decrypt a sealed artifact file
+    pub fn read(&self, path: &Path) -> AppResult<Vec<u8>> {
+        let sealed = std::fs::read(path).map_err(|e| AppError::Storage(format!("Failed to read artifact: {}", e)))?;
+        self.open(&file_name(path), &sealed)
+    }
+
+    /// Replace a plaintext file with its sealed version
+    ///
+    /// # Returns
+    ///
+    /// Returns the path of the sealed file.
+    pub fn seal_file(&self, path: &Path) -> AppResult<PathBuf> {
+        let plaintext = std::fs::read(path).map_err(|e| AppError::Storage(format!("Failed to read artifact: {}", e)))?;
+        let sealed_path = self.write(path, &plaintext)?;
+        std::fs::remove_file(path).map_err(|e| AppError::Storage(format!("Failed to delete plaintext artifact: {}", e)))?;
+        Ok(sealed_path)
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Path of the sealed version of a file
+pub fn sealed_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(constants::ARTIFACT_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Returns `true` if the file is a sealed artifact
+pub fn is_sealed(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some(constants::ARTIFACT_EXTENSION)
+}
+
+/// Extension of the artifact, ignoring the sealed extension
+/// (`panic-1.json.enc` → `json`)
+pub fn inner_extension(path: &Path) -> Option<&str> {
+    let path = if is_sealed(path) {
+        Path::new(path.file_stem()?)
+    } else {
+        path
+    };
+    path.extension().and_then(|ext| ext.to_str())
+}
+
+/// Derive the artifact key from the keychain master key
+///
+/// Called at launch, before crash reporting resolves pending reports.
+pub fn init(app: &AppHandle) -> AppResult<()> {
+    if KEY.get().is_some() {
+        return Ok(());
+    }
+    let key = ArtifactKey::derive(&database::master_key(app)?)?;
+    // Another thread may have derived the same key in the meantime
+    let _ = KEY.set(key);
+    Ok(())
+}
+
+/// Artifact key, once derived by `init`
+pub fn key() -> AppResult<&'static ArtifactKey> {
+    KEY.get().ok_or_else(|| AppError::Storage("Artifact key not initialized".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> ArtifactKey {
+        ArtifactKey::derive(&[7u8; 32]).unwrap()
+    }
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = key();
+        let sealed = key.seal("report.json.enc", b"secret crash report").unwrap();
+        assert!(!sealed.windows(6).any(|window| window == b"secret"));
+        assert_eq!(key.open("report.json.enc", &sealed).unwrap(), b"secret crash report");
+
+        assert!(key.open("other.json.enc", &sealed).is_err(), "File name is authenticated");
+        assert!(ArtifactKey::derive(&[8u8; 32]).unwrap().open("report.json.enc", &sealed).is_err());
+        assert!(key.open("report.json.enc", b"plaintext").is_err());
+    }
+
+    #[test]
+    fn test_seal_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("crash.dmp");
+        std::fs::write(&path, b"MDMP").unwrap();
+
+        let key = key();
+        let sealed = key.seal_file(&path).unwrap();
+        assert!(!path.exists(), "Plaintext is deleted");
+        assert!(is_sealed(&sealed));
+        assert_eq!(inner_extension(&sealed), Some("dmp"));
+        assert_eq!(key.read(&sealed).unwrap(), b"MDMP");
+    }
+}
@@ -0,0 +1,49 @@
+/// Android-specific offline asset cache hook
+///
+/// Requests of the webview to the web application host are intercepted in
+/// `shouldInterceptRequest` (see the `tls_pinning` module, which performs
+/// them with a pinned OkHttp client). This module makes that interceptor
+/// serve cached assets first and feed the cache on a miss.
+///
+/// Note: This implementation provides the structure for the Android asset cache.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Install the cache in the webview request interceptor
+///
+/// # Arguments
+///
+/// * `app_url` - URL of the web application
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn install_hook(app_url: &str) -> AppResult<()> {
+    tracing::info!("[Android] Installing offline asset cache for {}", app_url);
+
+    // TODO: Implement native Android request interception
+    // Example Kotlin implementation:
+    // ```kotlin
+    // override fun shouldInterceptRequest(view: WebView, request: WebResourceRequest): WebResourceResponse? {
+    //     if (request.url.host != appHost || request.method != "GET") return null
+    //     // asset_cache::lookup
+    //     rustLookupAsset(request.url.toString())?.let { cached ->
+    //         return WebResourceResponse(cached.mimeType, cached.encoding, ByteArrayInputStream(cached.body))
+    //     }
+    //     val response = pinnedClient.newCall(request.toOkHttp()).execute()
+    //     val body = response.body!!.bytes()
+    //     if (response.isSuccessful) {
+    //         // asset_cache::store
+    //         rustStoreAsset(request.url.toString(), response.header("Content-Type"),
+    //             response.header("ETag"), response.header("Last-Modified"), body)
+    //     }
+    //     return response.toWebResourceResponse(body)
+    // }
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
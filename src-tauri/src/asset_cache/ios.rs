@@ -0,0 +1,53 @@
+/// iOS-specific offline asset cache hook
+///
+/// `WKWebView` does not let apps intercept `https` requests. When loading
+/// the web application fails, the navigation delegate loads the cached HTML
+/// shell under the `elulib-asset://` scheme instead, so its scripts and
+/// stylesheets (relative URLs) are answered from the cache by a
+/// `WKURLSchemeHandler`. Online loads feed the cache from the responses of
+/// the app host observed by the navigation delegate.
+///
+/// Note: This implementation provides the structure for the iOS asset cache.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Install the cache in the webview navigation delegate
+///
+/// # Arguments
+///
+/// * `app_url` - URL of the web application
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn install_hook(app_url: &str) -> AppResult<()> {
+    tracing::info!("[iOS] Installing offline asset cache for {}", app_url);
+
+    // TODO: Implement native iOS offline loading
+    // Example Swift implementation:
+    // ```swift
+    // func webView(_ webView: WKWebView, didFailProvisionalNavigation navigation: WKNavigation!, withError error: Error) {
+    //     guard rustLookupAsset(appURL.absoluteString) != nil else { return showOfflineScreen() }  // asset_cache::lookup
+    //     webView.load(URLRequest(url: URL(string: "elulib-asset://" + appURL.host! + "/")!))
+    // }
+    //
+    // final class CachedAssetSchemeHandler: NSObject, WKURLSchemeHandler {
+    //     func webView(_ webView: WKWebView, start task: WKURLSchemeTask) {
+    //         let url = task.request.url!.absoluteString.replacingOccurrences(of: "elulib-asset://", with: "https://")
+    //         // asset_cache::lookup
+    //         guard let asset = rustLookupAsset(url) else { return task.didFailWithError(URLError(.notConnectedToInternet)) }
+    //         task.didReceive(URLResponse(url: task.request.url!, mimeType: asset.contentType,
+    //                                     expectedContentLength: asset.body.count, textEncodingName: nil))
+    //         task.didReceive(asset.body)
+    //         task.didFinish()
+    //     }
+    //     func webView(_ webView: WKWebView, stop task: WKURLSchemeTask) {}
+    // }
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
@@ -0,0 +1,547 @@
+/// Offline asset cache for the remote frontend
+///
+/// The webview loads the web application from `app_url`; without network (or
+/// on a slow link) it used to stay on a white screen. This module keeps the
+/// static assets of the web application on disk, in the app cache directory,
+/// so the app can open instantly:
+/// - cacheable assets are `GET` requests to the `app_url` origin for the HTML
+///   shell (extension-less routes, `.html`), scripts and stylesheets, outside
+///   of the backend API (`/api/`), answered with an HTML, JavaScript or CSS
+///   content type
+/// - the native hook asks `lookup` for each request (Android request
+///   interception) or when the web application fails to load (iOS navigation
+///   delegate, which cannot intercept `https` requests): a cached asset is
+///   served right away (stale-while-revalidate) and revalidated in background
+///   with a conditional request (`If-None-Match` / `If-Modified-Since`), at
+///   most once per `ASSET_CACHE_REVALIDATE_SECS`, so the next load gets the
+///   new version
+/// - on a miss, the native hook loads the asset from the network and hands the
+///   response to `store`
+/// - the cache is bounded (`ASSET_CACHE_MAX_BYTES`), least recently fetched
+///   assets are evicted first
+///
+/// The `clear_asset_cache` command busts the cache, e.g. after a deploy that
+/// must not be served stale. The cache can be disabled remotely with the
+/// `offline_asset_cache` feature flag.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, Url};
+
+use crate::constants;
+use crate::feature_flags;
+use crate::http;
+use crate::runtime_config;
+use crate::{AppError, AppResult};
+
+/// Cached asset, as recorded in the cache index
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedAsset {
+    /// Content type of the response
+    pub content_type: String,
+    /// Entity tag of the response, for revalidation
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// Last modification date of the response, for revalidation
+    #[serde(default)]
+    pub last_modified: Option<String>,
+    /// Time the asset was fetched or last revalidated (seconds since the Unix epoch)
+    pub fetched_at: u64,
+    /// Size of the body (bytes)
+    pub size: u64,
+}
+
+/// Asset served from the cache to the webview
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    /// Content type of the response
+    pub content_type: String,
+    /// Body of the response
+    pub body: Vec<u8>,
+}
+
+/// Offline asset cache, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct AssetCache {
+    dir: Mutex<Option<PathBuf>>,
+    index: Mutex<BTreeMap<String, CachedAsset>>,
+    /// Assets being revalidated
+    revalidating: Mutex<HashSet<String>>,
+}
+
+impl AssetCache {
+    /// Open the cache stored in `dir`, loading its index
+    pub fn open(&self, dir: PathBuf) {
+        let index = read_index(&dir);
+        tracing::debug!("Asset cache opened with {} asset(s)", index.len());
+        if let Ok(mut current) = self.index.lock() {
+            *current = index;
+        }
+        if let Ok(mut current) = self.dir.lock() {
+            *current = Some(dir);
+        }
+    }
+
+    fn dir(&self) -> AppResult<PathBuf> {
+        self.dir
+            .lock()
+            .map_err(|e| AppError::Storage(e.to_string()))?
+            .clone()
+            .ok_or_else(|| AppError::Storage("Asset cache not initialized".to_string()))
+    }
+
+    /// Index entry of a cached asset
+    pub fn entry(&self, url: &str) -> Option<CachedAsset> {
+        self.index.lock().ok()?.get(url).cloned()
+    }
+
+    /// Read a cached asset
+    ///
+    /// # Returns
+    ///
+    /// Returns the index entry and the body, or `None` if the asset is not
+    /// cached or its body is missing.
+    pub fn get(&self, url: &str) -> Option<(CachedAsset, Vec<u8>)> {
+        let entry = self.entry(url)?;
+        match std::fs::read(self.dir().ok()?.join(file_name(url))) {
+            Ok(body) => Some((entry, body)),
+            Err(e) => {
+                tracing::warn!("Dropping unreadable cached asset {}: {}", url, e);
+                let _ = self.remove(url);
+                None
+            }
+        }
+    }
+
+    /// Store an asset, evicting the least recently fetched assets beyond
+    /// `ASSET_CACHE_MAX_BYTES`
+    pub fn put(&self, url: &str, asset: CachedAsset, body: &[u8]) -> AppResult<()> {
+        let dir = self.dir()?;
+        std::fs::create_dir_all(&dir).map_err(|e| AppError::Storage(format!("Failed to create asset cache directory: {}", e)))?;
+        std::fs::write(dir.join(file_name(url)), body).map_err(|e| AppError::Storage(format!("Failed to write cached asset: {}", e)))?;
+
+        let mut index = self.index.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        index.insert(url.to_string(), asset);
+        for evicted in evictions(&index, constants::ASSET_CACHE_MAX_BYTES) {
+            index.remove(&evicted);
+            if let Err(e) = std::fs::remove_file(dir.join(file_name(&evicted))) {
+                tracing::warn!("Failed to delete evicted asset: {}", e);
+            }
+        }
+        write_index(&dir, &index)
+    }
+
+    /// Record that a cached asset was revalidated (not modified) at `now`
+    pub fn touch(&self, url: &str, now: u64) -> AppResult<()> {
+        let dir = self.dir()?;
+        let mut index = self.index.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        if let Some(asset) = index.get_mut(url) {
+            asset.fetched_at = now;
+        }
+        write_index(&dir, &index)
+    }
+
+    /// Remove a cached asset
+    pub fn remove(&self, url: &str) -> AppResult<()> {
+        let dir = self.dir()?;
+        let mut index = self.index.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        if index.remove(url).is_some() {
+            let _ = std::fs::remove_file(dir.join(file_name(url)));
+        }
+        write_index(&dir, &index)
+    }
+
+    /// Remove all cached assets
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of removed assets, or an error if the cache
+    /// directory could not be deleted.
+    pub fn clear(&self) -> AppResult<usize> {
+        let dir = self.dir()?;
+        let mut index = self.index.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        let count = index.len();
+        index.clear();
+        match std::fs::remove_dir_all(&dir) {
+            Ok(()) => Ok(count),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(count),
+            Err(e) => Err(AppError::Storage(format!("Failed to delete asset cache: {}", e))),
+        }
+    }
+
+    /// Mark an asset as being revalidated
+    ///
+    /// # Returns
+    ///
+    /// Returns `false` if it already is.
+    fn start_revalidation(&self, url: &str) -> bool {
+        self.revalidating
+            .lock()
+            .map(|mut revalidating| revalidating.insert(url.to_string()))
+            .unwrap_or(false)
+    }
+
+    fn end_revalidation(&self, url: &str) {
+        if let Ok(mut revalidating) = self.revalidating.lock() {
+            revalidating.remove(url);
+        }
+    }
+}
+
+/// Name of the file holding the body of an asset
+fn file_name(url: &str) -> String {
+    ring::digest::digest(&ring::digest::SHA256, url.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn read_index(dir: &Path) -> BTreeMap<String, CachedAsset> {
+    let Ok(contents) = std::fs::read_to_string(dir.join(constants::ASSET_CACHE_INDEX_FILE)) else {
+        return BTreeMap::new();
+    };
+    serde_json::from_str(&contents)
+        .map_err(|e| tracing::warn!("Ignoring invalid asset cache index: {}", e))
+        .unwrap_or_default()
+}
+
+fn write_index(dir: &Path, index: &BTreeMap<String, CachedAsset>) -> AppResult<()> {
+    let contents = serde_json::to_vec(index).map_err(|e| AppError::Storage(format!("Failed to serialize asset cache index: {}", e)))?;
+    std::fs::write(dir.join(constants::ASSET_CACHE_INDEX_FILE), contents)
+        .map_err(|e| AppError::Storage(format!("Failed to write asset cache index: {}", e)))
+}
+
+/// Assets to evict so the cache fits within `max_bytes`, least recently
+/// fetched first
+pub fn evictions(index: &BTreeMap<String, CachedAsset>, max_bytes: u64) -> Vec<String> {
+    let mut total: u64 = index.values().map(|asset| asset.size).sum();
+    let mut assets: Vec<(&String, &CachedAsset)> = index.iter().collect();
+    assets.sort_by_key(|(_, asset)| asset.fetched_at);
+
+    let mut evicted = Vec::new();
+    for (url, asset) in assets {
+        if total <= max_bytes {
+            break;
+        }
+        total -= asset.size;
+        evicted.push(url.clone());
+    }
+    evicted
+}
+
+/// Cache key of a request, if the asset is cacheable
+///
+/// # Arguments
+///
+/// * `url` - URL of the request
+/// * `app_url` - URL of the web application
+///
+/// # Returns
+///
+/// Returns the URL without its fragment, or `None` if the request is not for
+/// a static asset of the web application.
+pub fn cache_key(url: &str, app_url: &str) -> Option<String> {
+    let mut url = Url::parse(url).ok()?;
+    let app_url = Url::parse(app_url).ok()?;
+    if url.scheme() != "https" || url.origin() != app_url.origin() {
+        return None;
+    }
+    let path = url.path();
+    if constants::ASSET_CACHE_EXCLUDED_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+    {
+        return None;
+    }
+    let last_segment = path.rsplit('/').next().unwrap_or_default();
+    let cacheable = match last_segment.rsplit_once('.') {
+        Some((_, extension)) => constants::ASSET_CACHE_EXTENSIONS.contains(&extension),
+        None => true,
+    };
+    if !cacheable {
+        return None;
+    }
+    url.set_fragment(None);
+    Some(url.to_string())
+}
+
+/// Returns `true` if a response with this content type can be cached
+pub fn is_cacheable_content_type(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    constants::ASSET_CACHE_CONTENT_TYPES
+        .iter()
+        .any(|cacheable| mime.eq_ignore_ascii_case(cacheable))
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn enabled(app: &AppHandle) -> bool {
+    feature_flags::is_enabled(app, feature_flags::OFFLINE_ASSET_CACHE)
+}
+
+/// Serve a request from the cache, called by the native webview hook
+///
+/// Stale assets are served as is and revalidated in background.
+///
+/// # Arguments
+///
+/// * `url` - URL of the `GET` request
+///
+/// # Returns
+///
+/// Returns the cached response, or `None` if the request must go to the
+/// network.
+pub fn lookup(app: &AppHandle, url: &str) -> Option<CachedResponse> {
+    if !enabled(app) {
+        return None;
+    }
+    let key = cache_key(url, &runtime_config::get().app_url)?;
+    let cache = app.state::<AssetCache>();
+    let (asset, body) = cache.get(&key)?;
+
+    if now_secs().saturating_sub(asset.fetched_at) >= constants::ASSET_CACHE_REVALIDATE_SECS
+        && cache.start_revalidation(&key)
+    {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = revalidate(&app, &key).await {
+                tracing::debug!("{}", e);
+            }
+            app.state::<AssetCache>().end_revalidation(&key);
+        });
+    }
+
+    Some(CachedResponse {
+        content_type: asset.content_type,
+        body,
+    })
+}
+
+/// Store a response loaded from the network, called by the native webview
+/// hook after a cache miss
+///
+/// Responses that are not cacheable (URL, content type, size) are ignored.
+///
+/// # Arguments
+///
+/// * `url` - URL of the `GET` request
+/// * `content_type` - `Content-Type` of the response
+/// * `etag` - `ETag` of the response, if any
+/// * `last_modified` - `Last-Modified` of the response, if any
+/// * `body` - Body of the response
+pub fn store(
+    app: &AppHandle,
+    url: &str,
+    content_type: &str,
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+    body: &[u8],
+) {
+    if !enabled(app) || !is_cacheable_content_type(content_type) || body.len() as u64 > constants::ASSET_CACHE_MAX_ASSET_BYTES {
+        return;
+    }
+    let Some(key) = cache_key(url, &runtime_config::get().app_url) else {
+        return;
+    };
+    let asset = CachedAsset {
+        content_type: content_type.to_string(),
+        etag: etag.map(str::to_string),
+        last_modified: last_modified.map(str::to_string),
+        fetched_at: now_secs(),
+        size: body.len() as u64,
+    };
+    if let Err(e) = app.state::<AssetCache>().put(&key, asset, body) {
+        tracing::warn!("{}", e);
+    }
+}
+
+/// Revalidate a cached asset with a conditional request
+///
+/// The asset is kept if not modified, replaced if modified, and removed if
+/// it no longer exists.
+pub async fn revalidate(app: &AppHandle, url: &str) -> AppResult<()> {
+    let cache = app.state::<AssetCache>();
+    let Some(asset) = cache.entry(url) else {
+        return Ok(());
+    };
+
+    let mut request = http::client()?.get(url);
+    if let Some(etag) = &asset.etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &asset.last_modified {
+        request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| AppError::Storage(format!("Asset revalidation failed: {}", e)))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_MODIFIED {
+        return cache.touch(url, now_secs());
+    }
+    if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::GONE {
+        tracing::info!("Cached asset no longer exists: {}", url);
+        return cache.remove(url);
+    }
+    if !status.is_success() {
+        return Err(AppError::Storage(format!("Asset revalidation failed: HTTP {}", status)));
+    }
+
+    let header = |name: reqwest::header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let content_type = header(reqwest::header::CONTENT_TYPE).unwrap_or_default();
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| AppError::Storage(format!("Asset revalidation failed: {}", e)))?;
+
+    store(app, url, &content_type, etag.as_deref(), last_modified.as_deref(), &body);
+    tracing::debug!("Cached asset updated: {}", url);
+    Ok(())
+}
+
+/// Bust the cache
+///
+/// # Returns
+///
+/// Returns the number of removed assets, or an error if the cache
+/// could not be deleted.
+pub fn clear(app: &AppHandle) -> AppResult<usize> {
+    let count = app.state::<AssetCache>().clear()?;
+    tracing::info!("Asset cache cleared ({} asset(s))", count);
+    Ok(count)
+}
+
+/// Initialize the asset cache
+///
+/// Opens the cache in the app cache directory and installs the native
+/// webview hook serving from it.
+pub fn init(app: &AppHandle) -> AppResult<()> {
+    let dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve cache directory: {}", e)))?
+        .join(constants::ASSET_CACHE_DIR);
+    app.state::<AssetCache>().open(dir);
+    install_hook(&runtime_config::get().app_url)
+}
+
+/// Install the native webview hook serving assets of `app_url` from the cache
+fn install_hook(app_url: &str) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::install_hook(app_url)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::install_hook(app_url)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = app_url;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APP_URL: &str = "https://app.elulib.com";
+
+    fn asset(size: u64, fetched_at: u64) -> CachedAsset {
+        CachedAsset {
+            content_type: "text/javascript".to_string(),
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+            fetched_at,
+            size,
+        }
+    }
+
+    #[test]
+    fn test_cache_key() {
+        assert_eq!(
+            cache_key("https://app.elulib.com/", APP_URL).as_deref(),
+            Some("https://app.elulib.com/")
+        );
+        assert_eq!(
+            cache_key("https://app.elulib.com/assets/app.3f2a.js#map", APP_URL).as_deref(),
+            Some("https://app.elulib.com/assets/app.3f2a.js")
+        );
+        assert!(cache_key("https://app.elulib.com/agenda?week=12", APP_URL).is_some(), "HTML shell route");
+        assert!(cache_key("https://app.elulib.com/styles/main.css", APP_URL).is_some());
+        assert_eq!(cache_key("https://app.elulib.com/api/mobile/config", APP_URL), None, "Backend API");
+        assert_eq!(cache_key("https://app.elulib.com/files/report.pdf", APP_URL), None, "Not a static asset");
+        assert_eq!(cache_key("https://cdn.example.com/app.js", APP_URL), None, "Other origin");
+        assert_eq!(cache_key("http://app.elulib.com/app.js", APP_URL), None, "Plain HTTP");
+    }
+
+    #[test]
+    fn test_cacheable_content_types() {
+        assert!(is_cacheable_content_type("text/html; charset=utf-8"));
+        assert!(is_cacheable_content_type("application/javascript"));
+        assert!(is_cacheable_content_type("text/CSS"));
+        assert!(!is_cacheable_content_type("application/json"));
+        assert!(!is_cacheable_content_type(""));
+    }
+
+    #[test]
+    fn test_put_get_and_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = AssetCache::default();
+        cache.open(dir.path().join("assets"));
+        let url = "https://app.elulib.com/app.js";
+        cache.put(url, asset(7, 10), b"app.js;").unwrap();
+        cache.touch(url, 20).unwrap();
+
+        // The index survives a relaunch
+        let reopened = AssetCache::default();
+        reopened.open(dir.path().join("assets"));
+        let (entry, body) = reopened.get(url).unwrap();
+        assert_eq!(entry.fetched_at, 20);
+        assert_eq!(body, b"app.js;");
+
+        assert_eq!(reopened.clear().unwrap(), 1);
+        assert_eq!(reopened.get(url), None);
+        assert!(!dir.path().join("assets").exists());
+    }
+
+    #[test]
+    fn test_evictions_drop_least_recently_fetched() {
+        let index = BTreeMap::from([
+            ("a".to_string(), asset(40, 30)),
+            ("b".to_string(), asset(40, 10)),
+            ("c".to_string(), asset(40, 20)),
+        ]);
+        assert!(evictions(&index, 120).is_empty());
+        assert_eq!(evictions(&index, 100), ["b"]);
+        assert_eq!(evictions(&index, 40), ["b", "c"]);
+    }
+}
@@ -0,0 +1,47 @@
+/// iOS-specific attachment viewer
+///
+/// This module presents a `QLPreviewController` for a file, which renders
+/// PDF, images, Office and iWork documents and text files natively, with the
+/// share and markup actions of the system.
+///
+/// Note: This implementation provides the structure for the iOS attachment viewer.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use std::path::Path;
+
+use super::AttachmentType;
+use crate::AppResult;
+
+/// Present the QuickLook preview of a file
+///
+/// # Arguments
+///
+/// * `path` - File to open
+/// * `attachment_type` - Detected type of the file
+///
+/// # Returns
+///
+/// Returns `true` once the preview is presented, `false` if QuickLook cannot
+/// preview the file, or an error if the operation fails.
+pub fn open_viewer(path: &Path, attachment_type: AttachmentType) -> AppResult<bool> {
+    tracing::info!("[iOS] Presenting QuickLook preview ({})", attachment_type.mime_type);
+
+    // TODO: Implement native iOS QuickLook preview
+    // Example Swift implementation:
+    // ```swift
+    // let url = URL(fileURLWithPath: path) as NSURL
+    // guard QLPreviewController.canPreview(url) else { return false }
+    // let controller = QLPreviewController()
+    // controller.dataSource = SingleItemDataSource(url)
+    // DispatchQueue.main.async {
+    //     rootViewController.present(controller, animated: true)
+    // }
+    // return true
+    // ```
+
+    // Placeholder: Log the operation
+    // Replace this with actual native implementation
+    let _ = path;
+    Ok(true)
+}
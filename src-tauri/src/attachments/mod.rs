@@ -0,0 +1,244 @@
+/// Native attachment viewer
+///
+/// The `open_attachment` command opens a file of the app file storage in the
+/// native viewer matching its type: QuickLook on iOS (PDF, images, Office
+/// documents, text), an `ACTION_VIEW` intent on Android, the file being
+/// exposed through the app `FileProvider` with a temporary read grant.
+///
+/// The type is sniffed from the first bytes of the file (`ATTACHMENT_SNIFF_BYTES`)
+/// rather than trusted from the extension: the extension is only used to tell
+/// apart the formats sharing a container (Office documents are ZIP or OLE2
+/// files). A file of an unknown type, or a type no installed app can open, is
+/// reported with a clear error instead of failing silently.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::io::Read;
+use std::path::Path;
+
+use tauri::AppHandle;
+
+use crate::constants;
+use crate::file_storage;
+use crate::{AppError, AppResult};
+
+/// Kind of attachment, selecting the viewer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    /// PDF document
+    Pdf,
+    /// Image
+    Image,
+    /// Word processing, spreadsheet or presentation document
+    Office,
+    /// Plain text
+    Text,
+    /// ZIP archive
+    Archive,
+}
+
+impl AttachmentKind {
+    /// Description used in error messages
+    fn description(self) -> &'static str {
+        match self {
+            AttachmentKind::Pdf => "PDF documents",
+            AttachmentKind::Image => "images",
+            AttachmentKind::Office => "office documents",
+            AttachmentKind::Text => "text files",
+            AttachmentKind::Archive => "archives",
+        }
+    }
+}
+
+/// Detected type of an attachment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentType {
+    /// Kind of attachment
+    pub kind: AttachmentKind,
+    /// MIME type handed to the viewer
+    pub mime_type: &'static str,
+}
+
+const fn attachment(kind: AttachmentKind, mime_type: &'static str) -> Option<AttachmentType> {
+    Some(AttachmentType { kind, mime_type })
+}
+
+/// Detect the type of a file from its first bytes
+///
+/// # Arguments
+///
+/// * `header` - First bytes of the file
+/// * `extension` - Extension of the file, to tell apart Office formats
+///
+/// # Returns
+///
+/// Returns the `AttachmentType`, or `None` if the type is not recognized.
+pub fn sniff(header: &[u8], extension: Option<&str>) -> Option<AttachmentType> {
+    use AttachmentKind::*;
+
+    let extension = extension.unwrap_or_default().to_ascii_lowercase();
+    if header.starts_with(b"%PDF-") {
+        return attachment(Pdf, "application/pdf");
+    }
+    if header.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return attachment(Image, "image/png");
+    }
+    if header.starts_with(b"\xff\xd8\xff") {
+        return attachment(Image, "image/jpeg");
+    }
+    if header.starts_with(b"GIF87a") || header.starts_with(b"GIF89a") {
+        return attachment(Image, "image/gif");
+    }
+    if header.len() >= 12 && &header[..4] == b"RIFF" && &header[8..12] == b"WEBP" {
+        return attachment(Image, "image/webp");
+    }
+    if header.len() >= 12 && &header[4..8] == b"ftyp" && matches!(&header[8..12], b"heic" | b"heix" | b"mif1") {
+        return attachment(Image, "image/heic");
+    }
+    if header.starts_with(b"PK\x03\x04") {
+        return match extension.as_str() {
+            "docx" => attachment(Office, "application/vnd.openxmlformats-officedocument.wordprocessingml.document"),
+            "xlsx" => attachment(Office, "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+            "pptx" => attachment(Office, "application/vnd.openxmlformats-officedocument.presentationml.presentation"),
+            "odt" => attachment(Office, "application/vnd.oasis.opendocument.text"),
+            "ods" => attachment(Office, "application/vnd.oasis.opendocument.spreadsheet"),
+            "odp" => attachment(Office, "application/vnd.oasis.opendocument.presentation"),
+            _ => attachment(Archive, "application/zip"),
+        };
+    }
+    if header.starts_with(b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1") {
+        return match extension.as_str() {
+            "doc" => attachment(Office, "application/msword"),
+            "xls" => attachment(Office, "application/vnd.ms-excel"),
+            "ppt" => attachment(Office, "application/vnd.ms-powerpoint"),
+            _ => None,
+        };
+    }
+    let text = match std::str::from_utf8(header) {
+        Ok(text) => Some(text),
+        // The header may end in the middle of a character
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&header[..e.valid_up_to()]).ok(),
+        Err(_) => None,
+    };
+    match text {
+        Some(text) if !text.is_empty() && !text.contains('\0') => attachment(Text, "text/plain"),
+        _ => None,
+    }
+}
+
+/// Read the first bytes of a file (`ATTACHMENT_SNIFF_BYTES`)
+pub fn read_header(path: &Path) -> AppResult<Vec<u8>> {
+    let file = std::fs::File::open(path).map_err(|e| AppError::Platform(format!("Failed to open attachment: {}", e)))?;
+    let mut header = Vec::with_capacity(constants::ATTACHMENT_SNIFF_BYTES);
+    file.take(constants::ATTACHMENT_SNIFF_BYTES as u64)
+        .read_to_end(&mut header)
+        .map_err(|e| AppError::Platform(format!("Failed to read attachment: {}", e)))?;
+    Ok(header)
+}
+
+/// Open a file in the native viewer
+///
+/// # Returns
+///
+/// Returns `true` once the viewer is presented, `false` if no viewer can
+/// open the type, or an error.
+fn open_viewer(path: &Path, attachment_type: AttachmentType) -> AppResult<bool> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::open_viewer(path, attachment_type)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::open_viewer(path, attachment_type)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = (path, attachment_type);
+        Err(AppError::Platform("Attachment viewer not supported on this platform".to_string()))
+    }
+}
+
+/// Open a file of the app file storage in the native viewer
+///
+/// # Arguments
+///
+/// * `app` - The app handle
+/// * `path` - Relative `/`-separated path in the app file storage
+///
+/// # Returns
+///
+/// Returns the detected `AttachmentType` once the viewer is presented, or an
+/// error if the file does not exist, its type is not recognized or
+/// no viewer is available for it.
+pub fn open(app: &AppHandle, path: &str) -> AppResult<AttachmentType> {
+    let target = file_storage::resolve(&file_storage::root(app)?, path)?;
+    if !target.is_file() {
+        return Err(AppError::Platform(format!("File not found: {}", path)));
+    }
+    open_file(&target)
+}
+
+/// Open a file in the native viewer, detecting its type
+///
+/// The file must be covered by the Android `FileProvider` paths.
+pub(crate) fn open_file(target: &Path) -> AppResult<AttachmentType> {
+    let extension = target.extension().and_then(|extension| extension.to_str());
+    let name = target.file_name().unwrap_or_default().to_string_lossy();
+    let attachment_type = sniff(&read_header(target)?, extension)
+        .ok_or_else(|| AppError::InvalidArgument(format!("Unsupported attachment type: {}", name)))?;
+
+    if !open_viewer(target, attachment_type)? {
+        return Err(AppError::Platform(format!(
+            "No app available to open {} ({})",
+            attachment_type.kind.description(),
+            attachment_type.mime_type
+        )));
+    }
+    tracing::info!("Opened attachment ({})", attachment_type.mime_type);
+    Ok(attachment_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_by_content() {
+        let pdf = sniff(b"%PDF-1.7\n%\xe2\xe3\xcf\xd3", Some("bin")).unwrap();
+        assert_eq!((pdf.kind, pdf.mime_type), (AttachmentKind::Pdf, "application/pdf"));
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR", Some("pdf")).unwrap().mime_type, "image/png");
+        assert_eq!(sniff(b"\xff\xd8\xff\xe0\0\x10JFIF", None).unwrap().mime_type, "image/jpeg");
+        assert_eq!(sniff(b"RIFF\x24\0\0\0WEBPVP8 ", None).unwrap().mime_type, "image/webp");
+        assert_eq!(sniff(b"\0\0\0\x18ftypheic\0\0\0\0", None).unwrap().mime_type, "image/heic");
+    }
+
+    #[test]
+    fn test_containers_use_the_extension() {
+        let docx = sniff(b"PK\x03\x04\x14\0\x06\0", Some("DOCX")).unwrap();
+        assert_eq!(docx.kind, AttachmentKind::Office);
+        assert!(docx.mime_type.contains("wordprocessingml"));
+        assert_eq!(sniff(b"PK\x03\x04\x14\0\x06\0", Some("zip")).unwrap().kind, AttachmentKind::Archive);
+        assert_eq!(
+            sniff(b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1\0\0", Some("xls")).unwrap().mime_type,
+            "application/vnd.ms-excel"
+        );
+        assert_eq!(sniff(b"\xd0\xcf\x11\xe0\xa1\xb1\x1a\xe1\0\0", Some("msg")), None);
+    }
+
+    #[test]
+    fn test_text_and_unknown_content() {
+        assert_eq!(sniff("Ordre du jour : séance".as_bytes(), Some("txt")).unwrap().kind, AttachmentKind::Text);
+        // Truncated in the middle of "é"
+        assert_eq!(sniff(&"séance".as_bytes()[..2], None).unwrap().kind, AttachmentKind::Text);
+        assert_eq!(sniff(b"\x7fELF\x02\x01\x01\0\0", None), None);
+        assert_eq!(sniff(b"", None), None);
+    }
+}
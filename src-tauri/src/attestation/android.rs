@@ -0,0 +1,41 @@
+/// Android-specific attestation implementation
+///
+/// This module provides Play Integrity support using `IntegrityManager`
+/// from the Google Play Integrity library.
+///
+/// Note: This implementation provides the structure for Play Integrity.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::{AttestationError, AttestationToken};
+
+/// Request a Play Integrity token for the given nonce
+///
+/// # Returns
+///
+/// Returns the integrity token, `AttestationError::Unavailable` if Google
+/// Play services are unavailable or the request fails (network, transient
+/// `IntegrityErrorCode`s), or `AttestationError::Rejected` if Play Integrity
+/// refuses to attest the app.
+pub fn attest(nonce: &str) -> Result<AttestationToken, AttestationError> {
+    tracing::info!("[Android] Requesting Play Integrity token");
+
+    // TODO: Implement native Play Integrity request using IntegrityManager
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val integrityManager = IntegrityManagerFactory.create(context)
+    // integrityManager.requestIntegrityToken(
+    //     IntegrityTokenRequest.builder().setNonce(nonce).build()
+    // ).addOnSuccessListener { response ->
+    //     // return response.token()
+    // }.addOnFailureListener { e ->
+    //     // NETWORK_ERROR, GOOGLE_SERVER_UNAVAILABLE, ...: Unavailable;
+    //     // APP_NOT_INSTALLED, APP_UID_MISMATCH, ...: Rejected
+    // }
+    // ```
+
+    // Placeholder: Report Play Integrity as unavailable
+    // Replace this with actual native implementation
+    let _ = nonce;
+    Err(AttestationError::Unavailable("Play Integrity is not available".to_string()))
+}
@@ -0,0 +1,42 @@
+/// iOS-specific attestation implementation
+///
+/// This module provides App Attest support using `DCAppAttestService`
+/// from the DeviceCheck framework.
+///
+/// Note: This implementation provides the structure for App Attest.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::{AttestationError, AttestationToken};
+
+/// Attest the app key for the given nonce using App Attest
+///
+/// # Returns
+///
+/// Returns the attestation object and key identifier,
+/// `AttestationError::Unavailable` if App Attest is unsupported or
+/// unreachable (`DCError.serverUnavailable`), or `AttestationError::Rejected`
+/// if it refuses to attest the key (`DCError.invalidKey`,
+/// `DCError.invalidInput`).
+pub fn attest(nonce: &str) -> Result<AttestationToken, AttestationError> {
+    tracing::info!("[iOS] Requesting App Attest attestation");
+
+    // TODO: Implement native App Attest using DCAppAttestService
+    // The key identifier should be persisted in the keychain and reused.
+    // Example Swift implementation:
+    // ```swift
+    // let service = DCAppAttestService.shared
+    // guard service.isSupported else { throw AttestError.unsupported }
+    // service.generateKey { keyId, error in
+    //     let clientDataHash = Data(SHA256.hash(data: Data(nonce.utf8)))
+    //     service.attestKey(keyId!, clientDataHash: clientDataHash) { attestation, error in
+    //         // return (attestation!.base64EncodedString(), keyId!)
+    //     }
+    // }
+    // ```
+
+    // Placeholder: Report App Attest as unavailable
+    // Replace this with actual native implementation
+    let _ = nonce;
+    Err(AttestationError::Unavailable("App Attest is not available".to_string()))
+}
@@ -0,0 +1,146 @@
+/// Device attestation (Play Integrity / App Attest)
+///
+/// This module asks the platform attestation service to vouch for the
+/// integrity of the app and device, and returns the resulting token so the
+/// backend can verify it and reject tampered clients.
+///
+/// - Android: Google Play Integrity API
+/// - iOS: `DCAppAttestService` (App Attest)
+///
+/// The token is opaque to the app: it must be verified server-side with the
+/// nonce that was issued by the backend. The findings of the binary integrity
+/// self-check are sent along with the token.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use crate::integrity::IntegrityIndicator;
+use crate::tamper;
+use crate::{AppError, AppResult};
+
+/// Minimum nonce length accepted by the Play Integrity API (characters)
+pub const MIN_NONCE_LENGTH: usize = 16;
+
+/// Maximum nonce length accepted by the Play Integrity API (characters)
+pub const MAX_NONCE_LENGTH: usize = 500;
+
+/// Attestation service that produced a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationProvider {
+    /// Google Play Integrity API
+    PlayIntegrity,
+    /// Apple App Attest
+    AppAttest,
+}
+
+/// Attestation token to be verified by the backend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationToken {
+    /// Service that produced the token
+    pub provider: AttestationProvider,
+    /// Opaque attestation token (base64)
+    pub token: String,
+    /// App Attest key identifier (iOS only)
+    pub key_id: Option<String>,
+    /// Indicators found by the binary integrity self-check, reported by the
+    /// app itself (filled in by `attest`)
+    pub self_check: Vec<IntegrityIndicator>,
+}
+
+/// Errors of an attestation request
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AttestationError {
+    /// The attestation service refused to vouch for the app or device
+    #[error("Attestation rejected: {0}")]
+    Rejected(String),
+
+    /// The request did not complete (invalid nonce, network or transport
+    /// error, service unavailable): says nothing about the device
+    #[error("{0}")]
+    Unavailable(String),
+}
+
+/// Validate a nonce issued by the backend
+///
+/// The nonce must be URL-safe base64 (`A-Z`, `a-z`, `0-9`, `-`, `_`, optional
+/// `=` padding) between `MIN_NONCE_LENGTH` and `MAX_NONCE_LENGTH` characters,
+/// which satisfies both Play Integrity and App Attest requirements.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the nonce is valid, or an error if invalid.
+pub fn validate_nonce(nonce: &str) -> AppResult<()> {
+    let len = nonce.len();
+    if !(MIN_NONCE_LENGTH..=MAX_NONCE_LENGTH).contains(&len) {
+        return Err(AppError::InvalidArgument(format!(
+            "Nonce length must be between {} and {} characters, got {}",
+            MIN_NONCE_LENGTH, MAX_NONCE_LENGTH, len
+        )));
+    }
+    let is_base64 = nonce
+        .trim_end_matches('=')
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !is_base64 {
+        return Err(AppError::InvalidArgument("Nonce must be URL-safe base64".to_string()));
+    }
+    Ok(())
+}
+
+/// Request an attestation token for the given nonce
+///
+/// # Arguments
+///
+/// * `nonce` - Single-use challenge issued by the backend
+///
+/// # Returns
+///
+/// Returns the `AttestationToken`, `AttestationError::Rejected` if the
+/// service refuses to attest the app or device, or
+/// `AttestationError::Unavailable` if the nonce is invalid or the service
+/// cannot be reached.
+pub fn attest(nonce: &str) -> Result<AttestationToken, AttestationError> {
+    validate_nonce(nonce).map_err(|e| AttestationError::Unavailable(e.to_string()))?;
+
+    #[cfg(target_os = "ios")]
+    let token = ios::attest(nonce);
+
+    #[cfg(target_os = "android")]
+    let token = android::attest(nonce);
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    let token: Result<AttestationToken, AttestationError> = Err(AttestationError::Unavailable(
+        "Device attestation not supported on this platform".to_string(),
+    ));
+
+    token.map(|token| AttestationToken {
+        self_check: tamper::findings().into_iter().map(|finding| finding.indicator).collect(),
+        ..token
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_nonce() {
+        assert!(validate_nonce("dGhpcyBpcyBhIG5vbmNl").is_ok());
+        assert!(validate_nonce("abc-def_ghi-jkl_mno==").is_ok());
+        assert!(validate_nonce("short").is_err(), "Nonce below minimum length should be invalid");
+        assert!(validate_nonce(&"a".repeat(MAX_NONCE_LENGTH + 1)).is_err());
+        assert!(validate_nonce("not base64 at all!!").is_err());
+    }
+
+    #[test]
+    fn test_attest_rejects_invalid_nonce() {
+        let error = attest("").unwrap_err();
+        assert!(matches!(error, AttestationError::Unavailable(_)), "Not a rejection of the device");
+        assert!(error.to_string().contains("Nonce length"));
+    }
+}
@@ -0,0 +1,70 @@
+/// Android-specific audio recording implementation
+///
+/// This module provides native Android audio recording using `MediaRecorder`
+/// from the Android SDK.
+///
+/// Note: This implementation provides the structure for Android audio recording.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use std::path::Path;
+use crate::AppResult;
+
+/// Start recording to the given `.m4a` file
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start_recording(path: &Path) -> AppResult<()> {
+    tracing::info!("[Android] Starting audio recording: {}", path.display());
+
+    // TODO: Implement native Android recording using MediaRecorder
+    // Example Kotlin implementation:
+    // ```kotlin
+    // recorder = MediaRecorder(context).apply {
+    //     setAudioSource(MediaRecorder.AudioSource.MIC)
+    //     setOutputFormat(MediaRecorder.OutputFormat.MPEG_4)
+    //     setAudioEncoder(MediaRecorder.AudioEncoder.AAC)
+    //     setOutputFile(path)
+    //     prepare()
+    //     start()
+    // }
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
+
+/// Stop the recording in progress and finalize the file
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn stop_recording() -> AppResult<()> {
+    tracing::info!("[Android] Stopping audio recording");
+
+    // TODO: Implement native Android recording stop
+    // Example Kotlin implementation:
+    // ```kotlin
+    // recorder.stop()
+    // recorder.release()
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
+
+/// Current normalized input level, from `0.0` to `1.0`
+pub fn current_level() -> f32 {
+    // TODO: Implement native Android metering
+    // Example Kotlin implementation:
+    // ```kotlin
+    // return recorder.maxAmplitude / 32767f
+    // ```
+
+    // Placeholder: Return silence
+    // Replace this with actual native implementation
+    0.0
+}
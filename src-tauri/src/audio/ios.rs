@@ -0,0 +1,75 @@
+/// iOS-specific audio recording implementation
+///
+/// This module provides native iOS audio recording using `AVAudioRecorder`
+/// from the AVFoundation framework.
+///
+/// Note: This implementation provides the structure for iOS audio recording.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use std::path::Path;
+use crate::AppResult;
+
+/// Start recording to the given `.m4a` file
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start_recording(path: &Path) -> AppResult<()> {
+    tracing::info!("[iOS] Starting audio recording: {}", path.display());
+
+    // TODO: Implement native iOS recording using AVAudioRecorder
+    // Example Swift implementation:
+    // ```swift
+    // try AVAudioSession.sharedInstance().setCategory(.playAndRecord, mode: .default)
+    // try AVAudioSession.sharedInstance().setActive(true)
+    // let settings: [String: Any] = [
+    //     AVFormatIDKey: Int(kAudioFormatMPEG4AAC),
+    //     AVSampleRateKey: 44100,
+    //     AVNumberOfChannelsKey: 1,
+    //     AVEncoderAudioQualityKey: AVAudioQuality.high.rawValue,
+    // ]
+    // recorder = try AVAudioRecorder(url: URL(fileURLWithPath: path), settings: settings)
+    // recorder.isMeteringEnabled = true
+    // recorder.record()
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
+
+/// Stop the recording in progress and finalize the file
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn stop_recording() -> AppResult<()> {
+    tracing::info!("[iOS] Stopping audio recording");
+
+    // TODO: Implement native iOS recording stop
+    // Example Swift implementation:
+    // ```swift
+    // recorder.stop()
+    // try AVAudioSession.sharedInstance().setActive(false)
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
+
+/// Current normalized input level, from `0.0` to `1.0`
+pub fn current_level() -> f32 {
+    // TODO: Implement native iOS metering
+    // Example Swift implementation:
+    // ```swift
+    // recorder.updateMeters()
+    // let db = recorder.averagePower(forChannel: 0) // -160...0 dB
+    // return pow(10, db / 20)
+    // ```
+
+    // Placeholder: Return silence
+    // Replace this with actual native implementation
+    0.0
+}
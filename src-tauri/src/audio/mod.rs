@@ -0,0 +1,226 @@
+/// Native audio recording
+///
+/// This module records audio notes natively (AAC in an `.m4a` container) so
+/// the web app can upload them once the recording is complete. While a
+/// recording is in progress, the input level is periodically emitted to the
+/// webview as an `audio-level` event.
+///
+/// Only one recording can be in progress at a time.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::events;
+use crate::permissions::{self, Permission};
+use crate::{AppError, AppResult};
+
+/// Metadata of a completed recording
+#[derive(Debug, Clone, PartialEq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingInfo {
+    /// Absolute path of the `.m4a` file
+    pub path: String,
+    /// Duration of the recording in milliseconds
+    pub duration_ms: u64,
+}
+
+/// Payload of the `audio-level` event
+#[derive(Debug, Clone, Copy, serde::Serialize, specta::Type)]
+pub struct AudioLevel {
+    /// Normalized input level, from `0.0` (silence) to `1.0` (peak)
+    pub level: f32,
+}
+
+/// A recording in progress
+struct RecordingSession {
+    path: PathBuf,
+    started_at: Instant,
+    level_task: tauri::async_runtime::JoinHandle<()>,
+}
+
+/// Audio recorder state, managed by the Tauri application
+#[derive(Default)]
+pub struct AudioRecorder {
+    session: Mutex<Option<RecordingSession>>,
+}
+
+impl AudioRecorder {
+    /// Start a new recording
+    ///
+    /// Requests the microphone permission if needed, then starts recording
+    /// to a new file in the app cache directory.
+    ///
+    /// # Returns
+    ///
+    /// Returns the path of the file being recorded, or an error if a
+    /// recording is already in progress or the recording cannot be started.
+    pub fn start(&self, app: &AppHandle) -> AppResult<PathBuf> {
+        let mut session = self.session.lock().map_err(|e| AppError::Platform(e.to_string()))?;
+        if session.is_some() {
+            return Err(AppError::Platform("A recording is already in progress".to_string()));
+        }
+
+        if !permissions::request(Permission::Microphone)?.is_granted() {
+            return Err(AppError::Platform("Microphone permission not granted".to_string()));
+        }
+
+        let dir = app
+            .path()
+            .app_cache_dir()
+            .map_err(|e| AppError::Platform(format!("Failed to resolve cache directory: {}", e)))?
+            .join(constants::AUDIO_RECORDING_DIR);
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| AppError::Platform(format!("Failed to create recordings directory: {}", e)))?;
+        let path = dir.join(recording_file_name(std::time::SystemTime::now()));
+
+        start_native(&path)?;
+        tracing::info!("Audio recording started: {}", path.display());
+
+        *session = Some(RecordingSession {
+            path: path.clone(),
+            started_at: Instant::now(),
+            level_task: spawn_level_events(app.clone()),
+        });
+        Ok(path)
+    }
+
+    /// Stop the recording in progress
+    ///
+    /// # Returns
+    ///
+    /// Returns the path and duration of the recorded file, or an error
+    /// message if no recording is in progress or the recording cannot be
+    /// finalized.
+    pub fn stop(&self) -> AppResult<RecordingInfo> {
+        let session = self
+            .session
+            .lock()
+            .map_err(|e| AppError::Platform(e.to_string()))?
+            .take()
+            .ok_or_else(|| AppError::Platform("No recording in progress".to_string()))?;
+
+        session.level_task.abort();
+        let duration = session.started_at.elapsed();
+        stop_native()?;
+
+        tracing::info!(
+            "Audio recording stopped: {} ({}ms)",
+            session.path.display(),
+            duration.as_millis()
+        );
+        Ok(RecordingInfo {
+            path: session.path.to_string_lossy().into_owned(),
+            duration_ms: duration.as_millis() as u64,
+        })
+    }
+
+    /// Returns `true` if a recording is in progress
+    pub fn is_recording(&self) -> bool {
+        self.session.lock().map(|s| s.is_some()).unwrap_or(false)
+    }
+}
+
+/// Build the file name of a new recording from its start time
+fn recording_file_name(now: std::time::SystemTime) -> String {
+    let millis = now
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    format!("recording_{}.{}", millis, constants::AUDIO_RECORDING_EXTENSION)
+}
+
+/// Periodically emit the input level to the webview until aborted
+fn spawn_level_events(app: AppHandle) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_millis(constants::AUDIO_LEVEL_INTERVAL_MS));
+        loop {
+            interval.tick().await;
+            let level = AudioLevel { level: current_level() };
+            events::emit(&app, &level);
+        }
+    })
+}
+
+/// Start the native recorder writing to `path`
+fn start_native(path: &Path) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::start_recording(path)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::start_recording(path)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = path;
+        Err(AppError::Platform("Audio recording not supported on this platform".to_string()))
+    }
+}
+
+/// Stop the native recorder and finalize the file
+fn stop_native() -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::stop_recording()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::stop_recording()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(())
+    }
+}
+
+/// Current normalized input level of the native recorder
+fn current_level() -> f32 {
+    #[cfg(target_os = "ios")]
+    {
+        ios::current_level()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::current_level()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_file_name() {
+        let time = std::time::UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        assert_eq!(recording_file_name(time), "recording_1700000000123.m4a");
+    }
+
+    #[test]
+    fn test_stop_without_recording() {
+        let recorder = AudioRecorder::default();
+        assert!(!recorder.is_recording());
+        assert!(recorder.stop().is_err(), "stop should fail when nothing is recording");
+    }
+}
@@ -0,0 +1,439 @@
+/// Tamper-evident security audit log module
+///
+/// Security-relevant events are appended to a local log for incident
+/// response:
+/// - authentication attempts (native login, passkeys, session unlock)
+/// - runtime permission changes
+/// - binary integrity self-check failures
+/// - webview TLS pin failures
+///
+/// Each record is hash-chained to the previous one: its `hash` is the SHA-256
+/// of its own fields, including the `prevHash` of the previous record (zeros
+/// for the first one). Editing or removing a record breaks the chain. The
+/// sequence number and hash of the last record are also anchored in the
+/// keychain, so truncating the end of the log is detected as well.
+///
+/// The log is stored as JSON lines (`AUDIT_LOG_FILE`) in the app data
+/// directory and exported, with its verification result, by the
+/// `export_security_log` command.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_keystore::{RetrieveRequest, StoreRequest};
+
+use crate::constants::{self, helpers};
+use crate::integrity::IntegrityIndicator;
+use crate::permissions::{Permission, PermissionStatus};
+use crate::plugins::{self, KeystoreExt};
+use crate::{AppError, AppResult};
+
+/// Authentication method of an attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMethod {
+    /// Native OAuth/OIDC login
+    Oidc,
+    /// Passkey assertion
+    Passkey,
+    /// Biometric unlock of a locked session
+    SessionUnlock,
+}
+
+/// Security event
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SecurityEvent {
+    /// Authentication attempt
+    AuthAttempt {
+        /// Authentication method
+        method: AuthMethod,
+        /// Whether the attempt succeeded
+        success: bool,
+    },
+    /// Runtime permission status change
+    PermissionChange {
+        /// Permission
+        permission: Permission,
+        /// New status
+        status: PermissionStatus,
+    },
+    /// Binary integrity self-check failure
+    IntegrityFailure {
+        /// Indicators found
+        indicators: Vec<IntegrityIndicator>,
+    },
+    /// Webview TLS pin failure
+    PinFailure {
+        /// Host presenting the unpinned certificate
+        host: String,
+    },
+}
+
+/// Hash of the previous record of the first record
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// A record of the log
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    /// Sequence number, starting at 0
+    pub seq: u64,
+    /// Time of the event (seconds since the Unix epoch)
+    pub timestamp: u64,
+    /// Event
+    pub event: SecurityEvent,
+    /// Hash of the previous record (hex)
+    pub prev_hash: String,
+    /// Hash of this record (hex)
+    pub hash: String,
+}
+
+/// Fields covered by the hash of a record
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HashedFields<'a> {
+    seq: u64,
+    timestamp: u64,
+    event: &'a SecurityEvent,
+    prev_hash: &'a str,
+}
+
+/// Sequence number and hash of the last record
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainHead {
+    /// Sequence number of the last record
+    pub seq: u64,
+    /// Hash of the last record (hex)
+    pub hash: String,
+}
+
+/// Exported log
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditExport {
+    /// Records, oldest first
+    pub records: Vec<AuditRecord>,
+    /// Head anchored in the keychain, if any
+    pub anchor: Option<ChainHead>,
+    /// Whether the chain and the anchor are intact
+    pub verified: bool,
+    /// Why verification failed
+    pub error: Option<String>,
+}
+
+fn record_hash(seq: u64, timestamp: u64, event: &SecurityEvent, prev_hash: &str) -> String {
+    let fields = HashedFields {
+        seq,
+        timestamp,
+        event,
+        prev_hash,
+    };
+    let bytes = serde_json::to_vec(&fields).unwrap_or_default();
+    ring::digest::digest(&ring::digest::SHA256, &bytes)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+impl AuditRecord {
+    /// Create the record following `head`
+    pub fn next(head: Option<&ChainHead>, timestamp: u64, event: SecurityEvent) -> Self {
+        let (seq, prev_hash) = match head {
+            Some(head) => (head.seq + 1, head.hash.clone()),
+            None => (0, GENESIS_HASH.to_string()),
+        };
+        let hash = record_hash(seq, timestamp, &event, &prev_hash);
+        Self {
+            seq,
+            timestamp,
+            event,
+            prev_hash,
+            hash,
+        }
+    }
+
+    /// Head of the chain ending with this record
+    pub fn head(&self) -> ChainHead {
+        ChainHead {
+            seq: self.seq,
+            hash: self.hash.clone(),
+        }
+    }
+}
+
+/// Verify a log
+///
+/// # Arguments
+///
+/// * `records` - Records, oldest first
+/// * `anchor` - Head anchored outside of the log, if any
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the chain is intact and ends at the anchor, or an error
+/// message naming the first inconsistency.
+pub fn verify(records: &[AuditRecord], anchor: Option<&ChainHead>) -> AppResult<()> {
+    let mut head: Option<ChainHead> = None;
+    for record in records {
+        let expected = AuditRecord::next(head.as_ref(), record.timestamp, record.event.clone());
+        if record.seq != expected.seq {
+            return Err(AppError::Storage(format!("Record {} found where record {} was expected", record.seq, expected.seq)));
+        }
+        if record.prev_hash != expected.prev_hash {
+            return Err(AppError::Storage(format!("Record {} is not chained to the previous record", record.seq)));
+        }
+        if record.hash != expected.hash {
+            return Err(AppError::Storage(format!("Record {} was modified", record.seq)));
+        }
+        head = Some(record.head());
+    }
+    match (anchor, head.as_ref()) {
+        (Some(anchor), Some(head)) if anchor == head => Ok(()),
+        (Some(anchor), _) => Err(AppError::Storage(format!("Log was truncated: it should end at record {}", anchor.seq))),
+        (None, _) => Ok(()),
+    }
+}
+
+/// Parse the log file contents
+fn parse(contents: &str) -> AppResult<Vec<AuditRecord>> {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| AppError::Storage(format!("Malformed log line {}: {}", index + 1, e)))
+        })
+        .collect()
+}
+
+fn read_records(path: &Path) -> AppResult<Vec<AuditRecord>> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => parse(&contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(AppError::Storage(format!("Failed to read security log: {}", e))),
+    }
+}
+
+fn append_record(path: &Path, record: &AuditRecord) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| AppError::Storage(format!("Failed to create security log directory: {}", e)))?;
+    }
+    let mut line = serde_json::to_string(record).map_err(|e| AppError::Storage(format!("Failed to serialize security event: {}", e)))?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| AppError::Storage(format!("Failed to write security log: {}", e)))
+}
+
+fn log_path(app: &AppHandle) -> AppResult<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(constants::AUDIT_LOG_FILE))
+        .map_err(|e| AppError::Storage(format!("Failed to resolve app data directory: {}", e)))
+}
+
+/// Read the head anchored in the keychain
+fn read_anchor(app: &AppHandle) -> AppResult<Option<ChainHead>> {
+    plugins::ensure_keystore(app)?;
+    let value = app
+        .keystore()
+        .retrieve(RetrieveRequest {
+            service: constants::AUDIT_LOG_ANCHOR_KEY.to_string(),
+            user: constants::AUDIT_LOG_ANCHOR_KEY.to_string(),
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_retrieve_error(&e)))?
+        .value
+        .filter(|value| !value.is_empty());
+    value
+        .map(|value| serde_json::from_str(&value).map_err(|e| AppError::Storage(format!("Invalid security log anchor: {}", e))))
+        .transpose()
+}
+
+/// Anchor the head in the keychain
+fn write_anchor(app: &AppHandle, head: &ChainHead) -> AppResult<()> {
+    let value = serde_json::to_string(head)?;
+    plugins::ensure_keystore(app)?;
+    app.keystore()
+        .store(StoreRequest {
+            value: helpers::key_value_pair(constants::AUDIT_LOG_ANCHOR_KEY, &value),
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_store_error(&e)))
+}
+
+/// Audit log state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct AuditLog {
+    /// Head of the chain, loaded from the log and the anchor on first use
+    head: Mutex<Option<ChainHead>>,
+}
+
+/// Pick the head to chain the next record from
+///
+/// When the log was truncated, the anchor is ahead of the last logged record:
+/// chaining from the anchor keeps the sequence going instead of restarting it
+/// and overwriting the anchor, so the truncation stays detectable.
+fn initial_head(logged: Option<ChainHead>, anchored: Option<ChainHead>) -> Option<ChainHead> {
+    match (logged, anchored) {
+        (Some(logged), Some(anchored)) if anchored.seq > logged.seq => Some(anchored),
+        (Some(logged), _) => Some(logged),
+        (None, anchored) => anchored,
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Append a security event to the log
+///
+/// Failures are logged: recording an event never fails the flow reporting it.
+pub fn record(app: &AppHandle, event: SecurityEvent) {
+    if let Err(e) = try_record(app, event) {
+        tracing::error!("Failed to record security event: {}", e);
+    }
+}
+
+fn try_record(app: &AppHandle, event: SecurityEvent) -> AppResult<()> {
+    let path = log_path(app)?;
+    let state = app.state::<AuditLog>();
+    let mut head = state.head.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+    if head.is_none() {
+        let logged = read_records(&path)?.last().map(AuditRecord::head);
+        *head = initial_head(logged, read_anchor(app)?);
+    }
+
+    let record = AuditRecord::next(head.as_ref(), now_secs(), event);
+    append_record(&path, &record)?;
+    *head = Some(record.head());
+    tracing::info!("Security event recorded (#{})", record.seq);
+    write_anchor(app, &record.head())
+}
+
+/// Export the log with its verification result
+///
+/// # Returns
+///
+/// Returns the records and whether the chain is intact, or an error
+/// if the log cannot be read.
+pub fn export(app: &AppHandle) -> AppResult<AuditExport> {
+    let path = log_path(app)?;
+    // Hold the lock so no record is appended while the log is read
+    let state = app.state::<AuditLog>();
+    let _head = state.head.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+    let records = read_records(&path)?;
+    let anchor = read_anchor(app)?;
+    let error = verify(&records, anchor.as_ref()).err().map(|e| e.to_string());
+    if let Some(e) = &error {
+        tracing::warn!("Security log verification failed: {}", e);
+    }
+    Ok(AuditExport {
+        records,
+        anchor,
+        verified: error.is_none(),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(events: Vec<SecurityEvent>) -> Vec<AuditRecord> {
+        let mut records: Vec<AuditRecord> = Vec::new();
+        for (index, event) in events.into_iter().enumerate() {
+            let head = records.last().map(AuditRecord::head);
+            records.push(AuditRecord::next(head.as_ref(), 1_700_000_000 + index as u64, event));
+        }
+        records
+    }
+
+    fn events() -> Vec<SecurityEvent> {
+        vec![
+            SecurityEvent::AuthAttempt {
+                method: AuthMethod::Oidc,
+                success: false,
+            },
+            SecurityEvent::PermissionChange {
+                permission: Permission::Camera,
+                status: PermissionStatus::Granted,
+            },
+            SecurityEvent::PinFailure {
+                host: "app.elulib.com".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_intact_chain() {
+        let records = chain(events());
+        assert_eq!(records[0].prev_hash, GENESIS_HASH);
+        assert_eq!(records[1].prev_hash, records[0].hash);
+        assert_eq!(verify(&records, Some(&records[2].head())).unwrap(), ());
+        assert_eq!(verify(&[], None).unwrap(), ());
+    }
+
+    #[test]
+    fn test_edited_record_is_detected() {
+        let mut records = chain(events());
+        records[1].event = SecurityEvent::PermissionChange {
+            permission: Permission::Camera,
+            status: PermissionStatus::Denied,
+        };
+        assert_eq!(verify(&records, None).unwrap_err().to_string(), "Storage error: Record 1 was modified");
+    }
+
+    #[test]
+    fn test_removed_records_are_detected() {
+        let records = chain(events());
+        let anchor = records[2].head();
+
+        let without_middle = vec![records[0].clone(), records[2].clone()];
+        assert!(verify(&without_middle, Some(&anchor)).is_err());
+
+        let truncated = &records[..2];
+        assert_eq!(verify(truncated, None).unwrap(), (), "Truncation needs the anchor");
+        assert!(verify(truncated, Some(&anchor)).unwrap_err().to_string().contains("truncated"));
+    }
+
+    #[test]
+    fn test_truncated_log_chains_from_the_anchor() {
+        let records = chain(events());
+        let anchor = records[2].head();
+        let truncated = records[..1].to_vec();
+
+        let head = initial_head(truncated.last().map(AuditRecord::head), Some(anchor.clone()));
+        assert_eq!(head, Some(anchor.clone()));
+        let next = AuditRecord::next(head.as_ref(), 1_700_000_100, events().remove(0));
+        assert_eq!(next.seq, anchor.seq + 1, "The sequence must not restart");
+
+        let mut appended = truncated;
+        appended.push(next.clone());
+        assert!(verify(&appended, Some(&next.head())).is_err(), "Truncation stays detectable");
+
+        assert_eq!(initial_head(Some(anchor.clone()), None), Some(anchor.clone()));
+        assert_eq!(initial_head(None, None), None);
+    }
+
+    #[test]
+    fn test_log_file_round_trip() {
+        let dir = std::env::temp_dir().join(format!("elulib-audit-{}", std::process::id()));
+        let path = dir.join(constants::AUDIT_LOG_FILE);
+        let records = chain(events());
+        for record in &records {
+            append_record(&path, record).unwrap();
+        }
+        assert_eq!(read_records(&path).unwrap(), records);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,40 @@
+/// Android-specific browser session for native login
+///
+/// This module opens the authorization page in a Chrome Custom Tab. The
+/// redirect comes back to the activity as an intent and is delivered through
+/// the deep-link bridge (`deep_link::on_open_url`), which hands it to
+/// `auth::on_redirect`.
+///
+/// Note: This implementation provides the structure for the Android login session.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use crate::{AppError, AppResult};
+
+/// Open the authorization page
+///
+/// # Arguments
+///
+/// * `url` - Authorization request URL
+/// * `redirect_uri` - Redirect URI, declared in the activity intent filters
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the tab is opened, or an error if it cannot
+/// be opened.
+pub fn open_session(url: &str, redirect_uri: &str) -> AppResult<()> {
+    tracing::info!("[Android] Opening login session");
+
+    // TODO: Implement native Android Custom Tab
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val intent = CustomTabsIntent.Builder().setShareState(CustomTabsIntent.SHARE_STATE_OFF).build()
+    // intent.launchUrl(activity, Uri.parse(url))
+    // // The redirect (redirectUri) reaches onNewIntent and is forwarded like any deep link
+    // ```
+
+    // Placeholder: Report the session as unavailable
+    // Replace this with actual native implementation
+    let _ = (url, redirect_uri);
+    Err(AppError::Auth("Login session is not available".to_string()))
+}
@@ -0,0 +1,763 @@
+/// Native OAuth 2.0 / OpenID Connect login
+///
+/// This module runs the authorization code flow with PKCE natively, so tokens
+/// never transit through the webview:
+/// - the authorization page is opened in `ASWebAuthenticationSession` (iOS)
+///   or a Chrome Custom Tab (Android)
+/// - the redirect to `redirect_uri` is captured by the deep-link subsystem
+///   (`on_redirect`, called before deep-link routing)
+/// - the code is exchanged at the token endpoint by the native HTTP client
+/// - tokens are stored in the keychain under `auth.session.<handle>`, a key
+///   the frontend cannot read (see `session_handoff::is_retrievable`); the
+///   handles are listed under `AUTH_SESSION_INDEX_KEY`, so the local data
+///   wipe can remove every session (`session_keys`)
+///
+/// The frontend only receives an opaque session handle. Provider-specific
+/// requirements (FranceConnect, ...) are applied by the profiles of the
+/// `identity_provider` module.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use base64::Engine;
+use ring::rand::SecureRandom;
+use tauri::{AppHandle, Manager, Url};
+use tauri_plugin_keystore::{RemoveRequest, RetrieveRequest, StoreRequest};
+use tokio::sync::oneshot;
+
+use crate::breadcrumbs;
+use crate::constants::{self, helpers};
+use crate::deep_link::{self, DeepLinkSource};
+use crate::http;
+use crate::identity_provider::{self, EidasLevel, IdentityProfile};
+use crate::outbox;
+use crate::plugins::{self, KeystoreExt};
+use crate::redact;
+use crate::session_handoff;
+use crate::sync;
+#[cfg(feature = "module-sse")]
+use crate::sse;
+#[cfg(feature = "module-watch")]
+use crate::watch;
+#[cfg(feature = "module-realtime")]
+use crate::websocket;
+use crate::{AppError, AppResult};
+
+/// Identity provider configuration, provided by the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderConfig {
+    /// Authorization endpoint (https)
+    pub authorization_endpoint: String,
+    /// Token endpoint (https)
+    pub token_endpoint: String,
+    /// OAuth client identifier (public client, no secret)
+    pub client_id: String,
+    /// Redirect URI, an `elulib://` link or an app link
+    pub redirect_uri: String,
+    /// Requested scopes
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Additional authorization request parameters
+    #[serde(default)]
+    pub extra_params: BTreeMap<String, String>,
+    /// Identity provider profile (`oidc` when not set, `franceconnect`)
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// End-session (logout) endpoint (https)
+    #[serde(default)]
+    pub end_session_endpoint: Option<String>,
+    /// Redirect URI after logout, an `elulib://` link or an app link
+    #[serde(default)]
+    pub post_logout_redirect_uri: Option<String>,
+}
+
+impl ProviderConfig {
+    /// Validate the configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns the identity provider profile if the endpoints use https and
+    /// are allowed by the profile, and the redirect URIs can be captured by
+    /// the app, or an error.
+    pub fn validate(&self) -> AppResult<&'static IdentityProfile> {
+        let profile = identity_provider::profile(self.profile.as_deref())?;
+        let endpoints = [
+            ("authorization endpoint", Some(&self.authorization_endpoint)),
+            ("token endpoint", Some(&self.token_endpoint)),
+            ("end-session endpoint", self.end_session_endpoint.as_ref()),
+        ];
+        for (name, endpoint) in endpoints {
+            let Some(endpoint) = endpoint else {
+                continue;
+            };
+            let url = Url::parse(endpoint).map_err(|e| AppError::Config(format!("Invalid {}: {}", name, e)))?;
+            if url.scheme() != "https" {
+                return Err(AppError::InvalidArgument(format!("The {} must use https", name)));
+            }
+            profile.check_endpoint(endpoint)?;
+        }
+        if self.client_id.trim().is_empty() {
+            return Err(AppError::InvalidArgument("Client identifier must not be empty".to_string()));
+        }
+        for redirect_uri in [Some(&self.redirect_uri), self.post_logout_redirect_uri.as_ref()]
+            .into_iter()
+            .flatten()
+        {
+            deep_link::parse(redirect_uri, DeepLinkSource::Link)
+                .map_err(|_| AppError::Auth(format!("Redirect URI cannot be captured by the app: {}", redirect_uri)))?;
+        }
+        if profile.requires_end_session && self.end_session_endpoint.is_none() {
+            return Err(AppError::Auth(format!("The {} profile requires an end-session endpoint", profile.name)));
+        }
+        if self.end_session_endpoint.is_some() && self.post_logout_redirect_uri.is_none() {
+            return Err(AppError::Auth("An end-session endpoint requires a post-logout redirect URI".to_string()));
+        }
+        Ok(profile)
+    }
+}
+
+/// PKCE code verifier and its S256 challenge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pkce {
+    /// Code verifier, sent with the token request
+    pub verifier: String,
+    /// Code challenge, sent with the authorization request
+    pub challenge: String,
+}
+
+impl Pkce {
+    /// PKCE pair of a code verifier
+    pub fn from_verifier(verifier: String) -> Self {
+        let digest = ring::digest::digest(&ring::digest::SHA256, verifier.as_bytes());
+        Pkce {
+            challenge: base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest),
+            verifier,
+        }
+    }
+
+    /// Generate a random PKCE pair
+    pub fn generate() -> AppResult<Self> {
+        Ok(Self::from_verifier(random_token()?))
+    }
+}
+
+/// Random URL-safe token (256 bits)
+fn random_token() -> AppResult<String> {
+    let mut bytes = [0u8; 32];
+    ring::rand::SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| AppError::Auth("Failed to generate random value".to_string()))?;
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Build the authorization request URL
+///
+/// # Arguments
+///
+/// * `config` - Provider configuration
+/// * `profile` - Identity provider profile, adding its required scopes and parameters
+/// * `pkce` - PKCE pair of the request
+/// * `state` - Request state
+/// * `nonce` - OpenID Connect nonce, if the profile requires it
+pub fn authorization_url(
+    config: &ProviderConfig,
+    profile: &IdentityProfile,
+    pkce: &Pkce,
+    state: &str,
+    nonce: Option<&str>,
+) -> AppResult<Url> {
+    let mut url = Url::parse(&config.authorization_endpoint).map_err(|e| AppError::Auth(e.to_string()))?;
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &config.client_id)
+            .append_pair("redirect_uri", &config.redirect_uri)
+            .append_pair("code_challenge", &pkce.challenge)
+            .append_pair("code_challenge_method", "S256")
+            .append_pair("state", state);
+        let scopes = profile.scopes(&config.scopes);
+        if !scopes.is_empty() {
+            query.append_pair("scope", &scopes.join(" "));
+        }
+        if let Some(nonce) = nonce {
+            query.append_pair("nonce", nonce);
+        }
+        for (name, value) in &profile.params(&config.extra_params) {
+            query.append_pair(name, value);
+        }
+    }
+    Ok(url)
+}
+
+/// Build the end-session (logout) request URL
+pub fn end_session_url(logout: &LogoutConfig, id_token: Option<&str>, state: &str) -> AppResult<Url> {
+    let mut url = Url::parse(&logout.end_session_endpoint).map_err(|e| AppError::Auth(e.to_string()))?;
+    {
+        let mut query = url.query_pairs_mut();
+        if let Some(id_token) = id_token {
+            query.append_pair("id_token_hint", id_token);
+        }
+        query
+            .append_pair("post_logout_redirect_uri", &logout.post_logout_redirect_uri)
+            .append_pair("state", state);
+    }
+    Ok(url)
+}
+
+/// Extract the authorization code from the captured redirect
+///
+/// # Returns
+///
+/// Returns the code, or an error if the provider returned an error
+/// or the state does not match the request.
+pub fn parse_redirect(redirect: &str, expected_state: &str) -> AppResult<String> {
+    let url = Url::parse(redirect).map_err(|e| AppError::InvalidArgument(format!("Invalid redirect: {}", e)))?;
+    let params: BTreeMap<String, String> = url.query_pairs().into_owned().collect();
+
+    if let Some(error) = params.get("error") {
+        return Err(AppError::Auth(match params.get("error_description") {
+            Some(description) => format!("Login failed: {} ({})", error, description),
+            None => format!("Login failed: {}", error),
+        }));
+    }
+    if params.get("state").map(String::as_str) != Some(expected_state) {
+        return Err(AppError::Auth("Login failed: state mismatch".to_string()));
+    }
+    params
+        .get("code")
+        .cloned()
+        .ok_or_else(|| AppError::Auth("Login failed: no authorization code".to_string()))
+}
+
+/// Token endpoint response
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct TokenResponse {
+    /// Access token
+    pub access_token: String,
+    /// Token type (`Bearer`)
+    pub token_type: String,
+    /// Lifetime of the access token (seconds)
+    pub expires_in: Option<u64>,
+    /// Refresh token, if issued
+    pub refresh_token: Option<String>,
+    /// OpenID Connect ID token, if issued
+    pub id_token: Option<String>,
+}
+
+/// Tokens stored in the keychain for a session
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredTokens {
+    /// Access token
+    pub access_token: String,
+    /// Token type (`Bearer`)
+    pub token_type: String,
+    /// Refresh token, if issued
+    pub refresh_token: Option<String>,
+    /// OpenID Connect ID token, if issued
+    pub id_token: Option<String>,
+    /// Expiry of the access token (seconds since Unix epoch)
+    pub expires_at: Option<u64>,
+    /// Provider logout, if the session must be ended at the provider
+    #[serde(default)]
+    pub logout: Option<LogoutConfig>,
+}
+
+/// Provider logout of a session
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogoutConfig {
+    /// End-session endpoint
+    pub end_session_endpoint: String,
+    /// Redirect URI after logout
+    pub post_logout_redirect_uri: String,
+}
+
+impl StoredTokens {
+    /// Tokens to store for a token response received at `now`
+    pub fn from_response(response: TokenResponse, now: u64) -> Self {
+        StoredTokens {
+            expires_at: response.expires_in.map(|secs| now + secs),
+            access_token: response.access_token,
+            token_type: response.token_type,
+            refresh_token: response.refresh_token,
+            id_token: response.id_token,
+            logout: None,
+        }
+    }
+}
+
+/// Session returned to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthSession {
+    /// Opaque session handle
+    pub handle: String,
+    /// Expiry of the access token (seconds since Unix epoch)
+    pub expires_at: Option<u64>,
+    /// Identity provider profile
+    pub profile: String,
+    /// eIDAS level of the authentication, for profiles reporting it
+    pub eidas_level: Option<EidasLevel>,
+}
+
+/// Keychain key of the tokens of a session
+pub fn tokens_key(handle: &str) -> String {
+    format!("{}{}", constants::AUTH_TOKENS_KEY_PREFIX, handle)
+}
+
+/// Login waiting for its redirect
+#[derive(Debug)]
+struct PendingLogin {
+    redirect_uri: Url,
+    sender: oneshot::Sender<String>,
+}
+
+/// Login state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct AuthState {
+    pending: Mutex<Option<PendingLogin>>,
+}
+
+/// Returns `true` if `url` is a redirect to `redirect_uri`
+fn is_redirect_to(url: &Url, redirect_uri: &Url) -> bool {
+    url.scheme() == redirect_uri.scheme()
+        && url.host_str() == redirect_uri.host_str()
+        && url.path() == redirect_uri.path()
+}
+
+impl AuthState {
+    /// Start waiting for a redirect, cancelling any previous login
+    fn begin(&self, redirect_uri: &str) -> AppResult<oneshot::Receiver<String>> {
+        let redirect_uri = Url::parse(redirect_uri).map_err(|e| AppError::Auth(e.to_string()))?;
+        let (sender, receiver) = oneshot::channel();
+        *self.pending.lock().map_err(|e| AppError::Auth(e.to_string()))? = Some(PendingLogin { redirect_uri, sender });
+        Ok(receiver)
+    }
+
+    /// Stop waiting for a redirect
+    fn cancel(&self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            *pending = None;
+        }
+    }
+
+    /// Deliver a redirect to the pending login
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the URL was the redirect of the pending login.
+    pub fn deliver(&self, url: &str) -> bool {
+        let Ok(parsed) = Url::parse(url) else {
+            return false;
+        };
+        let Ok(mut pending) = self.pending.lock() else {
+            return false;
+        };
+        match pending.take() {
+            Some(login) if is_redirect_to(&parsed, &login.redirect_uri) => {
+                let _ = login.sender.send(url.to_string());
+                true
+            }
+            other => {
+                *pending = other;
+                false
+            }
+        }
+    }
+}
+
+/// Capture an authorization redirect received by the native layer
+///
+/// Called by the deep-link subsystem before routing a link, and by the
+/// native browser session on completion.
+///
+/// # Returns
+///
+/// Returns `true` if the URL was consumed by a pending login.
+pub fn on_redirect(app: &AppHandle, url: &str) -> bool {
+    let consumed = app.state::<AuthState>().deliver(url);
+    if consumed {
+        tracing::info!("Login redirect captured");
+    }
+    consumed
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Exchange the authorization code at the token endpoint
+async fn exchange(config: &ProviderConfig, code: &str, pkce: &Pkce) -> AppResult<TokenResponse> {
+    http::client()?
+        .post(&config.token_endpoint)
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", config.redirect_uri.as_str()),
+            ("client_id", config.client_id.as_str()),
+            ("code_verifier", pkce.verifier.as_str()),
+        ])
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| AppError::Auth(format!("Token request failed: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| AppError::Auth(format!("Invalid token response: {}", e)))
+}
+
+/// Store the tokens of a new session in the keychain
+fn store_tokens(app: &AppHandle, handle: &str, tokens: &StoredTokens) -> AppResult<()> {
+    let value = serde_json::to_string(tokens)?;
+    helpers::validate_keychain_value(&value)?;
+    plugins::ensure_keystore(app)?;
+    app.keystore()
+        .store(StoreRequest {
+            value: helpers::key_value_pair(&tokens_key(handle), &value),
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_store_error(&e)))
+}
+
+/// Handles of the stored sessions
+fn session_handles(app: &AppHandle) -> AppResult<Vec<String>> {
+    session_handoff::keychain_secret(app, constants::AUTH_SESSION_INDEX_KEY)?
+        .map(|value| serde_json::from_str(&value).map_err(|e| AppError::Auth(format!("Invalid session index: {}", e))))
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+/// Add or remove a handle from the session index
+fn update_session_index(app: &AppHandle, handle: &str, present: bool) -> AppResult<()> {
+    let mut handles = session_handles(app)?;
+    handles.retain(|stored| stored != handle);
+    if present {
+        handles.push(handle.to_string());
+    }
+    let key = constants::AUTH_SESSION_INDEX_KEY;
+    if handles.is_empty() {
+        return app
+            .keystore()
+            .remove(RemoveRequest {
+                service: key.to_string(),
+                user: key.to_string(),
+            })
+            .map_err(|e| AppError::Keychain(helpers::keychain_remove_error(&e)));
+    }
+    let value = serde_json::to_string(&handles)?;
+    app.keystore()
+        .store(StoreRequest {
+            value: helpers::key_value_pair(key, &value),
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_store_error(&e)))
+}
+
+/// Keychain keys of every stored session, index included (local data wipe)
+pub fn session_keys(app: &AppHandle) -> AppResult<Vec<String>> {
+    Ok(session_handles(app)?
+        .iter()
+        .map(|handle| tokens_key(handle))
+        .chain(std::iter::once(constants::AUTH_SESSION_INDEX_KEY.to_string()))
+        .collect())
+}
+
+/// Read the tokens of a session from the keychain
+fn retrieve_tokens(app: &AppHandle, handle: &str) -> AppResult<Option<StoredTokens>> {
+    plugins::ensure_keystore(app)?;
+    let key = tokens_key(handle);
+    let value = app
+        .keystore()
+        .retrieve(RetrieveRequest {
+            service: key.clone(),
+            user: key,
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_retrieve_error(&e)))?
+        .value
+        .filter(|value| !value.is_empty());
+    value
+        .map(|value| serde_json::from_str(&value).map_err(|e| AppError::Auth(format!("Invalid stored session: {}", e))))
+        .transpose()
+}
+
+/// Open a page in the browser session and wait for its redirect
+async fn browser_redirect(app: &AppHandle, url: &Url, redirect_uri: &str) -> AppResult<String> {
+    let auth = app.state::<AuthState>();
+    let receiver = auth.begin(redirect_uri)?;
+    if let Err(e) = open_session(url.as_str(), redirect_uri) {
+        auth.cancel();
+        return Err(e);
+    }
+
+    let timeout = Duration::from_secs(constants::AUTH_LOGIN_TIMEOUT_SECS);
+    match tokio::time::timeout(timeout, receiver).await {
+        Ok(Ok(redirect)) => Ok(redirect),
+        Ok(Err(_)) => Err(AppError::Auth("Browser session cancelled".to_string())),
+        Err(_) => {
+            auth.cancel();
+            Err(AppError::Auth("Browser session timed out".to_string()))
+        }
+    }
+}
+
+/// Run the login flow
+///
+/// # Returns
+///
+/// Returns the new session, or an error if the user cancelled, the
+/// login timed out (`AUTH_LOGIN_TIMEOUT_SECS`) or the provider rejected it.
+pub async fn login(app: &AppHandle, config: &ProviderConfig) -> AppResult<AuthSession> {
+    let profile = config.validate()?;
+    let pkce = Pkce::generate()?;
+    let state = random_token()?;
+    let nonce = if profile.requires_nonce { Some(random_token()?) } else { None };
+    let url = authorization_url(config, profile, &pkce, &state, nonce.as_deref())?;
+
+    let redirect = browser_redirect(app, &url, &config.redirect_uri).await?;
+    let code = parse_redirect(&redirect, &state)?;
+    let mut tokens = StoredTokens::from_response(exchange(config, &code, &pkce).await?, now_secs());
+
+    let claims = tokens
+        .id_token
+        .as_deref()
+        .map(identity_provider::id_token_claims)
+        .transpose()?;
+    if let (Some(claims), Some(nonce)) = (&claims, &nonce) {
+        identity_provider::check_nonce(claims, nonce)?;
+    }
+    if profile.requires_end_session && claims.is_none() {
+        return Err(AppError::Auth(format!("Login failed: {} did not return an ID token", profile.name)));
+    }
+    let eidas_level = claims
+        .as_ref()
+        .filter(|_| profile.reports_eidas_level)
+        .and_then(identity_provider::eidas_level);
+    tokens.logout = config
+        .end_session_endpoint
+        .clone()
+        .zip(config.post_logout_redirect_uri.clone())
+        .map(|(end_session_endpoint, post_logout_redirect_uri)| LogoutConfig {
+            end_session_endpoint,
+            post_logout_redirect_uri,
+        });
+
+    for secret in [Some(&tokens.access_token), tokens.refresh_token.as_ref(), tokens.id_token.as_ref()]
+        .into_iter()
+        .flatten()
+    {
+        redact::register_secret(secret);
+    }
+
+    let handle = random_token()?;
+    store_tokens(app, &handle, &tokens)?;
+    update_session_index(app, &handle, true)?;
+    tracing::info!("Login succeeded ({}, eIDAS level: {:?})", profile.name, eidas_level);
+    breadcrumbs::breadcrumb("auth", &format!("login ({})", profile.name));
+    Ok(AuthSession {
+        handle,
+        expires_at: tokens.expires_at,
+        profile: profile.name.to_string(),
+        eidas_level,
+    })
+}
+
+/// End a session
+///
+/// The tokens are removed from the keychain, then the session is ended at the
+/// provider if it has an end-session endpoint. A failed provider logout is
+/// logged but does not fail the local logout.
+pub async fn logout(app: &AppHandle, handle: &str) -> AppResult<()> {
+    let tokens = retrieve_tokens(app, handle)?;
+    let key = tokens_key(handle);
+    app.keystore()
+        .remove(RemoveRequest {
+            service: key.clone(),
+            user: key,
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_remove_error(&e)))?;
+    if let Err(e) = update_session_index(app, handle, false) {
+        tracing::warn!("Failed to update the session index: {}", e);
+    }
+    tracing::info!("Session removed");
+    breadcrumbs::breadcrumb("auth", "logout");
+    #[cfg(feature = "module-realtime")]
+    if let Err(e) = websocket::close(app) {
+        tracing::warn!("Failed to close the realtime connection: {}", e);
+    }
+    #[cfg(feature = "module-sse")]
+    sse::unsubscribe_all(app);
+    #[cfg(feature = "module-watch")]
+    watch::clear(app);
+    if let Err(e) = outbox::discard(app) {
+        tracing::warn!("Failed to clear the outbox: {}", e);
+    }
+    if let Err(e) = sync::discard(app) {
+        tracing::warn!("Failed to clear the synced data: {}", e);
+    }
+
+    let Some(tokens) = tokens else {
+        return Ok(());
+    };
+    let Some(logout) = &tokens.logout else {
+        return Ok(());
+    };
+    let url = end_session_url(logout, tokens.id_token.as_deref(), &random_token()?)?;
+    if let Err(e) = browser_redirect(app, &url, &logout.post_logout_redirect_uri).await {
+        tracing::warn!("Provider logout failed: {}", e);
+    }
+    Ok(())
+}
+
+/// Open the authorization page in the platform browser session
+fn open_session(url: &str, redirect_uri: &str) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::open_session(url, redirect_uri)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::open_session(url, redirect_uri)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = (url, redirect_uri);
+        Err(AppError::Auth("Native login not supported on this platform".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ProviderConfig {
+        ProviderConfig {
+            authorization_endpoint: "https://idp.example.org/authorize".to_string(),
+            token_endpoint: "https://idp.example.org/token".to_string(),
+            client_id: "elulib-mobile".to_string(),
+            redirect_uri: "elulib://auth/callback".to_string(),
+            scopes: vec!["openid".to_string(), "profile".to_string()],
+            extra_params: BTreeMap::from([("prompt".to_string(), "login".to_string())]),
+            profile: None,
+            end_session_endpoint: None,
+            post_logout_redirect_uri: None,
+        }
+    }
+
+    #[test]
+    fn test_pkce_challenge() {
+        // RFC 7636, appendix B
+        let pkce = Pkce::from_verifier("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_string());
+        assert_eq!(pkce.challenge, "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM");
+        assert_ne!(Pkce::generate().unwrap(), Pkce::generate().unwrap());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(config().validate().unwrap(), &identity_provider::OIDC);
+        assert!(ProviderConfig { token_endpoint: "http://idp.example.org/token".to_string(), ..config() }
+            .validate()
+            .is_err());
+        assert!(ProviderConfig { redirect_uri: "https://evil.example.com/cb".to_string(), ..config() }
+            .validate()
+            .is_err());
+        assert!(ProviderConfig { client_id: " ".to_string(), ..config() }.validate().is_err());
+        assert!(ProviderConfig { profile: Some("unknown".to_string()), ..config() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_franceconnect() {
+        let franceconnect = ProviderConfig {
+            authorization_endpoint: "https://app.franceconnect.gouv.fr/api/v1/authorize".to_string(),
+            token_endpoint: "https://app.franceconnect.gouv.fr/api/v1/token".to_string(),
+            profile: Some("franceconnect".to_string()),
+            end_session_endpoint: Some("https://app.franceconnect.gouv.fr/api/v1/logout".to_string()),
+            post_logout_redirect_uri: Some("elulib://auth/logout".to_string()),
+            ..config()
+        };
+        assert_eq!(franceconnect.validate().unwrap(), &identity_provider::FRANCECONNECT);
+        assert!(ProviderConfig { end_session_endpoint: None, ..franceconnect.clone() }.validate().is_err());
+        assert!(ProviderConfig { post_logout_redirect_uri: None, ..franceconnect.clone() }.validate().is_err());
+        assert!(ProviderConfig { token_endpoint: config().token_endpoint, ..franceconnect }
+            .validate()
+            .is_err(), "Endpoints outside of FranceConnect");
+    }
+
+    #[test]
+    fn test_authorization_url() {
+        let pkce = Pkce::from_verifier("verifier".to_string());
+        let url = authorization_url(&config(), &identity_provider::OIDC, &pkce, "xyz", Some("n-1")).unwrap();
+        let params: BTreeMap<String, String> = url.query_pairs().into_owned().collect();
+        assert_eq!(params["response_type"], "code");
+        assert_eq!(params["redirect_uri"], "elulib://auth/callback");
+        assert_eq!(params["code_challenge"], pkce.challenge);
+        assert_eq!(params["code_challenge_method"], "S256");
+        assert_eq!(params["scope"], "openid profile");
+        assert_eq!(params["state"], "xyz");
+        assert_eq!(params["nonce"], "n-1");
+        assert_eq!(params["prompt"], "login");
+
+        let url = authorization_url(&config(), &identity_provider::FRANCECONNECT, &pkce, "xyz", None).unwrap();
+        let params: BTreeMap<String, String> = url.query_pairs().into_owned().collect();
+        assert_eq!(params["scope"], "openid given_name family_name email profile");
+        assert_eq!(params["acr_values"], "eidas1");
+    }
+
+    #[test]
+    fn test_end_session_url() {
+        let logout = LogoutConfig {
+            end_session_endpoint: "https://app.franceconnect.gouv.fr/api/v1/logout".to_string(),
+            post_logout_redirect_uri: "elulib://auth/logout".to_string(),
+        };
+        let url = end_session_url(&logout, Some("id.token.sig"), "xyz").unwrap();
+        let params: BTreeMap<String, String> = url.query_pairs().into_owned().collect();
+        assert_eq!(params["id_token_hint"], "id.token.sig");
+        assert_eq!(params["post_logout_redirect_uri"], "elulib://auth/logout");
+        assert_eq!(params["state"], "xyz");
+    }
+
+    #[test]
+    fn test_parse_redirect() {
+        assert_eq!(parse_redirect("elulib://auth/callback?code=abc&state=xyz", "xyz").unwrap(), "abc".to_string());
+        assert!(parse_redirect("elulib://auth/callback?code=abc&state=other", "xyz").is_err());
+        let denied = parse_redirect("elulib://auth/callback?error=access_denied&state=xyz", "xyz");
+        assert_eq!(denied.unwrap_err().to_string(), "Authentication error: Login failed: access_denied");
+    }
+
+    #[test]
+    fn test_redirect_delivered_to_pending_login() {
+        let state = AuthState::default();
+        assert!(!state.deliver("elulib://auth/callback?code=abc"), "No pending login");
+
+        let mut receiver = state.begin("elulib://auth/callback").unwrap();
+        assert!(!state.deliver("elulib://agenda/42"), "Regular deep link");
+        assert!(state.deliver("elulib://auth/callback?code=abc&state=xyz"));
+        assert_eq!(receiver.try_recv().unwrap(), "elulib://auth/callback?code=abc&state=xyz");
+        assert!(!state.deliver("elulib://auth/callback?code=abc"), "Delivered once");
+    }
+
+    #[test]
+    fn test_stored_tokens_expiry() {
+        let response = TokenResponse {
+            access_token: "access".to_string(),
+            token_type: "Bearer".to_string(),
+            expires_in: Some(3600),
+            refresh_token: None,
+            id_token: None,
+        };
+        assert_eq!(StoredTokens::from_response(response, 1_000).expires_at, Some(4_600));
+        assert_eq!(tokens_key("h1"), "auth.session.h1");
+    }
+}
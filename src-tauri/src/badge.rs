@@ -0,0 +1,296 @@
+/// Automatic badge reset module
+///
+/// This module clears the app icon badge and dismisses delivered notifications
+/// when the user is back in the app, coordinating the lifecycle events with the
+/// native badge API:
+/// - without inbox acknowledgement, the reset happens as soon as the app is
+///   foregrounded
+/// - with inbox acknowledgement (default), the reset waits until the frontend
+///   reports that the inbox was displayed (`acknowledge_inbox` command), so
+///   unread items stay visible on the icon until the user actually saw them
+///
+/// Notifications can be dismissed for all channels or only for a list of
+/// channels. The policy is persisted in the app data directory.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::notifications;
+use crate::{AppError, AppResult};
+
+/// Badge reset policy
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BadgeResetPolicy {
+    /// Reset the badge automatically
+    pub enabled: bool,
+    /// Wait for the frontend to acknowledge the inbox before resetting
+    pub require_inbox_ack: bool,
+    /// Channels whose delivered notifications are dismissed (all if empty)
+    pub channels: Vec<String>,
+}
+
+impl Default for BadgeResetPolicy {
+    fn default() -> Self {
+        BadgeResetPolicy {
+            enabled: true,
+            require_inbox_ack: true,
+            channels: Vec::new(),
+        }
+    }
+}
+
+/// Foreground / acknowledgement tracking deciding when to reset the badge
+#[derive(Debug, Clone)]
+pub struct BadgeTracker {
+    policy: BadgeResetPolicy,
+    foreground: bool,
+    acknowledged: bool,
+}
+
+impl Default for BadgeTracker {
+    fn default() -> Self {
+        BadgeTracker::new(BadgeResetPolicy::default())
+    }
+}
+
+impl BadgeTracker {
+    /// Create a tracker; the app starts in foreground, inbox not acknowledged
+    pub fn new(policy: BadgeResetPolicy) -> Self {
+        BadgeTracker {
+            policy,
+            foreground: true,
+            acknowledged: false,
+        }
+    }
+
+    /// Current policy
+    pub fn policy(&self) -> &BadgeResetPolicy {
+        &self.policy
+    }
+
+    /// Replace the policy
+    pub fn set_policy(&mut self, policy: BadgeResetPolicy) {
+        self.policy = policy;
+    }
+
+    /// Record the app going to background
+    ///
+    /// Notifications received from now on are unread, so a new
+    /// acknowledgement is required.
+    pub fn on_background(&mut self) {
+        self.foreground = false;
+        self.acknowledged = false;
+    }
+
+    /// Record the app returning to foreground
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the badge must be reset now.
+    pub fn on_foreground(&mut self) -> bool {
+        self.foreground = true;
+        self.should_reset()
+    }
+
+    /// Record the frontend acknowledging the inbox
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the badge must be reset now.
+    pub fn acknowledge(&mut self) -> bool {
+        self.acknowledged = true;
+        self.should_reset()
+    }
+
+    fn should_reset(&self) -> bool {
+        self.policy.enabled
+            && self.foreground
+            && (self.acknowledged || !self.policy.require_inbox_ack)
+    }
+}
+
+/// Badge reset state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct BadgeState {
+    tracker: Mutex<BadgeTracker>,
+    /// Data directory, set by `init`
+    dir: Mutex<Option<PathBuf>>,
+}
+
+fn read_policy(path: &Path) -> Option<BadgeResetPolicy> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents)
+        .map_err(|e| tracing::warn!("Ignoring invalid badge settings {}: {}", path.display(), e))
+        .ok()
+}
+
+fn write_policy(path: &Path, policy: &BadgeResetPolicy) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| AppError::Platform(format!("Failed to create data directory: {}", e)))?;
+    }
+    let contents = serde_json::to_string(policy)
+        .map_err(|e| AppError::Platform(format!("Failed to serialize badge settings: {}", e)))?;
+    std::fs::write(path, contents).map_err(|e| AppError::Platform(format!("Failed to write badge settings: {}", e)))
+}
+
+/// Restore the persisted badge reset policy
+pub fn init(app: &AppHandle) -> AppResult<()> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Platform(format!("Failed to resolve data directory: {}", e)))?;
+    let state = app.state::<BadgeState>();
+
+    if let Some(policy) = read_policy(&dir.join(constants::BADGE_SETTINGS_FILE)) {
+        if let Ok(mut tracker) = state.tracker.lock() {
+            tracker.set_policy(policy);
+        }
+    }
+    if let Ok(mut stored) = state.dir.lock() {
+        *stored = Some(dir);
+    }
+    Ok(())
+}
+
+/// Current badge reset policy
+pub fn policy(app: &AppHandle) -> BadgeResetPolicy {
+    app.state::<BadgeState>()
+        .tracker
+        .lock()
+        .map(|tracker| tracker.policy().clone())
+        .unwrap_or_default()
+}
+
+/// Update and persist the badge reset policy
+pub fn set_policy(app: &AppHandle, policy: BadgeResetPolicy) -> AppResult<()> {
+    let state = app.state::<BadgeState>();
+    state
+        .tracker
+        .lock()
+        .map_err(|e| AppError::Platform(e.to_string()))?
+        .set_policy(policy.clone());
+
+    let dir = state
+        .dir
+        .lock()
+        .map_err(|e| AppError::Platform(e.to_string()))?
+        .clone()
+        .ok_or_else(|| AppError::Platform("Badge settings not initialized".to_string()))?;
+    write_policy(&dir.join(constants::BADGE_SETTINGS_FILE), &policy)
+}
+
+/// Handle the app going to background
+pub fn on_background(app: &AppHandle) {
+    if let Ok(mut tracker) = app.state::<BadgeState>().tracker.lock() {
+        tracker.on_background();
+    }
+}
+
+/// Handle the app returning to foreground, resetting the badge if the policy
+/// does not wait for the inbox acknowledgement
+pub fn on_foreground(app: &AppHandle) {
+    let reset = app
+        .state::<BadgeState>()
+        .tracker
+        .lock()
+        .map(|mut tracker| tracker.on_foreground().then(|| tracker.policy().clone()))
+        .unwrap_or(None);
+    if let Some(policy) = reset {
+        apply_reset(&policy);
+    }
+}
+
+/// Handle the frontend acknowledging the inbox
+///
+/// # Returns
+///
+/// Returns `true` if the badge was reset.
+pub fn acknowledge_inbox(app: &AppHandle) -> bool {
+    let reset = app
+        .state::<BadgeState>()
+        .tracker
+        .lock()
+        .map(|mut tracker| tracker.acknowledge().then(|| tracker.policy().clone()))
+        .unwrap_or(None);
+    match reset {
+        Some(policy) => {
+            apply_reset(&policy);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Clear the badge and dismiss delivered notifications per the policy
+fn apply_reset(policy: &BadgeResetPolicy) {
+    tracing::debug!("Resetting app badge (channels: {:?})", policy.channels);
+    if let Err(e) = notifications::set_badge_count(0) {
+        tracing::warn!("Failed to clear app badge: {}", e);
+    }
+
+    let channels: Vec<Option<&str>> = if policy.channels.is_empty() {
+        vec![None]
+    } else {
+        policy.channels.iter().map(|channel| Some(channel.as_str())).collect()
+    };
+    for channel in channels {
+        if let Err(e) = notifications::clear_delivered(channel) {
+            tracing::warn!("Failed to dismiss delivered notifications: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_waits_for_inbox_ack() {
+        let mut tracker = BadgeTracker::default();
+        tracker.on_background();
+        assert!(!tracker.on_foreground(), "Inbox not acknowledged yet");
+        assert!(tracker.acknowledge());
+
+        // A new background period requires a new acknowledgement
+        tracker.on_background();
+        assert!(!tracker.on_foreground());
+    }
+
+    #[test]
+    fn test_reset_on_foreground_without_ack() {
+        let mut tracker = BadgeTracker::new(BadgeResetPolicy {
+            require_inbox_ack: false,
+            ..BadgeResetPolicy::default()
+        });
+        tracker.on_background();
+        assert!(tracker.on_foreground());
+    }
+
+    #[test]
+    fn test_no_reset_in_background_or_when_disabled() {
+        let mut tracker = BadgeTracker::default();
+        tracker.on_background();
+        assert!(!tracker.acknowledge(), "App is in background");
+
+        let mut tracker = BadgeTracker::new(BadgeResetPolicy {
+            enabled: false,
+            require_inbox_ack: false,
+            channels: Vec::new(),
+        });
+        assert!(!tracker.acknowledge());
+        assert!(!tracker.on_foreground());
+    }
+
+    #[test]
+    fn test_policy_defaults_for_missing_fields() {
+        let policy: BadgeResetPolicy = serde_json::from_str(r#"{"channels":["messages"]}"#).unwrap();
+        assert!(policy.enabled);
+        assert!(policy.require_inbox_ack);
+        assert_eq!(policy.channels, vec!["messages".to_string()]);
+    }
+}
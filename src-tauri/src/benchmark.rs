@@ -0,0 +1,238 @@
+/// Keychain and bridge micro-benchmarks
+///
+/// Performance-motivated changes (a keychain cache, batch commands) are
+/// validated with numbers from real devices, where the keychain is much
+/// slower than on a desktop machine:
+/// - `run_keychain` times store / retrieve / remove of dedicated keys
+///   (`BENCHMARK_KEY_PREFIX`, reserved to the native layer), then compares
+///   storing the values as one keychain item each, as separate commands do,
+///   with storing them together as a single item, as a batch command would
+/// - the keychain is called off the async runtime (`run_keychain_blocking`),
+///   since a device keychain access can take milliseconds
+/// - the `benchmark_ping` command does nothing, so the frontend can time the
+///   invoke round trip alone
+///
+/// The on-device commands are only registered in debug builds. The criterion
+/// benchmarks (`benches/bridge.rs`) measure the same paths on the mock app.
+
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_keystore::{RemoveRequest, RetrieveRequest, StoreRequest};
+
+use crate::constants::{self, helpers};
+use crate::plugins::{self, KeystoreExt};
+use crate::{AppError, AppResult};
+
+/// Latency distribution of an operation, in microseconds
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyStats {
+    /// Number of samples
+    pub samples: usize,
+    /// Fastest sample
+    pub min_us: u64,
+    /// Median sample
+    pub median_us: u64,
+    /// 95th percentile
+    pub p95_us: u64,
+    /// Slowest sample
+    pub max_us: u64,
+}
+
+impl LatencyStats {
+    /// Statistics of a set of samples
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return LatencyStats::default();
+        }
+        samples.sort_unstable();
+        let micros = |duration: Duration| duration.as_micros().min(u64::MAX as u128) as u64;
+        let percentile = |p: usize| micros(samples[((samples.len() - 1) * p).div_ceil(100)]);
+        LatencyStats {
+            samples: samples.len(),
+            min_us: micros(samples[0]),
+            median_us: percentile(50),
+            p95_us: percentile(95),
+            max_us: micros(samples[samples.len() - 1]),
+        }
+    }
+}
+
+/// Results of `run_keychain`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct KeychainBenchmark {
+    /// Number of keys
+    pub iterations: u32,
+    /// Latency of a store
+    pub store: LatencyStats,
+    /// Latency of a retrieve
+    pub retrieve: LatencyStats,
+    /// Latency of a remove
+    pub remove: LatencyStats,
+    /// Total time storing the values as one keychain item each, as separate
+    /// commands do
+    pub sequential_us: u64,
+    /// Total time storing the values together as a single keychain item
+    pub batch_us: u64,
+}
+
+/// Key of the benchmark entry `index`
+pub fn key(index: u32) -> String {
+    format!("{}{}", constants::BENCHMARK_KEY_PREFIX, index)
+}
+
+/// Key of the single item written by `store_batch`
+pub fn batch_key() -> String {
+    format!("{}batch", constants::BENCHMARK_KEY_PREFIX)
+}
+
+fn timed<T>(operation: impl FnOnce() -> AppResult<T>) -> AppResult<Duration> {
+    let started = Instant::now();
+    operation()?;
+    Ok(started.elapsed())
+}
+
+/// Store a benchmark entry as `keychain_store` does: validation, plugin
+/// check, then store
+pub fn store_one<R: Runtime>(app: &AppHandle<R>, key: &str, value: &str) -> AppResult<()> {
+    helpers::validate_keychain_key(key)?;
+    helpers::validate_keychain_value(value)?;
+    plugins::ensure_keystore(app)?;
+    app.keystore()
+        .store(StoreRequest {
+            value: helpers::key_value_pair(key, value),
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_store_error(&e)))
+}
+
+/// Store benchmark entries as a batch command would: the entries are
+/// validated, then written together as a single keychain item (`batch_key`),
+/// a JSON object of the values by key
+pub fn store_batch<R: Runtime>(app: &AppHandle<R>, entries: &[(String, String)]) -> AppResult<()> {
+    for (key, value) in entries {
+        helpers::validate_keychain_key(key)?;
+        helpers::validate_keychain_value(value)?;
+    }
+    let values: serde_json::Map<String, serde_json::Value> = entries
+        .iter()
+        .map(|(key, value)| (key.clone(), serde_json::Value::from(value.as_str())))
+        .collect();
+    let batch = serde_json::to_string(&values)?;
+    plugins::ensure_keystore(app)?;
+    app.keystore()
+        .store(StoreRequest {
+            value: helpers::key_value_pair(&batch_key(), &batch),
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_store_error(&e)))
+}
+
+fn retrieve_one<R: Runtime>(app: &AppHandle<R>, key: &str) -> AppResult<()> {
+    app.keystore()
+        .retrieve(RetrieveRequest {
+            service: key.to_string(),
+            user: key.to_string(),
+        })
+        .map(|_| ())
+        .map_err(|e| AppError::Keychain(helpers::keychain_retrieve_error(&e)))
+}
+
+/// Remove a benchmark entry
+pub fn remove_one<R: Runtime>(app: &AppHandle<R>, key: &str) -> AppResult<()> {
+    app.keystore()
+        .remove(RemoveRequest {
+            service: key.to_string(),
+            user: key.to_string(),
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_remove_error(&e)))
+}
+
+/// Time the keychain operations on `iterations` dedicated keys
+///
+/// The keys are removed afterwards, even if an operation fails. Blocks on
+/// the keychain: call it from `run_keychain_blocking` in async code.
+pub fn run_keychain<R: Runtime>(app: &AppHandle<R>, iterations: u32) -> AppResult<KeychainBenchmark> {
+    if !(1..=constants::BENCHMARK_MAX_ITERATIONS).contains(&iterations) {
+        return Err(AppError::InvalidArgument(format!(
+            "Iterations must be between 1 and {}, got {}",
+            constants::BENCHMARK_MAX_ITERATIONS,
+            iterations
+        )));
+    }
+    let entries: Vec<(String, String)> = (0..iterations)
+        .map(|index| (key(index), format!("benchmark-value-{:0>32}", index)))
+        .collect();
+
+    let result = (|| {
+        let mut store = Vec::with_capacity(entries.len());
+        let mut retrieve = Vec::with_capacity(entries.len());
+        let mut remove = Vec::with_capacity(entries.len());
+        for (key, value) in &entries {
+            store.push(timed(|| store_one(app, key, value))?);
+            retrieve.push(timed(|| retrieve_one(app, key))?);
+            remove.push(timed(|| remove_one(app, key))?);
+        }
+
+        let sequential = timed(|| entries.iter().try_for_each(|(key, value)| store_one(app, key, value)))?;
+        let batch = timed(|| store_batch(app, &entries))?;
+        Ok(KeychainBenchmark {
+            iterations,
+            store: LatencyStats::from_samples(store),
+            retrieve: LatencyStats::from_samples(retrieve),
+            remove: LatencyStats::from_samples(remove),
+            sequential_us: sequential.as_micros() as u64,
+            batch_us: batch.as_micros() as u64,
+        })
+    })();
+
+    for key in entries.iter().map(|(key, _)| key.clone()).chain([batch_key()]) {
+        let _ = remove_one(app, &key);
+    }
+    if let Ok(report) = &result {
+        tracing::info!(
+            "Keychain benchmark ({} keys): store p50 {}us, retrieve p50 {}us, sequential {}us, batch {}us",
+            iterations,
+            report.store.median_us,
+            report.retrieve.median_us,
+            report.sequential_us,
+            report.batch_us
+        );
+    }
+    result
+}
+
+/// Run `run_keychain` on the blocking thread pool
+pub async fn run_keychain_blocking(app: AppHandle, iterations: u32) -> AppResult<KeychainBenchmark> {
+    tauri::async_runtime::spawn_blocking(move || run_keychain(&app, iterations))
+        .await
+        .map_err(|e| AppError::Keychain(format!("Keychain benchmark failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_stats() {
+        assert_eq!(LatencyStats::from_samples(Vec::new()), LatencyStats::default());
+
+        let samples = (1..=100).rev().map(Duration::from_micros).collect();
+        let stats = LatencyStats::from_samples(samples);
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.min_us, 1);
+        assert_eq!(stats.median_us, 51);
+        assert_eq!(stats.p95_us, 96);
+        assert_eq!(stats.max_us, 100);
+
+        let single = LatencyStats::from_samples(vec![Duration::from_micros(7)]);
+        assert_eq!((single.min_us, single.median_us, single.p95_us, single.max_us), (7, 7, 7, 7));
+    }
+
+    #[test]
+    fn test_benchmark_keys_are_reserved() {
+        assert!(helpers::is_reserved_keychain_key(&key(0)));
+        assert!(helpers::is_reserved_keychain_key(&batch_key()));
+        assert!(!helpers::is_reserved_keychain_key("benchmark_settings"));
+    }
+}
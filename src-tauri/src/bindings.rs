@@ -0,0 +1,241 @@
+/// TypeScript bindings module
+///
+/// The remote frontend calls the native commands through `invoke`. Instead of
+/// maintaining their argument and result types by hand, the frontend team
+/// consumes a definition file generated from the commands themselves
+/// (`specta`): `bindings/elulib-native.ts` at the repository root, with
+/// - a type per request/response type (`export type DeviceInfo = { ... }`)
+/// - a `commands` object with a typed wrapper per command; argument names are
+///   converted to camelCase, as `invoke` expects
+/// - an `EventPayloads` map from event channel to payload type (`events`),
+///   and a typed `listen` helper
+/// - `BINDINGS_VERSION`, the crate version the file was generated from
+///
+/// Commands returning `AppResult<T>` resolve with `T` and reject with the
+/// `AppError` as `{ code, message }`; `CommandResponse<T>` commands resolve
+/// with the envelope (native API version 2, see `api_version`).
+/// `*_packed` commands (`payload_encoding`) resolve with their MessagePack
+/// body decoded, so the frontend needs `@msgpack/msgpack`.
+///
+/// The file is committed and covers the commands of a debug build, the
+/// debug-only ones being documented as such. `test_bindings_up_to_date` fails
+/// when a command or type changes without regenerating it with
+/// `npm run bindings` (`UPDATE_BINDINGS=1 cargo test --lib bindings`).
+
+use specta::datatype::{DataType, Function, FunctionResultVariant};
+use specta::function::fn_datatype;
+use specta::TypeCollection;
+use specta_typescript::{BigIntExportBehavior, Typescript};
+#[cfg(feature = "module-keychain")]
+use tauri::Wry;
+
+use crate::{commands, constants, events, AppError, AppResult};
+
+#[cfg(feature = "module-notifications")]
+use crate::notification_bridge;
+
+/// Version stamped in the generated file
+pub const BINDINGS_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Path of the generated file, relative to the crate manifest
+pub const BINDINGS_PATH: &str = "../bindings/elulib-native.ts";
+
+/// Type information of a command list: the runtime-generic commands of
+/// `app_handler!`, followed by the given ones
+macro_rules! command_functions {
+    ($($(#[$meta:meta])* $($segment:ident)::+),* $(,)?) => {
+        fn app_functions(types: &mut TypeCollection) -> Vec<Function> {
+            let mut functions = Vec::new();
+            #[cfg(feature = "module-keychain")]
+            functions.push(fn_datatype!(commands::keychain_store::<Wry>)(types));
+            #[cfg(feature = "module-keychain")]
+            functions.push(fn_datatype!(commands::keychain_retrieve::<Wry>)(types));
+            #[cfg(feature = "module-keychain")]
+            functions.push(fn_datatype!(commands::keychain_remove::<Wry>)(types));
+            #[cfg(feature = "module-keychain")]
+            functions.push(fn_datatype!(commands::keychain_exists::<Wry>)(types));
+            $(
+                $(#[$meta])*
+                functions.push(fn_datatype!($($segment)::+)(types));
+            )*
+            functions
+        }
+    };
+}
+
+crate::app_commands!(command_functions);
+
+/// Type information of every command registered by `run()`, in registration
+/// order, and of the types they use
+pub fn functions(types: &mut TypeCollection) -> Vec<Function> {
+    app_functions(types)
+}
+
+/// `snake_case` argument name as passed to `invoke`
+fn camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        match c {
+            '_' => upper = !out.is_empty(),
+            c if upper => {
+                out.extend(c.to_uppercase());
+                upper = false;
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// TypeScript notation of a type
+fn typescript(config: &Typescript, types: &TypeCollection, datatype: &DataType) -> AppResult<String> {
+    specta_typescript::datatype(config, &FunctionResultVariant::Value(datatype.clone()), types)
+        .map_err(|e| AppError::Platform(format!("Failed to export type: {}", e)))
+}
+
+/// Wrapper of a command in the `commands` object
+fn command(config: &Typescript, types: &TypeCollection, function: &Function) -> AppResult<String> {
+    let mut out = String::new();
+    // First paragraph only: the rest documents the Rust side and raw invokes
+    let summary: Vec<&str> = function
+        .docs()
+        .lines()
+        .map(str::trim)
+        .take_while(|line| !line.is_empty())
+        .collect();
+    if !summary.is_empty() {
+        out += "  /**\n";
+        for line in summary {
+            out += &format!("   * {}\n", line);
+        }
+        out += "   */\n";
+    }
+
+    let mut params = Vec::new();
+    let mut names = Vec::new();
+    for (name, datatype) in function.args() {
+        let name = camel_case(name);
+        params.push(format!("{}: {}", name, typescript(config, types, datatype)?));
+        names.push(name);
+    }
+    let result = match function.result() {
+        Some(FunctionResultVariant::Value(datatype)) | Some(FunctionResultVariant::Result(datatype, _)) => {
+            typescript(config, types, datatype)?
+        }
+        None => "null".to_string(),
+    };
+    let args = if names.is_empty() {
+        String::new()
+    } else {
+        format!(", {{ {} }}", names.join(", "))
+    };
+    // MessagePack results arrive as an `ArrayBuffer`
+    let invoke = if function.name().ends_with(constants::PACKED_COMMAND_SUFFIX) {
+        format!(
+            "invoke<ArrayBuffer>(\"{}\"{}).then((body) => decode(new Uint8Array(body)) as {})",
+            function.name(),
+            args,
+            result
+        )
+    } else {
+        format!("invoke<{}>(\"{}\"{})", result, function.name(), args)
+    };
+    out += &format!(
+        "  {}: ({}): Promise<{}> =>\n    {},\n",
+        function.name(),
+        params.join(", "),
+        result,
+        invoke
+    );
+    Ok(out)
+}
+
+/// Render the TypeScript definition file
+pub fn render() -> AppResult<String> {
+    let mut types = TypeCollection::default();
+    let functions = functions(&mut types);
+    let events = events::registry(&mut types);
+    let config = Typescript::default()
+        .header("// Generated from the native commands by src-tauri/src/bindings.rs. Do not edit.\n// Regenerate with `npm run bindings`.")
+        .framework_header(
+            "import { decode } from \"@msgpack/msgpack\";\nimport { invoke } from \"@tauri-apps/api/core\";\nimport { listen as listenEvent } from \"@tauri-apps/api/event\";",
+        )
+        .bigint(BigIntExportBehavior::Number);
+
+    let mut out = config.export(&types).map_err(|e| AppError::Platform(format!("Failed to export types: {}", e)))?;
+    out += &format!("export const BINDINGS_VERSION = \"{}\";\n\n", BINDINGS_VERSION);
+    out += "export const commands = {\n";
+    for function in &functions {
+        out += &command(&config, &types, function)?;
+    }
+    out += "};\n\n";
+
+    out += "export type EventPayloads = {\n";
+    for (channel, payload) in &events {
+        out += &format!("  \"{}\": {};\n", channel, typescript(&config, &types, payload)?);
+    }
+    out += "};\n\n";
+    out += "export const listen = <E extends keyof EventPayloads>(\n";
+    out += "  event: E,\n";
+    out += "  handler: (payload: EventPayloads[E]) => void,\n";
+    out += ") => listenEvent<EventPayloads[E]>(event, ({ payload }) => handler(payload));\n";
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_scope::COMMAND_SCOPES;
+
+    #[test]
+    fn test_camel_case() {
+        assert_eq!(camel_case("key"), "key");
+        assert_eq!(camel_case("max_age_secs"), "maxAgeSecs");
+        assert_eq!(camel_case("_private"), "private");
+    }
+
+    #[test]
+    fn test_every_command_has_bindings() {
+        let functions = functions(&mut TypeCollection::default());
+        let names: Vec<&str> = functions.iter().map(|function| function.name().as_ref()).collect();
+        for (command, _) in COMMAND_SCOPES {
+            assert!(names.contains(command), "{} has no bindings", command);
+        }
+        assert_eq!(names.len(), COMMAND_SCOPES.len());
+    }
+
+    // The committed file has the commands of the default build: every module,
+    // no `dev-mock` commands
+    #[test]
+    #[cfg_attr(
+        any(
+            feature = "dev-mock",
+            not(all(
+                feature = "module-keychain",
+                feature = "module-notifications",
+                feature = "module-connectivity",
+                feature = "module-realtime",
+                feature = "module-sse",
+                feature = "module-watch",
+                feature = "module-shortcuts"
+            ))
+        ),
+        ignore
+    )]
+    fn test_bindings_up_to_date() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(BINDINGS_PATH);
+        let rendered = render().unwrap();
+        if std::env::var_os("UPDATE_BINDINGS").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, rendered).unwrap();
+            return;
+        }
+        let committed = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            committed == rendered,
+            "{} is out of date, regenerate it with `npm run bindings`",
+            path.display()
+        );
+    }
+}
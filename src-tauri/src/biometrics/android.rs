@@ -0,0 +1,36 @@
+/// Android-specific biometric authentication implementation
+///
+/// This module authenticates the user with `BiometricPrompt` from the
+/// AndroidX Biometric library.
+///
+/// Note: This implementation provides the structure for Android authentication.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use crate::{AppError, AppResult};
+
+/// Authenticate the user with biometrics, falling back to the device credential
+///
+/// # Returns
+///
+/// Returns `true` if the user authenticated successfully.
+pub fn authenticate(reason: &str) -> AppResult<bool> {
+    tracing::info!("[Android] Requesting authentication: {}", reason);
+
+    // TODO: Implement native Android authentication using BiometricPrompt
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val promptInfo = BiometricPrompt.PromptInfo.Builder()
+    //     .setTitle(reason)
+    //     .setAllowedAuthenticators(BIOMETRIC_STRONG or DEVICE_CREDENTIAL)
+    //     .build()
+    // BiometricPrompt(activity, executor, object : BiometricPrompt.AuthenticationCallback() {
+    //     override fun onAuthenticationSucceeded(result: BiometricPrompt.AuthenticationResult) { /* true */ }
+    //     override fun onAuthenticationError(code: Int, message: CharSequence) { /* false */ }
+    // }).authenticate(promptInfo)
+    // ```
+
+    // Placeholder: Authentication unavailable
+    // Replace this with actual native implementation
+    Err(AppError::Auth("Authentication not available".to_string()))
+}
@@ -0,0 +1,31 @@
+/// iOS-specific biometric authentication implementation
+///
+/// This module authenticates the user with `LAContext` from the
+/// LocalAuthentication framework.
+///
+/// Note: This implementation provides the structure for iOS authentication.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use crate::{AppError, AppResult};
+
+/// Authenticate the user with Face ID / Touch ID, falling back to the passcode
+///
+/// # Returns
+///
+/// Returns `true` if the user authenticated successfully.
+pub fn authenticate(reason: &str) -> AppResult<bool> {
+    tracing::info!("[iOS] Requesting authentication: {}", reason);
+
+    // TODO: Implement native iOS authentication using LAContext
+    // Example Swift implementation:
+    // ```swift
+    // LAContext().evaluatePolicy(.deviceOwnerAuthentication, localizedReason: reason) { success, error in
+    //     // return success
+    // }
+    // ```
+
+    // Placeholder: Authentication unavailable
+    // Replace this with actual native implementation
+    Err(AppError::Auth("Authentication not available".to_string()))
+}
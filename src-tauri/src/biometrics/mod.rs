@@ -0,0 +1,42 @@
+/// Biometric / device credential authentication
+///
+/// This module prompts the user to authenticate with biometrics (Face ID,
+/// Touch ID, fingerprint) or, as a fallback, the device passcode.
+
+use crate::{AppError, AppResult};
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+/// Prompt the user to authenticate with biometrics or the device passcode
+///
+/// # Arguments
+///
+/// * `reason` - Message displayed in the system prompt
+///
+/// # Returns
+///
+/// Returns `true` if the user authenticated successfully, `false` if the user
+/// cancelled or failed, or an error if authentication is unavailable.
+pub fn authenticate(reason: &str) -> AppResult<bool> {
+    tracing::info!("Requesting user authentication");
+
+    #[cfg(target_os = "ios")]
+    {
+        ios::authenticate(reason)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::authenticate(reason)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = reason;
+        Err(AppError::Auth("Biometric authentication not supported on this platform".to_string()))
+    }
+}
@@ -0,0 +1,122 @@
+/// Breadcrumb trail module
+///
+/// Log lines alone rarely tell what the user was doing before a failure, so
+/// native subsystems and the frontend (`add_breadcrumb` command) record short
+/// breadcrumbs of notable steps: lifecycle transitions, navigation, deep
+/// links, session lock, login... The last `BREADCRUMB_CAPACITY` breadcrumbs
+/// are kept in memory and attached to crash reports and diagnostics.
+///
+/// Recording never blocks: a breadcrumb is dropped if the trail is busy.
+/// Messages are redacted like log messages and truncated to
+/// `BREADCRUMB_MAX_MESSAGE_LENGTH` characters.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::constants;
+use crate::redact;
+use crate::{AppError, AppResult};
+
+/// Trail of the last breadcrumbs, oldest first
+static TRAIL: Mutex<VecDeque<Breadcrumb>> = Mutex::new(VecDeque::new());
+
+/// A step recorded before a potential failure
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Breadcrumb {
+    /// Time of the step (milliseconds since the Unix epoch)
+    pub timestamp_ms: u64,
+    /// Category (`lifecycle`, `navigation`, `auth`, ...)
+    pub category: String,
+    /// Redacted description of the step
+    pub message: String,
+}
+
+/// Check a category provided by the frontend
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the category is a short lowercase identifier
+/// (`[a-z0-9_.]`), or an error.
+pub fn validate_category(category: &str) -> AppResult<()> {
+    let valid = !category.is_empty()
+        && category.len() <= constants::BREADCRUMB_MAX_CATEGORY_LENGTH
+        && category
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::InvalidArgument(format!("Invalid breadcrumb category: {}", category)))
+    }
+}
+
+fn truncate(message: &str) -> String {
+    match message.char_indices().nth(constants::BREADCRUMB_MAX_MESSAGE_LENGTH) {
+        Some((end, _)) => format!("{}…", &message[..end]),
+        None => message.to_string(),
+    }
+}
+
+/// Record a breadcrumb
+pub fn breadcrumb(category: &str, message: &str) {
+    let breadcrumb = Breadcrumb {
+        timestamp_ms: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        category: category.to_string(),
+        message: truncate(&redact::redact(message)),
+    };
+    // Never block the caller: drop the breadcrumb if the trail is busy
+    if let Ok(mut trail) = TRAIL.try_lock() {
+        if trail.len() == constants::BREADCRUMB_CAPACITY {
+            trail.pop_front();
+        }
+        trail.push_back(breadcrumb);
+    }
+}
+
+/// Current trail, oldest first
+///
+/// Safe to call from the panic hook: returns an empty trail if it is busy.
+pub fn trail() -> Vec<Breadcrumb> {
+    TRAIL
+        .try_lock()
+        .map(|trail| trail.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_category() {
+        assert!(validate_category("navigation").is_ok());
+        assert!(validate_category("ui.form_submit").is_ok());
+        assert!(validate_category("").is_err());
+        assert!(validate_category("Navigation").is_err());
+        assert!(validate_category("jane@example.com").is_err());
+        assert!(validate_category(&"a".repeat(constants::BREADCRUMB_MAX_CATEGORY_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_trail_is_bounded_and_redacted() {
+        for i in 0..constants::BREADCRUMB_CAPACITY + 10 {
+            breadcrumb("test", &format!("step {}", i));
+        }
+        breadcrumb("auth", "login jane@example.com");
+        breadcrumb("test", &"x".repeat(constants::BREADCRUMB_MAX_MESSAGE_LENGTH + 10));
+
+        let trail = trail();
+        assert_eq!(trail.len(), constants::BREADCRUMB_CAPACITY);
+        let login = &trail[trail.len() - 2];
+        assert_eq!(login.category, "auth");
+        assert_eq!(login.message, "login [REDACTED]");
+        assert_eq!(
+            trail.last().unwrap().message.chars().count(),
+            constants::BREADCRUMB_MAX_MESSAGE_LENGTH + 1
+        );
+    }
+}
@@ -0,0 +1,36 @@
+/// Android-specific carrier information implementation
+///
+/// This module reads carrier details with `TelephonyManager` from the
+/// Android SDK.
+///
+/// Note: This implementation provides the structure for Android carrier info.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::CarrierInfo;
+use crate::AppResult;
+
+/// Get the carrier information on Android
+///
+/// # Returns
+///
+/// Returns the `CarrierInfo` of the device.
+pub fn info() -> AppResult<CarrierInfo> {
+    // TODO: Implement native Android lookup using TelephonyManager
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val tm = context.getSystemService(Context.TELEPHONY_SERVICE) as TelephonyManager
+    // if (tm.simState != TelephonyManager.SIM_STATE_READY) return CarrierInfo(permissionGranted = true)
+    // val carrierName = tm.networkOperatorName
+    // val countryIso = tm.networkCountryIso
+    // val mccMnc = tm.networkOperator
+    // val roaming = tm.isNetworkRoaming
+    // ```
+
+    // Placeholder: Report unknown carrier
+    // Replace this with actual native implementation
+    Ok(CarrierInfo {
+        permission_granted: true,
+        ..CarrierInfo::default()
+    })
+}
@@ -0,0 +1,39 @@
+/// iOS-specific carrier information implementation
+///
+/// This module reads carrier details with `CTTelephonyNetworkInfo` from the
+/// CoreTelephony framework.
+///
+/// Note: Since iOS 16, `CTCarrier` returns placeholder values ("--", "65535")
+/// for apps built with the iOS 16 SDK, so most fields will be `None` on
+/// recent systems.
+///
+/// This implementation provides the structure for iOS carrier info.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::CarrierInfo;
+use crate::AppResult;
+
+/// Get the carrier information on iOS
+///
+/// # Returns
+///
+/// Returns the `CarrierInfo` of the device.
+pub fn info() -> AppResult<CarrierInfo> {
+    // TODO: Implement native iOS lookup using CTTelephonyNetworkInfo
+    // Example Swift implementation:
+    // ```swift
+    // let carrier = CTTelephonyNetworkInfo().serviceSubscriberCellularProviders?.values.first
+    // let carrierName = carrier?.carrierName
+    // let countryIso = carrier?.isoCountryCode
+    // let mccMnc = (carrier?.mobileCountryCode ?? "") + (carrier?.mobileNetworkCode ?? "")
+    // // Roaming is not exposed on iOS
+    // ```
+
+    // Placeholder: Report unknown carrier
+    // Replace this with actual native implementation
+    Ok(CarrierInfo {
+        permission_granted: true,
+        ..CarrierInfo::default()
+    })
+}
@@ -0,0 +1,64 @@
+/// Carrier and SIM information
+///
+/// This module reports the mobile carrier name, country and roaming state so
+/// support can correlate connectivity complaints with specific operators.
+///
+/// The lookup is permission-aware: when the phone state permission is not
+/// granted, no native query is made and only `permissionGranted: false` is
+/// reported.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use crate::permissions::{self, Permission};
+use crate::AppResult;
+
+/// Carrier and SIM information reported to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CarrierInfo {
+    /// The phone state permission is granted
+    pub permission_granted: bool,
+    /// Name of the network operator (e.g. "Orange F")
+    pub carrier_name: Option<String>,
+    /// ISO 3166-1 country code of the network operator (lowercase)
+    pub country_iso: Option<String>,
+    /// Mobile country code + mobile network code (e.g. "20801")
+    pub mcc_mnc: Option<String>,
+    /// The device is roaming, if known
+    pub roaming: Option<bool>,
+}
+
+/// Get the carrier and SIM information of the device
+///
+/// # Returns
+///
+/// Returns the `CarrierInfo`, or an error if the platform query fails.
+/// Devices without a SIM card report `None` for every carrier field.
+pub fn info() -> AppResult<CarrierInfo> {
+    if !permissions::check(Permission::PhoneState)?.is_granted() {
+        tracing::debug!("Phone state permission not granted, skipping carrier lookup");
+        return Ok(CarrierInfo::default());
+    }
+
+    #[cfg(target_os = "ios")]
+    {
+        ios::info()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::info()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(CarrierInfo {
+            permission_granted: true,
+            ..CarrierInfo::default()
+        })
+    }
+}
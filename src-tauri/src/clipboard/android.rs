@@ -0,0 +1,88 @@
+/// Android-specific clipboard access
+///
+/// This module writes, reads and clears the primary clip through
+/// `ClipboardManager`. Sensitive copies are flagged with
+/// `ClipDescription.EXTRA_IS_SENSITIVE`, so Android 13+ hides them from the
+/// clipboard preview.
+///
+/// Note: This implementation provides the structure for the Android clipboard.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Write text to the primary clip
+///
+/// # Arguments
+///
+/// * `text` - Text to copy
+/// * `sensitive` - Flag the copy as sensitive
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn write_text(text: &str, sensitive: bool) -> AppResult<()> {
+    tracing::info!("[Android] Writing to the clipboard (sensitive: {})", sensitive);
+
+    // TODO: Implement native Android clipboard write via JNI
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val clipboard = context.getSystemService(ClipboardManager::class.java)
+    // val clip = ClipData.newPlainText("", text)
+    // if (sensitive) {
+    //     clip.description.extras = PersistableBundle().apply {
+    //         putBoolean(ClipDescription.EXTRA_IS_SENSITIVE, true)
+    //     }
+    // }
+    // clipboard.setPrimaryClip(clip)
+    // ```
+
+    // Placeholder: Log the operation
+    // Replace this with actual native implementation
+    let _ = text;
+    Ok(())
+}
+
+/// Read the text of the primary clip
+///
+/// # Returns
+///
+/// Returns the clipboard text (`None` if it holds no text), or an error
+/// message if the operation fails.
+pub fn read_text() -> AppResult<Option<String>> {
+    // TODO: Implement native Android clipboard read via JNI
+    // Android 10+ only lets the app read the clipboard while it has focus:
+    // when going to the background, the read must happen before focus is lost.
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val clip = clipboard.primaryClip ?: return null
+    // return clip.getItemAt(0)?.text?.toString()
+    // ```
+
+    // Placeholder: Report an empty clipboard
+    // Replace this with actual native implementation
+    Ok(None)
+}
+
+/// Clear the primary clip
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn clear() -> AppResult<()> {
+    tracing::info!("[Android] Clearing the clipboard");
+
+    // TODO: Implement native Android clipboard clear via JNI
+    // Example Kotlin implementation:
+    // ```kotlin
+    // if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.P) {
+    //     clipboard.clearPrimaryClip()
+    // } else {
+    //     clipboard.setPrimaryClip(ClipData.newPlainText("", ""))
+    // }
+    // ```
+
+    // Placeholder: Log the operation
+    // Replace this with actual native implementation
+    Ok(())
+}
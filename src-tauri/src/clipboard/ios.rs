@@ -0,0 +1,83 @@
+/// iOS-specific clipboard access
+///
+/// This module writes, reads and clears the general pasteboard. Sensitive
+/// copies are written local only (not synced through Universal Clipboard) and
+/// with an expiration date as a fallback if the app is killed before clearing.
+///
+/// Note: This implementation provides the structure for the iOS clipboard.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Write text to the general pasteboard
+///
+/// # Arguments
+///
+/// * `text` - Text to copy
+/// * `sensitive` - Write the copy local only, with an expiration date
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn write_text(text: &str, sensitive: bool) -> AppResult<()> {
+    tracing::info!("[iOS] Writing to the pasteboard (sensitive: {})", sensitive);
+
+    // TODO: Implement native iOS pasteboard write
+    // Example Swift implementation:
+    // ```swift
+    // let item = [UTType.utf8PlainText.identifier: text]
+    // if sensitive {
+    //     UIPasteboard.general.setItems([item], options: [
+    //         .localOnly: true,
+    //         .expirationDate: Date().addingTimeInterval(delay),
+    //     ])
+    // } else {
+    //     UIPasteboard.general.string = text
+    // }
+    // ```
+
+    // Placeholder: Log the operation
+    // Replace this with actual native implementation
+    let _ = text;
+    Ok(())
+}
+
+/// Read the text of the general pasteboard
+///
+/// # Returns
+///
+/// Returns the pasteboard text (`None` if it holds no text), or an error
+/// message if the operation fails.
+pub fn read_text() -> AppResult<Option<String>> {
+    // TODO: Implement native iOS pasteboard read
+    // Reading the pasteboard shows the paste banner on iOS 16+ only when the
+    // content comes from another app, which is not the case for our own copy.
+    // Example Swift implementation:
+    // ```swift
+    // return UIPasteboard.general.hasStrings ? UIPasteboard.general.string : nil
+    // ```
+
+    // Placeholder: Report an empty pasteboard
+    // Replace this with actual native implementation
+    Ok(None)
+}
+
+/// Clear the general pasteboard
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn clear() -> AppResult<()> {
+    tracing::info!("[iOS] Clearing the pasteboard");
+
+    // TODO: Implement native iOS pasteboard clear
+    // Example Swift implementation:
+    // ```swift
+    // UIPasteboard.general.items = []
+    // ```
+
+    // Placeholder: Log the operation
+    // Replace this with actual native implementation
+    Ok(())
+}
@@ -0,0 +1,272 @@
+/// Clipboard with auto-expiring sensitive copies
+///
+/// This module writes text to the system clipboard for the frontend. Copies
+/// flagged as sensitive (codes, identifiers, tokens) do not linger:
+/// - the clipboard is cleared after `CLIPBOARD_CLEAR_DELAY_SECS` (or the
+///   `clipboard.clear_delay_secs` remote config key)
+/// - the clipboard is cleared as soon as the app goes to the background
+/// - a `clipboard://cleared` event is emitted, so the UI can reflect it
+///
+/// The clipboard is only cleared if it still holds the sensitive copy: text
+/// copied afterwards, in the app or elsewhere, is never wiped. When the
+/// clipboard cannot be read (e.g. the app lost clipboard access in the
+/// background), it is cleared anyway rather than leaving the sensitive copy
+/// in place. Only a hash of
+/// the sensitive value is kept in memory. Sensitive copies are also marked as
+/// such natively (local only on iOS, `EXTRA_IS_SENSITIVE` on Android 13+).
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::events::{self, ClipboardCleared};
+use crate::remote_config::{keys, ConfigStore};
+use crate::{AppError, AppResult};
+
+/// Why the clipboard was cleared, sent with the `clipboard://cleared` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ClearReason {
+    /// The clear delay elapsed
+    Expired,
+    /// The app went to the background
+    Background,
+}
+
+/// Sensitive copy waiting to be cleared
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingClear {
+    /// Copy counter value, so an older timer does not clear a newer copy
+    generation: u64,
+    /// SHA-256 of the copied text
+    digest: Vec<u8>,
+}
+
+/// Clipboard state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct ClipboardState {
+    generation: Mutex<u64>,
+    pending: Mutex<Option<PendingClear>>,
+}
+
+fn digest(text: &str) -> Vec<u8> {
+    ring::digest::digest(&ring::digest::SHA256, text.as_bytes()).as_ref().to_vec()
+}
+
+impl ClipboardState {
+    /// Record a copy
+    ///
+    /// # Returns
+    ///
+    /// Returns the generation of the copy, to pass to `take_pending`.
+    pub fn record_copy(&self, text: &str, sensitive: bool) -> u64 {
+        let generation = match self.generation.lock() {
+            Ok(mut generation) => {
+                *generation += 1;
+                *generation
+            }
+            Err(_) => 0,
+        };
+        if let Ok(mut pending) = self.pending.lock() {
+            *pending = sensitive.then(|| PendingClear {
+                generation,
+                digest: digest(text),
+            });
+        }
+        generation
+    }
+
+    /// Take the pending sensitive copy, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `generation` - Generation the caller was scheduled for, or `None` to
+    ///   take any pending copy
+    ///
+    /// # Returns
+    ///
+    /// Returns the digest of the pending copy.
+    fn take_pending(&self, generation: Option<u64>) -> Option<Vec<u8>> {
+        let mut pending = self.pending.lock().ok()?;
+        let matches = pending
+            .as_ref()
+            .is_some_and(|current| generation.is_none_or(|generation| generation == current.generation));
+        if matches {
+            pending.take().map(|pending| pending.digest)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if a sensitive copy is waiting to be cleared
+    pub fn has_pending(&self) -> bool {
+        self.pending.lock().map(|pending| pending.is_some()).unwrap_or(false)
+    }
+}
+
+/// Delay before a sensitive copy is cleared, with the remote override applied
+pub fn clear_delay(app: &AppHandle) -> Duration {
+    Duration::from_secs(
+        app.state::<ConfigStore>()
+            .get_or(keys::CLIPBOARD_CLEAR_DELAY_SECS, constants::CLIPBOARD_CLEAR_DELAY_SECS),
+    )
+}
+
+/// Copy text to the clipboard
+///
+/// # Arguments
+///
+/// * `text` - Text to copy
+/// * `sensitive` - Clear the copy after the delay and on background
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the clipboard is
+/// unavailable.
+pub fn copy(app: &AppHandle, text: &str, sensitive: bool) -> AppResult<()> {
+    write_text(text, sensitive)?;
+    let generation = app.state::<ClipboardState>().record_copy(text, sensitive);
+    if !sensitive {
+        return Ok(());
+    }
+
+    let delay = clear_delay(app);
+    tracing::debug!("Sensitive copy, clearing the clipboard in {}s", delay.as_secs());
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(delay).await;
+        clear_pending(&app, Some(generation), ClearReason::Expired);
+    });
+    Ok(())
+}
+
+/// Clear the clipboard if it still holds the pending sensitive copy, or if
+/// the clipboard cannot be read
+fn clear_pending(app: &AppHandle, generation: Option<u64>, reason: ClearReason) {
+    let Some(expected) = app.state::<ClipboardState>().take_pending(generation) else {
+        return;
+    };
+    match read_text() {
+        Ok(Some(current)) if digest(&current) == expected => {}
+        Ok(_) => {
+            tracing::debug!("Clipboard changed since the sensitive copy, not clearing it");
+            return;
+        }
+        Err(e) => tracing::warn!("Failed to read the clipboard, clearing it anyway: {}", e),
+    }
+
+    if let Err(e) = clear() {
+        tracing::error!("Failed to clear the clipboard: {}", e);
+        return;
+    }
+    tracing::info!("Sensitive copy cleared from the clipboard ({:?})", reason);
+    events::emit(app, &ClipboardCleared { reason });
+}
+
+/// Clear a pending sensitive copy when the app goes to the background
+///
+/// Called by the lifecycle module.
+pub fn on_background(app: &AppHandle) {
+    if app.state::<ClipboardState>().has_pending() {
+        clear_pending(app, None, ClearReason::Background);
+    }
+}
+
+/// Write text to the system clipboard
+fn write_text(text: &str, sensitive: bool) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::write_text(text, sensitive)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::write_text(text, sensitive)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = (text, sensitive);
+        Err(AppError::Platform("Clipboard not supported on this platform".to_string()))
+    }
+}
+
+/// Read the text of the system clipboard
+fn read_text() -> AppResult<Option<String>> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::read_text()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::read_text()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Err(AppError::Platform("Clipboard not supported on this platform".to_string()))
+    }
+}
+
+/// Clear the system clipboard
+fn clear() -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::clear()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::clear()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Err(AppError::Platform("Clipboard not supported on this platform".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_sensitive_copies_are_pending() {
+        let state = ClipboardState::default();
+        state.record_copy("public", false);
+        assert!(!state.has_pending());
+
+        let generation = state.record_copy("123456", true);
+        assert!(state.has_pending());
+        assert_eq!(state.take_pending(Some(generation)), Some(digest("123456")));
+        assert!(!state.has_pending());
+    }
+
+    #[test]
+    fn test_older_timer_does_not_clear_newer_copy() {
+        let state = ClipboardState::default();
+        let first = state.record_copy("first-code", true);
+        let second = state.record_copy("second-code", true);
+
+        assert_eq!(state.take_pending(Some(first)), None);
+        assert_eq!(state.take_pending(Some(second)), Some(digest("second-code")));
+    }
+
+    #[test]
+    fn test_regular_copy_cancels_pending_clear() {
+        let state = ClipboardState::default();
+        let generation = state.record_copy("secret-code", true);
+        state.record_copy("public", false);
+        assert_eq!(state.take_pending(Some(generation)), None);
+        assert_eq!(state.take_pending(None), None);
+    }
+}
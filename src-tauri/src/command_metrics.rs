@@ -0,0 +1,205 @@
+/// Per-command execution metrics module
+///
+/// Every Tauri command is measured in the invoke layer: each invocation runs
+/// in a `command` span (`guard_invokes` in lib.rs), which the tracing bridge
+/// reports here when it closes, once the command has completed. The metrics
+/// are the invocation count, the failure count (the command resolved with an
+/// error) and a latency histogram with the
+/// `COMMAND_METRICS_BUCKETS_MS` bounds, so slow keystore or notification
+/// calls can be spotted in the field.
+///
+/// Metrics since launch are returned by the `get_metrics` command. When the
+/// `command_metrics` feature flag is enabled, the metrics of the commands
+/// invoked since the last report are also tracked as `command_metrics`
+/// analytics events when the app goes to the background (one event per
+/// command, with the p50/p95 latencies estimated from the histogram).
+///
+/// Invocations rejected before reaching the command (origin guard, scope,
+/// rate limit, ...) are not measured.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::AppHandle;
+
+use crate::analytics::{self, PropValue};
+use crate::constants;
+use crate::feature_flags;
+
+/// Execution metrics of a command
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandStats {
+    /// Number of invocations
+    pub invocations: u64,
+    /// Number of invocations returning an error
+    pub failures: u64,
+    /// Cumulated execution time (milliseconds)
+    pub total_ms: u64,
+    /// Longest execution time (milliseconds)
+    pub max_ms: u64,
+    /// Number of invocations per latency bucket (`COMMAND_METRICS_BUCKETS_MS`),
+    /// the last bucket counting the invocations above the highest bound
+    pub histogram: Vec<u64>,
+}
+
+impl CommandStats {
+    /// Record an invocation
+    pub fn record(&mut self, elapsed_ms: u64, ok: bool) {
+        if self.histogram.is_empty() {
+            self.histogram = vec![0; constants::COMMAND_METRICS_BUCKETS_MS.len() + 1];
+        }
+        let bucket = constants::COMMAND_METRICS_BUCKETS_MS
+            .iter()
+            .position(|bound| elapsed_ms <= *bound)
+            .unwrap_or(constants::COMMAND_METRICS_BUCKETS_MS.len());
+        self.histogram[bucket] += 1;
+        self.invocations += 1;
+        if !ok {
+            self.failures += 1;
+        }
+        self.total_ms += elapsed_ms;
+        self.max_ms = self.max_ms.max(elapsed_ms);
+    }
+
+    /// Estimated latency percentile (`0.0..=1.0`), in milliseconds
+    ///
+    /// Returns the upper bound of the bucket holding the percentile, or
+    /// `max_ms` for the last bucket.
+    pub fn percentile_ms(&self, percentile: f64) -> u64 {
+        let rank = (self.invocations as f64 * percentile).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.histogram.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return constants::COMMAND_METRICS_BUCKETS_MS
+                    .get(bucket)
+                    .map_or(self.max_ms, |bound| (*bound).min(self.max_ms));
+            }
+        }
+        self.max_ms
+    }
+}
+
+/// Metrics of all commands, as returned by `get_metrics`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandMetrics {
+    /// Upper bounds of the histogram buckets (milliseconds)
+    pub bucket_bounds_ms: &'static [u64],
+    /// Metrics by command name, for commands invoked since launch
+    pub commands: BTreeMap<String, CommandStats>,
+}
+
+/// Metrics since launch, and since the last analytics report
+struct Registry {
+    total: BTreeMap<String, CommandStats>,
+    unreported: BTreeMap<String, CommandStats>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    total: BTreeMap::new(),
+    unreported: BTreeMap::new(),
+});
+
+/// Record a completed invocation of a command
+///
+/// Called by the tracing bridge when the `command` span of the invocation
+/// closes.
+pub fn record(command: &str, elapsed: Duration, ok: bool) {
+    let elapsed_ms = elapsed.as_millis() as u64;
+    if let Ok(mut registry) = REGISTRY.lock() {
+        registry.total.entry(command.to_string()).or_default().record(elapsed_ms, ok);
+        registry.unreported.entry(command.to_string()).or_default().record(elapsed_ms, ok);
+    }
+    #[cfg(debug_assertions)]
+    crate::invoke_recorder::complete(command, elapsed, ok);
+}
+
+/// Metrics of the commands invoked since launch
+pub fn snapshot() -> CommandMetrics {
+    let commands = REGISTRY
+        .lock()
+        .map(|registry| {
+            registry
+                .total
+                .iter()
+                .map(|(command, stats)| (command.clone(), stats.clone()))
+                .collect()
+        })
+        .unwrap_or_default();
+    CommandMetrics {
+        bucket_bounds_ms: constants::COMMAND_METRICS_BUCKETS_MS,
+        commands,
+    }
+}
+
+/// Analytics properties of the `command_metrics` event of a command
+fn analytics_props(command: &str, stats: &CommandStats) -> BTreeMap<String, PropValue> {
+    BTreeMap::from([
+        ("command".to_string(), PropValue::Token(command.to_string())),
+        ("invocations".to_string(), PropValue::Number(stats.invocations as f64)),
+        ("failures".to_string(), PropValue::Number(stats.failures as f64)),
+        ("p50_ms".to_string(), PropValue::Number(stats.percentile_ms(0.5) as f64)),
+        ("p95_ms".to_string(), PropValue::Number(stats.percentile_ms(0.95) as f64)),
+        ("max_ms".to_string(), PropValue::Number(stats.max_ms as f64)),
+    ])
+}
+
+/// Track the metrics of the commands invoked since the last report
+///
+/// Called when the app goes to the background. Does nothing unless the
+/// `command_metrics` feature flag is enabled; the pending metrics are
+/// dropped either way.
+pub fn report(app: &AppHandle) {
+    let unreported = match REGISTRY.lock() {
+        Ok(mut registry) => std::mem::take(&mut registry.unreported),
+        Err(_) => return,
+    };
+    if unreported.is_empty() || !feature_flags::is_enabled(app, feature_flags::COMMAND_METRICS) {
+        return;
+    }
+    for (command, stats) in &unreported {
+        if let Err(e) = analytics::track(app, "command_metrics", analytics_props(command, stats)) {
+            tracing::warn!("Failed to track command metrics: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_histogram_and_percentiles() {
+        let mut stats = CommandStats::default();
+        for elapsed_ms in [3, 8, 8, 40, 40, 40, 40, 90, 700, 12_000] {
+            stats.record(elapsed_ms, elapsed_ms < 10_000);
+        }
+        assert_eq!(stats.invocations, 10);
+        assert_eq!(stats.failures, 1);
+        assert_eq!(stats.max_ms, 12_000);
+        assert_eq!(stats.histogram, [1, 2, 0, 4, 1, 0, 0, 1, 0, 0, 1]);
+        assert_eq!(stats.percentile_ms(0.5), 50);
+        assert_eq!(stats.percentile_ms(0.95), 12_000);
+    }
+
+    #[test]
+    fn test_record_counts_failures() {
+        record("test_measure_ok", Duration::from_millis(3), true);
+        record("test_measure_failed", Duration::from_millis(3), false);
+
+        let metrics = snapshot();
+        assert_eq!(metrics.commands["test_measure_ok"].failures, 0);
+        assert_eq!(metrics.commands["test_measure_failed"].invocations, 1);
+        assert_eq!(metrics.commands["test_measure_failed"].failures, 1);
+    }
+
+    #[test]
+    fn test_analytics_props_match_schema() {
+        let mut stats = CommandStats::default();
+        stats.record(12, true);
+        assert!(analytics::validate("command_metrics", &analytics_props("keychain_store", &stats)).is_ok());
+    }
+}
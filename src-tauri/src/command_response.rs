@@ -0,0 +1,236 @@
+/// Command response envelope module
+///
+/// Commands returning `Result<T, String>` reject the frontend promise with a
+/// free-form message, so the frontend has to match strings to react to a
+/// failure. Migrated commands (keychain, notifications, connectivity) resolve
+/// with a `CommandResponse<T>` instead:
+///
+/// ```json
+/// { "ok": true, "data": "value", "error": null }
+/// { "ok": false, "data": null, "error": { "code": "KEYCHAIN_UNAVAILABLE", "message": "...", "retryable": false } }
+/// ```
+///
+/// Error codes are stable: the frontend branches on `error.code` (and may
+/// retry when `error.retryable` is set), the message is only meant for logs.
+/// Pages that negotiated native API version 1 (`api_version`) get the shapes
+/// from before the envelope: the command resolves with `data` and rejects
+/// with `error.message`.
+/// Invocations rejected before reaching the command (origin guard, command
+/// scope, rate limit, invoke signing) still reject the promise.
+
+use tauri::ipc::{InvokeResponseBody, IpcResponse};
+
+use crate::api_version;
+use crate::connectivity::ConnectivityError;
+use crate::AppError;
+
+/// Stable error code of a failed command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// An argument failed validation (key or value length, ...)
+    InvalidArgument,
+    /// The operation is not allowed through this command
+    Forbidden,
+    /// The keystore plugin is not available
+    KeychainUnavailable,
+    /// The platform keychain/keystore operation failed
+    KeychainFailed,
+    /// The keychain holds no value for the key
+    KeychainNotFound,
+    /// The server could not be reached in time
+    ConnectivityTimeout,
+    /// The connection to the server failed
+    ConnectivityFailed,
+    /// The platform notification API failed
+    NotificationFailed,
+    /// The configuration is invalid or unavailable
+    ConfigInvalid,
+    /// Unexpected internal error (I/O, serialization, runtime)
+    Internal,
+}
+
+impl ErrorCode {
+    /// Whether the same call may succeed if retried later
+    pub fn retryable(self) -> bool {
+        matches!(
+            self,
+            ErrorCode::KeychainFailed | ErrorCode::ConnectivityTimeout | ErrorCode::ConnectivityFailed
+        )
+    }
+}
+
+/// Error of a failed command
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct CommandError {
+    /// Stable error code
+    pub code: ErrorCode,
+    /// Human-readable description, for logs
+    pub message: String,
+    /// Whether the same call may succeed if retried later
+    pub retryable: bool,
+}
+
+impl CommandError {
+    /// Create an error, retryable according to its code
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        CommandError {
+            code,
+            message: message.into(),
+            retryable: code.retryable(),
+        }
+    }
+}
+
+impl From<ConnectivityError> for CommandError {
+    fn from(error: ConnectivityError) -> Self {
+        let code = match error {
+            ConnectivityError::Timeout => ErrorCode::ConnectivityTimeout,
+            ConnectivityError::Io(_) | ConnectivityError::MaxRetriesExceeded => ErrorCode::ConnectivityFailed,
+        };
+        CommandError::new(code, error.to_string())
+    }
+}
+
+impl From<AppError> for CommandError {
+    fn from(error: AppError) -> Self {
+        let code = match &error {
+            AppError::Keychain(_) => ErrorCode::KeychainFailed,
+            AppError::Notification(_) => ErrorCode::NotificationFailed,
+            AppError::Connectivity(ConnectivityError::Timeout) => ErrorCode::ConnectivityTimeout,
+            AppError::Connectivity(_) => ErrorCode::ConnectivityFailed,
+            AppError::Config(_) => ErrorCode::ConfigInvalid,
+            AppError::InvalidArgument(_) => ErrorCode::InvalidArgument,
+            AppError::Denied(_) => ErrorCode::Forbidden,
+            AppError::Network(_) => ErrorCode::ConnectivityFailed,
+            AppError::Tauri(_)
+            | AppError::Io(_)
+            | AppError::Serialization(_)
+            | AppError::Database(_)
+            | AppError::Storage(_)
+            | AppError::Auth(_)
+            | AppError::Platform(_) => ErrorCode::Internal,
+        };
+        CommandError::new(code, error.to_string())
+    }
+}
+
+/// Envelope returned by migrated commands
+///
+/// Serialized for the API version of the page when sent to the webview.
+#[derive(Debug, Clone, PartialEq, Eq, specta::Type)]
+pub struct CommandResponse<T> {
+    /// Whether the command succeeded
+    pub ok: bool,
+    /// Result of the command, if it succeeded
+    pub data: Option<T>,
+    /// Error of the command, if it failed
+    pub error: Option<CommandError>,
+}
+
+impl<T> From<Result<T, CommandError>> for CommandResponse<T> {
+    fn from(result: Result<T, CommandError>) -> Self {
+        match result {
+            Ok(data) => CommandResponse {
+                ok: true,
+                data: Some(data),
+                error: None,
+            },
+            Err(error) => CommandResponse {
+                ok: false,
+                data: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// Serialized form of the envelope
+#[derive(serde::Serialize)]
+struct Envelope<'a, T> {
+    ok: bool,
+    data: &'a Option<T>,
+    error: &'a Option<CommandError>,
+}
+
+impl<T: serde::Serialize> CommandResponse<T> {
+    /// Response in the shape of an API version
+    ///
+    /// # Returns
+    ///
+    /// Returns the JSON the command resolves with, or the message it rejects
+    /// with (failures of API version 1).
+    pub fn for_version(&self, version: u32) -> Result<serde_json::Value, String> {
+        let value = if version >= 2 {
+            serde_json::to_value(Envelope {
+                ok: self.ok,
+                data: &self.data,
+                error: &self.error,
+            })
+        } else if let Some(error) = &self.error {
+            return Err(error.message.clone());
+        } else {
+            serde_json::to_value(&self.data)
+        };
+        value.map_err(|e| format!("Failed to serialize response: {}", e))
+    }
+}
+
+impl<T: serde::Serialize> IpcResponse for CommandResponse<T> {
+    fn body(self) -> tauri::Result<InvokeResponseBody> {
+        if !self.ok {
+            crate::tracing_bridge::fail_command();
+        }
+        self.for_version(api_version::current())
+            .map(|value| InvokeResponseBody::Json(value.to_string()))
+            .map_err(|message| std::io::Error::other(message).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_success_envelope() {
+        let response = CommandResponse::from(Ok::<_, CommandError>(true));
+        assert_eq!(
+            response.for_version(2).unwrap(),
+            serde_json::json!({ "ok": true, "data": true, "error": null })
+        );
+        assert_eq!(response.for_version(1).unwrap(), serde_json::json!(true));
+    }
+
+    #[test]
+    fn test_error_envelope() {
+        let response = CommandResponse::<()>::from(Err(CommandError::new(ErrorCode::KeychainUnavailable, "Keystore plugin not initialized")));
+        assert_eq!(
+            response.for_version(2).unwrap(),
+            serde_json::json!({
+                "ok": false,
+                "data": null,
+                "error": { "code": "KEYCHAIN_UNAVAILABLE", "message": "Keystore plugin not initialized", "retryable": false }
+            })
+        );
+        assert_eq!(response.for_version(1), Err("Keystore plugin not initialized".to_string()));
+    }
+
+    #[test]
+    fn test_connectivity_error_codes() {
+        let timeout = CommandError::from(ConnectivityError::Timeout);
+        assert_eq!(timeout.code, ErrorCode::ConnectivityTimeout);
+        assert!(timeout.retryable);
+        let refused = CommandError::from(ConnectivityError::Io(std::io::ErrorKind::ConnectionRefused.into()));
+        assert_eq!(refused.code, ErrorCode::ConnectivityFailed);
+        assert!(refused.retryable);
+    }
+
+    #[test]
+    fn test_keychain_not_found_is_not_retryable() {
+        assert!(crate::constants::helpers::is_keychain_not_found(&"The operation couldn't be completed. (OSStatus error -25300.)"));
+        assert!(crate::constants::helpers::is_keychain_not_found(&"No cipher data found in SharedPreferences"));
+        assert!(!crate::constants::helpers::is_keychain_not_found(&"Authentication failed"));
+        assert!(!ErrorCode::KeychainNotFound.retryable());
+        assert!(ErrorCode::KeychainFailed.retryable());
+    }
+}
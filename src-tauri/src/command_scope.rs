@@ -0,0 +1,356 @@
+/// Per-command capability scoping module
+///
+/// Each registered command declares a scope (`COMMAND_SCOPES`), and a policy
+/// controls which scopes are enabled, so a compromised or staging frontend
+/// cannot call every native API:
+/// - the build profile sets the scopes available to the frontend
+///   (`ELULIB_COMMAND_SCOPES` at build time, e.g. `keychain,notifications`;
+///   all scopes when not set)
+/// - the `command_scopes` remote config key can disable scopes at runtime; it
+///   can only narrow the build profile, never enable more scopes
+///
+/// The `core` scope (connectivity, app state, session, config) is always
+/// enabled. Commands without a declared scope are rejected.
+
+use std::collections::BTreeSet;
+
+use tauri::ipc::Invoke;
+use tauri::{Manager, Runtime};
+
+use crate::constants;
+use crate::remote_config::{keys, ConfigStore};
+use crate::{AppError, AppResult};
+
+/// Group of native APIs a command belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    /// Connectivity, app state, session, updates and config (always enabled)
+    Core,
+    /// Secure storage (keychain and encrypted database)
+    Keychain,
+    /// Notifications and badge
+    Notifications,
+    /// Device information, permissions, sensors and recording
+    Device,
+    /// Crash reports, analytics and performance metrics
+    Diagnostics,
+}
+
+impl Scope {
+    /// Scopes that can be enabled or disabled by the policy
+    pub const CONFIGURABLE: [Scope; 4] = [
+        Scope::Keychain,
+        Scope::Notifications,
+        Scope::Device,
+        Scope::Diagnostics,
+    ];
+
+    /// Parse a scope name (`keychain`, `notifications`, ...)
+    pub fn from_name(name: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(name.trim().to_string())).ok()
+    }
+}
+
+/// Scope of each registered command
+pub const COMMAND_SCOPES: &[(&str, Scope)] = &[
+    #[cfg(feature = "module-keychain")]
+    ("keychain_store", Scope::Keychain),
+    #[cfg(feature = "module-keychain")]
+    ("keychain_retrieve", Scope::Keychain),
+    #[cfg(feature = "module-keychain")]
+    ("keychain_remove", Scope::Keychain),
+    #[cfg(feature = "module-keychain")]
+    ("keychain_exists", Scope::Keychain),
+    #[cfg(feature = "module-connectivity")]
+    ("check_connectivity", Scope::Core),
+    #[cfg(feature = "module-connectivity")]
+    ("check_connectivity_quick", Scope::Core),
+    ("permission_check", Scope::Device),
+    ("permission_request", Scope::Device),
+    ("permission_status_all", Scope::Device),
+    ("start_recording", Scope::Device),
+    ("stop_recording", Scope::Device),
+    ("device_security_status", Scope::Device),
+    ("device_integrity", Scope::Device),
+    ("device_info", Scope::Device),
+    ("attest_device", Scope::Device),
+    ("accessibility_status", Scope::Device),
+    ("storage_info", Scope::Device),
+    ("network_interfaces", Scope::Device),
+    ("carrier_info", Scope::Device),
+    ("install_info", Scope::Device),
+    ("update_app_state", Scope::Core),
+    ("get_restored_state", Scope::Core),
+    ("session_activity", Scope::Core),
+    ("unlock_session", Scope::Core),
+    ("session_locked", Scope::Core),
+    ("check_app_update", Scope::Core),
+    ("start_flexible_update", Scope::Core),
+    ("start_immediate_update", Scope::Core),
+    ("complete_flexible_update", Scope::Core),
+    ("get_config", Scope::Core),
+    ("feature_enabled", Scope::Core),
+    ("get_last_crash", Scope::Diagnostics),
+    ("send_crash_reports", Scope::Diagnostics),
+    ("discard_crash_reports", Scope::Diagnostics),
+    ("set_crash_reporting_enabled", Scope::Diagnostics),
+    ("launched_after_crash", Scope::Diagnostics),
+    ("track_event", Scope::Diagnostics),
+    ("set_analytics_enabled", Scope::Diagnostics),
+    ("analytics_enabled", Scope::Diagnostics),
+    ("startup_metrics", Scope::Diagnostics),
+    ("rate_limit_metrics", Scope::Diagnostics),
+    ("export_security_log", Scope::Diagnostics),
+    ("device_binding_assertion", Scope::Keychain),
+    ("device_policy", Scope::Core),
+    ("ephemeral_store", Scope::Keychain),
+    ("ephemeral_get", Scope::Keychain),
+    ("log_storage_usage", Scope::Diagnostics),
+    ("set_log_level", Scope::Diagnostics),
+    ("get_log_level", Scope::Diagnostics),
+    ("export_logs", Scope::Diagnostics),
+    ("set_log_shipping", Scope::Diagnostics),
+    ("log_shipping_status", Scope::Diagnostics),
+    ("generate_diagnostics", Scope::Diagnostics),
+    ("add_breadcrumb", Scope::Diagnostics),
+    ("read_recent_logs", Scope::Diagnostics),
+    ("get_metrics", Scope::Diagnostics),
+    ("clear_asset_cache", Scope::Core),
+    ("sync_now", Scope::Core),
+    ("file_write", Scope::Device),
+    ("file_read", Scope::Device),
+    ("file_delete", Scope::Device),
+    ("file_list", Scope::Device),
+    ("download_start", Scope::Device),
+    ("download_pause", Scope::Device),
+    ("download_resume", Scope::Device),
+    ("download_cancel", Scope::Device),
+    ("download_list", Scope::Device),
+    ("upload_enqueue", Scope::Device),
+    ("upload_retry", Scope::Device),
+    ("upload_cancel", Scope::Device),
+    ("upload_list", Scope::Device),
+    ("cache_image", Scope::Core),
+    ("read_cached_image", Scope::Core),
+    ("get_image_cache_stats", Scope::Diagnostics),
+    ("clear_image_cache", Scope::Core),
+    ("prefs_get", Scope::Core),
+    ("prefs_set", Scope::Core),
+    ("prefs_remove", Scope::Core),
+    ("export_user_data", Scope::Core),
+    ("wipe_all_local_data", Scope::Core),
+    ("open_attachment", Scope::Device),
+    ("vault_import", Scope::Device),
+    ("vault_list", Scope::Device),
+    ("vault_read", Scope::Device),
+    ("vault_open", Scope::Device),
+    ("vault_remove", Scope::Device),
+    ("prefetch_set", Scope::Device),
+    ("prefetch_status", Scope::Device),
+    ("draft_save", Scope::Core),
+    ("draft_load", Scope::Core),
+    ("draft_delete", Scope::Core),
+    #[cfg(feature = "dev-mock")]
+    ("dev_mock_state", Scope::Diagnostics),
+    #[cfg(feature = "dev-mock")]
+    ("dev_mock_push", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("invoke_recording_start", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("invoke_recording_stop", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("open_devtools", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("reload_webview", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("navigate", Scope::Diagnostics),
+    ("environment_get", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("environment_set", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("debug_set_offline", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("debug_fake_push", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("debug_test_notifications", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("debug_corrupt_keychain", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("debug_crash", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("simulate_push", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("benchmark_keychain", Scope::Diagnostics),
+    #[cfg(debug_assertions)]
+    ("benchmark_ping", Scope::Diagnostics),
+    #[cfg(feature = "module-realtime")]
+    ("ws_connect", Scope::Core),
+    #[cfg(feature = "module-realtime")]
+    ("ws_send", Scope::Core),
+    #[cfg(feature = "module-realtime")]
+    ("ws_close", Scope::Core),
+    #[cfg(feature = "module-realtime")]
+    ("ws_status", Scope::Core),
+    ("db_query_packed", Scope::Keychain),
+    ("negotiate_api", Scope::Core),
+    ("outbox_ready", Scope::Core),
+    ("outbox_ack", Scope::Core),
+    #[cfg(feature = "module-sse")]
+    ("sse_subscribe", Scope::Core),
+    #[cfg(feature = "module-sse")]
+    ("sse_unsubscribe", Scope::Core),
+    #[cfg(feature = "module-sse")]
+    ("sse_subscriptions", Scope::Core),
+    #[cfg(feature = "module-notifications")]
+    ("get_launch_payload", Scope::Notifications),
+    #[cfg(feature = "module-shortcuts")]
+    ("donate_shortcut", Scope::Core),
+    ("acknowledge_inbox", Scope::Notifications),
+    ("get_badge_reset_policy", Scope::Notifications),
+    ("set_badge_reset_policy", Scope::Notifications),
+    ("current_locale", Scope::Core),
+    #[cfg(feature = "module-notifications")]
+    ("show_notification", Scope::Notifications),
+    #[cfg(feature = "module-notifications")]
+    ("request_notification_permission", Scope::Notifications),
+    #[cfg(feature = "module-notifications")]
+    ("check_notification_permission", Scope::Notifications),
+    #[cfg(feature = "module-notifications")]
+    ("is_notification_supported", Scope::Notifications),
+    ("signing_handshake", Scope::Core),
+    ("db_execute", Scope::Keychain),
+    ("db_query", Scope::Keychain),
+    ("claim_session_token", Scope::Keychain),
+    ("auth_login", Scope::Keychain),
+    ("auth_logout", Scope::Keychain),
+    ("clipboard_copy", Scope::Device),
+    ("passkey_register", Scope::Keychain),
+    ("passkey_authenticate", Scope::Keychain),
+];
+
+/// Declared scope of a command
+pub fn scope_of(command: &str) -> Option<Scope> {
+    COMMAND_SCOPES
+        .iter()
+        .find(|(name, _)| *name == command)
+        .map(|(_, scope)| *scope)
+}
+
+/// Parse a comma-separated list of scope names, ignoring unknown names
+pub fn parse_scopes(list: &str) -> BTreeSet<Scope> {
+    list.split(',')
+        .filter(|name| !name.trim().is_empty())
+        .filter_map(|name| {
+            let scope = Scope::from_name(name);
+            if scope.is_none() {
+                tracing::warn!("Ignoring unknown command scope: {}", name.trim());
+            }
+            scope
+        })
+        .collect()
+}
+
+/// Scopes enabled by the build profile
+pub fn build_scopes() -> BTreeSet<Scope> {
+    match constants::COMMAND_SCOPES {
+        Some(list) => parse_scopes(list),
+        None => Scope::CONFIGURABLE.into_iter().collect(),
+    }
+}
+
+/// Effective enabled scopes
+///
+/// # Arguments
+///
+/// * `build` - Scopes enabled by the build profile
+/// * `remote` - Scopes enabled by remote config, if set
+pub fn enabled_scopes(build: &BTreeSet<Scope>, remote: Option<&BTreeSet<Scope>>) -> BTreeSet<Scope> {
+    let mut enabled: BTreeSet<Scope> = match remote {
+        Some(remote) => build.intersection(remote).copied().collect(),
+        None => build.clone(),
+    };
+    enabled.insert(Scope::Core);
+    enabled
+}
+
+/// Check that a command may be invoked with the enabled scopes
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command scope is enabled, or an error if
+/// the scope is disabled or the command has no declared scope.
+pub fn check(command: &str, enabled: &BTreeSet<Scope>) -> AppResult<()> {
+    match scope_of(command) {
+        Some(scope) if enabled.contains(&scope) => Ok(()),
+        Some(scope) => Err(AppError::Denied(format!(
+            "Command {} is not allowed: scope {:?} is disabled",
+            command, scope
+        ))),
+        None => Err(AppError::Denied(format!("Command {} is not allowed: no declared scope", command))),
+    }
+}
+
+/// Validate an IPC invocation against the scope policy
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command may run, or an error to reject the
+/// invocation with.
+pub fn check_invoke<R: Runtime>(invoke: &Invoke<R>) -> AppResult<()> {
+    let remote: Option<BTreeSet<Scope>> = invoke
+        .message
+        .webview_ref()
+        .app_handle()
+        .state::<ConfigStore>()
+        .get_typed::<Vec<String>>(keys::COMMAND_SCOPES)
+        .map(|names| parse_scopes(&names.join(",")));
+
+    let enabled = enabled_scopes(&build_scopes(), remote.as_ref());
+    let result = check(invoke.message.command(), &enabled);
+    if let Err(e) = &result {
+        tracing::warn!("{}", e);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands_are_declared_once() {
+        let names: BTreeSet<&str> = COMMAND_SCOPES.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names.len(), COMMAND_SCOPES.len());
+    }
+
+    #[test]
+    fn test_parse_scopes() {
+        let scopes = parse_scopes("keychain, notifications,unknown,");
+        assert_eq!(scopes, BTreeSet::from([Scope::Keychain, Scope::Notifications]));
+        assert!(parse_scopes("").is_empty());
+    }
+
+    #[test]
+    fn test_remote_config_only_narrows_build_profile() {
+        let build = BTreeSet::from([Scope::Keychain, Scope::Notifications]);
+        let remote = BTreeSet::from([Scope::Notifications, Scope::Diagnostics]);
+        assert_eq!(
+            enabled_scopes(&build, Some(&remote)),
+            BTreeSet::from([Scope::Core, Scope::Notifications])
+        );
+        assert_eq!(
+            enabled_scopes(&build, None),
+            BTreeSet::from([Scope::Core, Scope::Keychain, Scope::Notifications])
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let enabled = enabled_scopes(&BTreeSet::from([Scope::Notifications]), None);
+        assert!(check("acknowledge_inbox", &enabled).is_ok());
+        assert!(check("get_config", &enabled).is_ok(), "Core is always enabled");
+        assert!(check("keychain_retrieve", &enabled).is_err());
+        assert!(check("undeclared_command", &enabled).is_err());
+    }
+}
@@ -1,16 +1,104 @@
-/// Tauri commands for keychain operations
+/// Tauri commands exposed to the remote frontend
 ///
 /// These commands allow the remote PHP frontend to interact with
-/// the device keychain/keystore securely.
+/// the device keychain/keystore securely, and to access native device
+/// features (permissions, device information, audio recording, ...).
 ///
 /// Note: The keystore plugin already provides commands, but we wrap them
 /// here for easier access from remote frontends and better error handling.
+///
+/// Each command runs in a `command` tracing span, so the logs of the native
+/// calls it makes are attributed to it and its duration is logged.
 
-use tauri::AppHandle;
-use tauri_plugin_keystore::{KeystoreExt, StoreRequest, RetrieveRequest, RemoveRequest};
+use tauri::{AppHandle, Manager, State};
+#[cfg(feature = "module-keychain")]
+use tauri::Runtime;
+#[cfg(feature = "module-keychain")]
+use tauri_plugin_keystore::{StoreRequest, RetrieveRequest, RemoveRequest};
 
-use crate::constants::helpers;
-use crate::connectivity;
+use crate::accessibility::{self, AccessibilityStatus};
+use crate::analytics::{self, Analytics, PropValue};
+use crate::api_version::{self, ApiNegotiation};
+use crate::app_update::{self, UpdateInfo};
+use crate::asset_cache;
+use crate::attachments::{self, AttachmentType};
+use crate::attestation::{self, AttestationError, AttestationToken};
+use crate::audit_log::{self, AuditExport, AuthMethod, SecurityEvent};
+use crate::auth::{self, AuthSession, ProviderConfig};
+use crate::audio::{AudioRecorder, RecordingInfo};
+use crate::badge::{self, BadgeResetPolicy};
+#[cfg(debug_assertions)]
+use crate::benchmark::{self, KeychainBenchmark};
+use crate::breadcrumbs;
+use crate::carrier::{self, CarrierInfo};
+use crate::clipboard;
+use crate::command_metrics::{self, CommandMetrics};
+#[cfg(any(feature = "module-keychain", feature = "module-connectivity"))]
+use crate::command_response::{CommandError, CommandResponse, ErrorCode};
+use crate::constants::{self, helpers};
+#[cfg(feature = "module-connectivity")]
+use crate::connectivity::{self, ConnectivityProber};
+use crate::crash::{self, CrashState, CrashSummary};
+use crate::data_export::{self, ExportedData};
+use crate::database::{self, Database};
+#[cfg(debug_assertions)]
+use crate::debug_tools;
+use crate::device_binding::{self, BindingAssertion};
+use crate::device_policy::{self, ActivePolicy, DevicePolicy};
+use crate::device_info::{self, DeviceInfo};
+use crate::device_security::{self, DeviceSecurityStatus};
+use crate::diagnostics::{self, DiagnosticsReport, ExportedLogs};
+use crate::downloads::{self, Download, DownloadManager};
+use crate::drafts::{self, Draft};
+use crate::environment::{self, EnvironmentStatus};
+use crate::ephemeral::EphemeralStore;
+use crate::feature_flags;
+use crate::file_storage::{self, FileEntry};
+use crate::image_cache::{self, ImageCache, ImageCacheStats, ImageHandle};
+use crate::in_app_update::{self, UpdateMode};
+use crate::install::{InstallInfo, InstallState};
+use crate::invoke_signing::InvokeSigning;
+#[cfg(debug_assertions)]
+use crate::invoke_recorder::{self, RecordingSummary};
+use crate::integrity::{self, IntegrityReport};
+#[cfg(feature = "module-notifications")]
+use crate::launch::{self, LaunchPayload};
+use crate::locale::{self, LocaleInfo};
+use crate::log_level::{self, LogLevelSetting};
+use crate::log_shipping::{self, LogShippingStatus};
+use crate::logging::{self, LogLine, LogStorageUsage};
+use crate::network_info::{self, NetworkInterfaces};
+use crate::outbox::{self, OutboxMessage};
+use crate::payload_encoding::Packed;
+use crate::passkey::{self, AuthenticationOptions, AuthenticationResponse, RegistrationOptions, RegistrationResponse};
+use crate::permissions::{self, Permission, PermissionEntry, PermissionStatus};
+#[cfg(feature = "module-keychain")]
+use crate::plugins::{self, KeystoreExt};
+use crate::prefetch::{self, PrefetchItem, PrefetchStatus};
+use crate::prefs::{PrefValue, Prefs};
+#[cfg(debug_assertions)]
+use crate::push::{self, PushRouting};
+use crate::rate_limit::{RateLimitMetrics, RateLimiter};
+use crate::redact;
+use crate::remote_config::ConfigStore;
+#[cfg(feature = "module-keychain")]
+use crate::session_handoff;
+use crate::session_handoff::SessionHandoff;
+use crate::session_lock::{self, SessionLock};
+#[cfg(feature = "module-shortcuts")]
+use crate::shortcuts::{self, Shortcut};
+#[cfg(feature = "module-sse")]
+use crate::sse::{self, SseClient, SseStatus};
+use crate::startup_metrics::{self, StartupMetrics};
+use crate::state_restore::{AppStateSnapshot, StateStore};
+use crate::storage_info::{self, StorageInfo};
+use crate::sync::{self, SyncReport, SyncTrigger};
+use crate::uploads::{self, Upload, UploadAuth, UploadQueue};
+use crate::vault::{self, Vault, VaultItem};
+#[cfg(feature = "module-realtime")]
+use crate::websocket::{self, WebSocketClient, WebSocketStatus};
+use crate::wipe::{self, WipeReport};
+use crate::{AppError, AppResult};
 
 /// Store a value in the keychain
 ///
@@ -22,22 +110,48 @@ use crate::connectivity;
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` on success, or an error if the operation fails.
+/// Returns a `CommandResponse` without data on success, or with an
+/// `INVALID_ARGUMENT`, `KEYCHAIN_UNAVAILABLE` or `KEYCHAIN_FAILED` error.
+/// Keys reserved to the native layer (session token, database key, ...)
+/// cannot be overwritten (`FORBIDDEN`).
+///
+/// # Examples
+///
+/// ```javascript
+/// const response = await invoke('keychain_store', { key: 'device_id', value: id });
+/// if (!response.ok && response.error.retryable) {
+///   scheduleRetry();
+/// }
+/// ```
+#[cfg(feature = "module-keychain")]
 #[tauri::command]
-pub async fn keychain_store(app: AppHandle, key: String, value: String) -> Result<(), String> {
-    log::info!("Storing value in keychain for key: {}", key);
+#[specta::specta]
+pub async fn keychain_store<R: Runtime>(app: AppHandle<R>, key: String, value: String) -> CommandResponse<()> {
+    store_value(&app, key, value).into()
+}
+
+/// Store a value in the keychain (`keychain_store`)
+#[cfg(feature = "module-keychain")]
+fn store_value<R: Runtime>(app: &AppHandle<R>, key: String, value: String) -> Result<(), CommandError> {
+    redact::register_key_name(&key);
+    tracing::info!("Storing value in keychain for key: {}", redact::key_name(&key));
+    redact::register_secret(&value);
     
     // Validate input lengths
     helpers::validate_keychain_key(&key)
         .map_err(|e| {
-            log::warn!("Keychain store validation failed for key: {}", e);
-            e
+            tracing::warn!("Keychain store validation failed for key: {}", e);
+            CommandError::new(ErrorCode::InvalidArgument, e.to_string())
         })?;
     helpers::validate_keychain_value(&value)
         .map_err(|e| {
-            log::warn!("Keychain store validation failed for value: {}", e);
-            e
+            tracing::warn!("Keychain store validation failed for value: {}", e);
+            CommandError::new(ErrorCode::InvalidArgument, e.to_string())
         })?;
+    if helpers::is_reserved_keychain_key(&key) {
+        tracing::warn!("Rejected keychain store of a reserved key");
+        return Err(CommandError::new(ErrorCode::Forbidden, "This keychain key is reserved by the app"));
+    }
     
     // For mobile, StoreRequest only needs the value
     // The key will be used as identifier
@@ -45,12 +159,13 @@ pub async fn keychain_store(app: AppHandle, key: String, value: String) -> Resul
         value: helpers::key_value_pair(&key, &value),
     };
     
+    plugins::ensure_keystore(app).map_err(|e| CommandError::new(ErrorCode::KeychainUnavailable, e.to_string()))?;
     app.keystore().store(request)
         .map_err(|e| {
-            log::error!("Failed to store value in keychain: {}", e);
-            helpers::keychain_store_error(&e)
+            tracing::error!("Failed to store value in keychain: {}", e);
+            CommandError::new(ErrorCode::KeychainFailed, helpers::keychain_store_error(&e))
         })?;
-    log::info!("Successfully stored value for key: {}", key);
+    tracing::info!("Successfully stored value for key: {}", redact::key_name(&key));
     Ok(())
 }
 
@@ -63,18 +178,41 @@ pub async fn keychain_store(app: AppHandle, key: String, value: String) -> Resul
 ///
 /// # Returns
 ///
-/// Returns the stored value as a String, or an error if the key doesn't exist
-/// or the operation fails.
+/// Returns a `CommandResponse` with the stored value, or with an
+/// `INVALID_ARGUMENT`, `KEYCHAIN_UNAVAILABLE` or `KEYCHAIN_FAILED` error, or
+/// with a `KEYCHAIN_NOT_FOUND` error if the key doesn't exist. The session token and the other keys
+/// reserved to the native layer cannot be retrieved this way (`FORBIDDEN`,
+/// see `claim_session_token`).
+#[cfg(feature = "module-keychain")]
 #[tauri::command]
-pub async fn keychain_retrieve(app: AppHandle, key: String) -> Result<String, String> {
-    log::info!("Retrieving value from keychain for key: {}", key);
+#[specta::specta]
+pub async fn keychain_retrieve<R: Runtime>(app: AppHandle<R>, key: String) -> CommandResponse<String> {
+    retrieve_value(&app, key).into()
+}
+
+/// Retrieve a value from the keychain (`keychain_retrieve`)
+#[cfg(feature = "module-keychain")]
+fn retrieve_value<R: Runtime>(app: &AppHandle<R>, key: String) -> Result<String, CommandError> {
+    redact::register_key_name(&key);
+    tracing::info!("Retrieving value from keychain for key: {}", redact::key_name(&key));
     
     // Validate input length
     helpers::validate_keychain_key(&key)
         .map_err(|e| {
-            log::warn!("Keychain retrieve validation failed for key: {}", e);
-            e
+            tracing::warn!("Keychain retrieve validation failed for key: {}", e);
+            CommandError::new(ErrorCode::InvalidArgument, e.to_string())
         })?;
+    if key == constants::SESSION_TOKEN_KEY {
+        tracing::warn!("Rejected keychain retrieve of the session token");
+        return Err(CommandError::new(
+            ErrorCode::Forbidden,
+            "The session token is only available through claim_session_token",
+        ));
+    }
+    if !session_handoff::is_retrievable(&key) {
+        tracing::warn!("Rejected keychain retrieve of a reserved key");
+        return Err(CommandError::new(ErrorCode::Forbidden, "This keychain key is reserved by the app"));
+    }
     
     // Clone is necessary: RetrieveRequest requires owned Strings for both service and user fields
     // We use the same key for both fields, so we clone for service and move key into user
@@ -83,14 +221,24 @@ pub async fn keychain_retrieve(app: AppHandle, key: String) -> Result<String, St
         user: key,
     };
     
+    plugins::ensure_keystore(app).map_err(|e| CommandError::new(ErrorCode::KeychainUnavailable, e.to_string()))?;
     let response = app.keystore().retrieve(request)
         .map_err(|e| {
-            log::error!("Failed to retrieve value from keychain: {}", e);
-            helpers::keychain_retrieve_error(&e)
+            if helpers::is_keychain_not_found(&e) {
+                tracing::info!("No value in keychain for key");
+                return CommandError::new(ErrorCode::KeychainNotFound, helpers::keychain_retrieve_error(&e));
+            }
+            tracing::error!("Failed to retrieve value from keychain: {}", e);
+            CommandError::new(ErrorCode::KeychainFailed, helpers::keychain_retrieve_error(&e))
         })?;
+    let value = response.value.ok_or_else(|| {
+        tracing::info!("No value in keychain for key");
+        CommandError::new(ErrorCode::KeychainNotFound, "No value in keychain for this key")
+    })?;
     
-    log::info!("Successfully retrieved value for key");
-    Ok(response.value.unwrap_or_default())
+    tracing::info!("Successfully retrieved value for key");
+    redact::register_secret(&value);
+    Ok(value)
 }
 
 /// Remove a value from the keychain
@@ -102,17 +250,32 @@ pub async fn keychain_retrieve(app: AppHandle, key: String) -> Result<String, St
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` on success, or an error if the operation fails.
+/// Returns a `CommandResponse` without data on success, or with an
+/// `INVALID_ARGUMENT`, `KEYCHAIN_UNAVAILABLE` or `KEYCHAIN_FAILED` error.
+/// Keys reserved to the native layer cannot be removed (`FORBIDDEN`).
+#[cfg(feature = "module-keychain")]
 #[tauri::command]
-pub async fn keychain_remove(app: AppHandle, key: String) -> Result<(), String> {
-    log::info!("Removing value from keychain for key: {}", key);
+#[specta::specta]
+pub async fn keychain_remove<R: Runtime>(app: AppHandle<R>, key: String) -> CommandResponse<()> {
+    remove_value(&app, key).into()
+}
+
+/// Remove a value from the keychain (`keychain_remove`)
+#[cfg(feature = "module-keychain")]
+fn remove_value<R: Runtime>(app: &AppHandle<R>, key: String) -> Result<(), CommandError> {
+    redact::register_key_name(&key);
+    tracing::info!("Removing value from keychain for key: {}", redact::key_name(&key));
     
     // Validate input length
     helpers::validate_keychain_key(&key)
         .map_err(|e| {
-            log::warn!("Keychain remove validation failed for key: {}", e);
-            e
+            tracing::warn!("Keychain remove validation failed for key: {}", e);
+            CommandError::new(ErrorCode::InvalidArgument, e.to_string())
         })?;
+    if helpers::is_reserved_keychain_key(&key) {
+        tracing::warn!("Rejected keychain remove of a reserved key");
+        return Err(CommandError::new(ErrorCode::Forbidden, "This keychain key is reserved by the app"));
+    }
     
     // Clone is necessary: RemoveRequest requires owned Strings for both service and user fields
     // We use the same key for both fields, so we clone for service and move key into user
@@ -121,12 +284,13 @@ pub async fn keychain_remove(app: AppHandle, key: String) -> Result<(), String>
         user: key,
     };
     
+    plugins::ensure_keystore(app).map_err(|e| CommandError::new(ErrorCode::KeychainUnavailable, e.to_string()))?;
     app.keystore().remove(request)
         .map_err(|e| {
-            log::error!("Failed to remove value from keychain: {}", e);
-            helpers::keychain_remove_error(&e)
+            tracing::error!("Failed to remove value from keychain: {}", e);
+            CommandError::new(ErrorCode::KeychainFailed, helpers::keychain_remove_error(&e))
         })?;
-    log::info!("Successfully removed value for key");
+    tracing::info!("Successfully removed value for key");
     Ok(())
 }
 
@@ -139,35 +303,36 @@ pub async fn keychain_remove(app: AppHandle, key: String) -> Result<(), String>
 ///
 /// # Returns
 ///
-/// Returns `true` if the key exists, `false` otherwise.
+/// Returns a `CommandResponse` with `true` if the key exists, `false`
+/// otherwise, or with an `INVALID_ARGUMENT` or `KEYCHAIN_UNAVAILABLE` error.
+#[cfg(feature = "module-keychain")]
 #[tauri::command]
-pub async fn keychain_exists(app: AppHandle, key: String) -> Result<bool, String> {
-    log::debug!("Checking if key exists in keychain: {}", key);
+#[specta::specta]
+pub async fn keychain_exists<R: Runtime>(app: AppHandle<R>, key: String) -> CommandResponse<bool> {
+    value_exists(&app, key).into()
+}
+
+/// Check if a key exists in the keychain (`keychain_exists`)
+#[cfg(feature = "module-keychain")]
+fn value_exists<R: Runtime>(app: &AppHandle<R>, key: String) -> Result<bool, CommandError> {
+    redact::register_key_name(&key);
+    tracing::debug!("Checking if key exists in keychain: {}", redact::key_name(&key));
     
     // Validate input length
     helpers::validate_keychain_key(&key)
         .map_err(|e| {
-            log::warn!("Keychain exists validation failed for key: {}", e);
-            e
+            tracing::warn!("Keychain exists validation failed for key: {}", e);
+            CommandError::new(ErrorCode::InvalidArgument, e.to_string())
         })?;
     
-    // Clone is necessary: RetrieveRequest requires owned Strings for both service and user fields
-    // We use the same key for both fields, so we clone for service and move key into user
-    let request = RetrieveRequest {
-        service: key.clone(),
-        user: key,
-    };
-    
-    match app.keystore().retrieve(request) {
-        Ok(_) => {
-            log::debug!("Key exists in keychain");
-            Ok(true)
-        }
-        Err(_) => {
-            log::debug!("Key does not exist in keychain");
-            Ok(false)
-        }
+    let exists = plugins::keychain_contains(app, &key)
+        .map_err(|e| CommandError::new(ErrorCode::KeychainUnavailable, e.to_string()))?;
+    if exists {
+        tracing::debug!("Key exists in keychain");
+    } else {
+        tracing::debug!("Key does not exist in keychain");
     }
+    Ok(exists)
 }
 
 /// Check connectivity to the application server
@@ -177,28 +342,35 @@ pub async fn keychain_exists(app: AppHandle, key: String) -> Result<bool, String
 ///
 /// # Returns
 ///
-/// Returns `true` if connectivity is available, `false` otherwise.
-/// Returns an error string if an unexpected error occurs.
+/// Returns a `CommandResponse` with `true` if connectivity is available, or
+/// with a `CONNECTIVITY_TIMEOUT` or `CONNECTIVITY_FAILED` error.
 ///
 /// # Examples
 ///
 /// ```javascript
-/// const isConnected = await invoke('check_connectivity');
-/// if (isConnected) {
+/// const response = await invoke('check_connectivity');
+/// if (response.ok) {
 ///   console.log('Connected to server');
+/// } else if (response.error.code === 'CONNECTIVITY_TIMEOUT') {
+///   showSlowNetworkBanner();
 /// }
 /// ```
+#[cfg(feature = "module-connectivity")]
 #[tauri::command]
-pub async fn check_connectivity() -> Result<bool, String> {
-    log::info!("Connectivity check requested via command");
+#[specta::specta]
+pub async fn check_connectivity(app: AppHandle) -> CommandResponse<bool> {
+    tracing::info!("Connectivity check requested via command");
     
-    connectivity::check_connectivity()
-        .await
+    let target = app.state::<ConfigStore>().connectivity_target();
+    let prober = app.state::<ConnectivityProber>().get();
+    let result = connectivity::check_connectivity_with(prober.as_ref(), &target).await;
+    connectivity::report(&app, matches!(result, Ok(true)));
+    result
         .map_err(|e| {
-            let error_msg = format!("Connectivity check failed: {}", e);
-            log::error!("{}", error_msg);
-            error_msg
+            tracing::error!("Connectivity check failed: {}", e);
+            CommandError::from(e)
         })
+        .into()
 }
 
 /// Perform a quick connectivity check without retries
@@ -208,23 +380,2932 @@ pub async fn check_connectivity() -> Result<bool, String> {
 ///
 /// # Returns
 ///
-/// Returns `true` if connectivity is available, `false` otherwise.
-/// Returns an error string if an unexpected error occurs.
+/// Returns a `CommandResponse` with `true` if connectivity is available, or
+/// with a `CONNECTIVITY_TIMEOUT` or `CONNECTIVITY_FAILED` error.
 ///
 /// # Examples
 ///
 /// ```javascript
-/// const isConnected = await invoke('check_connectivity_quick');
+/// const { ok } = await invoke('check_connectivity_quick');
 /// ```
+#[cfg(feature = "module-connectivity")]
 #[tauri::command]
-pub async fn check_connectivity_quick() -> Result<bool, String> {
-    log::info!("Quick connectivity check requested via command");
+#[specta::specta]
+pub async fn check_connectivity_quick(app: AppHandle) -> CommandResponse<bool> {
+    tracing::info!("Quick connectivity check requested via command");
     
-    connectivity::check_connectivity_quick()
-        .await
+    let target = app.state::<ConfigStore>().connectivity_target();
+    let prober = app.state::<ConnectivityProber>().get();
+    let result = connectivity::check_connectivity_quick_with(prober.as_ref(), &target).await;
+    connectivity::report(&app, matches!(result, Ok(true)));
+    result
+        .map_err(|e| {
+            tracing::error!("Quick connectivity check failed: {}", e);
+            CommandError::from(e)
+        })
+        .into()
+}
+
+/// Check the status of a runtime permission
+///
+/// This command does not prompt the user.
+///
+/// # Arguments
+///
+/// * `permission` - The permission to check (`notifications`, `camera`,
+///   `location`, `microphone`, `contacts` or `phone_state`)
+///
+/// # Returns
+///
+/// Returns the permission status (`granted`, `denied`, `permanently_denied`
+/// or `restricted`), or an error if the status cannot be determined.
+///
+/// # Examples
+///
+/// ```javascript
+/// const status = await invoke('permission_check', { permission: 'camera' });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn permission_check(permission: Permission) -> AppResult<PermissionStatus> {
+    permissions::check(permission)
+}
+
+/// Request a runtime permission from the user
+///
+/// # Arguments
+///
+/// * `permission` - The permission to request
+///
+/// # Returns
+///
+/// Returns the resulting permission status, or an error if the
+/// request fails.
+///
+/// # Examples
+///
+/// ```javascript
+/// const status = await invoke('permission_request', { permission: 'microphone' });
+/// if (status === 'permanently_denied') {
+///   // Invite the user to open the system settings
+/// }
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn permission_request(app: AppHandle, permission: Permission) -> AppResult<PermissionStatus> {
+    let previous = permissions::check(permission).ok();
+    let status = permissions::request(permission)?;
+    if previous != Some(status) {
+        audit_log::record(&app, SecurityEvent::PermissionChange { permission, status });
+    }
+    Ok(status)
+}
+
+/// Check the status of every runtime permission managed by the application
+///
+/// # Returns
+///
+/// Returns a list of `{ permission, status }` entries, or an error if
+/// any status cannot be determined.
+#[tauri::command]
+#[specta::specta]
+pub async fn permission_status_all() -> AppResult<Vec<PermissionEntry>> {
+    permissions::status_all()
+}
+
+/// Start recording an audio note
+///
+/// Requests the microphone permission if needed. While recording, the input
+/// level is emitted to the webview as `audio-level` events.
+///
+/// # Returns
+///
+/// Returns the path of the `.m4a` file being recorded, or an error if
+/// a recording is already in progress or the recording cannot be started.
+///
+/// # Examples
+///
+/// ```javascript
+/// await listen('audio-level', (event) => meter.update(event.payload.level));
+/// await invoke('start_recording');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn start_recording(app: AppHandle, recorder: State<'_, AudioRecorder>) -> AppResult<String> {
+    recorder
+        .start(&app)
+        .map(|path| path.to_string_lossy().into_owned())
         .map_err(|e| {
-            let error_msg = format!("Quick connectivity check failed: {}", e);
-            log::error!("{}", error_msg);
-            error_msg
+            tracing::error!("Failed to start audio recording: {}", e);
+            e
         })
 }
+
+/// Stop the audio recording in progress
+///
+/// # Returns
+///
+/// Returns `{ path, durationMs }` for the recorded file, or an error if
+/// no recording is in progress.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { path, durationMs } = await invoke('stop_recording');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_recording(recorder: State<'_, AudioRecorder>) -> AppResult<RecordingInfo> {
+    recorder.stop().map_err(|e| {
+        tracing::error!("Failed to stop audio recording: {}", e);
+        e
+    })
+}
+
+/// Get the lock-screen security status of the device
+///
+/// # Returns
+///
+/// Returns `{ passcodeSet, biometricsEnrolled }`, or an error if the
+/// status cannot be determined.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { passcodeSet } = await invoke('device_security_status');
+/// if (!passcodeSet) {
+///   // Do not cache sensitive documents on this device
+/// }
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn device_security_status() -> AppResult<DeviceSecurityStatus> {
+    tracing::debug!("Device security status requested via command");
+
+    device_security::status()
+}
+
+/// Run the root / jailbreak detection heuristics
+///
+/// # Returns
+///
+/// Returns `{ compromised, findings }`, where each finding has an `indicator`
+/// (`su_binary`, `magisk`, `writable_system_partition`, `jailbreak_files`,
+/// `sandbox_escape`, `signing_mismatch`, `bundle_modified`,
+/// `debugger_attached`) and a `detail` (path or mount point).
+///
+/// # Examples
+///
+/// ```javascript
+/// const { compromised } = await invoke('device_integrity');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn device_integrity() -> AppResult<IntegrityReport> {
+    tracing::info!("Device integrity check requested via command");
+
+    Ok(integrity::check())
+}
+
+/// Get general information about the device and the application
+///
+/// # Returns
+///
+/// Returns `{ platform, arch, appVersion, isEmulator }`. `isEmulator` allows
+/// the frontend to tag test traffic so it can be kept out of production
+/// analytics.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { isEmulator } = await invoke('device_info');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn device_info() -> AppResult<DeviceInfo> {
+    Ok(device_info::collect())
+}
+
+/// Request a device attestation token for a backend-issued nonce
+///
+/// Uses Google Play Integrity on Android and App Attest on iOS. The returned
+/// token must be verified by the backend.
+///
+/// # Arguments
+///
+/// * `nonce` - Single-use URL-safe base64 challenge issued by the backend
+///
+/// # Returns
+///
+/// Returns `{ provider, token, keyId, selfCheck }` (`selfCheck` lists the
+/// binary integrity self-check indicators), or an error if the nonce
+/// is invalid or attestation is unavailable.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { nonce } = await fetch('/api/attestation/nonce').then(r => r.json());
+/// const attestation = await invoke('attest_device', { nonce });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn attest_device(app: AppHandle, nonce: String) -> AppResult<AttestationToken> {
+    tracing::info!("Device attestation requested via command");
+
+    attestation::validate_nonce(&nonce)?;
+    let result = attestation::attest(&nonce);
+    // Only a rejection says something about the device, not a failed request
+    match &result {
+        Ok(_) => device_policy::record_attestation(&app, true),
+        Err(AttestationError::Rejected(_)) => device_policy::record_attestation(&app, false),
+        Err(AttestationError::Unavailable(_)) => {}
+    }
+    result.map_err(|e| {
+        tracing::warn!("Device attestation failed: {}", e);
+        AppError::Auth(e.to_string())
+    })
+}
+
+/// Get the accessibility preferences of the device
+///
+/// Changes are pushed to the webview as `accessibility-changed` events with
+/// the same payload.
+///
+/// # Returns
+///
+/// Returns `{ screenReaderActive, boldText, reduceMotion }`, or an error
+/// string if the status cannot be determined.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { reduceMotion } = await invoke('accessibility_status');
+/// await listen('accessibility-changed', (event) => applyPreferences(event.payload));
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn accessibility_status() -> AppResult<AccessibilityStatus> {
+    accessibility::status()
+}
+
+/// Get device storage, memory and app footprint information
+///
+/// # Returns
+///
+/// Returns `{ totalBytes, freeBytes, appCacheBytes, appDataBytes,
+/// memoryTotalBytes, memoryAvailableBytes }` (device values are `null` when
+/// unknown), or an error if the information cannot be collected.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { freeBytes } = await invoke('storage_info');
+/// if (freeBytes !== null && freeBytes < download.size) {
+///   // Refuse the download
+/// }
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn storage_info(app: AppHandle) -> AppResult<StorageInfo> {
+    storage_info::collect(&app)
+}
+
+/// Get details about the active network interface
+///
+/// # Returns
+///
+/// Returns `{ activeInterface, transport, localIp, vpnActive }`, or an error
+/// string if the network state cannot be determined.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { vpnActive, transport } = await invoke('network_interfaces');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn network_interfaces() -> AppResult<NetworkInterfaces> {
+    tracing::debug!("Network interfaces requested via command");
+
+    network_info::collect().await
+}
+
+/// Get the mobile carrier and SIM information
+///
+/// Requires the `phone_state` permission on Android; without it, only
+/// `permissionGranted: false` is reported.
+///
+/// # Returns
+///
+/// Returns `{ permissionGranted, carrierName, countryIso, mccMnc, roaming }`,
+/// or an error if the information cannot be determined.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { carrierName, roaming } = await invoke('carrier_info');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn carrier_info() -> AppResult<CarrierInfo> {
+    tracing::debug!("Carrier info requested via command");
+
+    carrier::info()
+}
+
+/// Get first-launch, install time, install source and previous version information
+///
+/// # Returns
+///
+/// Returns `{ firstLaunch, installedAt, currentVersion, previousVersion, source }`,
+/// or an error if the launch could not be recorded at startup.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { firstLaunch, previousVersion } = await invoke('install_info');
+/// if (firstLaunch) showOnboarding();
+/// else if (previousVersion) await migrateFrom(previousVersion);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn install_info(state: State<'_, InstallState>) -> AppResult<InstallInfo> {
+    state
+        .get()
+        .ok_or_else(|| AppError::Platform("Install information unavailable".to_string()))
+}
+
+/// Update the app state snapshot persisted when the app goes to background
+///
+/// The frontend should call this whenever the route, scroll position or
+/// pending drafts change significantly.
+///
+/// # Arguments
+///
+/// * `snapshot` - `{ lastRoute, scrollHints, pendingDraftIds }`
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the snapshot is too large.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('update_app_state', {
+///   snapshot: { lastRoute: location.pathname, scrollHints: { [location.pathname]: scrollY }, pendingDraftIds: [] },
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn update_app_state(store: State<'_, StateStore>, snapshot: AppStateSnapshot) -> AppResult<()> {
+    logging::set_route(snapshot.last_route.clone());
+    store.update(snapshot)
+}
+
+/// Get the app state snapshot restored from the previous session
+///
+/// # Returns
+///
+/// Returns the snapshot, or `null` if there is nothing to restore.
+///
+/// # Examples
+///
+/// ```javascript
+/// const restored = await invoke('get_restored_state');
+/// if (restored?.lastRoute) router.replace(restored.lastRoute);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn get_restored_state(store: State<'_, StateStore>) -> AppResult<Option<AppStateSnapshot>> {
+    Ok(store.restored())
+}
+
+/// Report user activity, postponing the inactivity auto-lock
+///
+/// The frontend should call this on user interaction (throttled, e.g. at most
+/// once every few seconds).
+#[tauri::command]
+#[specta::specta]
+pub async fn session_activity(lock: State<'_, SessionLock>) -> AppResult<()> {
+    lock.record_activity(std::time::Instant::now());
+    Ok(())
+}
+
+/// Re-authenticate the user with biometrics or the device passcode and
+/// unlock the session
+///
+/// # Returns
+///
+/// Returns `true` if the session is unlocked, `false` if authentication was
+/// rejected, or an error if authentication is unavailable. Keychain
+/// secrets and the encrypted database are only available again once unlocked.
+///
+/// # Examples
+///
+/// ```javascript
+/// await listen('session://locked', async ({ payload: { reason } }) => {
+///   while (!(await invoke('unlock_session'))) {}
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn unlock_session(app: AppHandle) -> AppResult<bool> {
+    session_lock::unlock(&app)
+}
+
+/// Check whether the session is locked
+///
+/// # Returns
+///
+/// Returns `true` if the session is locked.
+#[tauri::command]
+#[specta::specta]
+pub async fn session_locked(lock: State<'_, SessionLock>) -> AppResult<bool> {
+    Ok(lock.is_locked())
+}
+
+/// Check whether an app update is available or required
+///
+/// Compares the installed version with the latest and minimum supported
+/// versions published by the backend.
+///
+/// # Returns
+///
+/// Returns the `UpdateInfo`, or an error if the check failed.
+///
+/// # Examples
+///
+/// ```javascript
+/// const update = await invoke('check_app_update');
+/// if (update.updateAvailable) showUpdateBanner(update.storeUrl, update.updateRequired);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn check_app_update() -> AppResult<UpdateInfo> {
+    app_update::check().await
+}
+
+/// Start a flexible (background download) in-app update
+///
+/// Download progress is reported with `update://progress` events. Once an
+/// `update://result` event with outcome `downloaded` is received, call
+/// `complete_flexible_update` to install it. Android only.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the update flow is started, or an error if no
+/// update is available or the platform does not support in-app updates.
+///
+/// # Examples
+///
+/// ```javascript
+/// await listen('update://result', async ({ payload }) => {
+///   if (payload.outcome === 'downloaded') await invoke('complete_flexible_update');
+/// });
+/// await invoke('start_flexible_update');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn start_flexible_update(app: AppHandle) -> AppResult<()> {
+    in_app_update::start(&app, UpdateMode::Flexible)
+}
+
+/// Start an immediate (full-screen, blocking) in-app update
+///
+/// Android only.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the update flow is started, or an error if no
+/// update is available or the platform does not support in-app updates.
+#[tauri::command]
+#[specta::specta]
+pub async fn start_immediate_update(app: AppHandle) -> AppResult<()> {
+    in_app_update::start(&app, UpdateMode::Immediate)
+}
+
+/// Install a downloaded flexible update and restart the app
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if no downloaded update is
+/// pending.
+#[tauri::command]
+#[specta::specta]
+pub async fn complete_flexible_update() -> AppResult<()> {
+    in_app_update::complete_flexible()
+}
+
+/// Read a value from the remote config
+///
+/// # Arguments
+///
+/// * `key` - The config key
+///
+/// # Returns
+///
+/// Returns the JSON value of the key, or `null` if the key is not set.
+///
+/// # Examples
+///
+/// ```javascript
+/// const banner = await invoke('get_config', { key: 'home.banner' });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn get_config(
+    key: String,
+    config: State<'_, ConfigStore>,
+) -> AppResult<Option<serde_json::Value>> {
+    Ok(config.get(&key))
+}
+
+/// Check whether a feature flag is enabled for this installation
+///
+/// Listen to `feature://changed` events to react when flags change after a
+/// remote config refresh.
+///
+/// # Arguments
+///
+/// * `name` - The flag name
+///
+/// # Returns
+///
+/// Returns `true` if the flag is enabled. Unknown flags are disabled.
+///
+/// # Examples
+///
+/// ```javascript
+/// if (await invoke('feature_enabled', { name: 'new_agenda' })) {
+///   showNewAgenda();
+/// }
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn feature_enabled(app: AppHandle, name: String) -> AppResult<bool> {
+    Ok(feature_flags::is_enabled(&app, &name))
+}
+
+/// Get the most recent crash report left by a previous session
+///
+/// The frontend uses this at startup to offer sending the report
+/// ("the app crashed last time — send report?"). Reports are encrypted at
+/// rest, so only their kind and date are returned; the contents are decrypted
+/// when sent.
+///
+/// # Returns
+///
+/// Returns the `CrashSummary` of the most recent pending report, or `null` if
+/// there is none.
+///
+/// # Examples
+///
+/// ```javascript
+/// const crash = await invoke('get_last_crash');
+/// if (crash && await confirm('élulib a planté. Envoyer le rapport ?')) {
+///   await invoke('send_crash_reports');
+/// } else if (crash) {
+///   await invoke('discard_crash_reports');
+/// }
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn get_last_crash(state: State<'_, CrashState>) -> AppResult<Option<CrashSummary>> {
+    Ok(crash::last_crash(&state.dir()?))
+}
+
+/// Upload all pending crash reports
+///
+/// Reports are decrypted only for the upload, which must follow an explicit
+/// user action.
+///
+/// # Returns
+///
+/// Returns the number of uploaded reports, or an error if an upload failed.
+#[tauri::command]
+#[specta::specta]
+pub async fn send_crash_reports(state: State<'_, CrashState>) -> AppResult<usize> {
+    let dir = state.dir()?;
+    crash::upload(&dir).await
+}
+
+/// Delete all pending crash reports without sending them
+#[tauri::command]
+#[specta::specta]
+pub async fn discard_crash_reports(state: State<'_, CrashState>) -> AppResult<()> {
+    crash::discard(&state.dir()?)
+}
+
+/// Opt in or out of automatic crash report upload on next launch
+///
+/// # Arguments
+///
+/// * `enabled` - `true` to upload crash reports automatically
+#[tauri::command]
+#[specta::specta]
+pub async fn set_crash_reporting_enabled(
+    enabled: bool,
+    state: State<'_, CrashState>,
+) -> AppResult<()> {
+    tracing::info!("Crash report upload {}", if enabled { "enabled" } else { "disabled" });
+    crash::set_upload_enabled(&state.dir()?, enabled)
+}
+
+/// Check whether the previous session ended with a crash
+///
+/// # Returns
+///
+/// Returns `true` if the app panicked during the previous session.
+#[tauri::command]
+#[specta::specta]
+pub async fn launched_after_crash(state: State<'_, CrashState>) -> AppResult<bool> {
+    Ok(state.post_crash())
+}
+
+/// Track an analytics event
+///
+/// Only events and properties declared in the analytics schema are accepted,
+/// and string properties must be short identifiers (no free-form text). The
+/// event is dropped if the user opted out of analytics.
+///
+/// # Arguments
+///
+/// * `name` - The event name
+/// * `props` - The event properties
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the event does not match
+/// the schema.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('track_event', { name: 'screen_view', props: { screen: 'agenda' } });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn track_event(
+    app: AppHandle,
+    name: String,
+    props: Option<std::collections::BTreeMap<String, PropValue>>,
+) -> AppResult<()> {
+    analytics::track(&app, &name, props.unwrap_or_default())
+}
+
+/// Opt in or out of analytics
+///
+/// Opting out drops all events not yet uploaded. The choice is persisted.
+///
+/// # Arguments
+///
+/// * `enabled` - `false` to opt out
+#[tauri::command]
+#[specta::specta]
+pub async fn set_analytics_enabled(app: AppHandle, enabled: bool) -> AppResult<()> {
+    tracing::info!("Analytics {}", if enabled { "enabled" } else { "disabled" });
+    analytics::set_enabled(&app, enabled)
+}
+
+/// Check whether analytics are enabled (the user did not opt out)
+#[tauri::command]
+#[specta::specta]
+pub async fn analytics_enabled(state: State<'_, Analytics>) -> AppResult<bool> {
+    Ok(state.is_enabled())
+}
+
+/// Get the cold start durations of the current process
+///
+/// # Returns
+///
+/// Returns the `StartupMetrics`, in milliseconds since process start. Phases
+/// not reached yet are `null`.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { firstPageLoadMs } = await invoke('startup_metrics');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn startup_metrics() -> AppResult<StartupMetrics> {
+    Ok(startup_metrics::metrics())
+}
+
+/// Get the number of invocations rejected by the rate limiter
+///
+/// # Returns
+///
+/// Returns `{ rejected, globalRejected }`, where `rejected` counts rejected
+/// invocations by command since launch.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { rejected } = await invoke('rate_limit_metrics');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn rate_limit_metrics(state: State<'_, RateLimiter>) -> AppResult<RateLimitMetrics> {
+    Ok(state.metrics())
+}
+
+/// Acknowledge that the user saw the inbox
+///
+/// With the default badge reset policy, the app icon badge and delivered
+/// notifications are only cleared once the frontend calls this command while
+/// the app is in foreground.
+///
+/// # Returns
+///
+/// Returns `true` if the badge was reset.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('acknowledge_inbox');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn acknowledge_inbox(app: AppHandle) -> AppResult<bool> {
+    Ok(badge::acknowledge_inbox(&app))
+}
+
+/// Get the badge reset policy
+#[tauri::command]
+#[specta::specta]
+pub async fn get_badge_reset_policy(app: AppHandle) -> AppResult<BadgeResetPolicy> {
+    Ok(badge::policy(&app))
+}
+
+/// Configure the automatic badge reset
+///
+/// The policy is persisted.
+///
+/// # Arguments
+///
+/// * `policy` - New `BadgeResetPolicy`
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('set_badge_reset_policy', {
+///   policy: { enabled: true, requireInboxAck: false, channels: ['messages'] },
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn set_badge_reset_policy(app: AppHandle, policy: BadgeResetPolicy) -> AppResult<()> {
+    tracing::info!("Updating badge reset policy: {:?}", policy);
+    badge::set_policy(&app, policy)
+}
+
+/// Get the current system locale
+///
+/// # Returns
+///
+/// Returns the `LocaleInfo`: the system locale tag and the language used for
+/// native strings. Changes are notified with the `locale://changed` event.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { locale, language } = await invoke('current_locale');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn current_locale() -> AppResult<LocaleInfo> {
+    Ok(locale::current())
+}
+
+/// Retrieve the request signing secret of the current page
+///
+/// Must be called by the trusted frontend as soon as its bundle runs: the
+/// secret is only returned once per page load. When the `signed_invoke` flag
+/// is enabled, keychain commands must carry an `__hmac` argument computed with
+/// this secret (see the `invoke_signing` module).
+///
+/// # Returns
+///
+/// Returns the base64-encoded secret, or an error if it was already shared.
+///
+/// # Examples
+///
+/// ```javascript
+/// const secret = await invoke('signing_handshake');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn signing_handshake(state: State<'_, InvokeSigning>) -> AppResult<String> {
+    state.handshake().map_err(|e| {
+        tracing::warn!("Signing handshake rejected: {}", e);
+        e
+    })
+}
+
+/// Execute a statement on the encrypted local database
+///
+/// # Arguments
+///
+/// * `sql` - SQL statement, with `?` placeholders
+/// * `params` - Statement parameters (arrays and objects are stored as JSON text)
+///
+/// # Returns
+///
+/// Returns the number of rows changed, or an error if the statement fails.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('db_execute', {
+///   sql: 'INSERT OR REPLACE INTO documents (id, title, updated_at) VALUES (?, ?, ?)',
+///   params: [doc.id, doc.title, doc.updatedAt],
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn db_execute(
+    app: AppHandle,
+    state: State<'_, Database>,
+    sql: String,
+    params: Option<Vec<serde_json::Value>>,
+) -> AppResult<usize> {
+    state.with_connection(&app, |conn| database::execute(conn, &sql, &params.unwrap_or_default()))
+}
+
+/// Run a query on the encrypted local database
+///
+/// # Arguments
+///
+/// * `sql` - SQL query, with `?` placeholders
+/// * `params` - Query parameters
+///
+/// # Returns
+///
+/// Returns the rows as objects keyed by column name (blobs are base64-encoded),
+/// or an error if the query fails.
+///
+/// # Examples
+///
+/// ```javascript
+/// const documents = await invoke('db_query', {
+///   sql: 'SELECT id, title FROM documents ORDER BY updated_at DESC LIMIT ?',
+///   params: [20],
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn db_query(
+    app: AppHandle,
+    state: State<'_, Database>,
+    sql: String,
+    params: Option<Vec<serde_json::Value>>,
+) -> AppResult<Vec<serde_json::Map<String, serde_json::Value>>> {
+    state.with_connection(&app, |conn| database::query(conn, &sql, &params.unwrap_or_default()))
+}
+
+/// Claim the session token of the current page
+///
+/// Must be called by the trusted frontend as soon as its bundle runs: the
+/// token is read from the keychain by the native layer and only handed off
+/// once per page load (see the `session_handoff` module).
+///
+/// # Returns
+///
+/// Returns the session token, `null` if no session is stored, or an error if
+/// it was already claimed for this page.
+///
+/// # Examples
+///
+/// ```javascript
+/// const token = await invoke('claim_session_token');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn claim_session_token(
+    app: AppHandle,
+    state: State<'_, SessionHandoff>,
+) -> AppResult<Option<String>> {
+    state.claim(&app).map_err(|e| {
+        tracing::warn!("Session token claim rejected: {}", e);
+        e
+    })
+}
+
+/// Log in with an OAuth 2.0 / OpenID Connect provider
+///
+/// Runs the authorization code flow with PKCE in the system browser session
+/// (`ASWebAuthenticationSession` / Chrome Custom Tabs). The code is exchanged
+/// and the tokens are stored natively; they are never returned to the webview.
+///
+/// # Arguments
+///
+/// * `provider_config` - `{ authorizationEndpoint, tokenEndpoint, clientId,
+///   redirectUri, scopes, extraParams, profile, endSessionEndpoint,
+///   postLogoutRedirectUri }`; redirect URIs must be `elulib://` links or app
+///   links, `profile` is `oidc` (default) or `franceconnect`
+///
+/// # Returns
+///
+/// Returns `{ handle, expiresAt, profile, eidasLevel }`, where `handle` is an
+/// opaque session handle and `eidasLevel` (`eidas1` to `eidas3`) is reported
+/// by the FranceConnect profile, or an error if the login was cancelled,
+/// timed out or failed.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { handle } = await invoke('auth_login', {
+///   providerConfig: {
+///     authorizationEndpoint: 'https://idp.example.org/authorize',
+///     tokenEndpoint: 'https://idp.example.org/token',
+///     clientId: 'elulib-mobile',
+///     redirectUri: 'elulib://auth/callback',
+///     scopes: ['openid', 'profile'],
+///   },
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn auth_login(
+    app: AppHandle,
+    provider_config: ProviderConfig,
+) -> AppResult<AuthSession> {
+    tracing::info!("Native login requested via command");
+
+    let result = auth::login(&app, &provider_config).await;
+    audit_log::record(
+        &app,
+        SecurityEvent::AuthAttempt {
+            method: AuthMethod::Oidc,
+            success: result.is_ok(),
+        },
+    );
+    result.map_err(|e| {
+        tracing::warn!("Native login failed: {}", e);
+        e
+    })
+}
+
+/// End a native login session
+///
+/// Removes the session tokens from the keychain and, if the provider has an
+/// end-session endpoint (FranceConnect), ends the session at the provider.
+///
+/// # Arguments
+///
+/// * `handle` - Session handle returned by `auth_login`
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the local session is removed, or an error if it
+/// could not be removed.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('auth_logout', { handle });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn auth_logout(app: AppHandle, handle: String) -> AppResult<()> {
+    tracing::info!("Native logout requested via command");
+
+    auth::logout(&app, &handle).await.map_err(|e| {
+        tracing::warn!("Native logout failed: {}", e);
+        e
+    })
+}
+
+/// Copy text to the clipboard
+///
+/// Sensitive copies are cleared after a delay (60 seconds by default) and when
+/// the app goes to the background, unless the clipboard changed meanwhile.
+/// A `clipboard://cleared` event (`{ reason: 'expired' | 'background' }`) is
+/// emitted when a sensitive copy is cleared.
+///
+/// # Arguments
+///
+/// * `text` - Text to copy
+/// * `sensitive` - Clear the copy automatically (defaults to `false`)
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the clipboard is unavailable.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('clipboard_copy', { text: code, sensitive: true });
+/// await listen('clipboard://cleared', () => setCopied(false));
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn clipboard_copy(
+    app: AppHandle,
+    text: String,
+    sensitive: Option<bool>,
+) -> AppResult<()> {
+    clipboard::copy(&app, &text, sensitive.unwrap_or(false)).map_err(|e| {
+        tracing::warn!("Clipboard copy failed: {}", e);
+        e
+    })
+}
+
+/// Create a passkey with the platform authenticator
+///
+/// Runs the WebAuthn registration ceremony natively, as the webview's own
+/// WebAuthn support is unreliable. The relying party must be the application
+/// host or one of its parent domains.
+///
+/// # Arguments
+///
+/// * `options` - `PublicKeyCredentialCreationOptionsJSON` from the backend
+///
+/// # Returns
+///
+/// Returns the `RegistrationResponseJSON` to send to the backend, or an error
+/// if the options were rejected, the user cancelled or passkeys are
+/// unavailable.
+///
+/// # Examples
+///
+/// ```javascript
+/// const options = await api.post('/passkeys/register/options');
+/// const credential = await invoke('passkey_register', { options });
+/// await api.post('/passkeys/register', credential);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn passkey_register(options: RegistrationOptions) -> AppResult<RegistrationResponse> {
+    passkey::register(&options).map_err(|e| {
+        tracing::warn!("Passkey registration failed: {}", e);
+        e
+    })
+}
+
+/// Sign in with a passkey
+///
+/// Runs the WebAuthn authentication ceremony natively. An empty
+/// `allowCredentials` lets the user pick any passkey of the relying party.
+///
+/// # Arguments
+///
+/// * `options` - `PublicKeyCredentialRequestOptionsJSON` from the backend
+///
+/// # Returns
+///
+/// Returns the `AuthenticationResponseJSON` to send to the backend, or an
+/// error if the options were rejected, the user cancelled or no passkey is
+/// available.
+///
+/// # Examples
+///
+/// ```javascript
+/// const options = await api.post('/passkeys/login/options');
+/// const assertion = await invoke('passkey_authenticate', { options });
+/// await api.post('/passkeys/login', assertion);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn passkey_authenticate(
+    app: AppHandle,
+    options: AuthenticationOptions,
+) -> AppResult<AuthenticationResponse> {
+    let result = passkey::authenticate(&options);
+    audit_log::record(
+        &app,
+        SecurityEvent::AuthAttempt {
+            method: AuthMethod::Passkey,
+            success: result.is_ok(),
+        },
+    );
+    result.map_err(|e| {
+        tracing::warn!("Passkey authentication failed: {}", e);
+        e
+    })
+}
+
+/// Export the security audit log
+///
+/// Records are hash-chained and the last one is anchored in the keychain, so
+/// an edited or truncated log fails verification (see the `audit_log`
+/// module).
+///
+/// # Returns
+///
+/// Returns `{ records, anchor, verified, error }`, or an error if the log
+/// cannot be read.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { records, verified } = await invoke('export_security_log');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn export_security_log(app: AppHandle) -> AppResult<AuditExport> {
+    audit_log::export(&app)
+}
+
+/// Sign a backend-issued nonce with the device-binding key
+///
+/// The key is generated in secure hardware at first launch and never leaves
+/// the device, so the backend can bind refresh tokens to it (see the
+/// `device_binding` module for the protocol).
+///
+/// # Arguments
+///
+/// * `nonce` - Single-use URL-safe base64 challenge issued by the backend
+///
+/// # Returns
+///
+/// Returns `{ keyId, publicKey, algorithm, signature, storage }`, or an error
+/// string if the nonce is invalid or the key is unavailable.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { nonce } = await api.post('/auth/binding-nonce');
+/// const binding = await invoke('device_binding_assertion', { nonce });
+/// await api.post('/auth/refresh', { refreshToken, binding });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn device_binding_assertion(nonce: String) -> AppResult<BindingAssertion> {
+    device_binding::sign_nonce(&nonce).map_err(|e| {
+        tracing::warn!("Device-binding assertion failed: {}", e);
+        e
+    })
+}
+
+/// Get the active device policy
+///
+/// The policy degrades features on compromised or unprotected devices (see
+/// the `device_policy` module). Changes are notified with the
+/// `policy://changed` event.
+///
+/// # Returns
+///
+/// Returns `{ signals, consequences }`, e.g.
+/// `{ signals: ['no_lock_screen'], consequences: ['block_offline_cache'] }`.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { consequences } = await invoke('device_policy');
+/// const canCache = !consequences.includes('block_offline_cache');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn device_policy(state: State<'_, DevicePolicy>) -> AppResult<ActivePolicy> {
+    Ok(state.active())
+}
+
+/// Keep a secret in memory for the current session
+///
+/// The value never touches the disk or the keychain, and is wiped when the
+/// app goes to the background or the session locks. Use it for secrets the
+/// frontend needs briefly, like decrypted document keys.
+///
+/// # Arguments
+///
+/// * `key` - The key to store the value under
+/// * `value` - The secret
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the key or value is
+/// invalid or the store is full.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('ephemeral_store', { key: `doc.${id}`, value: documentKey });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn ephemeral_store(state: State<'_, EphemeralStore>, key: String, value: String) -> AppResult<()> {
+    redact::register_key_name(&key);
+    tracing::info!("Storing ephemeral secret for key: {}", redact::key_name(&key));
+    state.store(&key, &value).map_err(|e| {
+        tracing::warn!("Ephemeral store failed: {}", e);
+        e
+    })
+}
+
+/// Get a secret kept with `ephemeral_store`
+///
+/// # Arguments
+///
+/// * `key` - The key the value was stored under
+///
+/// # Returns
+///
+/// Returns the secret, or `null` if it was never stored or has been wiped
+/// (background, session lock); the frontend must then obtain it again.
+///
+/// # Examples
+///
+/// ```javascript
+/// const documentKey = await invoke('ephemeral_get', { key: `doc.${id}` })
+///   ?? await fetchDocumentKey(id);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn ephemeral_get(state: State<'_, EphemeralStore>, key: String) -> AppResult<Option<String>> {
+    Ok(state.get(&key))
+}
+
+/// Get the disk space used by the log files
+///
+/// The log file is rotated and old files are deleted at startup, so usage
+/// stays under `maxBytes`.
+///
+/// # Returns
+///
+/// Returns `{ totalBytes, fileCount, maxBytes }`, or an error if the
+/// log directory cannot be resolved.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { totalBytes } = await invoke('log_storage_usage');
+/// console.log(`Logs: ${(totalBytes / 1024).toFixed(0)} KB`);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn log_storage_usage(app: AppHandle) -> AppResult<LogStorageUsage> {
+    let dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve log directory: {}", e)))?;
+    Ok(logging::storage_usage(&dir))
+}
+
+/// Change the log level at runtime
+///
+/// The choice is persisted and applied at the next launches, so support can
+/// ask a user to enable debug logging without a reinstall.
+///
+/// # Arguments
+///
+/// * `level` - `off`, `error`, `warn`, `info`, `debug` or `trace`
+/// * `module_filter` - Module path the level applies to (e.g.
+///   `elulib_mobile::auth`), or all modules if omitted
+///
+/// # Returns
+///
+/// Returns the active `{ level, moduleFilter }`, or an error if the
+/// level is unknown or cannot be persisted.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('set_log_level', { level: 'debug', moduleFilter: 'elulib_mobile::auth' });
+/// // Back to normal
+/// await invoke('set_log_level', { level: 'info' });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn set_log_level(
+    app: AppHandle,
+    level: String,
+    module_filter: Option<String>,
+) -> AppResult<LogLevelSetting> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve data directory: {}", e)))?;
+    log_level::set(&dir, &LogLevelSetting { level, module_filter })
+}
+
+/// Get the active log level
+///
+/// # Returns
+///
+/// Returns `{ level, moduleFilter }`, e.g. `{ level: 'info', moduleFilter: null }`.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_log_level() -> AppResult<LogLevelSetting> {
+    Ok(log_level::current())
+}
+
+/// Export the logs and open the share sheet
+///
+/// The current and rotated log files are redacted and zipped with a
+/// diagnostics summary (device, storage, log settings), then handed to the
+/// native share sheet so the user can email them to support.
+///
+/// # Returns
+///
+/// Returns `{ fileName, bytes }` once the share sheet is presented, or an
+/// error if the archive cannot be written or sharing is unavailable.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('export_logs');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn export_logs(app: AppHandle) -> AppResult<ExportedLogs> {
+    diagnostics::export_logs(&app).map_err(|e| {
+        tracing::warn!("Log export failed: {}", e);
+        e
+    })
+}
+
+/// Enable or disable remote log shipping
+///
+/// Redacted log records are uploaded to support in batches while enabled,
+/// either because the user opted in or for a limited time with a token issued
+/// by support. Disabling drops the opt-in, the support grant and the records
+/// not uploaded yet.
+///
+/// # Arguments
+///
+/// * `enabled` - Ship logs
+/// * `support_token` - Token issued by support, enabling shipping for 24 hours
+///   instead of opting in
+///
+/// # Returns
+///
+/// Returns `{ enabled, optedIn, supportExpiresAt, queuedRecords,
+/// droppedRecords }`, or an error if the token is invalid or the
+/// choice cannot be persisted.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('set_log_shipping', { enabled: true });
+/// await invoke('set_log_shipping', { enabled: true, supportToken: 'CASE-4821-XK' });
+/// await invoke('set_log_shipping', { enabled: false });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn set_log_shipping(
+    enabled: bool,
+    support_token: Option<String>,
+) -> AppResult<LogShippingStatus> {
+    log_shipping::set_enabled(enabled, support_token.as_deref())
+}
+
+/// Get the remote log shipping status
+///
+/// # Returns
+///
+/// Returns `{ enabled, optedIn, supportExpiresAt, queuedRecords, droppedRecords }`.
+#[tauri::command]
+#[specta::specta]
+pub async fn log_shipping_status() -> AppResult<LogShippingStatus> {
+    Ok(log_shipping::status())
+}
+
+/// Generate the diagnostics report for the in-app support form
+///
+/// Assembles device information, permission statuses, a quick connectivity
+/// check, the keychain entries of the app (names only), the notification
+/// permission and channel, the device integrity checks, storage usage, log
+/// settings and the last crash summary. Parts that cannot be collected are
+/// `null`.
+///
+/// # Returns
+///
+/// Returns the report as a single JSON object, or an error if the log
+/// directory cannot be resolved.
+///
+/// # Examples
+///
+/// ```javascript
+/// const report = await invoke('generate_diagnostics');
+/// form.attach('diagnostics.json', JSON.stringify(report));
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_diagnostics(app: AppHandle) -> AppResult<DiagnosticsReport> {
+    tracing::info!("Diagnostics report requested via command");
+    diagnostics::report(&app).await
+}
+
+/// Record a breadcrumb
+///
+/// Breadcrumbs are short descriptions of notable steps (navigation, form
+/// submission, ...), attached with the native ones to crash reports and
+/// diagnostics to reconstruct what happened before a failure. Only the last
+/// 100 are kept; messages are redacted and truncated to 256 characters.
+///
+/// # Arguments
+///
+/// * `category` - Short lowercase identifier (`[a-z0-9_.]`, e.g. `navigation`)
+/// * `message` - Description of the step
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the category is invalid.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('add_breadcrumb', { category: 'navigation', message: '/agenda' });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn add_breadcrumb(category: String, message: String) -> AppResult<()> {
+    breadcrumbs::validate_category(&category)?;
+    breadcrumbs::breadcrumb(&category, &message);
+    Ok(())
+}
+
+/// Read the most recent records of the current log file
+///
+/// Intended for the hidden debug screen, to show native logs on-device.
+/// Messages are redacted like everywhere else in the logs.
+///
+/// # Arguments
+///
+/// * `level_filter` - Most verbose level returned (`error`, `warn`, `info`,
+///   `debug`, `trace`), all levels if omitted
+/// * `limit` - Maximum number of records (200 by default, at most 2000)
+///
+/// # Returns
+///
+/// Returns the records as `{ timestamp, module, level, message }`, oldest
+/// first, or an error if the level is unknown or the log file cannot
+/// be read.
+///
+/// # Examples
+///
+/// ```javascript
+/// const entries = await invoke('read_recent_logs', { levelFilter: 'warn', limit: 100 });
+/// entries.forEach(({ level, module, message }) => console.log(level, module, message));
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn read_recent_logs(level_filter: Option<String>, limit: Option<usize>) -> AppResult<Vec<LogLine>> {
+    let min_level = match level_filter.as_deref().map(str::trim) {
+        Some(level) => level.parse().map_err(|_| AppError::InvalidArgument(format!("Unknown log level: {}", level)))?,
+        None => log::LevelFilter::Trace,
+    };
+    let limit = limit
+        .unwrap_or(constants::LOG_VIEWER_DEFAULT_LIMIT)
+        .min(constants::LOG_VIEWER_MAX_LIMIT);
+    logging::read_recent(min_level, limit)
+}
+
+/// Get the execution metrics of the commands invoked since launch
+///
+/// # Returns
+///
+/// Returns `{ bucketBoundsMs, commands }`, where `commands` maps each invoked
+/// command to its invocation and failure counts, cumulated and maximum
+/// execution time, and latency histogram (one count per bucket bound, plus
+/// one for slower invocations).
+///
+/// # Examples
+///
+/// ```javascript
+/// const { commands } = await invoke('get_metrics');
+/// console.log(commands.keychain_store?.maxMs);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn get_metrics() -> AppResult<CommandMetrics> {
+    Ok(command_metrics::snapshot())
+}
+
+/// Clear the offline asset cache
+///
+/// The next load of each asset goes to the network. Useful after a deploy
+/// whose assets must not be served stale.
+///
+/// # Returns
+///
+/// Returns the number of removed assets, or an error if the cache
+/// could not be deleted.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('clear_asset_cache');
+/// location.reload();
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_asset_cache(app: AppHandle) -> AppResult<usize> {
+    asset_cache::clear(&app)
+}
+
+/// Sync collections into the local database now
+///
+/// Also emits `sync://completed` with the same report.
+///
+/// # Arguments
+///
+/// * `collections` - Names of the collections to sync, all if omitted
+///
+/// # Returns
+///
+/// Returns `{ trigger, collections, completedAt }`, where each collection
+/// reports its `upserted` and `deleted` record counts or its `error`, or an
+/// error if sync is disabled, already running, or no session is stored.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('sync_now', { collections: ['agenda'] });
+/// const events = await invoke('db_query', {
+///   sql: "SELECT data FROM sync_records WHERE collection = 'agenda' ORDER BY updated_at DESC",
+///   params: [],
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_now(app: AppHandle, collections: Option<Vec<String>>) -> AppResult<SyncReport> {
+    sync::run(&app, SyncTrigger::Manual, collections.as_deref()).await
+}
+
+/// Write a file to the app file storage
+///
+/// Files live in a dedicated app directory; each top-level directory has its
+/// own quota.
+///
+/// # Arguments
+///
+/// * `path` - Relative `/`-separated path (`annexes/deliberation-12.pdf`)
+/// * `data` - Base64-encoded contents
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the path is invalid,
+/// the contents are not base64, or the file or quota limit is exceeded.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('file_write', { path: `annexes/${annex.id}.pdf`, data: base64Pdf });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn file_write(app: AppHandle, path: String, data: String) -> AppResult<()> {
+    let contents = file_storage::decode(&data)?;
+    file_storage::write(&file_storage::root(&app)?, &path, &contents).map_err(|e| {
+        tracing::warn!("File write failed: {}", e);
+        e
+    })
+}
+
+/// Read a file from the app file storage
+///
+/// # Arguments
+///
+/// * `path` - Relative `/`-separated path
+///
+/// # Returns
+///
+/// Returns the base64-encoded contents, or an error if the path is
+/// invalid or the file does not exist.
+///
+/// # Examples
+///
+/// ```javascript
+/// const data = await invoke('file_read', { path: `annexes/${annex.id}.pdf` });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn file_read(app: AppHandle, path: String) -> AppResult<String> {
+    let contents = file_storage::read(&file_storage::root(&app)?, &path)?;
+    Ok(file_storage::encode(&contents))
+}
+
+/// Delete a file, or a directory and its contents, from the app file storage
+///
+/// # Arguments
+///
+/// * `path` - Relative `/`-separated path
+///
+/// # Returns
+///
+/// Returns `true` if something was deleted, `false` if the path did not
+/// exist, or an error if the path is invalid.
+#[tauri::command]
+#[specta::specta]
+pub async fn file_delete(app: AppHandle, path: String) -> AppResult<bool> {
+    file_storage::delete(&file_storage::root(&app)?, &path)
+}
+
+/// List a directory of the app file storage
+///
+/// # Arguments
+///
+/// * `directory` - Relative `/`-separated path of the directory, the storage
+///   root if omitted
+///
+/// # Returns
+///
+/// Returns the entries of the directory (not recursive) as `{ path, isDir,
+/// size, modifiedAt }`, sorted by path, or an error if the path is
+/// invalid.
+///
+/// # Examples
+///
+/// ```javascript
+/// const annexes = await invoke('file_list', { directory: 'annexes' });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn file_list(app: AppHandle, directory: Option<String>) -> AppResult<Vec<FileEntry>> {
+    file_storage::list(&file_storage::root(&app)?, directory.as_deref())
+}
+
+/// Download a file into the app file storage
+///
+/// The download runs natively in background, survives app restarts and can
+/// be paused and resumed. Progress is emitted as `download://progress`
+/// events (`{ id, downloadedBytes, totalBytes }`), status changes as
+/// `download://state` events (the download).
+///
+/// # Arguments
+///
+/// * `url` - `https` URL of the file
+/// * `path` - Destination, relative to the app file storage
+/// * `sha256` - Expected SHA-256 of the file (hex), verified before completion
+///
+/// # Returns
+///
+/// Returns the queued download (`{ id, url, path, status, downloadedBytes,
+/// totalBytes, ... }`), or an error if the arguments are invalid or a
+/// download to the same path is in progress.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { id } = await invoke('download_start', {
+///   url: packet.url,
+///   path: `packets/${meeting.id}.pdf`,
+///   sha256: packet.sha256,
+/// });
+/// await listen('download://progress', ({ payload }) => updateBar(payload));
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn download_start(app: AppHandle, url: String, path: String, sha256: Option<String>) -> AppResult<Download> {
+    downloads::start(&app, &url, &path, sha256.as_deref())
+}
+
+/// Pause a download
+///
+/// # Arguments
+///
+/// * `id` - Identifier of the download
+#[tauri::command]
+#[specta::specta]
+pub async fn download_pause(app: AppHandle, id: String) -> AppResult<()> {
+    downloads::pause(&app, &id)
+}
+
+/// Resume a paused or failed download, from where it stopped
+///
+/// # Arguments
+///
+/// * `id` - Identifier of the download
+#[tauri::command]
+#[specta::specta]
+pub async fn download_resume(app: AppHandle, id: String) -> AppResult<()> {
+    downloads::resume(&app, &id)
+}
+
+/// Cancel a download and delete its partial file, or remove a completed
+/// download from the list (the downloaded file is kept)
+///
+/// # Arguments
+///
+/// * `id` - Identifier of the download
+#[tauri::command]
+#[specta::specta]
+pub async fn download_cancel(app: AppHandle, id: String) -> AppResult<()> {
+    downloads::cancel(&app, &id)
+}
+
+/// List the downloads, oldest first
+#[tauri::command]
+#[specta::specta]
+pub async fn download_list(state: State<'_, DownloadManager>) -> AppResult<Vec<Download>> {
+    Ok(state.list())
+}
+
+/// Enqueue a file of the app file storage for upload
+///
+/// The upload runs natively in background, is retried with backoff on
+/// network and server errors, and survives app restarts. Status changes are
+/// emitted as `upload://state` events (the upload).
+///
+/// # Arguments
+///
+/// * `path` - File to upload, relative to the app file storage
+/// * `url` - `https` target URL
+/// * `method` - `POST` (default) or `PUT`
+/// * `content_type` - Content type of the body (default `application/octet-stream`)
+/// * `auth` - Authorization reference: `{ type: 'session' }` or
+///   `{ type: 'keychain', key }`, only sent to the application server; the
+///   token is read natively
+/// * `defer_on_metered` - Wait for an unmetered network (default from the
+///   remote config)
+///
+/// # Returns
+///
+/// Returns the queued upload (`{ id, path, url, status, attempts, ... }`), or
+/// an error if the arguments are invalid.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { id } = await invoke('upload_enqueue', {
+///   path: 'annexes/photo.jpg',
+///   url: `${API_URL}/annexes`,
+///   contentType: 'image/jpeg',
+///   auth: { type: 'session' },
+///   deferOnMetered: true,
+/// });
+/// await listen('upload://state', ({ payload }) => updateUpload(payload));
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn upload_enqueue(
+    app: AppHandle,
+    path: String,
+    url: String,
+    method: Option<String>,
+    content_type: Option<String>,
+    auth: Option<UploadAuth>,
+    defer_on_metered: Option<bool>,
+) -> AppResult<Upload> {
+    uploads::enqueue(&app, &path, &url, method.as_deref(), content_type.as_deref(), auth, defer_on_metered)
+}
+
+/// Retry a failed upload now
+///
+/// # Arguments
+///
+/// * `id` - Identifier of the upload
+#[tauri::command]
+#[specta::specta]
+pub async fn upload_retry(app: AppHandle, id: String) -> AppResult<Upload> {
+    uploads::retry(&app, &id)
+}
+
+/// Remove an upload from the queue
+///
+/// # Arguments
+///
+/// * `id` - Identifier of the upload
+#[tauri::command]
+#[specta::specta]
+pub async fn upload_cancel(app: AppHandle, id: String) -> AppResult<()> {
+    uploads::cancel(&app, &id)
+}
+
+/// List the uploads, oldest first
+#[tauri::command]
+#[specta::specta]
+pub async fn upload_list(state: State<'_, UploadQueue>) -> AppResult<Vec<Upload>> {
+    Ok(state.list())
+}
+
+/// Cache an image (avatar, document thumbnail) on disk
+///
+/// The image is downloaded on the first call only; later calls return the
+/// cached copy, across webview reloads and app restarts.
+///
+/// # Arguments
+///
+/// * `url` - `https` URL of the image
+///
+/// # Returns
+///
+/// Returns the local handle (`{ handle, contentType, size, cached }`), or an
+/// error if the image could not be downloaded.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { handle } = await invoke('cache_image', { url: user.avatarUrl });
+/// img.src = await invoke('read_cached_image', { handle });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn cache_image(app: AppHandle, url: String) -> AppResult<ImageHandle> {
+    image_cache::cache_image(&app, &url).await
+}
+
+/// Read a cached image as a `data:` URL, to use as `src` of an `<img>`
+///
+/// # Arguments
+///
+/// * `handle` - Handle returned by `cache_image`
+///
+/// # Returns
+///
+/// Returns the `data:` URL, or an error if the handle is unknown (the
+/// image was evicted: call `cache_image` again).
+#[tauri::command]
+#[specta::specta]
+pub async fn read_cached_image(app: AppHandle, handle: String) -> AppResult<String> {
+    image_cache::data_url(&app, &handle)
+}
+
+/// Get the image cache statistics
+///
+/// # Returns
+///
+/// Returns `{ entries, totalBytes, maxBytes, hits, misses, evictions }`, the
+/// counters covering the time since launch.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_image_cache_stats(state: State<'_, ImageCache>) -> AppResult<ImageCacheStats> {
+    Ok(state.stats())
+}
+
+/// Clear the image cache
+///
+/// # Returns
+///
+/// Returns the number of removed images.
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_image_cache(app: AppHandle) -> AppResult<usize> {
+    image_cache::clear(&app)
+}
+
+/// Get a preference
+///
+/// Preferences hold non-sensitive settings (theme, quiet hours, last route);
+/// secrets belong in the keychain.
+///
+/// # Arguments
+///
+/// * `key` - Preference key
+///
+/// # Returns
+///
+/// Returns the value (boolean, integer or string), or `null` if the
+/// preference is not set.
+///
+/// # Examples
+///
+/// ```javascript
+/// const theme = (await invoke('prefs_get', { key: 'theme' })) ?? 'system';
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn prefs_get(state: State<'_, Prefs>, key: String) -> AppResult<Option<PrefValue>> {
+    Ok(state.get(&key))
+}
+
+/// Set a preference
+///
+/// # Arguments
+///
+/// * `key` - Preference key (letters, digits, `.`, `_`, `-`)
+/// * `value` - Boolean, integer or string value
+///
+/// # Returns
+///
+/// Returns an error if the key or value is invalid, the key looks like
+/// a secret, or the preferences could not be written.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('prefs_set', { key: 'theme', value: 'dark' });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn prefs_set(state: State<'_, Prefs>, key: String, value: PrefValue) -> AppResult<()> {
+    state.set(&key, value)
+}
+
+/// Remove a preference
+///
+/// # Arguments
+///
+/// * `key` - Preference key
+///
+/// # Returns
+///
+/// Returns `true` if the preference was set.
+#[tauri::command]
+#[specta::specta]
+pub async fn prefs_remove(state: State<'_, Prefs>, key: String) -> AppResult<bool> {
+    state.remove(&key)
+}
+
+/// Export the personal data stored by the app and present the share sheet
+///
+/// Supports subject-access requests: preferences, app state, install and
+/// notification settings, pending analytics events, a summary of the
+/// security log, offline document metadata and the list of stored,
+/// downloaded, uploaded and cached files, as JSON files in a ZIP archive.
+/// Secrets (keychain values) are never exported.
+///
+/// # Returns
+///
+/// Returns `{ fileName, bytes, sections, failed }` once the share sheet is
+/// presented (`failed` lists the sections that could not be read), or an
+/// error if the archive cannot be written or sharing is unavailable.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { failed } = await invoke('export_user_data');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn export_user_data(app: AppHandle) -> AppResult<ExportedData> {
+    // Reads every store and writes the archive: off the async runtime
+    tauri::async_runtime::spawn_blocking(move || data_export::export(&app))
+        .await
+        .map_err(|e| AppError::Storage(format!("Data export failed: {}", e)))?
+}
+
+/// Wipe all local data (device decommissioning)
+///
+/// Clears, in order, the pending queues, the encrypted database, the file
+/// storage, the caches, the preferences, the keychain entries and the
+/// remaining app data, then verifies that nothing is left. A failing step
+/// does not stop the wipe; the report lists it.
+///
+/// # Arguments
+///
+/// * `keychain_keys` - Keychain keys written by the frontend, removed along
+///   with the native ones (native login sessions included)
+///
+/// # Returns
+///
+/// Returns `{ steps, remaining, verified, completedAt }`, or an error
+/// if a keychain key is invalid.
+///
+/// # Examples
+///
+/// ```javascript
+/// const report = await invoke('wipe_all_local_data', { keychainKeys: ['device_id'] });
+/// if (!report.verified) console.warn(report.remaining);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn wipe_all_local_data(app: AppHandle, keychain_keys: Option<Vec<String>>) -> AppResult<WipeReport> {
+    let keychain_keys = keychain_keys.unwrap_or_default();
+    for key in &keychain_keys {
+        helpers::validate_keychain_key(key)?;
+    }
+    Ok(wipe::wipe_all(&app, &keychain_keys))
+}
+
+/// Open a file of the app file storage in the native viewer
+///
+/// The type is detected from the file contents: PDF documents, images, Office
+/// documents and text files open in QuickLook on iOS and in the matching app
+/// on Android.
+///
+/// # Arguments
+///
+/// * `path` - Relative `/`-separated path in the app file storage
+///
+/// # Returns
+///
+/// Returns `{ kind, mimeType }` once the viewer is presented, or an error
+/// string if the file does not exist, its type is not supported or no app
+/// can open it.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('open_attachment', { path: `annexes/${annex.id}.pdf` });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn open_attachment(app: AppHandle, path: String) -> AppResult<AttachmentType> {
+    attachments::open(&app, &path)
+}
+
+/// Move a file of the app file storage into the encrypted document vault
+///
+/// The plaintext file is deleted once encrypted.
+///
+/// # Arguments
+///
+/// * `path` - Relative `/`-separated path in the app file storage
+/// * `expires_at` - Time after which the document is purged (seconds since
+///   the Unix epoch), if earlier than the vault policy
+///
+/// # Returns
+///
+/// Returns the `{ id, name, size, storedAt, lastOpenedAt, expiresAt }` of the
+/// document, or an error if the file does not exist or the keychain
+/// is unavailable.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('download_start', { url, path: `annexes/${annex.id}.pdf` });
+/// // once completed
+/// const { id } = await invoke('vault_import', { path: `annexes/${annex.id}.pdf` });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn vault_import(app: AppHandle, path: String, expires_at: Option<u64>) -> AppResult<VaultItem> {
+    vault::import(&app, &path, expires_at)
+}
+
+/// List the documents of the vault
+///
+/// # Returns
+///
+/// Returns the documents, oldest first.
+#[tauri::command]
+#[specta::specta]
+pub async fn vault_list(state: State<'_, Vault>) -> AppResult<Vec<VaultItem>> {
+    Ok(state.list())
+}
+
+/// Decrypt a document of the vault to memory
+///
+/// # Arguments
+///
+/// * `id` - Vault identifier of the document
+///
+/// # Returns
+///
+/// Returns the base64-encoded contents, or an error if the document
+/// does not exist, is too large (use `vault_open`) or cannot be decrypted.
+#[tauri::command]
+#[specta::specta]
+pub async fn vault_read(app: AppHandle, id: String) -> AppResult<String> {
+    let contents = vault::read(&app, &id)?;
+    Ok(file_storage::encode(&contents))
+}
+
+/// Open a document of the vault in the native viewer
+///
+/// The document is decrypted to a temporary file, deleted after a few
+/// minutes and when the session locks.
+///
+/// # Arguments
+///
+/// * `id` - Vault identifier of the document
+///
+/// # Returns
+///
+/// Returns `{ kind, mimeType }` once the viewer is presented, or an error
+/// string if the document does not exist or no app can open it.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('vault_open', { id });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn vault_open(app: AppHandle, id: String) -> AppResult<AttachmentType> {
+    vault::open(&app, &id)
+}
+
+/// Remove a document from the vault
+///
+/// # Arguments
+///
+/// * `id` - Vault identifier of the document
+///
+/// # Returns
+///
+/// Returns `true` if the document was in the vault.
+#[tauri::command]
+#[specta::specta]
+pub async fn vault_remove(state: State<'_, Vault>, id: String) -> AppResult<bool> {
+    state.remove(&id)
+}
+
+/// Set the documents to prefetch for the next session
+///
+/// Documents are downloaded into the app file storage when the device is on
+/// an unmetered Wi-Fi network and charging; unfinished downloads of the
+/// documents dropped from the previous list are cancelled.
+///
+/// # Arguments
+///
+/// * `items` - Documents to prefetch (`{ url, path, size, sha256 }`)
+///
+/// # Returns
+///
+/// Returns the prefetch status, or an error if the list is too long
+/// or a path is invalid.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('prefetch_set', {
+///   items: session.documents.map((doc) => ({ url: doc.url, path: `annexes/${doc.id}.pdf`, size: doc.size })),
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn prefetch_set(app: AppHandle, items: Vec<PrefetchItem>) -> AppResult<PrefetchStatus> {
+    prefetch::set(&app, items)
+}
+
+/// Get the prefetch status
+///
+/// # Returns
+///
+/// Returns `{ conditionsMet, entries }`, each entry carrying the document,
+/// its download once started, and why it was skipped, if it was.
+#[tauri::command]
+#[specta::specta]
+pub async fn prefetch_status(app: AppHandle) -> AppResult<PrefetchStatus> {
+    Ok(prefetch::status(&app))
+}
+
+/// Save the draft of a form
+///
+/// Drafts are stored in the encrypted database, so a form being typed
+/// survives the OS killing the app in background. Saving replaces the
+/// previous draft of the form.
+///
+/// # Arguments
+///
+/// * `form_id` - Form identifier (letters, digits, `.`, `_`, `-`, `:`, `/`)
+/// * `payload` - Draft contents, any JSON value
+///
+/// # Returns
+///
+/// Returns the saved draft, or an error if the identifier is invalid,
+/// the payload is too large or the database is unavailable.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('draft_save', { formId: `amendment:${amendment.id}`, payload: { text, article } });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn draft_save(app: AppHandle, form_id: String, payload: serde_json::Value) -> AppResult<Draft> {
+    drafts::save_draft(&app, &form_id, &payload)
+}
+
+/// Load the draft of a form
+///
+/// # Arguments
+///
+/// * `form_id` - Form identifier
+///
+/// # Returns
+///
+/// Returns `{ formId, payload, updatedAt }`, or `null` if the form has no
+/// draft or it has expired.
+///
+/// # Examples
+///
+/// ```javascript
+/// const draft = await invoke('draft_load', { formId: `amendment:${amendment.id}` });
+/// if (draft) restore(draft.payload);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn draft_load(app: AppHandle, form_id: String) -> AppResult<Option<Draft>> {
+    drafts::load_draft(&app, &form_id)
+}
+
+/// Delete the draft of a form, once it was submitted or discarded
+///
+/// # Arguments
+///
+/// * `form_id` - Form identifier
+///
+/// # Returns
+///
+/// Returns `true` if the form had a draft.
+#[tauri::command]
+#[specta::specta]
+pub async fn draft_delete(app: AppHandle, form_id: String) -> AppResult<bool> {
+    drafts::delete_draft(&app, &form_id)
+}
+
+/// Get the state of the mock backends (`dev-mock` builds only)
+///
+/// # Returns
+///
+/// Returns the keys stored in the mock keystore (never the values), the last
+/// shown notifications, the badge count, the notification permission and the
+/// last delivered push payloads.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { notifications, badgeCount } = await invoke('dev_mock_state');
+/// ```
+#[cfg(feature = "dev-mock")]
+#[tauri::command]
+#[specta::specta]
+pub async fn dev_mock_state() -> AppResult<crate::dev_mock::MockState> {
+    Ok(crate::dev_mock::state())
+}
+
+/// Deliver a push payload to the app, as the native push handlers would
+/// (`dev-mock` builds only)
+///
+/// # Arguments
+///
+/// * `payload` - Push payload, e.g. `{ "type": "sync", "collections": ["agenda"] }`
+///
+/// # Returns
+///
+/// Returns the sync report for silent sync pushes, `null` for other payloads,
+/// or an error if the sync failed.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('dev_mock_push', { payload: { type: 'sync', collections: ['agenda'] } });
+/// ```
+#[cfg(feature = "dev-mock")]
+#[tauri::command]
+#[specta::specta]
+pub async fn dev_mock_push(app: AppHandle, payload: serde_json::Value) -> AppResult<Option<SyncReport>> {
+    crate::dev_mock::deliver_push(&app, payload).await
+}
+
+/// Start recording the invokes to a file (debug builds only)
+///
+/// Each invoke is written as a JSON line with its command, masked arguments,
+/// outcome and duration, to be replayed against the mock app in tests. A
+/// running recording is replaced.
+///
+/// # Returns
+///
+/// Returns an error if the recording file cannot be created.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('invoke_recording_start');
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn invoke_recording_start(app: AppHandle) -> AppResult<()> {
+    let path = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve cache directory: {}", e)))?
+        .join(constants::INVOKE_RECORDING_FILE);
+    invoke_recorder::start(path)
+}
+
+/// Stop recording the invokes (debug builds only)
+///
+/// # Returns
+///
+/// Returns `{ path, invokes }` for the recording, or `null` if none was
+/// running.
+///
+/// # Examples
+///
+/// ```javascript
+/// const recording = await invoke('invoke_recording_stop');
+/// console.log(`${recording.invokes} invokes recorded to ${recording.path}`);
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn invoke_recording_stop() -> AppResult<Option<RecordingSummary>> {
+    invoke_recorder::stop()
+}
+
+/// Open the web inspector of the main window (debug builds only)
+///
+/// # Returns
+///
+/// Returns an error if the main window is not found.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('open_devtools');
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn open_devtools(app: AppHandle) -> AppResult<()> {
+    debug_tools::open_devtools(&app)
+}
+
+/// Reload the page of the main window (debug builds only)
+///
+/// # Returns
+///
+/// Returns an error if the main window is not found or cannot be
+/// reloaded.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('reload_webview');
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn reload_webview(app: AppHandle) -> AppResult<()> {
+    debug_tools::reload(&app)
+}
+
+/// Navigate the main window to a URL (debug builds only)
+///
+/// Sensitive commands are still rejected from origins outside the allowlist.
+///
+/// # Arguments
+///
+/// * `url` - `http` or `https` URL, e.g. a local dev server
+///
+/// # Returns
+///
+/// Returns an error if the URL is invalid or the navigation fails.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('navigate', { url: 'http://192.168.1.20:5173/' });
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn navigate(app: AppHandle, url: String) -> AppResult<()> {
+    debug_tools::navigate(&app, &url)
+}
+
+/// Get the backend environment and its effective endpoints
+///
+/// # Returns
+///
+/// Returns `{ environment, buildEnvironment, pending, appUrl,
+/// connectivityHost, pushSenderId }`; `pending` is the environment selected
+/// for the next launch, if different.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { environment } = await invoke('environment_get');
+/// if (environment !== 'prod') showEnvironmentRibbon(environment);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn environment_get(app: AppHandle) -> AppResult<EnvironmentStatus> {
+    Ok(environment::status(&app))
+}
+
+/// Select the backend environment of the next launch (debug builds only)
+///
+/// Hidden QA command: the app must be relaunched for the change to apply.
+///
+/// # Arguments
+///
+/// * `environment` - `prod`, `preprod` or `staging`
+///
+/// # Returns
+///
+/// Returns the environment status, with the selection as `pending`, or an
+/// error if it cannot be stored.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('environment_set', { environment: 'staging' });
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn environment_set(app: AppHandle, environment: environment::Environment) -> AppResult<EnvironmentStatus> {
+    environment::select(&app, environment)
+}
+
+/// Simulate offline from the debug menu (debug builds only)
+///
+/// While offline is simulated, connectivity checks fail at once.
+///
+/// # Arguments
+///
+/// * `offline` - `true` to simulate offline, `false` to go back to the real
+///   connectivity checks
+///
+/// # Returns
+///
+/// Returns whether offline is simulated.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('debug_set_offline', { offline: true });
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn debug_set_offline(app: AppHandle, offline: bool) -> AppResult<bool> {
+    debug_tools::set_offline(&app, offline);
+    Ok(debug_tools::is_offline())
+}
+
+/// Fire a fake push from the debug menu (debug builds only)
+///
+/// # Arguments
+///
+/// * `payload` - Push payload (defaults to a silent sync push of all
+///   collections)
+///
+/// # Returns
+///
+/// Returns the sync report for silent sync pushes, `null` for other payloads,
+/// or an error if the sync failed.
+///
+/// # Examples
+///
+/// ```javascript
+/// const report = await invoke('debug_fake_push');
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn debug_fake_push(app: AppHandle, payload: Option<serde_json::Value>) -> AppResult<Option<SyncReport>> {
+    debug_tools::fire_fake_push(&app, payload).await
+}
+
+/// Show a test notification on every delivery channel (debug builds only)
+///
+/// # Returns
+///
+/// Returns `[{ channel, error }]`, one entry per channel (`native`, `badge`,
+/// `webview`).
+///
+/// # Examples
+///
+/// ```javascript
+/// const failed = (await invoke('debug_test_notifications')).filter((d) => d.error);
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn debug_test_notifications(app: AppHandle) -> AppResult<Vec<debug_tools::ChannelDelivery>> {
+    Ok(debug_tools::test_notifications(&app))
+}
+
+/// Corrupt a keychain entry from the debug menu (debug builds only)
+///
+/// The value is overwritten with data no reader accepts, to test the recovery
+/// from unreadable secrets.
+///
+/// # Arguments
+///
+/// * `key` - Keychain key to corrupt
+///
+/// # Returns
+///
+/// Returns an error if the key is invalid or the keychain unavailable.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('debug_corrupt_keychain', { key: 'refresh_token' });
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn debug_corrupt_keychain(app: AppHandle, key: String) -> AppResult<()> {
+    debug_tools::corrupt_keychain_entry(&app, &key)
+}
+
+/// Crash the app from the debug menu (debug builds only)
+///
+/// The crash is recorded as a crash report; the promise never resolves.
+///
+/// # Examples
+///
+/// ```javascript
+/// invoke('debug_crash');
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn debug_crash() -> AppResult<()> {
+    debug_tools::force_crash()
+}
+
+/// Feed a synthetic push through the push pipeline (debug builds only)
+///
+/// The payload is routed like a push received by the native layer: sync,
+/// native notification, then deep link if the notification is tapped.
+///
+/// # Arguments
+///
+/// * `payload` - Push payload, or the name of a fixture (`reminder`,
+///   `message`, `sync`, `foreign_link`)
+/// * `tapped` - Whether the notification is tapped (defaults to `true`)
+///
+/// # Returns
+///
+/// Returns `{ kind, sync, notification, notificationError, deepLink }`, or an
+/// error if the fixture is unknown or the sync failed.
+///
+/// # Examples
+///
+/// ```javascript
+/// const routing = await invoke('simulate_push', { payload: 'reminder' });
+/// console.assert(routing.deepLink.path === '/agenda/42');
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn simulate_push(app: AppHandle, payload: serde_json::Value, tapped: Option<bool>) -> AppResult<PushRouting> {
+    let payload = match payload.as_str() {
+        Some(name) => push::fixture(name)?,
+        None => payload,
+    };
+    push::on_push(&app, &payload, tapped.unwrap_or(true)).await
+}
+
+/// Time the device keychain (debug builds only)
+///
+/// Stores, retrieves and removes dedicated keys, then compares storing the
+/// values as one keychain item each with storing them as a single item. The
+/// keys are removed afterwards.
+///
+/// # Arguments
+///
+/// * `iterations` - Number of keys (defaults to 50, at most 1000)
+///
+/// # Returns
+///
+/// Returns `{ iterations, store, retrieve, remove, sequentialUs, batchUs }`,
+/// with `{ samples, minUs, medianUs, p95Us, maxUs }` per operation, or an
+/// error if the keychain is unavailable.
+///
+/// # Examples
+///
+/// ```javascript
+/// const report = await invoke('benchmark_keychain', { iterations: 100 });
+/// console.table(report);
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn benchmark_keychain(app: AppHandle, iterations: Option<u32>) -> AppResult<KeychainBenchmark> {
+    benchmark::run_keychain_blocking(app, iterations.unwrap_or(constants::BENCHMARK_DEFAULT_ITERATIONS)).await
+}
+
+/// Do nothing, to time the invoke round trip (debug builds only)
+///
+/// # Examples
+///
+/// ```javascript
+/// const started = performance.now();
+/// for (let i = 0; i < 100; i++) await invoke('benchmark_ping');
+/// console.log(`${(performance.now() - started) / 100} ms per invoke`);
+/// ```
+#[cfg(debug_assertions)]
+#[tauri::command]
+#[specta::specta]
+pub async fn benchmark_ping() -> AppResult<()> {
+    Ok(())
+}
+
+/// Open the realtime connection to the backend
+///
+/// The connection is kept natively, so it survives the suspension of the
+/// page: messages are emitted as `ws://message` events (`{ data, binary }`)
+/// and state changes as `ws://state` events. The client reconnects after a
+/// disconnection and when the app returns to the foreground. A connection
+/// replaces the previous one.
+///
+/// # Arguments
+///
+/// * `url` - `wss` endpoint on the application origin
+///
+/// # Returns
+///
+/// Returns the connection status, or an error if the URL is not
+/// accepted.
+///
+/// # Examples
+///
+/// ```javascript
+/// await listen('ws://message', ({ payload }) => handleRealtime(JSON.parse(payload.data)));
+/// await invoke('ws_connect', { url: 'wss://app.elulib.com/realtime' });
+/// ```
+#[cfg(feature = "module-realtime")]
+#[tauri::command]
+#[specta::specta]
+pub async fn ws_connect(app: AppHandle, url: String) -> AppResult<WebSocketStatus> {
+    websocket::connect(&app, &url)
+}
+
+/// Send a text message on the realtime connection
+///
+/// Messages sent while disconnected are queued and sent once connected.
+///
+/// # Arguments
+///
+/// * `message` - Text of the message
+///
+/// # Returns
+///
+/// Returns an error if no connection was opened with `ws_connect`.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('ws_send', { message: JSON.stringify({ type: 'subscribe', channel: 'agenda' }) });
+/// ```
+#[cfg(feature = "module-realtime")]
+#[tauri::command]
+#[specta::specta]
+pub async fn ws_send(app: AppHandle, message: String) -> AppResult<()> {
+    websocket::send(&app, message)
+}
+
+/// Close the realtime connection, until the next `ws_connect`
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('ws_close');
+/// ```
+#[cfg(feature = "module-realtime")]
+#[tauri::command]
+#[specta::specta]
+pub async fn ws_close(app: AppHandle) -> AppResult<()> {
+    websocket::close(&app)
+}
+
+/// Get the state of the realtime connection
+///
+/// A reloaded page uses it to pick up the connection kept by the native layer.
+///
+/// # Returns
+///
+/// Returns `{ state, url, attempt, queued }`, `state` being `connecting`,
+/// `open`, `reconnecting`, `suspended` or `closed`.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { state } = await invoke('ws_status');
+/// if (state === 'closed') await invoke('ws_connect', { url: realtimeUrl });
+/// ```
+#[cfg(feature = "module-realtime")]
+#[tauri::command]
+#[specta::specta]
+pub async fn ws_status(client: State<'_, WebSocketClient>) -> AppResult<WebSocketStatus> {
+    Ok(client.status())
+}
+
+/// Run a query on the encrypted local database, with a MessagePack result
+///
+/// Same as `db_query`, for large results (document lists, synced records):
+/// the rows are sent as a raw MessagePack body, cheaper to produce and parse
+/// than JSON on low-end devices. The TypeScript bindings decode it.
+///
+/// # Arguments
+///
+/// * `sql` - SQL query, with `?` placeholders
+/// * `params` - Query parameters
+///
+/// # Returns
+///
+/// Returns the rows as objects keyed by column name (blobs are base64-encoded),
+/// encoded as MessagePack, or an error if the query fails.
+///
+/// # Examples
+///
+/// ```javascript
+/// import { decode } from '@msgpack/msgpack';
+///
+/// const body = await invoke('db_query_packed', {
+///   sql: "SELECT data FROM sync_records WHERE collection = 'documents' ORDER BY updated_at DESC",
+/// });
+/// const documents = decode(new Uint8Array(body));
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn db_query_packed(
+    app: AppHandle,
+    state: State<'_, Database>,
+    sql: String,
+    params: Option<Vec<serde_json::Value>>,
+) -> AppResult<Packed<Vec<serde_json::Map<String, serde_json::Value>>>> {
+    let rows = state.with_connection(&app, |conn| database::query(conn, &sql, &params.unwrap_or_default()))?;
+    Packed::new(&rows)
+}
+
+/// Negotiate the native API version of the page
+///
+/// Must be called by the frontend as soon as its bundle runs: until the next
+/// page load, the native layer answers with the response shapes of the
+/// negotiated version.
+///
+/// # Arguments
+///
+/// * `version` - Native API version the frontend was written for
+///
+/// # Returns
+///
+/// Returns `{ version, latest, oldest, appVersion }`, or an actionable error if
+/// the app does not support the version (update the app, or reload the web
+/// application).
+///
+/// # Examples
+///
+/// ```javascript
+/// try {
+///   await invoke('negotiate_api', { version: 2 });
+/// } catch (error) {
+///   showBlockingMessage(error);
+/// }
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn negotiate_api(version: u32) -> AppResult<ApiNegotiation> {
+    api_version::negotiate(version)
+}
+
+/// Signal the page is ready to receive events
+///
+/// Events posted while no page was ready were queued: they are returned,
+/// oldest first, and must be acknowledged with `outbox_ack` once handled
+/// (unacknowledged events are returned again to the next page). Later events
+/// are emitted on their channel. Call it once the event listeners are set up.
+///
+/// # Returns
+///
+/// Returns the queued events, each as `{ id, channel, payload, createdAt }`.
+///
+/// # Examples
+///
+/// ```javascript
+/// const messages = await invoke('outbox_ready');
+/// for (const message of messages) dispatch(message.channel, message.payload);
+/// await invoke('outbox_ack', { ids: messages.map((message) => message.id) });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn outbox_ready(app: AppHandle) -> AppResult<Vec<OutboxMessage>> {
+    outbox::ready(&app)
+}
+
+/// Acknowledge queued events handled by the page
+///
+/// # Arguments
+///
+/// * `ids` - Identifiers of the handled events
+///
+/// # Returns
+///
+/// Returns the number of events removed from the outbox.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('outbox_ack', { ids: [message.id] });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn outbox_ack(app: AppHandle, ids: Vec<u64>) -> AppResult<usize> {
+    outbox::acknowledge(&app, &ids)
+}
+
+/// Subscribe to a Server-Sent Events stream
+///
+/// The stream is kept by the native layer, which survives the screen locking.
+/// Its events are emitted as `sse://message` events and state changes as
+/// `sse://state` events. The subscriber reconnects after a disconnection with
+/// the id of the last event received, so the server can replay the missed
+/// events.
+///
+/// # Arguments
+///
+/// * `url` - `https` endpoint on the application origin
+///
+/// # Returns
+///
+/// Returns `{ id, url, state, lastEventId, attempt }`, or an error if
+/// the URL is not accepted or too many streams are subscribed.
+///
+/// # Examples
+///
+/// ```javascript
+/// const { id } = await invoke('sse_subscribe', { url: `https://app.elulib.com/live/${session.id}/events` });
+/// await listen('sse://message', ({ payload }) => {
+///   if (payload.subscription === id) handleLiveEvent(payload.event, JSON.parse(payload.data));
+/// });
+/// ```
+#[cfg(feature = "module-sse")]
+#[tauri::command]
+#[specta::specta]
+pub async fn sse_subscribe(app: AppHandle, url: String) -> AppResult<SseStatus> {
+    sse::subscribe(&app, &url)
+}
+
+/// Close a Server-Sent Events subscription
+///
+/// # Arguments
+///
+/// * `id` - Subscription id, returned by `sse_subscribe`
+///
+/// # Returns
+///
+/// Returns `true` if the subscription was open.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('sse_unsubscribe', { id });
+/// ```
+#[cfg(feature = "module-sse")]
+#[tauri::command]
+#[specta::specta]
+pub async fn sse_unsubscribe(app: AppHandle, id: u32) -> AppResult<bool> {
+    sse::unsubscribe(&app, id)
+}
+
+/// Get the open Server-Sent Events subscriptions
+///
+/// A reloaded page uses it to pick up the streams kept by the native layer.
+///
+/// # Returns
+///
+/// Returns the subscriptions, each as `{ id, url, state, lastEventId, attempt }`,
+/// `state` being `connecting`, `open` or `reconnecting`.
+///
+/// # Examples
+///
+/// ```javascript
+/// const subscriptions = await invoke('sse_subscriptions');
+/// const live = subscriptions.find((subscription) => subscription.url.endsWith('/events'));
+/// ```
+#[cfg(feature = "module-sse")]
+#[tauri::command]
+#[specta::specta]
+pub async fn sse_subscriptions(client: State<'_, SseClient>) -> AppResult<Vec<SseStatus>> {
+    Ok(client.statuses())
+}
+
+/// Get the notification that launched the app
+///
+/// When the app is launched by tapping a notification, the webview is not
+/// navigated to its link and no `notification://received` event is emitted:
+/// the page takes the original payload with this command once loaded, and
+/// routes to its link. The payload is returned once; later calls return
+/// `null`.
+///
+/// # Returns
+///
+/// Returns `{ payload, title, body, link, receivedAt }`, `link` being the
+/// parsed deep link (`{ url, path, source }`) if any, or `null` if the app was
+/// not launched from a notification or the payload was already taken.
+///
+/// # Examples
+///
+/// ```javascript
+/// const launch = await invoke('get_launch_payload');
+/// if (launch?.link) router.navigate(launch.link.path);
+/// ```
+#[cfg(feature = "module-notifications")]
+#[tauri::command]
+#[specta::specta]
+pub async fn get_launch_payload(app: AppHandle) -> AppResult<Option<LaunchPayload>> {
+    Ok(launch::take(&app))
+}
+
+/// Donate a Siri Shortcut / App Action for the displayed screen
+///
+/// The frontend calls it when the next meeting or the documents list is
+/// displayed, so the system can suggest opening that screen again. Invoking
+/// the shortcut opens its deep link (`deeplink://open` with the `shortcut`
+/// source while running).
+///
+/// # Arguments
+///
+/// * `shortcut` - Displayed screen: `next_meeting` or `documents`
+///
+/// # Returns
+///
+/// Returns `true` if the shortcut was donated, `false` if it already was since
+/// launch, or an error if the platform rejected the donation.
+///
+/// # Examples
+///
+/// ```javascript
+/// await invoke('donate_shortcut', { shortcut: 'next_meeting' });
+/// ```
+#[cfg(feature = "module-shortcuts")]
+#[tauri::command]
+#[specta::specta]
+pub async fn donate_shortcut(app: AppHandle, shortcut: Shortcut) -> AppResult<bool> {
+    shortcuts::donate(&app, shortcut)
+}
@@ -8,11 +8,57 @@
 /// - Exponential backoff retry mechanism
 /// - Non-blocking async implementation
 /// - Uses constants from the constants module
+/// - Each attempt runs in a `connectivity_attempt` span, so its duration is logged
+/// - Attempts and backoff waits go through a `Prober`, managed by the app
+///   (`ConnectivityProber`), so the retry logic can be tested with a scripted
+///   prober, without network and without waiting
+/// - Check results are reported to the webview (`report`), which receives a
+///   `connectivity://changed` event when the server becomes reachable or
+///   unreachable
+/// - The commands form the `connectivity` native module (`ConnectivityModule`),
+///   which checks connectivity in background at startup
 
 use crate::constants;
+use crate::events::{self, ConnectivityChanged};
+#[cfg(feature = "module-connectivity")]
+use crate::native_module::NativeModule;
+#[cfg(feature = "module-connectivity")]
+use crate::remote_config;
+use crate::runtime_config;
+#[cfg(feature = "module-connectivity")]
+use crate::AppResult;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tokio::net::TcpStream;
 use tokio::time::timeout;
+use tracing::Instrument;
+
+/// Server targeted by connectivity checks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectivityTarget {
+    /// Host name of the server
+    pub host: String,
+    /// TCP port of the server
+    pub port: u16,
+    /// Timeout of each connection attempt
+    pub timeout: Duration,
+}
+
+impl Default for ConnectivityTarget {
+    /// The application server, as configured in the constants module or the
+    /// runtime configuration file
+    fn default() -> Self {
+        let config = runtime_config::get();
+        ConnectivityTarget {
+            host: config.connectivity_host.clone(),
+            port: config.connectivity_port,
+            timeout: config.connectivity_timeout,
+        }
+    }
+}
 
 /// Result type for connectivity checks
 pub type ConnectivityResult = Result<bool, ConnectivityError>;
@@ -33,36 +79,233 @@ pub enum ConnectivityError {
     MaxRetriesExceeded,
 }
 
+/// Future returned by the `Prober` methods
+pub type ProbeFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Connection attempts and waits used by the connectivity checks
+pub trait Prober: Send + Sync {
+    /// Attempt a connection to the target once
+    ///
+    /// Returns `Ok(true)` if the server is reachable.
+    fn probe<'a>(&'a self, target: &'a ConnectivityTarget) -> ProbeFuture<'a, ConnectivityResult>;
+
+    /// Wait before the next attempt
+    fn wait(&self, delay: Duration) -> ProbeFuture<'_, ()>;
+}
+
+/// Prober connecting to the server over TCP
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TcpProber;
+
+impl Prober for TcpProber {
+    fn probe<'a>(&'a self, target: &'a ConnectivityTarget) -> ProbeFuture<'a, ConnectivityResult> {
+        Box::pin(check_connectivity_once(target))
+    }
+
+    fn wait(&self, delay: Duration) -> ProbeFuture<'_, ()> {
+        Box::pin(tokio::time::sleep(delay))
+    }
+}
+
+/// Prober used by the connectivity checks, managed by the Tauri application
+/// (`TcpProber` unless replaced)
+pub struct ConnectivityProber(RwLock<Arc<dyn Prober>>);
+
+impl Default for ConnectivityProber {
+    fn default() -> Self {
+        ConnectivityProber(RwLock::new(Arc::new(TcpProber)))
+    }
+}
+
+impl ConnectivityProber {
+    /// Current prober
+    pub fn get(&self) -> Arc<dyn Prober> {
+        match self.0.read() {
+            Ok(prober) => prober.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Replace the prober (scripted prober in tests)
+    pub fn replace(&self, prober: Arc<dyn Prober>) {
+        match self.0.write() {
+            Ok(mut current) => *current = prober,
+            Err(poisoned) => *poisoned.into_inner() = prober,
+        }
+    }
+}
+
+/// Last reachability reported to the webview: 0 unknown, 1 online, 2 offline
+static LAST_REPORTED: AtomicU8 = AtomicU8::new(0);
+
+/// Report the result of a connectivity check to the webview
+///
+/// Emits `connectivity://changed` when it differs from the previous result
+/// (and for the first one).
+pub fn report<R: tauri::Runtime>(app: &tauri::AppHandle<R>, online: bool) {
+    let state = if online { 1 } else { 2 };
+    if LAST_REPORTED.swap(state, Ordering::SeqCst) != state {
+        tracing::info!("Connectivity changed: {}", if online { "online" } else { "offline" });
+        events::emit(app, &ConnectivityChanged { online });
+    }
+}
+
+/// Connectivity native module (`module-connectivity`)
+#[cfg(feature = "module-connectivity")]
+pub struct ConnectivityModule;
+
+#[cfg(feature = "module-connectivity")]
+impl NativeModule for ConnectivityModule {
+    fn name(&self) -> &'static str {
+        "connectivity"
+    }
+
+    fn commands(&self) -> &'static [&'static str] {
+        &["check_connectivity", "check_connectivity_quick"]
+    }
+
+    fn events(&self) -> &'static [&'static str] {
+        &[constants::CONNECTIVITY_CHANGED_EVENT]
+    }
+
+    /// Perform a connectivity check at startup (non-blocking)
+    fn init(&self, app: &tauri::AppHandle) -> AppResult<()> {
+        use tauri::Manager;
+
+        let target = app.state::<remote_config::ConfigStore>().connectivity_target();
+        let prober = app.state::<ConnectivityProber>().get();
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tracing::info!("Starting background connectivity check...");
+            let result = check_connectivity_with(prober.as_ref(), &target).await;
+            report(&app, matches!(result, Ok(true)));
+            match result {
+                Ok(true) => {
+                    tracing::info!("Startup connectivity check: connected");
+                }
+                Ok(false) => {
+                    tracing::warn!("Startup connectivity check: not connected");
+                }
+                Err(e) => {
+                    tracing::error!("Startup connectivity check error: {}", e);
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Outcome of an attempt of the `ScriptedProber`
+#[cfg(any(test, feature = "test-harness"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeOutcome {
+    /// The server is reachable
+    Connected,
+    /// The connection is refused
+    Refused,
+    /// The connection times out
+    Timeout,
+}
+
+/// Prober replaying scripted outcomes, without network and without waiting
+///
+/// Once the script is exhausted, attempts are refused. Attempts and waits
+/// are recorded, so tests can assert on the retry schedule.
+#[cfg(any(test, feature = "test-harness"))]
+#[derive(Debug, Default)]
+pub struct ScriptedProber {
+    outcomes: std::sync::Mutex<std::collections::VecDeque<ProbeOutcome>>,
+    probes: std::sync::atomic::AtomicUsize,
+    waits: std::sync::Mutex<Vec<Duration>>,
+}
+
+#[cfg(any(test, feature = "test-harness"))]
+impl ScriptedProber {
+    /// Prober returning the outcomes in order
+    pub fn new(outcomes: impl IntoIterator<Item = ProbeOutcome>) -> Self {
+        ScriptedProber {
+            outcomes: std::sync::Mutex::new(outcomes.into_iter().collect()),
+            ..Default::default()
+        }
+    }
+
+    /// Number of attempts made
+    pub fn probes(&self) -> usize {
+        self.probes.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    /// Waits requested between attempts
+    pub fn waits(&self) -> Vec<Duration> {
+        self.waits.lock().map(|waits| waits.clone()).unwrap_or_default()
+    }
+}
+
+#[cfg(any(test, feature = "test-harness"))]
+impl Prober for ScriptedProber {
+    fn probe<'a>(&'a self, _target: &'a ConnectivityTarget) -> ProbeFuture<'a, ConnectivityResult> {
+        self.probes.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let outcome = self
+            .outcomes
+            .lock()
+            .ok()
+            .and_then(|mut outcomes| outcomes.pop_front())
+            .unwrap_or(ProbeOutcome::Refused);
+        Box::pin(async move {
+            match outcome {
+                ProbeOutcome::Connected => Ok(true),
+                ProbeOutcome::Refused => Err(ConnectivityError::Io(std::io::Error::new(
+                    std::io::ErrorKind::ConnectionRefused,
+                    "Scripted connection refused",
+                ))),
+                ProbeOutcome::Timeout => Err(ConnectivityError::Timeout),
+            }
+        })
+    }
+
+    fn wait(&self, delay: Duration) -> ProbeFuture<'_, ()> {
+        if let Ok(mut waits) = self.waits.lock() {
+            waits.push(delay);
+        }
+        Box::pin(std::future::ready(()))
+    }
+}
+
+/// Delay before a retry attempt (1-based): `RETRY_BASE_DELAY_MS * 2^(attempt - 1)`
+pub fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_millis(constants::RETRY_BASE_DELAY_MS * (1 << (attempt.max(1) - 1)))
+}
+
 /// Performs a single connectivity check attempt
 ///
-/// Attempts to establish a TCP connection to the configured host and port
-/// within the specified timeout period.
+/// Attempts to establish a TCP connection to the target host and port
+/// within the target timeout period.
 ///
 /// # Returns
 ///
 /// - `Ok(true)` if connection succeeds
 /// - `Err(ConnectivityError::Io(_))` if connection fails due to network I/O error
 /// - `Err(ConnectivityError::Timeout)` if connection times out
-async fn check_connectivity_once() -> ConnectivityResult {
-    let host = constants::CONNECTIVITY_HOST;
-    let port = constants::CONNECTIVITY_PORT;
-    let timeout_duration = Duration::from_secs(constants::CONNECTIVITY_TIMEOUT_SECS);
+async fn check_connectivity_once(target: &ConnectivityTarget) -> ConnectivityResult {
+    let host = &target.host;
+    let port = target.port;
+    let timeout_duration = target.timeout;
     
     let addr = format!("{}:{}", host, port);
+    let span = tracing::debug_span!("connectivity_attempt", host = %host, port);
     
-    log::debug!("Checking connectivity to {}:{}", host, port);
+    tracing::debug!(parent: &span, "Checking connectivity to {}:{}", host, port);
     
-    match timeout(timeout_duration, TcpStream::connect(&addr)).await {
+    match timeout(timeout_duration, TcpStream::connect(&addr)).instrument(span).await {
         Ok(Ok(_stream)) => {
-            log::debug!("Connectivity check successful: {}:{}", host, port);
+            tracing::debug!("Connectivity check successful: {}:{}", host, port);
             Ok(true)
         }
         Ok(Err(e)) => {
-            log::debug!("Connectivity check failed: {}:{} - {}", host, port, e);
+            tracing::debug!("Connectivity check failed: {}:{} - {}", host, port, e);
             Err(ConnectivityError::Io(e))
         }
         Err(_) => {
-            log::debug!("Connectivity check timeout: {}:{}", host, port);
+            tracing::debug!("Connectivity check timeout: {}:{}", host, port);
             Err(ConnectivityError::Timeout)
         }
     }
@@ -95,59 +338,55 @@ async fn check_connectivity_once() -> ConnectivityResult {
 /// # }
 /// ```
 pub async fn check_connectivity() -> ConnectivityResult {
+    check_connectivity_to(&ConnectivityTarget::default()).await
+}
+
+/// Performs a connectivity check against a specific target, with retry logic
+///
+/// Same as `check_connectivity()`, for a target overridden by remote config.
+pub async fn check_connectivity_to(target: &ConnectivityTarget) -> ConnectivityResult {
+    check_connectivity_with(&TcpProber, target).await
+}
+
+/// Performs a connectivity check with retry logic, using a prober
+///
+/// The first attempt is made right away; failed attempts are retried up to
+/// `MAX_CONNECTIVITY_RETRIES` times, after exponentially growing waits
+/// (`retry_delay`).
+pub async fn check_connectivity_with(prober: &dyn Prober, target: &ConnectivityTarget) -> ConnectivityResult {
     let max_retries = constants::MAX_CONNECTIVITY_RETRIES;
-    let base_delay_ms = constants::RETRY_BASE_DELAY_MS;
     
-    // First attempt (no delay)
-    match check_connectivity_once().await {
-        Ok(true) => {
-            log::info!("Connectivity check passed on first attempt");
-            return Ok(true);
+    for attempt in 0..=max_retries {
+        if attempt > 0 {
+            let delay = retry_delay(attempt);
+            tracing::debug!(
+                "Retrying connectivity check (attempt {}/{}) after {}ms",
+                attempt,
+                max_retries,
+                delay.as_millis()
+            );
+            prober.wait(delay).await;
         }
-        Ok(false) => {
-            // Unreachable: check_connectivity_once() only returns Ok(true) or Err
-            unreachable!("check_connectivity_once() never returns Ok(false)");
-        }
-        Err(ConnectivityError::Timeout) => {
-            // Will retry below
-        }
-        Err(e) => {
-            log::warn!("Connectivity check error: {}", e);
-            // Will retry below
-        }
-    }
-    
-    // Retry with exponential backoff
-    for attempt in 1..=max_retries {
-        let delay_ms = base_delay_ms * (1 << (attempt - 1)); // Exponential: 500ms, 1000ms, 2000ms...
-        let delay = Duration::from_millis(delay_ms);
-        
-        log::debug!("Retrying connectivity check (attempt {}/{}) after {}ms", attempt, max_retries, delay_ms);
         
-        tokio::time::sleep(delay).await;
-        
-        match check_connectivity_once().await {
-            Ok(true) => {
-                log::info!("Connectivity check passed on retry attempt {}", attempt);
+        match prober.probe(target).await {
+            Ok(true) if attempt == 0 => {
+                tracing::info!("Connectivity check passed on first attempt");
                 return Ok(true);
             }
-            Ok(false) => {
-                // Unreachable: check_connectivity_once() only returns Ok(true) or Err
-                unreachable!("check_connectivity_once() never returns Ok(false)");
+            Ok(true) => {
+                tracing::info!("Connectivity check passed on retry attempt {}", attempt);
+                return Ok(true);
             }
-            Err(ConnectivityError::Timeout) => {
-                // Continue to next retry
-                continue;
+            Ok(false) | Err(ConnectivityError::Timeout) => {
+                // Retry below
             }
             Err(e) => {
-                log::warn!("Connectivity check error on attempt {}: {}", attempt, e);
-                // Continue to next retry
-                continue;
+                tracing::warn!("Connectivity check error on attempt {}: {}", attempt, e);
             }
         }
     }
     
-    log::warn!("Connectivity check failed after {} retries", max_retries);
+    tracing::warn!("Connectivity check failed after {} retries", max_retries);
     Ok(false)
 }
 
@@ -162,11 +401,23 @@ pub async fn check_connectivity() -> ConnectivityResult {
 /// - `Ok(false)` if connectivity is not available
 /// - `Err(ConnectivityError)` if an unexpected error occurs
 pub async fn check_connectivity_quick() -> ConnectivityResult {
-    check_connectivity_once().await.map(|connected| {
+    check_connectivity_quick_to(&ConnectivityTarget::default()).await
+}
+
+/// Performs a quick connectivity check against a specific target
+///
+/// Same as `check_connectivity_quick()`, for a target overridden by remote config.
+pub async fn check_connectivity_quick_to(target: &ConnectivityTarget) -> ConnectivityResult {
+    check_connectivity_quick_with(&TcpProber, target).await
+}
+
+/// Performs a quick connectivity check, using a prober
+pub async fn check_connectivity_quick_with(prober: &dyn Prober, target: &ConnectivityTarget) -> ConnectivityResult {
+    prober.probe(target).await.map(|connected| {
         if connected {
-            log::info!("Quick connectivity check: connected");
+            tracing::info!("Quick connectivity check: connected");
         } else {
-            log::info!("Quick connectivity check: not connected");
+            tracing::info!("Quick connectivity check: not connected");
         }
         connected
     })
@@ -180,7 +431,7 @@ mod tests {
     async fn test_check_connectivity_once_invalid_host() {
         // This test would require mocking or a test server
         // For now, we just verify the function compiles and handles errors
-        let result = check_connectivity_once().await;
+        let result = check_connectivity_once(&ConnectivityTarget::default()).await;
         // Result will be Ok(true) on success or Err(ConnectivityError) on failure
         assert!(matches!(result, Ok(_) | Err(_)));
     }
@@ -189,7 +440,7 @@ mod tests {
     async fn test_check_connectivity_once_return_types() {
         // Verify that check_connectivity_once only returns Ok(true) or Err
         // It should never return Ok(false)
-        let result = check_connectivity_once().await;
+        let result = check_connectivity_once(&ConnectivityTarget::default()).await;
         
         match result {
             Ok(true) => {
@@ -238,5 +489,47 @@ mod tests {
             panic!("Should be ConnectivityError::Timeout");
         }
     }
+    
+    #[tokio::test]
+    async fn test_retries_with_exponential_backoff() {
+        let target = ConnectivityTarget::default();
+        let prober = ScriptedProber::new([ProbeOutcome::Timeout, ProbeOutcome::Refused, ProbeOutcome::Connected]);
+        
+        assert_eq!(check_connectivity_with(&prober, &target).await.unwrap(), true);
+        assert_eq!(prober.probes(), 3);
+        assert_eq!(prober.waits(), vec![retry_delay(1), retry_delay(2)]);
+        assert_eq!(retry_delay(1), Duration::from_millis(constants::RETRY_BASE_DELAY_MS));
+        assert_eq!(retry_delay(2), Duration::from_millis(constants::RETRY_BASE_DELAY_MS * 2));
+    }
+    
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let target = ConnectivityTarget::default();
+        let prober = ScriptedProber::new([]);
+        
+        assert_eq!(check_connectivity_with(&prober, &target).await.unwrap(), false);
+        assert_eq!(prober.probes(), constants::MAX_CONNECTIVITY_RETRIES as usize + 1);
+        assert_eq!(prober.waits().len(), constants::MAX_CONNECTIVITY_RETRIES as usize);
+        
+        let connected = ScriptedProber::new([ProbeOutcome::Connected]);
+        assert_eq!(check_connectivity_with(&connected, &target).await.unwrap(), true);
+        assert!(connected.waits().is_empty(), "No wait before the first attempt");
+    }
+    
+    #[tokio::test]
+    async fn test_quick_check_does_not_retry() {
+        let target = ConnectivityTarget::default();
+        let prober = ScriptedProber::new([ProbeOutcome::Timeout, ProbeOutcome::Connected]);
+        
+        assert!(matches!(
+            check_connectivity_quick_with(&prober, &target).await,
+            Err(ConnectivityError::Timeout)
+        ));
+        assert_eq!(prober.probes(), 1);
+        
+        let managed = ConnectivityProber::default();
+        managed.replace(Arc::new(ScriptedProber::new([ProbeOutcome::Connected])));
+        assert_eq!(check_connectivity_quick_with(managed.get().as_ref(), &target).await.unwrap(), true);
+    }
 }
 
@@ -7,15 +7,18 @@
 // Application Configuration
 // ============================================================================
 
-/// Main web application URL
+/// Main web application URL (production environment)
 pub const APP_URL: &str = "https://app.elulib.com";
 
-/// Host for connectivity verification
+/// Host for connectivity verification (production environment)
 pub const CONNECTIVITY_HOST: &str = "app.elulib.com";
 
 /// Port for connectivity verification (HTTPS)
 pub const CONNECTIVITY_PORT: u16 = 443;
 
+/// Push sender (Firebase project) identifier (production environment)
+pub const PUSH_SENDER_ID: &str = "elulib-mobile";
+
 /// Application title
 pub const APP_TITLE: &str = "élulib";
 
@@ -25,6 +28,32 @@ pub const APP_IDENTIFIER: &str = "com.elulib.mobile";
 /// Authorized identifier for keychain/keystore storage
 pub const KEYCHAIN_SERVICE_ID: &str = "com.elulib.mobile";
 
+// ============================================================================
+// Environments
+// ============================================================================
+
+/// Web application URL of the pre-production environment
+pub const PREPROD_APP_URL: &str = "https://preprod.elulib.com";
+
+/// Connectivity host of the pre-production environment
+pub const PREPROD_CONNECTIVITY_HOST: &str = "preprod.elulib.com";
+
+/// Push sender identifier of the pre-production environment
+pub const PREPROD_PUSH_SENDER_ID: &str = "elulib-mobile-preprod";
+
+/// Web application URL of the staging environment
+pub const STAGING_APP_URL: &str = "https://staging.elulib.com";
+
+/// Connectivity host of the staging environment
+pub const STAGING_CONNECTIVITY_HOST: &str = "staging.elulib.com";
+
+/// Push sender identifier of the staging environment
+pub const STAGING_PUSH_SENDER_ID: &str = "elulib-mobile-staging";
+
+/// File in the app config directory storing the environment selected with
+/// `environment_set` (debug builds only)
+pub const ENVIRONMENT_OVERRIDE_FILE: &str = "environment";
+
 // ============================================================================
 // Platform Requirements
 // ============================================================================
@@ -59,6 +88,19 @@ pub const MAX_KEYCHAIN_VALUE_LENGTH: usize = 4096;
 /// and provides clear error messages when validation fails.
 pub const MIN_KEYCHAIN_KEY_LENGTH: usize = 1;
 
+/// Keychain keys written by the native layer, which the keychain commands
+/// cannot read, overwrite or remove
+pub const RESERVED_KEYCHAIN_KEYS: &[&str] = &[SESSION_TOKEN_KEY, DATABASE_KEY_NAME, AUDIT_LOG_ANCHOR_KEY];
+
+/// Prefixes of the keychain keys reserved to the native layer
+pub const RESERVED_KEYCHAIN_KEY_PREFIXES: &[&str] = &[AUTH_TOKENS_KEY_PREFIX, BENCHMARK_KEY_PREFIX];
+
+/// Substrings of the keystore errors reporting a missing key
+///
+/// The keystore plugin only reports errors as messages: `errSecItemNotFound`
+/// on iOS, no stored ciphertext on Android, and the dev mock keystore.
+pub const KEYCHAIN_NOT_FOUND_ERRORS: &[&str] = &["-25300", "No cipher data found", "No value for"];
+
 // ============================================================================
 // Connectivity & Timeouts
 // ============================================================================
@@ -101,21 +143,19 @@ pub const MAX_CONNECTIVITY_RETRIES: u32 = 2;
 /// avoiding excessive load on the network stack.
 pub const RETRY_BASE_DELAY_MS: u64 = 500;
 
+/// Event emitted to the webview when the server becomes reachable or
+/// unreachable, with `{ online }`
+pub const CONNECTIVITY_CHANGED_EVENT: &str = "connectivity://changed";
+
 // ============================================================================
 // Rate Limiting
 // ============================================================================
 
-// TODO: Implement rate limiting for keychain operations to prevent abuse
-// Rate limiting constants are defined below but not yet used in the codebase
-
 /// Rate limiting: Maximum number of keychain operations per time window
 ///
 /// This constant defines the maximum number of keychain operations (store, retrieve,
 /// remove, exists) that can be performed within the time window defined by
-/// `RATE_LIMIT_WINDOW_SECS`.
-///
-/// **Note**: Rate limiting is not yet implemented. These constants are reserved
-/// for future implementation to prevent abuse and excessive keychain access.
+/// `RATE_LIMIT_WINDOW_SECS`. The keychain commands share a single bucket.
 ///
 /// Example: With `RATE_LIMIT_MAX_REQUESTS = 10` and `RATE_LIMIT_WINDOW_SECS = 60`,
 /// a maximum of 10 keychain operations would be allowed per 60-second window.
@@ -124,16 +164,1065 @@ pub const RATE_LIMIT_MAX_REQUESTS: u32 = 10;
 /// Rate limiting: Time window in seconds for keychain operations
 ///
 /// This constant defines the time window (in seconds) used for rate limiting
-/// keychain operations. Combined with `RATE_LIMIT_MAX_REQUESTS`, it determines
-/// how many operations are allowed per time period.
-///
-/// **Note**: Rate limiting is not yet implemented. These constants are reserved
-/// for future implementation.
+/// commands. Combined with the `RATE_LIMIT_*_MAX_REQUESTS` constants, it
+/// determines how many operations are allowed per time period.
 ///
 /// Example: With `RATE_LIMIT_WINDOW_SECS = 60` and `RATE_LIMIT_MAX_REQUESTS = 10`,
 /// a maximum of 10 keychain operations would be allowed per 60-second window.
 pub const RATE_LIMIT_WINDOW_SECS: u64 = 60;
 
+/// Rate limiting: Maximum number of interactive authentication requests (login,
+/// passkeys, unlock) per time window
+pub const RATE_LIMIT_AUTH_MAX_REQUESTS: u32 = 10;
+
+/// Rate limiting: Maximum number of invocations per time window of a command
+/// without a named bucket
+pub const RATE_LIMIT_DEFAULT_MAX_REQUESTS: u32 = 120;
+
+/// Rate limiting: Maximum number of local database queries (`db_query`,
+/// `db_query_packed`) per time window; every rendered list runs some
+pub const RATE_LIMIT_DB_QUERY_MAX_REQUESTS: u32 = 1200;
+
+/// Rate limiting: Maximum number of local database writes (`db_execute`) per
+/// time window
+pub const RATE_LIMIT_DB_EXECUTE_MAX_REQUESTS: u32 = 600;
+
+/// Rate limiting: Maximum number of `session_activity` reports per time
+/// window (the frontend reports user interactions, throttled)
+pub const RATE_LIMIT_SESSION_ACTIVITY_MAX_REQUESTS: u32 = 300;
+
+/// Rate limiting: Maximum number of realtime messages (`ws_send`) per time
+/// window
+pub const RATE_LIMIT_WS_SEND_MAX_REQUESTS: u32 = 1200;
+
+/// Rate limiting: Maximum number of invocations of all commands per time window
+///
+/// Above the sum of the high-frequency buckets, so they are not throttled by
+/// the global ceiling in normal use.
+pub const RATE_LIMIT_GLOBAL_MAX_REQUESTS: u32 = 3600;
+
+// ============================================================================
+// Native API Version
+// ============================================================================
+
+/// Latest native API version (see `api_version`)
+pub const NATIVE_API_VERSION: u32 = 2;
+
+/// Oldest native API version the app still answers with
+pub const NATIVE_API_MIN_VERSION: u32 = 1;
+
+// ============================================================================
+// Native SDK
+// ============================================================================
+
+/// Global object of the injected SDK (`window.EluNative`)
+pub const NATIVE_SDK_GLOBAL: &str = "EluNative";
+
+/// Invoke header carrying the request id of an SDK call
+pub const REQUEST_ID_HEADER: &str = "x-elu-request-id";
+
+/// Maximum length of a logged request id
+pub const REQUEST_ID_MAX_LEN: usize = 64;
+
+/// Delay after which an SDK call rejects with `TIMEOUT` (milliseconds)
+pub const NATIVE_SDK_TIMEOUT_MS: u64 = 30_000;
+
+/// Maximum number of retries of an SDK call failing with a retryable error
+pub const NATIVE_SDK_MAX_RETRIES: u32 = 2;
+
+/// Delay before the first retry of an SDK call (milliseconds), doubled at
+/// each retry; a rate limited call waits at least its `retryAfterMs`
+pub const NATIVE_SDK_RETRY_BASE_DELAY_MS: u64 = 250;
+
+// ============================================================================
+// App Lifecycle
+// ============================================================================
+
+/// Event emitted to the webview when the app comes to the foreground
+pub const LIFECYCLE_FOREGROUND_EVENT: &str = "lifecycle://foreground";
+
+/// Event emitted to the webview when the app goes to the background
+pub const LIFECYCLE_BACKGROUND_EVENT: &str = "lifecycle://background";
+
+/// Event emitted to the webview after the foreground event, with the time spent in background
+pub const LIFECYCLE_RESUME_EVENT: &str = "lifecycle://resume";
+
+// ============================================================================
+// Session Lock
+// ============================================================================
+
+/// Default delay of inactivity or background time before the session locks (seconds)
+///
+/// Can be overridden at runtime (see `SessionLock::set_timeout`).
+pub const SESSION_LOCK_TIMEOUT_SECS: u64 = 5 * 60;
+
+/// Interval between two inactivity checks while in foreground (seconds)
+pub const SESSION_LOCK_CHECK_INTERVAL_SECS: u64 = 15;
+
+/// Event emitted to the webview when the session locks, with `{ reason }`
+pub const SESSION_LOCKED_EVENT: &str = "session://locked";
+
+/// Event emitted to the webview when the session is unlocked
+pub const SESSION_UNLOCKED_EVENT: &str = "session://unlocked";
+
+/// Keychain key of the session token, handed off to the webview by the
+/// `session_handoff` module and not readable with `keychain_retrieve`
+pub const SESSION_TOKEN_KEY: &str = "session_token";
+
+// ============================================================================
+// Native Login
+// ============================================================================
+
+/// Prefix of the keychain keys holding the tokens of a native login session
+/// (`auth.session.<handle>`), not readable with `keychain_retrieve`
+pub const AUTH_TOKENS_KEY_PREFIX: &str = "auth.session.";
+
+/// Keychain key listing the handles of the stored native login sessions, so
+/// the local data wipe can remove their tokens (under the protected prefix)
+pub const AUTH_SESSION_INDEX_KEY: &str = "auth.session.index";
+
+/// Maximum time the user has to complete a login in the browser session (seconds)
+pub const AUTH_LOGIN_TIMEOUT_SECS: u64 = 10 * 60;
+
+// ============================================================================
+// HTTP & App Updates
+// ============================================================================
+
+/// Timeout for HTTP requests to the backend (seconds)
+pub const HTTP_TIMEOUT_SECS: u64 = 10;
+
+/// Endpoint returning the latest and minimum supported app versions
+///
+/// Queried with `?platform=ios|android`, it returns
+/// `{ "latestVersion": "1.4.0", "minimumVersion": "1.2.0", "storeUrl": "..." }`.
+pub const APP_VERSION_ENDPOINT: &str = "https://app.elulib.com/api/mobile/version";
+
+/// Play Store listing, used when the version endpoint does not provide a store URL
+pub const PLAY_STORE_URL: &str = "https://play.google.com/store/apps/details?id=com.elulib.mobile";
+
+/// File storing the last fetched version policy, in the app data directory
+pub const VERSION_POLICY_CACHE_FILE: &str = "version_policy.json";
+
+/// Maximum age of the cached version policy used when offline (seconds)
+pub const VERSION_POLICY_CACHE_MAX_AGE_SECS: u64 = 72 * 60 * 60;
+
+/// Event emitted when the installed version is below the minimum supported version
+pub const UPDATE_REQUIRED_EVENT: &str = "update://required";
+
+/// Event emitted with the download progress of an Android in-app update
+pub const UPDATE_PROGRESS_EVENT: &str = "update://progress";
+
+/// Event emitted when an Android in-app update flow finishes
+pub const UPDATE_RESULT_EVENT: &str = "update://result";
+
+// ============================================================================
+// Remote Configuration
+// ============================================================================
+
+/// Endpoint returning the signed remote config
+pub const REMOTE_CONFIG_ENDPOINT: &str = "https://app.elulib.com/api/mobile/config";
+
+/// Ed25519 public key verifying the remote config (base64, 32 raw bytes)
+///
+/// Provided at build time with the `ELULIB_REMOTE_CONFIG_PUBLIC_KEY` environment
+/// variable. Remote config is disabled when it is not set.
+pub const REMOTE_CONFIG_PUBLIC_KEY: Option<&str> = option_env!("ELULIB_REMOTE_CONFIG_PUBLIC_KEY");
+
+/// File caching the last fetched remote config, in the app data directory
+pub const REMOTE_CONFIG_CACHE_FILE: &str = "remote_config.json";
+
+/// Age after which the remote config is fetched again (seconds)
+pub const REMOTE_CONFIG_TTL_SECS: u64 = 60 * 60;
+
+/// Event emitted with the feature flags whose value changed
+pub const FEATURE_FLAGS_CHANGED_EVENT: &str = "feature://changed";
+
+// ============================================================================
+// Crash Reporting
+// ============================================================================
+
+/// Directory where crash reports are stored, in the app data directory
+pub const CRASH_REPORT_DIR: &str = "crashes";
+
+/// Extension of minidumps written by the native crash handlers
+pub const MINIDUMP_EXTENSION: &str = "dmp";
+
+/// File storing the crash reporting opt-in, in the crash report directory
+pub const CRASH_SETTINGS_FILE: &str = "settings.json";
+
+/// Marker file created by the panic hook, flagging the next launch as post-crash
+pub const CRASH_MARKER_FILE: &str = "crashed";
+
+/// Number of log lines kept as breadcrumbs for panic reports
+pub const CRASH_BREADCRUMB_CAPACITY: usize = 50;
+
+/// Number of breadcrumbs kept in the trail attached to crash reports and
+/// diagnostics (oldest are dropped first)
+pub const BREADCRUMB_CAPACITY: usize = 100;
+
+/// Maximum length of a breadcrumb category
+pub const BREADCRUMB_MAX_CATEGORY_LENGTH: usize = 32;
+
+/// Maximum length of a breadcrumb message (longer messages are truncated)
+pub const BREADCRUMB_MAX_MESSAGE_LENGTH: usize = 256;
+
+/// Interval between two pings of the main thread by the watchdog (milliseconds)
+pub const WATCHDOG_INTERVAL_MS: u64 = 1_000;
+
+/// Time without a main thread response after which the app is reported as
+/// not responding (milliseconds)
+pub const WATCHDOG_HANG_THRESHOLD_MS: u64 = 5_000;
+
+/// Endpoint receiving crash reports
+pub const CRASH_REPORT_ENDPOINT: &str = "https://app.elulib.com/api/mobile/crashes";
+
+// ============================================================================
+// Encrypted Artifacts
+// ============================================================================
+
+/// Extension appended to sealed artifacts (`panic-1.json.enc`)
+pub const ARTIFACT_EXTENSION: &str = "enc";
+
+/// Header of sealed artifacts, identifying the format version
+pub const ARTIFACT_MAGIC: &[u8] = b"ELA1";
+
+/// HKDF info deriving the artifact key from the master key
+pub const ARTIFACT_KEY_INFO: &str = "elulib-artifacts-v1";
+
+// ============================================================================
+// Analytics
+// ============================================================================
+
+/// Endpoint receiving analytics event batches
+pub const ANALYTICS_ENDPOINT: &str = "https://app.elulib.com/api/mobile/events";
+
+/// Number of queued events triggering an upload
+pub const ANALYTICS_BATCH_SIZE: usize = 20;
+
+/// Maximum number of events kept while offline (oldest are dropped first)
+pub const ANALYTICS_MAX_QUEUED_EVENTS: usize = 500;
+
+/// Interval between periodic uploads of queued events (seconds)
+pub const ANALYTICS_FLUSH_INTERVAL_SECS: u64 = 60;
+
+/// Maximum length of string event properties
+pub const ANALYTICS_MAX_VALUE_LENGTH: usize = 64;
+
+/// File storing queued events between launches, in the app data directory
+pub const ANALYTICS_QUEUE_FILE: &str = "analytics_queue.json";
+
+/// File storing the analytics opt-out, in the app data directory
+pub const ANALYTICS_SETTINGS_FILE: &str = "analytics_settings.json";
+
+// ============================================================================
+// Deep Links
+// ============================================================================
+
+/// Custom URL scheme of the application (`elulib://...`)
+pub const DEEP_LINK_SCHEME: &str = "elulib";
+
+/// Label of the main webview window receiving deep links
+pub const MAIN_WINDOW_LABEL: &str = "main";
+
+/// Event emitted to the webview when a deep link is opened while running
+pub const DEEP_LINK_EVENT: &str = "deeplink://open";
+
+/// Prefix of the `NSUserActivity` types of the Siri Shortcuts, followed by
+/// the shortcut id (declared under `NSUserActivityTypes` in `Info.plist`)
+pub const SHORTCUT_ACTIVITY_TYPE_PREFIX: &str = "com.elulib.mobile.shortcut.";
+
+/// Event emitted to the webview for every visible push, with
+/// `{ title, body, link, tapped }`
+pub const NOTIFICATION_RECEIVED_EVENT: &str = "notification://received";
+
+/// Maximum time the payload of the notification that launched the app waits
+/// for the page (seconds)
+pub const LAUNCH_PAYLOAD_MAX_AGE_SECS: u64 = 5 * 60;
+
+// ============================================================================
+// Badge
+// ============================================================================
+
+/// File storing the badge reset policy, in the app data directory
+pub const BADGE_SETTINGS_FILE: &str = "badge_settings.json";
+
+// ============================================================================
+// Locale
+// ============================================================================
+
+/// Event emitted to the webview when the system locale changes
+pub const LOCALE_CHANGED_EVENT: &str = "locale://changed";
+
+/// Reload the webview on locale change (overridable by remote config)
+pub const LOCALE_RELOAD_ON_CHANGE: bool = false;
+
+// ============================================================================
+// Install Tracking
+// ============================================================================
+
+/// File (relative to the app data directory) recording install and launch metadata
+pub const INSTALL_RECORD_FILE: &str = "install.json";
+
+// ============================================================================
+// Device Policy
+// ============================================================================
+
+/// Maximum session lock timeout when the device policy requires frequent
+/// re-authentication (seconds)
+pub const POLICY_REAUTH_TIMEOUT_SECS: u64 = 60;
+
+/// Event emitted when the active device policy changes
+pub const DEVICE_POLICY_CHANGED_EVENT: &str = "policy://changed";
+
+// ============================================================================
+// Device Binding
+// ============================================================================
+
+/// Alias (Android Keystore) and application tag (iOS keychain) of the
+/// device-binding key
+pub const DEVICE_BINDING_KEY_ALIAS: &str = "com.elulib.mobile.device_binding";
+
+// ============================================================================
+// App State Restoration
+// ============================================================================
+
+/// File (relative to the app data directory) holding the persisted app state snapshot
+pub const STATE_SNAPSHOT_FILE: &str = "state_snapshot.json";
+
+/// Maximum serialized size of an app state snapshot (bytes)
+///
+/// The snapshot only holds hints (route, scroll offsets, draft ids), not
+/// content, so 64 KiB is generous while keeping the write on background fast.
+pub const MAX_STATE_SNAPSHOT_BYTES: usize = 64 * 1024;
+
+/// Maximum age of a snapshot to be restored on launch (seconds)
+///
+/// After a day, restoring the previous route is more confusing than helpful.
+pub const STATE_SNAPSHOT_MAX_AGE_SECS: u64 = 24 * 60 * 60;
+
+// ============================================================================
+// Offline Asset Cache
+// ============================================================================
+
+/// Directory (relative to the app cache directory) where web application
+/// assets are cached
+pub const ASSET_CACHE_DIR: &str = "assets";
+
+/// Index of the cached assets, in the asset cache directory
+pub const ASSET_CACHE_INDEX_FILE: &str = "index.json";
+
+/// Maximum total size of the cached assets (bytes)
+pub const ASSET_CACHE_MAX_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Maximum size of a single cached asset (bytes)
+pub const ASSET_CACHE_MAX_ASSET_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Minimum time between two revalidations of a cached asset (seconds)
+pub const ASSET_CACHE_REVALIDATE_SECS: u64 = 60;
+
+/// Extensions of cacheable assets (extension-less paths are HTML shell routes)
+pub const ASSET_CACHE_EXTENSIONS: &[&str] = &["html", "js", "mjs", "css"];
+
+/// Content types of cacheable responses
+pub const ASSET_CACHE_CONTENT_TYPES: &[&str] = &[
+    "text/html",
+    "text/css",
+    "text/javascript",
+    "application/javascript",
+];
+
+/// Path prefixes never cached (backend API)
+pub const ASSET_CACHE_EXCLUDED_PREFIXES: &[&str] = &["/api/"];
+
+// ============================================================================
+// Image Cache
+// ============================================================================
+
+/// Image cache directory, in the app cache directory
+pub const IMAGE_CACHE_DIR: &str = "images";
+
+/// Index of the image cache, in the image cache directory
+pub const IMAGE_CACHE_INDEX_FILE: &str = "index.json";
+
+/// Maximum size of the image cache (bytes)
+pub const IMAGE_CACHE_MAX_BYTES: u64 = 30 * 1024 * 1024;
+
+/// Maximum size of a cached image (bytes)
+pub const IMAGE_CACHE_MAX_IMAGE_BYTES: u64 = 5 * 1024 * 1024;
+
+// ============================================================================
+// Background Sync
+// ============================================================================
+
+/// Collections synced into the local database when the remote config does not
+/// set `sync.collections` (name, endpoint)
+pub const SYNC_DEFAULT_COLLECTIONS: &[(&str, &str)] = &[
+    ("agenda", "https://app.elulib.com/api/mobile/sync/agenda"),
+    ("documents", "https://app.elulib.com/api/mobile/sync/documents"),
+];
+
+/// Interval between two syncs while the app is in foreground (seconds)
+pub const SYNC_INTERVAL_SECS: u64 = 15 * 60;
+
+/// Maximum number of pages pulled per collection and sync
+pub const SYNC_MAX_PAGES: usize = 20;
+
+/// Event emitted when a sync completes
+pub const SYNC_COMPLETED_EVENT: &str = "sync://completed";
+
+/// `type` of the data-only pushes asking for a sync
+pub const SYNC_PUSH_TYPE: &str = "sync";
+
+/// Delay during which sync pushes are coalesced into one sync (milliseconds)
+pub const SYNC_PUSH_DEBOUNCE_MS: u64 = 2000;
+
+/// Time within which a push-triggered sync must complete (seconds)
+///
+/// iOS grants about 30 seconds to handle a background push before
+/// suspending the app; Android expedited work gets a similar budget.
+pub const SYNC_PUSH_TIME_BUDGET_SECS: u64 = 25;
+
+// ============================================================================
+// Realtime Connection
+// ============================================================================
+
+/// Event emitted to the webview when the state of the realtime connection
+/// changes, with `{ state, url, attempt, queued }`
+pub const WS_STATE_EVENT: &str = "ws://state";
+
+/// Event emitted to the webview for every message of the realtime
+/// connection, with `{ data, binary }`
+pub const WS_MESSAGE_EVENT: &str = "ws://message";
+
+/// Delay before the first reconnection attempt (milliseconds), doubled at
+/// each failed attempt
+pub const WS_RECONNECT_BASE_DELAY_MS: u64 = 1000;
+
+/// Maximum delay between two reconnection attempts (milliseconds)
+pub const WS_RECONNECT_MAX_DELAY_MS: u64 = 60_000;
+
+/// Maximum number of reconnection attempts in a row before the realtime
+/// connection is given up (until the next `ws_connect`)
+pub const WS_MAX_RECONNECT_ATTEMPTS: u32 = 10;
+
+/// Close codes with which the server refuses the session credentials
+/// (policy violation, unauthorized); the client does not reconnect
+pub const WS_AUTH_CLOSE_CODES: &[u16] = &[1008, 4401];
+
+/// Interval between two pings of the realtime connection (seconds)
+///
+/// A connection that did not answer the previous ping is considered lost.
+pub const WS_PING_INTERVAL_SECS: u64 = 30;
+
+/// Maximum number of messages queued while disconnected
+pub const WS_MAX_QUEUED_MESSAGES: usize = 100;
+
+// ============================================================================
+// Server-Sent Events
+// ============================================================================
+
+/// Event emitted to the webview when the state of a subscription changes,
+/// with `{ id, url, state, lastEventId, attempt }`
+pub const SSE_STATE_EVENT: &str = "sse://state";
+
+/// Event emitted to the webview for every event of a stream, with
+/// `{ subscription, event, data, id }`
+pub const SSE_MESSAGE_EVENT: &str = "sse://message";
+
+/// Delay before the first reconnection attempt when the server did not send
+/// a `retry` (milliseconds), doubled at each failed attempt
+pub const SSE_RECONNECT_BASE_DELAY_MS: u64 = 1000;
+
+/// Maximum delay between two reconnection attempts (milliseconds)
+pub const SSE_RECONNECT_MAX_DELAY_MS: u64 = 60_000;
+
+/// Time without data after which a stream is considered lost (seconds)
+pub const SSE_IDLE_TIMEOUT_SECS: u64 = 90;
+
+/// Maximum number of streams subscribed at once
+pub const SSE_MAX_SUBSCRIPTIONS: usize = 4;
+
+/// Maximum size of an event (bytes)
+pub const SSE_MAX_EVENT_BYTES: usize = 1024 * 1024;
+
+// ============================================================================
+// App File Storage
+// ============================================================================
+
+/// Directory (relative to the app data directory) of the files written by
+/// the frontend
+pub const FILE_STORAGE_DIR: &str = "files";
+
+/// Quotas of the top-level storage directories (name, bytes)
+pub const FILE_STORAGE_QUOTAS: &[(&str, u64)] = &[("annexes", 500 * 1024 * 1024), (SHARED_FILES_DIR, 200 * 1024 * 1024)];
+
+/// Quota of the other top-level directories, and of the files at the root (bytes)
+pub const FILE_STORAGE_DEFAULT_QUOTA_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Maximum size of a single file (bytes)
+pub const FILE_STORAGE_MAX_FILE_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Maximum length of a storage path
+pub const FILE_STORAGE_MAX_PATH_LENGTH: usize = 512;
+
+/// Extension of files being written, renamed once complete
+pub const FILE_STORAGE_TEMP_EXTENSION: &str = "partial";
+
+/// Number of leading bytes read to detect the type of an attachment
+pub const ATTACHMENT_SNIFF_BYTES: usize = 512;
+
+// ============================================================================
+// Share Target
+// ============================================================================
+
+/// Event emitted to the webview when a file is shared with the app, with
+/// `{ path, name, mimeType, size, receivedAt }`
+pub const SHARE_RECEIVED_EVENT: &str = "share://received";
+
+/// Top-level directory of the app file storage receiving shared files
+pub const SHARED_FILES_DIR: &str = "shared";
+
+/// Directory of the app cache the Android activity copies shared files into
+pub const SHARED_INBOX_DIR: &str = "shared-inbox";
+
+/// Maximum length of the name of a shared file, once sanitized
+pub const SHARED_FILE_MAX_NAME_LENGTH: usize = 100;
+
+// ============================================================================
+// Watch Companion
+// ============================================================================
+
+/// Event emitted to the webview when a meeting is marked as read from the
+/// watch, with `{ meetingId, readAt }`
+pub const WATCH_MARK_READ_EVENT: &str = "watch://mark-read";
+
+/// Synced collection holding the meetings shown on the watch
+pub const WATCH_AGENDA_COLLECTION: &str = "agenda";
+
+/// Wear OS Data Layer path of the watch context
+pub const WATCH_DATA_PATH: &str = "/elulib/next-meeting";
+
+/// Wear OS Data Layer path of the watch requests
+pub const WATCH_MESSAGE_PATH: &str = "/elulib/request";
+
+// ============================================================================
+// Document Vault
+// ============================================================================
+
+/// Directory (relative to the app data directory) of the document vault
+pub const VAULT_DIR: &str = "vault";
+
+/// Index of the vault documents, in `VAULT_DIR`
+pub const VAULT_INDEX_FILE: &str = "index.json";
+
+/// Extension of the encrypted documents
+pub const VAULT_FILE_EXTENSION: &str = "vlt";
+
+/// Magic bytes at the start of an encrypted document
+pub const VAULT_MAGIC: &[u8] = b"ELV1";
+
+/// HKDF info of the key wrapping the document keys
+pub const VAULT_KEY_INFO: &str = "elulib-vault-v1";
+
+/// Size of the encrypted chunks of a document (bytes)
+pub const VAULT_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Directory (relative to the app cache directory) of the documents
+/// decrypted for the native viewer
+pub const VAULT_VIEW_DIR: &str = "vault-view";
+
+/// Delay after which a document decrypted for the viewer is deleted (seconds)
+pub const VAULT_VIEW_TTL_SECS: u64 = 10 * 60;
+
+/// Default maximum time a document is kept after being stored (seconds)
+pub const VAULT_MAX_AGE_SECS: u64 = 90 * 24 * 60 * 60;
+
+/// Default maximum time a document is kept without being opened (seconds)
+pub const VAULT_MAX_IDLE_SECS: u64 = 30 * 24 * 60 * 60;
+
+// ============================================================================
+// Form Drafts
+// ============================================================================
+
+/// Maximum length of a form identifier
+pub const DRAFT_MAX_FORM_ID_LENGTH: usize = 128;
+
+/// Maximum size of a serialized draft (bytes)
+pub const DRAFT_MAX_PAYLOAD_BYTES: usize = 512 * 1024;
+
+/// Maximum number of drafts kept, the oldest being dropped first
+pub const DRAFT_MAX_ENTRIES: usize = 50;
+
+/// Maximum time a draft is kept without being saved again (seconds)
+pub const DRAFT_MAX_AGE_SECS: u64 = 30 * 24 * 60 * 60;
+
+// ============================================================================
+// Event Outbox
+// ============================================================================
+
+/// Maximum number of events queued until the page is ready, the oldest being
+/// dropped first
+pub const OUTBOX_MAX_ENTRIES: usize = 200;
+
+/// Maximum time an event stays queued (seconds)
+pub const OUTBOX_MAX_AGE_SECS: u64 = 7 * 24 * 60 * 60;
+
+// ============================================================================
+// Downloads
+// ============================================================================
+
+/// File persisting the download list, in the app data directory
+pub const DOWNLOADS_FILE: &str = "downloads.json";
+
+/// Maximum number of downloads transferring at the same time
+pub const DOWNLOAD_MAX_CONCURRENT: usize = 2;
+
+/// Minimum interval between two progress events of a download (milliseconds)
+pub const DOWNLOAD_PROGRESS_INTERVAL_MS: u64 = 500;
+
+/// Event emitted with the progress of a running download
+pub const DOWNLOAD_PROGRESS_EVENT: &str = "download://progress";
+
+/// Event emitted when the status of a download changes
+pub const DOWNLOAD_STATE_EVENT: &str = "download://state";
+
+// ============================================================================
+// Prefetching
+// ============================================================================
+
+/// File (relative to the app data directory) holding the prefetch list
+pub const PREFETCH_FILE: &str = "prefetch.json";
+
+/// Maximum number of documents in the prefetch list
+pub const PREFETCH_MAX_ITEMS: usize = 100;
+
+/// Interval between two checks of the prefetch conditions (seconds)
+pub const PREFETCH_CHECK_INTERVAL_SECS: u64 = 5 * 60;
+
+/// Retries of a failed prefetch download before the document is skipped
+pub const PREFETCH_MAX_RETRIES: u32 = 5;
+
+/// Maximum delay between two retries of a failed prefetch download (seconds)
+pub const PREFETCH_RETRY_MAX_SECS: u64 = 6 * 60 * 60;
+
+// ============================================================================
+// Uploads
+// ============================================================================
+
+/// File persisting the upload queue, in the app data directory
+pub const UPLOADS_FILE: &str = "uploads.json";
+
+/// Maximum size of an uploaded file (bytes)
+///
+/// The file is read in memory to be sent.
+pub const UPLOAD_MAX_FILE_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Attempts before an upload fails permanently
+pub const UPLOAD_MAX_ATTEMPTS: u32 = 8;
+
+/// Delay before retrying a failed upload, doubled after each attempt (seconds)
+pub const UPLOAD_RETRY_BASE_SECS: u64 = 30;
+
+/// Maximum delay between two attempts of an upload (seconds)
+pub const UPLOAD_RETRY_MAX_SECS: u64 = 3600;
+
+/// Interval between two checks of the upload queue, to retry the failed and
+/// deferred uploads (seconds)
+pub const UPLOAD_CHECK_INTERVAL_SECS: u64 = 30;
+
+/// Event emitted when the status of an upload changes
+pub const UPLOAD_STATE_EVENT: &str = "upload://state";
+
+// ============================================================================
+// Preferences
+// ============================================================================
+
+/// File (relative to the app data directory) holding the preferences
+pub const PREFS_FILE: &str = "preferences.json";
+
+/// Maximum number of preferences
+pub const PREFS_MAX_ENTRIES: usize = 256;
+
+/// Maximum length of a preference key
+pub const PREFS_MAX_KEY_LENGTH: usize = 128;
+
+/// Maximum size of a string preference (bytes)
+pub const PREFS_MAX_VALUE_BYTES: usize = 4096;
+
+/// Key fragments of secrets, which must be stored in the keychain instead
+pub const PREFS_SECRET_KEY_MARKERS: &[&str] = &["token", "password", "secret", "passphrase", "pin_code"];
+
+// ============================================================================
+// Local Data Wipe
+// ============================================================================
+
+/// Managed app configuration key (iOS) / app restriction (Android) set by
+/// the MDM to request a wipe; its value identifies the request
+pub const WIPE_MANAGED_CONFIG_KEY: &str = "wipe_request_id";
+
+/// File (relative to the app data directory) recording the last handled
+/// wipe request
+pub const WIPE_MARKER_FILE: &str = "wipe_request";
+
+/// Event emitted when a wipe requested by the MDM completes
+pub const WIPE_COMPLETED_EVENT: &str = "wipe://completed";
+
+// ============================================================================
+// Audio Recording
+// ============================================================================
+
+/// Directory (relative to the app cache directory) where recordings are written
+pub const AUDIO_RECORDING_DIR: &str = "recordings";
+
+/// File extension of audio recordings (AAC in an MPEG-4 container)
+pub const AUDIO_RECORDING_EXTENSION: &str = "m4a";
+
+/// Event emitted to the webview with the current input level while recording
+pub const AUDIO_LEVEL_EVENT: &str = "audio-level";
+
+/// Interval between two audio level events (milliseconds)
+///
+/// 100ms (10 updates per second) is enough for a smooth level meter without
+/// flooding the IPC channel.
+pub const AUDIO_LEVEL_INTERVAL_MS: u64 = 100;
+
+// ============================================================================
+// Accessibility
+// ============================================================================
+
+/// Event emitted to the webview when the accessibility status changes
+pub const ACCESSIBILITY_CHANGED_EVENT: &str = "accessibility-changed";
+
+/// Interval between two accessibility status checks (seconds)
+pub const ACCESSIBILITY_POLL_INTERVAL_SECS: u64 = 5;
+
+// ============================================================================
+// Gestures
+// ============================================================================
+
+/// Event emitted to the webview when a shake gesture is detected
+pub const SHAKE_EVENT: &str = "gesture://shake";
+
+/// Acceleration magnitude (in g, gravity included) above which a sample counts as a spike
+///
+/// Normal handling stays well below 2g; a deliberate shake easily exceeds 2.5g.
+pub const SHAKE_THRESHOLD_G: f64 = 2.5;
+
+/// Number of spikes required within `SHAKE_WINDOW_MS` to detect a shake
+pub const SHAKE_MIN_SPIKES: usize = 3;
+
+/// Time window in which spikes are counted (milliseconds)
+pub const SHAKE_WINDOW_MS: u64 = 1000;
+
+/// Minimum delay between two detected shakes (milliseconds)
+///
+/// Prevents a single long shake from opening the report flow several times.
+pub const SHAKE_COOLDOWN_MS: u64 = 2000;
+
+// ============================================================================
+// Keyboard
+// ============================================================================
+
+/// Event emitted to the webview when the software keyboard is shown, hidden
+/// or resized, with `{ visible, height }`
+pub const KEYBOARD_CHANGED_EVENT: &str = "keyboard://changed";
+
+// ============================================================================
+// Encrypted Database
+// ============================================================================
+
+/// Encrypted database file, in the app data directory
+pub const DATABASE_FILE: &str = "elulib.db";
+
+/// Keychain entry holding the database key
+pub const DATABASE_KEY_NAME: &str = "elulib.database.key";
+
+// ============================================================================
+// Payload Encoding
+// ============================================================================
+
+/// Content type of MessagePack bodies exchanged with the backend
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// `Accept` header of the requests whose response may be MessagePack
+pub const MSGPACK_ACCEPT: &str = "application/msgpack, application/json;q=0.9";
+
+/// Suffix of the commands resolving with a MessagePack body
+pub const PACKED_COMMAND_SUFFIX: &str = "_packed";
+
+// ============================================================================
+// Command Scopes
+// ============================================================================
+
+/// Command scopes available to the frontend in this build (comma-separated,
+/// e.g. `keychain,notifications`)
+///
+/// Provided at build time with the `ELULIB_COMMAND_SCOPES` environment
+/// variable. All scopes are available when it is not set.
+pub const COMMAND_SCOPES: Option<&str> = option_env!("ELULIB_COMMAND_SCOPES");
+
+// ============================================================================
+// Command Metrics
+// ============================================================================
+
+/// Upper bounds of the command latency histogram buckets (milliseconds)
+pub const COMMAND_METRICS_BUCKETS_MS: &[u64] = &[5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+// ============================================================================
+// Invoke Recording
+// ============================================================================
+
+/// File (relative to the app cache directory) of the invoke recording
+pub const INVOKE_RECORDING_FILE: &str = "invoke-recording.jsonl";
+
+/// Invoke arguments masked in recordings, whatever their nesting
+pub const INVOKE_RECORDING_MASKED_ARGS: &[&str] = &[
+    "value",
+    "password",
+    "secret",
+    "token",
+    "accessToken",
+    "refreshToken",
+    "idToken",
+    "codeVerifier",
+];
+
+// ============================================================================
+// Debug Menu
+// ============================================================================
+
+/// Value written by the debug menu to corrupt a keychain entry: neither JSON,
+/// base64 nor a token
+pub const DEBUG_CORRUPTED_KEYCHAIN_VALUE: &str = "\u{fffd}corrupted{\u{0}";
+
+/// Proxy of the HTTP clients while offline is simulated from the debug menu:
+/// nothing listens on it, so requests fail with a connection error as
+/// without network
+pub const DEBUG_OFFLINE_PROXY: &str = "http://127.0.0.1:0";
+
+// ============================================================================
+// Benchmarks
+// ============================================================================
+
+/// Prefix of the keychain keys written by the benchmarks, reserved to the
+/// native layer so the benchmark cleanup never deletes a key of the frontend
+pub const BENCHMARK_KEY_PREFIX: &str = "elulib.benchmark.";
+
+/// Keys timed by `benchmark_keychain` when not specified
+pub const BENCHMARK_DEFAULT_ITERATIONS: u32 = 50;
+
+/// Maximum number of keys timed by `benchmark_keychain`
+pub const BENCHMARK_MAX_ITERATIONS: u32 = 1000;
+
+// ============================================================================
+// Binary Integrity
+// ============================================================================
+
+/// Signing identity expected for this build: SHA-256 fingerprint of the
+/// Android signing certificate, or iOS team identifier
+///
+/// Provided at build time with the `ELULIB_SIGNING_IDENTITY` environment
+/// variable. The signing check is skipped when it is not set.
+pub const SIGNING_IDENTITY: Option<&str> = option_env!("ELULIB_SIGNING_IDENTITY");
+
+/// Response to a failed binary integrity self-check (`warn` or `block`)
+///
+/// Provided at build time with the `ELULIB_TAMPER_RESPONSE` environment
+/// variable; `warn` when it is not set.
+pub const TAMPER_RESPONSE: Option<&str> = option_env!("ELULIB_TAMPER_RESPONSE");
+
+/// Event emitted when the binary integrity self-check fails
+pub const INTEGRITY_TAMPERED_EVENT: &str = "integrity://tampered";
+
+// ============================================================================
+// Webview TLS Pinning
+// ============================================================================
+
+/// Pins of the web application host (comma-separated base64 SHA-256 hashes
+/// of SubjectPublicKeyInfo)
+///
+/// Provided at build time with the `ELULIB_TLS_PINS` environment variable,
+/// including a backup key. Webview pinning is disabled when it is not set.
+pub const TLS_PINS: Option<&str> = option_env!("ELULIB_TLS_PINS");
+
+/// Event emitted when the web application certificate does not match the pins
+pub const TLS_PIN_FAILURE_EVENT: &str = "tls://pin-failure";
+
+// ============================================================================
+// Security Audit Log
+// ============================================================================
+
+/// Security audit log file (JSON lines), in the app data directory
+pub const AUDIT_LOG_FILE: &str = "security_audit.jsonl";
+
+/// Keychain key anchoring the last record of the security audit log
+pub const AUDIT_LOG_ANCHOR_KEY: &str = "security_audit.head";
+
+// ============================================================================
+// Passkeys
+// ============================================================================
+
+/// Minimum size of a passkey server challenge (bytes)
+pub const PASSKEY_MIN_CHALLENGE_BYTES: usize = 16;
+
+/// Maximum size of a passkey user handle, as set by WebAuthn (bytes)
+pub const PASSKEY_MAX_USER_ID_BYTES: usize = 64;
+
+/// COSE identifier of ES256, the algorithm of platform authenticators
+pub const PASSKEY_ALG_ES256: i64 = -7;
+
+// ============================================================================
+// Clipboard
+// ============================================================================
+
+/// Delay before a sensitive clipboard copy is cleared (seconds)
+pub const CLIPBOARD_CLEAR_DELAY_SECS: u64 = 60;
+
+/// Event emitted when a sensitive copy was cleared from the clipboard
+pub const CLIPBOARD_CLEARED_EVENT: &str = "clipboard://cleared";
+
+// ============================================================================
+// Ephemeral Secrets
+// ============================================================================
+
+/// Maximum number of secrets held by the ephemeral store
+pub const EPHEMERAL_MAX_ENTRIES: usize = 64;
+
+/// Maximum length of an ephemeral secret (bytes)
+pub const EPHEMERAL_MAX_VALUE_LENGTH: usize = 4096;
+
+// ============================================================================
+// Runtime Configuration
+// ============================================================================
+
+/// Optional TOML file overriding constants, in the bundled resources or the
+/// app config directory
+pub const RUNTIME_CONFIG_FILE: &str = "elulib.toml";
+
+/// Maximum timeout accepted in the runtime configuration file (seconds)
+pub const RUNTIME_CONFIG_MAX_TIMEOUT_SECS: u64 = 300;
+
+// ============================================================================
+// Logging
+// ============================================================================
+
+/// Number of log lines buffered for the log file writer
+///
+/// Lines logged while the buffer is full (before the writer starts, or if it
+/// cannot keep up) are dropped from the log file.
+pub const LOG_BUFFER_LINES: usize = 1000;
+
+/// Log level of a fresh install, unless set by the runtime configuration file
+pub const LOG_DEFAULT_LEVEL: log::LevelFilter = if cfg!(debug_assertions) {
+    log::LevelFilter::Debug
+} else {
+    log::LevelFilter::Info
+};
+
+/// File storing the log level chosen at runtime, in the app data directory
+pub const LOG_LEVEL_FILE: &str = "log_level.json";
+
+/// Extension of the JSON-lines log file, next to the log file
+pub const LOG_JSON_EXTENSION: &str = "jsonl";
+
+/// Size at which the log file is rotated (bytes)
+pub const LOG_MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Number of log files kept, current file included
+pub const LOG_MAX_FILES: usize = 5;
+
+/// Age after which rotated log files are deleted at startup (seconds)
+pub const LOG_MAX_AGE_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Number of log entries returned by `read_recent_logs` by default
+pub const LOG_VIEWER_DEFAULT_LIMIT: usize = 200;
+
+/// Maximum number of log entries returned by `read_recent_logs`
+pub const LOG_VIEWER_MAX_LIMIT: usize = 2000;
+
+/// Directory of the archives handed to the share sheet, in the app cache
+/// directory
+pub const DIAGNOSTICS_EXPORT_DIR: &str = "exports";
+
+/// `FileProvider` authority exposing shared files on Android
+pub const SHARE_FILE_PROVIDER_AUTHORITY: &str = "com.elulib.mobile.fileprovider";
+
+/// Endpoint receiving shipped log record batches
+pub const LOG_SHIPPING_ENDPOINT: &str = "https://app.elulib.com/api/mobile/logs";
+
+/// Number of log records uploaded per request
+pub const LOG_SHIPPING_BATCH_SIZE: usize = 200;
+
+/// Maximum number of log records kept while offline (oldest are dropped first)
+pub const LOG_SHIPPING_MAX_QUEUED_RECORDS: usize = 5000;
+
+/// Interval between periodic uploads of shipped log records (seconds)
+pub const LOG_SHIPPING_FLUSH_INTERVAL_SECS: u64 = 30;
+
+/// Maximum delay between uploads after consecutive failures (seconds)
+pub const LOG_SHIPPING_MAX_BACKOFF_SECS: u64 = 30 * 60;
+
+/// Duration of log shipping enabled with a support token (seconds)
+pub const LOG_SHIPPING_SUPPORT_GRANT_SECS: u64 = 24 * 60 * 60;
+
+/// Maximum length of a support token
+pub const LOG_SHIPPING_MAX_TOKEN_LENGTH: usize = 256;
+
+/// File storing log records not shipped yet, in the app data directory
+pub const LOG_SHIPPING_QUEUE_FILE: &str = "log_shipping_queue.json";
+
+/// File storing the log shipping consent, in the app data directory
+pub const LOG_SHIPPING_SETTINGS_FILE: &str = "log_shipping_settings.json";
+
+/// Patterns masked in log messages, with their replacement
+///
+/// Applied in order by the `redact` module.
+pub const LOG_REDACTION_PATTERNS: &[(&str, &str)] = &[
+    // Authorization: Bearer <token>
+    (r"(?i)\b(bearer)\s+[A-Za-z0-9\-._~+/]+=*", "$1 [REDACTED]"),
+    // JSON Web Tokens
+    (r"\beyJ[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+\.[A-Za-z0-9_-]+", "[REDACTED]"),
+    // Email addresses
+    (r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}", "[REDACTED]"),
+    // International phone numbers: +33 6 12 34 56 78, +14155550123
+    (r"\+[1-9][0-9]{0,2}(?:[ .-]?[0-9]){6,14}\b", "[REDACTED]"),
+    // French phone numbers: 06 12 34 56 78, 0612345678
+    (r"\b0[1-9](?:[ .-]?[0-9]{2}){4}\b", "[REDACTED]"),
+    // Hex-encoded keys and secrets
+    (r"\b[0-9a-fA-F]{32,}\b", "[REDACTED]"),
+];
+
+/// Fields whose values are masked in log messages, in the `name=value`,
+/// `name: value` and JSON `"name": "value"` forms (case-insensitive)
+pub const LOG_REDACTION_FIELDS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "client_secret",
+    "token",
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "api_key",
+    "api-key",
+    "apikey",
+    "code_verifier",
+    "email",
+    "phone",
+    "phone_number",
+];
+
+/// Keychain key names of the app, masked in log messages of release builds
+/// along with the keys used through the keychain commands
+pub const LOG_REDACTION_KEY_NAMES: &[&str] = &[SESSION_TOKEN_KEY, DATABASE_KEY_NAME, AUDIT_LOG_ANCHOR_KEY];
+
+/// Minimum length of a secret registered for redaction at runtime
+pub const LOG_REDACTION_MIN_SECRET_LENGTH: usize = 6;
+
+/// Maximum number of secrets and keychain key names registered for redaction
+/// (oldest are dropped first)
+pub const LOG_REDACTION_MAX_SECRETS: usize = 64;
+
 // ============================================================================
 // Error Messages
 // ============================================================================
@@ -200,26 +1289,30 @@ pub mod helpers {
         format!("{}:{}", key, value)
     }
 
-    use super::{MIN_KEYCHAIN_KEY_LENGTH, MAX_KEYCHAIN_KEY_LENGTH, MAX_KEYCHAIN_VALUE_LENGTH};
+    use super::{
+        KEYCHAIN_NOT_FOUND_ERRORS, MAX_KEYCHAIN_KEY_LENGTH, MAX_KEYCHAIN_VALUE_LENGTH, MIN_KEYCHAIN_KEY_LENGTH, RESERVED_KEYCHAIN_KEYS,
+        RESERVED_KEYCHAIN_KEY_PREFIXES,
+    };
+    use crate::{AppError, AppResult};
 
     /// Validate keychain key length
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if the key length is valid, or an error message if invalid.
-    pub fn validate_keychain_key(key: &str) -> Result<(), String> {
+    /// Returns `Ok(())` if the key length is valid, or a keychain error if invalid.
+    pub fn validate_keychain_key(key: &str) -> AppResult<()> {
         let len = key.len();
         if len < MIN_KEYCHAIN_KEY_LENGTH {
-            return Err(format!(
+            return Err(AppError::Keychain(format!(
                 "Key length must be at least {} characters, got {}",
                 MIN_KEYCHAIN_KEY_LENGTH, len
-            ));
+            )));
         }
         if len > MAX_KEYCHAIN_KEY_LENGTH {
-            return Err(format!(
+            return Err(AppError::Keychain(format!(
                 "Key length must be at most {} characters, got {}",
                 MAX_KEYCHAIN_KEY_LENGTH, len
-            ));
+            )));
         }
         Ok(())
     }
@@ -228,15 +1321,29 @@ pub mod helpers {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if the value length is valid, or an error message if invalid.
-    pub fn validate_keychain_value(value: &str) -> Result<(), String> {
+    /// Returns `Ok(())` if the value length is valid, or a keychain error if invalid.
+    pub fn validate_keychain_value(value: &str) -> AppResult<()> {
         let len = value.len();
         if len > MAX_KEYCHAIN_VALUE_LENGTH {
-            return Err(format!(
+            return Err(AppError::Keychain(format!(
                 "Value length must be at most {} characters, got {}",
                 MAX_KEYCHAIN_VALUE_LENGTH, len
-            ));
+            )));
         }
         Ok(())
     }
+
+    /// Returns `true` if the keychain key is reserved to the native layer
+    /// (`RESERVED_KEYCHAIN_KEYS`, `RESERVED_KEYCHAIN_KEY_PREFIXES`)
+    pub fn is_reserved_keychain_key(key: &str) -> bool {
+        RESERVED_KEYCHAIN_KEYS.contains(&key)
+            || RESERVED_KEYCHAIN_KEY_PREFIXES.iter().any(|prefix| key.starts_with(prefix))
+    }
+
+    /// Returns `true` if the keystore error reports a missing key
+    /// (`KEYCHAIN_NOT_FOUND_ERRORS`)
+    pub fn is_keychain_not_found(e: &dyn std::fmt::Display) -> bool {
+        let message = e.to_string();
+        KEYCHAIN_NOT_FOUND_ERRORS.iter().any(|marker| message.contains(marker))
+    }
 }
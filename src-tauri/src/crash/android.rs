@@ -0,0 +1,41 @@
+/// Android-specific crash handlers
+///
+/// This module installs native crash handlers using an NDK signal handler (Breakpad / Crashpad) and `Thread.setDefaultUncaughtExceptionHandler`, writing a
+/// minidump to the crash directory when the process crashes.
+///
+/// Note: This implementation provides the structure for Android crash handling.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use std::path::Path;
+use crate::AppResult;
+
+/// Install the native crash handlers on Android
+///
+/// # Arguments
+///
+/// * `dir` - Directory where minidumps (`.dmp`) must be written
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn install_handlers(dir: &Path) -> AppResult<()> {
+    tracing::info!("[Android] Installing crash handlers: {}", dir.display());
+
+    // TODO: Implement native Android crash handlers
+    // Example Kotlin implementation:
+    // ```kotlin
+    // // Native signals (SIGSEGV, SIGABRT, ...): Crashpad / Breakpad writing minidumps to crashDir
+    // CrashpadClient.startHandler(handlerPath, crashDir, ...)
+    // // JVM exceptions
+    // val previous = Thread.getDefaultUncaughtExceptionHandler()
+    // Thread.setDefaultUncaughtExceptionHandler { thread, error ->
+    //     File(crashDir, "${System.currentTimeMillis()}.dmp").writeText(error.stackTraceToString())
+    //     previous?.uncaughtException(thread, error)
+    // }
+    // ```
+
+    // Placeholder: Return success without native handlers
+    // Replace this with actual native implementation
+    Ok(())
+}
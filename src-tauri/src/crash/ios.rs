@@ -0,0 +1,40 @@
+/// iOS-specific crash handlers
+///
+/// This module installs native crash handlers using PLCrashReporter, writing a
+/// minidump to the crash directory when the process crashes.
+///
+/// Note: This implementation provides the structure for iOS crash handling.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use std::path::Path;
+use crate::AppResult;
+
+/// Install the native crash handlers on iOS
+///
+/// # Arguments
+///
+/// * `dir` - Directory where minidumps (`.dmp`) must be written
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn install_handlers(dir: &Path) -> AppResult<()> {
+    tracing::info!("[iOS] Installing crash handlers: {}", dir.display());
+
+    // TODO: Implement native iOS crash handlers
+    // Example Swift implementation:
+    // ```swift
+    // let config = PLCrashReporterConfig(signalHandlerType: .mach, symbolicationStrategy: [])
+    // let reporter = PLCrashReporter(configuration: config)
+    // if reporter.hasPendingCrashReport(), let data = try? reporter.loadPendingCrashReportDataAndReturnError() {
+    //     try data.write(to: crashDir.appendingPathComponent("\(Date().timeIntervalSince1970).dmp"))
+    //     reporter.purgePendingCrashReport()
+    // }
+    // try reporter.enableAndReturnError()
+    // ```
+
+    // Placeholder: Return success without native handlers
+    // Replace this with actual native implementation
+    Ok(())
+}
@@ -0,0 +1,642 @@
+/// Crash reporting module
+///
+/// This module records crashes to disk so they can be reported on the next
+/// launch:
+/// - Rust panics are captured by a panic hook and written as JSON reports,
+///   with the backtrace, the last log lines (breadcrumbs), the breadcrumb trail
+///   (`breadcrumbs` module) and the current app state snapshot
+/// - native crashes (signals, uncaught exceptions) are captured by native
+///   handlers writing minidumps (`.dmp`) to the same directory
+/// - main thread stalls ("app not responding") detected by the `watchdog`
+///   module are written as hang reports, with the main thread backtrace where
+///   available
+///
+/// Reports are encrypted at rest with the artifact key (`artifacts` module):
+/// panic reports are sealed when written, minidumps on the next launch. On
+/// the next launch, a summary of the most recent report (kind and date) is
+/// exposed to the frontend (`get_last_crash`) so it can offer to send it;
+/// reports are only decrypted to be uploaded, when the user sends them or
+/// opted in to automatic upload. Reports are deleted once uploaded or
+/// discarded.
+///
+/// The panic hook is installed first thing in `run()` (`install_panic_hook`);
+/// panics happening before `install()` resolved the crash directory are only
+/// printed to stderr.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use tauri::{AppHandle, Manager};
+
+use crate::artifacts::{self, ArtifactKey};
+use crate::breadcrumbs::{self, Breadcrumb};
+use crate::constants;
+use crate::http;
+use crate::state_restore::{AppStateSnapshot, StateStore};
+use crate::{AppError, AppResult};
+
+/// Crash directory and app handle used by the panic hook, set by `install()`
+static HOOK_CONTEXT: OnceLock<(PathBuf, AppHandle)> = OnceLock::new();
+
+/// Last log lines, attached to panic reports
+static BREADCRUMBS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Kind of crash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashKind {
+    /// Rust panic
+    Panic,
+    /// Native crash (signal, uncaught exception), recorded as a minidump
+    Native,
+    /// Main thread stall ("app not responding"), detected by the watchdog
+    Hang,
+}
+
+impl CrashKind {
+    /// Prefix of the report file names (`panic-1.json`)
+    fn file_prefix(self) -> &'static str {
+        match self {
+            CrashKind::Panic => "panic",
+            CrashKind::Native => "native",
+            CrashKind::Hang => "hang",
+        }
+    }
+}
+
+/// Crash report, as persisted on disk and uploaded
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    /// Kind of crash
+    pub kind: CrashKind,
+    /// Panic message, or a description of the native crash or hang
+    pub message: String,
+    /// Source location of the panic, if known
+    pub location: Option<String>,
+    /// Time of the crash (seconds since the Unix epoch)
+    pub occurred_at: u64,
+    /// App version that crashed
+    pub app_version: String,
+    /// Operating system
+    pub platform: String,
+    /// Backtrace of the panicking thread, or of the stalled main thread
+    #[serde(default)]
+    pub backtrace: Option<String>,
+    /// Last log lines before the crash, oldest first
+    #[serde(default)]
+    pub breadcrumbs: Vec<String>,
+    /// Breadcrumb trail before the crash, oldest first
+    #[serde(default)]
+    pub trail: Vec<Breadcrumb>,
+    /// App state snapshot provided by the frontend at the time of the crash
+    #[serde(default)]
+    pub app_state: Option<AppStateSnapshot>,
+}
+
+/// Summary of a pending crash report, readable without decrypting it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashSummary {
+    /// Kind of crash
+    pub kind: CrashKind,
+    /// Time of the crash (seconds since the Unix epoch)
+    pub occurred_at: u64,
+}
+
+/// Crash reporting settings persisted between launches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CrashSettings {
+    /// The user opted in to automatic crash report upload
+    upload_enabled: bool,
+}
+
+/// Crash reporting state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct CrashState {
+    dir: Mutex<Option<PathBuf>>,
+    post_crash: AtomicBool,
+}
+
+impl CrashState {
+    /// Crash report directory, once crash reporting is installed
+    pub fn dir(&self) -> AppResult<PathBuf> {
+        self.dir
+            .lock()
+            .map_err(|e| AppError::Storage(e.to_string()))?
+            .clone()
+            .ok_or_else(|| AppError::Storage("Crash reporting not initialized".to_string()))
+    }
+
+    /// Returns `true` if the previous session ended with a panic
+    pub fn post_crash(&self) -> bool {
+        self.post_crash.load(Ordering::SeqCst)
+    }
+}
+
+/// Record a log line as breadcrumb, keeping the last `CRASH_BREADCRUMB_CAPACITY`
+///
+/// Registered as a log target in `create_app()`.
+pub fn record_log(record: &log::Record) {
+    let line = format!("{} {} {}: {}", now_secs(), record.level(), record.target(), record.args());
+    // Never block logging: drop the line if the buffer is busy
+    if let Ok(mut breadcrumbs) = BREADCRUMBS.try_lock() {
+        if breadcrumbs.len() == constants::CRASH_BREADCRUMB_CAPACITY {
+            breadcrumbs.pop_front();
+        }
+        breadcrumbs.push_back(line);
+    }
+}
+
+/// Current breadcrumbs, oldest first
+fn breadcrumbs() -> Vec<String> {
+    BREADCRUMBS
+        .try_lock()
+        .map(|breadcrumbs| breadcrumbs.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Build a crash report for a panic
+pub fn panic_report(info: &std::panic::PanicHookInfo<'_>, occurred_at: u64) -> CrashReport {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Unknown panic".to_string());
+
+    CrashReport {
+        kind: CrashKind::Panic,
+        message,
+        location: info
+            .location()
+            .map(|location| format!("{}:{}", location.file(), location.line())),
+        occurred_at,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        platform: std::env::consts::OS.to_string(),
+        backtrace: Some(std::backtrace::Backtrace::force_capture().to_string()),
+        breadcrumbs: breadcrumbs(),
+        trail: breadcrumbs::trail(),
+        app_state: None,
+    }
+}
+
+/// Build a crash report for a main thread stall
+pub fn hang_report(stalled_ms: u64, backtrace: Option<String>, occurred_at: u64) -> CrashReport {
+    CrashReport {
+        kind: CrashKind::Hang,
+        message: format!("App not responding: main thread stalled for {}ms", stalled_ms),
+        location: None,
+        occurred_at,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        platform: std::env::consts::OS.to_string(),
+        backtrace,
+        breadcrumbs: breadcrumbs(),
+        trail: breadcrumbs::trail(),
+        app_state: None,
+    }
+}
+
+/// Write a hang report for a main thread stall
+///
+/// Called by the watchdog while the main thread is still stalled. Unlike a
+/// panic, a hang does not flag the next launch as post-crash: the report is
+/// only summarized and uploaded like the others.
+pub fn record_hang(stalled_ms: u64, backtrace: Option<String>) -> AppResult<PathBuf> {
+    let (dir, app) = HOOK_CONTEXT
+        .get()
+        .ok_or_else(|| AppError::Storage("Crash reporting not initialized".to_string()))?;
+    let mut report = hang_report(stalled_ms, backtrace, now_secs());
+    report.app_state = app.try_state::<StateStore>().and_then(|store| store.try_current());
+    write_report(dir, &report, artifacts::key()?)
+}
+
+/// Install the panic hook
+///
+/// Each panic is written as a crash report, a marker flagging the next launch
+/// as post-crash is created, then the default hook prints the panic to stderr.
+/// Must be called before anything else in `run()`.
+pub fn install_panic_hook() {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some((dir, app)) = HOOK_CONTEXT.get() {
+            let mut report = panic_report(info, now_secs());
+            report.app_state = app.try_state::<StateStore>().and_then(|store| store.try_current());
+            tracing::error!("Panic: {} at {:?}", report.message, report.location);
+
+            // Never write the report in plaintext
+            if let Err(e) = artifacts::key().and_then(|key| write_report(dir, &report, key)) {
+                eprintln!("{}", e);
+            }
+            if let Err(e) = std::fs::write(dir.join(constants::CRASH_MARKER_FILE), b"") {
+                eprintln!("Failed to write crash marker: {}", e);
+            }
+        }
+        previous_hook(info);
+    }));
+}
+
+/// Write an encrypted crash report to the crash directory
+pub fn write_report(dir: &Path, report: &CrashReport, key: &ArtifactKey) -> AppResult<PathBuf> {
+    std::fs::create_dir_all(dir).map_err(|e| AppError::Storage(format!("Failed to create crash directory: {}", e)))?;
+    let path = dir.join(format!("{}-{}.json", report.kind.file_prefix(), report.occurred_at));
+    let contents = serde_json::to_vec(report)
+        .map_err(|e| AppError::Storage(format!("Failed to serialize crash report: {}", e)))?;
+    key.write(&path, &contents)
+        .map_err(|e| AppError::Storage(format!("Failed to write crash report: {}", e)))
+}
+
+/// Crash report files (JSON reports and minidumps, sealed or not), oldest
+/// first
+pub fn pending_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(u64, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            matches!(
+                artifacts::inner_extension(path),
+                Some("json") | Some(constants::MINIDUMP_EXTENSION)
+            ) && path.file_name() != Some(constants::CRASH_SETTINGS_FILE.as_ref())
+                && path.file_name() != Some(constants::CRASH_MARKER_FILE.as_ref())
+        })
+        .map(|path| (modified_secs(&path), path))
+        .collect();
+    files.sort();
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+fn modified_secs(path: &Path) -> u64 {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Read the contents of a crash report file, decrypting it if sealed
+fn read_contents(path: &Path, key: &ArtifactKey) -> AppResult<Vec<u8>> {
+    if artifacts::is_sealed(path) {
+        key.read(path)
+    } else {
+        std::fs::read(path).map_err(|e| AppError::Storage(format!("Failed to read crash report: {}", e)))
+    }
+}
+
+/// Read and decrypt a crash report file
+///
+/// Minidumps cannot be parsed on device; they are described by a generic
+/// native crash report dated from the file modification time.
+pub fn read_report(path: &Path, key: &ArtifactKey) -> Option<CrashReport> {
+    if artifacts::inner_extension(path) == Some(constants::MINIDUMP_EXTENSION) {
+        return Some(CrashReport {
+            kind: CrashKind::Native,
+            message: "Native crash (minidump)".to_string(),
+            location: None,
+            occurred_at: modified_secs(path),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: std::env::consts::OS.to_string(),
+            backtrace: None,
+            breadcrumbs: Vec::new(),
+            trail: Vec::new(),
+            app_state: None,
+        });
+    }
+
+    let contents = read_contents(path, key)
+        .map_err(|e| tracing::warn!("Ignoring unreadable crash report {}: {}", path.display(), e))
+        .ok()?;
+    serde_json::from_slice(&contents)
+        .map_err(|e| tracing::warn!("Ignoring invalid crash report {}: {}", path.display(), e))
+        .ok()
+}
+
+/// Summarize a crash report file from its name and modification time
+pub fn summarize(path: &Path) -> Option<CrashSummary> {
+    match artifacts::inner_extension(path)? {
+        "json" => {
+            let name = path.file_name()?.to_str()?;
+            let (kind, rest) = [CrashKind::Panic, CrashKind::Hang]
+                .into_iter()
+                .find_map(|kind| {
+                    name.strip_prefix(kind.file_prefix())
+                        .and_then(|rest| rest.strip_prefix('-'))
+                        .map(|rest| (kind, rest))
+                })
+                .unwrap_or((CrashKind::Panic, name));
+            let occurred_at = rest
+                .split('.')
+                .next()
+                .and_then(|secs| secs.parse().ok())
+                .unwrap_or_else(|| modified_secs(path));
+            Some(CrashSummary { kind, occurred_at })
+        }
+        constants::MINIDUMP_EXTENSION => Some(CrashSummary {
+            kind: CrashKind::Native,
+            occurred_at: modified_secs(path),
+        }),
+        _ => None,
+    }
+}
+
+/// Summary of the most recent pending crash report, if any
+pub fn last_crash(dir: &Path) -> Option<CrashSummary> {
+    pending_files(dir).iter().rev().find_map(|path| summarize(path))
+}
+
+/// Seal the plaintext crash reports (minidumps written by the native handlers,
+/// reports written before encryption was introduced)
+pub fn seal_pending(dir: &Path, key: &ArtifactKey) {
+    for path in pending_files(dir) {
+        if !artifacts::is_sealed(&path) {
+            if let Err(e) = key.seal_file(&path) {
+                tracing::warn!("Failed to encrypt crash report {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+fn settings_path(dir: &Path) -> PathBuf {
+    dir.join(constants::CRASH_SETTINGS_FILE)
+}
+
+fn read_settings(dir: &Path) -> CrashSettings {
+    std::fs::read_to_string(settings_path(dir))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Enable or disable automatic crash report upload
+pub fn set_upload_enabled(dir: &Path, enabled: bool) -> AppResult<()> {
+    std::fs::create_dir_all(dir).map_err(|e| AppError::Storage(format!("Failed to create crash directory: {}", e)))?;
+    let contents = serde_json::to_string(&CrashSettings { upload_enabled: enabled })
+        .map_err(|e| AppError::Storage(format!("Failed to serialize crash settings: {}", e)))?;
+    std::fs::write(settings_path(dir), contents)
+        .map_err(|e| AppError::Storage(format!("Failed to write crash settings: {}", e)))
+}
+
+/// Delete all pending crash reports
+pub fn discard(dir: &Path) -> AppResult<()> {
+    for path in pending_files(dir) {
+        std::fs::remove_file(&path).map_err(|e| AppError::Storage(format!("Failed to delete crash report: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Upload all pending crash reports, deleting each one once uploaded
+///
+/// Reports are decrypted in memory just before being sent; this is the only
+/// place they are decrypted.
+///
+/// # Returns
+///
+/// Returns the number of uploaded reports, or an error if an upload
+/// failed (reports not yet uploaded are kept for a later attempt).
+pub async fn upload(dir: &Path) -> AppResult<usize> {
+    let client = http::client()?;
+    let key = artifacts::key()?;
+    let mut uploaded = 0;
+
+    for path in pending_files(dir) {
+        let body = read_contents(&path, key)?;
+        let content_type = if artifacts::inner_extension(&path) == Some("json") {
+            "application/json"
+        } else {
+            "application/octet-stream"
+        };
+
+        client
+            .post(http::endpoint(constants::CRASH_REPORT_ENDPOINT))
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::Storage(format!("Crash report upload failed: {}", e)))?;
+
+        std::fs::remove_file(&path).map_err(|e| AppError::Storage(format!("Failed to delete crash report: {}", e)))?;
+        uploaded += 1;
+    }
+
+    Ok(uploaded)
+}
+
+/// Initialize crash reporting
+///
+/// Resolves the crash directory used by the panic hook, installs the native
+/// crash handlers, derives the artifact key and encrypts the reports left in
+/// plaintext, and detects whether the previous session crashed. If the
+/// user opted in, reports left by a previous crash are uploaded in background.
+pub fn install(app: &AppHandle) -> AppResult<()> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve data directory: {}", e)))?
+        .join(constants::CRASH_REPORT_DIR);
+
+    if HOOK_CONTEXT.set((dir.clone(), app.clone())).is_err() {
+        tracing::warn!("Crash reporting already initialized");
+    }
+
+    if let Err(e) = install_native_handlers(&dir) {
+        tracing::warn!("Failed to install native crash handlers: {}", e);
+    }
+
+    let state = app.state::<CrashState>();
+    let marker = dir.join(constants::CRASH_MARKER_FILE);
+    if marker.exists() {
+        tracing::warn!("Launching after a crash");
+        state.post_crash.store(true, Ordering::SeqCst);
+        if let Err(e) = std::fs::remove_file(&marker) {
+            tracing::warn!("Failed to remove crash marker: {}", e);
+        }
+    }
+
+    match artifacts::init(app).and_then(|_| artifacts::key()) {
+        Ok(key) => seal_pending(&dir, key),
+        Err(e) => tracing::warn!("Crash reports cannot be encrypted: {}", e),
+    }
+
+    if let Some(summary) = last_crash(&dir) {
+        tracing::warn!("Previous session crashed ({:?} at {})", summary.kind, summary.occurred_at);
+        if read_settings(&dir).upload_enabled {
+            let upload_dir = dir.clone();
+            tauri::async_runtime::spawn(async move {
+                match upload(&upload_dir).await {
+                    Ok(count) => tracing::info!("Uploaded {} crash report(s)", count),
+                    Err(e) => tracing::warn!("{}", e),
+                }
+            });
+        }
+    }
+
+    if let Ok(mut stored) = state.dir.lock() {
+        *stored = Some(dir);
+    }
+    Ok(())
+}
+
+/// Install the native crash handlers writing minidumps to `dir`
+fn install_native_handlers(dir: &Path) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::install_handlers(dir)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::install_handlers(dir)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = dir;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(occurred_at: u64) -> CrashReport {
+        CrashReport {
+            kind: CrashKind::Panic,
+            message: format!("boom {}", occurred_at),
+            location: Some("src/lib.rs:1".to_string()),
+            occurred_at,
+            app_version: "1.0.0".to_string(),
+            platform: "android".to_string(),
+            backtrace: Some("backtrace".to_string()),
+            breadcrumbs: vec!["1 INFO app: started".to_string()],
+            trail: vec![Breadcrumb {
+                timestamp_ms: 1000,
+                category: "lifecycle".to_string(),
+                message: "foreground".to_string(),
+            }],
+            app_state: None,
+        }
+    }
+
+    fn key() -> ArtifactKey {
+        ArtifactKey::derive(&[1u8; 32]).unwrap()
+    }
+
+    #[test]
+    fn test_write_and_read_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_report(dir.path(), &report(10), &key()).unwrap();
+        assert!(artifacts::is_sealed(&path));
+        let contents = std::fs::read(&path).unwrap();
+        assert!(!contents.windows(4).any(|window| window == b"boom"), "Report is encrypted");
+
+        assert_eq!(read_report(&path, &key()), Some(report(10)));
+        assert_eq!(read_report(&path, &ArtifactKey::derive(&[2u8; 32]).unwrap()), None);
+        assert_eq!(
+            last_crash(dir.path()),
+            Some(CrashSummary {
+                kind: CrashKind::Panic,
+                occurred_at: 10
+            })
+        );
+    }
+
+    #[test]
+    fn test_hang_report_is_summarized_as_hang() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_report(dir.path(), &hang_report(6200, Some("main thread".to_string()), 20), &key()).unwrap();
+        assert!(path.file_name().unwrap().to_str().unwrap().starts_with("hang-20.json"));
+
+        let report = read_report(&path, &key()).unwrap();
+        assert_eq!(report.kind, CrashKind::Hang);
+        assert!(report.message.contains("6200ms"));
+        assert_eq!(report.backtrace.as_deref(), Some("main thread"));
+        assert_eq!(
+            last_crash(dir.path()),
+            Some(CrashSummary {
+                kind: CrashKind::Hang,
+                occurred_at: 20
+            })
+        );
+    }
+
+    #[test]
+    fn test_minidump_is_sealed_and_reported_as_native_crash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(format!("crash.{}", constants::MINIDUMP_EXTENSION));
+        std::fs::write(&path, b"MDMP").unwrap();
+
+        seal_pending(dir.path(), &key());
+        assert!(!path.exists(), "Plaintext minidump is deleted");
+        let pending = pending_files(dir.path());
+        assert_eq!(pending.len(), 1);
+        assert_eq!(key().read(&pending[0]).unwrap(), b"MDMP");
+
+        let summary = last_crash(dir.path()).expect("Minidump should be reported");
+        assert_eq!(summary.kind, CrashKind::Native);
+    }
+
+    #[test]
+    fn test_discard_keeps_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        write_report(dir.path(), &report(10), &key()).unwrap();
+        set_upload_enabled(dir.path(), true).unwrap();
+
+        discard(dir.path()).unwrap();
+        assert_eq!(last_crash(dir.path()), None);
+        assert!(read_settings(dir.path()).upload_enabled, "Settings are not a crash report");
+    }
+
+    #[test]
+    fn test_marker_is_not_a_report() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(constants::CRASH_MARKER_FILE), b"").unwrap();
+        assert!(pending_files(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_breadcrumbs_are_bounded() {
+        for i in 0..constants::CRASH_BREADCRUMB_CAPACITY + 10 {
+            record_log(
+                &log::Record::builder()
+                    .args(format_args!("line {}", i))
+                    .level(log::Level::Info)
+                    .target("test")
+                    .build(),
+            );
+        }
+        let breadcrumbs = breadcrumbs();
+        assert_eq!(breadcrumbs.len(), constants::CRASH_BREADCRUMB_CAPACITY);
+        assert!(breadcrumbs.last().unwrap().ends_with(&format!(
+            "line {}",
+            constants::CRASH_BREADCRUMB_CAPACITY + 9
+        )));
+    }
+
+    #[test]
+    fn test_no_crash_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing");
+        assert!(pending_files(&missing).is_empty());
+        assert!(!read_settings(&missing).upload_enabled);
+    }
+}
@@ -0,0 +1,179 @@
+/// Content-Security-Policy injection module
+///
+/// The `csp` setting of `tauri.conf.json` only applies to bundled assets, not
+/// to the remote frontend loaded in the webview. This module enforces a
+/// policy on the remote pages with an init script, injected by a plugin into
+/// every page before any page script runs:
+/// - a `Content-Security-Policy` meta tag, without `'unsafe-inline'` or
+///   `'unsafe-eval'` scripts, blocking inline-script injection paths into the
+///   privileged shell (Tauri's own init scripts are injected natively and are
+///   not affected)
+/// - a `referrer` meta tag, so navigations do not leak full URLs
+///
+/// Headers without a meta equivalent (`frame-ancestors`, HSTS, ...) must be
+/// sent by the backend. The policy is validated by `validate`; an invalid
+/// policy is not injected.
+
+use std::collections::BTreeMap;
+
+use tauri::plugin::TauriPlugin;
+use tauri::Wry;
+
+/// Directives of the injected policy
+///
+/// `'self'` is the origin of the remote frontend. `ipc:` and
+/// `http://ipc.localhost` are the Tauri IPC endpoints (iOS / Android).
+pub const POLICY_DIRECTIVES: &[(&str, &str)] = &[
+    ("default-src", "'self'"),
+    ("script-src", "'self'"),
+    ("style-src", "'self'"),
+    ("img-src", "'self' data:"),
+    ("font-src", "'self' data:"),
+    ("connect-src", "'self' ipc: http://ipc.localhost"),
+    ("object-src", "'none'"),
+    ("base-uri", "'self'"),
+    ("form-action", "'self'"),
+];
+
+/// Referrer policy of the remote frontend
+pub const REFERRER_POLICY: &str = "strict-origin-when-cross-origin";
+
+/// Script sources that re-open inline-script or remote-code injection
+const UNSAFE_SCRIPT_SOURCES: &[&str] = &[
+    "'unsafe-inline'",
+    "'unsafe-eval'",
+    "'unsafe-hashes'",
+    "*",
+    "data:",
+    "blob:",
+    "http:",
+    "https:",
+];
+
+/// Serialize directives as a policy string
+pub fn policy(directives: &[(&str, &str)]) -> String {
+    directives
+        .iter()
+        .map(|(name, sources)| format!("{} {}", name, sources))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Parse a policy string into directives (name -> sources)
+pub fn parse(policy: &str) -> BTreeMap<String, Vec<String>> {
+    policy
+        .split(';')
+        .filter_map(|directive| {
+            let mut parts = directive.split_whitespace();
+            let name = parts.next()?.to_ascii_lowercase();
+            Some((name, parts.map(str::to_string).collect()))
+        })
+        .collect()
+}
+
+/// Validate a policy
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the policy blocks script injection, or the list of
+/// problems found.
+pub fn validate(policy: &str) -> Result<(), Vec<String>> {
+    let directives = parse(policy);
+    let mut problems = Vec::new();
+
+    if !directives.contains_key("default-src") {
+        problems.push("missing default-src".to_string());
+    }
+    // script-src falls back to default-src
+    match directives.get("script-src").or_else(|| directives.get("default-src")) {
+        Some(sources) => {
+            for source in sources {
+                if UNSAFE_SCRIPT_SOURCES.contains(&source.to_ascii_lowercase().as_str()) {
+                    problems.push(format!("script-src allows {}", source));
+                }
+            }
+        }
+        None => problems.push("missing script-src".to_string()),
+    }
+    if directives.get("object-src").map(Vec::as_slice) != Some(&["'none'".to_string()]) {
+        problems.push("object-src must be 'none'".to_string());
+    }
+    if !directives.contains_key("base-uri") {
+        problems.push("missing base-uri".to_string());
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems)
+    }
+}
+
+/// Init script adding the policy and referrer meta tags to every page
+pub fn init_script(policy: &str) -> String {
+    // JSON string literals are valid JavaScript string literals
+    let policy = serde_json::to_string(policy).unwrap_or_default();
+    let referrer = serde_json::to_string(REFERRER_POLICY).unwrap_or_default();
+    format!(
+        r#"(function () {{
+  function inject() {{
+    var head = document.head || document.documentElement;
+    if (!head) return false;
+    var csp = document.createElement('meta');
+    csp.httpEquiv = 'Content-Security-Policy';
+    csp.content = {policy};
+    var referrer = document.createElement('meta');
+    referrer.name = 'referrer';
+    referrer.content = {referrer};
+    head.insertBefore(referrer, head.firstChild);
+    head.insertBefore(csp, head.firstChild);
+    return true;
+  }}
+  if (!inject()) {{
+    new MutationObserver(function (_, observer) {{
+      if (inject()) observer.disconnect();
+    }}).observe(document, {{ childList: true, subtree: true }});
+  }}
+}})();"#
+    )
+}
+
+/// Plugin injecting the policy into the remote frontend
+pub fn plugin() -> TauriPlugin<Wry> {
+    let builder = tauri::plugin::Builder::new("csp");
+    let policy = policy(POLICY_DIRECTIVES);
+    match validate(&policy) {
+        Ok(()) => builder.js_init_script(init_script(&policy)).build(),
+        Err(problems) => {
+            tracing::error!("Not injecting invalid Content-Security-Policy: {}", problems.join(", "));
+            builder.build()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_injected_policy_is_valid() {
+        assert_eq!(validate(&policy(POLICY_DIRECTIVES)), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_rejects_script_injection() {
+        let problems = validate("default-src 'self'; script-src 'self' 'unsafe-inline' https:; object-src 'none'; base-uri 'self'")
+            .unwrap_err();
+        assert_eq!(problems.len(), 2);
+
+        // script-src falls back to default-src
+        assert!(validate("default-src *; object-src 'none'; base-uri 'self'").is_err());
+        assert!(validate("script-src 'self'").is_err());
+    }
+
+    #[test]
+    fn test_init_script_escapes_policy() {
+        let script = init_script("default-src 'self'; img-src \"data:\"");
+        assert!(script.contains(r#"csp.content = "default-src 'self'; img-src \"data:\"";"#));
+    }
+}
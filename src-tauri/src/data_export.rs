@@ -0,0 +1,339 @@
+/// Personal data export (GDPR subject-access requests)
+///
+/// The `export_user_data` command gathers what the native layer stores about
+/// the user into a ZIP archive of JSON files, then hands it to the native
+/// share sheet:
+/// - `manifest.json`: export date, app version, exported sections, sections
+///   that could not be read and what is deliberately left out
+/// - `preferences.json`, `app_state.json`, `install.json`,
+///   `notifications.json` (badge policy), `analytics.json` (opt-out and
+///   events not uploaded yet)
+/// - `audit_summary.json`: security events counted by type, with the first
+///   and last event dates and the chain verification result
+/// - `database.json`: offline document metadata and synced collections
+///   (record counts and sync dates)
+/// - `files.json`: files of the app file storage, downloads, uploads, cached
+///   images and vault documents (names, URLs and sizes, not the contents)
+///
+/// Secrets are never exported: keychain values (session token, database
+/// key, login tokens) are left out, and every file is passed through the log
+/// redaction so registered secrets are masked. Notifications are delivered by
+/// the OS and no history of them is stored natively.
+///
+/// A section that cannot be read (e.g. the database while the keychain is
+/// unavailable) is listed in the manifest instead of failing the export.
+/// Like exported logs, the archive is written to the `exports` directory of
+/// the cache only for the share sheet and deleted at the next launch.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::analytics::Analytics;
+use crate::audit_log::{self, AuditExport};
+use crate::badge;
+use crate::database::{self, Database};
+use crate::diagnostics::{self, ZipArchive};
+use crate::downloads::DownloadManager;
+use crate::file_storage::{self, FileEntry};
+use crate::image_cache::ImageCache;
+use crate::install::InstallState;
+use crate::locale;
+use crate::prefs::Prefs;
+use crate::redact;
+use crate::share;
+use crate::state_restore::StateStore;
+use crate::uploads::UploadQueue;
+use crate::vault::Vault;
+use crate::{AppError, AppResult};
+
+/// Data left out of the export, listed in the manifest
+const EXCLUDED: &[&str] = &[
+    "Keychain values (session token, login tokens, database key)",
+    "Contents of downloaded and stored files (listed in files.json)",
+    "Notification history (not stored by the app)",
+];
+
+/// Exported archive
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedData {
+    /// Name of the archive handed to the share sheet
+    pub file_name: String,
+    /// Size of the archive (bytes)
+    pub bytes: u64,
+    /// Exported sections
+    pub sections: Vec<String>,
+    /// Sections that could not be read, with the error
+    pub failed: BTreeMap<String, String>,
+}
+
+/// Archive built from the collected sections
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataArchive {
+    /// ZIP archive
+    pub archive: Vec<u8>,
+    /// Exported sections
+    pub sections: Vec<String>,
+    /// Sections that could not be read, with the error
+    pub failed: BTreeMap<String, String>,
+}
+
+/// Security events summarized for the export
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditSummary {
+    /// Number of recorded events
+    pub total: usize,
+    /// Number of events by type
+    pub by_type: BTreeMap<String, usize>,
+    /// Time of the first event (seconds since the Unix epoch)
+    pub first_at: Option<u64>,
+    /// Time of the last event (seconds since the Unix epoch)
+    pub last_at: Option<u64>,
+    /// Whether the log is intact
+    pub verified: bool,
+}
+
+/// Summarize the security log
+pub fn audit_summary(export: &AuditExport) -> AuditSummary {
+    let mut by_type = BTreeMap::new();
+    for record in &export.records {
+        let kind = serde_json::to_value(&record.event)
+            .ok()
+            .and_then(|event| event.get("type").and_then(Value::as_str).map(str::to_string))
+            .unwrap_or_else(|| "unknown".to_string());
+        *by_type.entry(kind).or_insert(0) += 1;
+    }
+    AuditSummary {
+        total: export.records.len(),
+        by_type,
+        first_at: export.records.first().map(|record| record.timestamp),
+        last_at: export.records.last().map(|record| record.timestamp),
+        verified: export.verified,
+    }
+}
+
+/// All files and directories of the file storage, recursively
+pub fn storage_files(root: &Path) -> AppResult<Vec<FileEntry>> {
+    let mut files = Vec::new();
+    let mut pending = vec![None];
+    while let Some(directory) = pending.pop() {
+        for entry in file_storage::list(root, directory.as_deref())? {
+            if entry.is_dir {
+                pending.push(Some(entry.path.clone()));
+            }
+            files.push(entry);
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn database_section(app: &AppHandle) -> AppResult<Value> {
+    app.state::<Database>().with_connection(app, |conn| {
+        let documents = database::query(conn, "SELECT * FROM documents ORDER BY updated_at", &[])?;
+        let collections = database::query(
+            conn,
+            "SELECT c.collection, c.synced_at AS syncedAt,
+                (SELECT count(*) FROM sync_records r WHERE r.collection = c.collection) AS records
+             FROM sync_cursors c ORDER BY c.collection",
+            &[],
+        )?;
+        let drafts = database::query(
+            conn,
+            "SELECT form_id AS formId, payload, updated_at AS updatedAt FROM drafts ORDER BY updated_at",
+            &[],
+        )?;
+        Ok(json!({ "documents": documents, "syncedCollections": collections, "drafts": drafts }))
+    })
+}
+
+fn files_section(app: &AppHandle) -> AppResult<Value> {
+    Ok(json!({
+        "storage": storage_files(&file_storage::root(app)?)?,
+        "downloads": app.state::<DownloadManager>().list(),
+        "uploads": app.state::<UploadQueue>().list(),
+        "cachedImages": app.state::<ImageCache>().entries(),
+        "vault": app.state::<Vault>().list(),
+    }))
+}
+
+fn section<T: serde::Serialize>(value: &T) -> AppResult<Value> {
+    serde_json::to_value(value).map_err(|e| AppError::Storage(format!("Failed to serialize section: {}", e)))
+}
+
+/// Gather the sections of the export
+///
+/// # Returns
+///
+/// Returns the sections by file name; a section that could not be read is an
+/// error.
+pub fn collect(app: &AppHandle) -> BTreeMap<&'static str, AppResult<Value>> {
+    let analytics = app.state::<Analytics>();
+    let state = app.state::<StateStore>();
+
+    BTreeMap::from([
+        ("preferences.json", section(&app.state::<Prefs>().all())),
+        (
+            "app_state.json",
+            Ok(json!({ "current": state.current(), "restored": state.restored() })),
+        ),
+        ("install.json", section(&app.state::<InstallState>().get())),
+        ("notifications.json", Ok(json!({ "badgeResetPolicy": badge::policy(app) }))),
+        (
+            "analytics.json",
+            Ok(json!({ "enabled": analytics.is_enabled(), "pendingEvents": analytics.queued() })),
+        ),
+        (
+            "audit_summary.json",
+            audit_log::export(app).and_then(|export| section(&audit_summary(&export))),
+        ),
+        ("database.json", database_section(app)),
+        ("files.json", files_section(app)),
+    ])
+}
+
+/// Build the archive from the collected sections
+///
+/// The manifest lists the exported and failed sections.
+pub fn build_archive(
+    sections: BTreeMap<&'static str, AppResult<Value>>,
+    generated_at: u64,
+    app_version: &str,
+) -> AppResult<DataArchive> {
+    let mut archive = ZipArchive::default();
+    let mut exported = Vec::new();
+    let mut failed = BTreeMap::new();
+
+    for (name, section) in sections {
+        match section {
+            Ok(value) => {
+                let contents = serde_json::to_string_pretty(&value)
+                    .map_err(|e| AppError::Storage(format!("Failed to serialize {}: {}", name, e)))?;
+                archive.add(name, redact::redact(&contents).as_bytes())?;
+                exported.push(name.to_string());
+            }
+            Err(e) => {
+                tracing::warn!("Personal data export: {} unavailable: {}", name, e);
+                failed.insert(name.to_string(), e.to_string());
+            }
+        }
+    }
+
+    let manifest = json!({
+        "generatedAt": generated_at,
+        "appVersion": app_version,
+        "sections": exported,
+        "unavailable": failed,
+        "excluded": EXCLUDED,
+    });
+    let manifest = serde_json::to_string_pretty(&manifest).map_err(|e| AppError::Storage(format!("Failed to serialize manifest: {}", e)))?;
+    archive.add("manifest.json", manifest.as_bytes())?;
+    Ok(DataArchive {
+        archive: archive.finish()?,
+        sections: exported,
+        failed,
+    })
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Export the personal data and present the share sheet
+///
+/// # Returns
+///
+/// Returns the `ExportedData`, or an error if the archive cannot be
+/// written or sharing is unavailable.
+pub fn export(app: &AppHandle) -> AppResult<ExportedData> {
+    let generated_at = now_secs();
+    let DataArchive { archive, sections, failed } =
+        build_archive(collect(app), generated_at, &app.package_info().version.to_string())?;
+
+    diagnostics::cleanup_exports(app);
+    let dir = diagnostics::exports_dir(app)?;
+    std::fs::create_dir_all(&dir).map_err(|e| AppError::Storage(format!("Failed to create export directory: {}", e)))?;
+    let file_name = format!("elulib-data-{}.zip", generated_at);
+    let path = dir.join(&file_name);
+    std::fs::write(&path, &archive).map_err(|e| AppError::Storage(format!("Failed to write data export: {}", e)))?;
+
+    if let Err(e) = share::share_file(&path, "application/zip", locale::strings().data_export_share_subject) {
+        let _ = std::fs::remove_file(&path);
+        return Err(e);
+    }
+    tracing::info!("Exported personal data ({} bytes, {} section(s))", archive.len(), sections.len());
+    Ok(ExportedData {
+        file_name,
+        bytes: archive.len() as u64,
+        sections,
+        failed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit_log::{AuditRecord, AuthMethod, SecurityEvent};
+
+    #[test]
+    fn test_audit_summary_counts_by_type() {
+        let mut records: Vec<AuditRecord> = Vec::new();
+        let events = [
+            SecurityEvent::AuthAttempt { method: AuthMethod::Oidc, success: true },
+            SecurityEvent::PinFailure { host: "app.elulib.com".to_string() },
+            SecurityEvent::AuthAttempt { method: AuthMethod::Passkey, success: false },
+        ];
+        for (index, event) in events.into_iter().enumerate() {
+            let record = AuditRecord::next(records.last().map(AuditRecord::head).as_ref(), 100 + index as u64, event);
+            records.push(record);
+        }
+        let summary = audit_summary(&AuditExport {
+            records,
+            anchor: None,
+            verified: true,
+            error: None,
+        });
+
+        assert_eq!(summary.total, 3);
+        assert_eq!(summary.by_type["auth_attempt"], 2);
+        assert_eq!(summary.by_type["pin_failure"], 1);
+        assert_eq!((summary.first_at, summary.last_at), (Some(100), Some(102)));
+    }
+
+    #[test]
+    fn test_archive_lists_failed_sections() {
+        let sections = BTreeMap::from([
+            ("preferences.json", Ok(json!({ "theme": "dark" }))),
+            ("database.json", Err(AppError::Keychain("Keystore unavailable".to_string()))),
+        ]);
+        let built = build_archive(sections, 1000, "1.2.0").unwrap();
+
+        assert_eq!(built.sections, ["preferences.json"]);
+        assert_eq!(built.failed["database.json"], "Keychain error: Keystore unavailable");
+        let contents = String::from_utf8_lossy(&built.archive);
+        assert!(contents.contains("\"theme\": \"dark\""));
+        assert!(contents.contains("manifest.json"));
+        assert!(contents.contains("\"unavailable\""));
+    }
+
+    #[test]
+    fn test_storage_files_are_listed_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        file_storage::write(dir.path(), "annexes/2024/budget.pdf", b"pdf").unwrap();
+        file_storage::write(dir.path(), "notes.txt", b"note").unwrap();
+
+        let paths: Vec<String> = storage_files(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect();
+        assert_eq!(paths, ["annexes", "annexes/2024", "annexes/2024/budget.pdf", "notes.txt"]);
+    }
+}
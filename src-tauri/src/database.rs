@@ -0,0 +1,374 @@
+/// Encrypted local database module
+///
+/// This module provides an SQLCipher-encrypted SQLite database, so offline
+/// document metadata is never stored in plaintext on disk:
+/// - the 256-bit database key is generated on first use and stored in the
+///   keychain / keystore (`DATABASE_KEY_NAME`); it is never regenerated for
+///   an existing database, keychain errors are returned instead
+/// - the database is opened lazily by the first `db_execute` / `db_query` call
+/// - schema migrations (`MIGRATIONS`) are applied in order when opening, and
+///   the applied version is tracked with `PRAGMA user_version`
+///
+/// Statement parameters and results are JSON values: `null`, booleans,
+/// numbers and strings map to SQLite values, arrays and objects are stored as
+/// JSON text, and blobs are returned base64-encoded.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use base64::Engine;
+use ring::rand::SecureRandom;
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use rusqlite::{params_from_iter, Connection};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_keystore::{RetrieveRequest, StoreRequest};
+
+use crate::constants::{self, helpers};
+use crate::plugins::{self, KeystoreExt};
+use crate::redact;
+use crate::{AppError, AppResult};
+
+/// Schema migrations, applied in order; never edit a released migration,
+/// append a new one instead
+pub const MIGRATIONS: &[&str] = &[
+    // 1: offline document metadata
+    "CREATE TABLE documents (
+        id TEXT PRIMARY KEY NOT NULL,
+        title TEXT NOT NULL,
+        kind TEXT,
+        updated_at INTEGER NOT NULL,
+        metadata TEXT
+    );
+    CREATE INDEX documents_updated_at ON documents (updated_at);",
+    // 2: background sync (see the `sync` module)
+    "CREATE TABLE sync_records (
+        collection TEXT NOT NULL,
+        id TEXT NOT NULL,
+        updated_at INTEGER NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (collection, id)
+    );
+    CREATE TABLE sync_cursors (
+        collection TEXT PRIMARY KEY NOT NULL,
+        cursor TEXT,
+        synced_at INTEGER NOT NULL
+    );",
+    // 3: form drafts (see the `drafts` module)
+    "CREATE TABLE drafts (
+        form_id TEXT PRIMARY KEY NOT NULL,
+        payload TEXT NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE INDEX drafts_updated_at ON drafts (updated_at);",
+    // 4: events queued until the page is ready (see the `outbox` module)
+    "CREATE TABLE outbox (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        channel TEXT NOT NULL,
+        payload TEXT NOT NULL,
+        created_at INTEGER NOT NULL
+    );",
+];
+
+/// Open an encrypted database and check the key
+///
+/// # Arguments
+///
+/// * `path` - Database file, created if missing
+/// * `key` - Hex-encoded 256-bit key
+///
+/// # Returns
+///
+/// Returns the connection, or an error if the file cannot be opened
+/// or the key is wrong.
+pub fn open(path: &Path, key: &str) -> AppResult<Connection> {
+    if key.len() != 64 || !key.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(AppError::InvalidArgument("Invalid database key".to_string()));
+    }
+    let conn = Connection::open(path).map_err(|e| AppError::Database(format!("Failed to open database: {}", e)))?;
+    // Raw key: no key derivation, the key is already random
+    conn.execute_batch(&format!("PRAGMA key = \"x'{}'\";", key))
+        .map_err(|e| AppError::Database(format!("Failed to set database key: {}", e)))?;
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))
+        .map_err(|e| AppError::Database(format!("Failed to decrypt database: {}", e)))?;
+    Ok(conn)
+}
+
+/// Apply the migrations not yet applied
+///
+/// # Returns
+///
+/// Returns the schema version, or an error if a migration failed (the
+/// failed migration is rolled back).
+pub fn migrate(conn: &mut Connection, migrations: &[&str]) -> AppResult<usize> {
+    let version: usize = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| AppError::Database(format!("Failed to read schema version: {}", e)))?;
+    if version > migrations.len() {
+        return Err(AppError::Database(format!(
+            "Database schema version {} is newer than this app ({})",
+            version,
+            migrations.len()
+        )));
+    }
+
+    for (index, migration) in migrations.iter().enumerate().skip(version) {
+        let version = index + 1;
+        let tx = conn.transaction().map_err(|e| AppError::Database(e.to_string()))?;
+        tx.execute_batch(migration)
+            .and_then(|_| tx.pragma_update(None, "user_version", version))
+            .and_then(|_| tx.commit())
+            .map_err(|e| AppError::Database(format!("Database migration {} failed: {}", version, e)))?;
+        tracing::info!("Applied database migration {}", version);
+    }
+    Ok(migrations.len())
+}
+
+/// Convert a JSON parameter to an SQLite value
+fn to_sql(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(value) => SqlValue::Integer(*value as i64),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => SqlValue::Integer(integer),
+            None => SqlValue::Real(number.as_f64().unwrap_or_default()),
+        },
+        Value::String(text) => SqlValue::Text(text.clone()),
+        Value::Array(_) | Value::Object(_) => SqlValue::Text(value.to_string()),
+    }
+}
+
+/// Convert an SQLite value to JSON
+fn from_sql(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(integer) => Value::from(integer),
+        ValueRef::Real(real) => Value::from(real),
+        ValueRef::Text(text) => Value::String(String::from_utf8_lossy(text).into_owned()),
+        ValueRef::Blob(blob) => Value::String(base64::engine::general_purpose::STANDARD.encode(blob)),
+    }
+}
+
+/// Execute a statement
+///
+/// # Returns
+///
+/// Returns the number of rows changed, or an error.
+pub fn execute(conn: &Connection, sql: &str, params: &[Value]) -> AppResult<usize> {
+    conn.execute(sql, params_from_iter(params.iter().map(to_sql)))
+        .map_err(|e| AppError::Database(format!("Database execute failed: {}", e)))
+}
+
+/// Run a query
+///
+/// # Returns
+///
+/// Returns the rows as objects keyed by column name, or an error.
+pub fn query(conn: &Connection, sql: &str, params: &[Value]) -> AppResult<Vec<Map<String, Value>>> {
+    let mut statement = conn
+        .prepare(sql)
+        .map_err(|e| AppError::Database(format!("Database query failed: {}", e)))?;
+    let columns: Vec<String> = statement.column_names().iter().map(|name| name.to_string()).collect();
+
+    let rows = statement
+        .query_map(params_from_iter(params.iter().map(to_sql)), |row| {
+            columns
+                .iter()
+                .enumerate()
+                .map(|(index, column)| Ok((column.clone(), from_sql(row.get_ref(index)?))))
+                .collect::<rusqlite::Result<Map<String, Value>>>()
+        })
+        .map_err(|e| AppError::Database(format!("Database query failed: {}", e)))?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| AppError::Database(format!("Database query failed: {}", e)))
+}
+
+/// What to do with the database key read from the keychain
+#[derive(Debug, PartialEq, Eq)]
+enum KeyLookup {
+    /// Use the stored key
+    Stored(String),
+    /// First use: generate and store a key
+    Generate,
+}
+
+/// Decide whether a key may be generated
+///
+/// A key is only generated when the keychain has none and no database exists
+/// yet: replacing the key of an existing database (after a transient keychain
+/// failure, or a lost entry) would make it, and every key derived from it,
+/// unreadable for good.
+fn lookup_key(stored: AppResult<Option<String>>, database_exists: bool) -> AppResult<KeyLookup> {
+    match stored?.filter(|key| !key.is_empty()) {
+        Some(key) => Ok(KeyLookup::Stored(key)),
+        None if database_exists => Err(AppError::Database("Database key missing from the keychain".to_string())),
+        None => Ok(KeyLookup::Generate),
+    }
+}
+
+/// Get the database key from the keychain, generating it on first use
+fn database_key(app: &AppHandle) -> AppResult<String> {
+    plugins::ensure_keystore(app)?;
+    let name = constants::DATABASE_KEY_NAME.to_string();
+    let stored = app
+        .keystore()
+        .retrieve(RetrieveRequest {
+            service: name.clone(),
+            user: name.clone(),
+        })
+        .map(|response| response.value)
+        .map_err(|e| AppError::Keychain(helpers::keychain_retrieve_error(&e)));
+    let database_exists = app
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join(constants::DATABASE_FILE).exists())
+        .map_err(|e| AppError::Database(format!("Failed to resolve data directory: {}", e)))?;
+    let key = match lookup_key(stored, database_exists) {
+        Ok(KeyLookup::Stored(key)) => {
+            redact::register_secret(&key);
+            return Ok(key);
+        }
+        Ok(KeyLookup::Generate) => {
+            tracing::info!("Generating database key");
+            let mut bytes = [0u8; 32];
+            ring::rand::SystemRandom::new()
+                .fill(&mut bytes)
+                .map_err(|_| AppError::Database("Failed to generate database key".to_string()))?;
+            bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()
+        }
+        Err(e) => {
+            tracing::error!("Failed to read the database key: {}", e);
+            return Err(e);
+        }
+    };
+    redact::register_secret(&key);
+    app.keystore()
+        .store(StoreRequest {
+            value: helpers::key_value_pair(&name, &key),
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_store_error(&e)))?;
+    Ok(key)
+}
+
+/// Keychain master key (the database key), from which the other at-rest keys
+/// are derived (`artifacts`)
+pub(crate) fn master_key(app: &AppHandle) -> AppResult<Vec<u8>> {
+    let key = database_key(app)?;
+    (0..key.len())
+        .step_by(2)
+        .map(|i| key.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| AppError::Database("Invalid master key".to_string()))
+}
+
+/// Encrypted database connection, managed by the Tauri application
+#[derive(Default)]
+pub struct Database {
+    conn: Mutex<Option<Connection>>,
+}
+
+impl Database {
+    /// Run a function with the connection, opening and migrating the database
+    /// on first use
+    pub fn with_connection<T>(
+        &self,
+        app: &AppHandle,
+        f: impl FnOnce(&Connection) -> AppResult<T>,
+    ) -> AppResult<T> {
+        let mut conn = self.conn.lock().map_err(|e| AppError::Database(e.to_string()))?;
+        if conn.is_none() {
+            let dir = app
+                .path()
+                .app_data_dir()
+                .map_err(|e| AppError::Database(format!("Failed to resolve data directory: {}", e)))?;
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| AppError::Database(format!("Failed to create data directory: {}", e)))?;
+
+            let mut opened = open(&dir.join(constants::DATABASE_FILE), &database_key(app)?)?;
+            migrate(&mut opened, MIGRATIONS)?;
+            *conn = Some(opened);
+        }
+        f(conn.as_ref().ok_or_else(|| AppError::Database("Database not open".to_string()))?)
+    }
+
+    /// Close the database, dropping its key from memory (reopened on next use)
+    pub fn close(&self) {
+        if let Ok(mut conn) = self.conn.lock() {
+            if conn.take().is_some() {
+                tracing::info!("Encrypted database closed");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const KEY: &str = "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff";
+
+    #[test]
+    fn test_migrations_and_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let mut conn = open(&path, KEY).unwrap();
+        assert_eq!(migrate(&mut conn, MIGRATIONS).unwrap(), MIGRATIONS.len());
+        assert_eq!(migrate(&mut conn, MIGRATIONS).unwrap(), MIGRATIONS.len(), "Idempotent");
+
+        let changed = execute(
+            &conn,
+            "INSERT INTO documents (id, title, kind, updated_at, metadata) VALUES (?, ?, ?, ?, ?)",
+            &[json!("doc-1"), json!("Agenda"), Value::Null, json!(1700000000), json!({ "pages": 3 })],
+        )
+        .unwrap();
+        assert_eq!(changed, 1);
+
+        let rows = query(&conn, "SELECT * FROM documents WHERE updated_at > ?", &[json!(0)]).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["title"], json!("Agenda"));
+        assert_eq!(rows[0]["kind"], Value::Null);
+        assert_eq!(rows[0]["metadata"], json!("{\"pages\":3}"));
+    }
+
+    #[test]
+    fn test_database_is_encrypted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let mut conn = open(&path, KEY).unwrap();
+        migrate(&mut conn, MIGRATIONS).unwrap();
+        execute(&conn, "INSERT INTO documents (id, title, updated_at) VALUES ('a', 'Secret title', 1)", &[]).unwrap();
+        drop(conn);
+
+        let contents = std::fs::read(&path).unwrap();
+        assert!(!contents.windows(12).any(|window| window == b"Secret title"));
+        assert!(!contents.starts_with(b"SQLite format 3"));
+
+        let wrong_key = KEY.replace('0', "1");
+        assert!(open(&path, &wrong_key).is_err());
+        assert!(open(&path, "not-a-key").is_err());
+    }
+
+    #[test]
+    fn test_key_is_only_generated_for_a_new_database() {
+        assert_eq!(lookup_key(Ok(Some(KEY.to_string())), true).unwrap(), KeyLookup::Stored(KEY.to_string()));
+        assert_eq!(lookup_key(Ok(None), false).unwrap(), KeyLookup::Generate);
+        assert_eq!(lookup_key(Ok(Some(String::new())), false).unwrap(), KeyLookup::Generate);
+        assert!(lookup_key(Ok(None), true).is_err(), "Existing database keeps its key");
+        assert!(lookup_key(Err(AppError::Database("Keychain locked".to_string())), false).is_err(), "Read errors are propagated");
+    }
+
+    #[test]
+    fn test_failed_migration_is_rolled_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut conn = open(&dir.path().join("test.db"), KEY).unwrap();
+        let migrations = ["CREATE TABLE a (id INTEGER);", "CREATE TABLE b (id INTEGER); INVALID SQL;"];
+        assert!(migrate(&mut conn, &migrations).is_err());
+
+        let version: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, 1);
+        assert!(query(&conn, "SELECT * FROM b", &[]).is_err());
+        assert!(migrate(&mut conn, &migrations[..1]).is_ok());
+        assert!(migrate(&mut conn, &[]).is_err(), "Schema newer than the app");
+    }
+}
@@ -0,0 +1,207 @@
+/// Debug-only webview tools and hidden debug menu backend
+///
+/// Debug builds expose commands to open the web inspector, reload the page
+/// and navigate the main window, to debug the remote frontend on a device.
+///
+/// They also back the hidden debug menu of the frontend (opened with a
+/// shake), so QA can exercise error paths on real devices:
+/// - simulate offline: connectivity checks and HTTP requests (see `http`)
+///   fail until turned off
+/// - fire a fake silent push, handled like a native one
+/// - show a test notification on every delivery channel: system
+///   notification, app icon badge and in-app event
+/// - corrupt a keychain entry, to test the recovery from unreadable secrets
+/// - force a crash, recorded as a crash report by the panic hook
+///
+/// The module and its commands are compiled only with `debug_assertions`, so
+/// they cannot be enabled in a release build. The web inspector itself is
+/// built into Tauri for debug builds; the `devtools` cargo feature of `tauri`,
+/// which would enable it in release, must stay off.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager, Url, WebviewWindow};
+use tauri_plugin_keystore::StoreRequest;
+
+use crate::connectivity::{self, ConnectivityError, ConnectivityProber, ConnectivityResult, ConnectivityTarget, ProbeFuture, Prober, TcpProber};
+use crate::constants::{self, helpers};
+use crate::events::{Event, NotificationReceived};
+use crate::notifications;
+use crate::plugins::{self, KeystoreExt};
+use crate::sync::{self, SyncReport};
+use crate::{AppError, AppResult};
+
+/// Main webview window
+fn main_window(app: &AppHandle) -> AppResult<WebviewWindow> {
+    app.get_webview_window(constants::MAIN_WINDOW_LABEL)
+        .ok_or_else(|| AppError::Platform("Main window not found".to_string()))
+}
+
+/// Parse a navigation target; only `http` and `https` URLs are accepted
+pub fn parse_navigation_url(url: &str) -> AppResult<Url> {
+    let parsed = Url::parse(url).map_err(|e| AppError::InvalidArgument(format!("Invalid URL: {}", e)))?;
+    match parsed.scheme() {
+        "http" | "https" => Ok(parsed),
+        scheme => Err(AppError::InvalidArgument(format!("Unsupported URL scheme: {}", scheme))),
+    }
+}
+
+/// Open the web inspector of the main window
+pub fn open_devtools(app: &AppHandle) -> AppResult<()> {
+    main_window(app)?.open_devtools();
+    tracing::debug!("Devtools opened");
+    Ok(())
+}
+
+/// Reload the page of the main window
+pub fn reload(app: &AppHandle) -> AppResult<()> {
+    main_window(app)?
+        .reload()
+        .map_err(|e| AppError::Platform(format!("Failed to reload webview: {}", e)))
+}
+
+/// Navigate the main window to a URL
+///
+/// Sensitive commands stay limited to the allowed origins (`origin_guard`),
+/// whatever page is loaded.
+pub fn navigate(app: &AppHandle, url: &str) -> AppResult<()> {
+    let url = parse_navigation_url(url)?;
+    tracing::info!("Debug navigation to {}", url);
+    main_window(app)?
+        .navigate(url)
+        .map_err(|e| AppError::Platform(format!("Failed to navigate: {}", e)))
+}
+
+/// Prober failing every attempt at once, installed while offline is simulated
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OfflineProber;
+
+impl Prober for OfflineProber {
+    fn probe<'a>(&'a self, _target: &'a ConnectivityTarget) -> ProbeFuture<'a, ConnectivityResult> {
+        Box::pin(std::future::ready(Err(ConnectivityError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotConnected,
+            "Offline simulated from the debug menu",
+        )))))
+    }
+
+    fn wait(&self, _delay: Duration) -> ProbeFuture<'_, ()> {
+        Box::pin(std::future::ready(()))
+    }
+}
+
+/// Whether offline is simulated
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Whether offline is simulated
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::SeqCst)
+}
+
+/// Simulate offline, or go back to the real connectivity checks
+pub fn set_offline(app: &AppHandle, offline: bool) {
+    let prober: Arc<dyn Prober> = if offline { Arc::new(OfflineProber) } else { Arc::new(TcpProber) };
+    app.state::<ConnectivityProber>().replace(prober);
+    OFFLINE.store(offline, Ordering::SeqCst);
+    connectivity::report(app, !offline);
+    tracing::warn!("Simulated offline {}", if offline { "on" } else { "off" });
+}
+
+/// Deliver a fake push, as the native push handlers would
+///
+/// Without payload, a silent sync push of all collections is delivered.
+pub async fn fire_fake_push(app: &AppHandle, payload: Option<Value>) -> AppResult<Option<SyncReport>> {
+    let payload = payload.unwrap_or_else(|| json!({ "type": "sync" }));
+    tracing::info!("Fake push fired from the debug menu");
+    sync::on_silent_push(app, &payload).await
+}
+
+/// Outcome of a test notification on one delivery channel
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct ChannelDelivery {
+    /// Delivery channel: `native` (with its channel on Android), `badge` or
+    /// `webview` (`notification://received` event)
+    pub channel: String,
+    /// Error, if the delivery failed
+    pub error: Option<String>,
+}
+
+/// Show a test notification on every delivery channel
+pub fn test_notifications(app: &AppHandle) -> Vec<ChannelDelivery> {
+    const TITLE: &str = "élulib";
+    const BODY: &str = "Test notification from the debug menu";
+    let native = match notifications::default_channel() {
+        Some(channel) => format!("native:{}", channel),
+        None => "native".to_string(),
+    };
+    let in_app = NotificationReceived {
+        title: TITLE.to_string(),
+        body: BODY.to_string(),
+        link: None,
+        tapped: false,
+    };
+    let deliveries = [
+        (native, notifications::show_notification(TITLE, BODY, None)),
+        ("badge".to_string(), notifications::set_badge_count(1)),
+        (
+            "webview".to_string(),
+            app.emit(NotificationReceived::NAME, &in_app).map_err(AppError::from),
+        ),
+    ];
+    deliveries
+        .into_iter()
+        .map(|(channel, result)| ChannelDelivery { channel, error: result.err().map(|e| e.to_string()) })
+        .collect()
+}
+
+/// Overwrite a keychain entry with a value no reader accepts
+pub fn corrupt_keychain_entry(app: &AppHandle, key: &str) -> AppResult<()> {
+    helpers::validate_keychain_key(key)?;
+    plugins::ensure_keystore(app)?;
+    app.keystore()
+        .store(StoreRequest {
+            value: helpers::key_value_pair(key, constants::DEBUG_CORRUPTED_KEYCHAIN_VALUE),
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_store_error(&e)))?;
+    tracing::warn!("Keychain entry corrupted from the debug menu");
+    Ok(())
+}
+
+/// Crash the app: the panic is recorded as a crash report, then the process
+/// aborts, so the next launch goes through the post-crash flow
+pub fn force_crash() -> ! {
+    tracing::error!("Crash forced from the debug menu");
+    let _ = std::thread::spawn(|| panic!("Crash forced from the debug menu")).join();
+    std::process::abort()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_navigation_url_scheme() {
+        assert!(parse_navigation_url("https://app.elulib.fr/agenda").is_ok());
+        assert!(parse_navigation_url("http://192.168.1.20:5173/").is_ok());
+        assert!(parse_navigation_url("file:///etc/passwd").is_err());
+        assert!(parse_navigation_url("javascript:alert(1)").is_err());
+        assert!(parse_navigation_url("not a url").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_simulated_offline_fails_http_requests() {
+        OFFLINE.store(true, Ordering::SeqCst);
+        let result = crate::http::client().unwrap().get("https://app.elulib.com/").send().await;
+        OFFLINE.store(false, Ordering::SeqCst);
+        assert!(result.is_err_and(|e| e.is_connect()));
+    }
+
+    #[tokio::test]
+    async fn test_offline_prober_fails_at_once() {
+        let target = ConnectivityTarget::default();
+        let result = crate::connectivity::check_connectivity_with(&OfflineProber, &target).await;
+        assert!(!matches!(result, Ok(true)));
+    }
+}
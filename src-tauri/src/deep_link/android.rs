@@ -0,0 +1,56 @@
+/// Android-specific deep-link plumbing
+///
+/// The main activity is declared with `android:launchMode="singleTask"`, so
+/// links opened while the app runs are delivered to the existing activity
+/// through `onNewIntent` instead of starting a duplicate one. Both the launch
+/// intent and new intents are forwarded to the Rust side.
+///
+/// Note: This implementation provides the structure for Android deep links.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use tauri::AppHandle;
+use crate::AppResult;
+
+/// Start forwarding deep-link intents on Android
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start(app: AppHandle) -> AppResult<()> {
+    tracing::info!("[Android] Starting deep-link intent forwarding");
+
+    // TODO: Implement native Android intent forwarding
+    // Each intent URL must be forwarded to `deep_link::on_open_url`, except
+    // notification taps, forwarded with their data to
+    // `push::on_platform_push` (tapped).
+    // Example Kotlin implementation:
+    // ```kotlin
+    // // AndroidManifest.xml: <activity android:launchMode="singleTask"> with
+    // // intent filters for elulib:// and https://app.elulib.com (autoVerify)
+    // override fun onCreate(savedInstanceState: Bundle?) {
+    //     super.onCreate(savedInstanceState)
+    //     // Launch intent: a notification tap is kept as the launch payload (see `launch`)
+    //     forwardIntent(intent)
+    // }
+    // override fun onNewIntent(intent: Intent) {
+    //     super.onNewIntent(intent)
+    //     setIntent(intent)
+    //     forwardIntent(intent)
+    // }
+    // private fun forwardIntent(intent: Intent) {
+    //     if (intent.hasExtra("notification_id")) {
+    //         val extras = intent.extras ?: return
+    //         val payload = JSONObject(extras.keySet().associateWith { extras.getString(it) })
+    //         onPush(payload.toString(), true)
+    //         return
+    //     }
+    //     intent.data?.let { onOpenUrl(it.toString(), "link") }
+    // }
+    // ```
+
+    // Placeholder: Return success without forwarding intents
+    // Replace this with actual native implementation
+    let _ = app;
+    Ok(())
+}
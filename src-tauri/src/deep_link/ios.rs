@@ -0,0 +1,60 @@
+/// iOS-specific deep-link plumbing
+///
+/// Tauri forwards `application(_:open:options:)` (custom scheme) to the Rust
+/// side as `RunEvent::Opened`. Universal links and notification taps are
+/// delivered by the app delegate; taps go through the push pipeline
+/// (`push::on_platform_push`), which routes their link.
+///
+/// Note: This implementation provides the structure for iOS deep links.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Start listening for universal links and notification taps on iOS
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start() -> AppResult<()> {
+    tracing::info!("[iOS] Starting deep-link listener");
+
+    // TODO: Implement native iOS universal link and notification tap handling
+    // Universal links are forwarded to `deep_link::on_open_url`, notification
+    // taps to `push::on_platform_push` (tapped), with their whole payload.
+    // Example Swift implementation:
+    // ```swift
+    // func application(_ application: UIApplication,
+    //                  didFinishLaunchingWithOptions launchOptions: [UIApplication.LaunchOptionsKey: Any]?) -> Bool {
+    //     UNUserNotificationCenter.current().delegate = self
+    //     // Launched by a tap on a notification: kept as the launch payload (see `launch`); the
+    //     // same tap may also reach `didReceive` before the first page load, and replaces it
+    //     if let userInfo = launchOptions?[.remoteNotification] as? [AnyHashable: Any] {
+    //         onPush(jsonString(userInfo), true)
+    //     }
+    //     return true
+    // }
+    // func application(_ application: UIApplication, continue userActivity: NSUserActivity,
+    //                  restorationHandler: @escaping ([UIUserActivityRestoring]?) -> Void) -> Bool {
+    //     guard let url = userActivity.webpageURL else { return false }
+    //     onOpenUrl(url.absoluteString, "link")
+    //     return true
+    // }
+    // func userNotificationCenter(_ center: UNUserNotificationCenter, didReceive response: UNNotificationResponse,
+    //                             withCompletionHandler completionHandler: @escaping () -> Void) {
+    //     onPush(jsonString(response.notification.request.content.userInfo), true)
+    //     completionHandler()
+    // }
+    // func userNotificationCenter(_ center: UNUserNotificationCenter, willPresent notification: UNNotification,
+    //                             withCompletionHandler completionHandler:
+    //                             @escaping (UNNotificationPresentationOptions) -> Void) {
+    //     // Shown by `push::on_push` (`notifications::show_notification`)
+    //     onPush(jsonString(notification.request.content.userInfo), false)
+    //     completionHandler([])
+    // }
+    // ```
+
+    // Placeholder: Return success (custom scheme links still arrive via RunEvent::Opened)
+    // Replace this with actual native implementation
+    Ok(())
+}
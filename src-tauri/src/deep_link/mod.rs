@@ -0,0 +1,237 @@
+/// Deep-link forwarding
+///
+/// This module routes deep links (`elulib://...` custom scheme and
+/// `https://app.elulib.com/...` universal / app links) and notification taps
+/// to the single running webview, instead of dropping them or starting a
+/// duplicate activity:
+/// - while the app is running (first page loaded), a `deeplink://open` event
+///   is emitted and the frontend routes to the link in-place
+/// - during a cold start, the link is kept pending and the webview navigates
+///   to it once the first page has loaded (a notification tapped during a
+///   cold start is kept as the launch payload instead, see `launch`)
+///
+/// Links are delivered by `RunEvent::Opened` on iOS and by the native
+/// `onNewIntent` bridge on Android (the activity uses `launchMode="singleTask"`
+/// so a link never spawns a second instance). Invoked shortcuts are routed
+/// here too, by the `shortcuts` module.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, Url};
+
+use crate::auth;
+use crate::breadcrumbs;
+use crate::constants;
+use crate::events;
+use crate::runtime_config;
+use crate::{AppError, AppResult};
+
+/// Origin of a deep link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DeepLinkSource {
+    /// Custom scheme, universal link or app link
+    Link,
+    /// Tap on a notification
+    Notification,
+    /// Siri Shortcut or App Action (see `shortcuts`)
+    Shortcut,
+}
+
+/// A deep link into the application, emitted as `deeplink://open`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLink {
+    /// The link, as received
+    pub url: String,
+    /// Path (with query and fragment) to open in the frontend
+    pub path: String,
+    /// Origin of the link
+    pub source: DeepLinkSource,
+}
+
+impl DeepLink {
+    /// Frontend URL the webview should navigate to for this link
+    pub fn target_url(&self) -> String {
+        format!("{}{}", runtime_config::get().app_url, self.path)
+    }
+}
+
+/// Parse and validate a deep link
+///
+/// Accepts `elulib://<path>` and `https://app.elulib.com/<path>`.
+///
+/// # Returns
+///
+/// Returns the `DeepLink`, or an error if the URL does not point into
+/// the application.
+pub fn parse(url: &str, source: DeepLinkSource) -> AppResult<DeepLink> {
+    let parsed = Url::parse(url).map_err(|e| AppError::InvalidArgument(format!("Invalid deep link {}: {}", url, e)))?;
+    let app_url = Url::parse(&runtime_config::get().app_url).map_err(|e| AppError::Platform(e.to_string()))?;
+
+    let path = match parsed.scheme() {
+        scheme if scheme == constants::DEEP_LINK_SCHEME => {
+            // elulib://agenda/42 -> host "agenda", path "/42"
+            format!("/{}{}", parsed.host_str().unwrap_or_default(), parsed.path())
+                .replace("//", "/")
+        }
+        "https" if parsed.host_str() == app_url.host_str() => parsed.path().to_string(),
+        _ => return Err(AppError::Platform(format!("Deep link outside of the application: {}", url))),
+    };
+    let query = parsed.query().map(|query| format!("?{}", query)).unwrap_or_default();
+    let fragment = parsed.fragment().map(|fragment| format!("#{}", fragment)).unwrap_or_default();
+
+    Ok(DeepLink {
+        url: url.to_string(),
+        path: format!("{}{}{}", path, query, fragment),
+        source,
+    })
+}
+
+/// Deep-link routing state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct DeepLinkState {
+    /// `true` once the first page is loaded
+    ready: Mutex<bool>,
+    /// Link received before the first page load
+    pending: Mutex<Option<DeepLink>>,
+}
+
+impl DeepLinkState {
+    /// Keep a link received during startup
+    ///
+    /// # Returns
+    ///
+    /// Returns the link back if the app is ready and it must be delivered now.
+    pub fn defer(&self, link: DeepLink) -> Option<DeepLink> {
+        if self.is_ready() {
+            return Some(link);
+        }
+        if let Ok(mut pending) = self.pending.lock() {
+            // Only the most recent link is relevant
+            *pending = Some(link);
+        }
+        None
+    }
+
+    /// Whether the first page is loaded
+    pub fn is_ready(&self) -> bool {
+        self.ready.lock().map(|ready| *ready).unwrap_or(true)
+    }
+
+    /// Mark the app as ready and take the link received during startup
+    pub fn set_ready(&self) -> Option<DeepLink> {
+        if let Ok(mut ready) = self.ready.lock() {
+            *ready = true;
+        }
+        self.pending.lock().ok().and_then(|mut pending| pending.take())
+    }
+}
+
+/// Route a deep link received by the native layer
+///
+/// Redirects of a pending native login are handed to the `auth` module
+/// instead.
+pub fn on_open_url(app: &AppHandle, url: &str, source: DeepLinkSource) {
+    if auth::on_redirect(app, url) {
+        return;
+    }
+    let link = match parse(url, source) {
+        Ok(link) => link,
+        Err(e) => {
+            tracing::warn!("{}", e);
+            return;
+        }
+    };
+    tracing::info!("Deep link received: {}", link.path);
+    breadcrumbs::breadcrumb("deep_link", &link.path);
+
+    if let Some(link) = app.state::<DeepLinkState>().defer(link) {
+        events::emit(app, &link);
+    }
+}
+
+/// Called when the first page is loaded: navigate to the link received
+/// during startup, if any
+pub fn on_first_page_load(app: &AppHandle) {
+    let Some(link) = app.state::<DeepLinkState>().set_ready() else {
+        return;
+    };
+    let Some(window) = app.get_webview_window(constants::MAIN_WINDOW_LABEL) else {
+        tracing::warn!("Main window not found, dropping deep link {}", link.path);
+        return;
+    };
+
+    tracing::info!("Opening deep link received at startup: {}", link.path);
+    let navigated = Url::parse(&link.target_url())
+        .map_err(|e| e.to_string())
+        .and_then(|url| window.navigate(url).map_err(|e| e.to_string()));
+    if let Err(e) = navigated {
+        tracing::warn!("Failed to open deep link {}: {}", link.path, e);
+    }
+}
+
+/// Start listening for deep links delivered by the native layer
+pub fn start(app: &AppHandle) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        // Links are delivered through `RunEvent::Opened`
+        let _ = app;
+        ios::start()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::start(app.clone())
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = app;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_custom_scheme() {
+        let link = parse("elulib://agenda/42?tab=notes", DeepLinkSource::Link).unwrap();
+        assert_eq!(link.path, "/agenda/42?tab=notes");
+        assert_eq!(link.target_url(), "https://app.elulib.com/agenda/42?tab=notes");
+    }
+
+    #[test]
+    fn test_parse_app_link() {
+        let link = parse("https://app.elulib.com/documents/7#page-2", DeepLinkSource::Notification).unwrap();
+        assert_eq!(link.path, "/documents/7#page-2");
+        assert_eq!(link.source, DeepLinkSource::Notification);
+    }
+
+    #[test]
+    fn test_parse_rejects_foreign_links() {
+        assert!(parse("https://evil.example.com/agenda", DeepLinkSource::Link).is_err());
+        assert!(parse("http://app.elulib.com/agenda", DeepLinkSource::Link).is_err());
+        assert!(parse("not a url", DeepLinkSource::Link).is_err());
+    }
+
+    #[test]
+    fn test_links_are_deferred_until_ready() {
+        let state = DeepLinkState::default();
+        let link = |path: &str| parse(&format!("elulib://{}", path), DeepLinkSource::Link).unwrap();
+
+        assert_eq!(state.defer(link("first")), None);
+        assert_eq!(state.defer(link("second")), None);
+        assert_eq!(state.set_ready().map(|link| link.path), Some("/second".to_string()));
+        assert_eq!(state.set_ready(), None);
+        assert_eq!(state.defer(link("third")).map(|link| link.path), Some("/third".to_string()));
+    }
+}
@@ -0,0 +1,305 @@
+/// In-process mock backends for desktop development builds
+///
+/// With the `dev-mock` cargo feature, the subsystems that need a device are
+/// replaced by in-memory mocks, so the whole app can be exercised on a desktop
+/// machine without a device or simulator:
+/// - keystore: `plugins::KeystoreExt` resolves to `MockKeystore` instead of
+///   the keystore plugin
+/// - notifications: shown notifications, the badge and the permission are
+///   recorded instead of reaching the (missing) desktop implementation
+/// - push: payloads are delivered with the `dev_mock_push` command, and
+///   silent sync pushes are handled like native ones
+///
+/// The `dev_mock_state` command returns the state of the mocks. Nothing is
+/// persisted: the mocks are empty at each launch. The feature does not build
+/// for mobile targets or in release mode: secrets are kept in memory,
+/// unprotected.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_keystore::{RemoveRequest, RetrieveRequest, StoreRequest};
+
+use crate::sync::{self, SyncReport};
+use crate::AppResult;
+
+/// Maximum number of notifications and push payloads kept by the mocks
+const MOCK_HISTORY_LEN: usize = 50;
+
+/// Error returned by the mock keystore
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockKeystoreError(String);
+
+impl std::fmt::Display for MockKeystoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Value retrieved from the mock keystore
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockRetrieveResponse {
+    /// Stored value
+    pub value: Option<String>,
+}
+
+/// In-memory keystore, with the API of the keystore plugin
+#[derive(Debug)]
+pub struct MockKeystore {
+    values: Mutex<BTreeMap<String, String>>,
+}
+
+static KEYSTORE: MockKeystore = MockKeystore {
+    values: Mutex::new(BTreeMap::new()),
+};
+
+impl MockKeystore {
+    /// Store a `key:value` pair, as built by `helpers::key_value_pair`
+    pub fn store(&self, request: StoreRequest) -> Result<(), MockKeystoreError> {
+        let (key, value) = request
+            .value
+            .split_once(':')
+            .ok_or_else(|| MockKeystoreError("Expected a key:value pair".to_string()))?;
+        self.values
+            .lock()
+            .map_err(|e| MockKeystoreError(e.to_string()))?
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    /// Retrieve a value; like the keychain, missing keys are an error
+    pub fn retrieve(&self, request: RetrieveRequest) -> Result<MockRetrieveResponse, MockKeystoreError> {
+        self.values
+            .lock()
+            .map_err(|e| MockKeystoreError(e.to_string()))?
+            .get(&request.service)
+            .map(|value| MockRetrieveResponse {
+                value: Some(value.clone()),
+            })
+            .ok_or_else(|| MockKeystoreError(format!("No value for {}", request.service)))
+    }
+
+    /// Remove a value, if stored
+    pub fn remove(&self, request: RemoveRequest) -> Result<(), MockKeystoreError> {
+        self.values
+            .lock()
+            .map_err(|e| MockKeystoreError(e.to_string()))?
+            .remove(&request.service);
+        Ok(())
+    }
+
+    /// Stored keys (values are never exposed)
+    pub fn keys(&self) -> Vec<String> {
+        self.values
+            .lock()
+            .map(|values| values.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Access to the mock keystore, in place of `tauri_plugin_keystore::KeystoreExt`
+pub trait KeystoreExt<R: Runtime> {
+    /// Mock keystore, shared by the whole process
+    fn keystore(&self) -> &MockKeystore;
+}
+
+impl<R: Runtime, T: tauri::Manager<R>> KeystoreExt<R> for T {
+    fn keystore(&self) -> &MockKeystore {
+        &KEYSTORE
+    }
+}
+
+/// Notification recorded by the mock
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct MockNotification {
+    /// Notification title
+    pub title: String,
+    /// Notification body text
+    pub body: String,
+    /// Icon, if any
+    pub icon: Option<String>,
+}
+
+#[derive(Debug)]
+struct MockNotifications {
+    shown: VecDeque<MockNotification>,
+    badge_count: u32,
+    permission_granted: bool,
+}
+
+static NOTIFICATIONS: Mutex<MockNotifications> = Mutex::new(MockNotifications {
+    shown: VecDeque::new(),
+    badge_count: 0,
+    permission_granted: false,
+});
+
+static PUSHES: Mutex<VecDeque<Value>> = Mutex::new(VecDeque::new());
+
+fn push_bounded<T>(history: &mut VecDeque<T>, item: T) {
+    if history.len() >= MOCK_HISTORY_LEN {
+        history.pop_front();
+    }
+    history.push_back(item);
+}
+
+/// Mock notification backend, used by `notifications` on desktop
+pub mod notifications {
+    use super::*;
+
+    /// Record a notification
+    pub fn show(title: &str, body: &str, icon: Option<&str>) -> AppResult<()> {
+        tracing::info!("Mock notification: {}", title);
+        if let Ok(mut notifications) = NOTIFICATIONS.lock() {
+            push_bounded(
+                &mut notifications.shown,
+                MockNotification {
+                    title: title.to_string(),
+                    body: body.to_string(),
+                    icon: icon.map(str::to_string),
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Grant the permission, as a user accepting the prompt would
+    pub fn request_permission() -> AppResult<bool> {
+        if let Ok(mut notifications) = NOTIFICATIONS.lock() {
+            notifications.permission_granted = true;
+        }
+        Ok(true)
+    }
+
+    /// Whether the permission was requested
+    pub fn check_permission() -> AppResult<bool> {
+        Ok(NOTIFICATIONS
+            .lock()
+            .map(|notifications| notifications.permission_granted)
+            .unwrap_or_default())
+    }
+
+    /// Record the badge count
+    pub fn set_badge_count(count: u32) -> AppResult<()> {
+        if let Ok(mut notifications) = NOTIFICATIONS.lock() {
+            notifications.badge_count = count;
+        }
+        Ok(())
+    }
+
+    /// Forget the shown notifications (the mock has no channels)
+    pub fn clear_delivered() -> AppResult<()> {
+        if let Ok(mut notifications) = NOTIFICATIONS.lock() {
+            notifications.shown.clear();
+        }
+        Ok(())
+    }
+}
+
+/// Deliver a push payload, as the native push handlers would
+///
+/// # Returns
+///
+/// Returns the sync report for silent sync pushes, `None` for other payloads.
+pub async fn deliver_push(app: &AppHandle, payload: Value) -> AppResult<Option<SyncReport>> {
+    tracing::info!("Mock push delivered");
+    if let Ok(mut pushes) = PUSHES.lock() {
+        push_bounded(&mut pushes, payload.clone());
+    }
+    sync::on_silent_push(app, &payload).await
+}
+
+/// State of the mock backends, returned by `dev_mock_state`
+#[derive(Debug, Clone, PartialEq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MockState {
+    /// Keys stored in the mock keystore
+    pub keystore_keys: Vec<String>,
+    /// Last shown notifications, oldest first
+    pub notifications: Vec<MockNotification>,
+    /// Badge count
+    pub badge_count: u32,
+    /// Whether the notification permission was granted
+    pub notification_permission: bool,
+    /// Last delivered push payloads, oldest first
+    pub pushes: Vec<Value>,
+}
+
+/// Current state of the mock backends
+pub fn state() -> MockState {
+    let (notifications, badge_count, notification_permission) = NOTIFICATIONS
+        .lock()
+        .map(|n| (n.shown.iter().cloned().collect(), n.badge_count, n.permission_granted))
+        .unwrap_or_default();
+    MockState {
+        keystore_keys: KEYSTORE.keys(),
+        notifications,
+        badge_count,
+        notification_permission,
+        pushes: PUSHES
+            .lock()
+            .map(|pushes| pushes.iter().cloned().collect())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serializes the tests using the process-wide mock state
+    static MOCK_STATE: Mutex<()> = Mutex::new(());
+
+    fn reset() {
+        if let Ok(mut notifications) = NOTIFICATIONS.lock() {
+            notifications.shown.clear();
+            notifications.badge_count = 0;
+            notifications.permission_granted = false;
+        }
+        if let Ok(mut pushes) = PUSHES.lock() {
+            pushes.clear();
+        }
+    }
+
+    #[test]
+    fn test_mock_keystore_roundtrip() {
+        let request = |key: &str| RetrieveRequest {
+            service: key.to_string(),
+            user: key.to_string(),
+        };
+        KEYSTORE
+            .store(StoreRequest {
+                value: "mock_test_key:secret:with:colons".to_string(),
+            })
+            .unwrap();
+        assert_eq!(
+            KEYSTORE.retrieve(request("mock_test_key")).unwrap().value.as_deref(),
+            Some("secret:with:colons")
+        );
+        assert!(state().keystore_keys.contains(&"mock_test_key".to_string()));
+
+        KEYSTORE
+            .remove(RemoveRequest {
+                service: "mock_test_key".to_string(),
+                user: "mock_test_key".to_string(),
+            })
+            .unwrap();
+        assert!(KEYSTORE.retrieve(request("mock_test_key")).is_err());
+        assert!(KEYSTORE.store(StoreRequest { value: "no-separator".to_string() }).is_err());
+    }
+
+    #[test]
+    fn test_mock_notifications_are_bounded() {
+        let _guard = MOCK_STATE.lock().unwrap_or_else(|e| e.into_inner());
+        reset();
+        for index in 0..MOCK_HISTORY_LEN + 5 {
+            notifications::show(&format!("Title {}", index), "Body", None).unwrap();
+        }
+        notifications::set_badge_count(3).unwrap();
+        let shown = state().notifications;
+        assert_eq!(shown.len(), MOCK_HISTORY_LEN);
+        assert_eq!(shown.last().unwrap().title, format!("Title {}", MOCK_HISTORY_LEN + 4));
+        assert_eq!(state().badge_count, 3);
+    }
+}
@@ -0,0 +1,90 @@
+/// Android-specific device-binding key implementation
+///
+/// This module keeps the device-binding key in the Android Keystore, backed by
+/// StrongBox when the device has one (`setIsStrongBoxBacked`), falling back to
+/// the trusted execution environment. The key is never exportable and is
+/// removed with the app.
+///
+/// Note: This implementation provides the structure for the Android device key.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::BindingKey;
+use crate::constants;
+use crate::{AppError, AppResult};
+
+/// Get the device-binding key, generating it if missing
+///
+/// # Returns
+///
+/// Returns the public key and its storage (`KeyInfo.securityLevel`), or an
+/// error if the keystore is unavailable.
+pub fn ensure_key() -> AppResult<BindingKey> {
+    tracing::debug!("[Android] Loading device-binding key {}", constants::DEVICE_BINDING_KEY_ALIAS);
+
+    // TODO: Implement native Android key lookup and generation via JNI
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val keyStore = KeyStore.getInstance("AndroidKeyStore").apply { load(null) }
+    // if (!keyStore.containsAlias(alias)) {
+    //     val spec = KeyGenParameterSpec.Builder(alias, KeyProperties.PURPOSE_SIGN)
+    //         .setAlgorithmParameterSpec(ECGenParameterSpec("secp256r1"))
+    //         .setDigests(KeyProperties.DIGEST_SHA256)
+    //         .setIsStrongBoxBacked(hasStrongBox) // retry without on StrongBoxUnavailableException
+    //         .build()
+    //     KeyPairGenerator.getInstance(KeyProperties.KEY_ALGORITHM_EC, "AndroidKeyStore")
+    //         .apply { initialize(spec) }
+    //         .generateKeyPair()
+    // }
+    // val publicKey = keyStore.getCertificate(alias).publicKey.encoded // SubjectPublicKeyInfo DER
+    // ```
+
+    // Placeholder: Report the keystore as unavailable
+    // Replace this with actual native implementation
+    Err(AppError::Auth("Android Keystore not available".to_string()))
+}
+
+/// Sign a message with the device-binding key
+///
+/// # Returns
+///
+/// Returns the ECDSA P-256 SHA-256 signature (ASN.1 DER), or an error
+/// if signing fails.
+pub fn sign(message: &[u8]) -> AppResult<Vec<u8>> {
+    // TODO: Implement native Android signing via JNI
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val entry = keyStore.getEntry(alias, null) as KeyStore.PrivateKeyEntry
+    // Signature.getInstance("SHA256withECDSA").run {
+    //     initSign(entry.privateKey)
+    //     update(message)
+    //     sign()
+    // }
+    // ```
+
+    // Placeholder: Report the keystore as unavailable
+    // Replace this with actual native implementation
+    let _ = message;
+    Err(AppError::Auth("Android Keystore not available".to_string()))
+}
+
+/// Delete the device-binding key
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the key is deleted or did not exist, or an error
+/// message if the keystore is unavailable.
+pub fn delete_key() -> AppResult<()> {
+    tracing::info!("[Android] Deleting device-binding key {}", constants::DEVICE_BINDING_KEY_ALIAS);
+
+    // TODO: Implement native Android key deletion via JNI
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val keyStore = KeyStore.getInstance("AndroidKeyStore").apply { load(null) }
+    // if (keyStore.containsAlias(alias)) keyStore.deleteEntry(alias)
+    // ```
+
+    // Placeholder: No key is ever generated by the placeholder
+    // Replace this with actual native implementation
+    Ok(())
+}
@@ -0,0 +1,96 @@
+/// iOS-specific device-binding key implementation
+///
+/// This module keeps the device-binding key in the Secure Enclave
+/// (`kSecAttrTokenIDSecureEnclave`), accessible after the first unlock and
+/// only on this device (`kSecAttrAccessibleAfterFirstUnlockThisDeviceOnly`),
+/// so it is excluded from backups and device transfers.
+///
+/// Note: This implementation provides the structure for the iOS device key.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::BindingKey;
+use crate::constants;
+use crate::{AppError, AppResult};
+
+/// Get the device-binding key, generating it if missing
+///
+/// # Returns
+///
+/// Returns the public key, or an error if the Secure Enclave is
+/// unavailable.
+pub fn ensure_key() -> AppResult<BindingKey> {
+    tracing::debug!("[iOS] Loading device-binding key {}", constants::DEVICE_BINDING_KEY_ALIAS);
+
+    // TODO: Implement native iOS key lookup and generation
+    // Example Swift implementation:
+    // ```swift
+    // let tag = alias.data(using: .utf8)!
+    // var key = lookupKey(tag) // SecItemCopyMatching with kSecReturnRef
+    // if key == nil {
+    //     let access = SecAccessControlCreateWithFlags(nil,
+    //         kSecAttrAccessibleAfterFirstUnlockThisDeviceOnly, .privateKeyUsage, nil)!
+    //     key = SecKeyCreateRandomKey([
+    //         kSecAttrKeyType: kSecAttrKeyTypeECSECPrimeRandom,
+    //         kSecAttrKeySizeInBits: 256,
+    //         kSecAttrTokenID: kSecAttrTokenIDSecureEnclave,
+    //         kSecPrivateKeyAttrs: [
+    //             kSecAttrIsPermanent: true,
+    //             kSecAttrApplicationTag: tag,
+    //             kSecAttrAccessControl: access,
+    //         ],
+    //     ] as CFDictionary, nil)
+    // }
+    // // SecKeyCopyExternalRepresentation returns the X9.63 point: prefix it
+    // // with the P-256 SubjectPublicKeyInfo header
+    // let point = SecKeyCopyExternalRepresentation(SecKeyCopyPublicKey(key!)!, nil)! as Data
+    // ```
+
+    // Placeholder: Report the Secure Enclave as unavailable
+    // Replace this with actual native implementation
+    Err(AppError::Auth("Secure Enclave not available".to_string()))
+}
+
+/// Sign a message with the device-binding key
+///
+/// # Returns
+///
+/// Returns the ECDSA P-256 SHA-256 signature (ASN.1 DER), or an error
+/// if signing fails.
+pub fn sign(message: &[u8]) -> AppResult<Vec<u8>> {
+    // TODO: Implement native iOS signing
+    // Example Swift implementation:
+    // ```swift
+    // SecKeyCreateSignature(key, .ecdsaSignatureMessageX962SHA256, message as CFData, nil)
+    // ```
+
+    // Placeholder: Report the Secure Enclave as unavailable
+    // Replace this with actual native implementation
+    let _ = message;
+    Err(AppError::Auth("Secure Enclave not available".to_string()))
+}
+
+/// Delete the device-binding key
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the key is deleted or did not exist, or an error
+/// message if the keychain is unavailable.
+pub fn delete_key() -> AppResult<()> {
+    tracing::info!("[iOS] Deleting device-binding key {}", constants::DEVICE_BINDING_KEY_ALIAS);
+
+    // TODO: Implement native iOS key deletion
+    // Example Swift implementation:
+    // ```swift
+    // let query: [String: Any] = [
+    //     kSecClass as String: kSecClassKey,
+    //     kSecAttrApplicationTag as String: alias.data(using: .utf8)!,
+    // ]
+    // let status = SecItemDelete(query as CFDictionary)
+    // guard status == errSecSuccess || status == errSecItemNotFound else { throw ... }
+    // ```
+
+    // Placeholder: No key is ever generated by the placeholder
+    // Replace this with actual native implementation
+    Ok(())
+}
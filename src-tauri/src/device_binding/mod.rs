@@ -0,0 +1,234 @@
+/// Device-binding key
+///
+/// A non-exportable P-256 key is generated in the device's secure hardware at
+/// first launch (Secure Enclave on iOS, Android Keystore, StrongBox when
+/// available). The app signs backend-issued nonces with it, so the backend can
+/// bind refresh tokens to the physical device: a token copied to another
+/// phone is useless without the key, which never leaves the hardware.
+///
+/// Backend integration:
+/// 1. Binding, at login: the backend issues a nonce, the frontend calls
+///    `device_binding_assertion(nonce)` and sends the assertion along with the
+///    login. The backend verifies the signature with `publicKey` and stores
+///    `keyId` and `publicKey` with the refresh token it issues.
+/// 2. Refresh: the backend issues a new nonce, the frontend sends a fresh
+///    assertion with the refresh request. The backend rejects the refresh if
+///    `keyId` differs from the bound key or the signature does not verify.
+///
+/// The signature is ECDSA P-256 SHA-256 (ASN.1 DER, base64url) over
+/// `elulib-device-binding:v1:<nonce>` (`signed_message`), so it cannot be
+/// replayed as a signature of another protocol. `keyId` is the base64url
+/// SHA-256 of the public key (SubjectPublicKeyInfo DER). Nonces follow the
+/// attestation nonce rules (URL-safe base64, 16 to 500 characters) and must
+/// be single-use.
+///
+/// The key is deleted with the app: a reinstall produces a new key, and the
+/// user must log in again.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use base64::Engine;
+
+use crate::attestation;
+use crate::{AppError, AppResult};
+
+/// Domain separation prefix of the signed messages
+pub const SIGNED_MESSAGE_PREFIX: &str = "elulib-device-binding:v1:";
+
+/// Where the device-binding key is stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyStorage {
+    /// Secure Enclave (iOS)
+    SecureEnclave,
+    /// StrongBox secure element (Android)
+    StrongBox,
+    /// Trusted execution environment (Android)
+    TrustedEnvironment,
+    /// Software keystore (emulators, devices without secure hardware)
+    Software,
+}
+
+/// Public part of the device-binding key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingKey {
+    /// SubjectPublicKeyInfo DER
+    pub public_key: Vec<u8>,
+    /// Where the private key is stored
+    pub storage: KeyStorage,
+}
+
+/// Signature of a backend nonce
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BindingAssertion {
+    /// Key identifier (base64url SHA-256 of the public key)
+    pub key_id: String,
+    /// Public key (base64url SubjectPublicKeyInfo DER)
+    pub public_key: String,
+    /// Signature algorithm, always `ES256`
+    pub algorithm: &'static str,
+    /// Signature of `signed_message(nonce)` (base64url ASN.1 DER)
+    pub signature: String,
+    /// Where the private key is stored
+    pub storage: KeyStorage,
+}
+
+/// Message signed for a nonce
+pub fn signed_message(nonce: &str) -> Vec<u8> {
+    format!("{}{}", SIGNED_MESSAGE_PREFIX, nonce).into_bytes()
+}
+
+/// Identifier of a public key
+pub fn key_id(public_key: &[u8]) -> String {
+    let digest = ring::digest::digest(&ring::digest::SHA256, public_key);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest.as_ref())
+}
+
+/// Build the assertion of a signature
+fn assertion(key: &BindingKey, signature: &[u8]) -> BindingAssertion {
+    let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    BindingAssertion {
+        key_id: key_id(&key.public_key),
+        public_key: engine.encode(&key.public_key),
+        algorithm: "ES256",
+        signature: engine.encode(signature),
+        storage: key.storage,
+    }
+}
+
+/// Generate the device-binding key at first launch
+///
+/// Does nothing if the key already exists. Failures are logged: signing
+/// retries the generation.
+pub fn init() {
+    match ensure_key() {
+        Ok(key) => tracing::info!(
+            "Device-binding key ready ({}, {:?})",
+            key_id(&key.public_key),
+            key.storage
+        ),
+        Err(e) => tracing::warn!("Device-binding key unavailable: {}", e),
+    }
+}
+
+/// Sign a backend nonce with the device-binding key
+///
+/// # Arguments
+///
+/// * `nonce` - Single-use URL-safe base64 challenge issued by the backend
+///
+/// # Returns
+///
+/// Returns the `BindingAssertion`, or an error if the nonce is
+/// invalid or the key is unavailable.
+pub fn sign_nonce(nonce: &str) -> AppResult<BindingAssertion> {
+    attestation::validate_nonce(nonce)?;
+    let key = ensure_key()?;
+    let signature = sign(&signed_message(nonce))?;
+    Ok(assertion(&key, &signature))
+}
+
+/// Delete the device-binding key (local data wipe)
+///
+/// A new key is generated on next use; the backend no longer accepts the
+/// refresh tokens bound to the deleted key.
+pub fn delete_key() -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::delete_key()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::delete_key()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(())
+    }
+}
+
+/// Get the device-binding key, generating it if missing
+fn ensure_key() -> AppResult<BindingKey> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::ensure_key()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::ensure_key()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Err(AppError::Auth("Device binding not supported on this platform".to_string()))
+    }
+}
+
+/// Sign a message with the device-binding key
+fn sign(message: &[u8]) -> AppResult<Vec<u8>> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::sign(message)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::sign(message)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = message;
+        Err(AppError::Auth("Device binding not supported on this platform".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1, ECDSA_P256_SHA256_ASN1_SIGNING};
+
+    /// SubjectPublicKeyInfo DER prefix of a P-256 public key
+    const P256_SPKI_PREFIX: [u8; 26] = [
+        0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a, 0x86, 0x48,
+        0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+    ];
+
+    #[test]
+    fn test_assertion_verifies_with_public_key() {
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+        let pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng).unwrap();
+        let key = BindingKey {
+            public_key: [&P256_SPKI_PREFIX[..], pair.public_key().as_ref()].concat(),
+            storage: KeyStorage::Software,
+        };
+
+        let nonce = "dGhpcyBpcyBhIG5vbmNl";
+        let signature = pair.sign(&rng, &signed_message(nonce)).unwrap();
+        let assertion = assertion(&key, signature.as_ref());
+
+        // What the backend does
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        let spki = engine.decode(&assertion.public_key).unwrap();
+        assert_eq!(assertion.key_id, key_id(&spki));
+        let point = spki.strip_prefix(&P256_SPKI_PREFIX[..]).unwrap();
+        let message = format!("elulib-device-binding:v1:{}", nonce);
+        ring::signature::UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, point)
+            .verify(message.as_bytes(), &engine.decode(&assertion.signature).unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_sign_nonce_rejects_invalid_nonce() {
+        assert!(sign_nonce("short").unwrap_err().to_string().contains("Nonce length"));
+    }
+}
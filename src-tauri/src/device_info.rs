@@ -0,0 +1,30 @@
+/// Device information module
+///
+/// This module gathers general information about the device and the running
+/// application, reported to the frontend through the `device_info` command.
+
+use crate::integrity;
+
+/// General information about the device and the application
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceInfo {
+    /// Operating system (`ios`, `android`, ...)
+    pub platform: String,
+    /// CPU architecture (`aarch64`, `x86_64`, ...)
+    pub arch: String,
+    /// Application version
+    pub app_version: String,
+    /// `true` if running in an emulator or simulator
+    pub is_emulator: bool,
+}
+
+/// Collect information about the current device
+pub fn collect() -> DeviceInfo {
+    DeviceInfo {
+        platform: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        is_emulator: integrity::is_emulator(),
+    }
+}
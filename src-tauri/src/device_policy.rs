@@ -0,0 +1,305 @@
+/// Policy-driven degradation on compromised devices
+///
+/// This module combines the device trust signals into an active policy and
+/// applies its consequences.
+///
+/// Signals:
+/// - `integrity_compromised`: root, jailbreak or tamper indicators were found
+///   (`integrity` module)
+/// - `no_lock_screen`: no passcode protects the device (`device_security`)
+/// - `attestation_failed`: the attestation service rejected the last
+///   `attest_device` request (a request that did not complete is ignored)
+///
+/// Consequences:
+/// - `block_offline_cache`: the encrypted database is closed and the `db_*`
+///   commands are rejected
+/// - `frequent_reauth`: the session locks after `POLICY_REAUTH_TIMEOUT_SECS`
+///   of inactivity at most
+/// - `disable_keychain_export`: `keychain_retrieve` is rejected
+///
+/// The rules mapping signals to consequences default to `DEFAULT_RULES` and
+/// can be replaced with the `device_policy` remote config key, e.g.
+/// `{ "no_lock_screen": ["block_offline_cache"] }`.
+///
+/// The policy is evaluated at startup, on foreground (the user may have
+/// removed their passcode), after an attestation request and whenever the
+/// remote config is applied. The frontend reads it with the `device_policy`
+/// command and is notified of changes with the `policy://changed` event.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::ipc::Invoke;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::constants;
+use crate::database::Database;
+use crate::device_security;
+use crate::events;
+use crate::integrity;
+use crate::remote_config::{keys, ConfigStore};
+use crate::session_lock::SessionLock;
+use crate::{AppError, AppResult};
+
+/// Device trust signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Signal {
+    /// Root, jailbreak or tamper indicators were found
+    IntegrityCompromised,
+    /// No passcode protects the lock screen
+    NoLockScreen,
+    /// The attestation service rejected the last attestation request
+    AttestationFailed,
+}
+
+/// Consequence applied by the policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Consequence {
+    /// Do not cache content in the encrypted database
+    BlockOfflineCache,
+    /// Lock the session after a shorter inactivity delay
+    FrequentReauth,
+    /// Do not release keychain values to the frontend
+    DisableKeychainExport,
+}
+
+impl Consequence {
+    /// Commands rejected while the consequence is active
+    pub fn gated_commands(self) -> &'static [&'static str] {
+        match self {
+            Consequence::BlockOfflineCache => &["db_execute", "db_query", "db_query_packed", "draft_save", "draft_load"],
+            Consequence::FrequentReauth => &[],
+            Consequence::DisableKeychainExport => &["keychain_retrieve"],
+        }
+    }
+}
+
+/// Rules applied when the remote config does not define any
+pub const DEFAULT_RULES: &[(Signal, &[Consequence])] = &[
+    (
+        Signal::IntegrityCompromised,
+        &[
+            Consequence::BlockOfflineCache,
+            Consequence::FrequentReauth,
+            Consequence::DisableKeychainExport,
+        ],
+    ),
+    (Signal::NoLockScreen, &[Consequence::BlockOfflineCache]),
+    (Signal::AttestationFailed, &[Consequence::FrequentReauth]),
+];
+
+/// Consequences of each signal
+pub type Rules = BTreeMap<Signal, BTreeSet<Consequence>>;
+
+/// Default rules
+pub fn default_rules() -> Rules {
+    DEFAULT_RULES
+        .iter()
+        .map(|(signal, consequences)| (*signal, consequences.iter().copied().collect()))
+        .collect()
+}
+
+/// Active policy, as returned to the frontend
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivePolicy {
+    /// Signals currently raised
+    pub signals: BTreeSet<Signal>,
+    /// Consequences currently applied
+    pub consequences: BTreeSet<Consequence>,
+}
+
+impl ActivePolicy {
+    /// Apply the rules to the raised signals
+    pub fn resolve(signals: BTreeSet<Signal>, rules: &Rules) -> Self {
+        let consequences = signals
+            .iter()
+            .filter_map(|signal| rules.get(signal))
+            .flatten()
+            .copied()
+            .collect();
+        Self { signals, consequences }
+    }
+
+    /// Returns `true` if the consequence is applied
+    pub fn applies(&self, consequence: Consequence) -> bool {
+        self.consequences.contains(&consequence)
+    }
+
+    /// Session lock timeout under this policy
+    pub fn lock_timeout(&self, configured: Duration) -> Duration {
+        if self.applies(Consequence::FrequentReauth) {
+            configured.min(Duration::from_secs(constants::POLICY_REAUTH_TIMEOUT_SECS))
+        } else {
+            configured
+        }
+    }
+}
+
+/// Check that a command is allowed by the policy
+///
+/// # Returns
+///
+/// Returns `Ok(())` if no applied consequence gates the command, or an error
+/// message naming the consequence.
+pub fn check(command: &str, policy: &ActivePolicy) -> AppResult<()> {
+    match policy
+        .consequences
+        .iter()
+        .find(|consequence| consequence.gated_commands().contains(&command))
+    {
+        Some(consequence) => Err(AppError::Denied(format!(
+            "Command {} is disabled by the device policy ({:?})",
+            command, consequence
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Policy state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct DevicePolicy {
+    active: Mutex<ActivePolicy>,
+    attestation_failed: Mutex<bool>,
+}
+
+impl DevicePolicy {
+    /// Active policy
+    pub fn active(&self) -> ActivePolicy {
+        self.active.lock().map(|active| active.clone()).unwrap_or_default()
+    }
+}
+
+/// Raise the signals of the current device
+fn collect_signals(app: &AppHandle) -> BTreeSet<Signal> {
+    let mut signals = BTreeSet::new();
+    if integrity::check().compromised {
+        signals.insert(Signal::IntegrityCompromised);
+    }
+    // Desktop builds have no lock screen to check
+    if cfg!(any(target_os = "ios", target_os = "android")) {
+        match device_security::status() {
+            Ok(status) if !status.is_secure() => {
+                signals.insert(Signal::NoLockScreen);
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed to read the lock-screen status: {}", e),
+        }
+    }
+    if app
+        .state::<DevicePolicy>()
+        .attestation_failed
+        .lock()
+        .map(|failed| *failed)
+        .unwrap_or(false)
+    {
+        signals.insert(Signal::AttestationFailed);
+    }
+    signals
+}
+
+/// Evaluate the policy and apply its consequences
+pub fn evaluate(app: &AppHandle) {
+    let config = app.state::<ConfigStore>();
+    let rules = config.get_typed::<Rules>(keys::DEVICE_POLICY).unwrap_or_else(default_rules);
+    let policy = ActivePolicy::resolve(collect_signals(app), &rules);
+
+    let configured = config
+        .get_typed(keys::SESSION_LOCK_TIMEOUT_SECS)
+        .unwrap_or(constants::SESSION_LOCK_TIMEOUT_SECS);
+    app.state::<SessionLock>()
+        .set_timeout(policy.lock_timeout(Duration::from_secs(configured)));
+    if policy.applies(Consequence::BlockOfflineCache) {
+        app.state::<Database>().close();
+    }
+
+    let changed = match app.state::<DevicePolicy>().active.lock() {
+        Ok(mut active) if *active != policy => {
+            *active = policy.clone();
+            true
+        }
+        _ => false,
+    };
+    if changed {
+        tracing::warn!(
+            "Device policy changed: signals {:?}, consequences {:?}",
+            policy.signals,
+            policy.consequences
+        );
+        events::emit(app, &policy);
+    }
+}
+
+/// Record the outcome of a completed attestation request (`succeeded` is
+/// `false` for a rejection), then re-evaluate the policy
+pub fn record_attestation(app: &AppHandle, succeeded: bool) {
+    if let Ok(mut failed) = app.state::<DevicePolicy>().attestation_failed.lock() {
+        *failed = !succeeded;
+    }
+    evaluate(app);
+}
+
+/// Validate an IPC invocation against the active policy
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command may run, or an error to reject the
+/// invocation with.
+pub fn check_invoke<R: Runtime>(invoke: &Invoke<R>) -> AppResult<()> {
+    let policy = invoke.message.webview_ref().app_handle().state::<DevicePolicy>().active();
+    let result = check(invoke.message.command(), &policy);
+    if let Err(e) = &result {
+        tracing::warn!("{}", e);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_default_rules() {
+        let rules = default_rules();
+        assert_eq!(ActivePolicy::resolve(BTreeSet::new(), &rules), ActivePolicy::default());
+
+        let policy = ActivePolicy::resolve(BTreeSet::from([Signal::NoLockScreen]), &rules);
+        assert_eq!(policy.consequences, BTreeSet::from([Consequence::BlockOfflineCache]));
+        assert!(check("db_query", &policy).is_err());
+        assert!(check("keychain_retrieve", &policy).is_ok());
+
+        let policy = ActivePolicy::resolve(BTreeSet::from([Signal::IntegrityCompromised]), &rules);
+        assert!(check("keychain_retrieve", &policy).is_err());
+        assert!(check("device_info", &policy).is_ok());
+    }
+
+    #[test]
+    fn test_remote_rules() {
+        let rules: Rules = serde_json::from_value(serde_json::json!({
+            "attestation_failed": ["disable_keychain_export", "frequent_reauth"],
+        }))
+        .unwrap();
+        let policy = ActivePolicy::resolve(
+            BTreeSet::from([Signal::AttestationFailed, Signal::NoLockScreen]),
+            &rules,
+        );
+        assert!(!policy.applies(Consequence::BlockOfflineCache), "No rule for no_lock_screen");
+        assert!(policy.applies(Consequence::DisableKeychainExport));
+    }
+
+    #[test]
+    fn test_lock_timeout() {
+        let configured = Duration::from_secs(300);
+        assert_eq!(ActivePolicy::default().lock_timeout(configured), configured);
+
+        let policy = ActivePolicy::resolve(BTreeSet::from([Signal::AttestationFailed]), &default_rules());
+        assert_eq!(
+            policy.lock_timeout(configured),
+            Duration::from_secs(constants::POLICY_REAUTH_TIMEOUT_SECS)
+        );
+        assert_eq!(policy.lock_timeout(Duration::from_secs(10)), Duration::from_secs(10));
+    }
+}
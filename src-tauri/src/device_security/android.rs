@@ -0,0 +1,33 @@
+/// Android-specific device security implementation
+///
+/// This module queries the lock-screen configuration using `KeyguardManager`
+/// and `BiometricManager` from the Android SDK.
+///
+/// Note: This implementation provides the structure for Android device security.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::DeviceSecurityStatus;
+use crate::AppResult;
+
+/// Get the lock-screen security status on Android
+///
+/// # Returns
+///
+/// Returns the `DeviceSecurityStatus` of the device.
+pub fn status() -> AppResult<DeviceSecurityStatus> {
+    tracing::debug!("[Android] Checking device security status");
+
+    // TODO: Implement native Android check using KeyguardManager and BiometricManager
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val keyguard = context.getSystemService(Context.KEYGUARD_SERVICE) as KeyguardManager
+    // val passcodeSet = keyguard.isDeviceSecure
+    // val biometricsEnrolled = BiometricManager.from(context)
+    //     .canAuthenticate(BiometricManager.Authenticators.BIOMETRIC_STRONG) == BiometricManager.BIOMETRIC_SUCCESS
+    // ```
+
+    // Placeholder: Report an unsecured device
+    // Replace this with actual native implementation
+    Ok(DeviceSecurityStatus::default())
+}
@@ -0,0 +1,34 @@
+/// iOS-specific device security implementation
+///
+/// This module queries the lock-screen configuration using `LAContext`
+/// from the LocalAuthentication framework.
+///
+/// Note: This implementation provides the structure for iOS device security.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::DeviceSecurityStatus;
+use crate::AppResult;
+
+/// Get the lock-screen security status on iOS
+///
+/// # Returns
+///
+/// Returns the `DeviceSecurityStatus` of the device.
+pub fn status() -> AppResult<DeviceSecurityStatus> {
+    tracing::debug!("[iOS] Checking device security status");
+
+    // TODO: Implement native iOS check using LAContext
+    // Example Swift implementation:
+    // ```swift
+    // let context = LAContext()
+    // let passcodeSet = context.canEvaluatePolicy(.deviceOwnerAuthentication, error: nil)
+    // var error: NSError?
+    // let biometricsEnrolled = context.canEvaluatePolicy(.deviceOwnerAuthenticationWithBiometrics, error: &error)
+    // // error?.code == LAError.biometryNotEnrolled.rawValue when hardware exists but nothing is enrolled
+    // ```
+
+    // Placeholder: Report an unsecured device
+    // Replace this with actual native implementation
+    Ok(DeviceSecurityStatus::default())
+}
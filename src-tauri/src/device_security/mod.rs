@@ -0,0 +1,71 @@
+/// Device lock-screen security status
+///
+/// This module reports whether the device is protected by a passcode (or
+/// pattern/PIN) and whether biometrics are enrolled, so the frontend can
+/// refuse to cache sensitive documents on unsecured devices.
+
+use crate::AppResult;
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+/// Lock-screen security status of the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceSecurityStatus {
+    /// A passcode, PIN, pattern or password protects the lock screen
+    pub passcode_set: bool,
+    /// At least one biometric credential (Face ID, Touch ID, fingerprint) is enrolled
+    pub biometrics_enrolled: bool,
+}
+
+impl DeviceSecurityStatus {
+    /// Returns `true` if the device is considered secure enough to cache
+    /// sensitive content
+    ///
+    /// Biometrics cannot be enrolled without a passcode, so the passcode is
+    /// the only requirement.
+    pub fn is_secure(&self) -> bool {
+        self.passcode_set
+    }
+}
+
+/// Get the lock-screen security status of the current device
+///
+/// # Returns
+///
+/// Returns the `DeviceSecurityStatus`, or an error if it cannot be
+/// determined.
+pub fn status() -> AppResult<DeviceSecurityStatus> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::status()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::status()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(DeviceSecurityStatus::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_secure_requires_passcode() {
+        let unsecured = DeviceSecurityStatus::default();
+        assert!(!unsecured.is_secure());
+
+        let secured = DeviceSecurityStatus { passcode_set: true, biometrics_enrolled: false };
+        assert!(secured.is_secure());
+    }
+}
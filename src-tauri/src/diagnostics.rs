@@ -0,0 +1,514 @@
+/// Diagnostics export module
+///
+/// Support asks users for their logs; this module packs them in two taps:
+/// the `export_logs` command zips the current and rotated log files with a
+/// diagnostics summary (`summary.json`: device, storage, log settings,
+/// breadcrumb trail), then
+/// hands the archive to the native share sheet.
+///
+/// Log lines are redacted again when exported, so secrets registered after a
+/// line was written are masked too. Unlike crash reports (`artifacts`), the
+/// archive is not encrypted: it is written in plaintext to the `exports`
+/// directory of the cache, only for the share sheet, and deleted before the
+/// next export and at the next launch.
+///
+/// Archives use the ZIP format with stored (uncompressed) entries, which any
+/// desktop or mail client opens; the log files are bounded by the rotation
+/// limits.
+///
+/// The in-app support form attaches a fuller report instead
+/// (`generate_diagnostics` command, `report`): permissions, connectivity,
+/// keychain entries (names only, never values), notifications, device
+/// integrity and the last crash, in addition to the summary.
+
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Manager};
+
+use crate::breadcrumbs::{self, Breadcrumb};
+use crate::connectivity;
+use crate::constants;
+use crate::crash::{self, CrashState, CrashSummary};
+use crate::device_info::{self, DeviceInfo};
+use crate::integrity::{self, IntegrityReport};
+use crate::locale;
+use crate::log_level::{self, LogLevelSetting};
+use crate::logging::{self, LogStorageUsage};
+use crate::network_info::{self, NetworkInterfaces};
+use crate::notifications;
+use crate::permissions::{self, PermissionEntry};
+use crate::plugins;
+use crate::redact;
+use crate::remote_config::ConfigStore;
+use crate::share;
+use crate::storage_info::{self, StorageInfo};
+use crate::{AppError, AppResult};
+
+/// Keychain entries of the app reported by `report`
+const KEYCHAIN_ENTRIES: &[&str] = &[
+    constants::SESSION_TOKEN_KEY,
+    constants::DATABASE_KEY_NAME,
+    constants::AUDIT_LOG_ANCHOR_KEY,
+];
+
+/// Device and app summary attached to exported logs
+#[derive(Debug, Clone, PartialEq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsSummary {
+    /// Time of the export (seconds since the Unix epoch)
+    pub generated_at: u64,
+    /// Device and app version
+    pub device: DeviceInfo,
+    /// Storage and memory, if available
+    pub storage: Option<StorageInfo>,
+    /// Active log level
+    pub log_level: LogLevelSetting,
+    /// Disk space used by the logs
+    pub log_storage: LogStorageUsage,
+    /// Breadcrumb trail, oldest first
+    pub breadcrumbs: Vec<Breadcrumb>,
+}
+
+/// Exported archive, as reported to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedLogs {
+    /// Archive file name
+    pub file_name: String,
+    /// Archive size (bytes)
+    pub bytes: u64,
+}
+
+/// Reachability of the application server
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectivityReport {
+    /// The server answered a quick connection attempt
+    pub reachable: bool,
+    /// Error of the connection attempt, if any
+    pub error: Option<String>,
+    /// Active network, if available
+    pub network: Option<NetworkInterfaces>,
+}
+
+/// Keychain entries of the app (names only)
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct KeychainStats {
+    /// Number of entries checked
+    pub checked: usize,
+    /// Names of the entries present
+    pub present: Vec<String>,
+}
+
+/// Notification permission and channel
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationState {
+    /// Notifications are allowed, if known
+    pub permission_granted: Option<bool>,
+    /// Notification channel, on platforms with channels
+    pub channel: Option<String>,
+}
+
+/// Report attached to the in-app support form
+#[derive(Debug, Clone, PartialEq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsReport {
+    /// Device, storage and log settings
+    #[serde(flatten)]
+    pub summary: DiagnosticsSummary,
+    /// Runtime permission statuses, if available
+    pub permissions: Option<Vec<PermissionEntry>>,
+    /// Reachability of the application server
+    pub connectivity: ConnectivityReport,
+    /// Keychain entries of the app
+    pub keychain: KeychainStats,
+    /// Notification permission and channel
+    pub notifications: NotificationState,
+    /// Root/jailbreak and tamper indicators of the device
+    pub integrity: IntegrityReport,
+    /// Most recent pending crash report, if any
+    pub last_crash: Option<CrashSummary>,
+}
+
+/// CRC-32 (IEEE) of a buffer, as required by ZIP entries
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Central directory record of an archive entry
+struct ZipEntry {
+    name: String,
+    crc: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// In-memory ZIP archive with stored entries
+#[derive(Default)]
+pub struct ZipArchive {
+    data: Vec<u8>,
+    entries: Vec<ZipEntry>,
+}
+
+/// DOS date of 1980-01-01, the ZIP epoch; entries carry no meaningful date
+const ZIP_DOS_DATE: u16 = (1 << 5) | 1;
+
+/// General purpose flag: file names are UTF-8
+const ZIP_UTF8_FLAG: u16 = 1 << 11;
+
+/// ZIP version 2.0, the minimum for stored entries in directories
+const ZIP_VERSION: u16 = 20;
+
+fn to_u32(value: usize) -> AppResult<u32> {
+    u32::try_from(value).map_err(|_| AppError::Storage("Archive too large".to_string()))
+}
+
+impl ZipArchive {
+    /// Add a file to the archive
+    pub fn add(&mut self, name: &str, contents: &[u8]) -> AppResult<()> {
+        let entry = ZipEntry {
+            name: name.to_string(),
+            crc: crc32(contents),
+            size: to_u32(contents.len())?,
+            offset: to_u32(self.data.len())?,
+        };
+        let name_len = u16::try_from(name.len()).map_err(|_| AppError::Storage("Archive entry name too long".to_string()))?;
+
+        let data = &mut self.data;
+        data.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        data.extend_from_slice(&ZIP_VERSION.to_le_bytes());
+        data.extend_from_slice(&ZIP_UTF8_FLAG.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes()); // stored
+        data.extend_from_slice(&0u16.to_le_bytes()); // time
+        data.extend_from_slice(&ZIP_DOS_DATE.to_le_bytes());
+        data.extend_from_slice(&entry.crc.to_le_bytes());
+        data.extend_from_slice(&entry.size.to_le_bytes()); // compressed
+        data.extend_from_slice(&entry.size.to_le_bytes()); // uncompressed
+        data.extend_from_slice(&name_len.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes()); // extra field
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(contents);
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Write the central directory and return the archive
+    pub fn finish(mut self) -> AppResult<Vec<u8>> {
+        let directory_offset = to_u32(self.data.len())?;
+        let count = u16::try_from(self.entries.len()).map_err(|_| AppError::Storage("Too many archive entries".to_string()))?;
+
+        let data = &mut self.data;
+        for entry in &self.entries {
+            data.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            data.extend_from_slice(&ZIP_VERSION.to_le_bytes()); // made by
+            data.extend_from_slice(&ZIP_VERSION.to_le_bytes()); // needed
+            data.extend_from_slice(&ZIP_UTF8_FLAG.to_le_bytes());
+            data.extend_from_slice(&0u16.to_le_bytes()); // stored
+            data.extend_from_slice(&0u16.to_le_bytes()); // time
+            data.extend_from_slice(&ZIP_DOS_DATE.to_le_bytes());
+            data.extend_from_slice(&entry.crc.to_le_bytes());
+            data.extend_from_slice(&entry.size.to_le_bytes());
+            data.extend_from_slice(&entry.size.to_le_bytes());
+            data.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+            data.extend_from_slice(&[0u8; 12]); // extra, comment, disk, attributes
+            data.extend_from_slice(&entry.offset.to_le_bytes());
+            data.extend_from_slice(entry.name.as_bytes());
+        }
+        let directory_size = to_u32(data.len())? - directory_offset;
+
+        data.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        data.extend_from_slice(&[0u8; 4]); // disk numbers
+        data.extend_from_slice(&count.to_le_bytes());
+        data.extend_from_slice(&count.to_le_bytes());
+        data.extend_from_slice(&directory_size.to_le_bytes());
+        data.extend_from_slice(&directory_offset.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes()); // comment
+        Ok(self.data)
+    }
+}
+
+/// Redact every line of a log file
+pub fn redact_log(contents: &str) -> String {
+    contents
+        .lines()
+        .map(|line| redact::redact(line) + "\n")
+        .collect()
+}
+
+/// Log files of a directory, by name (current file first, then rotated files)
+fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Build the archive of the log directory
+///
+/// # Returns
+///
+/// Returns the ZIP archive, with the redacted logs under `logs/` and the
+/// summary as `summary.json`.
+pub fn build_archive(log_dir: &Path, summary: &DiagnosticsSummary) -> AppResult<Vec<u8>> {
+    let mut archive = ZipArchive::default();
+    let summary = serde_json::to_vec_pretty(summary)
+        .map_err(|e| AppError::Storage(format!("Failed to serialize diagnostics summary: {}", e)))?;
+    archive.add("summary.json", &summary)?;
+
+    for path in log_files(log_dir) {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        match std::fs::read(&path) {
+            Ok(contents) => archive.add(
+                &format!("logs/{}", name),
+                redact_log(&String::from_utf8_lossy(&contents)).as_bytes(),
+            )?,
+            Err(e) => tracing::warn!("Skipping log file {}: {}", name, e),
+        }
+    }
+    archive.finish()
+}
+
+/// Collect the diagnostics summary
+pub fn summary(app: &AppHandle, log_dir: &Path) -> DiagnosticsSummary {
+    DiagnosticsSummary {
+        generated_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        device: device_info::collect(),
+        storage: storage_info::collect(app)
+            .map_err(|e| tracing::warn!("Storage information unavailable: {}", e))
+            .ok(),
+        log_level: log_level::current(),
+        log_storage: logging::storage_usage(log_dir),
+        breadcrumbs: breadcrumbs::trail(),
+    }
+}
+
+/// Names of the keychain entries present among `KEYCHAIN_ENTRIES`
+///
+/// Existence is checked like `keychain_exists`: values are never reported.
+fn keychain_stats(app: &AppHandle) -> KeychainStats {
+    let mut present = Vec::new();
+    for name in KEYCHAIN_ENTRIES {
+        match plugins::keychain_contains(app, name) {
+            Ok(true) => present.push(name.to_string()),
+            Ok(false) => {}
+            Err(e) => {
+                tracing::warn!("Keychain unavailable: {}", e);
+                break;
+            }
+        }
+    }
+    KeychainStats {
+        checked: KEYCHAIN_ENTRIES.len(),
+        present,
+    }
+}
+
+async fn connectivity_report(app: &AppHandle) -> ConnectivityReport {
+    let target = app.state::<ConfigStore>().connectivity_target();
+    let prober = app.state::<connectivity::ConnectivityProber>().get();
+    let (reachable, error) = match connectivity::check_connectivity_quick_with(prober.as_ref(), &target).await {
+        Ok(reachable) => (reachable, None),
+        Err(e) => (false, Some(e.to_string())),
+    };
+    ConnectivityReport {
+        reachable,
+        error,
+        network: network_info::collect()
+            .await
+            .map_err(|e| tracing::warn!("Network information unavailable: {}", e))
+            .ok(),
+    }
+}
+
+/// Collect the diagnostics report for the support form
+///
+/// Each part is collected independently: a failing part is reported as
+/// missing (`null`) and logged, never failing the whole report.
+pub async fn report(app: &AppHandle) -> AppResult<DiagnosticsReport> {
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve log directory: {}", e)))?;
+
+    Ok(DiagnosticsReport {
+        summary: summary(app, &log_dir),
+        permissions: permissions::status_all()
+            .map_err(|e| tracing::warn!("Permission statuses unavailable: {}", e))
+            .ok(),
+        connectivity: connectivity_report(app).await,
+        keychain: keychain_stats(app),
+        notifications: NotificationState {
+            permission_granted: notifications::check_permission()
+                .map_err(|e| tracing::warn!("Notification permission unavailable: {}", e))
+                .ok(),
+            channel: notifications::default_channel().map(str::to_string),
+        },
+        integrity: integrity::check(),
+        last_crash: app
+            .state::<CrashState>()
+            .dir()
+            .ok()
+            .and_then(|dir| crash::last_crash(&dir)),
+    })
+}
+
+/// Directory of the archives handed to the share sheet (`DIAGNOSTICS_EXPORT_DIR`)
+pub fn exports_dir(app: &AppHandle) -> AppResult<PathBuf> {
+    app.path()
+        .app_cache_dir()
+        .map(|dir| dir.join(constants::DIAGNOSTICS_EXPORT_DIR))
+        .map_err(|e| AppError::Storage(format!("Failed to resolve cache directory: {}", e)))
+}
+
+/// Delete the archives of previous exports
+pub fn cleanup_exports(app: &AppHandle) {
+    let Ok(dir) = exports_dir(app) else {
+        return;
+    };
+    if dir.exists() {
+        if let Err(e) = std::fs::remove_dir_all(&dir) {
+            tracing::warn!("Failed to delete exported logs: {}", e);
+        }
+    }
+}
+
+/// Export the logs and present the share sheet
+///
+/// # Returns
+///
+/// Returns the `ExportedLogs`, or an error if the archive cannot be
+/// written or sharing is unavailable.
+pub fn export_logs(app: &AppHandle) -> AppResult<ExportedLogs> {
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve log directory: {}", e)))?;
+    let summary = summary(app, &log_dir);
+    let archive = build_archive(&log_dir, &summary)?;
+
+    cleanup_exports(app);
+    let dir = exports_dir(app)?;
+    std::fs::create_dir_all(&dir).map_err(|e| AppError::Storage(format!("Failed to create export directory: {}", e)))?;
+    let file_name = format!("elulib-logs-{}.zip", summary.generated_at);
+    let path = dir.join(&file_name);
+    std::fs::write(&path, &archive).map_err(|e| AppError::Storage(format!("Failed to write exported logs: {}", e)))?;
+
+    if let Err(e) = share::share_file(&path, "application/zip", locale::strings().logs_share_subject) {
+        let _ = std::fs::remove_file(&path);
+        return Err(e);
+    }
+    tracing::info!("Exported logs ({} bytes)", archive.len());
+    Ok(ExportedLogs {
+        file_name,
+        bytes: archive.len() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_zip_layout() {
+        let mut archive = ZipArchive::default();
+        archive.add("a.txt", b"hello").unwrap();
+        archive.add("logs/b.log", b"").unwrap();
+        let zip = archive.finish().unwrap();
+
+        assert!(zip.starts_with(&[0x50, 0x4b, 0x03, 0x04]));
+        let end = &zip[zip.len() - 22..];
+        assert_eq!(&end[..4], &[0x50, 0x4b, 0x05, 0x06]);
+        assert_eq!(u16::from_le_bytes([end[10], end[11]]), 2, "Entry count");
+        let directory_offset = u32::from_le_bytes([end[16], end[17], end[18], end[19]]) as usize;
+        assert_eq!(&zip[directory_offset..directory_offset + 4], &[0x50, 0x4b, 0x01, 0x02]);
+        // First entry: 30-byte header, name, then the stored contents
+        assert_eq!(&zip[30 + 5..30 + 10], b"hello");
+    }
+
+    #[test]
+    fn test_build_archive_redacts_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.log"), "[1][app][INFO] login jane@example.com\n").unwrap();
+        std::fs::write(dir.path().join("app.log.1"), "[0][app][INFO] started\n").unwrap();
+
+        let summary = DiagnosticsSummary {
+            generated_at: 1,
+            device: device_info::collect(),
+            storage: None,
+            log_level: log_level::current(),
+            log_storage: logging::storage_usage(dir.path()),
+            breadcrumbs: Vec::new(),
+        };
+        let zip = build_archive(dir.path(), &summary).unwrap();
+        let text = String::from_utf8_lossy(&zip);
+        assert!(text.contains("logs/app.log.1"));
+        assert!(text.contains("summary.json"));
+        assert!(!text.contains("jane@example.com"));
+        assert!(text.contains("login [REDACTED]"));
+    }
+
+    #[test]
+    fn test_report_is_a_single_object() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = DiagnosticsReport {
+            summary: DiagnosticsSummary {
+                generated_at: 1,
+                device: device_info::collect(),
+                storage: None,
+                log_level: log_level::current(),
+                log_storage: logging::storage_usage(dir.path()),
+                breadcrumbs: Vec::new(),
+            },
+            permissions: None,
+            connectivity: ConnectivityReport {
+                reachable: false,
+                error: Some("Network error: timed out".to_string()),
+                network: None,
+            },
+            keychain: KeychainStats {
+                checked: KEYCHAIN_ENTRIES.len(),
+                present: vec![constants::DATABASE_KEY_NAME.to_string()],
+            },
+            notifications: NotificationState {
+                permission_granted: Some(true),
+                channel: None,
+            },
+            integrity: integrity::check(),
+            last_crash: None,
+        };
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["generatedAt"], 1, "Summary fields are at the top level");
+        assert_eq!(json["keychain"]["present"][0], constants::DATABASE_KEY_NAME);
+        assert_eq!(json["notifications"]["permissionGranted"], true);
+        assert_eq!(json["integrity"], serde_json::to_value(&report.integrity).unwrap());
+        assert!(json["integrity"]["compromised"].is_boolean());
+        assert!(json["lastCrash"].is_null());
+    }
+}
@@ -0,0 +1,703 @@
+/// Resumable download manager
+///
+/// Large files (council meeting packets of several hundred MB) are
+/// downloaded natively into the app file storage (`file_storage` module):
+/// - downloads are written to a temporary `<path>.partial` file and renamed
+///   once complete; a paused or interrupted download resumes from the size of
+///   the temporary file with an HTTP range request (`Range`, `If-Range` with
+///   the strong `ETag`, or else the `Last-Modified` date), and restarts from
+///   scratch if the server ignores the range
+/// - when a SHA-256 checksum is given, the file is verified before being
+///   renamed; a mismatching file is deleted and the download fails
+/// - at most `DOWNLOAD_MAX_CONCURRENT` downloads run at a time, the others
+///   wait queued
+/// - progress is emitted as `download://progress` events (at most every
+///   `DOWNLOAD_PROGRESS_INTERVAL_MS`), status changes as `download://state`
+///   events
+/// - the download list is persisted (`DOWNLOADS_FILE`); downloads running
+///   when the app was killed are queued again at the next launch
+///
+/// Only `https` URLs are accepted, and the destination counts towards the
+/// quota of its storage directory: the transfer is aborted as soon as it goes
+/// over the quota, even when the server does not announce the size.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ring::rand::SecureRandom;
+use tauri::{AppHandle, Manager, Url};
+
+use crate::constants;
+use crate::events;
+use crate::file_storage;
+use crate::http;
+use crate::{AppError, AppResult};
+
+/// Status of a download
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadStatus {
+    /// Waiting for a download slot
+    Queued,
+    /// Transferring
+    Running,
+    /// Paused by the user
+    Paused,
+    /// Downloaded and verified
+    Completed,
+    /// Failed (see `error`); can be resumed
+    Failed,
+}
+
+/// Download, as persisted and sent to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Download {
+    /// Download identifier
+    pub id: String,
+    /// URL of the file
+    pub url: String,
+    /// Destination, relative to the app file storage
+    pub path: String,
+    /// Expected SHA-256 of the file (lowercase hex), if known
+    pub sha256: Option<String>,
+    /// Size of the file, once known
+    pub total_bytes: Option<u64>,
+    /// Bytes downloaded so far
+    pub downloaded_bytes: u64,
+    /// Status of the download
+    pub status: DownloadStatus,
+    /// Error of a failed download
+    pub error: Option<String>,
+    /// Entity tag of the file, to resume only if it did not change
+    pub etag: Option<String>,
+    /// Last modification date of the file (`Last-Modified`), to resume only
+    /// if it did not change when there is no strong entity tag
+    pub last_modified: Option<String>,
+    /// Time the download was added (seconds since the Unix epoch)
+    pub created_at: u64,
+}
+
+/// Payload of the `download://progress` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress<'a> {
+    /// Download identifier
+    pub id: &'a str,
+    /// Bytes downloaded so far
+    pub downloaded_bytes: u64,
+    /// Size of the file, if known
+    pub total_bytes: Option<u64>,
+}
+
+/// How a transfer ended, other than with an error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransferEnd {
+    Completed,
+    Paused,
+}
+
+/// Downloads, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct DownloadManager {
+    downloads: Mutex<BTreeMap<String, Download>>,
+    /// Pause flags of the running downloads
+    running: Mutex<HashMap<String, Arc<AtomicBool>>>,
+    /// Persisted download list
+    file: Mutex<Option<PathBuf>>,
+}
+
+impl DownloadManager {
+    /// Load the download list persisted in `file`, queuing the downloads that
+    /// were running
+    pub fn load(&self, file: PathBuf) {
+        let downloads: BTreeMap<String, Download> = std::fs::read_to_string(&file)
+            .ok()
+            .and_then(|contents| {
+                serde_json::from_str(&contents)
+                    .map_err(|e| tracing::warn!("Ignoring invalid download list: {}", e))
+                    .ok()
+            })
+            .unwrap_or_default();
+        if let Ok(mut current) = self.downloads.lock() {
+            *current = downloads
+                .into_iter()
+                .map(|(id, mut download)| {
+                    if download.status == DownloadStatus::Running {
+                        download.status = DownloadStatus::Queued;
+                    }
+                    (id, download)
+                })
+                .collect();
+        }
+        if let Ok(mut current) = self.file.lock() {
+            *current = Some(file);
+        }
+    }
+
+    /// Persist the download list
+    pub fn persist(&self) -> AppResult<()> {
+        let Some(file) = self.file.lock().map_err(|e| AppError::Network(e.to_string()))?.clone() else {
+            return Ok(());
+        };
+        let contents = {
+            let downloads = self.downloads.lock().map_err(|e| AppError::Network(e.to_string()))?;
+            serde_json::to_vec(&*downloads).map_err(|e| AppError::Network(format!("Failed to serialize download list: {}", e)))?
+        };
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AppError::Network(format!("Failed to create data directory: {}", e)))?;
+        }
+        std::fs::write(&file, contents).map_err(|e| AppError::Network(format!("Failed to write download list: {}", e)))
+    }
+
+    /// All downloads, oldest first
+    pub fn list(&self) -> Vec<Download> {
+        let mut downloads: Vec<Download> = self
+            .downloads
+            .lock()
+            .map(|downloads| downloads.values().cloned().collect())
+            .unwrap_or_default();
+        downloads.sort_by_key(|download| download.created_at);
+        downloads
+    }
+
+    /// A download
+    pub fn get(&self, id: &str) -> Option<Download> {
+        self.downloads.lock().ok()?.get(id).cloned()
+    }
+
+    /// Add a download
+    pub fn add(&self, download: Download) -> AppResult<()> {
+        let mut downloads = self.downloads.lock().map_err(|e| AppError::Network(e.to_string()))?;
+        let active = downloads.values().any(|existing| {
+            existing.path == download.path && existing.status != DownloadStatus::Completed
+        });
+        if active {
+            return Err(AppError::Network(format!("A download to {} is already in progress", download.path)));
+        }
+        downloads.insert(download.id.clone(), download);
+        Ok(())
+    }
+
+    /// Update a download
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated download, or `None` if it no longer exists.
+    pub fn update(&self, id: &str, f: impl FnOnce(&mut Download)) -> Option<Download> {
+        let mut downloads = self.downloads.lock().ok()?;
+        let download = downloads.get_mut(id)?;
+        f(download);
+        Some(download.clone())
+    }
+
+    /// Remove a download, pausing it if running
+    pub fn remove(&self, id: &str) -> Option<Download> {
+        self.pause_running(id);
+        self.downloads.lock().ok()?.remove(id)
+    }
+
+    /// Remove all downloads, pausing the running ones
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of removed downloads.
+    pub fn clear(&self) -> usize {
+        if let Ok(running) = self.running.lock() {
+            for flag in running.values() {
+                flag.store(true, Ordering::SeqCst);
+            }
+        }
+        self.downloads
+            .lock()
+            .map(|mut downloads| std::mem::take(&mut *downloads).len())
+            .unwrap_or(0)
+    }
+
+    /// Ask a running download to pause
+    ///
+    /// # Returns
+    ///
+    /// Returns `false` if the download is not running.
+    pub fn pause_running(&self, id: &str) -> bool {
+        match self.running.lock().ok().and_then(|running| running.get(id).cloned()) {
+            Some(flag) => {
+                flag.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Mark queued downloads as running, oldest first, within
+    /// `DOWNLOAD_MAX_CONCURRENT`
+    ///
+    /// # Returns
+    ///
+    /// Returns the downloads to start, with their pause flag.
+    pub fn claim_slots(&self) -> Vec<(Download, Arc<AtomicBool>)> {
+        let (Ok(mut downloads), Ok(mut running)) = (self.downloads.lock(), self.running.lock()) else {
+            return Vec::new();
+        };
+        let mut queued: Vec<&mut Download> = downloads
+            .values_mut()
+            .filter(|download| download.status == DownloadStatus::Queued)
+            .collect();
+        queued.sort_by_key(|download| download.created_at);
+
+        let free = constants::DOWNLOAD_MAX_CONCURRENT.saturating_sub(running.len());
+        queued
+            .into_iter()
+            .take(free)
+            .map(|download| {
+                download.status = DownloadStatus::Running;
+                download.error = None;
+                let flag = Arc::new(AtomicBool::new(false));
+                running.insert(download.id.clone(), flag.clone());
+                (download.clone(), flag)
+            })
+            .collect()
+    }
+
+    /// Release the slot of a download that stopped running
+    fn release(&self, id: &str) {
+        if let Ok(mut running) = self.running.lock() {
+            running.remove(id);
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn random_id() -> AppResult<String> {
+    let mut bytes = [0u8; 16];
+    ring::rand::SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| AppError::Network("Failed to generate random value".to_string()))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Validate an expected SHA-256 checksum
+///
+/// # Returns
+///
+/// Returns the checksum in lowercase hex, or an error if it is not
+/// 64 hex characters.
+pub fn validate_sha256(sha256: &str) -> AppResult<String> {
+    let sha256 = sha256.trim().to_ascii_lowercase();
+    if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(AppError::InvalidArgument("SHA-256 checksum must be 64 hex characters".to_string()));
+    }
+    Ok(sha256)
+}
+
+/// Hash the contents of an existing temporary file, to resume the checksum
+fn hash_file(path: &Path, context: &mut ring::digest::Context) -> AppResult<u64> {
+    let mut file = std::fs::File::open(path).map_err(|e| AppError::Network(format!("Failed to read partial download: {}", e)))?;
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut total = 0;
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| AppError::Network(format!("Failed to read partial download: {}", e)))?;
+        if read == 0 {
+            return Ok(total);
+        }
+        context.update(&buffer[..read]);
+        total += read as u64;
+    }
+}
+
+/// Validator sent in `If-Range` when resuming a download
+///
+/// Weak entity tags cannot be used for ranges, so the `Last-Modified` date is
+/// used instead.
+fn if_range(download: &Download) -> Option<&str> {
+    download
+        .etag
+        .as_deref()
+        .filter(|etag| !etag.starts_with("W/"))
+        .or(download.last_modified.as_deref())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn emit_state(app: &AppHandle, download: &Download) {
+    events::emit(app, download);
+}
+
+fn persist(app: &AppHandle) {
+    if let Err(e) = app.state::<DownloadManager>().persist() {
+        tracing::warn!("{}", e);
+    }
+}
+
+/// Transfer a download into its temporary file, then verify and rename it
+async fn transfer(app: &AppHandle, download: &Download, pause: &AtomicBool) -> AppResult<TransferEnd> {
+    let manager = app.state::<DownloadManager>();
+    let root = file_storage::root(app)?;
+    let target = file_storage::prepare_write(&root, &download.path, download.total_bytes.unwrap_or(0))?;
+    let partial = file_storage::temp_path(&target);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| AppError::Network(format!("Failed to create directory: {}", e)))?;
+    }
+
+    let (mut offset, mut context) = if partial.exists() {
+        let partial = partial.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+            hash_file(&partial, &mut context).map(|offset| (offset, context))
+        })
+        .await
+        .map_err(|e| AppError::Network(format!("Failed to read partial download: {}", e)))??
+    } else {
+        (0, ring::digest::Context::new(&ring::digest::SHA256))
+    };
+
+    let mut request = http::download_client()?.get(&download.url);
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+        if let Some(validator) = if_range(download) {
+            request = request.header(reqwest::header::IF_RANGE, validator);
+        }
+    }
+    let mut response = request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| AppError::Network(format!("Download failed: {}", e)))?;
+
+    let resumed = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    if offset > 0 && !resumed {
+        tracing::info!("Server ignored the range of download {}, restarting", download.id);
+        offset = 0;
+        context = ring::digest::Context::new(&ring::digest::SHA256);
+    }
+    let total_bytes = response.content_length().map(|length| offset + length);
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+    if let Some(total_bytes) = total_bytes {
+        file_storage::prepare_write(&root, &download.path, total_bytes)?;
+    }
+    // Checked while streaming too, since the size may not be announced
+    let available = file_storage::available(&root, &download.path)?;
+    manager.update(&download.id, |download| {
+        download.total_bytes = total_bytes;
+        download.downloaded_bytes = offset;
+        download.etag = etag.or(download.etag.take());
+        download.last_modified = last_modified.or(download.last_modified.take());
+    });
+    persist(app);
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&partial)
+        .map_err(|e| AppError::Network(format!("Failed to open partial download: {}", e)))?;
+    let mut downloaded = offset;
+    let mut last_progress = Instant::now();
+    let progress_interval = Duration::from_millis(constants::DOWNLOAD_PROGRESS_INTERVAL_MS);
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| AppError::Network(format!("Download interrupted: {}", e)))?
+    {
+        file.write_all(&chunk)
+            .map_err(|e| AppError::Network(format!("Failed to write partial download: {}", e)))?;
+        context.update(&chunk);
+        downloaded += chunk.len() as u64;
+        if downloaded > available {
+            drop(file);
+            let _ = std::fs::remove_file(&partial);
+            return Err(AppError::Network(format!(
+                "Download exceeds the storage quota ({} bytes available)",
+                available
+            )));
+        }
+
+        if last_progress.elapsed() >= progress_interval {
+            last_progress = Instant::now();
+            manager.update(&download.id, |download| download.downloaded_bytes = downloaded);
+            let progress = DownloadProgress {
+                id: &download.id,
+                downloaded_bytes: downloaded,
+                total_bytes,
+            };
+            events::emit(app, &progress);
+        }
+        if pause.load(Ordering::SeqCst) {
+            file.flush().map_err(|e| AppError::Network(format!("Failed to write partial download: {}", e)))?;
+            manager.update(&download.id, |download| download.downloaded_bytes = downloaded);
+            return Ok(TransferEnd::Paused);
+        }
+    }
+    file.flush().map_err(|e| AppError::Network(format!("Failed to write partial download: {}", e)))?;
+    drop(file);
+    manager.update(&download.id, |download| download.downloaded_bytes = downloaded);
+
+    if let Some(expected) = &download.sha256 {
+        let actual = hex(context.finish().as_ref());
+        if &actual != expected {
+            let _ = std::fs::remove_file(&partial);
+            return Err(AppError::Network(format!("Checksum mismatch: expected {}, got {}", expected, actual)));
+        }
+    }
+    std::fs::rename(&partial, &target).map_err(|e| AppError::Network(format!("Failed to move download: {}", e)))?;
+    Ok(TransferEnd::Completed)
+}
+
+/// Start the queued downloads for which a slot is free
+pub fn schedule(app: &AppHandle) {
+    let manager = app.state::<DownloadManager>();
+    for (download, pause) in manager.claim_slots() {
+        emit_state(app, &download);
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = transfer(&app, &download, &pause).await;
+            let manager = app.state::<DownloadManager>();
+            manager.release(&download.id);
+            let updated = manager.update(&download.id, |download| match result {
+                Ok(TransferEnd::Completed) => {
+                    tracing::info!("Download {} completed", download.id);
+                    download.status = DownloadStatus::Completed;
+                }
+                Ok(TransferEnd::Paused) => download.status = DownloadStatus::Paused,
+                Err(e) => {
+                    tracing::warn!("Download {} failed: {}", download.id, e);
+                    download.status = DownloadStatus::Failed;
+                    download.error = Some(e.to_string());
+                }
+            });
+            // Removed (cancelled) while running
+            if let Some(updated) = updated {
+                emit_state(&app, &updated);
+            }
+            persist(&app);
+            schedule(&app);
+        });
+    }
+}
+
+/// Add a download
+///
+/// # Arguments
+///
+/// * `url` - `https` URL of the file
+/// * `path` - Destination, relative to the app file storage
+/// * `sha256` - Expected SHA-256 of the file (hex), if known
+///
+/// # Returns
+///
+/// Returns the queued download, or an error if the arguments are
+/// invalid or a download to the same path is in progress.
+pub fn start(app: &AppHandle, url: &str, path: &str, sha256: Option<&str>) -> AppResult<Download> {
+    let parsed = Url::parse(url).map_err(|e| AppError::InvalidArgument(format!("Invalid download URL: {}", e)))?;
+    if parsed.scheme() != "https" {
+        return Err(AppError::InvalidArgument("Download URL must be https".to_string()));
+    }
+    file_storage::prepare_write(&file_storage::root(app)?, path, 0)?;
+    let download = Download {
+        id: random_id()?,
+        url: parsed.to_string(),
+        path: path.to_string(),
+        sha256: sha256.map(validate_sha256).transpose()?,
+        total_bytes: None,
+        downloaded_bytes: 0,
+        status: DownloadStatus::Queued,
+        error: None,
+        etag: None,
+        last_modified: None,
+        created_at: now_secs(),
+    };
+    let manager = app.state::<DownloadManager>();
+    manager.add(download.clone())?;
+    persist(app);
+    emit_state(app, &download);
+    schedule(app);
+    Ok(manager.get(&download.id).unwrap_or(download))
+}
+
+/// Pause a queued or running download
+pub fn pause(app: &AppHandle, id: &str) -> AppResult<()> {
+    let manager = app.state::<DownloadManager>();
+    if manager.pause_running(id) {
+        // The transfer stops at the next chunk and reports the pause
+        return Ok(());
+    }
+    let download = manager
+        .update(id, |download| {
+            if download.status == DownloadStatus::Queued {
+                download.status = DownloadStatus::Paused;
+            }
+        })
+        .ok_or_else(|| AppError::InvalidArgument(format!("Unknown download: {}", id)))?;
+    emit_state(app, &download);
+    persist(app);
+    Ok(())
+}
+
+/// Resume a paused or failed download
+pub fn resume(app: &AppHandle, id: &str) -> AppResult<()> {
+    let manager = app.state::<DownloadManager>();
+    let download = manager
+        .update(id, |download| {
+            if matches!(download.status, DownloadStatus::Paused | DownloadStatus::Failed) {
+                download.status = DownloadStatus::Queued;
+            }
+        })
+        .ok_or_else(|| AppError::InvalidArgument(format!("Unknown download: {}", id)))?;
+    emit_state(app, &download);
+    persist(app);
+    schedule(app);
+    Ok(())
+}
+
+/// Cancel a download, deleting its partial file, or forget a completed one
+/// (the downloaded file is kept)
+pub fn cancel(app: &AppHandle, id: &str) -> AppResult<()> {
+    let download = app
+        .state::<DownloadManager>()
+        .remove(id)
+        .ok_or_else(|| AppError::InvalidArgument(format!("Unknown download: {}", id)))?;
+    if download.status != DownloadStatus::Completed {
+        let target = file_storage::resolve(&file_storage::root(app)?, &download.path)?;
+        let _ = std::fs::remove_file(file_storage::temp_path(&target));
+    }
+    persist(app);
+    schedule(app);
+    Ok(())
+}
+
+/// Restore the persisted downloads and resume the interrupted ones
+pub fn init(app: &AppHandle) -> AppResult<()> {
+    let file = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Network(format!("Failed to resolve data directory: {}", e)))?
+        .join(constants::DOWNLOADS_FILE);
+    app.state::<DownloadManager>().load(file);
+    schedule(app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn download(id: &str, created_at: u64, status: DownloadStatus) -> Download {
+        Download {
+            id: id.to_string(),
+            url: format!("https://app.elulib.com/packets/{}.pdf", id),
+            path: format!("packets/{}.pdf", id),
+            sha256: None,
+            total_bytes: None,
+            downloaded_bytes: 0,
+            status,
+            error: None,
+            etag: None,
+            last_modified: None,
+            created_at,
+        }
+    }
+
+    #[test]
+    fn test_claim_slots_respects_concurrency_and_order() {
+        let manager = DownloadManager::default();
+        for i in 0..constants::DOWNLOAD_MAX_CONCURRENT as u64 + 2 {
+            manager.add(download(&format!("d{}", i), 100 - i, DownloadStatus::Queued)).unwrap();
+        }
+        manager.add(download("paused", 0, DownloadStatus::Paused)).unwrap();
+
+        let claimed = manager.claim_slots();
+        assert_eq!(claimed.len(), constants::DOWNLOAD_MAX_CONCURRENT);
+        assert!(claimed.iter().all(|(download, _)| download.status == DownloadStatus::Running));
+        let newest = format!("d{}", constants::DOWNLOAD_MAX_CONCURRENT + 1);
+        assert_eq!(claimed[0].0.id, newest, "Oldest queued download first");
+        assert!(manager.claim_slots().is_empty(), "No free slot");
+
+        assert!(manager.pause_running(&newest));
+        assert!(claimed[0].1.load(Ordering::SeqCst));
+        manager.release(&newest);
+        assert_eq!(manager.claim_slots().len(), 1);
+    }
+
+    #[test]
+    fn test_duplicate_destination_is_rejected() {
+        let manager = DownloadManager::default();
+        manager.add(download("a", 1, DownloadStatus::Paused)).unwrap();
+        let mut same_path = download("b", 2, DownloadStatus::Queued);
+        same_path.path = "packets/a.pdf".to_string();
+        assert!(manager.add(same_path.clone()).is_err());
+
+        manager.update("a", |download| download.status = DownloadStatus::Completed);
+        assert!(manager.add(same_path).is_ok(), "Completed downloads can be replaced");
+    }
+
+    #[test]
+    fn test_running_downloads_are_queued_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(constants::DOWNLOADS_FILE);
+        let manager = DownloadManager::default();
+        manager.load(file.clone());
+        manager.add(download("running", 1, DownloadStatus::Queued)).unwrap();
+        manager.add(download("paused", 2, DownloadStatus::Paused)).unwrap();
+        manager.claim_slots();
+        manager.persist().unwrap();
+
+        let restarted = DownloadManager::default();
+        restarted.load(file);
+        let statuses: Vec<DownloadStatus> = restarted.list().iter().map(|download| download.status).collect();
+        assert_eq!(statuses, [DownloadStatus::Queued, DownloadStatus::Paused]);
+    }
+
+    #[test]
+    fn test_sha256_validation_and_resumed_hash() {
+        assert!(validate_sha256("abc").is_err());
+        assert!(validate_sha256(&"g".repeat(64)).is_err());
+        assert_eq!(validate_sha256(&"AB".repeat(32)).unwrap(), "ab".repeat(32));
+
+        // Hashing the partial file then the rest gives the hash of the whole file
+        let dir = tempfile::tempdir().unwrap();
+        let partial = dir.path().join("packet.pdf.partial");
+        std::fs::write(&partial, b"first half, ").unwrap();
+        let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+        assert_eq!(hash_file(&partial, &mut context).unwrap(), 12);
+        context.update(b"second half");
+        assert_eq!(
+            hex(context.finish().as_ref()),
+            hex(ring::digest::digest(&ring::digest::SHA256, b"first half, second half").as_ref())
+        );
+    }
+
+    #[test]
+    fn test_if_range_prefers_a_strong_etag() {
+        let mut download = download("a", 1, DownloadStatus::Paused);
+        assert_eq!(if_range(&download), None);
+        download.last_modified = Some("Tue, 06 Oct 2026 08:00:00 GMT".to_string());
+        download.etag = Some("W/\"v1\"".to_string());
+        assert_eq!(if_range(&download), Some("Tue, 06 Oct 2026 08:00:00 GMT"));
+        download.etag = Some("\"v1\"".to_string());
+        assert_eq!(if_range(&download), Some("\"v1\""));
+    }
+}
@@ -0,0 +1,234 @@
+/// Form draft autosave store
+///
+/// The webview autosaves the forms being typed (amendments, comments) so
+/// they survive the OS killing the app in background. Drafts are stored in
+/// the encrypted database (`drafts` table), keyed by a form identifier chosen
+/// by the frontend:
+/// - the payload is any JSON value, at most `DRAFT_MAX_PAYLOAD_BYTES` once
+///   serialized
+/// - saving a draft replaces the previous one of the same form
+/// - drafts not saved for `DRAFT_MAX_AGE_SECS` are purged, and only the
+///   `DRAFT_MAX_ENTRIES` most recent drafts are kept
+///
+/// The frontend deletes the draft once the form is submitted or discarded.
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::database::Database;
+use crate::{AppError, AppResult};
+
+/// Stored draft of a form
+#[derive(Debug, Clone, PartialEq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Draft {
+    /// Form identifier
+    pub form_id: String,
+    /// Draft contents, as saved by the frontend
+    pub payload: Value,
+    /// When the draft was last saved (seconds since the Unix epoch)
+    pub updated_at: u64,
+}
+
+/// Validate a form identifier
+pub fn validate_form_id(form_id: &str) -> AppResult<()> {
+    let valid = !form_id.is_empty()
+        && form_id.len() <= constants::DRAFT_MAX_FORM_ID_LENGTH
+        && form_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | ':' | '/'));
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::InvalidArgument(format!(
+            "Form identifiers must be 1 to {} characters among letters, digits, '.', '_', '-', ':' and '/'",
+            constants::DRAFT_MAX_FORM_ID_LENGTH
+        )))
+    }
+}
+
+/// Save the draft of a form, replacing the previous one
+///
+/// Expired drafts are purged, and the oldest drafts are dropped beyond
+/// `DRAFT_MAX_ENTRIES`.
+pub fn save(conn: &Connection, form_id: &str, payload: &Value, now: u64) -> AppResult<Draft> {
+    validate_form_id(form_id)?;
+    let contents = payload.to_string();
+    if contents.len() > constants::DRAFT_MAX_PAYLOAD_BYTES {
+        return Err(AppError::InvalidArgument(format!(
+            "Drafts must be at most {} bytes",
+            constants::DRAFT_MAX_PAYLOAD_BYTES
+        )));
+    }
+
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| AppError::Database(format!("Failed to start draft transaction: {}", e)))?;
+    tx.execute(
+        "INSERT INTO drafts (form_id, payload, updated_at) VALUES (?, ?, ?)
+         ON CONFLICT (form_id) DO UPDATE SET payload = excluded.payload, updated_at = excluded.updated_at",
+        params![form_id, contents, now as i64],
+    )
+    .map_err(|e| AppError::Database(format!("Failed to save draft: {}", e)))?;
+    purge(&tx, now)?;
+    tx.commit().map_err(|e| AppError::Database(format!("Failed to save draft: {}", e)))?;
+
+    Ok(Draft {
+        form_id: form_id.to_string(),
+        payload: payload.clone(),
+        updated_at: now,
+    })
+}
+
+/// Draft of a form, unless it was never saved or has expired
+pub fn load(conn: &Connection, form_id: &str, now: u64) -> AppResult<Option<Draft>> {
+    validate_form_id(form_id)?;
+    let row = conn
+        .query_row(
+            "SELECT payload, updated_at FROM drafts WHERE form_id = ? AND updated_at >= ?",
+            params![form_id, expiry_cutoff(now)],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+        )
+        .optional()
+        .map_err(|e| AppError::Database(format!("Failed to load draft: {}", e)))?;
+
+    row.map(|(contents, updated_at)| {
+        let payload = serde_json::from_str(&contents).map_err(|e| AppError::Database(format!("Invalid stored draft: {}", e)))?;
+        Ok(Draft {
+            form_id: form_id.to_string(),
+            payload,
+            updated_at: updated_at.max(0) as u64,
+        })
+    })
+    .transpose()
+}
+
+/// Delete the draft of a form
+///
+/// # Returns
+///
+/// Returns `true` if the form had a draft.
+pub fn delete(conn: &Connection, form_id: &str) -> AppResult<bool> {
+    validate_form_id(form_id)?;
+    conn.execute("DELETE FROM drafts WHERE form_id = ?", params![form_id])
+        .map(|deleted| deleted > 0)
+        .map_err(|e| AppError::Database(format!("Failed to delete draft: {}", e)))
+}
+
+/// Delete the expired drafts, and the oldest ones beyond `DRAFT_MAX_ENTRIES`
+///
+/// # Returns
+///
+/// Returns the number of deleted drafts.
+pub fn purge(conn: &Connection, now: u64) -> AppResult<usize> {
+    let expired = conn
+        .execute("DELETE FROM drafts WHERE updated_at < ?", params![expiry_cutoff(now)])
+        .map_err(|e| AppError::Database(format!("Failed to purge drafts: {}", e)))?;
+    let evicted = conn
+        .execute(
+            "DELETE FROM drafts WHERE form_id NOT IN
+                (SELECT form_id FROM drafts ORDER BY updated_at DESC, form_id LIMIT ?)",
+            params![constants::DRAFT_MAX_ENTRIES as i64],
+        )
+        .map_err(|e| AppError::Database(format!("Failed to purge drafts: {}", e)))?;
+    Ok(expired + evicted)
+}
+
+fn expiry_cutoff(now: u64) -> i64 {
+    now.saturating_sub(constants::DRAFT_MAX_AGE_SECS) as i64
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Save the draft of a form in the app database
+pub fn save_draft(app: &AppHandle, form_id: &str, payload: &Value) -> AppResult<Draft> {
+    app.state::<Database>()
+        .with_connection(app, |conn| save(conn, form_id, payload, now_secs()))
+}
+
+/// Draft of a form from the app database
+pub fn load_draft(app: &AppHandle, form_id: &str) -> AppResult<Option<Draft>> {
+    app.state::<Database>()
+        .with_connection(app, |conn| load(conn, form_id, now_secs()))
+}
+
+/// Delete the draft of a form from the app database
+pub fn delete_draft(app: &AppHandle, form_id: &str) -> AppResult<bool> {
+    app.state::<Database>()
+        .with_connection(app, |conn| delete(conn, form_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{migrate, open, MIGRATIONS};
+    use serde_json::json;
+
+    const KEY: &str = "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff";
+
+    fn test_db(dir: &tempfile::TempDir) -> Connection {
+        let mut conn = open(&dir.path().join("test.db"), KEY).unwrap();
+        migrate(&mut conn, MIGRATIONS).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_draft_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = test_db(&dir);
+        let now = 1_700_000_000;
+
+        assert_eq!(load(&conn, "amendment:42", now).unwrap(), None);
+        save(&conn, "amendment:42", &json!({ "text": "First" }), now).unwrap();
+        save(&conn, "amendment:42", &json!({ "text": "Second", "article": 3 }), now + 5).unwrap();
+
+        let draft = load(&conn, "amendment:42", now + 10).unwrap().unwrap();
+        assert_eq!(draft.payload, json!({ "text": "Second", "article": 3 }));
+        assert_eq!(draft.updated_at, now + 5);
+
+        assert_eq!(delete(&conn, "amendment:42").unwrap(), true);
+        assert_eq!(delete(&conn, "amendment:42").unwrap(), false);
+        assert_eq!(load(&conn, "amendment:42", now + 10).unwrap(), None);
+    }
+
+    #[test]
+    fn test_drafts_expire_and_are_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = test_db(&dir);
+        let now = 1_700_000_000;
+
+        save(&conn, "old", &json!("x"), now).unwrap();
+        let later = now + constants::DRAFT_MAX_AGE_SECS + 1;
+        assert_eq!(load(&conn, "old", later).unwrap(), None);
+
+        for index in 0..constants::DRAFT_MAX_ENTRIES {
+            save(&conn, &format!("form-{}", index), &json!(index), later + index as u64).unwrap();
+        }
+        let count: i64 = conn.query_row("SELECT count(*) FROM drafts", [], |row| row.get(0)).unwrap();
+        assert_eq!(count as usize, constants::DRAFT_MAX_ENTRIES, "Expired draft purged on save");
+
+        save(&conn, "newest", &json!(true), later + 1_000).unwrap();
+        assert_eq!(load(&conn, "form-0", later + 1_000).unwrap(), None, "Oldest draft evicted");
+        assert!(load(&conn, "newest", later + 1_000).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_invalid_drafts_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = test_db(&dir);
+
+        assert!(save(&conn, "", &json!({}), 1).is_err());
+        assert!(save(&conn, "form id", &json!({}), 1).is_err());
+        assert!(save(&conn, &"a".repeat(constants::DRAFT_MAX_FORM_ID_LENGTH + 1), &json!({}), 1).is_err());
+        let large = json!("x".repeat(constants::DRAFT_MAX_PAYLOAD_BYTES));
+        assert!(save(&conn, "amendment:42", &large, 1).is_err());
+        assert!(save(&conn, "amendment:42", &json!({ "text": "ok" }), 1).is_ok());
+    }
+}
@@ -0,0 +1,217 @@
+/// Backend environment profiles
+///
+/// The app targets one of three backends, each with its own application URL,
+/// connectivity host and push sender:
+/// - `prod`: the default
+/// - `preprod`: selected at build time with the `env-preprod` cargo feature
+/// - `staging`: selected at build time with the `env-staging` cargo feature
+///
+/// In debug builds, QA can switch environment without a rebuild with the
+/// `environment_set` command: the choice is stored in the app config
+/// directory (`ENVIRONMENT_OVERRIDE_FILE`) and applied at the next launch.
+/// Release builds ignore that file. A runtime configuration file still takes
+/// precedence over the profile (see `runtime_config`).
+
+use std::path::Path;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::runtime_config;
+use crate::{AppError, AppResult};
+
+#[cfg(all(feature = "env-preprod", feature = "env-staging"))]
+compile_error!("The env-preprod and env-staging features are mutually exclusive");
+
+/// Backend environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    /// Production
+    Prod,
+    /// Pre-production, mirroring production data
+    Preprod,
+    /// Staging, for QA of unreleased features
+    Staging,
+}
+
+/// Endpoints of an environment
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentProfile {
+    /// Environment
+    pub environment: Environment,
+    /// URL of the web application
+    pub app_url: &'static str,
+    /// Host name targeted by connectivity checks
+    pub connectivity_host: &'static str,
+    /// Push sender (Firebase project) identifier
+    pub push_sender_id: &'static str,
+}
+
+impl Environment {
+    /// All environments
+    pub const ALL: [Environment; 3] = [Environment::Prod, Environment::Preprod, Environment::Staging];
+
+    /// Environment selected by the cargo features
+    pub const fn build() -> Self {
+        if cfg!(feature = "env-staging") {
+            Environment::Staging
+        } else if cfg!(feature = "env-preprod") {
+            Environment::Preprod
+        } else {
+            Environment::Prod
+        }
+    }
+
+    /// Name of the environment, as accepted by `parse`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Environment::Prod => "prod",
+            Environment::Preprod => "preprod",
+            Environment::Staging => "staging",
+        }
+    }
+
+    /// Parse an environment name
+    pub fn parse(name: &str) -> AppResult<Self> {
+        Environment::ALL
+            .into_iter()
+            .find(|environment| environment.as_str() == name.trim())
+            .ok_or_else(|| AppError::InvalidArgument(format!("Unknown environment {:?}, expected prod, preprod or staging", name)))
+    }
+
+    /// Endpoints of the environment
+    pub fn profile(self) -> EnvironmentProfile {
+        let (app_url, connectivity_host, push_sender_id) = match self {
+            Environment::Prod => (
+                constants::APP_URL,
+                constants::CONNECTIVITY_HOST,
+                constants::PUSH_SENDER_ID,
+            ),
+            Environment::Preprod => (
+                constants::PREPROD_APP_URL,
+                constants::PREPROD_CONNECTIVITY_HOST,
+                constants::PREPROD_PUSH_SENDER_ID,
+            ),
+            Environment::Staging => (
+                constants::STAGING_APP_URL,
+                constants::STAGING_CONNECTIVITY_HOST,
+                constants::STAGING_PUSH_SENDER_ID,
+            ),
+        };
+        EnvironmentProfile {
+            environment: self,
+            app_url,
+            connectivity_host,
+            push_sender_id,
+        }
+    }
+}
+
+/// Environment stored by `environment_set`, if any
+///
+/// Always `None` in release builds. An invalid file is logged and ignored.
+pub fn read_override(config_dir: &Path) -> Option<Environment> {
+    if !cfg!(debug_assertions) {
+        return None;
+    }
+    let contents = std::fs::read_to_string(config_dir.join(constants::ENVIRONMENT_OVERRIDE_FILE)).ok()?;
+    Environment::parse(&contents)
+        .map_err(|e| tracing::warn!("Ignoring environment override: {}", e))
+        .ok()
+}
+
+/// Store the environment to use from the next launch (debug builds only)
+///
+/// Selecting the build environment removes the override.
+pub fn write_override(config_dir: &Path, environment: Environment) -> AppResult<()> {
+    let path = config_dir.join(constants::ENVIRONMENT_OVERRIDE_FILE);
+    if environment == Environment::build() {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(AppError::Config(format!("Failed to remove environment override: {}", e)))
+            }
+            _ => Ok(()),
+        };
+    }
+    std::fs::create_dir_all(config_dir).map_err(|e| AppError::Config(format!("Failed to create config directory: {}", e)))?;
+    std::fs::write(&path, environment.as_str()).map_err(|e| AppError::Config(format!("Failed to store environment override: {}", e)))
+}
+
+/// Environment status, returned by `environment_get`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentStatus {
+    /// Environment in use
+    pub environment: Environment,
+    /// Environment selected at build time
+    pub build_environment: Environment,
+    /// Environment applied at the next launch, if different
+    pub pending: Option<Environment>,
+    /// Effective application URL (a runtime config file may override it)
+    pub app_url: String,
+    /// Effective connectivity host
+    pub connectivity_host: String,
+    /// Push sender identifier
+    pub push_sender_id: String,
+}
+
+/// Current environment and effective endpoints
+pub fn status(app: &AppHandle) -> EnvironmentStatus {
+    let config = runtime_config::get();
+    let next = app
+        .path()
+        .app_config_dir()
+        .ok()
+        .and_then(|dir| read_override(&dir))
+        .unwrap_or(Environment::build());
+    EnvironmentStatus {
+        environment: config.environment,
+        build_environment: Environment::build(),
+        pending: (next != config.environment).then_some(next),
+        app_url: config.app_url.clone(),
+        connectivity_host: config.connectivity_host.clone(),
+        push_sender_id: config.push_sender_id.clone(),
+    }
+}
+
+/// Select the environment of the next launch (debug builds only)
+pub fn select(app: &AppHandle, environment: Environment) -> AppResult<EnvironmentStatus> {
+    let config_dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| AppError::Config(format!("Failed to resolve config directory: {}", e)))?;
+    write_override(&config_dir, environment)?;
+    tracing::info!("Environment {} selected for the next launch", environment.as_str());
+    Ok(status(app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environment_names() {
+        for environment in Environment::ALL {
+            assert_eq!(Environment::parse(environment.as_str()).unwrap(), environment);
+            assert_eq!(environment.profile().environment, environment);
+        }
+        assert_eq!(Environment::parse("staging\n").unwrap(), Environment::Staging);
+        assert!(Environment::parse("production").is_err());
+        assert_eq!(Environment::Prod.profile().app_url, constants::APP_URL);
+    }
+
+    #[test]
+    fn test_override_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(read_override(dir.path()), None);
+
+        write_override(dir.path(), Environment::Staging).unwrap();
+        assert_eq!(read_override(dir.path()), Some(Environment::Staging));
+
+        write_override(dir.path(), Environment::build()).unwrap();
+        assert_eq!(read_override(dir.path()), None);
+        assert!(write_override(dir.path(), Environment::build()).is_ok(), "Removing twice is not an error");
+    }
+}
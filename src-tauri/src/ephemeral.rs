@@ -0,0 +1,151 @@
+/// Session-scoped ephemeral secrets store
+///
+/// Some secrets are needed briefly by the frontend but must never be
+/// persisted, e.g. decrypted document keys. This store keeps them in memory
+/// only: they never touch the disk, the keychain or the logs, and are wiped
+/// (bytes zeroed) as soon as the app goes to the background or the session
+/// locks. The frontend uses the `ephemeral_store` and `ephemeral_get`
+/// commands, and must expect values to disappear at any time.
+///
+/// The store holds at most `EPHEMERAL_MAX_ENTRIES` values of at most
+/// `EPHEMERAL_MAX_VALUE_LENGTH` bytes.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants::{self, helpers};
+use crate::{AppError, AppResult};
+
+/// Secret bytes, zeroed when dropped
+struct Secret(Vec<u8>);
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.fill(0);
+        std::hint::black_box(&self.0);
+    }
+}
+
+/// In-memory secrets store, managed by the Tauri application
+#[derive(Default)]
+pub struct EphemeralStore {
+    entries: Mutex<HashMap<String, Secret>>,
+}
+
+impl std::fmt::Debug for EphemeralStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EphemeralStore")
+            .field("entries", &self.len())
+            .finish()
+    }
+}
+
+impl EphemeralStore {
+    /// Store a secret, replacing the previous value of the key
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if the key or value
+    /// is invalid or the store is full.
+    pub fn store(&self, key: &str, value: &str) -> AppResult<()> {
+        helpers::validate_keychain_key(key)?;
+        if value.len() > constants::EPHEMERAL_MAX_VALUE_LENGTH {
+            return Err(AppError::InvalidArgument(format!(
+                "Value length must be at most {} characters, got {}",
+                constants::EPHEMERAL_MAX_VALUE_LENGTH,
+                value.len()
+            )));
+        }
+
+        let mut entries = self.entries.lock().map_err(|e| AppError::Keychain(e.to_string()))?;
+        if !entries.contains_key(key) && entries.len() >= constants::EPHEMERAL_MAX_ENTRIES {
+            return Err(AppError::Keychain(format!(
+                "Ephemeral store is full ({} entries)",
+                constants::EPHEMERAL_MAX_ENTRIES
+            )));
+        }
+        entries.insert(key.to_string(), Secret(value.as_bytes().to_vec()));
+        Ok(())
+    }
+
+    /// Get a secret, if still in the store
+    pub fn get(&self, key: &str) -> Option<String> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(key)
+            .and_then(|secret| String::from_utf8(secret.0.clone()).ok())
+    }
+
+    /// Number of stored secrets
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|entries| entries.len()).unwrap_or(0)
+    }
+
+    /// Returns `true` if no secret is stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all secrets
+    pub fn wipe(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+/// Wipe the store
+///
+/// Called when the app goes to the background and when the session locks.
+pub fn wipe(app: &AppHandle) {
+    let store = app.state::<EphemeralStore>();
+    let count = store.len();
+    store.wipe();
+    if count > 0 {
+        tracing::info!("Wiped {} ephemeral secret(s)", count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_get_and_wipe() {
+        let store = EphemeralStore::default();
+        assert_eq!(store.get("document.key"), None);
+
+        store.store("document.key", "k1").unwrap();
+        store.store("document.key", "k2").unwrap();
+        assert_eq!(store.get("document.key"), Some("k2".to_string()));
+        assert_eq!(store.len(), 1);
+
+        store.wipe();
+        assert!(store.is_empty());
+        assert_eq!(store.get("document.key"), None);
+    }
+
+    #[test]
+    fn test_limits() {
+        let store = EphemeralStore::default();
+        assert!(store.store("", "value").is_err());
+        assert!(store
+            .store("document.key", &"x".repeat(constants::EPHEMERAL_MAX_VALUE_LENGTH + 1))
+            .is_err());
+
+        for i in 0..constants::EPHEMERAL_MAX_ENTRIES {
+            store.store(&format!("key.{}", i), "value").unwrap();
+        }
+        assert!(store.store("one.more", "value").unwrap_err().to_string().contains("full"));
+        assert!(store.store("key.0", "replaced").is_ok(), "Replacing does not grow the store");
+    }
+
+    #[test]
+    fn test_debug_does_not_leak_values() {
+        let store = EphemeralStore::default();
+        store.store("document.key", "super-secret").unwrap();
+        assert!(!format!("{:?}", store).contains("super-secret"));
+    }
+}
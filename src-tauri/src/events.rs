@@ -0,0 +1,227 @@
+/// Native → webview events
+///
+/// Every event the native layer emits to the webview is declared here: its
+/// channel name (`constants::*_EVENT`) and its payload type, emitted with the
+/// single `emit` helper. The payloads are part of the TypeScript bindings
+/// (`bindings`), with an `EventPayloads` map from channel to payload type, so
+/// the frontend listens with typed handlers.
+///
+/// Channels, by area:
+/// - connectivity: `connectivity://changed`
+/// - lifecycle: `lifecycle://foreground`, `lifecycle://background`,
+///   `lifecycle://resume`
+/// - notifications: `notification://received`, `deeplink://open`,
+///   `watch://mark-read`
+/// - sync and transfers: `sync://completed`, `download://progress`,
+///   `download://state`, `upload://state`, `share://received`
+/// - realtime connection: `ws://state`, `ws://message`, `sse://state`,
+///   `sse://message`
+/// - keyboard and device: `keyboard://changed`, `gesture://shake`,
+///   `accessibility-changed`, `audio-level`, `locale://changed`
+/// - session and security: `session://locked`, `session://unlocked`,
+///   `clipboard://cleared`, `policy://changed`, `integrity://tampered`,
+///   `tls://pin-failure`, `wipe://completed`
+/// - app: `feature://changed`, `update://required`, `update://progress`,
+///   `update://result`
+///
+/// Payload types shared with commands (`SyncReport`, `Download`, ...) are
+/// emitted as they are; events without a dedicated type get one here. Events
+/// that must not be lost while no page listens (`sync://completed`,
+/// `notification://received`, `share://received`, `watch://mark-read`) are
+/// posted through the `outbox` instead.
+
+use specta::datatype::DataType;
+use specta::TypeCollection;
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::accessibility::AccessibilityStatus;
+use crate::app_update::UpdateInfo;
+use crate::audio::AudioLevel;
+use crate::clipboard::ClearReason;
+use crate::constants;
+use crate::deep_link::DeepLink;
+use crate::device_policy::ActivePolicy;
+use crate::downloads::{Download, DownloadProgress};
+use crate::feature_flags::FlagChange;
+use crate::in_app_update::{UpdateProgress, UpdateResult};
+use crate::integrity::IntegrityFinding;
+use crate::locale::LocaleInfo;
+use crate::session_lock::LockReason;
+use crate::share_target::SharedFile;
+#[cfg(feature = "module-sse")]
+use crate::sse::{SseMessage, SseStatus};
+use crate::sync::SyncReport;
+use crate::tls_pinning::PinFailure;
+use crate::uploads::Upload;
+#[cfg(feature = "module-watch")]
+use crate::watch::WatchMarkRead;
+#[cfg(feature = "module-realtime")]
+use crate::websocket::{WebSocketMessage, WebSocketStatus};
+use crate::wipe::WipeReport;
+
+/// Event emitted to the webview
+pub trait Event: serde::Serialize + Clone + specta::Type {
+    /// Channel the webview listens on
+    const NAME: &'static str;
+}
+
+/// Emit an event to the webview, logging failures
+pub fn emit<R: Runtime, E: Event>(app: &AppHandle<R>, event: &E) {
+    if let Err(e) = app.emit(E::NAME, event) {
+        tracing::warn!("Failed to emit {} event: {}", E::NAME, e);
+    }
+}
+
+/// Payload of `connectivity://changed`, emitted when a connectivity check
+/// result differs from the previous one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct ConnectivityChanged {
+    /// Whether the application server is reachable
+    pub online: bool,
+}
+
+/// Payload of `lifecycle://foreground`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct AppForeground;
+
+/// Payload of `lifecycle://background`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct AppBackground;
+
+/// Payload of `lifecycle://resume`, emitted after `lifecycle://foreground`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct AppResumed {
+    /// Time spent in background (seconds)
+    pub away_secs: u64,
+}
+
+/// Payload of `notification://received`, emitted for every visible push
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct NotificationReceived {
+    /// Notification title
+    pub title: String,
+    /// Notification body text
+    pub body: String,
+    /// Link opened when the notification is tapped
+    pub link: Option<String>,
+    /// Whether the user tapped the notification
+    pub tapped: bool,
+}
+
+/// Payload of `keyboard://changed`
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, specta::Type)]
+pub struct KeyboardChanged {
+    /// Whether the software keyboard is shown
+    pub visible: bool,
+    /// Height of the keyboard over the webview (CSS pixels, 0 when hidden)
+    pub height: f64,
+}
+
+/// Payload of `gesture://shake`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct ShakeDetected;
+
+/// Payload of `session://locked`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct SessionLocked {
+    /// Why the session locked
+    pub reason: LockReason,
+}
+
+/// Payload of `session://unlocked`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct SessionUnlocked;
+
+/// Payload of `clipboard://cleared`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct ClipboardCleared {
+    /// Why the clipboard was cleared
+    pub reason: ClearReason,
+}
+
+/// Payload of `integrity://tampered`: the findings of the self-check
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(transparent)]
+pub struct IntegrityTampered(pub Vec<IntegrityFinding>);
+
+/// Payload of `feature://changed`: the flags whose value changed
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(transparent)]
+pub struct FeatureFlagsChanged(pub Vec<FlagChange>);
+
+macro_rules! events {
+    ($($(#[$meta:meta])* $payload:ty => $name:path,)*) => {
+        $(
+            $(#[$meta])*
+            impl Event for $payload {
+                const NAME: &'static str = $name;
+            }
+        )*
+
+        /// Channel and payload type of every event, for the TypeScript bindings
+        pub fn registry(types: &mut TypeCollection) -> Vec<(&'static str, DataType)> {
+            vec![$($(#[$meta])* ($name, <$payload as specta::Type>::reference(types, &[]).inner)),*]
+        }
+    };
+}
+
+events! {
+    ConnectivityChanged => constants::CONNECTIVITY_CHANGED_EVENT,
+    AppForeground => constants::LIFECYCLE_FOREGROUND_EVENT,
+    AppBackground => constants::LIFECYCLE_BACKGROUND_EVENT,
+    AppResumed => constants::LIFECYCLE_RESUME_EVENT,
+    NotificationReceived => constants::NOTIFICATION_RECEIVED_EVENT,
+    DeepLink => constants::DEEP_LINK_EVENT,
+    SyncReport => constants::SYNC_COMPLETED_EVENT,
+    DownloadProgress<'_> => constants::DOWNLOAD_PROGRESS_EVENT,
+    Download => constants::DOWNLOAD_STATE_EVENT,
+    Upload => constants::UPLOAD_STATE_EVENT,
+    #[cfg(feature = "module-realtime")]
+    WebSocketStatus => constants::WS_STATE_EVENT,
+    #[cfg(feature = "module-realtime")]
+    WebSocketMessage => constants::WS_MESSAGE_EVENT,
+    #[cfg(feature = "module-sse")]
+    SseStatus => constants::SSE_STATE_EVENT,
+    #[cfg(feature = "module-sse")]
+    SseMessage => constants::SSE_MESSAGE_EVENT,
+    SharedFile => constants::SHARE_RECEIVED_EVENT,
+    #[cfg(feature = "module-watch")]
+    WatchMarkRead => constants::WATCH_MARK_READ_EVENT,
+    KeyboardChanged => constants::KEYBOARD_CHANGED_EVENT,
+    ShakeDetected => constants::SHAKE_EVENT,
+    AccessibilityStatus => constants::ACCESSIBILITY_CHANGED_EVENT,
+    AudioLevel => constants::AUDIO_LEVEL_EVENT,
+    LocaleInfo => constants::LOCALE_CHANGED_EVENT,
+    SessionLocked => constants::SESSION_LOCKED_EVENT,
+    SessionUnlocked => constants::SESSION_UNLOCKED_EVENT,
+    ClipboardCleared => constants::CLIPBOARD_CLEARED_EVENT,
+    ActivePolicy => constants::DEVICE_POLICY_CHANGED_EVENT,
+    IntegrityTampered => constants::INTEGRITY_TAMPERED_EVENT,
+    PinFailure => constants::TLS_PIN_FAILURE_EVENT,
+    WipeReport => constants::WIPE_COMPLETED_EVENT,
+    FeatureFlagsChanged => constants::FEATURE_FLAGS_CHANGED_EVENT,
+    UpdateInfo => constants::UPDATE_REQUIRED_EVENT,
+    UpdateProgress => constants::UPDATE_PROGRESS_EVENT,
+    UpdateResult => constants::UPDATE_RESULT_EVENT,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channels_are_unique() {
+        let registry = registry(&mut TypeCollection::default());
+        let mut channels: Vec<&str> = registry.iter().map(|(channel, _)| *channel).collect();
+        channels.sort_unstable();
+        channels.dedup();
+        assert_eq!(channels.len(), registry.len());
+    }
+
+    #[test]
+    fn test_payloads_keep_their_shape() {
+        assert_eq!(serde_json::to_value(AppForeground).unwrap(), serde_json::Value::Null);
+        assert_eq!(serde_json::to_value(AppResumed { away_secs: 3 }).unwrap(), serde_json::json!({ "away_secs": 3 }));
+        assert_eq!(serde_json::to_value(FeatureFlagsChanged(Vec::new())).unwrap(), serde_json::json!([]));
+    }
+}
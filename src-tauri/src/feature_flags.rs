@@ -0,0 +1,295 @@
+/// Feature flag evaluation module
+///
+/// This module evaluates feature flags on top of the remote config, so new
+/// native capabilities can be rolled out gradually.
+///
+/// Flags are read from the `feature_flags` remote config key:
+///
+/// ```json
+/// { "feature_flags": { "shake_to_report": true, "session_lock": { "rollout": 25 } } }
+/// ```
+///
+/// A flag is either a boolean, or a rule enabling it for a percentage of
+/// installations. The rollout bucket is derived from the installation id, so
+/// a given device keeps the same value across launches. Flags missing from
+/// the remote config use their default value (`DEFAULT_FLAGS`).
+///
+/// Whenever the config changes, flags are re-evaluated and a
+/// `feature://changed` event is emitted with the flags whose value changed
+/// (every flag on the first evaluation of a launch). An invalid rule only
+/// drops its own flag, which then uses its default value.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::events::{self, FeatureFlagsChanged};
+use crate::install::InstallState;
+use crate::logging;
+use crate::remote_config::{self, ConfigStore};
+
+/// Shake gesture opens the bug-report flow
+pub const SHAKE_TO_REPORT: &str = "shake_to_report";
+
+/// Inactivity auto-lock of the session
+pub const SESSION_LOCK: &str = "session_lock";
+
+/// HMAC signature required on sensitive commands (see the `invoke_signing` module)
+pub const SIGNED_INVOKE: &str = "signed_invoke";
+
+/// Structured JSON-lines log file (see the `logging` module)
+pub const JSON_LOGS: &str = "json_logs";
+
+/// Per-command execution metrics shipped with analytics (see the `command_metrics` module)
+pub const COMMAND_METRICS: &str = "command_metrics";
+
+/// Static assets of the web application served from the offline cache (see the `asset_cache` module)
+pub const OFFLINE_ASSET_CACHE: &str = "offline_asset_cache";
+
+/// Periodic and push-triggered data sync into the local database (see the `sync` module)
+pub const BACKGROUND_SYNC: &str = "background_sync";
+
+/// Prefetching of the next session's documents on Wi-Fi while charging (see the `prefetch` module)
+pub const CONTENT_PREFETCH: &str = "content_prefetch";
+
+/// Default value of known flags, used when the remote config does not set them
+pub const DEFAULT_FLAGS: &[(&str, bool)] = &[
+    (SHAKE_TO_REPORT, true),
+    (SESSION_LOCK, true),
+    (SIGNED_INVOKE, false),
+    (JSON_LOGS, false),
+    (COMMAND_METRICS, false),
+    (OFFLINE_ASSET_CACHE, true),
+    (BACKGROUND_SYNC, false),
+    (CONTENT_PREFETCH, true),
+];
+
+/// Remote rule of a feature flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(untagged)]
+pub enum FlagRule {
+    /// Enabled or disabled for everyone
+    Fixed(bool),
+    /// Enabled for a percentage of installations
+    Rollout {
+        /// Percentage of installations with the flag enabled (0-100)
+        rollout: u8,
+    },
+}
+
+/// A flag whose value changed, emitted with `feature://changed`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagChange {
+    /// Flag name
+    pub name: String,
+    /// New value of the flag
+    pub enabled: bool,
+}
+
+/// Rollout bucket (0-99) of an installation for a flag
+///
+/// Each flag uses its own bucket, so the same installations do not get every
+/// partial rollout first.
+pub fn rollout_bucket(installation_id: &str, name: &str) -> u8 {
+    let digest = ring::digest::digest(
+        &ring::digest::SHA256,
+        format!("{}:{}", name, installation_id).as_bytes(),
+    );
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&digest.as_ref()[..8]);
+    (u64::from_be_bytes(prefix) % 100) as u8
+}
+
+/// Default value of a flag (`false` for unknown flags)
+pub fn default_value(name: &str) -> bool {
+    DEFAULT_FLAGS
+        .iter()
+        .find(|(flag, _)| *flag == name)
+        .is_some_and(|(_, enabled)| *enabled)
+}
+
+/// Evaluate a flag
+///
+/// # Arguments
+///
+/// * `name` - Flag name
+/// * `rule` - Remote rule of the flag, if any
+/// * `installation_id` - Identifier of this installation, if known
+///
+/// Percentage rollouts are disabled when the installation id is unknown.
+pub fn evaluate(name: &str, rule: Option<FlagRule>, installation_id: Option<&str>) -> bool {
+    match rule {
+        None => default_value(name),
+        Some(FlagRule::Fixed(enabled)) => enabled,
+        Some(FlagRule::Rollout { rollout }) => installation_id
+            .is_some_and(|id| rollout_bucket(id, name) < rollout.min(100)),
+    }
+}
+
+/// Parse the remote rules, skipping invalid entries
+fn parse_rules(value: &serde_json::Value) -> BTreeMap<String, FlagRule> {
+    let Some(entries) = value.as_object() else {
+        tracing::warn!("Ignoring feature flags: not an object");
+        return BTreeMap::new();
+    };
+    entries
+        .iter()
+        .filter_map(|(name, rule)| match serde_json::from_value(rule.clone()) {
+            Ok(rule) => Some((name.clone(), rule)),
+            Err(e) => {
+                tracing::warn!("Ignoring feature flag {}: {}", name, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Remote rules of all flags
+fn rules(config: &ConfigStore) -> BTreeMap<String, FlagRule> {
+    config
+        .get(remote_config::keys::FEATURE_FLAGS)
+        .map(|value| parse_rules(&value))
+        .unwrap_or_default()
+}
+
+/// Evaluate all known flags (defaults and remote rules)
+fn evaluate_all(app: &AppHandle) -> BTreeMap<String, bool> {
+    let rules = rules(&app.state::<ConfigStore>());
+    let installation_id = app.state::<InstallState>().installation_id();
+
+    DEFAULT_FLAGS
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .chain(rules.keys().cloned())
+        .map(|name| {
+            let enabled = evaluate(&name, rules.get(&name).copied(), installation_id.as_deref());
+            (name, enabled)
+        })
+        .collect()
+}
+
+/// Evaluate a single flag against the current remote config
+pub fn is_enabled<R: Runtime>(app: &AppHandle<R>, name: &str) -> bool {
+    let rule = rules(&app.state::<ConfigStore>()).get(name).copied();
+    let installation_id = app.state::<InstallState>().installation_id();
+    evaluate(name, rule, installation_id.as_deref())
+}
+
+/// Last evaluated flag values, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct FeatureFlags {
+    values: Mutex<BTreeMap<String, bool>>,
+}
+
+impl FeatureFlags {
+    /// Store newly evaluated values
+    ///
+    /// # Returns
+    ///
+    /// Returns the flags whose value changed since the previous evaluation.
+    /// The first evaluation reports every flag.
+    pub fn update(&self, values: BTreeMap<String, bool>) -> Vec<FlagChange> {
+        let Ok(mut current) = self.values.lock() else {
+            return Vec::new();
+        };
+        let first = current.is_empty();
+        let changes = values
+            .iter()
+            .filter(|(name, enabled)| first || current.get(*name) != Some(enabled))
+            .map(|(name, &enabled)| FlagChange { name: name.clone(), enabled })
+            .collect();
+        *current = values;
+        changes
+    }
+}
+
+/// Re-evaluate all flags and notify the webview of changes
+///
+/// Called whenever the remote config is loaded or refreshed.
+pub fn refresh(app: &AppHandle) {
+    let changes = app.state::<FeatureFlags>().update(evaluate_all(app));
+    logging::set_json_enabled(is_enabled(app, JSON_LOGS));
+    if changes.is_empty() {
+        return;
+    }
+
+    tracing::info!("Feature flags changed: {:?}", changes);
+    events::emit(app, &FeatureFlagsChanged(changes));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_defaults() {
+        assert!(evaluate(SHAKE_TO_REPORT, None, None));
+        assert!(!evaluate("unknown_flag", None, None));
+    }
+
+    #[test]
+    fn test_evaluate_fixed_rule() {
+        assert!(!evaluate(SHAKE_TO_REPORT, Some(FlagRule::Fixed(false)), None));
+        assert!(evaluate("unknown_flag", Some(FlagRule::Fixed(true)), None));
+    }
+
+    #[test]
+    fn test_evaluate_rollout() {
+        let rule = |rollout| Some(FlagRule::Rollout { rollout });
+        assert!(!evaluate("flag", rule(0), Some("install")));
+        assert!(evaluate("flag", rule(100), Some("install")));
+        assert!(!evaluate("flag", rule(100), None), "Rollouts need an installation id");
+
+        let bucket = rollout_bucket("install", "flag");
+        assert!(evaluate("flag", rule(bucket + 1), Some("install")));
+        assert!(!evaluate("flag", rule(bucket), Some("install")));
+    }
+
+    #[test]
+    fn test_rollout_bucket_distribution() {
+        let enabled = (0..1000)
+            .filter(|i| rollout_bucket(&format!("install-{}", i), "flag") < 25)
+            .count();
+        assert!((150..350).contains(&enabled), "~25% expected, got {}", enabled);
+    }
+
+    #[test]
+    fn test_flag_rule_deserialize() {
+        let rules: BTreeMap<String, FlagRule> =
+            serde_json::from_str(r#"{ "a": true, "b": { "rollout": 25 } }"#).unwrap();
+        assert_eq!(rules["a"], FlagRule::Fixed(true));
+        assert_eq!(rules["b"], FlagRule::Rollout { rollout: 25 });
+    }
+
+    #[test]
+    fn test_invalid_rule_only_drops_its_flag() {
+        let rules = parse_rules(&serde_json::json!({
+            "a": true,
+            "b": { "rollout": 300 },
+            "c": { "rollout": 25 },
+        }));
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules["a"], FlagRule::Fixed(true));
+        assert_eq!(rules["c"], FlagRule::Rollout { rollout: 25 });
+        assert!(parse_rules(&serde_json::json!([true])).is_empty());
+    }
+
+    #[test]
+    fn test_update_reports_changes() {
+        let flags = FeatureFlags::default();
+        let values = |a, b| BTreeMap::from([("a".to_string(), a), ("b".to_string(), b)]);
+
+        assert_eq!(
+            flags.update(values(true, false)).len(),
+            2,
+            "First evaluation reports every flag"
+        );
+        assert!(flags.update(values(true, false)).is_empty());
+        assert_eq!(
+            flags.update(values(true, true)),
+            vec![FlagChange { name: "b".to_string(), enabled: true }]
+        );
+    }
+}
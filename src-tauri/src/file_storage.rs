@@ -0,0 +1,432 @@
+/// Sandboxed app file storage
+///
+/// The frontend persists downloaded files (document annexes, ...) through the
+/// `file_write`, `file_read`, `file_delete` and `file_list` commands, without
+/// any access to the rest of the filesystem:
+/// - all files live under a dedicated directory (`FILE_STORAGE_DIR`, in the
+///   app data directory)
+/// - paths are relative and `/`-separated; absolute paths, `.` and `..`
+///   segments, backslashes and empty segments are rejected, and resolved
+///   paths are checked to stay under the storage directory (symlinks)
+/// - each top-level directory has a quota (`FILE_STORAGE_QUOTAS`, or
+///   `FILE_STORAGE_DEFAULT_QUOTA_BYTES`), and the files directly at the root
+///   share the default one; a write exceeding it is rejected
+/// - contents are exchanged base64-encoded, at most
+///   `FILE_STORAGE_MAX_FILE_BYTES` per file, and written atomically
+
+use std::path::{Component, Path, PathBuf};
+
+use base64::Engine;
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::{AppError, AppResult};
+
+/// File or directory of the app file storage
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileEntry {
+    /// Path relative to the storage directory (`annexes/deliberation-12.pdf`)
+    pub path: String,
+    /// `true` for a directory
+    pub is_dir: bool,
+    /// Size in bytes (0 for directories)
+    pub size: u64,
+    /// Last modification time (seconds since the Unix epoch)
+    pub modified_at: u64,
+}
+
+/// Storage directory of the app
+pub fn root(app: &AppHandle) -> AppResult<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(constants::FILE_STORAGE_DIR))
+        .map_err(|e| AppError::Storage(format!("Failed to resolve data directory: {}", e)))
+}
+
+/// Validate a relative storage path
+///
+/// # Returns
+///
+/// Returns the path segments, or an error if the path is empty, too
+/// long, absolute, or contains `.`/`..`, empty or backslash segments.
+pub fn validate_path(path: &str) -> AppResult<Vec<&str>> {
+    if path.is_empty() || path.len() > constants::FILE_STORAGE_MAX_PATH_LENGTH {
+        return Err(AppError::InvalidArgument(format!(
+            "File path must be 1 to {} characters",
+            constants::FILE_STORAGE_MAX_PATH_LENGTH
+        )));
+    }
+    let segments: Vec<&str> = path.split('/').collect();
+    let valid = segments.iter().all(|segment| {
+        !segment.is_empty()
+            && *segment != "."
+            && *segment != ".."
+            && !segment.contains(['\\', '\0', ':'])
+    });
+    if !valid || Path::new(path).components().any(|component| !matches!(component, Component::Normal(_))) {
+        return Err(AppError::InvalidArgument(format!("Invalid file path: {}", path)));
+    }
+    Ok(segments)
+}
+
+/// Resolve a relative storage path under `root`
+///
+/// # Returns
+///
+/// Returns the absolute path, or an error if the path is invalid or
+/// an existing part of it resolves outside of `root` (symlink).
+pub fn resolve(root: &Path, path: &str) -> AppResult<PathBuf> {
+    let segments = validate_path(path)?;
+    let resolved = segments.iter().fold(root.to_path_buf(), |resolved, segment| resolved.join(segment));
+
+    // The deepest existing ancestor must stay under the storage directory
+    if let Ok(canonical_root) = root.canonicalize() {
+        let existing = resolved.ancestors().find(|ancestor| ancestor.exists());
+        if let Some(existing) = existing {
+            let canonical = existing
+                .canonicalize()
+                .map_err(|e| AppError::Storage(format!("Failed to resolve file path: {}", e)))?;
+            if !canonical.starts_with(&canonical_root) {
+                return Err(AppError::InvalidArgument(format!("Invalid file path: {}", path)));
+            }
+        }
+    }
+    Ok(resolved)
+}
+
+/// Quota of a top-level directory (bytes)
+pub fn quota(directory: &str) -> u64 {
+    constants::FILE_STORAGE_QUOTAS
+        .iter()
+        .find(|(name, _)| *name == directory)
+        .map_or(constants::FILE_STORAGE_DEFAULT_QUOTA_BYTES, |(_, quota)| *quota)
+}
+
+/// Total size of the files under `path` (0 if missing)
+fn usage(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| usage(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Total size of the files directly under `root`, not counting the top-level
+/// directories, which have their own quota
+fn root_usage(root: &Path) -> u64 {
+    std::fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| std::fs::symlink_metadata(entry.path()).ok())
+                .filter(|metadata| !metadata.is_dir())
+                .map(|metadata| metadata.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn modified_secs(metadata: &std::fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Write a file, creating its parent directories
+///
+/// # Arguments
+///
+/// * `root` - Storage directory
+/// * `path` - Relative path of the file
+/// * `contents` - Contents of the file
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the path is invalid,
+/// the file is too large or the quota of its top-level directory would be
+/// exceeded.
+pub fn write(root: &Path, path: &str, contents: &[u8]) -> AppResult<()> {
+    write_with_quota(root, path, contents, quota)
+}
+
+fn write_with_quota(root: &Path, path: &str, contents: &[u8], quota_of: fn(&str) -> u64) -> AppResult<()> {
+    if contents.len() as u64 > constants::FILE_STORAGE_MAX_FILE_BYTES {
+        return Err(AppError::InvalidArgument(format!(
+            "File must be at most {} bytes, got {}",
+            constants::FILE_STORAGE_MAX_FILE_BYTES,
+            contents.len()
+        )));
+    }
+    let target = prepare_write_with_quota(root, path, contents.len() as u64, quota_of)?;
+
+    let parent = target.parent().ok_or_else(|| AppError::InvalidArgument("Invalid file path".to_string()))?;
+    std::fs::create_dir_all(parent).map_err(|e| AppError::Storage(format!("Failed to create directory: {}", e)))?;
+    let temp = temp_path(&target);
+    std::fs::write(&temp, contents).map_err(|e| AppError::Storage(format!("Failed to write file: {}", e)))?;
+    std::fs::rename(&temp, &target).map_err(|e| {
+        let _ = std::fs::remove_file(&temp);
+        AppError::Storage(format!("Failed to write file: {}", e))
+    })
+}
+
+/// Path of the temporary file of a file being written (`<name>.partial`)
+pub fn temp_path(target: &Path) -> PathBuf {
+    let file_name = target.file_name().unwrap_or_default().to_string_lossy();
+    target.with_file_name(format!("{}.{}", file_name, constants::FILE_STORAGE_TEMP_EXTENSION))
+}
+
+/// Resolve the path of a file about to be written, checking that `size`
+/// bytes fit in the quota of its top-level directory
+///
+/// The current size of the file and of its temporary file are not counted,
+/// since they are replaced.
+///
+/// # Returns
+///
+/// Returns the absolute path, or an error if the path is invalid or
+/// the quota would be exceeded.
+pub fn prepare_write(root: &Path, path: &str, size: u64) -> AppResult<PathBuf> {
+    prepare_write_with_quota(root, path, size, quota)
+}
+
+fn prepare_write_with_quota(root: &Path, path: &str, size: u64, quota_of: fn(&str) -> u64) -> AppResult<PathBuf> {
+    let (target, directory, used, quota) = quota_usage(root, path, quota_of)?;
+    if used + size > quota {
+        return Err(AppError::Storage(format!(
+            "Storage quota of {} exceeded ({} of {} bytes used)",
+            if directory.is_empty() { "/" } else { directory },
+            used,
+            quota
+        )));
+    }
+    Ok(target)
+}
+
+/// Bytes still available for the file at `path` in the quota of its
+/// top-level directory
+///
+/// As with `prepare_write`, the current size of the file and of its
+/// temporary file are not counted.
+///
+/// # Returns
+///
+/// Returns the number of bytes, or an error if the path is invalid.
+pub fn available(root: &Path, path: &str) -> AppResult<u64> {
+    let (_, _, used, quota) = quota_usage(root, path, quota)?;
+    Ok(quota.saturating_sub(used))
+}
+
+/// Resolve the path of a file about to be written, with its top-level
+/// directory, the bytes used in that directory (not counting the file) and
+/// its quota
+fn quota_usage<'a>(
+    root: &Path,
+    path: &'a str,
+    quota_of: fn(&str) -> u64,
+) -> AppResult<(PathBuf, &'a str, u64, u64)> {
+    let segments = validate_path(path)?;
+    if Path::new(path).extension() == Some(constants::FILE_STORAGE_TEMP_EXTENSION.as_ref()) {
+        return Err(AppError::InvalidArgument(format!("Invalid file path: {}", path)));
+    }
+    let target = resolve(root, path)?;
+    if target.is_dir() {
+        return Err(AppError::InvalidArgument(format!("{} is a directory", path)));
+    }
+
+    let directory = if segments.len() > 1 { segments[0] } else { "" };
+    let used = if directory.is_empty() { root_usage(root) } else { usage(&root.join(directory)) }
+        .saturating_sub(usage(&target))
+        .saturating_sub(usage(&temp_path(&target)));
+    Ok((target, directory, used, quota_of(directory)))
+}
+
+/// Read a file
+pub fn read(root: &Path, path: &str) -> AppResult<Vec<u8>> {
+    let target = resolve(root, path)?;
+    if target.is_dir() {
+        return Err(AppError::InvalidArgument(format!("{} is a directory", path)));
+    }
+    std::fs::read(&target).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => AppError::Storage(format!("File not found: {}", path)),
+        _ => AppError::Storage(format!("Failed to read file: {}", e)),
+    })
+}
+
+/// Delete a file, or a directory and its contents
+///
+/// # Returns
+///
+/// Returns `true` if something was deleted, `false` if the path did not
+/// exist, or an error.
+pub fn delete(root: &Path, path: &str) -> AppResult<bool> {
+    let target = resolve(root, path)?;
+    let Ok(metadata) = std::fs::symlink_metadata(&target) else {
+        return Ok(false);
+    };
+    if metadata.is_dir() {
+        std::fs::remove_dir_all(&target)
+    } else {
+        std::fs::remove_file(&target)
+    }
+    .map_err(|e| AppError::Storage(format!("Failed to delete {}: {}", path, e)))?;
+    Ok(true)
+}
+
+/// List the entries of a directory (not recursive), sorted by path
+///
+/// # Arguments
+///
+/// * `root` - Storage directory
+/// * `directory` - Relative path of the directory, the storage directory
+///   itself if `None`
+pub fn list(root: &Path, directory: Option<&str>) -> AppResult<Vec<FileEntry>> {
+    let (target, prefix) = match directory {
+        Some(directory) => (resolve(root, directory)?, format!("{}/", directory)),
+        None => (root.to_path_buf(), String::new()),
+    };
+    let entries = match std::fs::read_dir(&target) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(AppError::Storage(format!("Failed to list directory: {}", e))),
+    };
+
+    let mut files: Vec<FileEntry> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let metadata = entry.metadata().ok()?;
+            // Interrupted atomic writes
+            if Path::new(&name).extension() == Some(constants::FILE_STORAGE_TEMP_EXTENSION.as_ref()) {
+                return None;
+            }
+            Some(FileEntry {
+                path: format!("{}{}", prefix, name),
+                is_dir: metadata.is_dir(),
+                size: if metadata.is_dir() { 0 } else { metadata.len() },
+                modified_at: modified_secs(&metadata),
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Decode base64 file contents received from the frontend
+pub fn decode(data: &str) -> AppResult<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| AppError::InvalidArgument(format!("File contents must be base64-encoded: {}", e)))
+}
+
+/// Encode file contents for the frontend
+pub fn encode(contents: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_validation() {
+        assert_eq!(validate_path("annexes/deliberation-12.pdf").unwrap(), ["annexes", "deliberation-12.pdf"]);
+        assert!(validate_path("notes.txt").is_ok());
+        for invalid in [
+            "",
+            "/etc/passwd",
+            "../secrets",
+            "annexes/../../keychain",
+            "annexes/./file",
+            "annexes//file",
+            "annexes/",
+            "annexes\\..\\file",
+            "C:file",
+        ] {
+            assert!(validate_path(invalid).is_err(), "{} should be rejected", invalid);
+        }
+        assert!(validate_path(&"a".repeat(constants::FILE_STORAGE_MAX_PATH_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_write_read_list_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("files");
+        write(&root, "annexes/a.pdf", b"%PDF-a").unwrap();
+        write(&root, "annexes/b.pdf", b"%PDF-b").unwrap();
+        write(&root, "annexes/a.pdf", b"%PDF-a2").unwrap();
+        assert_eq!(read(&root, "annexes/a.pdf").unwrap(), b"%PDF-a2");
+        assert!(write(&root, "annexes/a.partial", b"x").is_err(), "Reserved for atomic writes");
+        assert!(read(&root, "annexes/missing.pdf").unwrap_err().to_string().contains("not found"));
+
+        let top = list(&root, None).unwrap();
+        assert_eq!(top.len(), 1);
+        assert!(top[0].is_dir);
+        let annexes = list(&root, Some("annexes")).unwrap();
+        assert_eq!(
+            annexes.iter().map(|entry| entry.path.as_str()).collect::<Vec<_>>(),
+            ["annexes/a.pdf", "annexes/b.pdf"]
+        );
+        assert_eq!(annexes[0].size, 7);
+
+        assert!(delete(&root, "annexes/a.pdf").unwrap());
+        assert!(!delete(&root, "annexes/a.pdf").unwrap());
+        assert!(delete(&root, "annexes").unwrap());
+        assert!(list(&root, None).unwrap().is_empty());
+        assert!(list(&root, Some("missing")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_quota_per_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("files");
+        let quota_of: fn(&str) -> u64 = |_| 10;
+        write_with_quota(&root, "annexes/one", b"12345", quota_of).unwrap();
+        write_with_quota(&root, "annexes/sub/two", b"12345", quota_of).unwrap();
+        let error = write_with_quota(&root, "annexes/three", b"x", quota_of).unwrap_err().to_string();
+        assert!(error.contains("quota of annexes exceeded"), "{}", error);
+
+        // Overwriting a file only counts the difference
+        write_with_quota(&root, "annexes/one", b"54321", quota_of).unwrap();
+        // Other directories have their own quota
+        write_with_quota(&root, "drafts/three", b"x", quota_of).unwrap();
+        // Files at the root are not charged for the top-level directories
+        write_with_quota(&root, "notes.txt", b"123456789", quota_of).unwrap();
+        let error = write_with_quota(&root, "other.txt", b"xx", quota_of).unwrap_err().to_string();
+        assert!(error.contains("quota of / exceeded"), "{}", error);
+        assert_eq!(quota("annexes"), constants::FILE_STORAGE_QUOTAS[0].1);
+        assert_eq!(quota("unknown"), constants::FILE_STORAGE_DEFAULT_QUOTA_BYTES);
+    }
+
+    #[test]
+    fn test_file_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let too_large = vec![0u8; constants::FILE_STORAGE_MAX_FILE_BYTES as usize + 1];
+        assert!(write(dir.path(), "annexes/large.bin", &too_large).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_escape_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("files");
+        let outside = dir.path().join("outside");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+
+        assert!(write(&root, "link/escaped.txt", b"x").is_err());
+        assert!(!outside.join("escaped.txt").exists());
+    }
+}
@@ -0,0 +1,41 @@
+/// Android-specific gesture implementation
+///
+/// This module feeds accelerometer samples to the shake detector using
+/// `SensorManager` from the Android SDK.
+///
+/// Note: This implementation provides the structure for the Android accelerometer.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use tauri::AppHandle;
+use crate::AppResult;
+
+/// Start the accelerometer listener on Android
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start_accelerometer(app: AppHandle) -> AppResult<()> {
+    tracing::info!("[Android] Starting accelerometer listener");
+
+    // TODO: Implement native Android accelerometer listener using SensorManager
+    // Each sample must be converted to g (divide by SensorManager.GRAVITY_EARTH)
+    // and forwarded to `gestures::on_accelerometer_sample`.
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val sensorManager = context.getSystemService(Context.SENSOR_SERVICE) as SensorManager
+    // val accelerometer = sensorManager.getDefaultSensor(Sensor.TYPE_ACCELEROMETER)
+    // sensorManager.registerListener(object : SensorEventListener {
+    //     override fun onSensorChanged(event: SensorEvent) {
+    //         val g = SensorManager.GRAVITY_EARTH
+    //         onAccelerometerSample(event.values[0] / g, event.values[1] / g, event.values[2] / g, event.timestamp / 1_000_000)
+    //     }
+    //     override fun onAccuracyChanged(sensor: Sensor, accuracy: Int) {}
+    // }, accelerometer, SensorManager.SENSOR_DELAY_GAME)
+    // ```
+
+    // Placeholder: Return success without listening
+    // Replace this with actual native implementation
+    let _ = app;
+    Ok(())
+}
@@ -0,0 +1,36 @@
+/// iOS-specific gesture implementation
+///
+/// This module feeds accelerometer samples to the shake detector using
+/// `CMMotionManager` from the CoreMotion framework.
+///
+/// Note: This implementation provides the structure for the iOS accelerometer.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use tauri::AppHandle;
+use crate::AppResult;
+
+/// Start the accelerometer listener on iOS
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start_accelerometer(app: AppHandle) -> AppResult<()> {
+    tracing::info!("[iOS] Starting accelerometer listener");
+
+    // TODO: Implement native iOS accelerometer listener using CMMotionManager
+    // Each sample must be forwarded to `gestures::on_accelerometer_sample`.
+    // Example Swift implementation:
+    // ```swift
+    // motionManager.accelerometerUpdateInterval = 0.02
+    // motionManager.startAccelerometerUpdates(to: .main) { data, _ in
+    //     guard let a = data?.acceleration else { return }
+    //     on_accelerometer_sample(a.x, a.y, a.z, UInt64(data!.timestamp * 1000))
+    // }
+    // ```
+
+    // Placeholder: Return success without listening
+    // Replace this with actual native implementation
+    let _ = app;
+    Ok(())
+}
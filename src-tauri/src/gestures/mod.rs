@@ -0,0 +1,197 @@
+/// Gesture detection
+///
+/// This module detects a shake gesture from accelerometer samples and emits a
+/// `gesture://shake` event to the webview, which the frontend uses to open the
+/// bug-report / diagnostics flow. The shake also starts the native
+/// diagnostics export (`diagnostics::export_logs`), so logs can be shared
+/// even when the page is broken.
+///
+/// The native accelerometer listener forwards each sample to
+/// `on_accelerometer_sample()`; the detection logic itself is platform
+/// independent (`ShakeDetector`).
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::diagnostics;
+use crate::events::{self, ShakeDetected};
+use crate::feature_flags;
+use crate::AppResult;
+
+/// A single accelerometer sample, in g (gravity included)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AccelerometerSample {
+    /// Acceleration along the X axis
+    pub x: f64,
+    /// Acceleration along the Y axis
+    pub y: f64,
+    /// Acceleration along the Z axis
+    pub z: f64,
+}
+
+impl AccelerometerSample {
+    /// Magnitude of the acceleration vector
+    pub fn magnitude(&self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+}
+
+/// Shake gesture detector
+///
+/// A shake is detected when at least `SHAKE_MIN_SPIKES` samples exceed
+/// `SHAKE_THRESHOLD_G` within `SHAKE_WINDOW_MS`. After a detection, further
+/// shakes are ignored for `SHAKE_COOLDOWN_MS`.
+#[derive(Debug, Default)]
+pub struct ShakeDetector {
+    spikes: VecDeque<u64>,
+    last_shake_ms: Option<u64>,
+}
+
+impl ShakeDetector {
+    /// Feed a sample taken at `timestamp_ms` (monotonic milliseconds)
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if this sample completes a shake gesture.
+    pub fn on_sample(&mut self, sample: AccelerometerSample, timestamp_ms: u64) -> bool {
+        if let Some(last) = self.last_shake_ms {
+            if timestamp_ms.saturating_sub(last) < constants::SHAKE_COOLDOWN_MS {
+                return false;
+            }
+        }
+
+        if sample.magnitude() < constants::SHAKE_THRESHOLD_G {
+            return false;
+        }
+
+        self.spikes.push_back(timestamp_ms);
+        while let Some(&first) = self.spikes.front() {
+            if timestamp_ms.saturating_sub(first) > constants::SHAKE_WINDOW_MS {
+                self.spikes.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.spikes.len() >= constants::SHAKE_MIN_SPIKES {
+            self.spikes.clear();
+            self.last_shake_ms = Some(timestamp_ms);
+            return true;
+        }
+        false
+    }
+}
+
+/// Gesture state, managed by the Tauri application
+#[derive(Default)]
+pub struct GestureState {
+    shake: Mutex<ShakeDetector>,
+}
+
+/// Handle a sample from the native accelerometer listener
+///
+/// Emits a `gesture://shake` event and exports the logs when a shake is
+/// detected.
+pub fn on_accelerometer_sample(app: &AppHandle, sample: AccelerometerSample, timestamp_ms: u64) {
+    let state = app.state::<GestureState>();
+    let shaken = match state.shake.lock() {
+        Ok(mut detector) => detector.on_sample(sample, timestamp_ms),
+        Err(e) => {
+            tracing::warn!("Shake detector unavailable: {}", e);
+            return;
+        }
+    };
+
+    if shaken && feature_flags::is_enabled(app, feature_flags::SHAKE_TO_REPORT) {
+        tracing::info!("Shake gesture detected");
+        events::emit(app, &ShakeDetected);
+        // Builds an archive of the logs: off the sensor callback
+        let app = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = diagnostics::export_logs(&app) {
+                tracing::warn!("Failed to export logs after shake: {}", e);
+            }
+        });
+    }
+}
+
+/// Start the native accelerometer listener
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the accelerometer is
+/// unavailable.
+pub fn start(app: &AppHandle) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::start_accelerometer(app.clone())
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::start_accelerometer(app.clone())
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = app;
+        tracing::debug!("Accelerometer not available on this platform");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STILL: AccelerometerSample = AccelerometerSample { x: 0.0, y: 0.0, z: 1.0 };
+    const SHAKE: AccelerometerSample = AccelerometerSample { x: 2.5, y: 1.0, z: 1.0 };
+
+    #[test]
+    fn test_still_device_never_shakes() {
+        let mut detector = ShakeDetector::default();
+        for t in 0..100 {
+            assert!(!detector.on_sample(STILL, t * 20));
+        }
+    }
+
+    #[test]
+    fn test_spikes_within_window_detect_shake() {
+        let mut detector = ShakeDetector::default();
+        assert!(!detector.on_sample(SHAKE, 0));
+        assert!(!detector.on_sample(SHAKE, 200));
+        assert!(detector.on_sample(SHAKE, 400));
+    }
+
+    #[test]
+    fn test_spikes_outside_window_are_ignored() {
+        let mut detector = ShakeDetector::default();
+        assert!(!detector.on_sample(SHAKE, 0));
+        assert!(!detector.on_sample(SHAKE, 1500));
+        assert!(!detector.on_sample(SHAKE, 3000));
+    }
+
+    #[test]
+    fn test_cooldown_after_shake() {
+        let mut detector = ShakeDetector::default();
+        for t in [0, 100, 200] {
+            detector.on_sample(SHAKE, t);
+        }
+        for t in [300, 400, 500] {
+            assert!(!detector.on_sample(SHAKE, t), "Shakes during cooldown should be ignored");
+        }
+        for t in [2300, 2400] {
+            assert!(!detector.on_sample(SHAKE, t));
+        }
+        assert!(detector.on_sample(SHAKE, 2500));
+    }
+}
@@ -0,0 +1,84 @@
+/// Shared HTTP client module
+///
+/// This module builds the HTTP client used to call backend endpoints, with a
+/// consistent timeout and user agent.
+///
+/// While offline is simulated from the debug menu (`debug_tools`), new
+/// clients send every request to an unreachable proxy, so sync, uploads and
+/// other requests fail as they would without network.
+
+use crate::runtime_config;
+use crate::{AppError, AppResult};
+
+/// User agent sent with backend requests (`elulib-mobile/<version> (<os>)`)
+pub fn user_agent() -> String {
+    format!(
+        "elulib-mobile/{} ({})",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS
+    )
+}
+
+/// Client builder with the user agent, and the offline proxy while offline
+/// is simulated (debug builds only)
+fn builder() -> AppResult<reqwest::ClientBuilder> {
+    let builder = reqwest::Client::builder().user_agent(user_agent());
+    #[cfg(debug_assertions)]
+    if crate::debug_tools::is_offline() {
+        let proxy = reqwest::Proxy::all(crate::constants::DEBUG_OFFLINE_PROXY)
+            .map_err(|e| AppError::Config(format!("Invalid offline proxy: {}", e)))?;
+        return Ok(builder.proxy(proxy));
+    }
+    Ok(builder)
+}
+
+/// URL of a backend endpoint, rebased on the configured application URL
+pub fn endpoint(url: &str) -> String {
+    runtime_config::get().rebase(url)
+}
+
+/// Build an HTTP client for backend requests
+///
+/// # Returns
+///
+/// Returns a client using `HTTP_TIMEOUT_SECS` (or its runtime configuration
+/// override) as request timeout, or an error
+/// message if the client could not be built.
+pub fn client() -> AppResult<reqwest::Client> {
+    builder()?
+        .timeout(runtime_config::get().http_timeout)
+        .build()
+        .map_err(|e| AppError::Network(format!("Failed to build HTTP client: {}", e)))
+}
+
+/// Build an HTTP client for large downloads
+///
+/// # Returns
+///
+/// Returns a client without overall request timeout (a download may take
+/// minutes), but using `HTTP_TIMEOUT_SECS` (or its runtime configuration
+/// override) to connect and between two reads, or an error if the
+/// client could not be built.
+pub fn download_client() -> AppResult<reqwest::Client> {
+    let timeout = runtime_config::get().http_timeout;
+    builder()?
+        .connect_timeout(timeout)
+        .read_timeout(timeout)
+        .build()
+        .map_err(|e| AppError::Network(format!("Failed to build HTTP client: {}", e)))
+}
+
+/// Build an HTTP client for long-lived streams
+///
+/// # Returns
+///
+/// Returns a client without overall request timeout, using `HTTP_TIMEOUT_SECS`
+/// (or its runtime configuration override) to connect and `idle_timeout`
+/// between two reads, or an error if the client could not be built.
+pub fn stream_client(idle_timeout: std::time::Duration) -> AppResult<reqwest::Client> {
+    builder()?
+        .connect_timeout(runtime_config::get().http_timeout)
+        .read_timeout(idle_timeout)
+        .build()
+        .map_err(|e| AppError::Network(format!("Failed to build HTTP client: {}", e)))
+}
@@ -0,0 +1,204 @@
+/// Identity provider profiles
+///
+/// A profile adapts the generic native login flow (`auth` module) to a
+/// specific identity provider. It is selected with the `profile` field of the
+/// provider configuration and declares:
+/// - the scopes and authorization parameters the provider requires
+/// - whether an OpenID Connect `nonce` must be sent and checked
+/// - the hosts its endpoints may use, so a compromised frontend cannot point
+///   a profile at another provider
+/// - whether logout must go through the provider's end-session endpoint
+/// - whether the eIDAS assurance level (`acr` claim) is reported
+///
+/// Profiles:
+/// - `oidc`: any OpenID Connect provider, no extra requirement (default)
+/// - `franceconnect`: FranceConnect, used by many collectivités to
+///   authenticate their agents
+///
+/// New providers are added to `PROFILES`.
+
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use serde_json::{Map, Value};
+use tauri::Url;
+use crate::{AppError, AppResult};
+
+/// Requirements of an identity provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdentityProfile {
+    /// Profile name, as selected by the frontend
+    pub name: &'static str,
+    /// Scopes always requested
+    pub required_scopes: &'static [&'static str],
+    /// Authorization parameters always sent (the frontend cannot override them)
+    pub required_params: &'static [(&'static str, &'static str)],
+    /// Send and check an OpenID Connect nonce
+    pub requires_nonce: bool,
+    /// Hosts the endpoints may use (any host when empty)
+    pub allowed_hosts: &'static [&'static str],
+    /// Logout must go through the end-session endpoint
+    pub requires_end_session: bool,
+    /// Report the eIDAS level of the authentication
+    pub reports_eidas_level: bool,
+}
+
+/// Generic OpenID Connect provider
+pub const OIDC: IdentityProfile = IdentityProfile {
+    name: "oidc",
+    required_scopes: &["openid"],
+    required_params: &[],
+    requires_nonce: true,
+    allowed_hosts: &[],
+    requires_end_session: false,
+    reports_eidas_level: false,
+};
+
+/// FranceConnect
+pub const FRANCECONNECT: IdentityProfile = IdentityProfile {
+    name: "franceconnect",
+    required_scopes: &["openid", "given_name", "family_name", "email"],
+    // Minimum eIDAS level accepted
+    required_params: &[("acr_values", "eidas1")],
+    requires_nonce: true,
+    allowed_hosts: &["app.franceconnect.gouv.fr", "fcp.integ01.dev-franceconnect.fr"],
+    requires_end_session: true,
+    reports_eidas_level: true,
+};
+
+/// Available profiles
+pub const PROFILES: &[IdentityProfile] = &[OIDC, FRANCECONNECT];
+
+/// Find a profile by name (`None` selects the generic OpenID Connect profile)
+pub fn profile(name: Option<&str>) -> AppResult<&'static IdentityProfile> {
+    let name = name.unwrap_or(OIDC.name);
+    PROFILES
+        .iter()
+        .find(|profile| profile.name == name)
+        .ok_or_else(|| AppError::InvalidArgument(format!("Unknown identity provider profile: {}", name)))
+}
+
+impl IdentityProfile {
+    /// Check that an endpoint is allowed for this profile
+    pub fn check_endpoint(&self, endpoint: &str) -> AppResult<()> {
+        if self.allowed_hosts.is_empty() {
+            return Ok(());
+        }
+        let url = Url::parse(endpoint).map_err(|e| AppError::Config(format!("Invalid endpoint {}: {}", endpoint, e)))?;
+        match url.host_str() {
+            Some(host) if self.allowed_hosts.contains(&host) => Ok(()),
+            _ => Err(AppError::Auth(format!("Endpoint {} is not allowed for {}", endpoint, self.name))),
+        }
+    }
+
+    /// Scopes to request: the required scopes, then the requested ones
+    pub fn scopes(&self, requested: &[String]) -> Vec<String> {
+        let mut scopes: Vec<String> = self.required_scopes.iter().map(|scope| scope.to_string()).collect();
+        for scope in requested {
+            if !scopes.contains(scope) {
+                scopes.push(scope.clone());
+            }
+        }
+        scopes
+    }
+
+    /// Authorization parameters: the requested ones, overridden by the
+    /// required ones
+    pub fn params(&self, requested: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+        let mut params = requested.clone();
+        for (name, value) in self.required_params {
+            params.insert(name.to_string(), value.to_string());
+        }
+        params
+    }
+}
+
+/// eIDAS assurance level of an authentication
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum EidasLevel {
+    /// Low
+    Eidas1,
+    /// Substantial
+    Eidas2,
+    /// High
+    Eidas3,
+}
+
+impl EidasLevel {
+    /// Parse an `acr` claim value
+    pub fn from_acr(acr: &str) -> Option<Self> {
+        serde_json::from_value(Value::String(acr.trim().to_ascii_lowercase())).ok()
+    }
+}
+
+/// Decode the claims of an ID token
+///
+/// The signature is not verified: claims are only used to check the nonce and
+/// report the assurance level, the backend verifies the token.
+pub fn id_token_claims(id_token: &str) -> AppResult<Map<String, Value>> {
+    let payload = id_token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| AppError::Auth("Malformed ID token".to_string()))?;
+    let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|e| AppError::Auth(format!("Malformed ID token: {}", e)))?;
+    serde_json::from_slice(&payload).map_err(|e| AppError::Auth(format!("Malformed ID token: {}", e)))
+}
+
+/// Check the nonce of an ID token
+pub fn check_nonce(claims: &Map<String, Value>, expected: &str) -> AppResult<()> {
+    match claims.get("nonce").and_then(Value::as_str) {
+        Some(nonce) if nonce == expected => Ok(()),
+        _ => Err(AppError::Auth("Login failed: ID token nonce mismatch".to_string())),
+    }
+}
+
+/// eIDAS level of an ID token, if reported
+pub fn eidas_level(claims: &Map<String, Value>) -> Option<EidasLevel> {
+    claims.get("acr").and_then(Value::as_str).and_then(EidasLevel::from_acr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn id_token(claims: Value) -> String {
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        format!("{}.{}.sig", engine.encode(r#"{"alg":"RS256"}"#), engine.encode(claims.to_string()))
+    }
+
+    #[test]
+    fn test_profile_lookup() {
+        assert_eq!(profile(None).unwrap().name, "oidc");
+        assert_eq!(profile(Some("franceconnect")).unwrap(), &FRANCECONNECT);
+        assert!(profile(Some("unknown")).is_err());
+    }
+
+    #[test]
+    fn test_franceconnect_requirements() {
+        let scopes = FRANCECONNECT.scopes(&["email".to_string(), "birthdate".to_string()]);
+        assert_eq!(scopes, vec!["openid", "given_name", "family_name", "email", "birthdate"]);
+
+        let requested = BTreeMap::from([("acr_values".to_string(), "none".to_string())]);
+        assert_eq!(FRANCECONNECT.params(&requested)["acr_values"], "eidas1");
+
+        assert!(FRANCECONNECT.check_endpoint("https://app.franceconnect.gouv.fr/api/v1/token").is_ok());
+        assert!(FRANCECONNECT.check_endpoint("https://idp.example.org/token").is_err());
+        assert!(OIDC.check_endpoint("https://idp.example.org/token").is_ok());
+    }
+
+    #[test]
+    fn test_id_token_claims() {
+        let claims = id_token_claims(&id_token(json!({ "nonce": "n-1", "acr": "eidas2" }))).unwrap();
+        assert_eq!(check_nonce(&claims, "n-1").unwrap(), ());
+        assert!(check_nonce(&claims, "n-2").is_err());
+        assert_eq!(eidas_level(&claims), Some(EidasLevel::Eidas2));
+
+        assert!(id_token_claims("not-a-token").is_err());
+        assert_eq!(EidasLevel::from_acr("urn:other"), None);
+        assert!(EidasLevel::Eidas3 > EidasLevel::Eidas1);
+    }
+}
@@ -0,0 +1,485 @@
+/// Image cache for avatars and document thumbnails
+///
+/// The webview cache does not survive reloads reliably, so avatars and
+/// thumbnails were downloaded again on every load. This module keeps images
+/// on disk, in the app cache directory:
+/// - `cache_image(url)` downloads the image once (`https`, `image/*` content
+///   type, at most `IMAGE_CACHE_MAX_IMAGE_BYTES`, enforced while the body is
+///   read) and returns a local handle
+/// - the handle is read back as a `data:` URL (allowed by the `img-src`
+///   directive of the CSP) to set as `src` of an `<img>`
+/// - the cache is bounded (`IMAGE_CACHE_MAX_BYTES`), least recently used
+///   images are evicted first
+/// - hits, misses and evictions are counted since launch and reported with
+///   the cache size
+///
+/// Images of the application server are fetched with the session token, if
+/// one is stored; other hosts never receive it. Cached images do not expire
+/// and are refreshed by clearing the cache or by a new URL (e.g. a versioned
+/// avatar URL). Use times are kept in memory and saved to the index with the
+/// next stored image, so lookups never write to disk.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use base64::Engine;
+use tauri::{AppHandle, Manager, Url};
+
+use crate::constants;
+use crate::http;
+use crate::runtime_config;
+use crate::session_handoff;
+use crate::{AppError, AppResult};
+
+/// Cached image, as recorded in the cache index
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedImage {
+    /// URL of the image
+    pub url: String,
+    /// Content type of the image
+    pub content_type: String,
+    /// Size of the image (bytes)
+    pub size: u64,
+    /// Time the image was last used (seconds since the Unix epoch)
+    pub last_used_at: u64,
+}
+
+/// Handle of a cached image, returned to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageHandle {
+    /// Local handle of the image
+    pub handle: String,
+    /// Content type of the image
+    pub content_type: String,
+    /// Size of the image (bytes)
+    pub size: u64,
+    /// The image was already cached
+    pub cached: bool,
+}
+
+/// Image cache statistics
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageCacheStats {
+    /// Number of cached images
+    pub entries: usize,
+    /// Total size of the cached images (bytes)
+    pub total_bytes: u64,
+    /// Maximum size of the cache (bytes)
+    pub max_bytes: u64,
+    /// Requests served from the cache since launch
+    pub hits: u64,
+    /// Requests downloading the image since launch
+    pub misses: u64,
+    /// Images evicted since launch
+    pub evictions: u64,
+}
+
+/// Image cache, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct ImageCache {
+    dir: Mutex<Option<PathBuf>>,
+    /// Cached images by handle
+    index: Mutex<BTreeMap<String, CachedImage>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl ImageCache {
+    /// Open the cache stored in `dir`, loading its index
+    pub fn open(&self, dir: PathBuf) {
+        let index = read_index(&dir);
+        tracing::debug!("Image cache opened with {} image(s)", index.len());
+        if let Ok(mut current) = self.index.lock() {
+            *current = index;
+        }
+        if let Ok(mut current) = self.dir.lock() {
+            *current = Some(dir);
+        }
+    }
+
+    fn dir(&self) -> AppResult<PathBuf> {
+        self.dir
+            .lock()
+            .map_err(|e| AppError::Storage(e.to_string()))?
+            .clone()
+            .ok_or_else(|| AppError::Storage("Image cache not initialized".to_string()))
+    }
+
+    /// Look up a cached image, recording a hit and its use at `now`
+    ///
+    /// The use time is only recorded in memory; the index is written when an
+    /// entry whose file disappeared is dropped.
+    ///
+    /// # Returns
+    ///
+    /// Returns the handle, or `None` (recording a miss) if the image is not
+    /// cached.
+    pub fn lookup(&self, url: &str, now: u64) -> Option<ImageHandle> {
+        let handle = handle(url);
+        let dir = self.dir().ok()?;
+        let mut index = self.index.lock().ok()?;
+        let found = match index.get_mut(&handle) {
+            Some(image) if dir.join(&handle).is_file() => {
+                image.last_used_at = now;
+                Some(ImageHandle {
+                    handle: handle.clone(),
+                    content_type: image.content_type.clone(),
+                    size: image.size,
+                    cached: true,
+                })
+            }
+            Some(_) => {
+                index.remove(&handle);
+                if let Err(e) = write_index(&dir, &index) {
+                    tracing::warn!("{}", e);
+                }
+                None
+            }
+            None => None,
+        };
+        match &found {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        found
+    }
+
+    /// Store an image, evicting the least recently used images beyond
+    /// `IMAGE_CACHE_MAX_BYTES`
+    pub fn put(&self, image: CachedImage, body: &[u8]) -> AppResult<ImageHandle> {
+        let dir = self.dir()?;
+        std::fs::create_dir_all(&dir).map_err(|e| AppError::Storage(format!("Failed to create image cache directory: {}", e)))?;
+        let handle = handle(&image.url);
+        std::fs::write(dir.join(&handle), body).map_err(|e| AppError::Storage(format!("Failed to write cached image: {}", e)))?;
+
+        let result = ImageHandle {
+            handle: handle.clone(),
+            content_type: image.content_type.clone(),
+            size: image.size,
+            cached: false,
+        };
+        let mut index = self.index.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        index.insert(handle, image);
+        for evicted in evictions(&index, constants::IMAGE_CACHE_MAX_BYTES) {
+            index.remove(&evicted);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+            if let Err(e) = std::fs::remove_file(dir.join(&evicted)) {
+                tracing::warn!("Failed to delete evicted image: {}", e);
+            }
+        }
+        write_index(&dir, &index)?;
+        Ok(result)
+    }
+
+    /// Read a cached image
+    ///
+    /// # Returns
+    ///
+    /// Returns the index entry and the image, or `None` if the handle is
+    /// unknown or the image was evicted.
+    pub fn read(&self, handle: &str) -> Option<(CachedImage, Vec<u8>)> {
+        let image = self.index.lock().ok()?.get(handle).cloned()?;
+        let body = std::fs::read(self.dir().ok()?.join(handle)).ok()?;
+        Some((image, body))
+    }
+
+    /// Cached images
+    pub fn entries(&self) -> Vec<CachedImage> {
+        self.index
+            .lock()
+            .map(|index| index.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Cache statistics
+    pub fn stats(&self) -> ImageCacheStats {
+        let (entries, total_bytes) = self
+            .index
+            .lock()
+            .map(|index| (index.len(), index.values().map(|image| image.size).sum()))
+            .unwrap_or_default();
+        ImageCacheStats {
+            entries,
+            total_bytes,
+            max_bytes: constants::IMAGE_CACHE_MAX_BYTES,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Remove all cached images
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of removed images, or an error if the cache
+    /// directory could not be deleted.
+    pub fn clear(&self) -> AppResult<usize> {
+        let dir = self.dir()?;
+        let mut index = self.index.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        let count = index.len();
+        index.clear();
+        match std::fs::remove_dir_all(&dir) {
+            Ok(()) => Ok(count),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(count),
+            Err(e) => Err(AppError::Storage(format!("Failed to delete image cache: {}", e))),
+        }
+    }
+}
+
+/// Handle of the image at `url`, also the name of its file
+pub fn handle(url: &str) -> String {
+    ring::digest::digest(&ring::digest::SHA256, url.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn read_index(dir: &Path) -> BTreeMap<String, CachedImage> {
+    let Ok(contents) = std::fs::read_to_string(dir.join(constants::IMAGE_CACHE_INDEX_FILE)) else {
+        return BTreeMap::new();
+    };
+    serde_json::from_str(&contents)
+        .map_err(|e| tracing::warn!("Ignoring invalid image cache index: {}", e))
+        .unwrap_or_default()
+}
+
+fn write_index(dir: &Path, index: &BTreeMap<String, CachedImage>) -> AppResult<()> {
+    std::fs::create_dir_all(dir).map_err(|e| AppError::Storage(format!("Failed to create image cache directory: {}", e)))?;
+    let contents = serde_json::to_vec(index).map_err(|e| AppError::Storage(format!("Failed to serialize image cache index: {}", e)))?;
+    std::fs::write(dir.join(constants::IMAGE_CACHE_INDEX_FILE), contents)
+        .map_err(|e| AppError::Storage(format!("Failed to write image cache index: {}", e)))
+}
+
+/// Images to evict so the cache fits within `max_bytes`, least recently used
+/// first
+pub fn evictions(index: &BTreeMap<String, CachedImage>, max_bytes: u64) -> Vec<String> {
+    let mut total: u64 = index.values().map(|image| image.size).sum();
+    let mut images: Vec<(&String, &CachedImage)> = index.iter().collect();
+    images.sort_by_key(|(_, image)| image.last_used_at);
+
+    let mut evicted = Vec::new();
+    for (handle, image) in images {
+        if total <= max_bytes {
+            break;
+        }
+        total -= image.size;
+        evicted.push(handle.clone());
+    }
+    evicted
+}
+
+/// Returns `true` if the session token may be sent with a request to `url`,
+/// i.e. `url` is on the application server (`app_url`)
+pub fn sends_credentials(url: &Url, app_url: &str) -> bool {
+    Url::parse(app_url).is_ok_and(|app_url| app_url.origin() == url.origin())
+}
+
+/// Returns `true` if a response content type is an image
+pub fn is_image_content_type(content_type: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+        .starts_with("image/")
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Cache an image, downloading it on a miss
+///
+/// # Arguments
+///
+/// * `url` - `https` URL of the image
+///
+/// # Returns
+///
+/// Returns the handle of the image, or an error if the URL is
+/// invalid or the image could not be downloaded.
+pub async fn cache_image(app: &AppHandle, url: &str) -> AppResult<ImageHandle> {
+    let parsed = Url::parse(url).map_err(|e| AppError::InvalidArgument(format!("Invalid image URL: {}", e)))?;
+    if parsed.scheme() != "https" {
+        return Err(AppError::InvalidArgument("Image URL must be https".to_string()));
+    }
+    let cache = app.state::<ImageCache>();
+    if let Some(found) = cache.lookup(url, now_secs()) {
+        return Ok(found);
+    }
+
+    let mut request = http::client()?.get(parsed.clone());
+    if sends_credentials(&parsed, &runtime_config::get().app_url) {
+        if let Some(token) = session_handoff::session_token(app)? {
+            request = request.bearer_auth(token);
+        }
+    }
+    let mut response = request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| AppError::Storage(format!("Image download failed: {}", e)))?;
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    if !is_image_content_type(&content_type) {
+        return Err(AppError::Storage(format!("Not an image: {}", content_type)));
+    }
+    if response
+        .content_length()
+        .is_some_and(|length| length > constants::IMAGE_CACHE_MAX_IMAGE_BYTES)
+    {
+        return Err(AppError::Storage(format!("Image exceeds {} bytes", constants::IMAGE_CACHE_MAX_IMAGE_BYTES)));
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| AppError::Storage(format!("Image download failed: {}", e)))?
+    {
+        // The announced length may be missing or wrong
+        if (body.len() + chunk.len()) as u64 > constants::IMAGE_CACHE_MAX_IMAGE_BYTES {
+            return Err(AppError::Storage(format!("Image exceeds {} bytes", constants::IMAGE_CACHE_MAX_IMAGE_BYTES)));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    let image = CachedImage {
+        url: url.to_string(),
+        content_type,
+        size: body.len() as u64,
+        last_used_at: now_secs(),
+    };
+    cache.put(image, &body)
+}
+
+/// Read a cached image as a `data:` URL
+///
+/// # Returns
+///
+/// Returns the `data:` URL, or an error if the handle is unknown
+/// (e.g. the image was evicted: cache it again).
+pub fn data_url(app: &AppHandle, handle: &str) -> AppResult<String> {
+    let (image, body) = app
+        .state::<ImageCache>()
+        .read(handle)
+        .ok_or_else(|| AppError::InvalidArgument(format!("Unknown image handle: {}", handle)))?;
+    Ok(format!(
+        "data:{};base64,{}",
+        image.content_type,
+        base64::engine::general_purpose::STANDARD.encode(body)
+    ))
+}
+
+/// Clear the cache
+///
+/// # Returns
+///
+/// Returns the number of removed images, or an error if the cache
+/// could not be deleted.
+pub fn clear(app: &AppHandle) -> AppResult<usize> {
+    let count = app.state::<ImageCache>().clear()?;
+    tracing::info!("Image cache cleared ({} image(s))", count);
+    Ok(count)
+}
+
+/// Open the image cache in the app cache directory
+pub fn init(app: &AppHandle) -> AppResult<()> {
+    let dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve cache directory: {}", e)))?
+        .join(constants::IMAGE_CACHE_DIR);
+    app.state::<ImageCache>().open(dir);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(url: &str, size: u64, last_used_at: u64) -> CachedImage {
+        CachedImage {
+            url: url.to_string(),
+            content_type: "image/png".to_string(),
+            size,
+            last_used_at,
+        }
+    }
+
+    #[test]
+    fn test_evictions_least_recently_used_first() {
+        let mut index = BTreeMap::new();
+        index.insert("a".to_string(), image("https://app.elulib.com/a.png", 40, 30));
+        index.insert("b".to_string(), image("https://app.elulib.com/b.png", 40, 10));
+        index.insert("c".to_string(), image("https://app.elulib.com/c.png", 40, 20));
+
+        assert!(evictions(&index, 120).is_empty());
+        assert_eq!(evictions(&index, 100), ["b"]);
+        assert_eq!(evictions(&index, 40), ["b", "c"]);
+    }
+
+    #[test]
+    fn test_lookup_counts_hits_and_misses() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ImageCache::default();
+        cache.open(dir.path().to_path_buf());
+        let url = "https://app.elulib.com/avatars/42.png";
+
+        assert_eq!(cache.lookup(url, 1), None);
+        let stored = cache.put(image(url, 3, 1), b"png").unwrap();
+        assert!(!stored.cached);
+        assert_eq!(stored.handle, handle(url));
+
+        let found = cache.lookup(url, 2).unwrap();
+        assert!(found.cached);
+        assert_eq!(cache.read(&found.handle).unwrap().1, b"png");
+
+        let stats = cache.stats();
+        assert_eq!((stats.entries, stats.total_bytes), (1, 3));
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 1, 0));
+
+        // Lookups do not write the index
+        let index_file = dir.path().join(constants::IMAGE_CACHE_INDEX_FILE);
+        let written = std::fs::read(&index_file).unwrap();
+        cache.lookup(url, 5).unwrap();
+        assert_eq!(std::fs::read(&index_file).unwrap(), written);
+
+        // A missing file is a miss
+        std::fs::remove_file(dir.path().join(handle(url))).unwrap();
+        assert_eq!(cache.lookup(url, 3), None);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_credentials_only_sent_to_the_app_server() {
+        let app_url = "https://app.elulib.com";
+        let url = |url: &str| Url::parse(url).unwrap();
+        assert!(sends_credentials(&url("https://app.elulib.com/avatars/42.png"), app_url));
+        assert!(!sends_credentials(&url("https://cdn.example.com/42.png"), app_url));
+        assert!(!sends_credentials(&url("https://app.elulib.com:8443/42.png"), app_url));
+    }
+
+    #[test]
+    fn test_image_content_types() {
+        assert!(is_image_content_type("image/png"));
+        assert!(is_image_content_type("Image/JPEG; charset=binary"));
+        assert!(!is_image_content_type("text/html"));
+        assert!(!is_image_content_type(""));
+    }
+}
@@ -0,0 +1,70 @@
+/// Android-specific in-app update implementation
+///
+/// This module uses `AppUpdateManager` from the Play Core library to start
+/// flexible and immediate updates.
+///
+/// Note: This implementation provides the structure for Android in-app updates.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use tauri::AppHandle;
+
+use super::UpdateMode;
+use crate::{AppError, AppResult};
+
+/// Start an in-app update flow on Android
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start_update(app: AppHandle, mode: UpdateMode) -> AppResult<()> {
+    tracing::info!("[Android] Starting in-app update: {:?}", mode);
+
+    // TODO: Implement native Android in-app update using Play Core
+    // Progress must be forwarded to `in_app_update::on_progress` and the
+    // outcome to `in_app_update::on_result`.
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val manager = AppUpdateManagerFactory.create(context)
+    // val type = if (immediate) AppUpdateType.IMMEDIATE else AppUpdateType.FLEXIBLE
+    // manager.appUpdateInfo.addOnSuccessListener { info ->
+    //     if (info.updateAvailability() != UpdateAvailability.UPDATE_AVAILABLE || !info.isUpdateTypeAllowed(type)) {
+    //         return@addOnSuccessListener reject("No update available")
+    //     }
+    //     manager.registerListener { state ->
+    //         when (state.installStatus()) {
+    //             InstallStatus.DOWNLOADING -> onUpdateProgress(state.bytesDownloaded(), state.totalBytesToDownload())
+    //             InstallStatus.DOWNLOADED -> onUpdateResult("downloaded", null)
+    //             InstallStatus.INSTALLED -> onUpdateResult("installed", null)
+    //             InstallStatus.FAILED -> onUpdateResult("failed", "error ${state.installErrorCode()}")
+    //             InstallStatus.CANCELED -> onUpdateResult("cancelled", null)
+    //         }
+    //     }
+    //     manager.startUpdateFlowForResult(info, activityResultLauncher, AppUpdateOptions.defaultOptions(type))
+    // }
+    // ```
+
+    // Placeholder: Return an error (no update available)
+    // Replace this with actual native implementation
+    let _ = app;
+    Err(AppError::Platform("No update available".to_string()))
+}
+
+/// Install a downloaded flexible update on Android
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn complete_update() -> AppResult<()> {
+    tracing::info!("[Android] Completing flexible update");
+
+    // TODO: Implement native Android update completion
+    // Example Kotlin implementation:
+    // ```kotlin
+    // AppUpdateManagerFactory.create(context).completeUpdate()
+    // ```
+
+    // Placeholder: Return an error (no downloaded update)
+    // Replace this with actual native implementation
+    Err(AppError::Platform("No downloaded update to install".to_string()))
+}
@@ -0,0 +1,132 @@
+/// Android in-app update flow
+///
+/// This module drives the Play Core in-app update API, so critical fixes reach
+/// devices without users visiting the store:
+/// - a *flexible* update downloads in background while the app stays usable,
+///   then is installed with `complete_flexible_update()` (which restarts the app)
+/// - an *immediate* update shows the full-screen Play update UI
+///
+/// The native listener reports download progress through `on_progress()` and
+/// the outcome through `on_result()`, which are forwarded to the webview as
+/// `update://progress` and `update://result` events.
+///
+/// In-app updates are an Android-only API; other platforms return an error
+/// and should fall back to the store link from `check_app_update`.
+
+#[cfg(target_os = "android")]
+mod android;
+
+use tauri::AppHandle;
+
+use crate::events;
+use crate::{AppError, AppResult};
+
+/// In-app update flow type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateMode {
+    /// Background download, installed on demand
+    Flexible,
+    /// Blocking full-screen update
+    Immediate,
+}
+
+/// Download progress of an in-app update
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProgress {
+    /// Bytes downloaded so far
+    pub bytes_downloaded: u64,
+    /// Total size of the update, in bytes
+    pub total_bytes: u64,
+}
+
+/// Outcome of an in-app update flow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateOutcome {
+    /// The flexible update is downloaded and ready to install
+    Downloaded,
+    /// The update was installed
+    Installed,
+    /// The user declined or cancelled the update
+    Cancelled,
+    /// The update failed
+    Failed,
+}
+
+/// Result of an in-app update flow, emitted as `update://result`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateResult {
+    /// The update flow type
+    pub mode: UpdateMode,
+    /// The outcome of the flow
+    pub outcome: UpdateOutcome,
+    /// Error details, if the update failed
+    pub error: Option<String>,
+}
+
+/// Start an in-app update flow
+///
+/// # Arguments
+///
+/// * `app` - The application handle, used by the native listener to report progress
+/// * `mode` - Flexible or immediate update
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the flow is started, or an error if no update
+/// is available or in-app updates are not supported.
+pub fn start(app: &AppHandle, mode: UpdateMode) -> AppResult<()> {
+    tracing::info!("Starting {:?} in-app update", mode);
+
+    #[cfg(target_os = "android")]
+    {
+        android::start_update(app.clone(), mode)
+    }
+
+    #[cfg(not(target_os = "android"))]
+    {
+        let _ = app;
+        Err(AppError::Platform("In-app updates are not supported on this platform".to_string()))
+    }
+}
+
+/// Install a downloaded flexible update and restart the app
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if no downloaded update is
+/// pending or in-app updates are not supported.
+pub fn complete_flexible() -> AppResult<()> {
+    tracing::info!("Completing flexible in-app update");
+
+    #[cfg(target_os = "android")]
+    {
+        android::complete_update()
+    }
+
+    #[cfg(not(target_os = "android"))]
+    {
+        Err(AppError::Platform("In-app updates are not supported on this platform".to_string()))
+    }
+}
+
+/// Called by the native listener when download progress is reported
+pub fn on_progress(app: &AppHandle, progress: UpdateProgress) {
+    events::emit(app, &progress);
+}
+
+/// Called by the native listener when an update flow finishes
+pub fn on_result(app: &AppHandle, result: UpdateResult) {
+    match result.outcome {
+        UpdateOutcome::Failed => tracing::error!(
+            "In-app update failed: {}",
+            result.error.as_deref().unwrap_or("unknown error")
+        ),
+        outcome => tracing::info!("In-app update {:?}: {:?}", result.mode, outcome),
+    }
+
+    events::emit(app, &result);
+}
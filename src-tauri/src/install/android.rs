@@ -0,0 +1,27 @@
+/// Android-specific install source implementation
+///
+/// This module identifies the installer package with `PackageManager`.
+///
+/// Note: This implementation provides the structure for Android install source.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::InstallSource;
+
+/// Get the source the app was installed from on Android
+pub fn install_source() -> InstallSource {
+    // TODO: Implement native Android lookup
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val installer = if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.R) {
+    //     context.packageManager.getInstallSourceInfo(context.packageName).installingPackageName
+    // } else {
+    //     context.packageManager.getInstallerPackageName(context.packageName)
+    // }
+    // return if (installer == "com.android.vending") "play_store" else "sideload"
+    // ```
+
+    // Placeholder: Report unknown source
+    // Replace this with actual native implementation
+    InstallSource::Unknown
+}
@@ -0,0 +1,24 @@
+/// iOS-specific install source implementation
+///
+/// This module identifies TestFlight builds from the App Store receipt name.
+///
+/// Note: This implementation provides the structure for iOS install source.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::InstallSource;
+
+/// Get the source the app was installed from on iOS
+pub fn install_source() -> InstallSource {
+    // TODO: Implement native iOS lookup
+    // Example Swift implementation:
+    // ```swift
+    // guard let receipt = Bundle.main.appStoreReceiptURL else { return "sideload" }
+    // if receipt.lastPathComponent == "sandboxReceipt" { return "test_flight" }
+    // return FileManager.default.fileExists(atPath: receipt.path) ? "app_store" : "sideload"
+    // ```
+
+    // Placeholder: Report unknown source
+    // Replace this with actual native implementation
+    InstallSource::Unknown
+}
@@ -0,0 +1,302 @@
+/// First-launch and install source detection
+///
+/// This module records install metadata in the app data directory on every
+/// launch, which lets it report:
+/// - whether this is the first launch after install
+/// - when the app was installed (first launch time)
+/// - the previous app version, if the app was updated since the last launch
+/// - where the app was installed from (store, TestFlight, sideload)
+///
+/// The frontend uses this to drive onboarding and data migrations.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::{AppError, AppResult};
+
+/// Source the app was installed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallSource {
+    /// Apple App Store
+    AppStore,
+    /// Apple TestFlight
+    TestFlight,
+    /// Google Play Store
+    PlayStore,
+    /// Installed from outside of a store (APK, Xcode, enterprise MDM, ...)
+    Sideload,
+    /// Unknown source (desktop, development builds)
+    #[default]
+    Unknown,
+}
+
+/// Install metadata persisted between launches
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallRecord {
+    /// Time of the first launch (seconds since the Unix epoch)
+    pub installed_at: u64,
+    /// App version of the last launch
+    pub last_version: String,
+    /// Random identifier of this installation (empty in records written
+    /// before it was introduced)
+    #[serde(default)]
+    pub installation_id: String,
+}
+
+/// Install information reported to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallInfo {
+    /// This is the first launch since the app was installed
+    pub first_launch: bool,
+    /// Time of the first launch (seconds since the Unix epoch)
+    pub installed_at: u64,
+    /// Current app version
+    pub current_version: String,
+    /// App version of the previous launch, if it differs from the current one
+    pub previous_version: Option<String>,
+    /// Source the app was installed from
+    pub source: InstallSource,
+    /// Random identifier of this installation, reset on reinstall
+    pub installation_id: String,
+}
+
+/// Install information of the current launch, managed by the Tauri application
+#[derive(Default)]
+pub struct InstallState {
+    info: Mutex<Option<InstallInfo>>,
+}
+
+impl InstallState {
+    /// Install information recorded at startup, if any
+    pub fn get(&self) -> Option<InstallInfo> {
+        self.info.lock().ok().and_then(|info| info.clone())
+    }
+
+    /// Identifier of this installation, if the launch was recorded
+    pub fn installation_id(&self) -> Option<String> {
+        self.get().map(|info| info.installation_id)
+    }
+}
+
+/// Compute the install information and the record to persist for this launch
+///
+/// # Arguments
+///
+/// * `previous` - Record persisted by the previous launch, if any
+/// * `current_version` - Version of the running app
+/// * `now` - Current time (seconds since the Unix epoch)
+/// * `source` - Source the app was installed from
+/// * `new_installation_id` - Identifier used if the previous record has none
+pub fn resolve(
+    previous: Option<InstallRecord>,
+    current_version: &str,
+    now: u64,
+    source: InstallSource,
+    new_installation_id: String,
+) -> (InstallInfo, InstallRecord) {
+    let first_launch = previous.is_none();
+    let installed_at = previous.as_ref().map_or(now, |record| record.installed_at);
+    let installation_id = previous
+        .as_ref()
+        .map(|record| record.installation_id.clone())
+        .filter(|id| !id.is_empty())
+        .unwrap_or(new_installation_id);
+    let previous_version = previous
+        .map(|record| record.last_version)
+        .filter(|version| version != current_version);
+
+    let info = InstallInfo {
+        first_launch,
+        installed_at,
+        current_version: current_version.to_string(),
+        previous_version,
+        source,
+        installation_id: installation_id.clone(),
+    };
+    let record = InstallRecord {
+        installed_at,
+        last_version: current_version.to_string(),
+        installation_id,
+    };
+    (info, record)
+}
+
+/// Read the install record persisted by the previous launch
+fn read_record(path: &Path) -> Option<InstallRecord> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents)
+        .map_err(|e| tracing::warn!("Ignoring invalid install record: {}", e))
+        .ok()
+}
+
+/// Persist the install record for the next launch
+fn write_record(path: &Path, record: &InstallRecord) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| AppError::Platform(format!("Failed to create data directory: {}", e)))?;
+    }
+    let contents = serde_json::to_string(record)
+        .map_err(|e| AppError::Platform(format!("Failed to serialize install record: {}", e)))?;
+    std::fs::write(path, contents).map_err(|e| AppError::Platform(format!("Failed to write install record: {}", e)))
+}
+
+/// Generate a random installation identifier (128 bits, hex encoded)
+fn generate_installation_id() -> String {
+    use ring::rand::SecureRandom;
+
+    let mut bytes = [0u8; 16];
+    if let Err(e) = ring::rand::SystemRandom::new().fill(&mut bytes) {
+        tracing::warn!("Failed to generate installation id: {:?}", e);
+    }
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Get the source the app was installed from
+pub fn install_source() -> InstallSource {
+    #[cfg(target_os = "ios")]
+    {
+        ios::install_source()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::install_source()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        InstallSource::Unknown
+    }
+}
+
+/// Record the current launch and store the install information in the app state
+///
+/// Must be called once at startup, before the record is overwritten by a
+/// later launch.
+///
+/// # Returns
+///
+/// Returns the `InstallInfo` for this launch, or an error if the
+/// install record cannot be persisted.
+pub fn record_launch(app: &AppHandle) -> AppResult<InstallInfo> {
+    let path = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Platform(format!("Failed to resolve data directory: {}", e)))?
+        .join(constants::INSTALL_RECORD_FILE);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let (info, record) = resolve(
+        read_record(&path),
+        env!("CARGO_PKG_VERSION"),
+        now,
+        install_source(),
+        generate_installation_id(),
+    );
+    write_record(&path, &record)?;
+
+    if info.first_launch {
+        tracing::info!("First launch of version {}", info.current_version);
+    } else if let Some(previous) = &info.previous_version {
+        tracing::info!("App updated from {} to {}", previous, info.current_version);
+    }
+
+    if let Ok(mut stored) = app.state::<InstallState>().info.lock() {
+        *stored = Some(info.clone());
+    }
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_first_launch() {
+        let (info, record) = resolve(None, "1.0.0", 1000, InstallSource::PlayStore, "id".to_string());
+        assert!(info.first_launch);
+        assert_eq!(info.installed_at, 1000);
+        assert_eq!(info.previous_version, None);
+        assert_eq!(info.source, InstallSource::PlayStore);
+        assert_eq!(info.installation_id, "id");
+        assert_eq!(
+            record,
+            InstallRecord {
+                installed_at: 1000,
+                last_version: "1.0.0".to_string(),
+                installation_id: "id".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_same_version() {
+        let previous = InstallRecord {
+            installed_at: 1000,
+            last_version: "1.0.0".to_string(),
+            installation_id: "old".to_string(),
+        };
+        let (info, record) =
+            resolve(Some(previous), "1.0.0", 5000, InstallSource::Unknown, "new".to_string());
+        assert!(!info.first_launch);
+        assert_eq!(info.installed_at, 1000, "Install time should be kept across launches");
+        assert_eq!(info.installation_id, "old", "Installation id should be kept across launches");
+        assert_eq!(info.previous_version, None);
+        assert_eq!(record.installed_at, 1000);
+    }
+
+    #[test]
+    fn test_resolve_after_update() {
+        let previous = InstallRecord {
+            installed_at: 1000,
+            last_version: "1.0.0".to_string(),
+            installation_id: String::new(),
+        };
+        let (info, record) =
+            resolve(Some(previous), "1.1.0", 5000, InstallSource::Unknown, "new".to_string());
+        assert!(!info.first_launch);
+        assert_eq!(info.previous_version.as_deref(), Some("1.0.0"));
+        assert_eq!(record.installation_id, "new", "Legacy records get an installation id");
+        assert_eq!(record.last_version, "1.1.0");
+    }
+
+    #[test]
+    fn test_generate_installation_id() {
+        let id = generate_installation_id();
+        assert_eq!(id.len(), 32);
+        assert_ne!(id, generate_installation_id());
+    }
+
+    #[test]
+    fn test_record_roundtrip() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = dir.path().join("nested").join(constants::INSTALL_RECORD_FILE);
+        assert_eq!(read_record(&path), None);
+
+        let record = InstallRecord {
+            installed_at: 42,
+            last_version: "0.1.0".to_string(),
+            installation_id: "abc".to_string(),
+        };
+        write_record(&path, &record).expect("write_record should succeed");
+        assert_eq!(read_record(&path), Some(record));
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(read_record(&path), None, "Corrupted records should be ignored");
+    }
+}
@@ -0,0 +1,357 @@
+/// Device integrity (root / jailbreak) detection
+///
+/// This module runs a set of heuristics to detect rooted Android devices and
+/// jailbroken iOS devices, so security policy can degrade functionality on
+/// compromised devices.
+///
+/// Heuristics:
+/// - `su` binaries in well-known locations (Android)
+/// - Magisk files and directories (Android)
+/// - System partition mounted read-write (Android)
+/// - Cydia, Sileo and MobileSubstrate paths (iOS)
+/// - Writable paths outside of the app sandbox (iOS)
+///
+/// None of these checks is conclusive on its own and all of them can be
+/// bypassed by a determined attacker. Results should be treated as a signal,
+/// not as proof.
+///
+/// The findings of the binary integrity self-check (`tamper` module) are
+/// reported with the device findings.
+///
+/// The module also detects emulators and simulators (`is_emulator()`), so test
+/// traffic can be segregated from production analytics and policies. Running
+/// in an emulator does not mark the device as compromised.
+
+use std::path::Path;
+
+use crate::tamper;
+
+/// Well-known locations of `su` binaries on rooted Android devices
+const SU_PATHS: &[&str] = &[
+    "/system/bin/su",
+    "/system/xbin/su",
+    "/system/sbin/su",
+    "/sbin/su",
+    "/vendor/bin/su",
+    "/su/bin/su",
+    "/data/local/su",
+    "/data/local/bin/su",
+    "/data/local/xbin/su",
+];
+
+/// Files and directories left by Magisk on Android
+const MAGISK_PATHS: &[&str] = &[
+    "/sbin/.magisk",
+    "/data/adb/magisk",
+    "/data/adb/modules",
+    "/cache/.disable_magisk",
+];
+
+/// Files and directories left by jailbreak tools on iOS
+const JAILBREAK_PATHS: &[&str] = &[
+    "/Applications/Cydia.app",
+    "/Applications/Sileo.app",
+    "/Library/MobileSubstrate/MobileSubstrate.dylib",
+    "/usr/sbin/sshd",
+    "/etc/apt",
+    "/private/var/lib/apt",
+    "/var/jb",
+];
+
+/// Path outside of the iOS sandbox that must not be writable
+const SANDBOX_PROBE_PATH: &str = "/private/elulib_integrity_probe";
+
+/// Files exposed by the Android emulator (QEMU) kernel drivers
+const EMULATOR_DEVICE_PATHS: &[&str] = &["/dev/qemu_pipe", "/dev/goldfish_pipe", "/dev/socket/qemud"];
+
+/// Build property values that identify Android emulator images
+const EMULATOR_BUILD_HINTS: &[&str] = &[
+    "generic",
+    "emulator",
+    "goldfish",
+    "ranchu",
+    "sdk_gphone",
+    "vbox86",
+    "genymotion",
+];
+
+/// Build properties inspected for emulator hints
+const EMULATOR_BUILD_PROPS: &[&str] = &[
+    "ro.build.fingerprint",
+    "ro.hardware",
+    "ro.product.model",
+    "ro.product.device",
+    "ro.kernel.qemu",
+];
+
+/// Environment variables set by the iOS simulator
+const SIMULATOR_ENV_VARS: &[&str] = &["SIMULATOR_UDID", "SIMULATOR_DEVICE_NAME"];
+
+/// Kind of integrity indicator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityIndicator {
+    /// An `su` binary was found
+    SuBinary,
+    /// Magisk files were found
+    Magisk,
+    /// The system partition is mounted read-write
+    WritableSystemPartition,
+    /// Jailbreak tool files were found
+    JailbreakFiles,
+    /// The app was able to write outside of its sandbox
+    SandboxEscape,
+    /// The app is not signed with the expected identity
+    SigningMismatch,
+    /// The app bundle lost its code signature
+    BundleModified,
+    /// A debugger is attached to the app
+    DebuggerAttached,
+}
+
+/// A single integrity finding
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct IntegrityFinding {
+    /// Kind of indicator
+    pub indicator: IntegrityIndicator,
+    /// Path or mount point that triggered the finding
+    pub detail: String,
+}
+
+/// Result of the device integrity checks
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct IntegrityReport {
+    /// `true` if at least one indicator was found
+    pub compromised: bool,
+    /// Findings that triggered the `compromised` flag
+    pub findings: Vec<IntegrityFinding>,
+}
+
+impl IntegrityReport {
+    fn from_findings(findings: Vec<IntegrityFinding>) -> Self {
+        IntegrityReport {
+            compromised: !findings.is_empty(),
+            findings,
+        }
+    }
+}
+
+/// Run the integrity checks for the current platform
+///
+/// # Returns
+///
+/// Returns an `IntegrityReport`. Platforms without checks (desktop) always
+/// report an uncompromised device.
+pub fn check() -> IntegrityReport {
+    #[cfg(target_os = "android")]
+    let mut findings = android_findings(Path::new("/"));
+
+    #[cfg(target_os = "ios")]
+    let mut findings = ios_findings(Path::new("/"));
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    let mut findings = Vec::new();
+
+    findings.extend(tamper::findings());
+    let report = IntegrityReport::from_findings(findings);
+    if report.compromised {
+        tracing::warn!("Device integrity check found {} indicator(s)", report.findings.len());
+    } else {
+        tracing::debug!("Device integrity check passed");
+    }
+    report
+}
+
+/// Run the Android heuristics against the filesystem below `root`
+pub fn android_findings(root: &Path) -> Vec<IntegrityFinding> {
+    let mut findings = find_paths(root, SU_PATHS, IntegrityIndicator::SuBinary);
+    findings.extend(find_paths(root, MAGISK_PATHS, IntegrityIndicator::Magisk));
+    if let Ok(mounts) = std::fs::read_to_string(root.join("proc/mounts")) {
+        findings.extend(writable_system_mounts(&mounts));
+    }
+    findings
+}
+
+/// Run the iOS heuristics against the filesystem below `root`
+pub fn ios_findings(root: &Path) -> Vec<IntegrityFinding> {
+    let mut findings = find_paths(root, JAILBREAK_PATHS, IntegrityIndicator::JailbreakFiles);
+    let probe = root.join(SANDBOX_PROBE_PATH.trim_start_matches('/'));
+    if std::fs::write(&probe, b"").is_ok() {
+        let _ = std::fs::remove_file(&probe);
+        findings.push(IntegrityFinding {
+            indicator: IntegrityIndicator::SandboxEscape,
+            detail: SANDBOX_PROBE_PATH.to_string(),
+        });
+    }
+    findings
+}
+
+/// Returns `true` if the app is running in an emulator or simulator
+///
+/// Always returns `false` on desktop platforms.
+pub fn is_emulator() -> bool {
+    #[cfg(target_os = "android")]
+    let emulator = is_android_emulator(Path::new("/"));
+
+    #[cfg(target_os = "ios")]
+    let emulator = is_ios_simulator();
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    let emulator = false;
+
+    if emulator {
+        tracing::info!("Running in an emulator/simulator");
+    }
+    emulator
+}
+
+/// Detect the Android emulator from the filesystem below `root`
+///
+/// Checks the QEMU device files and the build fingerprint properties in
+/// `system/build.prop`.
+pub fn is_android_emulator(root: &Path) -> bool {
+    let has_device = EMULATOR_DEVICE_PATHS
+        .iter()
+        .any(|path| root.join(path.trim_start_matches('/')).exists());
+    has_device
+        || std::fs::read_to_string(root.join("system/build.prop"))
+            .map(|props| build_props_look_emulated(&props))
+            .unwrap_or(false)
+}
+
+/// Detect the iOS simulator
+///
+/// Simulator builds are identified at compile time (`sim` ABI or x86_64
+/// target) and at runtime by the environment variables set by the simulator.
+pub fn is_ios_simulator() -> bool {
+    cfg!(target_abi = "sim")
+        || cfg!(all(target_os = "ios", target_arch = "x86_64"))
+        || SIMULATOR_ENV_VARS.iter().any(|var| std::env::var_os(var).is_some())
+}
+
+/// Returns `true` if any inspected build property contains an emulator hint
+fn build_props_look_emulated(props: &str) -> bool {
+    props
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .filter(|(key, _)| EMULATOR_BUILD_PROPS.contains(&key.trim()))
+        .any(|(key, value)| {
+            let value = value.trim().to_ascii_lowercase();
+            (key.trim() == "ro.kernel.qemu" && value == "1")
+                || EMULATOR_BUILD_HINTS.iter().any(|hint| value.contains(hint))
+        })
+}
+
+/// Find which of `paths` exist below `root`
+fn find_paths(root: &Path, paths: &[&str], indicator: IntegrityIndicator) -> Vec<IntegrityFinding> {
+    paths
+        .iter()
+        .filter(|path| root.join(path.trim_start_matches('/')).exists())
+        .map(|path| IntegrityFinding {
+            indicator,
+            detail: path.to_string(),
+        })
+        .collect()
+}
+
+/// Find system partitions mounted read-write in a `/proc/mounts` listing
+fn writable_system_mounts(mounts: &str) -> Vec<IntegrityFinding> {
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = fields.nth(1)?;
+            let options = fields.nth(1)?;
+            let is_system = mount_point == "/system" || mount_point == "/";
+            let is_rw = options.split(',').any(|option| option == "rw");
+            (is_system && is_rw).then(|| IntegrityFinding {
+                indicator: IntegrityIndicator::WritableSystemPartition,
+                detail: mount_point.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_paths_detects_existing_files() {
+        let root = tempfile::tempdir().expect("Failed to create temp dir");
+        std::fs::create_dir_all(root.path().join("system/xbin")).unwrap();
+        std::fs::write(root.path().join("system/xbin/su"), b"").unwrap();
+
+        let findings = find_paths(root.path(), SU_PATHS, IntegrityIndicator::SuBinary);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].detail, "/system/xbin/su");
+        assert_eq!(findings[0].indicator, IntegrityIndicator::SuBinary);
+    }
+
+    #[test]
+    fn test_find_paths_clean_root() {
+        let root = tempfile::tempdir().expect("Failed to create temp dir");
+        assert!(find_paths(root.path(), JAILBREAK_PATHS, IntegrityIndicator::JailbreakFiles).is_empty());
+        assert!(find_paths(root.path(), MAGISK_PATHS, IntegrityIndicator::Magisk).is_empty());
+    }
+
+    #[test]
+    fn test_writable_system_mounts() {
+        let mounts = "\
+/dev/block/dm-0 / ext4 ro,seclabel,relatime 0 0
+/dev/block/dm-1 /system ext4 rw,seclabel,relatime 0 0
+/dev/block/dm-2 /data f2fs rw,nosuid,nodev 0 0
+";
+        let findings = writable_system_mounts(mounts);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].detail, "/system");
+
+        let clean = "/dev/block/dm-1 /system ext4 ro,seclabel 0 0\n";
+        assert!(writable_system_mounts(clean).is_empty());
+    }
+
+    #[test]
+    fn test_android_findings_reads_mounts() {
+        let root = tempfile::tempdir().expect("Failed to create temp dir");
+        std::fs::create_dir_all(root.path().join("proc")).unwrap();
+        std::fs::write(root.path().join("proc/mounts"), "/dev/root /system ext4 rw 0 0\n").unwrap();
+
+        let findings = android_findings(root.path());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].indicator, IntegrityIndicator::WritableSystemPartition);
+    }
+
+    #[test]
+    fn test_build_props_look_emulated() {
+        let emulator = "ro.build.fingerprint=google/sdk_gphone64_arm64/emu64a:14/UE1A/1:userdebug/dev-keys\n";
+        assert!(build_props_look_emulated(emulator));
+        assert!(build_props_look_emulated("ro.kernel.qemu=1\n"));
+
+        let device = "\
+ro.build.fingerprint=google/husky/husky:14/UQ1A/11:user/release-keys
+ro.hardware=husky
+ro.product.model=Pixel 8 Pro
+";
+        assert!(!build_props_look_emulated(device));
+    }
+
+    #[test]
+    fn test_is_android_emulator_detects_qemu_pipe() {
+        let root = tempfile::tempdir().expect("Failed to create temp dir");
+        assert!(!is_android_emulator(root.path()));
+
+        std::fs::create_dir_all(root.path().join("dev")).unwrap();
+        std::fs::write(root.path().join("dev/qemu_pipe"), b"").unwrap();
+        assert!(is_android_emulator(root.path()));
+    }
+
+    #[test]
+    fn test_report_from_findings() {
+        assert!(!IntegrityReport::from_findings(Vec::new()).compromised);
+        let report = IntegrityReport::from_findings(vec![IntegrityFinding {
+            indicator: IntegrityIndicator::Magisk,
+            detail: "/data/adb/magisk".to_string(),
+        }]);
+        assert!(report.compromised);
+    }
+}
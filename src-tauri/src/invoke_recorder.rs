@@ -0,0 +1,410 @@
+/// Record and replay of command invocations
+///
+/// Frontend/native interaction bugs are often hard to reproduce by hand. In
+/// debug builds, the invokes can be recorded to a file
+/// (`invoke_recording_start` / `invoke_recording_stop`), one JSON line per
+/// invoke:
+/// - the command, its arguments and when it was invoked
+/// - its outcome (`ok`, `error`, or `rejected` by the invoke guards) and its
+///   duration, taken when the command future completes (`command_metrics`)
+///
+/// Arguments named like secrets (`INVOKE_RECORDING_MASKED_ARGS`) are masked,
+/// and the other values go through the log redaction rules. Completions are
+/// matched to invokes of the same command in order, so the timing of
+/// concurrent invokes of a command may be swapped.
+///
+/// With the `test-harness` feature, `replay` re-executes a recording against
+/// the mock app (`create_test_app()`) and reports the invokes whose outcome
+/// changed.
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use tauri::ipc::{Invoke, InvokeBody};
+use tauri::Runtime;
+
+use crate::constants;
+use crate::redact;
+use crate::{AppError, AppResult};
+
+/// Outcome of a recorded invoke
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvokeOutcome {
+    /// The command succeeded
+    Ok,
+    /// The command failed (`Err`, or a `CommandResponse` with `ok: false`)
+    Error,
+    /// The invoke was rejected before reaching the command
+    Rejected,
+    /// The command had not completed when the recording stopped
+    Pending,
+}
+
+/// Invoke in a recording
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedInvoke {
+    /// Order of the invoke in the recording, from 1
+    pub seq: u64,
+    /// Command name
+    pub command: String,
+    /// Arguments, with secrets masked
+    pub args: Value,
+    /// When the command was invoked (milliseconds since the Unix epoch)
+    pub invoked_at: u64,
+    /// Outcome of the command
+    pub outcome: InvokeOutcome,
+    /// Execution time of the command (milliseconds)
+    pub duration_ms: u64,
+}
+
+/// Summary of a stopped recording
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingSummary {
+    /// Recording file
+    pub path: PathBuf,
+    /// Number of recorded invokes
+    pub invokes: u64,
+}
+
+struct PendingInvoke {
+    entry: RecordedInvoke,
+    started: Instant,
+}
+
+struct Recorder {
+    path: PathBuf,
+    next_seq: u64,
+    pending: VecDeque<PendingInvoke>,
+}
+
+impl Recorder {
+    fn write(&self, entry: &RecordedInvoke) {
+        let line = match serde_json::to_string(entry) {
+            Ok(line) => line,
+            Err(e) => return tracing::warn!("Failed to serialize recorded invoke: {}", e),
+        };
+        let written = std::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", line));
+        if let Err(e) = written {
+            tracing::warn!("Failed to write recorded invoke: {}", e);
+        }
+    }
+}
+
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Mask the secrets of invoke arguments
+pub fn mask_args(args: &Value) -> Value {
+    match args {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| {
+                    let masked = constants::INVOKE_RECORDING_MASKED_ARGS
+                        .iter()
+                        .any(|secret| name.eq_ignore_ascii_case(secret));
+                    let value = if masked && !value.is_null() {
+                        Value::String(redact::MASK.to_string())
+                    } else {
+                        mask_args(value)
+                    };
+                    (name.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(mask_args).collect()),
+        Value::String(text) => Value::String(redact::redact(text)),
+        other => other.clone(),
+    }
+}
+
+/// Start recording the invokes to a file, replacing its contents
+pub fn start(path: PathBuf) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| AppError::Storage(format!("Failed to create recording directory: {}", e)))?;
+    }
+    std::fs::write(&path, b"").map_err(|e| AppError::Storage(format!("Failed to create recording: {}", e)))?;
+    let mut recorder = RECORDER.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+    *recorder = Some(Recorder {
+        path,
+        next_seq: 1,
+        pending: VecDeque::new(),
+    });
+    tracing::info!("Invoke recording started");
+    Ok(())
+}
+
+/// Stop recording, writing the invokes still running as `pending`
+///
+/// # Returns
+///
+/// Returns the recording file and its number of invokes, or `None` if no
+/// recording was running.
+pub fn stop() -> AppResult<Option<RecordingSummary>> {
+    let Some(mut recorder) = RECORDER.lock().map_err(|e| AppError::Storage(e.to_string()))?.take() else {
+        return Ok(None);
+    };
+    while let Some(pending) = recorder.pending.pop_front() {
+        let mut entry = pending.entry;
+        entry.outcome = InvokeOutcome::Pending;
+        entry.duration_ms = pending.started.elapsed().as_millis() as u64;
+        recorder.write(&entry);
+    }
+    tracing::info!("Invoke recording stopped");
+    Ok(Some(RecordingSummary {
+        path: recorder.path,
+        invokes: recorder.next_seq - 1,
+    }))
+}
+
+/// Whether a recording is running
+pub fn is_recording() -> bool {
+    RECORDER.lock().map(|recorder| recorder.is_some()).unwrap_or_default()
+}
+
+/// Record an invoke reaching its command
+pub fn begin(command: &str, args: &Value) {
+    let Ok(mut guard) = RECORDER.lock() else {
+        return;
+    };
+    let Some(recorder) = guard.as_mut() else {
+        return;
+    };
+    let entry = RecordedInvoke {
+        seq: recorder.next_seq,
+        command: command.to_string(),
+        args: mask_args(args),
+        invoked_at: now_ms(),
+        outcome: InvokeOutcome::Pending,
+        duration_ms: 0,
+    };
+    recorder.next_seq += 1;
+    recorder.pending.push_back(PendingInvoke {
+        entry,
+        started: Instant::now(),
+    });
+}
+
+/// JSON arguments of an invoke (`null` for raw payloads)
+fn invoke_args<R: Runtime>(invoke: &Invoke<R>) -> Value {
+    match invoke.message.payload() {
+        InvokeBody::Json(args) => args.clone(),
+        InvokeBody::Raw(_) => Value::Null,
+    }
+}
+
+/// Record an invoke reaching its command, if recording
+pub fn begin_invoke<R: Runtime>(invoke: &Invoke<R>) {
+    if is_recording() {
+        begin(invoke.message.command(), &invoke_args(invoke));
+    }
+}
+
+/// Record an invoke rejected by the invoke guards, if recording
+pub fn reject_invoke<R: Runtime>(invoke: &Invoke<R>) {
+    if is_recording() {
+        reject(invoke.message.command(), &invoke_args(invoke));
+    }
+}
+
+/// Record an invoke rejected by the invoke guards
+pub fn reject(command: &str, args: &Value) {
+    let Ok(mut guard) = RECORDER.lock() else {
+        return;
+    };
+    let Some(recorder) = guard.as_mut() else {
+        return;
+    };
+    let entry = RecordedInvoke {
+        seq: recorder.next_seq,
+        command: command.to_string(),
+        args: mask_args(args),
+        invoked_at: now_ms(),
+        outcome: InvokeOutcome::Rejected,
+        duration_ms: 0,
+    };
+    recorder.next_seq += 1;
+    recorder.write(&entry);
+}
+
+/// Record the completion of a command, matched to its oldest running invoke
+pub fn complete(command: &str, elapsed: Duration, ok: bool) {
+    let Ok(mut guard) = RECORDER.lock() else {
+        return;
+    };
+    let Some(recorder) = guard.as_mut() else {
+        return;
+    };
+    let Some(index) = recorder.pending.iter().position(|pending| pending.entry.command == command) else {
+        return;
+    };
+    if let Some(pending) = recorder.pending.remove(index) {
+        let mut entry = pending.entry;
+        entry.outcome = if ok { InvokeOutcome::Ok } else { InvokeOutcome::Error };
+        entry.duration_ms = elapsed.as_millis() as u64;
+        recorder.write(&entry);
+    }
+}
+
+/// Read a recording, ordered by invoke
+pub fn read_recording(path: &Path) -> AppResult<Vec<RecordedInvoke>> {
+    let contents = std::fs::read_to_string(path).map_err(|e| AppError::Storage(format!("Failed to read recording: {}", e)))?;
+    let mut entries = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(|e| AppError::Storage(format!("Invalid recorded invoke: {}", e))))
+        .collect::<AppResult<Vec<RecordedInvoke>>>()?;
+    entries.sort_by_key(|entry| entry.seq);
+    Ok(entries)
+}
+
+/// Invoke whose replayed outcome differs from the recording
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayMismatch {
+    /// Order of the invoke in the recording
+    pub seq: u64,
+    /// Command name
+    pub command: String,
+    /// Recorded outcome
+    pub expected: InvokeOutcome,
+    /// Replayed outcome
+    pub actual: InvokeOutcome,
+    /// Replayed response or rejection
+    pub response: Value,
+}
+
+/// Result of a replay
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayReport {
+    /// Number of replayed invokes
+    pub replayed: usize,
+    /// Invokes not replayed: rejected or pending in the recording, or
+    /// commands not registered on the mock app
+    pub skipped: usize,
+    /// Invokes whose outcome changed
+    pub mismatches: Vec<ReplayMismatch>,
+}
+
+/// Outcome of a replayed invoke, from its response or rejection
+pub fn replayed_outcome(response: &Result<Value, Value>) -> InvokeOutcome {
+    match response {
+        Ok(body) => match body.get("ok").and_then(Value::as_bool) {
+            Some(false) => InvokeOutcome::Error,
+            _ => InvokeOutcome::Ok,
+        },
+        Err(_) => InvokeOutcome::Error,
+    }
+}
+
+/// Re-execute a recording against the mock app, in order
+///
+/// Only the completed invokes of commands registered on the mock app
+/// (`TEST_APP_COMMANDS`) are replayed.
+#[cfg(feature = "test-harness")]
+pub fn replay(
+    webview: &tauri::WebviewWindow<tauri::test::MockRuntime>,
+    entries: &[RecordedInvoke],
+) -> ReplayReport {
+    let mut report = ReplayReport::default();
+    for entry in entries {
+        let replayable = matches!(entry.outcome, InvokeOutcome::Ok | InvokeOutcome::Error)
+            && crate::TEST_APP_COMMANDS.contains(&entry.command.as_str());
+        if !replayable {
+            report.skipped += 1;
+            continue;
+        }
+        let response = crate::invoke_test_command(webview, &entry.command, entry.args.clone());
+        let actual = replayed_outcome(&response);
+        report.replayed += 1;
+        if actual != entry.outcome {
+            report.mismatches.push(ReplayMismatch {
+                seq: entry.seq,
+                command: entry.command.clone(),
+                expected: entry.outcome,
+                actual,
+                response: response.unwrap_or_else(|rejection| rejection),
+            });
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_secret_args_are_masked() {
+        let args = json!({
+            "key": "api_token",
+            "value": "s3cr3t",
+            "options": { "password": "hunter2", "label": "Agenda" },
+            "empty": { "token": null },
+        });
+        let masked = mask_args(&args);
+        assert_eq!(masked["key"], json!("api_token"));
+        assert_eq!(masked["value"], json!(redact::MASK));
+        assert_eq!(masked["options"]["password"], json!(redact::MASK));
+        assert_eq!(masked["options"]["label"], json!("Agenda"));
+        assert_eq!(masked["empty"]["token"], Value::Null);
+    }
+
+    #[test]
+    fn test_recording_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(constants::INVOKE_RECORDING_FILE);
+
+        start(path.clone()).unwrap();
+        begin("recorder_test_a", &json!({ "n": 1 }));
+        begin("recorder_test_b", &json!({}));
+        begin("recorder_test_a", &json!({ "n": 2 }));
+        reject("recorder_test_c", &json!({}));
+        complete("recorder_test_a", Duration::from_millis(12), true);
+        complete("recorder_test_b", Duration::from_millis(3), false);
+        let summary = stop().unwrap().unwrap();
+        assert_eq!(summary.invokes, 4);
+        assert!(!is_recording());
+
+        let entries = read_recording(&path).unwrap();
+        let outcomes: Vec<_> = entries.iter().map(|entry| (entry.seq, entry.outcome)).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                (1, InvokeOutcome::Ok),
+                (2, InvokeOutcome::Error),
+                (3, InvokeOutcome::Pending),
+                (4, InvokeOutcome::Rejected),
+            ]
+        );
+        assert_eq!(entries[0].args, json!({ "n": 1 }));
+        assert_eq!(entries[0].duration_ms, 12);
+    }
+
+    #[test]
+    fn test_replayed_outcome() {
+        assert_eq!(replayed_outcome(&Ok(json!({ "ok": true, "data": 1 }))), InvokeOutcome::Ok);
+        assert_eq!(replayed_outcome(&Ok(json!({ "ok": false }))), InvokeOutcome::Error);
+        assert_eq!(replayed_outcome(&Ok(json!([1, 2]))), InvokeOutcome::Ok);
+        assert_eq!(replayed_outcome(&Err(json!("Invalid path"))), InvokeOutcome::Error);
+    }
+}
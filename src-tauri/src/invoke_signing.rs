@@ -0,0 +1,249 @@
+/// HMAC-signed invoke requests module
+///
+/// Optional request signing for sensitive commands (`SIGNED_COMMANDS`),
+/// enabled with the `signed_invoke` feature flag, so scripts injected into the
+/// page cannot call them even from the trusted origin:
+/// - a new per-session secret is generated whenever a page starts loading
+/// - the trusted frontend retrieves it once with the `signing_handshake`
+///   command, first thing when its bundle runs; later handshakes are rejected
+///   until the next page load
+/// - each sensitive call carries an `__hmac` argument
+///   (`{ nonce, mac }`), where `mac` is the hex HMAC-SHA256 of
+///   `<command>\n<nonce>\n<arguments as JSON with sorted keys, without __hmac>`
+///   and `nonce` is strictly increasing within the session (no replay)
+///
+/// Unsigned or invalid calls are rejected and logged.
+
+use std::sync::Mutex;
+
+use base64::Engine;
+use ring::hmac;
+use ring::rand::SecureRandom;
+use serde_json::Value;
+use tauri::ipc::{Invoke, InvokeBody};
+use tauri::{Manager, Runtime};
+
+use crate::feature_flags;
+use crate::{AppError, AppResult};
+
+/// Commands requiring a signed payload when signing is enabled
+pub const SIGNED_COMMANDS: &[&str] = &[
+    "keychain_store",
+    "keychain_retrieve",
+    "keychain_remove",
+    "keychain_exists",
+];
+
+/// Argument carrying the signature
+pub const SIGNATURE_ARG: &str = "__hmac";
+
+/// Signature of an invoke request
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct Signature {
+    /// Strictly increasing request counter
+    pub nonce: u64,
+    /// Hex-encoded HMAC-SHA256
+    pub mac: String,
+}
+
+/// Signing session of the current page
+struct SigningSession {
+    secret: Vec<u8>,
+    shared: bool,
+    last_nonce: Option<u64>,
+}
+
+/// Signing state, managed by the Tauri application
+#[derive(Default)]
+pub struct InvokeSigning {
+    session: Mutex<Option<SigningSession>>,
+}
+
+/// Message authenticated by the signature
+fn signed_message(command: &str, nonce: u64, args: &Value) -> String {
+    // serde_json maps are sorted by key, so the serialization is canonical
+    format!("{}\n{}\n{}", command, nonce, args)
+}
+
+/// Compute the hex signature of a request (used by tests and tooling)
+pub fn sign(secret: &[u8], command: &str, nonce: u64, args: &Value) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+    hmac::sign(&key, signed_message(command, nonce, args).as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+impl InvokeSigning {
+    /// Start a new signing session with a random secret
+    pub fn rotate(&self) -> AppResult<()> {
+        let mut secret = vec![0u8; 32];
+        ring::rand::SystemRandom::new()
+            .fill(&mut secret)
+            .map_err(|_| AppError::Denied("Failed to generate signing secret".to_string()))?;
+        self.rotate_with(secret)
+    }
+
+    fn rotate_with(&self, secret: Vec<u8>) -> AppResult<()> {
+        *self.session.lock().map_err(|e| AppError::Denied(e.to_string()))? = Some(SigningSession {
+            secret,
+            shared: false,
+            last_nonce: None,
+        });
+        Ok(())
+    }
+
+    /// Share the session secret with the frontend, once per session
+    ///
+    /// # Returns
+    ///
+    /// Returns the base64-encoded secret, or an error if it was
+    /// already shared.
+    pub fn handshake(&self) -> AppResult<String> {
+        let mut session = self.session.lock().map_err(|e| AppError::Denied(e.to_string()))?;
+        let session = session.as_mut().ok_or_else(|| AppError::Denied("No signing session".to_string()))?;
+        if session.shared {
+            return Err(AppError::Denied("Signing secret already shared for this page".to_string()));
+        }
+        session.shared = true;
+        Ok(base64::engine::general_purpose::STANDARD.encode(&session.secret))
+    }
+
+    /// Verify the signature of a request
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - Name of the invoked command
+    /// * `payload` - Invoke arguments, including the signature argument
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the signature is valid, or an error.
+    pub fn verify(&self, command: &str, payload: &Value) -> AppResult<()> {
+        let mut args = payload
+            .as_object()
+            .cloned()
+            .ok_or_else(|| AppError::Denied(format!("Command {} rejected: arguments are not an object", command)))?;
+        let signature: Signature = args
+            .remove(SIGNATURE_ARG)
+            .and_then(|signature| serde_json::from_value(signature).ok())
+            .ok_or_else(|| AppError::Denied(format!("Command {} rejected: missing signature", command)))?;
+        let mac = decode_hex(&signature.mac)
+            .ok_or_else(|| AppError::Denied(format!("Command {} rejected: malformed signature", command)))?;
+
+        let mut session = self.session.lock().map_err(|e| AppError::Denied(e.to_string()))?;
+        let session = session
+            .as_mut()
+            .filter(|session| session.shared)
+            .ok_or_else(|| AppError::Denied(format!("Command {} rejected: no signing handshake", command)))?;
+        if session.last_nonce.is_some_and(|last| signature.nonce <= last) {
+            return Err(AppError::Denied(format!("Command {} rejected: replayed nonce {}", command, signature.nonce)));
+        }
+
+        let key = hmac::Key::new(hmac::HMAC_SHA256, &session.secret);
+        let message = signed_message(command, signature.nonce, &Value::Object(args));
+        hmac::verify(&key, message.as_bytes(), &mac)
+            .map_err(|_| AppError::Denied(format!("Command {} rejected: invalid signature", command)))?;
+        session.last_nonce = Some(signature.nonce);
+        Ok(())
+    }
+}
+
+/// Validate the signature of an IPC invocation, if required
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command may run, or an error to reject the
+/// invocation with.
+pub fn check_invoke<R: Runtime>(invoke: &Invoke<R>) -> AppResult<()> {
+    let command = invoke.message.command();
+    if !SIGNED_COMMANDS.contains(&command) {
+        return Ok(());
+    }
+    let app = invoke.message.webview_ref().app_handle();
+    if !feature_flags::is_enabled(app, feature_flags::SIGNED_INVOKE) {
+        return Ok(());
+    }
+
+    let result = match invoke.message.payload() {
+        InvokeBody::Json(payload) => app.state::<InvokeSigning>().verify(command, payload),
+        InvokeBody::Raw(_) => Err(AppError::Denied(format!("Command {} rejected: unsigned raw payload", command))),
+    };
+    if let Err(e) = &result {
+        tracing::warn!("{}", e);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SECRET: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+    fn session() -> InvokeSigning {
+        let signing = InvokeSigning::default();
+        signing.rotate_with(SECRET.to_vec()).unwrap();
+        signing
+    }
+
+    fn signed(command: &str, nonce: u64, args: Value) -> Value {
+        let mac = sign(SECRET, command, nonce, &args);
+        let mut payload = args;
+        payload[SIGNATURE_ARG] = json!({ "nonce": nonce, "mac": mac });
+        payload
+    }
+
+    #[test]
+    fn test_handshake_once_per_session() {
+        let signing = session();
+        let secret = signing.handshake().unwrap();
+        assert_eq!(base64::engine::general_purpose::STANDARD.decode(secret).unwrap(), SECRET);
+        assert!(signing.handshake().is_err());
+
+        signing.rotate().unwrap();
+        assert!(signing.handshake().is_ok(), "A new page gets a new secret");
+    }
+
+    #[test]
+    fn test_valid_signature_is_accepted_once() {
+        let signing = session();
+        let payload = signed("keychain_retrieve", 1, json!({ "key": "token" }));
+        assert!(signing.verify("keychain_retrieve", &payload).is_err(), "No handshake yet");
+
+        signing.handshake().unwrap();
+        assert_eq!(signing.verify("keychain_retrieve", &payload).unwrap(), ());
+        assert!(signing.verify("keychain_retrieve", &payload).is_err(), "Replay");
+        let next = signed("keychain_retrieve", 2, json!({ "key": "token" }));
+        assert_eq!(signing.verify("keychain_retrieve", &next).unwrap(), ());
+    }
+
+    #[test]
+    fn test_invalid_signatures_are_rejected() {
+        let signing = session();
+        signing.handshake().unwrap();
+
+        let mut tampered = signed("keychain_retrieve", 1, json!({ "key": "token" }));
+        tampered["key"] = json!("other");
+        assert!(signing.verify("keychain_retrieve", &tampered).is_err());
+
+        let other_command = signed("keychain_remove", 1, json!({ "key": "token" }));
+        assert!(signing.verify("keychain_retrieve", &other_command).is_err());
+
+        assert!(signing.verify("keychain_retrieve", &json!({ "key": "token" })).is_err());
+        let malformed = json!({ "key": "token", "__hmac": { "nonce": 1, "mac": "zz" } });
+        assert!(signing.verify("keychain_retrieve", &malformed).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+/// Android-specific keyboard observer
+///
+/// This module listens to the IME `WindowInsets` of the webview and forwards
+/// the keyboard height to the tracker.
+///
+/// Note: This implementation provides the structure for the Android keyboard observer.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use tauri::AppHandle;
+use crate::AppResult;
+
+/// Start the keyboard observer on Android
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start_observer(app: AppHandle) -> AppResult<()> {
+    tracing::info!("[Android] Starting keyboard observer");
+
+    // TODO: Implement native Android keyboard observer using WindowInsetsCompat
+    // Each IME inset change must be converted to CSS pixels (divide by the
+    // display density) and forwarded to `keyboard::on_keyboard_frame`.
+    // Example Kotlin implementation:
+    // ```kotlin
+    // ViewCompat.setOnApplyWindowInsetsListener(webView) { view, insets ->
+    //     val ime = insets.getInsets(WindowInsetsCompat.Type.ime()).bottom
+    //     val nav = insets.getInsets(WindowInsetsCompat.Type.navigationBars()).bottom
+    //     onKeyboardFrame(max(0, ime - nav) / view.resources.displayMetrics.density.toDouble())
+    //     insets
+    // }
+    // ```
+
+    // Placeholder: Return success without observing
+    // Replace this with actual native implementation
+    let _ = app;
+    Ok(())
+}
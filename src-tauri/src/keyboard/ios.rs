@@ -0,0 +1,42 @@
+/// iOS-specific keyboard observer
+///
+/// This module observes `UIKeyboardWillChangeFrameNotification` and forwards
+/// the part of the keyboard frame covering the webview to the tracker.
+///
+/// Note: This implementation provides the structure for the iOS keyboard observer.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use tauri::AppHandle;
+use crate::AppResult;
+
+/// Start the keyboard observer on iOS
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start_observer(app: AppHandle) -> AppResult<()> {
+    tracing::info!("[iOS] Starting keyboard observer");
+
+    // TODO: Implement native iOS keyboard observer using NotificationCenter
+    // Each frame change must be forwarded to `keyboard::on_keyboard_frame`
+    // with the height covering the webview, in points (CSS pixels).
+    // Example Swift implementation:
+    // ```swift
+    // NotificationCenter.default.addObserver(forName: UIResponder.keyboardWillChangeFrameNotification,
+    //                                        object: nil, queue: .main) { note in
+    //     guard let frame = note.userInfo?[UIResponder.keyboardFrameEndUserInfoKey] as? CGRect else { return }
+    //     let local = webView.convert(frame, from: nil)
+    //     on_keyboard_frame(max(0, webView.bounds.maxY - local.minY))
+    // }
+    // NotificationCenter.default.addObserver(forName: UIResponder.keyboardWillHideNotification,
+    //                                        object: nil, queue: .main) { _ in
+    //     on_keyboard_frame(0)
+    // }
+    // ```
+
+    // Placeholder: Return success without observing
+    // Replace this with actual native implementation
+    let _ = app;
+    Ok(())
+}
@@ -0,0 +1,115 @@
+/// Software keyboard tracking
+///
+/// The native keyboard observers (`UIKeyboardWillChangeFrameNotification` on
+/// iOS, the IME `WindowInsets` on Android) forward the height of the keyboard
+/// over the webview to `on_keyboard_frame`. The webview receives a
+/// `keyboard://changed` event when the keyboard is shown, hidden or resized,
+/// so the frontend can keep the focused field and its bottom bars visible.
+/// The observers are registered by `start`, during setup.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::sync::Mutex;
+
+use tauri::AppHandle;
+
+use crate::events::{self, KeyboardChanged};
+use crate::AppResult;
+
+/// Tracks the keyboard height, to emit only actual changes
+#[derive(Debug, Default)]
+pub struct KeyboardTracker {
+    height: f64,
+}
+
+impl KeyboardTracker {
+    /// Record a keyboard height (CSS pixels)
+    ///
+    /// # Returns
+    ///
+    /// Returns the change to emit, or `None` if the keyboard did not move by
+    /// at least one pixel.
+    pub fn update(&mut self, height: f64) -> Option<KeyboardChanged> {
+        let height = if height.is_finite() { height.max(0.0).round() } else { 0.0 };
+        if (self.height - height).abs() < 1.0 {
+            return None;
+        }
+        self.height = height;
+        Some(KeyboardChanged {
+            visible: height > 0.0,
+            height,
+        })
+    }
+}
+
+static TRACKER: Mutex<KeyboardTracker> = Mutex::new(KeyboardTracker { height: 0.0 });
+
+/// Handle a keyboard frame change from the native observers
+///
+/// # Arguments
+///
+/// * `height` - Height of the keyboard over the webview (CSS pixels, 0 when hidden)
+pub fn on_keyboard_frame(app: &AppHandle, height: f64) {
+    let change = match TRACKER.lock() {
+        Ok(mut tracker) => tracker.update(height),
+        Err(e) => {
+            tracing::warn!("Keyboard tracker unavailable: {}", e);
+            return;
+        }
+    };
+    if let Some(change) = change {
+        tracing::debug!("Keyboard {} ({}px)", if change.visible { "shown" } else { "hidden" }, change.height);
+        events::emit(app, &change);
+    }
+}
+
+/// Start the native keyboard observer
+pub fn start(app: &AppHandle) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::start_observer(app.clone())
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::start_observer(app.clone())
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = app;
+        tracing::debug!("Keyboard observer not available on this platform");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_changes_are_reported() {
+        let mut tracker = KeyboardTracker::default();
+        assert_eq!(tracker.update(0.0), None, "Hidden at launch");
+        assert_eq!(
+            tracker.update(291.4),
+            Some(KeyboardChanged {
+                visible: true,
+                height: 291.0
+            })
+        );
+        assert_eq!(tracker.update(291.2), None, "Sub-pixel move");
+        assert_eq!(tracker.update(335.0).map(|change| change.height), Some(335.0));
+        assert_eq!(
+            tracker.update(f64::NAN),
+            Some(KeyboardChanged {
+                visible: false,
+                height: 0.0
+            })
+        );
+    }
+}
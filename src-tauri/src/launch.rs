@@ -0,0 +1,203 @@
+/// Notification launch payload
+///
+/// When the app is launched by tapping a notification, the push reaches the
+/// native layer before any page is loaded. Instead of being emitted into the
+/// void, or routed by navigating the webview once the page has loaded, the
+/// original payload is kept for the frontend:
+/// - a tap received before the first page load is kept as the launch payload
+///   (`on_notification_tap`, called by `push::on_push`), in place of the
+///   `notification://received` event and the deep link; the launch options
+///   (iOS) and the launch intent (Android) are fed through the same pipeline
+///   (`push::on_platform_push`)
+/// - the page takes it with `get_launch_payload` once loaded and routes to
+///   its link itself; the payload is returned once, later calls (and reloaded
+///   pages) get `null`
+/// - a payload not taken within `LAUNCH_PAYLOAD_MAX_AGE_SECS` is dropped
+/// - if the app goes to the background before the page took it, the payload
+///   is handed to the `outbox` as a `notification://received` event, so it
+///   is still delivered once
+///
+/// Taps received while a page is loaded go through the usual event and deep
+/// link (see `push`).
+
+use std::sync::Mutex;
+
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::deep_link::{self, DeepLink, DeepLinkSource, DeepLinkState};
+use crate::events::NotificationReceived;
+use crate::outbox;
+
+/// Notification that launched the app, returned by `get_launch_payload`
+#[derive(Debug, Clone, PartialEq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchPayload {
+    /// Original data of the push notification
+    pub payload: Value,
+    /// Notification title
+    pub title: String,
+    /// Notification body text
+    pub body: String,
+    /// Deep link of the notification, if it has a valid one
+    pub link: Option<DeepLink>,
+    /// When the notification was tapped (seconds since the Unix epoch)
+    pub received_at: u64,
+}
+
+impl LaunchPayload {
+    /// Whether the payload can still be delivered at `now`
+    pub fn is_fresh(&self, now: u64) -> bool {
+        now.saturating_sub(self.received_at) <= constants::LAUNCH_PAYLOAD_MAX_AGE_SECS
+    }
+
+    /// Event delivering the payload through the outbox
+    pub fn to_event(&self) -> NotificationReceived {
+        NotificationReceived {
+            title: self.title.clone(),
+            body: self.body.clone(),
+            link: self.link.as_ref().map(|link| link.url.clone()),
+            tapped: true,
+        }
+    }
+}
+
+/// Launch payload waiting for the page, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct LaunchState {
+    pending: Mutex<Option<LaunchPayload>>,
+}
+
+impl LaunchState {
+    /// Keep the launch payload, replacing a previous one
+    pub fn keep(&self, payload: LaunchPayload) {
+        if let Ok(mut pending) = self.pending.lock() {
+            // Only the last tap is relevant
+            *pending = Some(payload);
+        }
+    }
+
+    /// Keep the payload of a notification tapped before the first page load
+    ///
+    /// # Returns
+    ///
+    /// Returns `false`, without keeping the payload, if a page is already
+    /// loaded.
+    pub fn keep_if_launching(&self, deep_links: &DeepLinkState, payload: LaunchPayload) -> bool {
+        if deep_links.is_ready() {
+            return false;
+        }
+        self.keep(payload);
+        true
+    }
+
+    /// Take the launch payload, unless it has expired
+    pub fn take(&self, now: u64) -> Option<LaunchPayload> {
+        self.pending
+            .lock()
+            .ok()?
+            .take()
+            .filter(|payload| payload.is_fresh(now))
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Keep a notification tapped during a cold start as the launch payload
+///
+/// # Returns
+///
+/// Returns `false` if a page is already loaded: the tap must then be
+/// delivered as an event and a deep link.
+pub fn on_notification_tap(app: &AppHandle, payload: &Value, title: &str, body: &str, link: Option<&str>) -> bool {
+    let deep_links = app.state::<DeepLinkState>();
+    if deep_links.is_ready() {
+        return false;
+    }
+    let link = link.and_then(|link| {
+        deep_link::parse(link, DeepLinkSource::Notification)
+            .map_err(|e| tracing::warn!("{}", e))
+            .ok()
+    });
+    let payload = LaunchPayload {
+        payload: payload.clone(),
+        title: title.to_string(),
+        body: body.to_string(),
+        link,
+        received_at: now_secs(),
+    };
+    let kept = app.state::<LaunchState>().keep_if_launching(&deep_links, payload);
+    if kept {
+        tracing::info!("App launched from a notification");
+    }
+    kept
+}
+
+/// Take the launch payload, for the page
+pub fn take(app: &AppHandle) -> Option<LaunchPayload> {
+    let payload = app.state::<LaunchState>().take(now_secs());
+    if payload.is_some() {
+        tracing::info!("Launch payload delivered to the page");
+    }
+    payload
+}
+
+/// Hand a launch payload the page did not take to the outbox, when the app
+/// goes to the background
+pub fn on_background(app: &AppHandle) {
+    if let Some(payload) = take(app) {
+        outbox::post(app, &payload.to_event());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn payload(received_at: u64) -> LaunchPayload {
+        LaunchPayload {
+            payload: json!({ "aps": { "alert": { "title": "Reminder" } }, "url": "elulib://agenda/42" }),
+            title: "Reminder".to_string(),
+            body: "Session at 10:00".to_string(),
+            link: deep_link::parse("elulib://agenda/42", DeepLinkSource::Notification).ok(),
+            received_at,
+        }
+    }
+
+    #[test]
+    fn test_launch_payload_is_taken_once() {
+        let state = LaunchState::default();
+        assert_eq!(state.take(1_000), None);
+        state.keep(payload(1_000));
+        assert_eq!(state.take(1_001), Some(payload(1_000)));
+        assert_eq!(state.take(1_002), None, "Delivered exactly once");
+
+        state.keep(payload(1_000));
+        assert_eq!(state.take(1_001 + constants::LAUNCH_PAYLOAD_MAX_AGE_SECS), None, "Expired");
+    }
+
+    #[test]
+    fn test_tap_before_first_page_load_is_the_launch_payload() {
+        let deep_links = DeepLinkState::default();
+        let state = LaunchState::default();
+        assert!(state.keep_if_launching(&deep_links, payload(1_000)));
+
+        deep_links.set_ready();
+        assert!(!state.keep_if_launching(&deep_links, payload(1_001)), "Page loaded: event and deep link");
+        assert_eq!(state.take(1_002), Some(payload(1_000)));
+    }
+
+    #[test]
+    fn test_launch_payload_event_keeps_the_link() {
+        let event = payload(1_000).to_event();
+        assert_eq!(event.link.as_deref(), Some("elulib://agenda/42"));
+        assert!(event.tapped);
+    }
+}
@@ -1,31 +1,145 @@
+
 /// Application error type
 ///
-/// This enum represents all possible errors that can occur in the application.
-/// Errors are automatically converted from Tauri errors using the `From` trait.
-///
-/// # Future Improvements
+/// This enum represents the errors that can occur in the application, by
+/// subsystem. Errors from Tauri, connectivity checks, the keystore plugin,
+/// I/O and JSON serialization are converted with the `From` trait; the other
+/// variants carry a description.
 ///
-/// Consider adding error variants for:
-/// - Connectivity errors (`ConnectivityError`)
-/// - Keychain validation errors
-/// - Structured keychain operation errors
+/// Each variant has a stable numeric `code` which is part of the frontend
+/// contract: commands returning `AppResult` reject with
+/// `{ code, message }` instead of a bare string. Codes are never reused.
 ///
-/// Note: Tauri commands currently return `Result<T, String>`, so errors are
-/// converted to strings for serialization. To use structured errors in commands,
-/// they would need to implement serialization (e.g., via `serde::Serialize`).
+/// Modules and commands return `AppResult`. There is no conversion from or
+/// to `String`, so an error always gets a subsystem and is never flattened
+/// by accident.
 ///
 /// # Examples
 ///
 /// ```rust,no_run
 /// use elulib_mobile::AppError;
 /// let error = AppError::Tauri(tauri::Error::FailedToReceiveMessage);
-/// println!("Error: {}", error);
+/// println!("Error {}: {}", error.code(), error);
 /// ```
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
     /// Error originating from the Tauri framework
     #[error("Tauri runtime error: {0}")]
     Tauri(#[from] tauri::Error),
+
+    /// Keychain/keystore validation or operation error
+    #[error("Keychain error: {0}")]
+    Keychain(String),
+
+    /// Native notification error
+    #[error("Notification error: {0}")]
+    Notification(String),
+
+    /// Connectivity check error
+    #[error("Connectivity error: {0}")]
+    Connectivity(#[from] connectivity::ConnectivityError),
+
+    /// Invalid or unavailable configuration
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    /// File system error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// JSON serialization or deserialization error
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    /// Invalid command or function argument
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    /// Encrypted local database error (database, sync, outbox, drafts)
+    #[error("Database error: {0}")]
+    Database(String),
+
+    /// App file storage error (files, vault, caches, exports)
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    /// HTTP, WebSocket or Server-Sent Events error
+    #[error("Network error: {0}")]
+    Network(String),
+
+    /// Login, passkey, device binding or attestation error
+    #[error("Authentication error: {0}")]
+    Auth(String),
+
+    /// Operation refused by a security policy (locked session, origin,
+    /// signature, reserved key)
+    #[error("Not allowed: {0}")]
+    Denied(String),
+
+    /// Native platform API unavailable or failed
+    #[error("Platform error: {0}")]
+    Platform(String),
+}
+
+impl AppError {
+    /// Stable numeric code of the error, by variant
+    pub fn code(&self) -> u16 {
+        match self {
+            AppError::Tauri(_) => 1000,
+            AppError::Keychain(_) => 1100,
+            AppError::Notification(_) => 1200,
+            AppError::Connectivity(_) => 1300,
+            AppError::Config(_) => 1400,
+            AppError::Io(_) => 1500,
+            AppError::Serialization(_) => 1600,
+            AppError::InvalidArgument(_) => 1700,
+            AppError::Database(_) => 1800,
+            AppError::Storage(_) => 1900,
+            AppError::Network(_) => 2000,
+            AppError::Auth(_) => 2100,
+            AppError::Denied(_) => 2200,
+            AppError::Platform(_) => 2300,
+        }
+    }
+
+    /// Rejection payload, `{ "code": 1400, "message": "Configuration error: ..." }`
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({ "code": self.code(), "message": self.to_string() })
+    }
+}
+
+impl From<tauri_plugin_keystore::Error> for AppError {
+    fn from(error: tauri_plugin_keystore::Error) -> Self {
+        AppError::Keychain(error.to_string())
+    }
+}
+
+/// Rejection of a command returning `AppResult`
+///
+/// Tauri converts the error in the `command` span of the command, which is
+/// then recorded as failed in `command_metrics`.
+impl From<AppError> for tauri::ipc::InvokeError {
+    fn from(error: AppError) -> Self {
+        tracing_bridge::fail_command();
+        tauri::ipc::InvokeError(error.to_json())
+    }
+}
+
+/// Rejection of a command returning `AppResult`, as serialized
+#[derive(specta::Type)]
+#[specta(rename = "AppError")]
+#[allow(dead_code)]
+struct AppErrorPayload {
+    /// Stable numeric code (`AppError::code`)
+    code: u16,
+    /// Description of the error
+    message: String,
+}
+
+impl specta::Type for AppError {
+    fn inline(types: &mut specta::TypeCollection, generics: specta::Generics) -> specta::datatype::DataType {
+        AppErrorPayload::inline(types, generics)
+    }
 }
 
 /// Result type for application operations
@@ -43,21 +157,304 @@ pub enum AppError {
 /// ```
 pub type AppResult<T> = Result<T, AppError>;
 
+/// Accessibility status reporting module
+pub mod accessibility;
+
+/// Privacy-respecting analytics module
+pub mod analytics;
+
+/// Native API versioning module
+pub mod api_version;
+
+/// App update availability module
+pub mod app_update;
+
+/// Encrypted diagnostic artifacts module
+pub mod artifacts;
+
+/// Native attachment viewer module
+pub mod attachments;
+
+/// Offline asset cache module
+pub mod asset_cache;
+
+/// Device attestation (Play Integrity / App Attest) module
+pub mod attestation;
+
+/// Tamper-evident security audit log module
+pub mod audit_log;
+
+/// Native OAuth / OpenID Connect login module
+pub mod auth;
+
+/// Native audio recording module
+pub mod audio;
+
+/// Automatic app badge reset module
+pub mod badge;
+
+/// Keychain and bridge micro-benchmarks module
+pub mod benchmark;
+
+/// TypeScript bindings of the commands module
+pub mod bindings;
+
+/// Biometric / device credential authentication module
+pub mod biometrics;
+
+/// Breadcrumb trail module
+pub mod breadcrumbs;
+
+/// Carrier and SIM information module
+pub mod carrier;
+
+/// Clipboard with auto-expiring sensitive copies module
+pub mod clipboard;
+
+/// Per-command execution metrics module
+pub mod command_metrics;
+
+/// Command response envelope module
+pub mod command_response;
+
+/// Per-command capability scoping module
+pub mod command_scope;
+
 /// Application commands module
 pub mod commands;
 
 /// Application constants module
 pub mod constants;
 
+/// Crash reporting module
+pub mod crash;
+
+/// Content-Security-Policy injection module
+pub mod csp;
+
+/// Deep-link forwarding module
+pub mod deep_link;
+
+/// Debug-only webview tools module
+#[cfg(debug_assertions)]
+pub mod debug_tools;
+
+/// Diagnostics export module
+pub mod diagnostics;
+
+/// Encrypted local database module
+pub mod database;
+
+/// Personal data export module
+pub mod data_export;
+
+/// Hardware-backed device-binding key module
+pub mod device_binding;
+
+/// Device trust policy module
+pub mod device_policy;
+
+/// Device information module
+pub mod device_info;
+
+/// Device lock-screen security module
+pub mod device_security;
+
+/// Resumable download manager module
+pub mod downloads;
+
+/// Form draft autosave module
+pub mod drafts;
+
+/// Mock backends for desktop development builds
+#[cfg(feature = "dev-mock")]
+pub mod dev_mock;
+
 /// Connectivity check module
 pub mod connectivity;
 
+/// Backend environment profiles module
+pub mod environment;
+
+/// Session-scoped ephemeral secrets module
+pub mod ephemeral;
+
+/// Native to webview events module
+pub mod events;
+
+/// Runtime log level module
+pub mod log_level;
+
+/// Remote log shipping module
+pub mod log_shipping;
+
+/// Feature flag evaluation module
+pub mod feature_flags;
+
+/// Sandboxed app file storage module
+pub mod file_storage;
+
+/// Gesture (shake) detection module
+pub mod gestures;
+
+/// Shared HTTP client module
+pub mod http;
+
+/// Identity provider profiles (FranceConnect) module
+pub mod identity_provider;
+
+/// Image cache module
+pub mod image_cache;
+
+/// Android in-app update flow module
+pub mod in_app_update;
+
+/// First-launch and install source detection module
+pub mod install;
+
+/// Device integrity (root / jailbreak) detection module
+pub mod integrity;
+
+/// HMAC-signed invoke requests module
+pub mod invoke_signing;
+
+/// Invoke record and replay module
+pub mod invoke_recorder;
+
+/// Software keyboard tracking module
+pub mod keyboard;
+
+/// Notification launch payload module
+pub mod launch;
+
+/// App lifecycle event forwarding module
+pub mod lifecycle;
+
+/// System locale change handling module
+pub mod locale;
+
+/// Network interface details module
+pub mod network_info;
+
+/// Background log file writer module
+pub mod logging;
+
+/// Native module registry module
+pub mod native_module;
+
+/// Injected JavaScript SDK module
+pub mod native_sdk;
+
 /// Notification bridge module
+#[cfg(feature = "module-notifications")]
 pub mod notification_bridge;
 
+/// Binary payload encoding module
+pub mod payload_encoding;
+
+/// Passkey (WebAuthn platform authenticator) bridge module
+pub mod passkey;
+
+/// Command origin validation module
+pub mod origin_guard;
+
 /// Platform-specific notifications module
 pub mod notifications;
 
+/// Durable event outbox module
+pub mod outbox;
+
+/// Lazy plugin initialization module
+pub mod plugins;
+
+/// Content prefetching module
+pub mod prefetch;
+
+/// Non-sensitive preferences module
+pub mod prefs;
+
+/// Push payload routing module
+pub mod push;
+
+/// Command rate limiting module
+pub mod rate_limit;
+
+/// Log redaction module
+pub mod redact;
+
+/// Signed remote configuration module
+pub mod remote_config;
+
+/// Runtime configuration file module
+pub mod runtime_config;
+
+/// Session token handoff module
+pub mod session_handoff;
+
+/// Inactivity auto-lock with re-authentication module
+pub mod session_lock;
+
+/// Native share sheet module
+pub mod share;
+
+/// Siri Shortcuts and App Actions module
+#[cfg(feature = "module-shortcuts")]
+pub mod shortcuts;
+
+/// Share target (files shared with the app) module
+pub mod share_target;
+
+/// Native Server-Sent Events subscriber module
+#[cfg(feature = "module-sse")]
+pub mod sse;
+
+/// Startup performance metrics module
+pub mod startup_metrics;
+
+/// Persisted app state snapshot and restore module
+pub mod state_restore;
+
+/// Storage and memory information module
+pub mod storage_info;
+
+/// Background data sync module
+pub mod sync;
+
+/// Binary integrity self-check module
+pub mod tamper;
+
+/// Webview TLS pinning module
+pub mod tls_pinning;
+
+/// Tracing to log bridge module
+pub mod tracing_bridge;
+
+/// Force-upgrade gate module
+pub mod upgrade_gate;
+
+/// Upload queue module
+pub mod uploads;
+
+/// Encrypted document vault module
+pub mod vault;
+
+/// Watch companion data channel module
+#[cfg(feature = "module-watch")]
+pub mod watch;
+
+/// Main thread watchdog module
+pub mod watchdog;
+
+/// Native WebSocket client module
+#[cfg(feature = "module-realtime")]
+pub mod websocket;
+
+/// Local data wipe module
+pub mod wipe;
+
+/// Unified permission manager module
+pub mod permissions;
+
 /// Builds and returns a configured Tauri application builder
 ///
 /// This function creates a Tauri application builder that can be
@@ -71,13 +468,23 @@ pub mod notifications;
 ///
 /// Logging is automatically configured via `tauri-plugin-log` with:
 /// - Standard output (stdout) for console logging
-/// - Log directory for persistent file logging
+/// - Log directory for persistent file logging, written by a background thread
 /// - Webview console for in-app logging
+/// - Secrets (tokens, emails, keychain values) masked in every message (`redact`)
+///
+/// A Content-Security-Policy is injected into the remote frontend by the
+/// `csp` module.
 ///
-/// Secure storage (keychain) is configured via `tauri-plugin-keystore`:
+/// Secure storage (keychain) is provided by `tauri-plugin-keystore`, which is
+/// initialized lazily on first use to keep it off the cold start path (see the
+/// `plugins` module):
 /// - iOS: Uses Keychain Services for secure data storage
 /// - Android: Uses Android Keystore for secure data storage
 ///
+/// Native subsystems (keychain, notifications, connectivity, realtime, SSE) are
+/// registered as native modules, each behind a `module-*` cargo feature (see
+/// the `native_module` module).
+///
 /// # Returns
 ///
 /// A `tauri::Builder` instance ready for configuration
@@ -92,17 +499,390 @@ pub mod notifications;
 pub fn create_app() -> tauri::Builder<tauri::Wry> {
     use tauri_plugin_log::{Target, TargetKind};
     
-    tauri::Builder::default()
+    // Bound the disk space used by the log file
+    logging::configure(logging::Rotation {
+        max_file_bytes: constants::LOG_MAX_FILE_BYTES,
+        max_files: constants::LOG_MAX_FILES,
+        max_age: std::time::Duration::from_secs(constants::LOG_MAX_AGE_SECS),
+    });
+    
+    let builder = tauri::Builder::default()
         .plugin(
             tauri_plugin_log::Builder::new()
+                // Mask secrets before messages reach any target
+                .format(|out, message, record| {
+                    redact::format(out, message, record, &[
+                        // Log file, written off the calling thread
+                        logging::record,
+                        // Upload to support, with the user's consent
+                        log_shipping::record,
+                        // Keep the last log lines as breadcrumbs for crash reports
+                        crash::record_log,
+                    ])
+                })
+                // Let everything through the plugin, the level is set at runtime
+                .level(log::LevelFilter::Trace)
+                .filter(log_level::enabled)
                 .targets([
                     Target::new(TargetKind::Stdout),
-                    Target::new(TargetKind::LogDir { file_name: None }),
                     Target::new(TargetKind::Webview),
                 ])
                 .build(),
         )
-        .plugin(tauri_plugin_keystore::init())
+        // Enforce a Content-Security-Policy on the remote frontend
+        .plugin(csp::plugin())
+        // Provide window.EluNative to every page
+        .plugin(native_sdk::plugin())
+        // Native modules of the build, started during setup
+        .manage(native_module::NativeModules::default());
+    manage_state(builder)
+}
+
+/// Register the state managed by the application on a builder
+fn manage_state<R: tauri::Runtime>(builder: tauri::Builder<R>) -> tauri::Builder<R> {
+    let builder = builder
+        .manage(plugins::LazyPlugins::default())
+        .manage(analytics::Analytics::default())
+        .manage(asset_cache::AssetCache::default())
+        .manage(audio::AudioRecorder::default())
+        .manage(audit_log::AuditLog::default())
+        .manage(auth::AuthState::default())
+        .manage(badge::BadgeState::default())
+        .manage(clipboard::ClipboardState::default())
+        .manage(connectivity::ConnectivityProber::default())
+        .manage(crash::CrashState::default())
+        .manage(database::Database::default())
+        .manage(deep_link::DeepLinkState::default())
+        .manage(downloads::DownloadManager::default())
+        .manage(prefetch::Prefetcher::default())
+        .manage(device_policy::DevicePolicy::default())
+        .manage(ephemeral::EphemeralStore::default())
+        .manage(gestures::GestureState::default())
+        .manage(image_cache::ImageCache::default())
+        .manage(outbox::Outbox::default())
+        .manage(install::InstallState::default())
+        .manage(invoke_signing::InvokeSigning::default())
+        .manage(launch::LaunchState::default())
+        .manage(lifecycle::LifecycleTracker::default())
+        .manage(state_restore::StateStore::default())
+        .manage(sync::SyncState::default())
+        .manage(uploads::UploadQueue::default())
+        .manage(vault::Vault::default())
+        .manage(session_handoff::SessionHandoff::default())
+        .manage(session_lock::SessionLock::default())
+        .manage(rate_limit::RateLimiter::default())
+        .manage(prefs::Prefs::default())
+        .manage(remote_config::ConfigStore::default())
+        .manage(feature_flags::FeatureFlags::default());
+    // State of the native modules of the build
+    #[cfg(feature = "module-realtime")]
+    let builder = builder.manage(websocket::WebSocketClient::default());
+    #[cfg(feature = "module-sse")]
+    let builder = builder.manage(sse::SseClient::default());
+    #[cfg(feature = "module-watch")]
+    let builder = builder.manage(watch::WatchState::default());
+    #[cfg(feature = "module-shortcuts")]
+    let builder = builder.manage(shortcuts::ShortcutState::default());
+    builder
+}
+
+/// Commands of `run()` taking the Wry runtime, in registration order
+///
+/// The single list behind the command handler (`app_handler!`) and the
+/// TypeScript bindings (`bindings::functions`): `app_commands!(callback)`
+/// expands to `callback! { .. }` with the list, `#[cfg]` attributes included.
+macro_rules! app_commands {
+    ($callback:ident) => {
+        $callback! {
+            #[cfg(feature = "module-connectivity")]
+            commands::check_connectivity,
+            #[cfg(feature = "module-connectivity")]
+            commands::check_connectivity_quick,
+            commands::permission_check,
+            commands::permission_request,
+            commands::permission_status_all,
+            commands::start_recording,
+            commands::stop_recording,
+            commands::device_security_status,
+            commands::device_integrity,
+            commands::device_info,
+            commands::attest_device,
+            commands::accessibility_status,
+            commands::storage_info,
+            commands::network_interfaces,
+            commands::carrier_info,
+            commands::install_info,
+            commands::update_app_state,
+            commands::get_restored_state,
+            commands::session_activity,
+            commands::unlock_session,
+            commands::session_locked,
+            commands::check_app_update,
+            commands::start_flexible_update,
+            commands::start_immediate_update,
+            commands::complete_flexible_update,
+            commands::get_config,
+            commands::feature_enabled,
+            commands::get_last_crash,
+            commands::send_crash_reports,
+            commands::discard_crash_reports,
+            commands::set_crash_reporting_enabled,
+            commands::launched_after_crash,
+            commands::track_event,
+            commands::set_analytics_enabled,
+            commands::analytics_enabled,
+            commands::startup_metrics,
+            commands::acknowledge_inbox,
+            commands::get_badge_reset_policy,
+            commands::set_badge_reset_policy,
+            commands::current_locale,
+            #[cfg(feature = "module-notifications")]
+            notification_bridge::show_notification,
+            #[cfg(feature = "module-notifications")]
+            notification_bridge::request_notification_permission,
+            #[cfg(feature = "module-notifications")]
+            notification_bridge::check_notification_permission,
+            #[cfg(feature = "module-notifications")]
+            notification_bridge::is_notification_supported,
+            commands::signing_handshake,
+            commands::db_execute,
+            commands::db_query,
+            commands::claim_session_token,
+            commands::auth_login,
+            commands::auth_logout,
+            commands::clipboard_copy,
+            commands::passkey_register,
+            commands::passkey_authenticate,
+            commands::rate_limit_metrics,
+            commands::export_security_log,
+            commands::device_binding_assertion,
+            commands::device_policy,
+            commands::ephemeral_store,
+            commands::ephemeral_get,
+            commands::log_storage_usage,
+            commands::set_log_level,
+            commands::get_log_level,
+            commands::export_logs,
+            commands::set_log_shipping,
+            commands::log_shipping_status,
+            commands::generate_diagnostics,
+            commands::add_breadcrumb,
+            commands::read_recent_logs,
+            commands::get_metrics,
+            commands::clear_asset_cache,
+            commands::sync_now,
+            commands::file_write,
+            commands::file_read,
+            commands::file_delete,
+            commands::file_list,
+            commands::download_start,
+            commands::download_pause,
+            commands::download_resume,
+            commands::download_cancel,
+            commands::download_list,
+            commands::upload_enqueue,
+            commands::upload_retry,
+            commands::upload_cancel,
+            commands::upload_list,
+            commands::cache_image,
+            commands::read_cached_image,
+            commands::get_image_cache_stats,
+            commands::clear_image_cache,
+            commands::prefs_get,
+            commands::prefs_set,
+            commands::prefs_remove,
+            commands::export_user_data,
+            commands::wipe_all_local_data,
+            commands::open_attachment,
+            commands::vault_import,
+            commands::vault_list,
+            commands::vault_read,
+            commands::vault_open,
+            commands::vault_remove,
+            commands::prefetch_set,
+            commands::prefetch_status,
+            commands::draft_save,
+            commands::draft_load,
+            commands::draft_delete,
+            #[cfg(feature = "dev-mock")]
+            commands::dev_mock_state,
+            #[cfg(feature = "dev-mock")]
+            commands::dev_mock_push,
+            #[cfg(debug_assertions)]
+            commands::invoke_recording_start,
+            #[cfg(debug_assertions)]
+            commands::invoke_recording_stop,
+            #[cfg(debug_assertions)]
+            commands::open_devtools,
+            #[cfg(debug_assertions)]
+            commands::reload_webview,
+            #[cfg(debug_assertions)]
+            commands::navigate,
+            commands::environment_get,
+            #[cfg(debug_assertions)]
+            commands::environment_set,
+            #[cfg(debug_assertions)]
+            commands::debug_set_offline,
+            #[cfg(debug_assertions)]
+            commands::debug_fake_push,
+            #[cfg(debug_assertions)]
+            commands::debug_test_notifications,
+            #[cfg(debug_assertions)]
+            commands::debug_corrupt_keychain,
+            #[cfg(debug_assertions)]
+            commands::debug_crash,
+            #[cfg(debug_assertions)]
+            commands::simulate_push,
+            #[cfg(debug_assertions)]
+            commands::benchmark_keychain,
+            #[cfg(debug_assertions)]
+            commands::benchmark_ping,
+            #[cfg(feature = "module-realtime")]
+            commands::ws_connect,
+            #[cfg(feature = "module-realtime")]
+            commands::ws_send,
+            #[cfg(feature = "module-realtime")]
+            commands::ws_close,
+            #[cfg(feature = "module-realtime")]
+            commands::ws_status,
+            commands::db_query_packed,
+            commands::negotiate_api,
+            commands::outbox_ready,
+            commands::outbox_ack,
+            #[cfg(feature = "module-sse")]
+            commands::sse_subscribe,
+            #[cfg(feature = "module-sse")]
+            commands::sse_unsubscribe,
+            #[cfg(feature = "module-sse")]
+            commands::sse_subscriptions,
+            #[cfg(feature = "module-notifications")]
+            commands::get_launch_payload,
+            #[cfg(feature = "module-shortcuts")]
+            commands::donate_shortcut,
+        }
+    };
+}
+pub(crate) use app_commands;
+
+/// Command handler of the application
+///
+/// Registers the commands generic over the runtime, followed by the given
+/// ones. The test app (`create_test_app()`) registers the generic commands
+/// only, since the others need the Wry runtime.
+macro_rules! app_handler {
+    ($($(#[$meta:meta])* $command:path),* $(,)?) => {
+        tauri::generate_handler![
+            // Keep in sync with `TEST_APP_COMMANDS`
+            #[cfg(feature = "module-keychain")]
+            commands::keychain_store,
+            #[cfg(feature = "module-keychain")]
+            commands::keychain_retrieve,
+            #[cfg(feature = "module-keychain")]
+            commands::keychain_remove,
+            #[cfg(feature = "module-keychain")]
+            commands::keychain_exists,
+            $($(#[$meta])* $command,)*
+        ]
+    };
+}
+
+/// Wrap a command handler with the invoke middleware of the application
+///
+/// Rejects sensitive commands invoked from an untrusted page or without a
+/// valid signature, commands whose scope is disabled, commands releasing
+/// secrets while the session is locked or disabled by the device policy, and
+/// commands over their rate limit. The other commands run in a `command`
+/// span, which Tauri keeps open until the command completes and which
+/// measures it (`command_metrics`).
+fn guard_invokes<R: tauri::Runtime>(
+    handler: impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        // Correlate the native logs of an SDK call with its request id
+        if let Some(request_id) = native_sdk::request_id(&invoke) {
+            tracing::debug!("Invoke {} (request {})", invoke.message.command(), request_id);
+        }
+        // Rejected with the `AppError`, or with the JSON string of the rate limit
+        let rejection = origin_guard::check_invoke(&invoke)
+            .and_then(|_| invoke_signing::check_invoke(&invoke))
+            .and_then(|_| command_scope::check_invoke(&invoke))
+            .and_then(|_| session_lock::check_invoke(&invoke))
+            .and_then(|_| device_policy::check_invoke(&invoke))
+            .map_err(|e| e.to_json())
+            .and_then(|_| rate_limit::check_invoke(&invoke).map_err(|rejected| serde_json::json!(rejected.to_json())));
+        if let Err(rejection) = rejection {
+            #[cfg(debug_assertions)]
+            invoke_recorder::reject_invoke(&invoke);
+            invoke.resolver.reject(rejection);
+            return true;
+        }
+        #[cfg(debug_assertions)]
+        invoke_recorder::begin_invoke(&invoke);
+        // A root span: the Tauri IPC spans around it hold the request payload
+        let span = tracing::info_span!(parent: None, tracing_bridge::COMMAND_SPAN, name = invoke.message.command());
+        let _entered = span.enter();
+        handler(invoke)
+    }
+}
+
+/// Builds the application on the Tauri mock runtime, for integration tests
+///
+/// The app has the managed state of `create_app()`, its command handler and
+/// invoke middleware (`guard_invokes`), and the mock backends of the
+/// `dev-mock` feature (keystore, notifications, push), without the log and
+/// CSP plugins or the setup steps. Commands run on the mock runtime only if
+/// they are generic over the runtime: the keychain commands for now.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let app = elulib_mobile::create_test_app();
+/// let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+///     .build()
+///     .unwrap();
+/// ```
+#[cfg(feature = "test-harness")]
+pub fn create_test_app() -> tauri::App<tauri::test::MockRuntime> {
+    manage_state(tauri::test::mock_builder())
+        .invoke_handler(guard_invokes(app_handler![]))
+        .build(tauri::test::mock_context(tauri::test::noop_assets()))
+        .expect("Failed to build the test app")
+}
+
+/// Commands registered on the test app (`create_test_app()`)
+#[cfg(feature = "test-harness")]
+pub const TEST_APP_COMMANDS: &[&str] = &[
+    "keychain_store",
+    "keychain_retrieve",
+    "keychain_remove",
+    "keychain_exists",
+];
+
+/// Invokes a command on a webview of the test app, as the frontend would
+///
+/// # Returns
+///
+/// Returns the command response, or the rejection of the invoke (unknown
+/// command, invalid arguments, or a command returning `Err`).
+#[cfg(feature = "test-harness")]
+pub fn invoke_test_command(
+    webview: &tauri::WebviewWindow<tauri::test::MockRuntime>,
+    command: &str,
+    args: serde_json::Value,
+) -> Result<serde_json::Value, serde_json::Value> {
+    tauri::test::get_ipc_response(
+        webview,
+        tauri::webview::InvokeRequest {
+            cmd: command.to_string(),
+            callback: tauri::ipc::CallbackFn(0),
+            error: tauri::ipc::CallbackFn(1),
+            url: "http://tauri.localhost".parse().expect("Invalid test URL"),
+            body: tauri::ipc::InvokeBody::Json(args),
+            headers: Default::default(),
+            invoke_key: tauri::test::INVOKE_KEY.to_string(),
+        },
+    )
+    .map(|body| body.deserialize().expect("Invalid command response"))
 }
 
 /// Runs the Tauri application
@@ -134,66 +914,245 @@ pub fn create_app() -> tauri::Builder<tauri::Wry> {
 /// ```
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() -> AppResult<()> {
-    log::info!("Initializing Tauri application");
+    use tauri::Manager;
     
-    create_app()
-        .invoke_handler(tauri::generate_handler![
-            commands::keychain_store,
-            commands::keychain_retrieve,
-            commands::keychain_remove,
-            commands::keychain_exists,
-            commands::check_connectivity,
-            commands::check_connectivity_quick,
-            notification_bridge::show_notification,
-            notification_bridge::request_notification_permission,
-            notification_bridge::check_notification_permission,
-            notification_bridge::is_notification_supported,
-        ])
-        .setup(|_app| {
-            log::debug!("Setting up application");
+    startup_metrics::record_process_start();
+    
+    // Forward tracing events and spans to the log plugin targets
+    tracing_bridge::install();
+    
+    // Persist panics instead of letting the process die with stderr output only
+    crash::install_panic_hook();
+    
+    tracing::info!("Initializing Tauri application");
+    
+    let handler = app_commands!(app_handler);
+    
+    let builder = create_app()
+        .invoke_handler(guard_invokes(handler))
+        .on_page_load(|webview, payload| {
+            if let tauri::webview::PageLoadEvent::Started = payload.event() {
+                // Each page gets its own signing secret and session token handoff
+                if let Err(e) = webview.state::<invoke_signing::InvokeSigning>().rotate() {
+                    tracing::error!("{}", e);
+                }
+                webview.state::<session_handoff::SessionHandoff>().open();
+                // The new page negotiates its own API version, and receives
+                // events once it signals it is ready
+                api_version::reset();
+                webview.state::<outbox::Outbox>().set_ready(false);
+            }
+            if let tauri::webview::PageLoadEvent::Finished = payload.event() {
+                startup_metrics::mark(Some(webview.app_handle()), startup_metrics::StartupPhase::FirstPageLoad);
+                plugins::prewarm(webview.app_handle());
+                deep_link::on_first_page_load(webview.app_handle());
+            }
+        })
+        .setup(|app| {
+            tracing::debug!("Setting up application");
+            startup_metrics::mark(Some(app.handle()), startup_metrics::StartupPhase::WebviewCreated);
+            
+            // Apply the runtime configuration file before anything reads the constants it overrides
+            runtime_config::load(app.handle());
+            let config = runtime_config::get();
+            let redaction = redact::ScrubRules::with_extra(&config.redaction_patterns, &config.redaction_fields);
+            if let Err(e) = redact::configure(&redaction) {
+                tracing::error!("{}; using built-in log redaction", e);
+            }
+            
+            // Apply the log level chosen by the user, if any
+            match app.path().app_data_dir() {
+                Ok(dir) => log_level::load(&dir, runtime_config::get().log_level),
+                Err(e) => tracing::warn!("Failed to resolve data directory: {}", e),
+            }
+            
+            // Start writing buffered log lines to the log file
+            match app.path().app_log_dir() {
+                Ok(dir) => {
+                    let file_name = format!("{}.log", app.handle().package_info().name);
+                    if let Err(e) = logging::start(dir, &file_name) {
+                        tracing::warn!("{}", e);
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to resolve log directory: {}", e),
+            }
+            
+            // Capture crashes as early as possible
+            if let Err(e) = crash::install(app.handle()) {
+                tracing::error!("Failed to install crash reporting: {}", e);
+            }
+            
+            // Report main thread stalls ("app not responding")
+            if let Err(e) = watchdog::start(app.handle()) {
+                tracing::warn!("{}", e);
+            }
+            
+            // Exported logs are only kept on disk for the share sheet
+            diagnostics::cleanup_exports(app.handle());
+            
+            // Record this launch before anything reads the install metadata
+            let launch = install::record_launch(app.handle());
+            match &launch {
+                Ok(info) => logging::set_installation_id(&info.installation_id),
+                Err(e) => tracing::error!("Failed to record launch: {}", e),
+            }
+            
+            // Generate the device-binding key if missing (first launch)
+            device_binding::init();
+            
+            // Restore queued analytics events and track the launch
+            if let Err(e) = analytics::init(app.handle()) {
+                tracing::warn!("Failed to initialize analytics: {}", e);
+            }
+            
+            // Restore the log shipping consent and queued records
+            if let Err(e) = log_shipping::init(app.handle()) {
+                tracing::warn!("Failed to initialize log shipping: {}", e);
+            }
+            let first_launch = launch.is_ok_and(|info| info.first_launch);
+            let post_crash = app.state::<crash::CrashState>().post_crash();
+            if let Err(e) = analytics::track_app_open(app.handle(), first_launch, post_crash) {
+                tracing::warn!("Failed to track app open: {}", e);
+            }
+            
+            // Restore the badge reset policy
+            if let Err(e) = badge::init(app.handle()) {
+                tracing::warn!("Failed to initialize badge reset: {}", e);
+            }
+            
+            // Load the preferences
+            if let Err(e) = prefs::init(app.handle()) {
+                tracing::warn!("Failed to load preferences: {}", e);
+            }
+            
+            // Load the state snapshot saved before the previous process was killed
+            if let Err(e) = state_restore::load_on_launch(app.handle()) {
+                tracing::warn!("Failed to load app state snapshot: {}", e);
+            }
+            
+            // Apply the cached remote config, then refresh it in background
+            if let Err(e) = remote_config::load_cached(app.handle()) {
+                tracing::warn!("Failed to load cached remote config: {}", e);
+            }
+            remote_config::spawn_refresh(app.handle().clone());
+
+            // Check the app binary has not been tampered with (release builds)
+            tamper::check_on_launch(app.handle());
+
+            // Degrade features on compromised or unprotected devices
+            device_policy::evaluate(app.handle());
+
+            // Pin the web application certificates in the webview (updated
+            // whenever the remote config is applied)
+            tls_pinning::refresh(app.handle());
+            
+            // Serve the web application assets from disk when offline
+            if let Err(e) = asset_cache::init(app.handle()) {
+                tracing::warn!("Failed to initialize offline asset cache: {}", e);
+            }
+            
+            // Keep avatars and thumbnails across webview reloads
+            if let Err(e) = image_cache::init(app.handle()) {
+                tracing::warn!("Failed to initialize image cache: {}", e);
+            }
+            
+            // Open the document vault and purge the expired documents
+            if let Err(e) = vault::init(app.handle()) {
+                tracing::warn!("Failed to initialize document vault: {}", e);
+            }
             
             // Application setup logic can go here
             // For example: initialize plugins, setup state, etc.
             #[cfg(debug_assertions)]
             {
-                log::debug!("Debug mode enabled");
-                // Enable devtools in debug mode if needed
-                // app.handle().plugin(tauri_plugin_devtools::init())?;
-            }
-            
-            // Note: For remote frontends, the notification bridge script should be
-            // injected by the frontend itself or via a content script.
-            // The JavaScript bridge file is available at src-tauri/notification-bridge.js
-            // and should be loaded by the remote frontend or injected via Tauri's
-            // content script mechanism if available.
-            log::info!("Notification bridge module loaded - frontend should inject bridge script");
-            
-            // Perform connectivity check at startup (non-blocking)
-            tauri::async_runtime::spawn(async move {
-                log::info!("Starting background connectivity check...");
-                match connectivity::check_connectivity().await {
-                    Ok(true) => {
-                        log::info!("Startup connectivity check: connected");
-                    }
-                    Ok(false) => {
-                        log::warn!("Startup connectivity check: not connected");
-                    }
-                    Err(e) => {
-                        log::error!("Startup connectivity check error: {}", e);
-                    }
-                }
-            });
+                tracing::debug!("Debug mode enabled");
+                // The web inspector is built in for debug builds, and opened
+                // with the `open_devtools` command (see `debug_tools`)
+            }
+            
+            #[cfg(feature = "dev-mock")]
+            tracing::warn!("Mock keystore, notifications and push enabled (dev-mock): never ship this build");
+            
+            // Start the native modules of the build (notifications,
+            // connectivity, ...)
+            app.state::<native_module::NativeModules>().init(app.handle());
             
-            log::info!("Application setup completed successfully");
+            // Block unsupported builds behind the "update required" screen
+            upgrade_gate::spawn_check(app.handle().clone());
+            
+            // Notify the webview when accessibility preferences change
+            accessibility::spawn_watcher(app.handle().clone());
+            
+            // Lock the session after inactivity
+            session_lock::spawn_idle_watcher(app.handle().clone());
+            
+            // Follow system locale changes
+            if let Err(e) = locale::start(app.handle()) {
+                tracing::warn!("Failed to start locale change detection: {}", e);
+            }
+            
+            // Forward deep links and notification taps to the running webview
+            if let Err(e) = deep_link::start(app.handle()) {
+                tracing::warn!("Failed to start deep-link forwarding: {}", e);
+            }
+            
+            // Receive the PDF documents and images shared with the app
+            if let Err(e) = share_target::start(app.handle()) {
+                tracing::warn!("Failed to start share target: {}", e);
+            }
+            
+            // Resume the downloads interrupted when the app was killed
+            if let Err(e) = downloads::init(app.handle()) {
+                tracing::warn!("Failed to restore downloads: {}", e);
+            }
+            
+            // Resume the upload queue
+            if let Err(e) = uploads::start(app.handle()) {
+                tracing::warn!("Failed to start upload queue: {}", e);
+            }
+            
+            // Prefetch the next session's documents when on Wi-Fi and charging
+            if let Err(e) = prefetch::start(app.handle()) {
+                tracing::warn!("Failed to start prefetching: {}", e);
+            }
+            
+            // Run the wipe requested by the device management, if any
+            if let Err(e) = wipe::start(app.handle()) {
+                tracing::error!("Failed to run managed wipe: {}", e);
+            }
+            
+            // Pull agenda and documents into the local database for offline use
+            sync::start(app.handle());
+            
+            // Listen for the shake gesture opening the bug-report flow
+            if let Err(e) = gestures::start(app.handle()) {
+                tracing::warn!("Failed to start shake detection: {}", e);
+            }
+            
+            // Report the software keyboard height to the frontend
+            if let Err(e) = keyboard::start(app.handle()) {
+                tracing::warn!("Failed to start keyboard observer: {}", e);
+            }
+            
+            tracing::info!("Application setup completed successfully");
             Ok(())
-        })
-        .run(tauri::generate_context!())
+        });
+    startup_metrics::mark(None, startup_metrics::StartupPhase::BuilderReady);
+    
+    builder
+        .build(tauri::generate_context!())
         .map_err(|e| {
-            log::error!("Tauri runtime error: {}", e);
+            tracing::error!("Tauri runtime error: {}", e);
             AppError::Tauri(e)
-        })?;
+        })?
+        .run(|app, event| {
+            // Forward foreground/background transitions to the webview
+            lifecycle::handle_run_event(app, &event);
+            // Stop the native modules when the app exits
+            native_module::handle_run_event(app, &event);
+        });
     
-    log::info!("Tauri application started successfully");
+    tracing::info!("Tauri application started successfully");
     Ok(())
 }
 
@@ -219,7 +1178,7 @@ pub fn run_with_error_handling() {
     
     if let Err(e) = run() {
         // Log error using log crate (will work if logger is initialized)
-        log::error!("Failed to run application: {}", e);
+        tracing::error!("Failed to run application: {}", e);
         // Also print to stderr for environments without logger
         eprintln!("Failed to run application: {}", e);
         std::process::exit(exit::FAILURE);
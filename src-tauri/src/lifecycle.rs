@@ -0,0 +1,205 @@
+/// App lifecycle event forwarding
+///
+/// This module forwards the native app lifecycle to the remote frontend so it
+/// can refresh data on resume and pause timers while backgrounded:
+/// - `lifecycle://background` when the app leaves the foreground
+/// - `lifecycle://foreground` when the app returns to the foreground
+/// - `lifecycle://resume` right after `foreground`, with `{ away_secs }`
+///
+/// Lifecycle transitions are derived from Tauri run events (`Resumed`, main
+/// window focus changes). Duplicate transitions are ignored, so native hooks
+/// can also call `on_background()` / `on_foreground()` directly.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager, RunEvent, WindowEvent};
+
+use crate::analytics;
+use crate::badge;
+use crate::breadcrumbs;
+use crate::clipboard;
+use crate::command_metrics;
+use crate::device_policy;
+use crate::ephemeral;
+use crate::events::{self, AppBackground, AppForeground, AppResumed};
+use crate::launch;
+use crate::locale;
+use crate::log_shipping;
+use crate::remote_config;
+use crate::session_lock;
+#[cfg(feature = "module-sse")]
+use crate::sse;
+use crate::state_restore;
+use crate::watchdog;
+#[cfg(feature = "module-realtime")]
+use crate::websocket;
+
+/// Tracks whether the app is in background, and since when
+#[derive(Debug, Default)]
+pub struct LifecycleTracker {
+    background_since: Mutex<Option<Instant>>,
+}
+
+impl LifecycleTracker {
+    /// Record that the app entered the background at `now`
+    ///
+    /// # Returns
+    ///
+    /// Returns `false` if the app was already in background.
+    pub fn enter_background(&self, now: Instant) -> bool {
+        match self.background_since.lock() {
+            Ok(mut since) if since.is_none() => {
+                *since = Some(now);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Record that the app returned to the foreground at `now`
+    ///
+    /// # Returns
+    ///
+    /// Returns the time spent in background, or `None` if the app was
+    /// already in the foreground.
+    pub fn enter_foreground(&self, now: Instant) -> Option<Duration> {
+        let since = self.background_since.lock().ok()?.take()?;
+        Some(now.saturating_duration_since(since))
+    }
+
+    /// Returns `true` if the app is in background
+    pub fn is_background(&self) -> bool {
+        self.background_since
+            .lock()
+            .map(|since| since.is_some())
+            .unwrap_or(false)
+    }
+}
+
+/// Handle the app leaving the foreground
+pub fn on_background(app: &AppHandle) {
+    if !app.state::<LifecycleTracker>().enter_background(Instant::now()) {
+        return;
+    }
+    tracing::info!("App entered background");
+    // The OS may suspend the main thread while in background
+    watchdog::pause();
+    breadcrumbs::breadcrumb("lifecycle", "background");
+    events::emit(app, &AppBackground);
+    badge::on_background(app);
+    clipboard::on_background(app);
+    // Close the realtime connection cleanly before the OS suspends the app
+    #[cfg(feature = "module-realtime")]
+    websocket::on_background(app);
+    // Keep the notification that launched the app if the page did not take it
+    launch::on_background(app);
+    ephemeral::wipe(app);
+
+    command_metrics::report(app);
+
+    // The OS may kill the process at any time from now on
+    if let Err(e) = state_restore::persist(app) {
+        tracing::warn!("Failed to persist app state snapshot: {}", e);
+    }
+    if let Err(e) = analytics::persist(app) {
+        tracing::warn!("Failed to persist analytics events: {}", e);
+    }
+    if let Err(e) = log_shipping::persist() {
+        tracing::warn!("Failed to persist shipped log records: {}", e);
+    }
+}
+
+/// Handle the app returning to the foreground
+pub fn on_foreground(app: &AppHandle) {
+    let away = match app.state::<LifecycleTracker>().enter_foreground(Instant::now()) {
+        Some(away) => away,
+        None => return,
+    };
+    tracing::info!("App returned to foreground after {}s", away.as_secs());
+    watchdog::resume();
+    breadcrumbs::breadcrumb("lifecycle", &format!("foreground after {}s", away.as_secs()));
+    events::emit(app, &AppForeground);
+    events::emit(app, &AppResumed { away_secs: away.as_secs() });
+
+    // Clear the app badge once the user is back (or saw the inbox)
+    badge::on_foreground(app);
+
+    // The locale may have been changed in the system settings
+    locale::refresh(app);
+
+    // The user may have removed the passcode while in background
+    device_policy::evaluate(app);
+
+    // Require re-authentication after a long background period
+    session_lock::on_resume(app, away);
+
+    // Reopen the realtime connection and streams without waiting for a backoff
+    #[cfg(feature = "module-realtime")]
+    websocket::on_foreground(app);
+    #[cfg(feature = "module-sse")]
+    sse::on_foreground(app);
+
+    // Pick up config changes made while the app was in background
+    remote_config::spawn_refresh(app.clone());
+}
+
+/// Map Tauri run events to lifecycle transitions
+pub fn handle_run_event(app: &AppHandle, event: &RunEvent) {
+    match event {
+        RunEvent::Resumed => on_foreground(app),
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        RunEvent::Opened { urls } => {
+            for url in urls {
+                // Files shared with the app, custom scheme links opened while
+                // running or at launch
+                if !crate::share_target::on_open_url(app, url) {
+                    crate::deep_link::on_open_url(app, url.as_str(), crate::deep_link::DeepLinkSource::Link);
+                }
+            }
+        }
+        RunEvent::WindowEvent {
+            event: WindowEvent::Focused(focused),
+            ..
+        } => {
+            if *focused {
+                on_foreground(app);
+            } else {
+                on_background(app);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_background_then_foreground() {
+        let tracker = LifecycleTracker::default();
+        let start = Instant::now();
+
+        assert!(!tracker.is_background());
+        assert!(tracker.enter_background(start));
+        assert!(tracker.is_background());
+
+        let away = tracker.enter_foreground(start + Duration::from_secs(42));
+        assert_eq!(away, Some(Duration::from_secs(42)));
+        assert!(!tracker.is_background());
+    }
+
+    #[test]
+    fn test_duplicate_transitions_are_ignored() {
+        let tracker = LifecycleTracker::default();
+        let start = Instant::now();
+
+        assert_eq!(tracker.enter_foreground(start), None, "Already in foreground");
+        assert!(tracker.enter_background(start));
+        assert!(!tracker.enter_background(start + Duration::from_secs(5)), "Already in background");
+
+        let away = tracker.enter_foreground(start + Duration::from_secs(10));
+        assert_eq!(away, Some(Duration::from_secs(10)), "First background time should be kept");
+    }
+}
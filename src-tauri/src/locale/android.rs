@@ -0,0 +1,55 @@
+/// Android-specific locale detection
+///
+/// This module reads the locale from the resources configuration and listens
+/// for the `ACTION_LOCALE_CHANGED` broadcast.
+///
+/// Note: This implementation provides the structure for Android locale detection.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use tauri::AppHandle;
+use crate::AppResult;
+
+/// Read the current locale tag on Android
+///
+/// # Returns
+///
+/// Returns the BCP 47 tag of the first configured locale (e.g. `fr-FR`).
+pub fn current_locale() -> String {
+    // TODO: Implement native Android locale read
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val tag = context.resources.configuration.locales[0].toLanguageTag()
+    // ```
+
+    // Placeholder: Return the default locale
+    // Replace this with actual native implementation
+    "fr-FR".to_string()
+}
+
+/// Listen for locale changes on Android
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start_receiver(app: AppHandle) -> AppResult<()> {
+    tracing::info!("[Android] Registering locale change receiver");
+
+    // TODO: Implement native Android locale change receiver
+    // Each change must be forwarded to `locale::on_locale_changed`.
+    // Example Kotlin implementation:
+    // ```kotlin
+    // context.registerReceiver(object : BroadcastReceiver() {
+    //     override fun onReceive(context: Context, intent: Intent) {
+    //         onLocaleChanged(context.resources.configuration.locales[0].toLanguageTag())
+    //     }
+    // }, IntentFilter(Intent.ACTION_LOCALE_CHANGED))
+    // ```
+    // The activity must also declare `android:configChanges="locale|layoutDirection"`
+    // so the webview is not recreated by the system.
+
+    // Placeholder: Return success (changes are still picked up on foreground)
+    // Replace this with actual native implementation
+    let _ = app;
+    Ok(())
+}
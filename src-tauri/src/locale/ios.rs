@@ -0,0 +1,53 @@
+/// iOS-specific locale detection
+///
+/// This module reads the preferred language from `Locale.preferredLanguages`
+/// and observes `NSLocale.currentLocaleDidChangeNotification`.
+///
+/// Note: This implementation provides the structure for iOS locale detection.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use tauri::AppHandle;
+use crate::AppResult;
+
+/// Read the current locale tag on iOS
+///
+/// # Returns
+///
+/// Returns the BCP 47 tag of the preferred language (e.g. `fr-FR`).
+pub fn current_locale() -> String {
+    // TODO: Implement native iOS locale read
+    // Example Swift implementation:
+    // ```swift
+    // let tag = Locale.preferredLanguages.first ?? Locale.current.identifier
+    // ```
+
+    // Placeholder: Return the default locale
+    // Replace this with actual native implementation
+    "fr-FR".to_string()
+}
+
+/// Observe locale changes on iOS
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start_observer(app: AppHandle) -> AppResult<()> {
+    tracing::info!("[iOS] Observing locale changes");
+
+    // TODO: Implement native iOS locale observer
+    // Each change must be forwarded to `locale::on_locale_changed`.
+    // Example Swift implementation:
+    // ```swift
+    // NotificationCenter.default.addObserver(
+    //     forName: NSLocale.currentLocaleDidChangeNotification, object: nil, queue: .main
+    // ) { _ in
+    //     onLocaleChanged(Locale.preferredLanguages.first ?? Locale.current.identifier)
+    // }
+    // ```
+
+    // Placeholder: Return success (changes are still picked up on foreground)
+    // Replace this with actual native implementation
+    let _ = app;
+    Ok(())
+}
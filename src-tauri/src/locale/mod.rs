@@ -0,0 +1,261 @@
+/// Locale change handling
+///
+/// This module follows the system locale at runtime so the app does not stay
+/// half-French half-English until restart:
+/// - the locale is read at startup, and again on each return to foreground
+///   and when the native layer reports a change (iOS
+///   `NSCurrentLocaleDidChangeNotification`, Android `ACTION_LOCALE_CHANGED`)
+/// - native strings (notification channel, authentication prompt) are
+///   switched to the new language
+/// - a `locale://changed` event is emitted to the webview
+/// - if enabled by the `locale.reload_on_change` remote config key, the
+///   webview is reloaded so the frontend is fetched again with the new
+///   `Accept-Language` header (the webview derives it from the system locale)
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::events;
+#[cfg(feature = "module-notifications")]
+use crate::notifications;
+use crate::remote_config::{keys, ConfigStore};
+use crate::AppResult;
+
+/// Language of the native strings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    /// French (default)
+    #[default]
+    French,
+    /// English
+    English,
+}
+
+impl Language {
+    /// Language of a BCP 47 locale tag (`fr-FR`, `en_US`, ...), falling back
+    /// to French for unsupported languages
+    pub fn from_tag(tag: &str) -> Self {
+        let primary = tag
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Language::English,
+            _ => Language::French,
+        }
+    }
+
+    /// Native strings in this language
+    pub fn strings(self) -> &'static NativeStrings {
+        match self {
+            Language::French => &FRENCH,
+            Language::English => &ENGLISH,
+        }
+    }
+}
+
+/// User-visible strings displayed by the native layer
+#[derive(Debug, PartialEq, Eq)]
+pub struct NativeStrings {
+    /// Name of the default notification channel (Android settings)
+    pub notification_channel_name: &'static str,
+    /// Description of the default notification channel
+    pub notification_channel_description: &'static str,
+    /// Reason displayed in the authentication prompt when unlocking the session
+    pub session_unlock_reason: &'static str,
+    /// Subject of the exported diagnostics logs, used by email clients
+    pub logs_share_subject: &'static str,
+    /// Subject of the personal data export, used by email clients
+    pub data_export_share_subject: &'static str,
+}
+
+const FRENCH: NativeStrings = NativeStrings {
+    notification_channel_name: "Notifications élulib",
+    notification_channel_description: "Notifications de l'application élulib",
+    session_unlock_reason: "Déverrouiller élulib",
+    logs_share_subject: "Journaux de diagnostic élulib",
+    data_export_share_subject: "Mes données élulib",
+};
+
+const ENGLISH: NativeStrings = NativeStrings {
+    notification_channel_name: "élulib Notifications",
+    notification_channel_description: "Notifications from élulib app",
+    session_unlock_reason: "Unlock élulib",
+    logs_share_subject: "élulib diagnostic logs",
+    data_export_share_subject: "My élulib data",
+};
+
+/// Current locale, payload of the `locale://changed` event
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleInfo {
+    /// System locale tag (`fr-FR`, `en-US`, ...)
+    pub locale: String,
+    /// Language of the native strings
+    pub language: Language,
+}
+
+impl LocaleInfo {
+    fn new(locale: &str) -> Self {
+        LocaleInfo {
+            locale: locale.to_string(),
+            language: Language::from_tag(locale),
+        }
+    }
+}
+
+/// Locale of the running process
+///
+/// A static rather than managed state, so strings can be resolved from code
+/// without an `AppHandle` (notifications, authentication prompts).
+static CURRENT: Mutex<Option<LocaleInfo>> = Mutex::new(None);
+
+/// Current locale, read from the system on first use
+pub fn current() -> LocaleInfo {
+    let Ok(mut current) = CURRENT.lock() else {
+        return LocaleInfo::new(&system_locale());
+    };
+    current
+        .get_or_insert_with(|| LocaleInfo::new(&system_locale()))
+        .clone()
+}
+
+/// Native strings in the current language
+pub fn strings() -> &'static NativeStrings {
+    current().language.strings()
+}
+
+/// Store a new locale
+///
+/// # Returns
+///
+/// Returns the new `LocaleInfo` if the locale changed.
+fn update(locale: &str) -> Option<LocaleInfo> {
+    let mut current = CURRENT.lock().ok()?;
+    let info = LocaleInfo::new(locale);
+    if current.as_ref() == Some(&info) {
+        return None;
+    }
+    let first = current.is_none();
+    *current = Some(info.clone());
+    // The first read is the startup locale, not a change
+    (!first).then_some(info)
+}
+
+/// Read the system locale tag
+pub fn system_locale() -> String {
+    #[cfg(target_os = "ios")]
+    {
+        ios::current_locale()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::current_locale()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+            .unwrap_or_else(|| "fr-FR".to_string())
+    }
+}
+
+/// Read the locale at startup and listen for changes
+pub fn start(app: &AppHandle) -> AppResult<()> {
+    let info = current();
+    tracing::info!("System locale: {} ({:?})", info.locale, info.language);
+
+    #[cfg(target_os = "ios")]
+    {
+        ios::start_observer(app.clone())
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::start_receiver(app.clone())
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = app;
+        Ok(())
+    }
+}
+
+/// Re-read the system locale, e.g. when returning to foreground
+pub fn refresh(app: &AppHandle) {
+    on_locale_changed(app, &system_locale());
+}
+
+/// Apply a locale reported by the native layer
+pub fn on_locale_changed(app: &AppHandle, locale: &str) {
+    let Some(info) = update(locale) else {
+        return;
+    };
+    tracing::info!("System locale changed to {} ({:?})", info.locale, info.language);
+
+    // Rename the notification channel shown in the system settings
+    #[cfg(feature = "module-notifications")]
+    if let Err(e) = notifications::ensure_default_channel() {
+        tracing::warn!("Failed to update notification channel: {}", e);
+    }
+
+    events::emit(app, &info);
+
+    let reload = app
+        .state::<ConfigStore>()
+        .get_or(keys::LOCALE_RELOAD_ON_CHANGE, constants::LOCALE_RELOAD_ON_CHANGE);
+    if reload {
+        reload_webview(app);
+    }
+}
+
+/// Reload the main webview so the frontend is requested with the new
+/// `Accept-Language`
+fn reload_webview(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(constants::MAIN_WINDOW_LABEL) else {
+        tracing::warn!("Main window not found, not reloading for the new locale");
+        return;
+    };
+    tracing::info!("Reloading the webview for the new locale");
+    if let Err(e) = window.reload() {
+        tracing::warn!("Failed to reload the webview: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_from_tag() {
+        assert_eq!(Language::from_tag("en-US"), Language::English);
+        assert_eq!(Language::from_tag("en_GB.UTF-8"), Language::English);
+        assert_eq!(Language::from_tag("EN"), Language::English);
+        assert_eq!(Language::from_tag("fr-CA"), Language::French);
+        assert_eq!(Language::from_tag("de-DE"), Language::French, "Unsupported languages fall back to French");
+        assert_eq!(Language::from_tag(""), Language::French);
+    }
+
+    #[test]
+    fn test_strings_are_translated() {
+        assert_ne!(
+            Language::French.strings().session_unlock_reason,
+            Language::English.strings().session_unlock_reason
+        );
+    }
+}
@@ -0,0 +1,229 @@
+/// Runtime log level control
+///
+/// Release builds log at `LOG_DEFAULT_LEVEL`, or at the `log_level` of the
+/// runtime configuration file (the base level). Support can ask a user to
+/// enable more verbose logging from the app (`set_log_level` command), which
+/// is impossible with `RUST_LOG` on mobile:
+/// - the level applies to all modules, or only to the modules under
+///   `module_filter` (e.g. `elulib_mobile::auth`), the others keeping the
+///   base level
+/// - the choice is persisted in `LOG_LEVEL_FILE` and applied at the next
+///   launches, until changed again
+///
+/// The filter is evaluated by the log plugin for every record
+/// (`enabled`, registered in `create_app()`), and the `log` max level is
+/// raised accordingly so records reach it.
+
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use log::LevelFilter;
+
+use crate::constants;
+use crate::{AppError, AppResult};
+
+/// Log level setting, as persisted and reported to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLevelSetting {
+    /// Level: `off`, `error`, `warn`, `info`, `debug` or `trace`
+    pub level: String,
+    /// Module path the level applies to, or all modules if `None`
+    #[serde(default)]
+    pub module_filter: Option<String>,
+}
+
+/// Parsed log level setting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFilter {
+    /// Level of the filtered modules
+    pub level: LevelFilter,
+    /// Module path the level applies to, or all modules if `None`
+    pub module_filter: Option<String>,
+    /// Level of the other modules
+    pub base: LevelFilter,
+}
+
+/// Active filter
+static FILTER: RwLock<LogFilter> = RwLock::new(LogFilter::DEFAULT);
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl LogFilter {
+    /// Filter of a fresh install
+    pub const DEFAULT: LogFilter = LogFilter::base(constants::LOG_DEFAULT_LEVEL);
+
+    /// Filter logging every module at the base level
+    pub const fn base(base: LevelFilter) -> Self {
+        LogFilter {
+            level: base,
+            module_filter: None,
+            base,
+        }
+    }
+
+    /// Parse a setting
+    ///
+    /// # Arguments
+    ///
+    /// * `setting` - Level and module filter
+    /// * `base` - Level of the modules outside the module filter
+    ///
+    /// # Returns
+    ///
+    /// Returns the filter, or an error if the level is unknown or the
+    /// module filter is empty.
+    pub fn parse(setting: &LogLevelSetting, base: LevelFilter) -> AppResult<Self> {
+        let level = LevelFilter::from_str(setting.level.trim())
+            .map_err(|_| AppError::InvalidArgument(format!("Unknown log level: {}", setting.level)))?;
+        let module_filter = match setting.module_filter.as_deref().map(str::trim) {
+            Some("") => return Err(AppError::InvalidArgument("Module filter must not be empty".to_string())),
+            Some(module) => Some(module.to_string()),
+            None => None,
+        };
+        Ok(Self {
+            level,
+            module_filter,
+            base,
+        })
+    }
+
+    /// Setting of the filter
+    pub fn setting(&self) -> LogLevelSetting {
+        LogLevelSetting {
+            level: self.level.as_str().to_lowercase(),
+            module_filter: self.module_filter.clone(),
+        }
+    }
+
+    /// Level applying to a log target
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        match &self.module_filter {
+            Some(module)
+                if target == module
+                    || target.strip_prefix(module.as_str()).is_some_and(|rest| rest.starts_with("::")) =>
+            {
+                self.level
+            }
+            Some(_) => self.base,
+            None => self.level,
+        }
+    }
+
+    /// Most verbose level of any target
+    pub fn max_level(&self) -> LevelFilter {
+        match self.module_filter {
+            Some(_) => self.level.max(self.base),
+            None => self.level,
+        }
+    }
+}
+
+/// Returns `true` if a record passes the active filter
+///
+/// Registered as the log plugin filter in `create_app()`.
+pub fn enabled(metadata: &log::Metadata) -> bool {
+    FILTER
+        .read()
+        .map(|filter| metadata.level() <= filter.level_for(metadata.target()))
+        .unwrap_or(true)
+}
+
+/// Active setting
+pub fn current() -> LogLevelSetting {
+    FILTER
+        .read()
+        .map(|filter| filter.setting())
+        .unwrap_or_else(|_| LogFilter::DEFAULT.setting())
+}
+
+fn apply(filter: LogFilter) {
+    log::set_max_level(filter.max_level());
+    if let Ok(mut active) = FILTER.write() {
+        *active = filter;
+    }
+}
+
+/// Apply the setting persisted in `dir`, if any
+///
+/// Called at startup, once the log plugin is installed and the runtime
+/// configuration (providing `base`) is loaded.
+pub fn load(dir: &Path, base: LevelFilter) {
+    let persisted = std::fs::read_to_string(dir.join(constants::LOG_LEVEL_FILE))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<LogLevelSetting>(&contents).ok());
+    let filter = match persisted.map(|setting| LogFilter::parse(&setting, base)) {
+        Some(Ok(filter)) => filter,
+        Some(Err(e)) => {
+            tracing::warn!("Ignoring persisted log level: {}", e);
+            LogFilter::base(base)
+        }
+        None => LogFilter::base(base),
+    };
+    apply(filter);
+    tracing::info!("Log level: {:?}", current());
+}
+
+/// Change the log level and persist it in `dir`
+///
+/// # Returns
+///
+/// Returns the active setting, or an error if the setting is invalid
+/// or cannot be persisted.
+pub fn set(dir: &Path, setting: &LogLevelSetting) -> AppResult<LogLevelSetting> {
+    let base = FILTER.read().map_or(constants::LOG_DEFAULT_LEVEL, |filter| filter.base);
+    let filter = LogFilter::parse(setting, base)?;
+    let setting = filter.setting();
+    std::fs::create_dir_all(dir).map_err(|e| AppError::Config(format!("Failed to create data directory: {}", e)))?;
+    let contents = serde_json::to_string(&setting)
+        .map_err(|e| AppError::Config(format!("Failed to serialize log level: {}", e)))?;
+    std::fs::write(dir.join(constants::LOG_LEVEL_FILE), contents)
+        .map_err(|e| AppError::Config(format!("Failed to write log level: {}", e)))?;
+
+    apply(filter);
+    tracing::info!("Log level changed: {:?}", setting);
+    Ok(setting)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setting(level: &str, module_filter: Option<&str>) -> LogLevelSetting {
+        LogLevelSetting {
+            level: level.to_string(),
+            module_filter: module_filter.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let filter = LogFilter::parse(&setting("DEBUG", None), LevelFilter::Info).unwrap();
+        assert_eq!(filter.level, LevelFilter::Debug);
+        assert_eq!(filter.setting(), setting("debug", None));
+
+        assert!(LogFilter::parse(&setting("verbose", None), LevelFilter::Info).is_err());
+        assert!(LogFilter::parse(&setting("debug", Some(" ")), LevelFilter::Info).is_err());
+    }
+
+    #[test]
+    fn test_module_filter() {
+        let filter = LogFilter::parse(&setting("trace", Some("elulib_mobile::auth")), LevelFilter::Info).unwrap();
+        assert_eq!(filter.level_for("elulib_mobile::auth"), LevelFilter::Trace);
+        assert_eq!(filter.level_for("elulib_mobile::auth::ios"), LevelFilter::Trace);
+        assert_eq!(
+            filter.level_for("elulib_mobile::authz"),
+            LevelFilter::Info,
+            "Only whole module paths match"
+        );
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+
+        let quiet = LogFilter::parse(&setting("error", Some("reqwest")), LevelFilter::Warn).unwrap();
+        assert_eq!(quiet.max_level(), LevelFilter::Warn);
+    }
+}
@@ -0,0 +1,576 @@
+/// Remote log shipping module
+///
+/// Support cannot ask users to dig log files out of their phone, so log
+/// records can be uploaded to `LOG_SHIPPING_ENDPOINT`, only with consent:
+/// - the user opts in from the app settings, until they opt out, or
+/// - support issues a token the user enters in the app, enabling shipping for
+///   `LOG_SHIPPING_SUPPORT_GRANT_SECS` only; the token is sent with each batch
+///   so the backend can attach the logs to the support case
+///
+/// Records are the redacted JSON records of the `logging` module. They are
+/// buffered in memory, uploaded in batches of `LOG_SHIPPING_BATCH_SIZE` every
+/// `LOG_SHIPPING_FLUSH_INTERVAL_SECS`, and persisted to disk when the app goes
+/// to background or an upload fails, so they survive offline periods.
+///
+/// Backpressure: logging never waits for shipping. At most
+/// `LOG_SHIPPING_MAX_QUEUED_RECORDS` records are kept (oldest are dropped
+/// first, and the number of dropped records is reported with the next batch),
+/// and uploads back off exponentially while they fail, up to
+/// `LOG_SHIPPING_MAX_BACKOFF_SECS`.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::http;
+use crate::logging;
+use crate::redact;
+use crate::{AppError, AppResult};
+
+/// Shipping state, shared with the log target
+static SHIPPER: LogShipper = LogShipper::new();
+
+/// Time-limited consent given with a support token
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SupportGrant {
+    token: String,
+    /// End of the grant (seconds since the Unix epoch)
+    expires_at: u64,
+}
+
+/// Log shipping consent persisted between launches
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShippingConsent {
+    opted_in: bool,
+    support_grant: Option<SupportGrant>,
+}
+
+impl ShippingConsent {
+    /// Returns `true` if logs may be shipped at `now`
+    fn allows(&self, now: u64) -> bool {
+        self.opted_in || self.support_token(now).is_some()
+    }
+
+    /// Support token, while its grant is valid
+    fn support_token(&self, now: u64) -> Option<&str> {
+        self.support_grant
+            .as_ref()
+            .filter(|grant| grant.expires_at > now)
+            .map(|grant| grant.token.as_str())
+    }
+}
+
+/// Log shipping status, as reported to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogShippingStatus {
+    /// Log records are being shipped
+    pub enabled: bool,
+    /// The user opted in
+    pub opted_in: bool,
+    /// End of the support grant, if any (seconds since the Unix epoch)
+    pub support_expires_at: Option<u64>,
+    /// Records waiting for upload
+    pub queued_records: usize,
+    /// Records dropped because the queue was full, not reported yet
+    pub dropped_records: u64,
+}
+
+/// Batch of records, as uploaded to `LOG_SHIPPING_ENDPOINT`
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogBatch<'a> {
+    app_version: &'a str,
+    platform: &'a str,
+    support_token: Option<&'a str>,
+    /// Records dropped before this batch
+    dropped: u64,
+    records: Vec<serde_json::Value>,
+}
+
+/// Log shipping state
+#[derive(Debug)]
+struct LogShipper {
+    /// Mirrors the consent, checked for every log record
+    active: AtomicBool,
+    /// Set by the local data wipe: nothing is shipped for the rest of the
+    /// process
+    stopped: AtomicBool,
+    consent: Mutex<ShippingConsent>,
+    /// JSON records waiting for upload
+    queue: Mutex<VecDeque<String>>,
+    dropped: AtomicU64,
+    dir: Mutex<Option<PathBuf>>,
+}
+
+impl LogShipper {
+    const fn new() -> Self {
+        Self {
+            active: AtomicBool::new(false),
+            stopped: AtomicBool::new(false),
+            consent: Mutex::new(ShippingConsent {
+                opted_in: false,
+                support_grant: None,
+            }),
+            queue: Mutex::new(VecDeque::new()),
+            dropped: AtomicU64::new(0),
+            dir: Mutex::new(None),
+        }
+    }
+
+    /// Queue a record, dropping the oldest one if the queue is full
+    ///
+    /// The record is dropped if the queue is busy, so logging never blocks.
+    fn push(&self, line: String) {
+        let Ok(mut queue) = self.queue.try_lock() else {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        };
+        if queue.len() >= constants::LOG_SHIPPING_MAX_QUEUED_RECORDS {
+            queue.pop_front();
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        queue.push_back(line);
+    }
+
+    /// Remove up to `LOG_SHIPPING_BATCH_SIZE` records from the queue
+    fn take_batch(&self) -> Vec<String> {
+        match self.queue.lock() {
+            Ok(mut queue) => {
+                let count = queue.len().min(constants::LOG_SHIPPING_BATCH_SIZE);
+                queue.drain(..count).collect()
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Put back records whose upload failed, ahead of newer records
+    fn requeue(&self, records: Vec<String>) {
+        if let Ok(mut queue) = self.queue.lock() {
+            for record in records.into_iter().rev() {
+                queue.push_front(record);
+            }
+            // Like `push`, the oldest records are dropped first
+            let overflow = queue.len().saturating_sub(constants::LOG_SHIPPING_MAX_QUEUED_RECORDS);
+            queue.drain(..overflow);
+            self.dropped.fetch_add(overflow as u64, Ordering::Relaxed);
+        }
+    }
+
+    fn queued(&self) -> Vec<String> {
+        self.queue
+            .lock()
+            .map(|queue| queue.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn clear(&self) {
+        if let Ok(mut queue) = self.queue.lock() {
+            queue.clear();
+        }
+        self.dropped.store(0, Ordering::Relaxed);
+    }
+
+    /// Replace the consent, clearing the queue when shipping stops
+    fn set_consent(&self, consent: ShippingConsent, now: u64) {
+        let allowed = consent.allows(now) && !self.stopped.load(Ordering::SeqCst);
+        self.active.store(allowed, Ordering::SeqCst);
+        if !allowed {
+            self.clear();
+        }
+        if let Ok(mut stored) = self.consent.lock() {
+            *stored = consent;
+        }
+    }
+
+    fn consent(&self) -> ShippingConsent {
+        self.consent.lock().map(|consent| consent.clone()).unwrap_or_default()
+    }
+
+    fn status(&self) -> LogShippingStatus {
+        let consent = self.consent();
+        LogShippingStatus {
+            enabled: self.active.load(Ordering::SeqCst),
+            opted_in: consent.opted_in,
+            support_expires_at: consent.support_grant.map(|grant| grant.expires_at),
+            queued_records: self.queue.lock().map(|queue| queue.len()).unwrap_or(0),
+            dropped_records: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    logging::now_ms() / 1000
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents)
+        .map_err(|e| tracing::warn!("Ignoring invalid log shipping file {}: {}", path.display(), e))
+        .ok()
+}
+
+fn write_json<T: serde::Serialize>(path: &Path, value: &T) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| AppError::Network(format!("Failed to create data directory: {}", e)))?;
+    }
+    let contents = serde_json::to_string(value)
+        .map_err(|e| AppError::Network(format!("Failed to serialize log shipping file: {}", e)))?;
+    std::fs::write(path, contents).map_err(|e| AppError::Network(format!("Failed to write log shipping file: {}", e)))
+}
+
+fn dir() -> AppResult<PathBuf> {
+    SHIPPER
+        .dir
+        .lock()
+        .map_err(|e| AppError::Network(e.to_string()))?
+        .clone()
+        .ok_or_else(|| AppError::Network("Log shipping not initialized".to_string()))
+}
+
+/// Targets whose records are not shipped: this module and the HTTP stack
+/// uploading the batches, so uploads do not feed the queue
+const UNSHIPPED_TARGETS: &[&str] = &[module_path!(), "reqwest", "hyper", "hyper_util", "h2", "rustls"];
+
+/// Returns `true` if records of the target are shipped
+fn is_shipped(target: &str) -> bool {
+    !UNSHIPPED_TARGETS.iter().any(|prefix| {
+        target
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+    })
+}
+
+/// Queue a log record for shipping, if shipping is enabled
+///
+/// Registered as a log target in `create_app()`. Records of this module and
+/// of the HTTP stack are not shipped (`UNSHIPPED_TARGETS`).
+pub fn record(record: &log::Record) {
+    if !SHIPPER.active.load(Ordering::Relaxed) || !is_shipped(record.target()) {
+        return;
+    }
+    match logging::json_line(record, logging::now_ms()) {
+        Some(line) => SHIPPER.push(line),
+        None => {
+            SHIPPER.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Initialize log shipping: restore the consent and queued records, and
+/// start the periodic upload
+pub fn init(app: &AppHandle) -> AppResult<()> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Network(format!("Failed to resolve data directory: {}", e)))?;
+
+    let consent: ShippingConsent =
+        read_json(&dir.join(constants::LOG_SHIPPING_SETTINGS_FILE)).unwrap_or_default();
+    if let Some(grant) = &consent.support_grant {
+        redact::register_secret(&grant.token);
+    }
+    SHIPPER.set_consent(consent, now_secs());
+
+    let queue_path = dir.join(constants::LOG_SHIPPING_QUEUE_FILE);
+    let queued: Vec<String> = read_json(&queue_path).unwrap_or_default();
+    if !queued.is_empty() && SHIPPER.active.load(Ordering::SeqCst) {
+        tracing::debug!("Restored {} queued log record(s)", queued.len());
+        SHIPPER.requeue(queued);
+    }
+    let _ = std::fs::remove_file(&queue_path);
+
+    if let Ok(mut stored) = SHIPPER.dir.lock() {
+        *stored = Some(dir);
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let interval = Duration::from_secs(constants::LOG_SHIPPING_FLUSH_INTERVAL_SECS);
+        let mut failures = 0u32;
+        loop {
+            tokio::time::sleep(backoff(interval, failures)).await;
+            if SHIPPER.stopped.load(Ordering::SeqCst) {
+                break;
+            }
+            expire_grant(now_secs());
+            if !SHIPPER.active.load(Ordering::SeqCst) {
+                failures = 0;
+                continue;
+            }
+            failures = match flush().await {
+                Ok(()) => 0,
+                Err(e) => {
+                    tracing::debug!("Log shipping failed, keeping records for later: {}", e);
+                    if let Err(e) = persist() {
+                        tracing::warn!("{}", e);
+                    }
+                    failures.saturating_add(1)
+                }
+            };
+        }
+    });
+    Ok(())
+}
+
+/// Delay before the next upload after `failures` consecutive failures
+fn backoff(interval: Duration, failures: u32) -> Duration {
+    interval
+        .saturating_mul(2u32.saturating_pow(failures))
+        .min(Duration::from_secs(constants::LOG_SHIPPING_MAX_BACKOFF_SECS))
+}
+
+/// Stop shipping once the support grant expired, unless the user opted in
+fn expire_grant(now: u64) {
+    let mut consent = SHIPPER.consent();
+    let expired = consent
+        .support_grant
+        .as_ref()
+        .is_some_and(|grant| grant.expires_at <= now);
+    if !expired {
+        return;
+    }
+
+    consent.support_grant = None;
+    SHIPPER.set_consent(consent.clone(), now);
+    tracing::info!("Support log shipping grant expired");
+    if let Ok(dir) = dir() {
+        if let Err(e) = write_json(&dir.join(constants::LOG_SHIPPING_SETTINGS_FILE), &consent) {
+            tracing::warn!("{}", e);
+        }
+        if !consent.opted_in {
+            let _ = std::fs::remove_file(dir.join(constants::LOG_SHIPPING_QUEUE_FILE));
+        }
+    }
+}
+
+/// Upload all queued records, batch by batch
+///
+/// On failure, the remaining records are kept for a later attempt.
+async fn flush() -> AppResult<()> {
+    let client = http::client()?;
+    loop {
+        let records = SHIPPER.take_batch();
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let consent = SHIPPER.consent();
+        let dropped = SHIPPER.dropped.swap(0, Ordering::Relaxed);
+        let batch = LogBatch {
+            app_version: env!("CARGO_PKG_VERSION"),
+            platform: std::env::consts::OS,
+            support_token: consent.support_token(now_secs()),
+            dropped,
+            records: records
+                .iter()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect(),
+        };
+        let result = client
+            .post(http::endpoint(constants::LOG_SHIPPING_ENDPOINT))
+            .json(&batch)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        if let Err(e) = result {
+            SHIPPER.dropped.fetch_add(dropped, Ordering::Relaxed);
+            SHIPPER.requeue(records);
+            return Err(AppError::Network(e.to_string()));
+        }
+    }
+}
+
+/// Drop the records waiting to be shipped (local data wipe)
+pub fn discard_queued() {
+    SHIPPER.clear();
+}
+
+/// Stop shipping for the rest of the process (local data wipe)
+pub fn stop() {
+    SHIPPER.stopped.store(true, Ordering::SeqCst);
+    SHIPPER.active.store(false, Ordering::SeqCst);
+    SHIPPER.clear();
+}
+
+/// Persist queued records, so they survive the process being killed
+pub fn persist() -> AppResult<()> {
+    if !SHIPPER.active.load(Ordering::SeqCst) || SHIPPER.stopped.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+    let Ok(dir) = dir() else {
+        return Ok(());
+    };
+    write_json(&dir.join(constants::LOG_SHIPPING_QUEUE_FILE), &SHIPPER.queued())
+}
+
+fn validate_support_token(token: &str) -> AppResult<()> {
+    let valid = !token.is_empty()
+        && token.len() <= constants::LOG_SHIPPING_MAX_TOKEN_LENGTH
+        && token.chars().all(|c| c.is_ascii_graphic());
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::InvalidArgument("Invalid support token".to_string()))
+    }
+}
+
+/// Enable or disable log shipping
+///
+/// # Arguments
+///
+/// * `enabled` - Ship logs; disabling drops the opt-in, the support grant and
+///   all queued records
+/// * `support_token` - Token issued by support: enables shipping for
+///   `LOG_SHIPPING_SUPPORT_GRANT_SECS` instead of opting in
+///
+/// # Returns
+///
+/// Returns the new status, or an error if the token is invalid or the
+/// consent cannot be persisted.
+pub fn set_enabled(enabled: bool, support_token: Option<&str>) -> AppResult<LogShippingStatus> {
+    let dir = dir()?;
+    let now = now_secs();
+    let mut consent = SHIPPER.consent();
+    match (enabled, support_token.map(str::trim)) {
+        (true, Some(token)) => {
+            validate_support_token(token)?;
+            redact::register_secret(token);
+            consent.support_grant = Some(SupportGrant {
+                token: token.to_string(),
+                expires_at: now + constants::LOG_SHIPPING_SUPPORT_GRANT_SECS,
+            });
+        }
+        (true, None) => consent.opted_in = true,
+        (false, _) => consent = ShippingConsent::default(),
+    }
+
+    write_json(&dir.join(constants::LOG_SHIPPING_SETTINGS_FILE), &consent)?;
+    SHIPPER.set_consent(consent, now);
+    if !enabled {
+        let _ = std::fs::remove_file(dir.join(constants::LOG_SHIPPING_QUEUE_FILE));
+    }
+    let status = SHIPPER.status();
+    tracing::info!(
+        "Log shipping {} (support grant until {:?})",
+        if status.enabled { "enabled" } else { "disabled" },
+        status.support_expires_at
+    );
+    Ok(status)
+}
+
+/// Current log shipping status
+pub fn status() -> LogShippingStatus {
+    SHIPPER.status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grant(expires_at: u64) -> ShippingConsent {
+        ShippingConsent {
+            opted_in: false,
+            support_grant: Some(SupportGrant {
+                token: "case-1234".to_string(),
+                expires_at,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_consent() {
+        assert!(!ShippingConsent::default().allows(100));
+        assert!(ShippingConsent { opted_in: true, support_grant: None }.allows(100));
+
+        assert!(grant(200).allows(100));
+        assert_eq!(grant(200).support_token(100), Some("case-1234"));
+        assert!(!grant(200).allows(200), "Support grants are time-limited");
+        assert_eq!(grant(200).support_token(300), None);
+    }
+
+    #[test]
+    fn test_queue_is_bounded() {
+        let shipper = LogShipper::new();
+        for i in 0..constants::LOG_SHIPPING_MAX_QUEUED_RECORDS + 5 {
+            shipper.push(format!("{{\"n\":{}}}", i));
+        }
+        assert_eq!(shipper.queued().len(), constants::LOG_SHIPPING_MAX_QUEUED_RECORDS);
+        assert_eq!(shipper.queued()[0], "{\"n\":5}", "Oldest records are dropped first");
+        assert_eq!(shipper.status().dropped_records, 5);
+    }
+
+    #[test]
+    fn test_take_batch_and_requeue_keep_order() {
+        let shipper = LogShipper::new();
+        for line in ["a", "b", "c"] {
+            shipper.push(line.to_string());
+        }
+        let batch = shipper.take_batch();
+        assert_eq!(batch, ["a", "b", "c"]);
+        shipper.push("d".to_string());
+        shipper.requeue(batch);
+        assert_eq!(shipper.queued(), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_requeue_drops_the_oldest_records() {
+        let shipper = LogShipper::new();
+        shipper.push("old".to_string());
+        let batch = shipper.take_batch();
+        for i in 0..constants::LOG_SHIPPING_MAX_QUEUED_RECORDS {
+            shipper.push(format!("{{\"n\":{}}}", i));
+        }
+        shipper.requeue(batch);
+        let queued = shipper.queued();
+        assert_eq!(queued.len(), constants::LOG_SHIPPING_MAX_QUEUED_RECORDS);
+        assert_eq!(queued[0], "{\"n\":0}", "Failed batch is the oldest");
+        assert_eq!(
+            queued.last().unwrap(),
+            &format!("{{\"n\":{}}}", constants::LOG_SHIPPING_MAX_QUEUED_RECORDS - 1),
+            "Newest records are kept"
+        );
+        assert_eq!(shipper.status().dropped_records, 1);
+    }
+
+    #[test]
+    fn test_http_stack_is_not_shipped() {
+        assert!(!is_shipped(module_path!()));
+        assert!(!is_shipped("reqwest::connect"));
+        assert!(!is_shipped("hyper_util::client::legacy::pool"));
+        assert!(!is_shipped("rustls"));
+        assert!(is_shipped("elulib_mobile::sync"));
+        assert!(is_shipped("hyperlink"));
+    }
+
+    #[test]
+    fn test_revoking_consent_clears_queue() {
+        let shipper = LogShipper::new();
+        shipper.set_consent(grant(200), 100);
+        assert!(shipper.status().enabled);
+        shipper.push("a".to_string());
+
+        shipper.set_consent(grant(200), 300);
+        assert!(!shipper.status().enabled);
+        assert_eq!(shipper.status().queued_records, 0);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let interval = Duration::from_secs(30);
+        assert_eq!(backoff(interval, 0), interval);
+        assert_eq!(backoff(interval, 2), Duration::from_secs(120));
+        assert_eq!(
+            backoff(interval, 40),
+            Duration::from_secs(constants::LOG_SHIPPING_MAX_BACKOFF_SECS)
+        );
+    }
+}
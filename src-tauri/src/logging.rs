@@ -0,0 +1,688 @@
+/// Background log file writer
+///
+/// Log lines are written to the log directory by a dedicated thread, instead of
+/// the log plugin opening and writing the file synchronously on the calling
+/// thread (including the main thread during startup).
+///
+/// Lines logged before the writer is started are buffered, up to
+/// `LOG_BUFFER_LINES`; lines are dropped rather than blocking the caller when
+/// the buffer is full.
+///
+/// The log file is rotated so long-lived installs do not slowly fill the
+/// device storage (`Rotation`, configured in `create_app()`):
+/// - the file is rotated when it reaches `max_file_bytes`: `app.log` becomes
+///   `app.log.1`, `app.log.1` becomes `app.log.2`, and so on
+/// - at most `max_files` files are kept, current file included
+/// - rotated files older than `max_age` are deleted at startup (`cleanup`)
+///
+/// When the `json_logs` feature flag is enabled, each record is also written
+/// as a JSON line to a `.jsonl` file next to the log file (same rotation), so
+/// logs shipped to the backend can be parsed and searched: timestamp, level,
+/// module, message, installation id and context (`JsonRecord`).
+///
+/// Log files are not encrypted (unlike crash reports, see `artifacts`): lines
+/// are redacted before they are written, and the files stay in the
+/// app-private log directory.
+///
+/// The tail of the current log file can be read back as structured entries
+/// (`read_recent`), for the hidden debug screen of the frontend.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{LazyLock, Mutex, OnceLock, RwLock};
+use std::time::{Duration, SystemTime};
+
+use log::LevelFilter;
+
+use crate::constants;
+use crate::redact;
+use crate::{AppError, AppResult};
+
+/// Record queued for the writer thread
+struct LogEntry {
+    /// Human-readable line
+    line: String,
+    /// JSON line, if JSON logging is enabled
+    json: Option<String>,
+}
+
+/// Channel between loggers and the writer thread
+struct LogChannel {
+    sender: SyncSender<LogEntry>,
+    /// Taken by the writer thread when it starts
+    receiver: Mutex<Option<Receiver<LogEntry>>>,
+}
+
+/// JSON lines are written, set from the `json_logs` feature flag
+static JSON_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Context attached to JSON records
+static CONTEXT: RwLock<LogContext> = RwLock::new(LogContext {
+    installation_id: None,
+    route: None,
+});
+
+/// Context of the app when a record was logged
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogContext {
+    /// Installation identifier
+    #[serde(skip_serializing)]
+    pub installation_id: Option<String>,
+    /// Last route displayed by the frontend
+    pub route: Option<String>,
+}
+
+/// Structured log record, written as one JSON line
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonRecord<'a> {
+    /// Time of the record (milliseconds since the Unix epoch)
+    pub timestamp_ms: u64,
+    /// Level (`ERROR`, `WARN`, `INFO`, `DEBUG`, `TRACE`)
+    pub level: &'a str,
+    /// Module path of the record
+    pub module: &'a str,
+    /// Redacted message
+    pub message: String,
+    /// Installation identifier
+    pub installation_id: Option<&'a str>,
+    /// App context
+    pub context: &'a LogContext,
+}
+
+/// Current log file, set by `start()`
+static FILE_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Log file line parsed back into its parts
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLine {
+    /// Time of the record (seconds since the Unix epoch)
+    pub timestamp: u64,
+    /// Module path of the record
+    pub module: String,
+    /// Level (`ERROR`, `WARN`, `INFO`, `DEBUG`, `TRACE`)
+    pub level: String,
+    /// Redacted message, possibly spanning several lines
+    pub message: String,
+}
+
+/// Rotation limits, set by `configure()`
+static ROTATION: OnceLock<Rotation> = OnceLock::new();
+
+/// Log file rotation limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rotation {
+    /// Size at which the log file is rotated (bytes)
+    pub max_file_bytes: u64,
+    /// Number of log files kept, current file included
+    pub max_files: usize,
+    /// Age after which rotated files are deleted
+    pub max_age: Duration,
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: constants::LOG_MAX_FILE_BYTES,
+            max_files: constants::LOG_MAX_FILES,
+            max_age: Duration::from_secs(constants::LOG_MAX_AGE_SECS),
+        }
+    }
+}
+
+impl Rotation {
+    /// Maximum disk space used by the log files (bytes)
+    pub fn max_total_bytes(&self) -> u64 {
+        self.max_file_bytes.saturating_mul(self.max_files as u64)
+    }
+}
+
+/// Set the rotation limits of the log file
+///
+/// Called in `create_app()`, before the writer starts. Later calls are ignored.
+pub fn configure(rotation: Rotation) {
+    let _ = ROTATION.set(rotation);
+}
+
+fn rotation() -> Rotation {
+    ROTATION.get().copied().unwrap_or_default()
+}
+
+static CHANNEL: LazyLock<LogChannel> = LazyLock::new(|| {
+    let (sender, receiver) = mpsc::sync_channel(constants::LOG_BUFFER_LINES);
+    LogChannel { sender, receiver: Mutex::new(Some(receiver)) }
+});
+
+/// Format a log record as a file line
+pub fn format_line(record: &log::Record, timestamp: u64) -> String {
+    format!("[{}][{}][{}] {}\n", timestamp, record.target(), record.level(), record.args())
+}
+
+/// Format a log record as a JSON line
+pub fn format_json(record: &log::Record, timestamp_ms: u64, context: &LogContext) -> String {
+    let json = JsonRecord {
+        timestamp_ms,
+        level: record.level().as_str(),
+        module: record.target(),
+        message: record.args().to_string(),
+        installation_id: context.installation_id.as_deref(),
+        context,
+    };
+    serde_json::to_string(&json).map_or_else(|_| String::new(), |line| line + "\n")
+}
+
+/// Enable or disable the JSON log file
+///
+/// Called whenever the feature flags are evaluated.
+pub fn set_json_enabled(enabled: bool) {
+    JSON_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Set the installation identifier attached to JSON records
+pub fn set_installation_id(installation_id: &str) {
+    if let Ok(mut context) = CONTEXT.write() {
+        context.installation_id = Some(installation_id.to_string());
+    }
+}
+
+/// Route as attached to JSON records: without its query string or
+/// fragment, and redacted
+fn log_route(route: &str) -> String {
+    let path = route.split(['?', '#']).next().unwrap_or_default();
+    redact::redact(path)
+}
+
+/// Set the route attached to JSON records
+pub fn set_route(route: Option<String>) {
+    if let Ok(mut context) = CONTEXT.write() {
+        context.route = route.as_deref().map(log_route);
+    }
+}
+
+/// Current time (milliseconds since the Unix epoch)
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Format a log record as a JSON line with the current context
+///
+/// Never blocks logging: returns `None` if the context is being updated.
+pub fn json_line(record: &log::Record, timestamp_ms: u64) -> Option<String> {
+    CONTEXT
+        .try_read()
+        .ok()
+        .map(|context| format_json(record, timestamp_ms, &context))
+}
+
+/// Queue a log record for the log file
+///
+/// Registered as a log target in `create_app()`.
+pub fn record(record: &log::Record) {
+    let timestamp_ms = now_ms();
+    let json = JSON_ENABLED
+        .load(Ordering::Relaxed)
+        .then(|| json_line(record, timestamp_ms))
+        .flatten();
+    let _ = CHANNEL.sender.try_send(LogEntry {
+        line: format_line(record, timestamp_ms / 1000),
+        json,
+    });
+}
+
+/// Parse a line written by `format_line`
+///
+/// # Returns
+///
+/// Returns the parsed line, or `None` if the line does not start a record
+/// (continuation of a multi-line message).
+pub fn parse_line(line: &str) -> Option<LogLine> {
+    let rest = line.strip_prefix('[')?;
+    let (timestamp, rest) = rest.split_once("][")?;
+    let (module, rest) = rest.split_once("][")?;
+    let (level, message) = rest.split_once("] ")?;
+    log::Level::from_str(level).ok()?;
+    Some(LogLine {
+        timestamp: timestamp.parse().ok()?,
+        module: module.to_string(),
+        level: level.to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// Parse the last `limit` records of log file contents at `min_level` or more
+/// severe, oldest first
+pub fn parse_recent(contents: &str, min_level: LevelFilter, limit: usize) -> Vec<LogLine> {
+    let mut lines: Vec<LogLine> = Vec::new();
+    for line in contents.lines() {
+        match parse_line(line) {
+            Some(parsed) => lines.push(parsed),
+            // Continuation of the previous message; skipped at the start of
+            // the tail, whose first record may have been rotated out
+            None => {
+                if let Some(last) = lines.last_mut() {
+                    last.message.push('\n');
+                    last.message.push_str(line);
+                }
+            }
+        }
+    }
+    lines.retain(|line| log::Level::from_str(&line.level).is_ok_and(|level| level <= min_level));
+    let skip = lines.len().saturating_sub(limit);
+    lines.split_off(skip)
+}
+
+/// Read the last `limit` records of the current log file at `min_level` or
+/// more severe, oldest first
+///
+/// # Returns
+///
+/// Returns the records, or an error if the writer is not started or
+/// the file cannot be read.
+pub fn read_recent(min_level: LevelFilter, limit: usize) -> AppResult<Vec<LogLine>> {
+    let path = FILE_PATH.get().ok_or_else(|| AppError::Storage("Log writer not started".to_string()))?;
+    let contents = match std::fs::read(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(AppError::Storage(format!("Failed to read log file: {}", e))),
+    };
+    Ok(parse_recent(&String::from_utf8_lossy(&contents), min_level, limit))
+}
+
+/// Name of the JSON log file of a log file (`app.log` → `app.jsonl`)
+pub fn json_file_name(file_name: &str) -> String {
+    Path::new(file_name)
+        .with_extension(constants::LOG_JSON_EXTENSION)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Path of the rotated file `index` (`app.log.1` is the most recent)
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Rotate the log file at `path`, shifting the rotated files and deleting
+/// the oldest one beyond `max_files`
+pub fn rotate(path: &Path, rotation: &Rotation) -> std::io::Result<()> {
+    let keep = rotation.max_files.saturating_sub(1);
+    if keep == 0 {
+        return remove_if_exists(path);
+    }
+    remove_if_exists(&rotated_path(path, keep))?;
+    for index in (1..keep).rev() {
+        let from = rotated_path(path, index);
+        if from.exists() {
+            std::fs::rename(&from, rotated_path(path, index + 1))?;
+        }
+    }
+    std::fs::rename(path, rotated_path(path, 1))
+}
+
+/// Delete the log files exceeding the rotation limits
+///
+/// Rotates the current file if already too large, then deletes rotated files
+/// beyond `max_files` or older than `max_age`. Other files of the log
+/// directory are left untouched.
+///
+/// # Returns
+///
+/// Returns the number of deleted files.
+pub fn cleanup(dir: &Path, file_name: &str, rotation: &Rotation, now: SystemTime) -> usize {
+    let path = dir.join(file_name);
+    let size = std::fs::metadata(&path).map_or(0, |metadata| metadata.len());
+    if size >= rotation.max_file_bytes {
+        if let Err(e) = rotate(&path, rotation) {
+            eprintln!("Failed to rotate log file {}: {}", path.display(), e);
+        }
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let prefix = format!("{}.", file_name);
+    let mut removed = 0;
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let name = entry.file_name();
+        let Some(index) = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(&prefix))
+            .and_then(|index| index.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        let expired = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age > rotation.max_age);
+        if (index >= rotation.max_files || expired) && std::fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Disk space used by the log directory, as reported to the frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LogStorageUsage {
+    /// Total size of the log files (bytes)
+    pub total_bytes: u64,
+    /// Number of log files
+    pub file_count: usize,
+    /// Maximum size of the log files under the rotation limits (bytes)
+    pub max_bytes: u64,
+}
+
+/// Measure the disk space used by the log directory
+pub fn storage_usage(dir: &Path) -> LogStorageUsage {
+    let mut usage = LogStorageUsage {
+        max_bytes: rotation().max_total_bytes(),
+        ..LogStorageUsage::default()
+    };
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for metadata in entries.filter_map(|entry| entry.ok()?.metadata().ok()) {
+            if metadata.is_file() {
+                usage.total_bytes += metadata.len();
+                usage.file_count += 1;
+            }
+        }
+    }
+    usage
+}
+
+/// Open the log file for appending
+///
+/// # Returns
+///
+/// Returns the writer and the current size of the file.
+fn open(path: &Path) -> std::io::Result<(BufWriter<File>, u64)> {
+    let file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    let size = file.metadata()?.len();
+    Ok((BufWriter::new(file), size))
+}
+
+/// Open the log file emptied, when it cannot be rotated
+fn truncate(path: &Path) -> std::io::Result<(BufWriter<File>, u64)> {
+    let file = std::fs::OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+    Ok((BufWriter::new(file), 0))
+}
+
+/// Log file written by the writer thread, opened on first write
+struct RotatingFile {
+    path: PathBuf,
+    file: Option<(BufWriter<File>, u64)>,
+}
+
+impl RotatingFile {
+    fn new(path: PathBuf) -> Self {
+        Self { path, file: None }
+    }
+
+    /// Append a line, rotating the file first if it would exceed the limit
+    ///
+    /// If the file cannot be rotated, it is emptied instead, so it never
+    /// grows past the limit.
+    fn write(&mut self, line: &str, rotation: &Rotation) {
+        let full = matches!(
+            &self.file,
+            Some((_, size)) if *size > 0 && *size + line.len() as u64 > rotation.max_file_bytes
+        );
+        let mut rotated = true;
+        if full {
+            self.flush();
+            self.file = None;
+            if let Err(e) = rotate(&self.path, rotation) {
+                eprintln!("Failed to rotate log file {}: {}", self.path.display(), e);
+                rotated = false;
+            }
+        }
+        if self.file.is_none() {
+            let opened = if rotated { open(&self.path) } else { truncate(&self.path) };
+            match opened {
+                Ok(opened) => self.file = Some(opened),
+                Err(e) => {
+                    eprintln!("Failed to open log file {}: {}", self.path.display(), e);
+                    return;
+                }
+            }
+        }
+        if let Some((file, size)) = &mut self.file {
+            if file.write_all(line.as_bytes()).is_ok() {
+                *size += line.len() as u64;
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some((file, _)) = &mut self.file {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Start the writer thread appending queued lines to `dir/file_name`
+///
+/// The writer first runs a cleanup pass (`cleanup`), then rotates the files
+/// whenever they reach the configured size. JSON lines go to
+/// `json_file_name(file_name)`.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the thread is started, or an error if the
+/// writer was already started or the thread could not be spawned.
+pub fn start(dir: PathBuf, file_name: &str) -> AppResult<()> {
+    let receiver = CHANNEL
+        .receiver
+        .lock()
+        .map_err(|e| AppError::Storage(e.to_string()))?
+        .take()
+        .ok_or_else(|| AppError::Storage("Log writer already started".to_string()))?;
+    let file_name = file_name.to_string();
+    let json_file_name = json_file_name(&file_name);
+    let rotation = rotation();
+    let _ = FILE_PATH.set(dir.join(&file_name));
+
+    std::thread::Builder::new()
+        .name("log-writer".to_string())
+        .spawn(move || {
+            if let Err(e) = std::fs::create_dir_all(&dir) {
+                eprintln!("Failed to create log directory {}: {}", dir.display(), e);
+                return;
+            }
+            let now = SystemTime::now();
+            cleanup(&dir, &file_name, &rotation, now);
+            cleanup(&dir, &json_file_name, &rotation, now);
+
+            let mut files = [
+                RotatingFile::new(dir.join(&file_name)),
+                RotatingFile::new(dir.join(&json_file_name)),
+            ];
+            let write = |files: &mut [RotatingFile; 2], entry: LogEntry| {
+                files[0].write(&entry.line, &rotation);
+                if let Some(json) = entry.json {
+                    files[1].write(&json, &rotation);
+                }
+            };
+
+            while let Ok(entry) = receiver.recv() {
+                write(&mut files, entry);
+                // Flush once the backlog is written
+                while let Ok(entry) = receiver.try_recv() {
+                    write(&mut files, entry);
+                }
+                files.iter_mut().for_each(RotatingFile::flush);
+            }
+        })
+        .map(|_| ())
+        .map_err(|e| AppError::Storage(format!("Failed to start log writer: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_line() {
+        let line = format_line(
+            &log::Record::builder()
+                .args(format_args!("hello"))
+                .level(log::Level::Warn)
+                .target("elulib_mobile::lifecycle")
+                .build(),
+            42,
+        );
+        assert_eq!(line, "[42][elulib_mobile::lifecycle][WARN] hello\n");
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line("[42][elulib_mobile::lifecycle][WARN] hello [world]"),
+            Some(LogLine {
+                timestamp: 42,
+                module: "elulib_mobile::lifecycle".to_string(),
+                level: "WARN".to_string(),
+                message: "hello [world]".to_string(),
+            })
+        );
+        assert_eq!(parse_line("  at src/lib.rs:1"), None);
+        assert_eq!(parse_line("[42][app][LOUD] hello"), None);
+    }
+
+    #[test]
+    fn test_parse_recent() {
+        let contents = "second line of a rotated record\n\
+            [1][app][INFO] started\n\
+            [2][app][ERROR] failed:\n\
+            caused by timeout\n\
+            [3][app][DEBUG] retrying\n\
+            [4][app][WARN] slow\n";
+
+        let lines = parse_recent(contents, LevelFilter::Warn, 10);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].message, "failed:\ncaused by timeout");
+        assert_eq!(lines[1].timestamp, 4);
+
+        let tail = parse_recent(contents, LevelFilter::Trace, 2);
+        let timestamps: Vec<u64> = tail.iter().map(|line| line.timestamp).collect();
+        assert_eq!(timestamps, [3, 4], "Most recent records, oldest first");
+    }
+
+    #[test]
+    fn test_format_json() {
+        let context = LogContext {
+            installation_id: Some("install-1".to_string()),
+            route: Some("/documents".to_string()),
+        };
+        let line = format_json(
+            &log::Record::builder()
+                .args(format_args!("hello \"world\""))
+                .level(log::Level::Info)
+                .target("elulib_mobile::auth")
+                .build(),
+            42_000,
+            &context,
+        );
+        assert!(line.ends_with('\n'));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&line).unwrap(),
+            serde_json::json!({
+                "timestampMs": 42_000,
+                "level": "INFO",
+                "module": "elulib_mobile::auth",
+                "message": "hello \"world\"",
+                "installationId": "install-1",
+                "context": { "route": "/documents" },
+            })
+        );
+        assert_eq!(json_file_name("elulib.log"), "elulib.jsonl");
+    }
+
+    #[test]
+    fn test_log_route_drops_query_and_secrets() {
+        assert_eq!(log_route("/documents"), "/documents");
+        assert_eq!(log_route("/reset?token=abc123#top"), "/reset");
+        assert!(!log_route("/users/jane@example.com").contains("jane@example.com"));
+    }
+
+    fn rotation(max_files: usize) -> Rotation {
+        Rotation {
+            max_file_bytes: 10,
+            max_files,
+            max_age: Duration::from_secs(3600),
+        }
+    }
+
+    #[test]
+    fn test_rotate_keeps_max_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        for content in ["first", "second", "third", "fourth"] {
+            std::fs::write(&path, content).unwrap();
+            rotate(&path, &rotation(3)).unwrap();
+        }
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_to_string(rotated_path(&path, 1)).unwrap(), "fourth");
+        assert_eq!(std::fs::read_to_string(rotated_path(&path, 2)).unwrap(), "third");
+        assert!(!rotated_path(&path, 3).exists(), "Current file counts toward max_files");
+    }
+
+    #[test]
+    fn test_writer_rotates_at_max_file_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let mut file = RotatingFile::new(path.clone());
+        for line in ["line 1\n", "line 2\n", "line 3\n"] {
+            file.write(line, &rotation(2));
+        }
+        file.flush();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "line 3\n");
+        assert_eq!(std::fs::read_to_string(rotated_path(&path, 1)).unwrap(), "line 2\n");
+        assert!(!rotated_path(&path, 2).exists());
+    }
+
+    #[test]
+    fn test_cleanup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "more than ten bytes").unwrap();
+        std::fs::write(rotated_path(&path, 1), "old").unwrap();
+        std::fs::write(rotated_path(&path, 7), "beyond max files").unwrap();
+        std::fs::write(dir.path().join("other.txt"), "unrelated").unwrap();
+
+        // The oversized current file is rotated, `.1` shifted to `.2`
+        assert_eq!(cleanup(dir.path(), "app.log", &rotation(3), SystemTime::now()), 1);
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_to_string(rotated_path(&path, 2)).unwrap(), "old");
+        assert!(dir.path().join("other.txt").exists());
+
+        // A week later, rotated files have expired
+        let later = SystemTime::now() + Duration::from_secs(7 * 24 * 3600);
+        assert_eq!(cleanup(dir.path(), "app.log", &rotation(3), later), 2);
+
+        let usage = storage_usage(dir.path());
+        assert_eq!(usage.file_count, 1);
+        assert_eq!(usage.total_bytes, "unrelated".len() as u64);
+    }
+}
@@ -0,0 +1,150 @@
+/// Internal native module architecture
+///
+/// Native subsystems implement `NativeModule`: a name, the commands and
+/// events they provide, and `init` / `shutdown` hooks. `create_app()`
+/// registers the modules of the build (`NativeModules`), which are then
+/// driven uniformly:
+/// - `init` runs once during setup, in registration order; a failing module
+///   is logged and the app starts without it
+/// - `shutdown` runs when the app exits, in reverse order
+/// - each module is compiled in with its cargo feature (`module-keychain`,
+///   `module-notifications`, `module-connectivity`, `module-realtime`,
+///   `module-sse`, `module-watch`, `module-shortcuts`, all enabled by
+///   default); a module left out of the build has no code in the binary:
+///   its commands are neither registered nor exported in the bindings, and
+///   the app never calls into it (lifecycle, locale, logout and lock hooks)
+///
+/// Commands are still registered in `run()` (`app_commands!`, a static list
+/// shared with the bindings) and events declared in `events`: a module lists
+/// them, which the tests check against both registries.
+
+use tauri::{AppHandle, Manager, RunEvent};
+use crate::AppResult;
+
+/// Native subsystem plugged into the app
+pub trait NativeModule: Send + Sync {
+    /// Name of the module, as in its cargo feature (`module-<name>`)
+    fn name(&self) -> &'static str;
+
+    /// Commands provided by the module
+    fn commands(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Channels of the events emitted by the module
+    fn events(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Start the module, during setup
+    fn init(&self, _app: &AppHandle) -> AppResult<()> {
+        Ok(())
+    }
+
+    /// Release the resources of the module, when the app exits
+    fn shutdown(&self, _app: &AppHandle) {}
+}
+
+/// Modules compiled in this build
+fn catalog() -> Vec<Box<dyn NativeModule>> {
+    vec![
+        #[cfg(feature = "module-keychain")]
+        Box::new(crate::plugins::KeychainModule),
+        #[cfg(feature = "module-notifications")]
+        Box::new(crate::notification_bridge::NotificationsModule),
+        #[cfg(feature = "module-connectivity")]
+        Box::new(crate::connectivity::ConnectivityModule),
+        #[cfg(feature = "module-realtime")]
+        Box::new(crate::websocket::RealtimeModule),
+        #[cfg(feature = "module-sse")]
+        Box::new(crate::sse::SseModule),
+        #[cfg(feature = "module-watch")]
+        Box::new(crate::watch::WatchModule),
+        #[cfg(feature = "module-shortcuts")]
+        Box::new(crate::shortcuts::ShortcutsModule),
+    ]
+}
+
+/// Modules of the build, managed by the Tauri application
+pub struct NativeModules {
+    modules: Vec<Box<dyn NativeModule>>,
+}
+
+impl Default for NativeModules {
+    fn default() -> Self {
+        NativeModules { modules: catalog() }
+    }
+}
+
+impl NativeModules {
+    /// Names of the registered modules
+    pub fn names(&self) -> Vec<&'static str> {
+        self.modules.iter().map(|module| module.name()).collect()
+    }
+
+    /// Start the registered modules
+    pub fn init(&self, app: &AppHandle) {
+        for module in &self.modules {
+            match module.init(app) {
+                Ok(()) => tracing::debug!("Native module {} started", module.name()),
+                Err(e) => tracing::error!("Failed to start native module {}: {}", module.name(), e),
+            }
+        }
+    }
+
+    /// Stop the registered modules, last started first
+    pub fn shutdown(&self, app: &AppHandle) {
+        for module in self.modules.iter().rev() {
+            module.shutdown(app);
+            tracing::debug!("Native module {} stopped", module.name());
+        }
+    }
+}
+
+/// Stop the modules when the app exits
+pub fn handle_run_event(app: &AppHandle, event: &RunEvent) {
+    if let RunEvent::Exit = event {
+        app.state::<NativeModules>().shutdown(app);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_scope::COMMAND_SCOPES;
+
+    #[test]
+    fn test_modules_declare_registered_commands_and_events() {
+        let channels: Vec<&str> = crate::events::registry(&mut specta::TypeCollection::default())
+            .into_iter()
+            .map(|(channel, _)| channel)
+            .collect();
+        let mut owned = Vec::new();
+        for module in catalog() {
+            for command in module.commands() {
+                assert!(COMMAND_SCOPES.iter().any(|(name, _)| name == command), "{} is not registered", command);
+                assert!(!owned.contains(command), "{} belongs to two modules", command);
+                owned.push(*command);
+            }
+            for event in module.events() {
+                assert!(channels.contains(event), "{} is not declared in events", event);
+            }
+        }
+    }
+
+    #[test]
+    fn test_modules_follow_their_feature() {
+        let names = NativeModules::default().names();
+        for (name, enabled) in [
+            ("keychain", cfg!(feature = "module-keychain")),
+            ("notifications", cfg!(feature = "module-notifications")),
+            ("connectivity", cfg!(feature = "module-connectivity")),
+            ("realtime", cfg!(feature = "module-realtime")),
+            ("sse", cfg!(feature = "module-sse")),
+            ("watch", cfg!(feature = "module-watch")),
+            ("shortcuts", cfg!(feature = "module-shortcuts")),
+        ] {
+            assert_eq!(names.contains(&name), enabled, "module {}", name);
+        }
+    }
+}
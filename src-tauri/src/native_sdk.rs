@@ -0,0 +1,72 @@
+/// Injected JavaScript SDK module
+///
+/// Every page of the remote frontend gets `window.EluNative`, injected by a
+/// plugin before any page script runs (`native-sdk.js`), so all pages call the
+/// native layer with the same semantics:
+/// - `invoke(command, args, { timeoutMs, retries, requestId })` tags each call
+///   with a request id (`REQUEST_ID_HEADER`, logged by the native layer with
+///   the command), rejects after `NATIVE_SDK_TIMEOUT_MS`, settles
+///   `CommandResponse` envelopes with their data, and retries retryable
+///   errors and rate limited calls (at most `NATIVE_SDK_MAX_RETRIES` times,
+///   with exponential backoff)
+/// - failures reject with a `NativeError` carrying `command`, `requestId`,
+///   `code` (the envelope code, `RATE_LIMITED`, `TIMEOUT` or `REJECTED`) and
+///   `retryable`
+/// - `on(event, handler)` subscribes to a native event and resolves with the
+///   unsubscribe function; `once(event)` resolves with the next payload
+///
+/// The SDK is untyped; the generated bindings (`bindings`) remain the typed
+/// interface, and `*_packed` results are returned undecoded.
+
+use serde_json::json;
+use tauri::ipc::Invoke;
+use tauri::plugin::TauriPlugin;
+use tauri::{Runtime, Wry};
+
+use crate::constants;
+
+/// SDK function, called by the init script with its configuration
+const SDK_SOURCE: &str = include_str!("../native-sdk.js");
+
+/// Init script defining `window.EluNative`
+pub fn init_script() -> String {
+    let config = json!({
+        "global": constants::NATIVE_SDK_GLOBAL,
+        "version": env!("CARGO_PKG_VERSION"),
+        "requestIdHeader": constants::REQUEST_ID_HEADER,
+        "timeoutMs": constants::NATIVE_SDK_TIMEOUT_MS,
+        "maxRetries": constants::NATIVE_SDK_MAX_RETRIES,
+        "retryBaseDelayMs": constants::NATIVE_SDK_RETRY_BASE_DELAY_MS,
+    });
+    format!("{}({});\n", SDK_SOURCE.trim_end(), config)
+}
+
+/// Plugin injecting the SDK into the remote frontend
+pub fn plugin() -> TauriPlugin<Wry> {
+    tauri::plugin::Builder::new("elu-native").js_init_script(init_script()).build()
+}
+
+/// Request id of an invocation made through the SDK, if any
+pub fn request_id<R: Runtime>(invoke: &Invoke<R>) -> Option<&str> {
+    invoke
+        .message
+        .headers()
+        .get(constants::REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= constants::REQUEST_ID_MAX_LEN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_script_calls_sdk_with_config() {
+        let script = init_script();
+        assert!(script.starts_with("// window.EluNative"));
+        assert!(script.contains("})({\""), "The SDK function is called");
+        assert!(script.trim_end().ends_with("});"));
+        assert!(script.contains(&format!("\"requestIdHeader\":\"{}\"", constants::REQUEST_ID_HEADER)));
+        assert!(script.contains(&format!("\"global\":\"{}\"", constants::NATIVE_SDK_GLOBAL)));
+    }
+}
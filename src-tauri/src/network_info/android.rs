@@ -0,0 +1,40 @@
+/// Android-specific network information implementation
+///
+/// This module reads the active network with `ConnectivityManager` and
+/// `NetworkCapabilities` from the Android SDK.
+///
+/// Note: This implementation provides the structure for Android network info.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::NativeNetworkState;
+use crate::AppResult;
+
+/// Get the network state on Android
+///
+/// # Returns
+///
+/// Returns the `NativeNetworkState` of the device.
+pub fn native_state() -> AppResult<NativeNetworkState> {
+    // TODO: Implement native Android lookup
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val cm = context.getSystemService(Context.CONNECTIVITY_SERVICE) as ConnectivityManager
+    // val network = cm.activeNetwork
+    // val caps = cm.getNetworkCapabilities(network)
+    // val transport = when {
+    //     caps == null -> "none"
+    //     caps.hasTransport(NetworkCapabilities.TRANSPORT_WIFI) -> "wifi"
+    //     caps.hasTransport(NetworkCapabilities.TRANSPORT_CELLULAR) -> "cellular"
+    //     caps.hasTransport(NetworkCapabilities.TRANSPORT_ETHERNET) -> "ethernet"
+    //     else -> "other"
+    // }
+    // val vpnActive = caps?.hasTransport(NetworkCapabilities.TRANSPORT_VPN) == true
+    // val interfaceName = cm.getLinkProperties(network)?.interfaceName
+    // val metered = caps?.hasCapability(NetworkCapabilities.NET_CAPABILITY_NOT_METERED) != true
+    // ```
+
+    // Placeholder: Report unknown network state
+    // Replace this with actual native implementation
+    Ok(NativeNetworkState::default())
+}
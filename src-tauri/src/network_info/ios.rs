@@ -0,0 +1,35 @@
+/// iOS-specific network information implementation
+///
+/// This module reads the active path with `NWPathMonitor` from the Network
+/// framework and the VPN state from the system proxy settings.
+///
+/// Note: This implementation provides the structure for iOS network info.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::NativeNetworkState;
+use crate::AppResult;
+
+/// Get the network state on iOS
+///
+/// # Returns
+///
+/// Returns the `NativeNetworkState` of the device.
+pub fn native_state() -> AppResult<NativeNetworkState> {
+    // TODO: Implement native iOS lookup
+    // Example Swift implementation:
+    // ```swift
+    // let path = NWPathMonitor().currentPath
+    // let transport = path.usesInterfaceType(.wifi) ? "wifi"
+    //     : path.usesInterfaceType(.cellular) ? "cellular"
+    //     : path.usesInterfaceType(.wiredEthernet) ? "ethernet" : "other"
+    // let interfaceName = path.availableInterfaces.first?.name
+    // let scoped = (CFNetworkCopySystemProxySettings()?.takeRetainedValue() as? [String: Any])?["__SCOPED__"] as? [String: Any]
+    // let vpnActive = scoped?.keys.contains { ["tap", "tun", "ppp", "ipsec", "utun"].contains(where: $0.hasPrefix) } ?? false
+    // let metered = path.isExpensive || path.isConstrained
+    // ```
+
+    // Placeholder: Report unknown network state
+    // Replace this with actual native implementation
+    Ok(NativeNetworkState::default())
+}
@@ -0,0 +1,144 @@
+/// Network interface details
+///
+/// This module reports the active network interface, the local IP address
+/// used to reach the application server, and whether a VPN is active (a
+/// common cause of connectivity failures on managed devices).
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::net::IpAddr;
+
+use tokio::net::UdpSocket;
+
+use crate::runtime_config;
+use crate::AppResult;
+
+/// Transport of the active network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkTransport {
+    /// Wi-Fi network
+    Wifi,
+    /// Cellular data network
+    Cellular,
+    /// Wired network
+    Ethernet,
+    /// Other or unknown transport
+    #[default]
+    Other,
+    /// No active network
+    None,
+}
+
+/// Network state reported by the platform
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NativeNetworkState {
+    /// Name of the active interface (`en0`, `wlan0`, `rmnet0`, ...)
+    pub interface_name: Option<String>,
+    /// Transport of the active network
+    pub transport: NetworkTransport,
+    /// A VPN is active
+    pub vpn_active: bool,
+    /// The network is metered (cellular, personal hotspot, data saver)
+    pub metered: bool,
+}
+
+/// Network interface details reported to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInterfaces {
+    /// Name of the active interface, if known
+    pub active_interface: Option<String>,
+    /// Transport of the active network
+    pub transport: NetworkTransport,
+    /// Local IP address used to reach the application server, if any
+    pub local_ip: Option<String>,
+    /// A VPN is active
+    pub vpn_active: bool,
+}
+
+/// Interface name prefixes used by VPN tunnels
+const VPN_INTERFACE_PREFIXES: &[&str] = &["tun", "tap", "ppp", "ipsec", "utun", "wg"];
+
+/// Returns `true` if `name` looks like a VPN tunnel interface
+pub fn is_vpn_interface(name: &str) -> bool {
+    VPN_INTERFACE_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Get the network state from the platform
+///
+/// # Returns
+///
+/// Returns the `NativeNetworkState`, or an error if the platform
+/// query fails.
+pub fn native_state() -> AppResult<NativeNetworkState> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::native_state()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::native_state()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(NativeNetworkState::default())
+    }
+}
+
+/// Find the local IP address used to reach the application server
+///
+/// Connecting a UDP socket only selects a route; no packet is sent.
+///
+/// # Returns
+///
+/// Returns `None` if there is no route to the server.
+pub async fn local_ip() -> Option<IpAddr> {
+    let config = runtime_config::get();
+    let socket = UdpSocket::bind("0.0.0.0:0").await.ok()?;
+    socket
+        .connect((config.connectivity_host.as_str(), config.connectivity_port))
+        .await
+        .ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
+}
+
+/// Collect network interface details
+///
+/// # Returns
+///
+/// Returns the `NetworkInterfaces`, or an error if the platform
+/// query fails.
+pub async fn collect() -> AppResult<NetworkInterfaces> {
+    let native = native_state()?;
+    let vpn_active = native.vpn_active
+        || native.interface_name.as_deref().is_some_and(is_vpn_interface);
+
+    Ok(NetworkInterfaces {
+        active_interface: native.interface_name,
+        transport: native.transport,
+        local_ip: local_ip().await.map(|ip| ip.to_string()),
+        vpn_active,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_vpn_interface() {
+        assert!(is_vpn_interface("utun3"));
+        assert!(is_vpn_interface("tun0"));
+        assert!(is_vpn_interface("ipsec0"));
+        assert!(!is_vpn_interface("en0"));
+        assert!(!is_vpn_interface("wlan0"));
+        assert!(!is_vpn_interface("rmnet_data0"));
+    }
+}
@@ -1,10 +1,49 @@
 /// Notification bridge module
 ///
 /// This module provides functionality to convert web notifications
-/// from the remote frontend into native push notifications.
+/// from the remote frontend into native push notifications. Its commands form
+/// the `notifications` native module (`NotificationsModule`).
 
 use tauri::AppHandle;
+use crate::command_response::{CommandError, CommandResponse};
+use crate::constants;
+use crate::native_module::NativeModule;
 use crate::notifications;
+use crate::AppResult;
+
+/// Notifications native module (`module-notifications`)
+pub struct NotificationsModule;
+
+impl NativeModule for NotificationsModule {
+    fn name(&self) -> &'static str {
+        "notifications"
+    }
+
+    fn commands(&self) -> &'static [&'static str] {
+        &[
+            "show_notification",
+            "request_notification_permission",
+            "check_notification_permission",
+            "is_notification_supported",
+            "get_launch_payload",
+        ]
+    }
+
+    fn events(&self) -> &'static [&'static str] {
+        &[constants::NOTIFICATION_RECEIVED_EVENT]
+    }
+
+    /// Create the default notification channel (Android)
+    fn init(&self, _app: &AppHandle) -> AppResult<()> {
+        // Note: For remote frontends, the notification bridge script should be
+        // injected by the frontend itself or via a content script.
+        // The JavaScript bridge file is available at src-tauri/notification-bridge.js
+        // and should be loaded by the remote frontend or injected via Tauri's
+        // content script mechanism if available.
+        tracing::info!("Notification bridge module loaded - frontend should inject bridge script");
+        notifications::ensure_default_channel()
+    }
+}
 
 /// Show a native notification
 ///
@@ -20,15 +59,17 @@ use crate::notifications;
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` on success, or an error if the operation fails.
+/// Returns a `CommandResponse` without data on success, or with a
+/// `NOTIFICATION_FAILED` error.
 #[tauri::command]
+#[specta::specta]
 pub async fn show_notification(
     _app: AppHandle,
     title: String,
     body: String,
     icon: Option<String>,
-) -> Result<(), String> {
-    log::info!("Showing native notification: {} - {}", title, body);
+) -> CommandResponse<()> {
+    tracing::info!("Showing native notification: {} - {}", title, body);
     
     // Use platform-specific notification implementation
     notifications::show_notification(
@@ -36,6 +77,8 @@ pub async fn show_notification(
         &body,
         icon.as_deref(),
     )
+    .map_err(CommandError::from)
+    .into()
 }
 
 /// Request notification permissions
@@ -45,31 +88,35 @@ pub async fn show_notification(
 ///
 /// # Returns
 ///
-/// Returns `true` if permission is granted, `false` otherwise.
+/// Returns a `CommandResponse` with `true` if permission is granted, `false`
+/// otherwise, or with a `NOTIFICATION_FAILED` error.
 #[tauri::command]
-pub async fn request_notification_permission(_app: AppHandle) -> Result<bool, String> {
-    log::info!("Requesting notification permission");
+#[specta::specta]
+pub async fn request_notification_permission(_app: AppHandle) -> CommandResponse<bool> {
+    tracing::info!("Requesting notification permission");
     
     // Use platform-specific permission request
-    notifications::request_permission()
+    notifications::request_permission().map_err(CommandError::from).into()
 }
 
 /// Check if notifications are supported
 ///
 /// # Returns
 ///
-/// Returns `true` if notifications are supported on this platform.
+/// Returns a `CommandResponse` with `true` if notifications are supported on
+/// this platform.
 #[tauri::command]
-pub async fn is_notification_supported() -> Result<bool, String> {
+#[specta::specta]
+pub async fn is_notification_supported() -> CommandResponse<bool> {
     // Notifications are supported on both iOS and Android
     #[cfg(any(target_os = "ios", target_os = "android"))]
     {
-        Ok(true)
+        Ok::<_, CommandError>(true).into()
     }
     
     #[cfg(not(any(target_os = "ios", target_os = "android")))]
     {
-        Ok(false)
+        Ok::<_, CommandError>(false).into()
     }
 }
 
@@ -77,12 +124,14 @@ pub async fn is_notification_supported() -> Result<bool, String> {
 ///
 /// # Returns
 ///
-/// Returns `true` if permission is granted, `false` otherwise.
+/// Returns a `CommandResponse` with `true` if permission is granted, `false`
+/// otherwise, or with a `NOTIFICATION_FAILED` error.
 #[tauri::command]
-pub async fn check_notification_permission(_app: AppHandle) -> Result<bool, String> {
-    log::info!("Checking notification permission status");
+#[specta::specta]
+pub async fn check_notification_permission(_app: AppHandle) -> CommandResponse<bool> {
+    tracing::info!("Checking notification permission status");
     
     // Use platform-specific permission check
-    notifications::check_permission()
+    notifications::check_permission().map_err(CommandError::from).into()
 }
 
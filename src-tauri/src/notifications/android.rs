@@ -7,6 +7,8 @@
 /// The actual native implementation should be done in Java/Kotlin
 /// and connected via JNI or Tauri's native bridge.
 
+use crate::AppResult;
+
 /// Show a native Android notification
 ///
 /// # Arguments
@@ -18,14 +20,14 @@
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` on success, or an error message if the operation fails.
+/// Returns `Ok(())` on success, or an error if the operation fails.
 pub fn show_notification(
     title: &str,
     body: &str,
     channel_id: &str,
     icon: Option<&str>,
-) -> Result<(), String> {
-    log::info!("[Android] Showing notification: {} - {} (channel: {})", title, body, channel_id);
+) -> AppResult<()> {
+    tracing::info!("[Android] Showing notification: {} - {} (channel: {})", title, body, channel_id);
     
     // TODO: Implement native Android notification using NotificationManager
     // This requires:
@@ -49,7 +51,7 @@ pub fn show_notification(
     
     // For now, log the notification
     // In production, this should call the native implementation
-    log::debug!("[Android] Notification would be shown: {} - {} (channel: {}, icon: {:?})", 
+    tracing::debug!("[Android] Notification would be shown: {} - {} (channel: {}, icon: {:?})", 
                 title, body, channel_id, icon);
     
     // Placeholder: Return success
@@ -62,8 +64,8 @@ pub fn show_notification(
 /// # Returns
 ///
 /// Returns `true` if permission is granted, `false` otherwise.
-pub fn request_permission() -> Result<bool, String> {
-    log::info!("[Android] Requesting notification permission");
+pub fn request_permission() -> AppResult<bool> {
+    tracing::info!("[Android] Requesting notification permission");
     
     // TODO: Implement native Android permission request
     // For Android 13+, request POST_NOTIFICATIONS permission
@@ -88,7 +90,7 @@ pub fn request_permission() -> Result<bool, String> {
 /// # Returns
 ///
 /// Returns `true` if permission is granted, `false` otherwise.
-pub fn check_permission() -> Result<bool, String> {
+pub fn check_permission() -> AppResult<bool> {
     // TODO: Implement native Android permission check
     // Example Kotlin implementation:
     // ```kotlin
@@ -114,13 +116,13 @@ pub fn check_permission() -> Result<bool, String> {
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` on success, or an error message if the operation fails.
+/// Returns `Ok(())` on success, or an error if the operation fails.
 pub fn create_notification_channel(
     channel_id: &str,
     channel_name: &str,
     description: &str,
-) -> Result<(), String> {
-    log::info!("[Android] Creating notification channel: {} - {}", channel_id, channel_name);
+) -> AppResult<()> {
+    tracing::info!("[Android] Creating notification channel: {} - {}", channel_id, channel_name);
     
     // TODO: Implement native Android channel creation
     // Example Kotlin implementation:
@@ -143,3 +145,61 @@ pub fn create_notification_channel(
     Ok(())
 }
 
+
+/// Set the app icon badge count on Android
+///
+/// Android derives the launcher badge from the active notifications, so the
+/// count is only a hint for launchers supporting explicit counts.
+///
+/// # Arguments
+///
+/// * `count` - Badge count (`0` clears the badge)
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn set_badge_count(count: u32) -> AppResult<()> {
+    tracing::debug!("[Android] Setting badge count to {}", count);
+
+    // TODO: Implement native Android badge update
+    // Example Kotlin implementation:
+    // ```kotlin
+    // // Launchers show a badge while notifications are active; the count is
+    // // attached to the next notification with NotificationCompat.Builder.setNumber(count)
+    // ShortcutBadger.applyCount(context, count)
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
+
+/// Dismiss delivered notifications on Android
+///
+/// # Arguments
+///
+/// * `channel_id` - Only dismiss notifications of this channel, or all if `None`
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn clear_delivered(channel_id: Option<&str>) -> AppResult<()> {
+    tracing::debug!("[Android] Dismissing delivered notifications (channel: {:?})", channel_id);
+
+    // TODO: Implement native Android notification dismissal
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val notificationManager = context.getSystemService(Context.NOTIFICATION_SERVICE) as NotificationManager
+    // if (channelId == null) {
+    //     notificationManager.cancelAll()
+    // } else {
+    //     notificationManager.activeNotifications
+    //         .filter { it.notification.channelId == channelId }
+    //         .forEach { notificationManager.cancel(it.tag, it.id) }
+    // }
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
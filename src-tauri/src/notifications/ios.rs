@@ -7,6 +7,8 @@
 /// The actual native implementation should be done in Objective-C/Swift
 /// and connected via FFI or Tauri's native bridge.
 
+use crate::AppResult;
+
 /// Show a native iOS notification
 ///
 /// # Arguments
@@ -17,9 +19,9 @@
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` on success, or an error message if the operation fails.
-pub fn show_notification(title: &str, body: &str, identifier: Option<&str>) -> Result<(), String> {
-    log::info!("[iOS] Showing notification: {} - {}", title, body);
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn show_notification(title: &str, body: &str, identifier: Option<&str>) -> AppResult<()> {
+    tracing::info!("[iOS] Showing notification: {} - {}", title, body);
     
     // TODO: Implement native iOS notification using UNUserNotificationCenter
     // This requires:
@@ -53,7 +55,7 @@ pub fn show_notification(title: &str, body: &str, identifier: Option<&str>) -> R
     
     // For now, log the notification
     // In production, this should call the native implementation
-    log::debug!("[iOS] Notification would be shown: {} - {} (id: {:?})", title, body, identifier);
+    tracing::debug!("[iOS] Notification would be shown: {} - {} (id: {:?})", title, body, identifier);
     
     // Placeholder: Return success
     // Replace this with actual native implementation
@@ -65,8 +67,8 @@ pub fn show_notification(title: &str, body: &str, identifier: Option<&str>) -> R
 /// # Returns
 ///
 /// Returns `true` if permission is granted, `false` otherwise.
-pub fn request_permission() -> Result<bool, String> {
-    log::info!("[iOS] Requesting notification permission");
+pub fn request_permission() -> AppResult<bool> {
+    tracing::info!("[iOS] Requesting notification permission");
     
     // TODO: Implement native iOS permission request using UNUserNotificationCenter
     // Example Swift implementation:
@@ -86,7 +88,7 @@ pub fn request_permission() -> Result<bool, String> {
 /// # Returns
 ///
 /// Returns `true` if permission is granted, `false` otherwise.
-pub fn check_permission() -> Result<bool, String> {
+pub fn check_permission() -> AppResult<bool> {
     // TODO: Implement native iOS permission check using UNUserNotificationCenter
     // Example Swift implementation:
     // ```swift
@@ -100,3 +102,63 @@ pub fn check_permission() -> Result<bool, String> {
     Ok(true)
 }
 
+
+/// Set the app icon badge count on iOS
+///
+/// # Arguments
+///
+/// * `count` - Badge count (`0` clears the badge)
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn set_badge_count(count: u32) -> AppResult<()> {
+    tracing::debug!("[iOS] Setting badge count to {}", count);
+
+    // TODO: Implement native iOS badge update
+    // Example Swift implementation:
+    // ```swift
+    // if #available(iOS 16.0, *) {
+    //     UNUserNotificationCenter.current().setBadgeCount(count)
+    // } else {
+    //     UIApplication.shared.applicationIconBadgeNumber = count
+    // }
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
+
+/// Remove delivered notifications from the Notification Center on iOS
+///
+/// # Arguments
+///
+/// * `thread_id` - Only remove notifications of this thread, or all if `None`
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn clear_delivered(thread_id: Option<&str>) -> AppResult<()> {
+    tracing::debug!("[iOS] Removing delivered notifications (thread: {:?})", thread_id);
+
+    // TODO: Implement native iOS delivered notification removal
+    // Example Swift implementation:
+    // ```swift
+    // let center = UNUserNotificationCenter.current()
+    // guard let threadId = threadId else {
+    //     center.removeAllDeliveredNotifications()
+    //     return
+    // }
+    // center.getDeliveredNotifications { notifications in
+    //     let ids = notifications
+    //         .filter { $0.request.content.threadIdentifier == threadId }
+    //         .map { $0.request.identifier }
+    //     center.removeDeliveredNotifications(withIdentifiers: ids)
+    // }
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
@@ -1,7 +1,10 @@
 /// Platform-specific notification implementations
 ///
 /// This module provides platform-specific notification functionality
-/// for iOS and Android using native APIs.
+/// for iOS and Android using native APIs. On desktop, the `dev-mock` feature
+/// records notifications in an in-process mock (see `dev_mock`).
+
+use crate::AppResult;
 
 #[cfg(target_os = "ios")]
 mod ios;
@@ -9,6 +12,23 @@ mod ios;
 #[cfg(target_os = "android")]
 mod android;
 
+/// ID of the default Android notification channel
+#[cfg(target_os = "android")]
+const DEFAULT_CHANNEL_ID: &str = "elulib_default_channel";
+
+/// Notification channel used by the app, on platforms with channels
+pub fn default_channel() -> Option<&'static str> {
+    #[cfg(target_os = "android")]
+    {
+        Some(DEFAULT_CHANNEL_ID)
+    }
+
+    #[cfg(not(target_os = "android"))]
+    {
+        None
+    }
+}
+
 /// Show a native notification on the current platform
 ///
 /// # Arguments
@@ -19,8 +39,8 @@ mod android;
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` on success, or an error message if the operation fails.
-pub fn show_notification(title: &str, body: &str, icon: Option<&str>) -> Result<(), String> {
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn show_notification(title: &str, body: &str, icon: Option<&str>) -> AppResult<()> {
     #[cfg(target_os = "ios")]
     {
         // Generate a unique identifier for the notification
@@ -35,27 +55,46 @@ pub fn show_notification(title: &str, body: &str, icon: Option<&str>) -> Result<
     #[cfg(target_os = "android")]
     {
         // Android requires a notification channel
-        // Use default channel or create one if needed
-        const DEFAULT_CHANNEL_ID: &str = "elulib_default_channel";
-        const DEFAULT_CHANNEL_NAME: &str = "élulib Notifications";
-        const DEFAULT_CHANNEL_DESCRIPTION: &str = "Notifications from élulib app";
-        
         // Ensure channel exists (idempotent operation)
-        let _ = android::create_notification_channel(
-            DEFAULT_CHANNEL_ID,
-            DEFAULT_CHANNEL_NAME,
-            DEFAULT_CHANNEL_DESCRIPTION,
-        );
+        let _ = ensure_default_channel();
         
         android::show_notification(title, body, DEFAULT_CHANNEL_ID, icon)
     }
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(all(feature = "dev-mock", not(any(target_os = "ios", target_os = "android"))))]
+    {
+        crate::dev_mock::notifications::show(title, body, icon)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         // Fallback for other platforms (should not happen in mobile app)
         let _ = (title, body, icon); // Suppress unused variable warnings
-        log::warn!("Notifications not implemented for this platform");
-        Err("Notifications not supported on this platform".to_string())
+        tracing::warn!("Notifications not implemented for this platform");
+        Err(crate::AppError::Notification("Notifications not supported on this platform".to_string()))
+    }
+}
+
+/// Create the default notification channel, or rename it after a locale
+/// change (Android only, no-op elsewhere)
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn ensure_default_channel() -> AppResult<()> {
+    #[cfg(target_os = "android")]
+    {
+        let strings = crate::locale::strings();
+        android::create_notification_channel(
+            DEFAULT_CHANNEL_ID,
+            strings.notification_channel_name,
+            strings.notification_channel_description,
+        )
+    }
+    
+    #[cfg(not(target_os = "android"))]
+    {
+        Ok(())
     }
 }
 
@@ -64,7 +103,7 @@ pub fn show_notification(title: &str, body: &str, icon: Option<&str>) -> Result<
 /// # Returns
 ///
 /// Returns `true` if permission is granted, `false` otherwise.
-pub fn request_permission() -> Result<bool, String> {
+pub fn request_permission() -> AppResult<bool> {
     #[cfg(target_os = "ios")]
     {
         ios::request_permission()
@@ -75,7 +114,12 @@ pub fn request_permission() -> Result<bool, String> {
         android::request_permission()
     }
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(all(feature = "dev-mock", not(any(target_os = "ios", target_os = "android"))))]
+    {
+        crate::dev_mock::notifications::request_permission()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         Ok(false)
     }
@@ -86,7 +130,7 @@ pub fn request_permission() -> Result<bool, String> {
 /// # Returns
 ///
 /// Returns `true` if permission is granted, `false` otherwise.
-pub fn check_permission() -> Result<bool, String> {
+pub fn check_permission() -> AppResult<bool> {
     #[cfg(target_os = "ios")]
     {
         ios::check_permission()
@@ -97,12 +141,83 @@ pub fn check_permission() -> Result<bool, String> {
         android::check_permission()
     }
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(all(feature = "dev-mock", not(any(target_os = "ios", target_os = "android"))))]
+    {
+        crate::dev_mock::notifications::check_permission()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         Ok(false)
     }
 }
 
+/// Set the app icon badge count on the current platform
+///
+/// # Arguments
+///
+/// * `count` - Badge count (`0` clears the badge)
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn set_badge_count(count: u32) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::set_badge_count(count)
+    }
+    
+    #[cfg(target_os = "android")]
+    {
+        android::set_badge_count(count)
+    }
+    
+    #[cfg(all(feature = "dev-mock", not(any(target_os = "ios", target_os = "android"))))]
+    {
+        crate::dev_mock::notifications::set_badge_count(count)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
+    {
+        let _ = count;
+        Ok(())
+    }
+}
+
+/// Dismiss delivered notifications on the current platform
+///
+/// # Arguments
+///
+/// * `channel` - Only dismiss notifications of this channel (Android channel,
+///   iOS thread identifier), or all notifications if `None`
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn clear_delivered(channel: Option<&str>) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::clear_delivered(channel)
+    }
+    
+    #[cfg(target_os = "android")]
+    {
+        android::clear_delivered(channel)
+    }
+    
+    #[cfg(all(feature = "dev-mock", not(any(target_os = "ios", target_os = "android"))))]
+    {
+        let _ = channel;
+        crate::dev_mock::notifications::clear_delivered()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
+    {
+        let _ = channel;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,8 +225,8 @@ mod tests {
     #[test]
     fn test_show_notification_basic() {
         let result = show_notification("Test Title", "Test Body", None);
-        // Should succeed on iOS/Android, fail on other platforms
-        #[cfg(any(target_os = "ios", target_os = "android"))]
+        // Should succeed on iOS/Android (and with the mock), fail on other platforms
+        #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
         {
             assert!(result.is_ok(), "show_notification should succeed on mobile platforms");
         }
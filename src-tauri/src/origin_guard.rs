@@ -0,0 +1,156 @@
+/// Command origin validation module
+///
+/// The app loads a remote frontend, so any page that ends up in the webview
+/// (after a redirect, a link or an injected navigation) could invoke native
+/// commands. This module checks the origin of the invoking webview's current
+/// URL against an allowlist before sensitive commands (`SENSITIVE_COMMANDS`)
+/// are executed; calls from any other origin are rejected and logged.
+///
+/// The allowlist contains the origin of the application URL (`APP_URL`, or
+/// its runtime configuration override).
+
+use tauri::ipc::Invoke;
+use tauri::{Runtime, Url};
+
+use crate::runtime_config;
+use crate::{AppError, AppResult};
+
+/// Commands only accepted from an allowed origin
+pub const SENSITIVE_COMMANDS: &[&str] = &[
+    "keychain_store",
+    "keychain_retrieve",
+    "keychain_remove",
+    "keychain_exists",
+    "signing_handshake",
+    "db_execute",
+    "db_query",
+    "claim_session_token",
+    "auth_login",
+    "auth_logout",
+    "passkey_register",
+    "passkey_authenticate",
+    "export_security_log",
+    "device_binding_assertion",
+    "ephemeral_store",
+    "ephemeral_get",
+    "set_log_shipping",
+    "ws_connect",
+    "ws_send",
+    "db_query_packed",
+    "wipe_all_local_data",
+    "sse_subscribe",
+    "outbox_ready",
+    "outbox_ack",
+    "vault_import",
+    "vault_read",
+    "vault_open",
+    "vault_remove",
+    "export_user_data",
+    "draft_save",
+    "draft_load",
+    "draft_delete",
+    "generate_diagnostics",
+    "upload_enqueue",
+    "upload_retry",
+];
+
+/// Returns `true` if the command must be invoked from an allowed origin
+pub fn is_sensitive(command: &str) -> bool {
+    SENSITIVE_COMMANDS.contains(&command)
+}
+
+/// Origins allowed to invoke sensitive commands
+pub fn allowed_origins() -> Vec<String> {
+    Url::parse(&runtime_config::get().app_url)
+        .map(|url| vec![url.origin().ascii_serialization()])
+        .unwrap_or_default()
+}
+
+/// Check that a command may be invoked from a page
+///
+/// # Arguments
+///
+/// * `command` - Name of the invoked command
+/// * `url` - Current URL of the invoking webview
+/// * `allowed` - Allowed origins (`scheme://host[:port]`)
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command is not sensitive or the page origin is
+/// allowed, or an error otherwise.
+pub fn check(command: &str, url: &Url, allowed: &[String]) -> AppResult<()> {
+    if !is_sensitive(command) {
+        return Ok(());
+    }
+    // Opaque origins (data:, about:blank, ...) serialize as "null"
+    let origin = url.origin().ascii_serialization();
+    if allowed.contains(&origin) {
+        return Ok(());
+    }
+    Err(AppError::Denied(format!("Command {} is not allowed from origin {}", command, origin)))
+}
+
+/// Validate an IPC invocation before it reaches the command handler
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command may run, or an error to reject the
+/// invocation with.
+pub fn check_invoke<R: Runtime>(invoke: &Invoke<R>) -> AppResult<()> {
+    let command = invoke.message.command();
+    if !is_sensitive(command) {
+        return Ok(());
+    }
+
+    let result = invoke
+        .message
+        .webview_ref()
+        .url()
+        .map_err(|e| AppError::Denied(format!("Command {} rejected: unknown origin ({})", command, e)))
+        .and_then(|url| check(command, &url, &allowed_origins()));
+    if let Err(e) = &result {
+        tracing::warn!("{}", e);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed() -> Vec<String> {
+        vec!["https://app.elulib.com".to_string()]
+    }
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn test_sensitive_commands_require_allowed_origin() {
+        assert!(check("keychain_retrieve", &url("https://app.elulib.com/agenda?x=1"), &allowed()).is_ok());
+        assert!(check("keychain_retrieve", &url("https://app.elulib.com:443/"), &allowed()).is_ok());
+
+        for rejected in [
+            "https://evil.example.com/",
+            "http://app.elulib.com/",
+            "https://app.elulib.com.evil.example.com/",
+            "https://app.elulib.com:8443/",
+            "data:text/html,<script></script>",
+            "about:blank",
+        ] {
+            let result = check("keychain_retrieve", &url(rejected), &allowed());
+            assert!(result.is_err(), "{} should be rejected", rejected);
+        }
+    }
+
+    #[test]
+    fn test_other_commands_are_not_checked() {
+        assert!(check("device_info", &url("https://evil.example.com/"), &allowed()).is_ok());
+    }
+
+    #[test]
+    fn test_default_allowlist_is_app_origin() {
+        assert_eq!(allowed_origins(), allowed());
+    }
+}
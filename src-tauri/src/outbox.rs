@@ -0,0 +1,260 @@
+/// Durable event outbox
+///
+/// Events emitted while no page listens (a background sync completing while
+/// the webview is not loaded, a push handled at launch) would be lost. The
+/// events that must reach the frontend are posted with `post` instead of
+/// `events::emit`:
+/// - once the page has signalled it is ready (`outbox_ready`), they are
+///   emitted on their channel as usual
+/// - until then, they are queued in the encrypted database (`outbox` table)
+///
+/// `outbox_ready` marks the page ready and returns the queued events, oldest
+/// first, as `{ id, channel, payload, createdAt }`. The page handles them and
+/// acknowledges them with `outbox_ack`: unacknowledged events are returned
+/// again to the next page. Every page load starts not ready.
+///
+/// At most `OUTBOX_MAX_ENTRIES` events are queued (the oldest dropped first),
+/// for at most `OUTBOX_MAX_AGE_SECS`. The queue is cleared on logout, so the
+/// events of a user never reach the page of the next one.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rusqlite::{params, params_from_iter, Connection};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::database::Database;
+use crate::events::{self, Event};
+use crate::{AppError, AppResult};
+
+/// Event queued while the page was not ready
+#[derive(Debug, Clone, PartialEq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxMessage {
+    /// Identifier, acknowledged with `outbox_ack`
+    pub id: u64,
+    /// Channel the event was emitted on
+    pub channel: String,
+    /// Event payload
+    pub payload: Value,
+    /// When the event was queued (seconds since the Unix epoch)
+    pub created_at: u64,
+}
+
+/// Readiness of the page, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct Outbox {
+    ready: AtomicBool,
+}
+
+impl Outbox {
+    /// Whether the current page has signalled it is ready
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    /// Mark the current page ready, or not ready when a new page loads
+    pub fn set_ready(&self, ready: bool) {
+        self.ready.store(ready, Ordering::SeqCst);
+    }
+}
+
+/// Queue an event
+///
+/// Expired events are purged, and the oldest events are dropped beyond
+/// `OUTBOX_MAX_ENTRIES`.
+pub fn enqueue(conn: &Connection, channel: &str, payload: &Value, now: u64) -> AppResult<u64> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| AppError::Database(format!("Failed to start outbox transaction: {}", e)))?;
+    tx.execute(
+        "INSERT INTO outbox (channel, payload, created_at) VALUES (?, ?, ?)",
+        params![channel, payload.to_string(), now as i64],
+    )
+    .map_err(|e| AppError::Database(format!("Failed to queue event: {}", e)))?;
+    let id = tx.last_insert_rowid();
+    purge(&tx, now)?;
+    tx.commit().map_err(|e| AppError::Database(format!("Failed to queue event: {}", e)))?;
+    Ok(id.max(0) as u64)
+}
+
+/// Queued events, oldest first (expired events excluded)
+pub fn pending(conn: &Connection, now: u64) -> AppResult<Vec<OutboxMessage>> {
+    let mut statement = conn
+        .prepare("SELECT id, channel, payload, created_at FROM outbox WHERE created_at >= ? ORDER BY id")
+        .map_err(|e| AppError::Database(format!("Failed to read outbox: {}", e)))?;
+    let rows = statement
+        .query_map(params![expiry_cutoff(now)], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })
+        .map_err(|e| AppError::Database(format!("Failed to read outbox: {}", e)))?;
+
+    rows.map(|row| {
+        let (id, channel, payload, created_at) = row.map_err(|e| AppError::Database(format!("Failed to read outbox: {}", e)))?;
+        Ok(OutboxMessage {
+            id: id.max(0) as u64,
+            channel,
+            payload: serde_json::from_str(&payload).map_err(|e| AppError::Database(format!("Invalid queued event: {}", e)))?,
+            created_at: created_at.max(0) as u64,
+        })
+    })
+    .collect()
+}
+
+/// Delete acknowledged events
+///
+/// # Returns
+///
+/// Returns the number of deleted events; unknown identifiers are ignored.
+pub fn ack(conn: &Connection, ids: &[u64]) -> AppResult<usize> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let placeholders = vec!["?"; ids.len()].join(", ");
+    conn.execute(
+        &format!("DELETE FROM outbox WHERE id IN ({})", placeholders),
+        params_from_iter(ids.iter().map(|id| *id as i64)),
+    )
+    .map_err(|e| AppError::Database(format!("Failed to acknowledge events: {}", e)))
+}
+
+/// Delete every queued event
+///
+/// # Returns
+///
+/// Returns the number of deleted events.
+pub fn clear(conn: &Connection) -> AppResult<usize> {
+    conn.execute("DELETE FROM outbox", [])
+        .map_err(|e| AppError::Database(format!("Failed to clear outbox: {}", e)))
+}
+
+/// Delete the expired events, and the oldest ones beyond `OUTBOX_MAX_ENTRIES`
+///
+/// # Returns
+///
+/// Returns the number of deleted events.
+pub fn purge(conn: &Connection, now: u64) -> AppResult<usize> {
+    let expired = conn
+        .execute("DELETE FROM outbox WHERE created_at < ?", params![expiry_cutoff(now)])
+        .map_err(|e| AppError::Database(format!("Failed to purge outbox: {}", e)))?;
+    let evicted = conn
+        .execute(
+            "DELETE FROM outbox WHERE id NOT IN (SELECT id FROM outbox ORDER BY id DESC LIMIT ?)",
+            params![constants::OUTBOX_MAX_ENTRIES as i64],
+        )
+        .map_err(|e| AppError::Database(format!("Failed to purge outbox: {}", e)))?;
+    Ok(expired + evicted)
+}
+
+fn expiry_cutoff(now: u64) -> i64 {
+    now.saturating_sub(constants::OUTBOX_MAX_AGE_SECS) as i64
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Emit an event to the page, or queue it until the page is ready
+///
+/// An event that cannot be queued is emitted anyway, and the failure logged.
+pub fn post<E: Event>(app: &AppHandle, event: &E) {
+    if app.state::<Outbox>().is_ready() {
+        events::emit(app, event);
+        return;
+    }
+    let queued = serde_json::to_value(event)
+        .map_err(AppError::from)
+        .and_then(|payload| {
+            app.state::<Database>()
+                .with_connection(app, |conn| enqueue(conn, E::NAME, &payload, now_secs()))
+        });
+    match queued {
+        Ok(id) => tracing::debug!("Queued {} event {} until the page is ready", E::NAME, id),
+        Err(e) => {
+            tracing::warn!("Failed to queue {} event: {}", E::NAME, e);
+            events::emit(app, event);
+        }
+    }
+}
+
+/// Mark the page ready and return the queued events
+pub fn ready(app: &AppHandle) -> AppResult<Vec<OutboxMessage>> {
+    app.state::<Outbox>().set_ready(true);
+    app.state::<Database>()
+        .with_connection(app, |conn| pending(conn, now_secs()))
+}
+
+/// Acknowledge events handled by the page
+pub fn acknowledge(app: &AppHandle, ids: &[u64]) -> AppResult<usize> {
+    app.state::<Database>()
+        .with_connection(app, |conn| ack(conn, ids))
+}
+
+/// Drop the events queued for the user logging out
+pub fn discard(app: &AppHandle) -> AppResult<usize> {
+    app.state::<Database>().with_connection(app, clear)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{migrate, open, MIGRATIONS};
+    use serde_json::json;
+
+    const KEY: &str = "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff";
+
+    fn test_db(dir: &tempfile::TempDir) -> Connection {
+        let mut conn = open(&dir.path().join("test.db"), KEY).unwrap();
+        migrate(&mut conn, MIGRATIONS).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_events_are_kept_until_acknowledged() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = test_db(&dir);
+        let now = 1_700_000_000;
+
+        let first = enqueue(&conn, constants::SYNC_COMPLETED_EVENT, &json!({ "trigger": "background" }), now).unwrap();
+        let second = enqueue(&conn, constants::NOTIFICATION_RECEIVED_EVENT, &json!({ "title": "Hi" }), now + 1).unwrap();
+
+        let messages = pending(&conn, now + 2).unwrap();
+        assert_eq!(messages.iter().map(|message| message.id).collect::<Vec<_>>(), vec![first, second]);
+        assert_eq!(messages[0].channel, constants::SYNC_COMPLETED_EVENT);
+        assert_eq!(messages[1].payload, json!({ "title": "Hi" }));
+
+        assert_eq!(ack(&conn, &[first, 999]).unwrap(), 1);
+        assert_eq!(pending(&conn, now + 2).unwrap().len(), 1, "Unacknowledged events are delivered again");
+        assert_eq!(ack(&conn, &[]).unwrap(), 0);
+
+        assert_eq!(clear(&conn).unwrap(), 1);
+        assert!(pending(&conn, now + 2).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_outbox_expires_and_is_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = test_db(&dir);
+        let now = 1_700_000_000;
+
+        enqueue(&conn, "old", &json!(null), now).unwrap();
+        let later = now + constants::OUTBOX_MAX_AGE_SECS + 1;
+        assert!(pending(&conn, later).unwrap().is_empty());
+
+        for index in 0..=constants::OUTBOX_MAX_ENTRIES {
+            enqueue(&conn, "event", &json!(index), later).unwrap();
+        }
+        let messages = pending(&conn, later).unwrap();
+        assert_eq!(messages.len(), constants::OUTBOX_MAX_ENTRIES);
+        assert_eq!(messages[0].payload, json!(1), "Oldest event dropped");
+    }
+}
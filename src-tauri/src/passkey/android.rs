@@ -0,0 +1,63 @@
+/// Android-specific passkey implementation
+///
+/// This module runs passkey ceremonies with Credential Manager
+/// (`androidx.credentials`), which takes and returns the WebAuthn JSON
+/// serialization directly. The relying party must publish a Digital Asset
+/// Links file (`/.well-known/assetlinks.json`) granting
+/// `delegate_permission/common.get_login_creds` to the app.
+///
+/// Note: This implementation provides the structure for Android passkeys.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::{AuthenticationOptions, AuthenticationResponse, RegistrationOptions, RegistrationResponse};
+use crate::{AppError, AppResult};
+
+/// Create a passkey
+///
+/// # Returns
+///
+/// Returns the new credential, or an error if the user cancelled or
+/// the request failed.
+pub fn register(options: &RegistrationOptions) -> AppResult<RegistrationResponse> {
+    let request_json = serde_json::to_string(options)?;
+    tracing::info!("[Android] Requesting passkey registration for {}", options.rp.id);
+
+    // TODO: Implement native Android registration via JNI
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val request = CreatePublicKeyCredentialRequest(requestJson)
+    // val result = credentialManager.createCredential(activity, request)
+    //     as CreatePublicKeyCredentialResponse
+    // return result.registrationResponseJson
+    // ```
+
+    // Placeholder: Passkeys unavailable
+    // Replace this with actual native implementation
+    let _ = request_json;
+    Err(AppError::Auth("Passkeys not available".to_string()))
+}
+
+/// Sign a server challenge with a passkey
+///
+/// # Returns
+///
+/// Returns the assertion, or an error if the user cancelled or the
+/// request failed.
+pub fn authenticate(options: &AuthenticationOptions) -> AppResult<AuthenticationResponse> {
+    let request_json = serde_json::to_string(options)?;
+    tracing::info!("[Android] Requesting passkey assertion for {}", options.rp_id);
+
+    // TODO: Implement native Android assertion via JNI
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val request = GetCredentialRequest(listOf(GetPublicKeyCredentialOption(requestJson)))
+    // val result = credentialManager.getCredential(activity, request)
+    // return (result.credential as PublicKeyCredential).authenticationResponseJson
+    // ```
+
+    // Placeholder: Passkeys unavailable
+    // Replace this with actual native implementation
+    let _ = request_json;
+    Err(AppError::Auth("Passkeys not available".to_string()))
+}
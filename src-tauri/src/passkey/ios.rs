@@ -0,0 +1,70 @@
+/// iOS-specific passkey implementation
+///
+/// This module runs passkey ceremonies with
+/// `ASAuthorizationPlatformPublicKeyCredentialProvider` from the
+/// AuthenticationServices framework. The relying party must be listed in the
+/// `webcredentials` associated domains of the app entitlements.
+///
+/// Note: This implementation provides the structure for iOS passkeys.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::{AuthenticationOptions, AuthenticationResponse, RegistrationOptions, RegistrationResponse};
+use crate::{AppError, AppResult};
+
+/// Create a passkey
+///
+/// # Returns
+///
+/// Returns the new credential, or an error if the user cancelled or
+/// the request failed.
+pub fn register(options: &RegistrationOptions) -> AppResult<RegistrationResponse> {
+    tracing::info!("[iOS] Requesting passkey registration for {}", options.rp.id);
+
+    // TODO: Implement native iOS registration using ASAuthorizationController
+    // Example Swift implementation:
+    // ```swift
+    // let provider = ASAuthorizationPlatformPublicKeyCredentialProvider(relyingPartyIdentifier: rpId)
+    // let request = provider.createCredentialRegistrationRequest(
+    //     challenge: challenge, name: userName, userID: userId)
+    // request.excludedCredentials = excluded // iOS 17.4+
+    // let controller = ASAuthorizationController(authorizationRequests: [request])
+    // controller.delegate = delegate
+    // controller.performRequests()
+    // // In the delegate, read ASAuthorizationPlatformPublicKeyCredentialRegistration:
+    // // credentialID, rawClientDataJSON, rawAttestationObject
+    // ```
+
+    // Placeholder: Passkeys unavailable
+    // Replace this with actual native implementation
+    Err(AppError::Auth("Passkeys not available".to_string()))
+}
+
+/// Sign a server challenge with a passkey
+///
+/// # Returns
+///
+/// Returns the assertion, or an error if the user cancelled or the
+/// request failed.
+pub fn authenticate(options: &AuthenticationOptions) -> AppResult<AuthenticationResponse> {
+    tracing::info!("[iOS] Requesting passkey assertion for {}", options.rp_id);
+
+    // TODO: Implement native iOS assertion using ASAuthorizationController
+    // Example Swift implementation:
+    // ```swift
+    // let provider = ASAuthorizationPlatformPublicKeyCredentialProvider(relyingPartyIdentifier: rpId)
+    // let request = provider.createCredentialAssertionRequest(challenge: challenge)
+    // request.allowedCredentials = allowed.map {
+    //     ASAuthorizationPlatformPublicKeyCredentialDescriptor(credentialID: $0)
+    // }
+    // let controller = ASAuthorizationController(authorizationRequests: [request])
+    // controller.delegate = delegate
+    // controller.performRequests()
+    // // In the delegate, read ASAuthorizationPlatformPublicKeyCredentialAssertion:
+    // // credentialID, rawClientDataJSON, rawAuthenticatorData, signature, userID
+    // ```
+
+    // Placeholder: Passkeys unavailable
+    // Replace this with actual native implementation
+    Err(AppError::Auth("Passkeys not available".to_string()))
+}
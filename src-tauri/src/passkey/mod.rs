@@ -0,0 +1,399 @@
+/// Passkey (WebAuthn platform authenticator) bridge
+///
+/// The embedded webview's WebAuthn support is unreliable (missing on older
+/// Android System WebView versions, restricted in `WKWebView`), so the
+/// backend's passkey ceremonies are run natively instead:
+/// - iOS: `ASAuthorizationPlatformPublicKeyCredentialProvider`
+/// - Android: Credential Manager (`CreatePublicKeyCredentialRequest`,
+///   `GetPublicKeyCredentialOption`)
+///
+/// Options and responses use the WebAuthn JSON serialization
+/// (`PublicKeyCredentialCreationOptionsJSON`, `RegistrationResponseJSON`,
+/// ...), so the frontend can pass the backend's options through and send the
+/// response back as-is for verification.
+///
+/// The relying party must be the web application host or one of its parent
+/// domains: the app is associated with it (`webcredentials` associated domain
+/// on iOS, Digital Asset Links on Android), and a compromised frontend cannot
+/// request assertions for another site.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use base64::Engine;
+use tauri::Url;
+
+use crate::constants;
+use crate::runtime_config;
+use crate::{AppError, AppResult};
+
+/// Relying party of a registration
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RelyingParty {
+    /// Relying party identifier (a domain)
+    pub id: String,
+    /// Display name
+    pub name: String,
+}
+
+/// User account of a registration
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyUser {
+    /// Opaque user handle (base64url)
+    pub id: String,
+    /// Account name (e.g. an email)
+    pub name: String,
+    /// Name displayed by the system sheet
+    pub display_name: String,
+}
+
+/// Requested credential algorithm
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialParameter {
+    /// COSE algorithm identifier (`-7` for ES256)
+    pub alg: i64,
+    /// Always `public-key`
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// Existing credential, to exclude or allow
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialDescriptor {
+    /// Credential identifier (base64url)
+    pub id: String,
+    /// Always `public-key`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Transport hints (`internal`, `hybrid`, ...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transports: Vec<String>,
+}
+
+/// Authenticator requirements of a registration
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorSelection {
+    /// `required`, `preferred` or `discouraged`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resident_key: Option<String>,
+    /// `required`, `preferred` or `discouraged`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_verification: Option<String>,
+}
+
+/// Options of a passkey registration, as returned by the backend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistrationOptions {
+    /// Server challenge (base64url)
+    pub challenge: String,
+    /// Relying party
+    pub rp: RelyingParty,
+    /// User account
+    pub user: PasskeyUser,
+    /// Acceptable algorithms, by preference
+    pub pub_key_cred_params: Vec<CredentialParameter>,
+    /// Timeout hint, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// Credentials already registered for the user
+    #[serde(default)]
+    pub exclude_credentials: Vec<CredentialDescriptor>,
+    /// Authenticator requirements
+    #[serde(default)]
+    pub authenticator_selection: AuthenticatorSelection,
+    /// Attestation conveyance (`none`, `direct`, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation: Option<String>,
+}
+
+/// Options of a passkey authentication, as returned by the backend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticationOptions {
+    /// Server challenge (base64url)
+    pub challenge: String,
+    /// Relying party identifier
+    pub rp_id: String,
+    /// Credentials accepted (any discoverable credential when empty)
+    #[serde(default)]
+    pub allow_credentials: Vec<CredentialDescriptor>,
+    /// Timeout hint, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    /// `required`, `preferred` or `discouraged`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_verification: Option<String>,
+}
+
+/// Authenticator response of a registration
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationResponse {
+    /// Client data (base64url)
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    /// Attestation object (base64url)
+    pub attestation_object: String,
+    /// Transports of the new credential
+    #[serde(default)]
+    pub transports: Vec<String>,
+}
+
+/// New passkey, to send to the backend for verification
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistrationResponse {
+    /// Credential identifier (base64url)
+    pub id: String,
+    /// Credential identifier (base64url)
+    pub raw_id: String,
+    /// Always `public-key`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Authenticator response
+    pub response: AttestationResponse,
+    /// `platform` or `cross-platform`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authenticator_attachment: Option<String>,
+}
+
+/// Authenticator response of an authentication
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionResponse {
+    /// Client data (base64url)
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    /// Authenticator data (base64url)
+    pub authenticator_data: String,
+    /// Assertion signature (base64url)
+    pub signature: String,
+    /// User handle of the credential (base64url)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_handle: Option<String>,
+}
+
+/// Passkey assertion, to send to the backend for verification
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticationResponse {
+    /// Credential identifier (base64url)
+    pub id: String,
+    /// Credential identifier (base64url)
+    pub raw_id: String,
+    /// Always `public-key`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Authenticator response
+    pub response: AssertionResponse,
+    /// `platform` or `cross-platform`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authenticator_attachment: Option<String>,
+}
+
+/// Decode a base64url value (padding tolerated)
+fn decode(field: &str, value: &str) -> AppResult<Vec<u8>> {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|e| AppError::Auth(format!("{} is not valid base64url: {}", field, e)))
+}
+
+/// Check a server challenge
+fn check_challenge(challenge: &str) -> AppResult<()> {
+    if decode("challenge", challenge)?.len() < constants::PASSKEY_MIN_CHALLENGE_BYTES {
+        return Err(AppError::InvalidArgument(format!(
+            "challenge must be at least {} bytes",
+            constants::PASSKEY_MIN_CHALLENGE_BYTES
+        )));
+    }
+    Ok(())
+}
+
+/// Check that a relying party identifier matches the web application
+///
+/// # Arguments
+///
+/// * `rp_id` - Relying party identifier
+/// * `app_url` - Web application URL
+///
+/// # Returns
+///
+/// Returns `Ok(())` if `rp_id` is the application host or one of its parent
+/// domains, or an error otherwise.
+pub fn check_rp_id(rp_id: &str, app_url: &str) -> AppResult<()> {
+    let url = Url::parse(app_url).map_err(|e| AppError::Config(format!("Invalid application URL {}: {}", app_url, e)))?;
+    let host = url.host_str().ok_or_else(|| AppError::Auth("Application URL has no host".to_string()))?;
+    let rp_id = rp_id.to_ascii_lowercase();
+    // A parent domain must still be a registrable domain, not a public suffix
+    let is_parent = rp_id.contains('.') && host.ends_with(&format!(".{}", rp_id));
+    if rp_id == host || is_parent {
+        return Ok(());
+    }
+    Err(AppError::Auth(format!("Relying party {} does not match the application host {}", rp_id, host)))
+}
+
+impl RegistrationOptions {
+    /// Validate the options against the web application
+    pub fn validate(&self, app_url: &str) -> AppResult<()> {
+        check_challenge(&self.challenge)?;
+        check_rp_id(&self.rp.id, app_url)?;
+        let user_id = decode("user.id", &self.user.id)?;
+        if user_id.is_empty() || user_id.len() > constants::PASSKEY_MAX_USER_ID_BYTES {
+            return Err(AppError::InvalidArgument(format!(
+                "user.id must be between 1 and {} bytes",
+                constants::PASSKEY_MAX_USER_ID_BYTES
+            )));
+        }
+        if !self
+            .pub_key_cred_params
+            .iter()
+            .any(|param| param.alg == constants::PASSKEY_ALG_ES256)
+        {
+            return Err(AppError::InvalidArgument("pubKeyCredParams must include ES256 (-7), the only platform algorithm".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl AuthenticationOptions {
+    /// Validate the options against the web application
+    pub fn validate(&self, app_url: &str) -> AppResult<()> {
+        check_challenge(&self.challenge)?;
+        check_rp_id(&self.rp_id, app_url)
+    }
+}
+
+/// Create a passkey with the platform authenticator
+///
+/// # Arguments
+///
+/// * `options` - Registration options from the backend
+///
+/// # Returns
+///
+/// Returns the new credential, or an error if the options are
+/// rejected, the user cancelled or passkeys are unavailable.
+pub fn register(options: &RegistrationOptions) -> AppResult<RegistrationResponse> {
+    options.validate(&runtime_config::get().app_url)?;
+    tracing::info!("Requesting passkey registration for {}", options.rp.id);
+
+    #[cfg(target_os = "ios")]
+    {
+        ios::register(options)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::register(options)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Err(AppError::Auth("Passkeys not supported on this platform".to_string()))
+    }
+}
+
+/// Sign a server challenge with a passkey
+///
+/// # Arguments
+///
+/// * `options` - Authentication options from the backend
+///
+/// # Returns
+///
+/// Returns the assertion, or an error if the options are rejected,
+/// the user cancelled or no passkey is available.
+pub fn authenticate(options: &AuthenticationOptions) -> AppResult<AuthenticationResponse> {
+    options.validate(&runtime_config::get().app_url)?;
+    tracing::info!("Requesting passkey authentication for {}", options.rp_id);
+
+    #[cfg(target_os = "ios")]
+    {
+        ios::authenticate(options)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::authenticate(options)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Err(AppError::Auth("Passkeys not supported on this platform".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const APP_URL: &str = "https://app.elulib.com";
+
+    fn registration() -> RegistrationOptions {
+        serde_json::from_value(json!({
+            "challenge": "q83vEjRWeJCrze8SNFZ4kA",
+            "rp": { "id": "elulib.com", "name": "elulib" },
+            "user": { "id": "dXNlci0x", "name": "agent@mairie.fr", "displayName": "Agent" },
+            "pubKeyCredParams": [{ "alg": -8, "type": "public-key" }, { "alg": -7, "type": "public-key" }],
+            "authenticatorSelection": { "residentKey": "required" },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_rp_id() {
+        assert!(check_rp_id("app.elulib.com", APP_URL).is_ok());
+        assert!(check_rp_id("elulib.com", APP_URL).is_ok());
+        assert!(check_rp_id("com", APP_URL).is_err());
+        assert!(check_rp_id("lib.com", APP_URL).is_err());
+        assert!(check_rp_id("evil.example", APP_URL).is_err());
+    }
+
+    #[test]
+    fn test_registration_options() {
+        let options = registration();
+        assert_eq!(options.validate(APP_URL).unwrap(), ());
+
+        let mut short_challenge = registration();
+        short_challenge.challenge = "AAAA".to_string();
+        assert!(short_challenge.validate(APP_URL).is_err());
+
+        let mut no_es256 = registration();
+        no_es256.pub_key_cred_params.retain(|param| param.alg != constants::PASSKEY_ALG_ES256);
+        assert!(no_es256.validate(APP_URL).is_err());
+    }
+
+    #[test]
+    fn test_webauthn_json_names() {
+        let value = serde_json::to_value(registration()).unwrap();
+        assert_eq!(value["pubKeyCredParams"][1]["type"], "public-key");
+        assert_eq!(value["user"]["displayName"], "Agent");
+
+        let response = AuthenticationResponse {
+            id: "Y3JlZA".to_string(),
+            raw_id: "Y3JlZA".to_string(),
+            kind: "public-key".to_string(),
+            response: AssertionResponse {
+                client_data_json: "e30".to_string(),
+                authenticator_data: "AA".to_string(),
+                signature: "AA".to_string(),
+                user_handle: None,
+            },
+            authenticator_attachment: Some("platform".to_string()),
+        };
+        let value = serde_json::to_value(response).unwrap();
+        assert_eq!(value["rawId"], "Y3JlZA");
+        assert_eq!(value["response"]["clientDataJSON"], "e30");
+        assert!(value["response"].get("userHandle").is_none());
+    }
+}
@@ -0,0 +1,158 @@
+/// Binary payload encoding
+///
+/// On low-end devices, serializing and parsing JSON is a noticeable share of
+/// the cost of large payloads (document lists, sync batches). They can travel
+/// as MessagePack instead:
+/// - over the invoke bridge, the `*_packed` variants of the commands resolve
+///   with a raw MessagePack body (`Packed`) instead of a JSON string; the
+///   TypeScript bindings decode it (`@msgpack/msgpack`) and type the result
+///   like the JSON variant
+/// - from the backend, sync requests accept MessagePack (`MSGPACK_ACCEPT`)
+///   and responses are decoded according to their content type
+///   (`read_response`), so the backend can switch format per endpoint
+///
+/// Structs are encoded as maps keyed by their serde field names, so a
+/// MessagePack payload has the exact shape of its JSON counterpart: the types
+/// of the TypeScript bindings are the schema shared with the backend for both
+/// formats, and a payload can be converted from one to the other losslessly
+/// (except blobs, which JSON carries base64-encoded).
+
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use specta::datatype::reference::Reference;
+use specta::datatype::DataType;
+use specta::{Generics, Type, TypeCollection};
+use tauri::ipc::{InvokeResponseBody, IpcResponse};
+
+use crate::constants;
+use crate::{AppError, AppResult};
+
+/// Encode a value as MessagePack, structs as maps keyed by field name
+pub fn encode<T: Serialize + ?Sized>(value: &T) -> AppResult<Vec<u8>> {
+    rmp_serde::to_vec_named(value).map_err(|e| AppError::Platform(format!("Failed to encode MessagePack payload: {}", e)))
+}
+
+/// Decode a MessagePack payload
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> AppResult<T> {
+    rmp_serde::from_slice(bytes).map_err(|e| AppError::InvalidArgument(format!("Invalid MessagePack payload: {}", e)))
+}
+
+/// Whether a `Content-Type` header value designates MessagePack
+pub fn is_msgpack(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    mime.eq_ignore_ascii_case(constants::MSGPACK_CONTENT_TYPE) || mime.eq_ignore_ascii_case("application/x-msgpack")
+}
+
+/// Decode a response body, as MessagePack or JSON according to its content type
+pub fn decode_body<T: DeserializeOwned>(content_type: Option<&str>, body: &[u8]) -> AppResult<T> {
+    if content_type.is_some_and(is_msgpack) {
+        decode(body)
+    } else {
+        serde_json::from_slice(body).map_err(|e| AppError::InvalidArgument(format!("Invalid JSON payload: {}", e)))
+    }
+}
+
+/// Read and decode a backend response, MessagePack or JSON
+///
+/// Requests expecting a large response send `MSGPACK_ACCEPT` as `Accept`
+/// header; backends that do not support MessagePack keep answering JSON.
+pub async fn read_response<T: DeserializeOwned>(response: reqwest::Response) -> AppResult<T> {
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| AppError::Network(format!("Failed to read response: {}", e)))?;
+    decode_body(content_type.as_deref(), &body)
+}
+
+/// Command result sent to the webview as a raw MessagePack body
+///
+/// Typed as `T` in the TypeScript bindings, which decode the body. Returned
+/// by the commands named with `PACKED_COMMAND_SUFFIX` only, since the
+/// bindings rely on the name to decode the result.
+pub struct Packed<T> {
+    body: Vec<u8>,
+    payload: PhantomData<fn() -> T>,
+}
+
+impl<T: Serialize> Packed<T> {
+    /// Encode a command result
+    pub fn new(value: &T) -> AppResult<Self> {
+        Ok(Packed {
+            body: encode(value)?,
+            payload: PhantomData,
+        })
+    }
+}
+
+impl<T> Packed<T> {
+    /// Encoded body
+    pub fn bytes(&self) -> &[u8] {
+        &self.body
+    }
+}
+
+impl<T> std::fmt::Debug for Packed<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Packed({} bytes)", self.body.len())
+    }
+}
+
+impl<T> IpcResponse for Packed<T> {
+    fn body(self) -> tauri::Result<InvokeResponseBody> {
+        Ok(InvokeResponseBody::Raw(self.body))
+    }
+}
+
+impl<T: Type> Type for Packed<T> {
+    fn inline(types: &mut TypeCollection, generics: Generics) -> DataType {
+        T::inline(types, generics)
+    }
+
+    fn reference(types: &mut TypeCollection, generics: &[DataType]) -> Reference {
+        T::reference(types, generics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_payloads_keep_their_json_shape() {
+        let rows = json!([
+            { "id": "doc-1", "title": "Procès-verbal du conseil", "updatedAt": 120, "archived": false },
+            { "id": "doc-2", "title": null, "size": 1.5, "tags": ["budget", "voirie"] },
+        ]);
+        let packed = Packed::new(&rows).unwrap();
+        assert!(packed.bytes().len() < serde_json::to_vec(&rows).unwrap().len());
+        assert_eq!(decode::<serde_json::Value>(packed.bytes()).unwrap(), rows);
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Page {
+            has_more: bool,
+        }
+        assert_eq!(decode::<serde_json::Value>(&encode(&Page { has_more: true }).unwrap()).unwrap(), json!({ "hasMore": true }));
+    }
+
+    #[test]
+    fn test_bodies_are_decoded_by_content_type() {
+        let value = json!({ "items": [], "cursor": "c-2" });
+        let packed = encode(&value).unwrap();
+        assert_eq!(decode_body::<serde_json::Value>(Some("application/msgpack"), &packed).unwrap(), value.clone());
+        assert_eq!(decode_body::<serde_json::Value>(Some("Application/X-MsgPack; charset=binary"), &packed).unwrap(), value.clone());
+
+        let text = serde_json::to_vec(&value).unwrap();
+        assert_eq!(decode_body::<serde_json::Value>(Some("application/json"), &text).unwrap(), value.clone());
+        assert_eq!(decode_body::<serde_json::Value>(None, &text).unwrap(), value);
+        assert!(decode_body::<serde_json::Value>(Some("application/msgpack"), &packed[..packed.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+/// Android-specific permission implementation
+///
+/// This module maps Android runtime permission results to the unified
+/// `PermissionStatus`.
+///
+/// Note: This implementation provides the structure for Android permissions.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::{Permission, PermissionStatus};
+use crate::AppResult;
+
+/// Android manifest permission for a managed permission
+fn manifest_permission(permission: Permission) -> &'static str {
+    match permission {
+        Permission::Notifications => "android.permission.POST_NOTIFICATIONS",
+        Permission::Camera => "android.permission.CAMERA",
+        Permission::Location => "android.permission.ACCESS_FINE_LOCATION",
+        Permission::Microphone => "android.permission.RECORD_AUDIO",
+        Permission::Contacts => "android.permission.READ_CONTACTS",
+        Permission::PhoneState => "android.permission.READ_PHONE_STATE",
+    }
+}
+
+/// Check the status of a permission on Android
+///
+/// # Returns
+///
+/// Returns the current `PermissionStatus` for the permission.
+pub fn check(permission: Permission) -> AppResult<PermissionStatus> {
+    tracing::debug!("[Android] Checking permission: {}", manifest_permission(permission));
+
+    // TODO: Implement native Android permission check
+    // Example Kotlin implementation:
+    // ```kotlin
+    // when {
+    //     ContextCompat.checkSelfPermission(context, permission) == PackageManager.PERMISSION_GRANTED -> "granted"
+    //     wasRequested && !activity.shouldShowRequestPermissionRationale(permission) -> "permanently_denied"
+    //     devicePolicyManager.getPermissionGrantState(...) == PERMISSION_GRANT_STATE_DENIED -> "restricted"
+    //     else -> "denied"
+    // }
+    // ```
+
+    // Placeholder: Return denied (permission not yet requested)
+    // Replace this with actual native implementation
+    Ok(PermissionStatus::Denied)
+}
+
+/// Request a permission on Android
+///
+/// # Returns
+///
+/// Returns the resulting `PermissionStatus` for the permission.
+pub fn request(permission: Permission) -> AppResult<PermissionStatus> {
+    tracing::info!("[Android] Requesting permission: {}", manifest_permission(permission));
+
+    // TODO: Implement native Android permission request
+    // Example Kotlin implementation:
+    // ```kotlin
+    // ActivityCompat.requestPermissions(activity, arrayOf(permission), REQUEST_CODE)
+    // ```
+
+    // Placeholder: Return the current status
+    // Replace this with actual native implementation
+    check(permission)
+}
@@ -0,0 +1,68 @@
+/// iOS-specific permission implementation
+///
+/// This module maps iOS authorization statuses (AVFoundation, CoreLocation,
+/// Contacts) to the unified `PermissionStatus`.
+///
+/// Note: This implementation provides the structure for iOS permissions.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::{Permission, PermissionStatus};
+use crate::AppResult;
+
+/// Check the authorization status of a permission on iOS
+///
+/// # Returns
+///
+/// Returns the current `PermissionStatus` for the permission.
+pub fn check(permission: Permission) -> AppResult<PermissionStatus> {
+    tracing::debug!("[iOS] Checking permission: {:?}", permission);
+
+    // Carrier details do not require a runtime permission on iOS
+    if permission == Permission::PhoneState {
+        return Ok(PermissionStatus::Granted);
+    }
+
+    // TODO: Implement native iOS authorization status lookup
+    // Example Swift implementation:
+    // ```swift
+    // switch AVCaptureDevice.authorizationStatus(for: .video) {
+    // case .authorized: return "granted"
+    // case .notDetermined: return "denied"
+    // case .denied: return "permanently_denied"
+    // case .restricted: return "restricted"
+    // }
+    // // Location: CLLocationManager().authorizationStatus
+    // // Microphone: AVAudioSession.sharedInstance().recordPermission
+    // // Contacts: CNContactStore.authorizationStatus(for: .contacts)
+    // ```
+
+    // Placeholder: Return denied (permission not yet requested)
+    // Replace this with actual native implementation
+    Ok(PermissionStatus::Denied)
+}
+
+/// Request a permission on iOS
+///
+/// iOS only shows the system prompt once; subsequent denials are reported
+/// as `PermissionStatus::PermanentlyDenied`.
+///
+/// # Returns
+///
+/// Returns the resulting `PermissionStatus` for the permission.
+pub fn request(permission: Permission) -> AppResult<PermissionStatus> {
+    tracing::info!("[iOS] Requesting permission: {:?}", permission);
+
+    // TODO: Implement native iOS permission request
+    // Example Swift implementation:
+    // ```swift
+    // AVCaptureDevice.requestAccess(for: .video) { granted in ... }
+    // AVAudioSession.sharedInstance().requestRecordPermission { granted in ... }
+    // locationManager.requestWhenInUseAuthorization()
+    // CNContactStore().requestAccess(for: .contacts) { granted, error in ... }
+    // ```
+
+    // Placeholder: Return the current status
+    // Replace this with actual native implementation
+    check(permission)
+}
@@ -0,0 +1,196 @@
+/// Unified permission manager
+///
+/// This module provides a single entry point for checking and requesting
+/// runtime permissions on iOS and Android, so each feature does not need to
+/// reimplement its own permission plumbing.
+///
+/// All permissions report a consistent `PermissionStatus`, regardless of the
+/// underlying platform representation (iOS authorization status, Android
+/// runtime permission result).
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use crate::notifications;
+use crate::AppResult;
+
+/// Runtime permissions managed by the application
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// Permission to display notifications
+    Notifications,
+    /// Permission to access the camera
+    Camera,
+    /// Permission to access the device location
+    Location,
+    /// Permission to record audio with the microphone
+    Microphone,
+    /// Permission to read the user's contacts
+    Contacts,
+    /// Permission to read the phone state (SIM and carrier details)
+    PhoneState,
+}
+
+impl Permission {
+    /// All permissions managed by the application
+    pub const ALL: [Permission; 6] = [
+        Permission::Notifications,
+        Permission::Camera,
+        Permission::Location,
+        Permission::Microphone,
+        Permission::Contacts,
+        Permission::PhoneState,
+    ];
+}
+
+/// Status of a runtime permission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionStatus {
+    /// The permission has been granted by the user
+    Granted,
+    /// The permission has not been granted, but can still be requested
+    Denied,
+    /// The permission was denied and the system will no longer prompt the user
+    ///
+    /// The user must enable it manually from the system settings.
+    PermanentlyDenied,
+    /// The permission cannot be granted (parental controls, MDM policy, etc.)
+    Restricted,
+}
+
+impl PermissionStatus {
+    /// Returns `true` if the permission is granted
+    pub fn is_granted(self) -> bool {
+        self == PermissionStatus::Granted
+    }
+}
+
+impl From<bool> for PermissionStatus {
+    fn from(granted: bool) -> Self {
+        if granted {
+            PermissionStatus::Granted
+        } else {
+            PermissionStatus::Denied
+        }
+    }
+}
+
+/// Status of a single permission, as reported by `status_all()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct PermissionEntry {
+    /// The permission
+    pub permission: Permission,
+    /// The current status of the permission
+    pub status: PermissionStatus,
+}
+
+/// Check the status of a permission without prompting the user
+///
+/// # Arguments
+///
+/// * `permission` - The permission to check
+///
+/// # Returns
+///
+/// Returns the current `PermissionStatus`, or an error if the status
+/// could not be determined.
+pub fn check(permission: Permission) -> AppResult<PermissionStatus> {
+    tracing::debug!("Checking permission: {:?}", permission);
+
+    if permission == Permission::Notifications {
+        return notifications::check_permission().map(PermissionStatus::from);
+    }
+
+    #[cfg(target_os = "ios")]
+    {
+        ios::check(permission)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::check(permission)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(PermissionStatus::Denied)
+    }
+}
+
+/// Request a permission from the user
+///
+/// If the permission is already granted, permanently denied or restricted,
+/// the system prompt is not shown and the current status is returned.
+///
+/// # Arguments
+///
+/// * `permission` - The permission to request
+///
+/// # Returns
+///
+/// Returns the resulting `PermissionStatus`, or an error if the
+/// request could not be performed.
+pub fn request(permission: Permission) -> AppResult<PermissionStatus> {
+    tracing::info!("Requesting permission: {:?}", permission);
+
+    if permission == Permission::Notifications {
+        return notifications::request_permission().map(PermissionStatus::from);
+    }
+
+    #[cfg(target_os = "ios")]
+    {
+        ios::request(permission)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::request(permission)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(PermissionStatus::Denied)
+    }
+}
+
+/// Check the status of every managed permission
+///
+/// # Returns
+///
+/// Returns one `PermissionEntry` per permission in `Permission::ALL`, or an
+/// error if any status could not be determined.
+pub fn status_all() -> AppResult<Vec<PermissionEntry>> {
+    Permission::ALL
+        .iter()
+        .map(|&permission| {
+            check(permission).map(|status| PermissionEntry { permission, status })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_all_covers_every_permission() {
+        let entries = status_all().expect("status_all should return Ok");
+        assert_eq!(entries.len(), Permission::ALL.len());
+        for (entry, permission) in entries.iter().zip(Permission::ALL.iter()) {
+            assert_eq!(entry.permission, *permission);
+        }
+    }
+
+    #[test]
+    fn test_permission_status_from_bool() {
+        assert_eq!(PermissionStatus::from(true), PermissionStatus::Granted);
+        assert_eq!(PermissionStatus::from(false), PermissionStatus::Denied);
+        assert!(PermissionStatus::Granted.is_granted());
+        assert!(!PermissionStatus::PermanentlyDenied.is_granted());
+    }
+}
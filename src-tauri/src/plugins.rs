@@ -0,0 +1,121 @@
+/// Lazy plugin initialization module
+///
+/// Heavy plugins are not registered on the builder, which runs synchronously
+/// before the first frame. They are registered on the running app instead:
+/// - on first use, by the commands needing them (`ensure_*`)
+/// - in background once the first page is loaded (`prewarm`), so the first
+///   use usually does not pay the initialization cost either
+///
+/// Registration happens at most once per plugin; a failed registration is
+/// reported to every caller.
+///
+/// The keychain is accessed through `KeystoreExt`, which resolves to the
+/// in-process mock keystore with the `dev-mock` feature (see `dev_mock`). Its
+/// commands form the `keychain` native module (`KeychainModule`).
+
+use std::sync::OnceLock;
+
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_keystore::RetrieveRequest;
+
+#[cfg(feature = "module-keychain")]
+use crate::native_module::NativeModule;
+use crate::{AppError, AppResult};
+
+#[cfg(not(feature = "dev-mock"))]
+pub use tauri_plugin_keystore::KeystoreExt;
+
+#[cfg(feature = "dev-mock")]
+pub use crate::dev_mock::KeystoreExt;
+
+// The mock keystore keeps secrets in memory, unprotected
+#[cfg(all(feature = "dev-mock", any(target_os = "ios", target_os = "android", not(debug_assertions))))]
+compile_error!("The dev-mock feature is for desktop debug builds only");
+
+/// Registration state of the lazily initialized plugins, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct LazyPlugins {
+    keystore: OnceLock<Result<(), String>>,
+}
+
+/// Register the keystore plugin, if not done yet
+///
+/// Must be called before any use of `app.keystore()`.
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the plugin is registered, or a keychain error if the
+/// registration failed.
+pub fn ensure_keystore<R: Runtime>(app: &AppHandle<R>) -> AppResult<()> {
+    app.state::<LazyPlugins>()
+        .keystore
+        .get_or_init(|| {
+            #[cfg(feature = "dev-mock")]
+            {
+                tracing::debug!("Using mock keystore");
+                let _ = app;
+                Ok(())
+            }
+
+            #[cfg(not(feature = "dev-mock"))]
+            {
+                tracing::debug!("Initializing keystore plugin");
+                app.plugin(tauri_plugin_keystore::init())
+                    .map_err(|e| format!("Failed to initialize keystore: {}", e))
+            }
+        })
+        .clone()
+        .map_err(AppError::Keychain)
+}
+
+/// Returns `true` if the keychain holds a value for the key
+///
+/// The keystore has no existence query, so the value is retrieved and dropped
+/// right away, never returned. Shared by `keychain_exists` and the
+/// diagnostics report.
+///
+/// # Returns
+///
+/// Returns whether the key exists, or a keychain error if the keystore cannot
+/// be registered.
+pub fn keychain_contains<R: Runtime>(app: &AppHandle<R>, key: &str) -> AppResult<bool> {
+    ensure_keystore(app)?;
+    Ok(app
+        .keystore()
+        .retrieve(RetrieveRequest {
+            service: key.to_string(),
+            user: key.to_string(),
+        })
+        .is_ok())
+}
+
+/// Initialize the lazy plugins in background
+///
+/// Called once the first page is loaded, so plugin initialization does not
+/// compete with the cold start.
+pub fn prewarm(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = ensure_keystore(&app) {
+            tracing::error!("{}", e);
+        }
+    });
+}
+
+/// Keychain native module (`module-keychain`)
+///
+/// The keystore plugin is registered lazily, so the module has nothing to
+/// start.
+#[cfg(feature = "module-keychain")]
+pub struct KeychainModule;
+
+#[cfg(feature = "module-keychain")]
+impl NativeModule for KeychainModule {
+    fn name(&self) -> &'static str {
+        "keychain"
+    }
+
+    fn commands(&self) -> &'static [&'static str] {
+        &["keychain_store", "keychain_retrieve", "keychain_remove", "keychain_exists"]
+    }
+}
@@ -0,0 +1,30 @@
+/// Android-specific power state
+///
+/// This module reads the charging state with `BatteryManager` and battery
+/// saver with `PowerManager`.
+///
+/// Note: This implementation provides the structure for the Android power state.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::PowerState;
+use crate::AppResult;
+
+/// Get the power state on Android
+///
+/// # Returns
+///
+/// Returns the `PowerState` of the device.
+pub fn power_state() -> AppResult<PowerState> {
+    // TODO: Implement native Android power state lookup
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val battery = context.getSystemService(Context.BATTERY_SERVICE) as BatteryManager
+    // val charging = battery.isCharging
+    // val lowPowerMode = (context.getSystemService(Context.POWER_SERVICE) as PowerManager).isPowerSaveMode
+    // ```
+
+    // Placeholder: Report a device on battery, so nothing is prefetched
+    // Replace this with actual native implementation
+    Ok(PowerState::default())
+}
@@ -0,0 +1,30 @@
+/// iOS-specific power state
+///
+/// This module reads the battery state with `UIDevice` and the low power mode
+/// with `ProcessInfo`.
+///
+/// Note: This implementation provides the structure for the iOS power state.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::PowerState;
+use crate::AppResult;
+
+/// Get the power state on iOS
+///
+/// # Returns
+///
+/// Returns the `PowerState` of the device.
+pub fn power_state() -> AppResult<PowerState> {
+    // TODO: Implement native iOS power state lookup
+    // Example Swift implementation:
+    // ```swift
+    // UIDevice.current.isBatteryMonitoringEnabled = true
+    // let charging = [.charging, .full].contains(UIDevice.current.batteryState)
+    // let lowPowerMode = ProcessInfo.processInfo.isLowPowerModeEnabled
+    // ```
+
+    // Placeholder: Report a device on battery, so nothing is prefetched
+    // Replace this with actual native implementation
+    Ok(PowerState::default())
+}
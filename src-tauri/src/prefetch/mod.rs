@@ -0,0 +1,595 @@
+/// Connectivity-aware content prefetching
+///
+/// The frontend gives the documents of the next council session with the
+/// `prefetch_set` command; they are downloaded ahead of time, so the session
+/// can be followed offline, but only when it costs the user nothing:
+/// - the device is on an unmetered Wi-Fi (or wired) network, charging, and
+///   not in low power mode (`conditions_allow`)
+/// - documents go through the download manager (`downloads` module), so they
+///   share its concurrency limit, resume support and checksum verification;
+///   prefetch downloads are paused when the conditions stop being met and
+///   resumed when they are met again
+/// - a document is only started if its announced size fits in the storage
+///   quota of its directory; documents already in the file storage are not
+///   downloaded again, and documents dropped from the list have their
+///   unfinished download cancelled
+/// - completed documents are moved into the encrypted vault (`vault`), and
+///   read from there (`PrefetchEntry::vault_id`); a document purged from the
+///   vault is downloaded again while it is in the list
+/// - failed downloads are retried with an exponential backoff from
+///   `PREFETCH_CHECK_INTERVAL_SECS` up to `PREFETCH_RETRY_MAX_SECS`, and
+///   skipped after `PREFETCH_MAX_RETRIES` retries
+///
+/// The conditions are checked every `PREFETCH_CHECK_INTERVAL_SECS` and when
+/// the list changes. The list is persisted (`PREFETCH_FILE`) and prefetching
+/// can be disabled remotely with the `content_prefetch` feature flag.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::downloads::{self, Download, DownloadManager, DownloadStatus};
+use crate::feature_flags;
+use crate::file_storage;
+use crate::network_info::{self, NativeNetworkState, NetworkTransport};
+use crate::vault::{self, Vault};
+use crate::{AppError, AppResult};
+
+/// Power state reported by the platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerState {
+    /// The device is plugged in (charging or full)
+    pub charging: bool,
+    /// Low power mode (iOS) or battery saver (Android) is on
+    pub low_power_mode: bool,
+}
+
+/// Document to prefetch, as given by the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefetchItem {
+    /// `https` URL of the document
+    pub url: String,
+    /// Destination, relative to the app file storage
+    pub path: String,
+    /// Size of the document (bytes), checked against the storage quota
+    pub size: Option<u64>,
+    /// Expected SHA-256 of the document (hex), if known
+    pub sha256: Option<String>,
+}
+
+/// Prefetch status of a document
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefetchEntry {
+    /// Document to prefetch
+    pub item: PrefetchItem,
+    /// Download of the document, once started
+    pub download: Option<Download>,
+    /// Vault identifier of the document, once downloaded (`vault_open`)
+    pub vault_id: Option<String>,
+    /// Why the document is not downloaded (quota, invalid URL), if skipped
+    pub skipped: Option<String>,
+}
+
+/// Prefetch status, returned to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefetchStatus {
+    /// Whether the network and power conditions currently allow prefetching
+    pub conditions_met: bool,
+    /// Status of each document
+    pub entries: Vec<PrefetchEntry>,
+}
+
+/// Retries of a failed download
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct Retry {
+    /// Retries made so far
+    count: u32,
+    /// Time before which the download is not retried (seconds since the Unix
+    /// epoch)
+    next_at: u64,
+}
+
+/// Persisted prefetch list
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct PrefetchList {
+    items: Vec<PrefetchItem>,
+    /// Download started for each path
+    downloads: BTreeMap<String, String>,
+    /// Vault document of each downloaded path
+    #[serde(default)]
+    vaulted: BTreeMap<String, String>,
+    /// Retries of each failed download
+    #[serde(default)]
+    retries: BTreeMap<String, Retry>,
+}
+
+/// Delay before retrying a failed download, after `count` retries (seconds)
+fn retry_delay_secs(count: u32) -> u64 {
+    constants::PREFETCH_CHECK_INTERVAL_SECS
+        .saturating_mul(2u64.saturating_pow(count.saturating_sub(1)))
+        .min(constants::PREFETCH_RETRY_MAX_SECS)
+}
+
+/// Prefetch list, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct Prefetcher {
+    list: Mutex<PrefetchList>,
+    /// Skipped paths, with the reason
+    skipped: Mutex<BTreeMap<String, String>>,
+    /// Persisted prefetch list
+    file: Mutex<Option<PathBuf>>,
+    /// Set by the local data wipe: no download starts for the rest of the
+    /// process
+    stopped: AtomicBool,
+}
+
+impl Prefetcher {
+    /// Load the prefetch list persisted in `file`
+    pub fn load(&self, file: PathBuf) {
+        let list: PrefetchList = std::fs::read_to_string(&file)
+            .ok()
+            .and_then(|contents| {
+                serde_json::from_str(&contents)
+                    .map_err(|e| tracing::warn!("Ignoring invalid prefetch list: {}", e))
+                    .ok()
+            })
+            .unwrap_or_default();
+        if let Ok(mut current) = self.list.lock() {
+            *current = list;
+        }
+        if let Ok(mut current) = self.file.lock() {
+            *current = Some(file);
+        }
+    }
+
+    /// Persist the prefetch list
+    pub fn persist(&self) -> AppResult<()> {
+        let Some(file) = self.file.lock().map_err(|e| AppError::Network(e.to_string()))?.clone() else {
+            return Ok(());
+        };
+        let contents = {
+            let list = self.list.lock().map_err(|e| AppError::Network(e.to_string()))?;
+            serde_json::to_vec(&*list).map_err(|e| AppError::Network(format!("Failed to serialize prefetch list: {}", e)))?
+        };
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AppError::Network(format!("Failed to create data directory: {}", e)))?;
+        }
+        let temp = file.with_extension("tmp");
+        std::fs::write(&temp, contents).map_err(|e| AppError::Network(format!("Failed to write prefetch list: {}", e)))?;
+        std::fs::rename(&temp, &file).map_err(|e| AppError::Network(format!("Failed to write prefetch list: {}", e)))
+    }
+
+    /// Documents to prefetch
+    pub fn items(&self) -> Vec<PrefetchItem> {
+        self.list.lock().map(|list| list.items.clone()).unwrap_or_default()
+    }
+
+    /// Replace the documents to prefetch
+    ///
+    /// # Returns
+    ///
+    /// Returns the downloads of the documents no longer in the list.
+    pub fn set(&self, items: Vec<PrefetchItem>) -> AppResult<Vec<String>> {
+        let mut list = self.list.lock().map_err(|e| AppError::Network(e.to_string()))?;
+        let listed = |path: &String| items.iter().any(|item| &item.path == path);
+        let (kept, dropped): (BTreeMap<String, String>, BTreeMap<String, String>) = std::mem::take(&mut list.downloads)
+            .into_iter()
+            .partition(|(path, _)| listed(path));
+        list.downloads = kept;
+        list.vaulted.retain(|path, _| listed(path));
+        list.retries.retain(|path, _| listed(path));
+        list.items = items;
+        if let Ok(mut skipped) = self.skipped.lock() {
+            skipped.clear();
+        }
+        Ok(dropped.into_values().collect())
+    }
+
+    /// Download started for a path
+    pub fn download_id(&self, path: &str) -> Option<String> {
+        self.list.lock().ok()?.downloads.get(path).cloned()
+    }
+
+    fn record_download(&self, path: &str, id: &str) {
+        if let Ok(mut list) = self.list.lock() {
+            list.downloads.insert(path.to_string(), id.to_string());
+        }
+    }
+
+    /// Vault document of a path, once downloaded
+    pub fn vault_id(&self, path: &str) -> Option<String> {
+        self.list.lock().ok()?.vaulted.get(path).cloned()
+    }
+
+    fn record_vaulted(&self, path: &str, id: &str) {
+        if let Ok(mut list) = self.list.lock() {
+            list.vaulted.insert(path.to_string(), id.to_string());
+            list.retries.remove(path);
+        }
+    }
+
+    /// Forget the download and vault document of a path, so it is downloaded
+    /// again
+    fn forget(&self, path: &str) {
+        if let Ok(mut list) = self.list.lock() {
+            list.downloads.remove(path);
+            list.vaulted.remove(path);
+        }
+    }
+
+    /// Decide whether the failed download of a path is retried now
+    ///
+    /// Retries are spaced by `retry_delay_secs`; after `PREFETCH_MAX_RETRIES`
+    /// retries the document is skipped.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the download must be resumed now.
+    fn take_retry(&self, path: &str, now: u64) -> bool {
+        let Ok(mut list) = self.list.lock() else {
+            return false;
+        };
+        let retry = list.retries.entry(path.to_string()).or_default();
+        if now < retry.next_at {
+            return false;
+        }
+        if retry.count >= constants::PREFETCH_MAX_RETRIES {
+            drop(list);
+            self.skip(path, format!("Download failed after {} retries", constants::PREFETCH_MAX_RETRIES));
+            return false;
+        }
+        retry.count += 1;
+        retry.next_at = now.saturating_add(retry_delay_secs(retry.count));
+        true
+    }
+
+    fn skip(&self, path: &str, reason: String) {
+        if let Ok(mut skipped) = self.skipped.lock() {
+            skipped.insert(path.to_string(), reason);
+        }
+    }
+
+    fn skipped(&self, path: &str) -> Option<String> {
+        self.skipped.lock().ok()?.get(path).cloned()
+    }
+}
+
+/// Returns `true` if the network and power conditions allow prefetching
+pub fn conditions_allow(network: &NativeNetworkState, power: &PowerState) -> bool {
+    let unmetered = matches!(network.transport, NetworkTransport::Wifi | NetworkTransport::Ethernet) && !network.metered;
+    unmetered && power.charging && !power.low_power_mode
+}
+
+/// Get the power state of the device
+pub fn power_state() -> AppResult<PowerState> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::power_state()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::power_state()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(PowerState::default())
+    }
+}
+
+/// Whether prefetching may run now
+fn allowed(app: &AppHandle) -> bool {
+    if !feature_flags::is_enabled(app, feature_flags::CONTENT_PREFETCH) {
+        return false;
+    }
+    match (network_info::native_state(), power_state()) {
+        (Ok(network), Ok(power)) => conditions_allow(&network, &power),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::debug!("Prefetch conditions unknown: {}", e);
+            false
+        }
+    }
+}
+
+/// Start, pause or resume the prefetch downloads according to the conditions
+pub fn check(app: &AppHandle) {
+    let prefetcher = app.state::<Prefetcher>();
+    if prefetcher.stopped.load(Ordering::SeqCst) {
+        return;
+    }
+    let items = prefetcher.items();
+    if items.is_empty() {
+        return;
+    }
+    let allowed = allowed(app);
+    let now = now_secs();
+    let manager = app.state::<DownloadManager>();
+    let vault = app.state::<Vault>();
+    let root = match file_storage::root(app) {
+        Ok(root) => root,
+        Err(e) => {
+            tracing::warn!("Prefetch unavailable: {}", e);
+            return;
+        }
+    };
+
+    for item in items {
+        match prefetcher.vault_id(&item.path) {
+            Some(id) if vault.contains(&id) => continue,
+            Some(_) => {
+                tracing::debug!("Prefetched {} was purged from the vault", item.path);
+                prefetcher.forget(&item.path);
+            }
+            None => {}
+        }
+        let download = prefetcher.download_id(&item.path).and_then(|id| manager.get(&id));
+        let result = match download {
+            _ if prefetcher.skipped(&item.path).is_some() => Ok(()),
+            Some(download) if download.status == DownloadStatus::Completed => store_in_vault(app, &item.path),
+            Some(download) if allowed => match download.status {
+                DownloadStatus::Paused => downloads::resume(app, &download.id),
+                DownloadStatus::Failed if prefetcher.take_retry(&item.path, now) => {
+                    downloads::resume(app, &download.id)
+                }
+                _ => Ok(()),
+            },
+            Some(download) => match download.status {
+                DownloadStatus::Queued | DownloadStatus::Running => downloads::pause(app, &download.id),
+                _ => Ok(()),
+            },
+            None if !allowed => Ok(()),
+            None => {
+                let present = file_storage::resolve(&root, &item.path).is_ok_and(|target| target.is_file());
+                if present {
+                    Ok(())
+                } else {
+                    start_download(app, &root, &item)
+                }
+            }
+        };
+        if let Err(e) = result {
+            tracing::warn!("Prefetch of {} failed: {}", item.path, e);
+        }
+    }
+    if let Err(e) = prefetcher.persist() {
+        tracing::warn!("{}", e);
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Move a downloaded document into the vault
+fn store_in_vault(app: &AppHandle, path: &str) -> AppResult<()> {
+    let prefetcher = app.state::<Prefetcher>();
+    match vault::import(app, path, None) {
+        Ok(item) => {
+            prefetcher.record_vaulted(path, &item.id);
+            Ok(())
+        }
+        Err(e) => {
+            prefetcher.skip(path, e.to_string());
+            Err(e)
+        }
+    }
+}
+
+/// Start the download of a document, if it fits in the storage quota
+fn start_download(app: &AppHandle, root: &std::path::Path, item: &PrefetchItem) -> AppResult<()> {
+    let prefetcher = app.state::<Prefetcher>();
+    if let Err(e) = file_storage::prepare_write(root, &item.path, item.size.unwrap_or(0)) {
+        prefetcher.skip(&item.path, e.to_string());
+        return Err(e);
+    }
+    match downloads::start(app, &item.url, &item.path, item.sha256.as_deref()) {
+        Ok(download) => {
+            tracing::info!("Prefetching {}", item.path);
+            prefetcher.record_download(&item.path, &download.id);
+            Ok(())
+        }
+        Err(e) => {
+            prefetcher.skip(&item.path, e.to_string());
+            Err(e)
+        }
+    }
+}
+
+/// Replace the documents to prefetch
+///
+/// The unfinished downloads of the documents dropped from the list are
+/// cancelled, and the new documents are started if the conditions allow.
+pub fn set(app: &AppHandle, items: Vec<PrefetchItem>) -> AppResult<PrefetchStatus> {
+    if items.len() > constants::PREFETCH_MAX_ITEMS {
+        return Err(AppError::Network(format!("At most {} documents can be prefetched", constants::PREFETCH_MAX_ITEMS)));
+    }
+    for item in &items {
+        file_storage::validate_path(&item.path)?;
+    }
+
+    let prefetcher = app.state::<Prefetcher>();
+    let manager = app.state::<DownloadManager>();
+    for id in prefetcher.set(items)? {
+        let unfinished = manager
+            .get(&id)
+            .is_some_and(|download| download.status != DownloadStatus::Completed);
+        if unfinished {
+            downloads::cancel(app, &id)?;
+        }
+    }
+    prefetcher.persist()?;
+    check(app);
+    Ok(status(app))
+}
+
+/// Current prefetch status
+pub fn status(app: &AppHandle) -> PrefetchStatus {
+    let prefetcher = app.state::<Prefetcher>();
+    let manager = app.state::<DownloadManager>();
+    let entries = prefetcher
+        .items()
+        .into_iter()
+        .map(|item| PrefetchEntry {
+            download: prefetcher.download_id(&item.path).and_then(|id| manager.get(&id)),
+            vault_id: prefetcher.vault_id(&item.path),
+            skipped: prefetcher.skipped(&item.path),
+            item,
+        })
+        .collect();
+    PrefetchStatus {
+        conditions_met: allowed(app),
+        entries,
+    }
+}
+
+/// Stop prefetching for the rest of the process (local data wipe)
+pub fn stop(app: &AppHandle) {
+    app.state::<Prefetcher>().stopped.store(true, Ordering::SeqCst);
+}
+
+/// Restore the prefetch list and check the conditions periodically
+pub fn start(app: &AppHandle) -> AppResult<()> {
+    let file = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Network(format!("Failed to resolve data directory: {}", e)))?
+        .join(constants::PREFETCH_FILE);
+    app.state::<Prefetcher>().load(file);
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(constants::PREFETCH_CHECK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if app.state::<Prefetcher>().stopped.load(Ordering::SeqCst) {
+                break;
+            }
+            check(&app);
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(path: &str) -> PrefetchItem {
+        PrefetchItem {
+            url: format!("https://app.elulib.com/api/documents/{}", path),
+            path: path.to_string(),
+            size: Some(1024),
+            sha256: None,
+        }
+    }
+
+    #[test]
+    fn test_conditions() {
+        let wifi = NativeNetworkState {
+            transport: NetworkTransport::Wifi,
+            ..Default::default()
+        };
+        let charging = PowerState {
+            charging: true,
+            low_power_mode: false,
+        };
+        assert!(conditions_allow(&wifi, &charging));
+        assert!(!conditions_allow(&wifi, &PowerState::default()), "On battery");
+        assert!(!conditions_allow(&wifi, &PowerState { low_power_mode: true, ..charging }));
+
+        let hotspot = NativeNetworkState { metered: true, ..wifi.clone() };
+        assert!(!conditions_allow(&hotspot, &charging), "Metered Wi-Fi");
+        let cellular = NativeNetworkState {
+            transport: NetworkTransport::Cellular,
+            ..Default::default()
+        };
+        assert!(!conditions_allow(&cellular, &charging));
+    }
+
+    #[test]
+    fn test_set_drops_downloads_of_removed_items() {
+        let prefetcher = Prefetcher::default();
+        prefetcher.set(vec![item("annexes/a.pdf"), item("annexes/b.pdf")]).unwrap();
+        prefetcher.record_download("annexes/a.pdf", "d1");
+        prefetcher.record_download("annexes/b.pdf", "d2");
+        prefetcher.skip("annexes/b.pdf", "Storage quota exceeded".to_string());
+
+        let dropped = prefetcher.set(vec![item("annexes/a.pdf"), item("annexes/c.pdf")]).unwrap();
+        assert_eq!(dropped, ["d2"]);
+        assert_eq!(prefetcher.download_id("annexes/a.pdf").as_deref(), Some("d1"));
+        assert_eq!(prefetcher.download_id("annexes/c.pdf"), None);
+        assert_eq!(prefetcher.skipped("annexes/b.pdf"), None, "Skips are retried with a new list");
+    }
+
+    #[test]
+    fn test_failed_downloads_back_off_then_give_up() {
+        let prefetcher = Prefetcher::default();
+        prefetcher.set(vec![item("annexes/a.pdf")]).unwrap();
+        let mut now = 1_000;
+        for count in 1..=constants::PREFETCH_MAX_RETRIES {
+            assert!(prefetcher.take_retry("annexes/a.pdf", now), "Retry {}", count);
+            assert!(!prefetcher.take_retry("annexes/a.pdf", now + 1), "Backing off");
+            now += retry_delay_secs(count);
+        }
+        assert!(!prefetcher.take_retry("annexes/a.pdf", now));
+        assert!(prefetcher.skipped("annexes/a.pdf").is_some());
+
+        assert_eq!(retry_delay_secs(1), constants::PREFETCH_CHECK_INTERVAL_SECS);
+        assert_eq!(retry_delay_secs(2), 2 * constants::PREFETCH_CHECK_INTERVAL_SECS);
+        assert_eq!(retry_delay_secs(30), constants::PREFETCH_RETRY_MAX_SECS);
+    }
+
+    #[test]
+    fn test_vaulted_documents_follow_the_list() {
+        let prefetcher = Prefetcher::default();
+        prefetcher.set(vec![item("annexes/a.pdf"), item("annexes/b.pdf")]).unwrap();
+        prefetcher.take_retry("annexes/a.pdf", 0);
+        prefetcher.record_vaulted("annexes/a.pdf", "v1");
+        prefetcher.record_vaulted("annexes/b.pdf", "v2");
+        assert_eq!(prefetcher.list.lock().unwrap().retries.len(), 0, "Retries end once stored");
+
+        prefetcher.set(vec![item("annexes/a.pdf")]).unwrap();
+        assert_eq!(prefetcher.vault_id("annexes/a.pdf").as_deref(), Some("v1"));
+        assert_eq!(prefetcher.vault_id("annexes/b.pdf"), None);
+        prefetcher.forget("annexes/a.pdf");
+        assert_eq!(prefetcher.vault_id("annexes/a.pdf"), None);
+    }
+
+    #[test]
+    fn test_list_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(constants::PREFETCH_FILE);
+        let prefetcher = Prefetcher::default();
+        prefetcher.load(file.clone());
+        prefetcher.set(vec![item("annexes/a.pdf")]).unwrap();
+        prefetcher.record_download("annexes/a.pdf", "d1");
+        prefetcher.record_vaulted("annexes/a.pdf", "v1");
+        prefetcher.persist().unwrap();
+        assert!(!file.with_extension("tmp").exists(), "Written through a temporary file");
+
+        let reloaded = Prefetcher::default();
+        reloaded.load(file);
+        assert_eq!(reloaded.items(), [item("annexes/a.pdf")]);
+        assert_eq!(reloaded.download_id("annexes/a.pdf").as_deref(), Some("d1"));
+        assert_eq!(reloaded.vault_id("annexes/a.pdf").as_deref(), Some("v1"));
+    }
+}
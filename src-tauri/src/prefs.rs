@@ -0,0 +1,233 @@
+/// Preferences store for non-sensitive settings
+///
+/// Settings such as the theme, quiet hours or the last route used to be
+/// written to the keychain, which is slow, may be unavailable while the
+/// device is locked, and should be reserved for secrets. They are now kept in
+/// a JSON file in the app data directory (`PREFS_FILE`):
+/// - values are typed: booleans, integers or strings
+/// - keys are short identifiers (letters, digits, `.`, `_`, `-`); keys that
+///   look like secrets (`PREFS_SECRET_KEY_MARKERS`) are rejected, those
+///   belong in the keychain
+/// - the file is loaded at startup and rewritten atomically on every change
+///
+/// The file is not encrypted: never store personal or sensitive data here.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::{AppError, AppResult};
+
+/// Preference value
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(untagged)]
+pub enum PrefValue {
+    /// Boolean value
+    Bool(bool),
+    /// Integer value
+    Int(i64),
+    /// String value
+    String(String),
+}
+
+/// Preferences, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct Prefs {
+    values: Mutex<BTreeMap<String, PrefValue>>,
+    /// Preferences file, once loaded
+    file: Mutex<Option<PathBuf>>,
+}
+
+impl Prefs {
+    /// Load the preferences stored in `file`
+    pub fn load(&self, file: PathBuf) {
+        let values = read_prefs(&file);
+        tracing::debug!("Loaded {} preference(s)", values.len());
+        if let Ok(mut current) = self.values.lock() {
+            *current = values;
+        }
+        if let Ok(mut current) = self.file.lock() {
+            *current = Some(file);
+        }
+    }
+
+    /// Value of a preference
+    pub fn get(&self, key: &str) -> Option<PrefValue> {
+        self.values.lock().ok()?.get(key).cloned()
+    }
+
+    /// All preferences
+    pub fn all(&self) -> BTreeMap<String, PrefValue> {
+        self.values.lock().map(|values| values.clone()).unwrap_or_default()
+    }
+
+    /// Set a preference and persist the preferences
+    pub fn set(&self, key: &str, value: PrefValue) -> AppResult<()> {
+        validate_key(key)?;
+        validate_value(&value)?;
+        let mut values = self.values.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        if !values.contains_key(key) && values.len() >= constants::PREFS_MAX_ENTRIES {
+            return Err(AppError::Storage(format!("At most {} preferences can be stored", constants::PREFS_MAX_ENTRIES)));
+        }
+        values.insert(key.to_string(), value);
+        self.persist(&values)
+    }
+
+    /// Remove a preference and persist the preferences
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the preference existed.
+    pub fn remove(&self, key: &str) -> AppResult<bool> {
+        let mut values = self.values.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        if values.remove(key).is_none() {
+            return Ok(false);
+        }
+        self.persist(&values)?;
+        Ok(true)
+    }
+
+    /// Remove all preferences and their file
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of removed preferences.
+    pub fn clear(&self) -> AppResult<usize> {
+        let mut values = self.values.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        let count = values.len();
+        values.clear();
+        if let Some(file) = self.file.lock().map_err(|e| AppError::Storage(e.to_string()))?.as_deref() {
+            match std::fs::remove_file(file) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(AppError::Storage(format!("Failed to delete preferences: {}", e))),
+            }
+        }
+        Ok(count)
+    }
+
+    fn persist(&self, values: &BTreeMap<String, PrefValue>) -> AppResult<()> {
+        match self.file.lock().map_err(|e| AppError::Storage(e.to_string()))?.as_deref() {
+            Some(file) => write_prefs(file, values),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Validate a preference key
+pub fn validate_key(key: &str) -> AppResult<()> {
+    let valid = !key.is_empty()
+        && key.len() <= constants::PREFS_MAX_KEY_LENGTH
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    if !valid {
+        return Err(AppError::InvalidArgument(format!(
+            "Preference keys must be 1 to {} characters among letters, digits, '.', '_' and '-'",
+            constants::PREFS_MAX_KEY_LENGTH
+        )));
+    }
+    let lowercase = key.to_ascii_lowercase();
+    if constants::PREFS_SECRET_KEY_MARKERS
+        .iter()
+        .any(|marker| lowercase.contains(marker))
+    {
+        return Err(AppError::Storage(format!("{} looks like a secret: store it in the keychain", key)));
+    }
+    Ok(())
+}
+
+/// Validate a preference value
+pub fn validate_value(value: &PrefValue) -> AppResult<()> {
+    match value {
+        PrefValue::String(text) if text.len() > constants::PREFS_MAX_VALUE_BYTES => Err(AppError::InvalidArgument(format!(
+            "Preference values must be at most {} bytes",
+            constants::PREFS_MAX_VALUE_BYTES
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Read a preferences file, ignoring missing or corrupted files
+pub fn read_prefs(path: &Path) -> BTreeMap<String, PrefValue> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return BTreeMap::new();
+    };
+    serde_json::from_str(&contents)
+        .map_err(|e| tracing::warn!("Ignoring invalid preferences file: {}", e))
+        .unwrap_or_default()
+}
+
+/// Write a preferences file atomically
+pub fn write_prefs(path: &Path, values: &BTreeMap<String, PrefValue>) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| AppError::Storage(format!("Failed to create data directory: {}", e)))?;
+    }
+    let contents = serde_json::to_vec(values).map_err(|e| AppError::Storage(format!("Failed to serialize preferences: {}", e)))?;
+    let temp = path.with_extension("tmp");
+    std::fs::write(&temp, contents).map_err(|e| AppError::Storage(format!("Failed to write preferences: {}", e)))?;
+    std::fs::rename(&temp, path).map_err(|e| AppError::Storage(format!("Failed to write preferences: {}", e)))
+}
+
+/// Load the preferences from the app data directory
+pub fn init(app: &AppHandle) -> AppResult<()> {
+    let file = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve data directory: {}", e)))?
+        .join(constants::PREFS_FILE);
+    app.state::<Prefs>().load(file);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefs_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(constants::PREFS_FILE);
+        let prefs = Prefs::default();
+        prefs.load(file.clone());
+
+        prefs.set("theme", PrefValue::String("dark".to_string())).unwrap();
+        prefs.set("notifications.quiet_hours.start", PrefValue::Int(22)).unwrap();
+        prefs.set("onboarding.done", PrefValue::Bool(true)).unwrap();
+        assert_eq!(prefs.remove("onboarding.done").unwrap(), true);
+        assert_eq!(prefs.remove("onboarding.done").unwrap(), false);
+
+        let reloaded = Prefs::default();
+        reloaded.load(file);
+        assert_eq!(reloaded.get("theme"), Some(PrefValue::String("dark".to_string())));
+        assert_eq!(reloaded.get("notifications.quiet_hours.start"), Some(PrefValue::Int(22)));
+        assert_eq!(reloaded.get("onboarding.done"), None);
+    }
+
+    #[test]
+    fn test_values_are_typed() {
+        let values: BTreeMap<String, PrefValue> =
+            serde_json::from_str(r#"{"a": true, "b": 3, "c": "x"}"#).unwrap();
+        assert_eq!(values["a"], PrefValue::Bool(true));
+        assert_eq!(values["b"], PrefValue::Int(3));
+        assert_eq!(values["c"], PrefValue::String("x".to_string()));
+        assert!(serde_json::from_str::<PrefValue>("1.5").is_err());
+        assert!(serde_json::from_str::<PrefValue>(r#"{"nested": 1}"#).is_err());
+    }
+
+    #[test]
+    fn test_invalid_keys_and_values_are_rejected() {
+        let prefs = Prefs::default();
+        assert!(prefs.set("", PrefValue::Bool(true)).is_err());
+        assert!(prefs.set("last route", PrefValue::Bool(true)).is_err());
+        assert!(prefs.set("api_token", PrefValue::String("secret".to_string())).is_err());
+        assert!(prefs.set("userPassword", PrefValue::String("secret".to_string())).is_err());
+        assert!(prefs
+            .set("last_route", PrefValue::String("/".repeat(constants::PREFS_MAX_VALUE_BYTES + 1)))
+            .is_err());
+        assert!(prefs.set("last_route", PrefValue::String("/dossiers/42".to_string())).is_ok());
+    }
+}
@@ -0,0 +1,257 @@
+/// Push payload routing
+///
+/// Every push received by the native layer goes through `on_push`, fed by
+/// the platform callbacks with `on_platform_push` (APNs delivery and
+/// notification responses on iOS, FCM messages and notification intents on
+/// Android):
+/// - data-only pushes of type `sync` start a sync (`sync::on_silent_push`)
+/// - visible pushes (`aps.alert` on iOS, `notification` on Android) show a
+///   native notification and are forwarded to the webview
+///   (`notification://received`, queued in the `outbox` until the page is
+///   ready); when the notification is tapped, its link
+///   (`url`, at the top level or in `data`) is routed like a deep link
+/// - a notification tapped before the first page load launched the app: it
+///   is kept for the page as the launch payload instead (see `launch`)
+/// - other payloads are ignored
+///
+/// In debug builds, the `simulate_push` command feeds synthetic payloads
+/// (or the named `fixture`s) through the same pipeline, so the tap-to-route
+/// flow can be tested without a FCM/APNs backend.
+
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::deep_link::{self, DeepLink, DeepLinkSource};
+use crate::events::NotificationReceived;
+use crate::launch;
+use crate::notifications;
+use crate::outbox;
+use crate::sync::{self, SyncReport};
+use crate::AppResult;
+
+/// Visible part of a push
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct PushAlert {
+    /// Notification title
+    pub title: String,
+    /// Notification body text
+    pub body: String,
+}
+
+/// Kind of a push payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PushKind {
+    /// Data-only push asking for a sync
+    Sync,
+    /// User-visible notification, with the link opened on tap
+    Visible {
+        /// Notification contents
+        alert: PushAlert,
+        /// Link opened when the notification is tapped
+        link: Option<String>,
+    },
+    /// Payload the app does not handle
+    Ignored,
+}
+
+/// Notification contents of a visible push (APNs or FCM format)
+fn alert(payload: &Value) -> Option<PushAlert> {
+    let text = |value: Option<&Value>| value.and_then(Value::as_str).unwrap_or_default().to_string();
+    if let Some(alert) = payload.pointer("/aps/alert") {
+        return Some(match alert.as_str() {
+            Some(body) => PushAlert {
+                title: String::new(),
+                body: body.to_string(),
+            },
+            None => PushAlert {
+                title: text(alert.get("title")),
+                body: text(alert.get("body")),
+            },
+        });
+    }
+    payload.get("notification").map(|notification| PushAlert {
+        title: text(notification.get("title")),
+        body: text(notification.get("body")),
+    })
+}
+
+/// Classify a push payload
+pub fn classify(payload: &Value) -> PushKind {
+    if sync::is_sync_push(payload) {
+        return PushKind::Sync;
+    }
+    match alert(payload) {
+        Some(alert) => PushKind::Visible {
+            alert,
+            link: payload
+                .get("url")
+                .or_else(|| payload.pointer("/data/url"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        },
+        None => PushKind::Ignored,
+    }
+}
+
+/// What the app did with a push, returned by `simulate_push`
+#[derive(Debug, Clone, PartialEq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PushRouting {
+    /// `sync`, `visible` or `ignored`
+    pub kind: &'static str,
+    /// Report of the sync started by a sync push
+    pub sync: Option<SyncReport>,
+    /// Notification shown for a visible push
+    pub notification: Option<PushAlert>,
+    /// Error showing the notification, if any
+    pub notification_error: Option<String>,
+    /// Deep link routed when the notification was tapped
+    pub deep_link: Option<DeepLink>,
+}
+
+/// Handle a push received by the native layer
+///
+/// # Arguments
+///
+/// * `payload` - Data of the push notification
+/// * `tapped` - Whether the user tapped the notification (visible pushes)
+pub async fn on_push(app: &AppHandle, payload: &Value, tapped: bool) -> AppResult<PushRouting> {
+    let mut routing = PushRouting {
+        kind: "ignored",
+        sync: None,
+        notification: None,
+        notification_error: None,
+        deep_link: None,
+    };
+    match classify(payload) {
+        PushKind::Sync => {
+            routing.kind = "sync";
+            routing.sync = sync::on_silent_push(app, payload).await?;
+        }
+        PushKind::Visible { alert, link } => {
+            routing.kind = "visible";
+            routing.notification_error = notifications::show_notification(&alert.title, &alert.body, None)
+                .err()
+                .map(|e| e.to_string());
+            if tapped && launch::on_notification_tap(app, payload, &alert.title, &alert.body, link.as_deref()) {
+                // Cold start: the page takes the payload with `get_launch_payload`
+                routing.deep_link = link.and_then(|link| deep_link::parse(&link, DeepLinkSource::Notification).ok());
+                routing.notification = Some(alert);
+                return Ok(routing);
+            }
+            outbox::post(
+                app,
+                &NotificationReceived {
+                    title: alert.title.clone(),
+                    body: alert.body.clone(),
+                    link: link.clone(),
+                    tapped,
+                },
+            );
+            routing.notification = Some(alert);
+            if let (true, Some(link)) = (tapped, link) {
+                routing.deep_link = deep_link::parse(&link, DeepLinkSource::Notification).ok();
+                deep_link::on_open_url(app, &link, DeepLinkSource::Notification);
+            }
+        }
+        PushKind::Ignored => tracing::debug!("Ignoring push without alert or sync type"),
+    }
+    Ok(routing)
+}
+
+/// Route a push delivered by a platform callback
+///
+/// # Arguments
+///
+/// * `app` - The app handle
+/// * `payload` - Data of the push notification, as JSON (`userInfo` on iOS,
+///   the message data on Android)
+/// * `tapped` - Whether the callback is a tap on the notification
+pub fn on_platform_push(app: &AppHandle, payload: &str, tapped: bool) {
+    let payload: Value = match serde_json::from_str(payload) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::warn!("Invalid push payload: {}", e);
+            return;
+        }
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = on_push(&app, &payload, tapped).await {
+            tracing::warn!("Failed to handle push: {}", e);
+        }
+    });
+}
+
+/// Named push payloads for end-to-end tests (debug builds only)
+///
+/// - `reminder`: APNs alert linking to an agenda item
+/// - `message`: FCM notification linking to a conversation
+/// - `sync`: data-only sync push of the agenda
+/// - `foreign_link`: visible push linking outside the app (never routed)
+#[cfg(any(debug_assertions, test))]
+pub fn fixture(name: &str) -> AppResult<Value> {
+    match name {
+        "reminder" => Ok(serde_json::json!({
+            "aps": { "alert": { "title": "Conseil municipal", "body": "La séance commence dans 1 heure" } },
+            "url": "elulib://agenda/42",
+        })),
+        "message" => Ok(serde_json::json!({
+            "notification": { "title": "Nouveau message", "body": "Marie vous a écrit" },
+            "data": { "url": "https://app.elulib.com/messages/7" },
+        })),
+        "sync" => Ok(serde_json::json!({ "type": "sync", "collections": ["agenda"] })),
+        "foreign_link" => Ok(serde_json::json!({
+            "notification": { "title": "Lien externe", "body": "Ce lien n'est pas ouvert" },
+            "data": { "url": "https://evil.example/phishing" },
+        })),
+        _ => Err(crate::AppError::InvalidArgument(format!(
+            "Unknown push fixture {:?}, expected reminder, message, sync or foreign_link",
+            name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_classify_payloads() {
+        assert_eq!(classify(&fixture("sync").unwrap()), PushKind::Sync);
+        assert_eq!(
+            classify(&fixture("reminder").unwrap()),
+            PushKind::Visible {
+                alert: PushAlert {
+                    title: "Conseil municipal".to_string(),
+                    body: "La séance commence dans 1 heure".to_string(),
+                },
+                link: Some("elulib://agenda/42".to_string()),
+            }
+        );
+        let PushKind::Visible { link, .. } = classify(&fixture("message").unwrap()) else {
+            panic!("FCM notification should be visible");
+        };
+        assert_eq!(link.as_deref(), Some("https://app.elulib.com/messages/7"));
+
+        let string_alert = json!({ "aps": { "alert": "Rappel" } });
+        assert!(matches!(classify(&string_alert), PushKind::Visible { alert, link: None } if alert.body == "Rappel"));
+        assert_eq!(classify(&json!({ "type": "unknown" })), PushKind::Ignored);
+        assert!(fixture("missing").is_err());
+    }
+
+    #[test]
+    fn test_fixture_links_route_like_deep_links() {
+        for name in ["reminder", "message"] {
+            let PushKind::Visible { link: Some(link), .. } = classify(&fixture(name).unwrap()) else {
+                panic!("{} should link into the app", name);
+            };
+            assert!(deep_link::parse(&link, DeepLinkSource::Notification).is_ok());
+        }
+        let PushKind::Visible { link: Some(link), .. } = classify(&fixture("foreign_link").unwrap()) else {
+            panic!("foreign_link should have a link");
+        };
+        assert!(deep_link::parse(&link, DeepLinkSource::Notification).is_err());
+    }
+}
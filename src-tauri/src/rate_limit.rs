@@ -0,0 +1,400 @@
+/// Command rate limiting module
+///
+/// Every IPC invocation goes through a token bucket before reaching its
+/// command handler, so a compromised or buggy frontend cannot hammer native
+/// APIs (keychain, biometric prompts, login flows):
+/// - each command draws from a bucket: the named buckets of `BUCKETS` (the
+///   keychain operations share one; high-frequency commands such as database
+///   queries, activity reports and realtime messages get explicit limits),
+///   or its own bucket with `RATE_LIMIT_DEFAULT_MAX_REQUESTS`
+/// - all commands also draw from a global bucket
+///   (`RATE_LIMIT_GLOBAL_MAX_REQUESTS`)
+/// - buckets refill continuously over `RATE_LIMIT_WINDOW_SECS`
+///
+/// New commands are rate limited without any change. Limits can be tuned with
+/// the `rate_limit.*` remote config keys.
+///
+/// Rejected invocations fail with a JSON error the frontend can parse:
+/// `{ "error": "rate_limited", "command": "...", "limit": "command" | "global",
+/// "retryAfterMs": 1500 }`. Rejections are counted per command
+/// (`rate_limit_metrics` command).
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::ipc::Invoke;
+use tauri::{Manager, Runtime};
+
+use crate::constants;
+use crate::remote_config::{keys, ConfigStore};
+
+/// Bucket shared by several commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    /// Bucket name
+    pub name: &'static str,
+    /// Commands drawing from the bucket
+    pub commands: &'static [&'static str],
+    /// Requests allowed per window
+    pub max_requests: u32,
+}
+
+/// Keychain operations
+pub const KEYCHAIN: Bucket = Bucket {
+    name: "keychain",
+    commands: &["keychain_store", "keychain_retrieve", "keychain_remove", "keychain_exists"],
+    max_requests: constants::RATE_LIMIT_MAX_REQUESTS,
+};
+
+/// Interactive authentication flows (system sheets and browser sessions)
+pub const AUTHENTICATION: Bucket = Bucket {
+    name: "authentication",
+    commands: &[
+        "auth_login",
+        "auth_logout",
+        "passkey_register",
+        "passkey_authenticate",
+        "unlock_session",
+        "claim_session_token",
+    ],
+    max_requests: constants::RATE_LIMIT_AUTH_MAX_REQUESTS,
+};
+
+/// Local database queries
+pub const DATABASE_QUERIES: Bucket = Bucket {
+    name: "database_queries",
+    commands: &["db_query", "db_query_packed"],
+    max_requests: constants::RATE_LIMIT_DB_QUERY_MAX_REQUESTS,
+};
+
+/// Local database writes
+pub const DATABASE_WRITES: Bucket = Bucket {
+    name: "database_writes",
+    commands: &["db_execute"],
+    max_requests: constants::RATE_LIMIT_DB_EXECUTE_MAX_REQUESTS,
+};
+
+/// User activity reports, resetting the session lock timer
+pub const SESSION_ACTIVITY: Bucket = Bucket {
+    name: "session_activity",
+    commands: &["session_activity"],
+    max_requests: constants::RATE_LIMIT_SESSION_ACTIVITY_MAX_REQUESTS,
+};
+
+/// Realtime messages
+pub const REALTIME_MESSAGES: Bucket = Bucket {
+    name: "realtime_messages",
+    commands: &["ws_send"],
+    max_requests: constants::RATE_LIMIT_WS_SEND_MAX_REQUESTS,
+};
+
+/// Named buckets (other commands get their own bucket)
+pub const BUCKETS: &[Bucket] = &[
+    KEYCHAIN,
+    AUTHENTICATION,
+    DATABASE_QUERIES,
+    DATABASE_WRITES,
+    SESSION_ACTIVITY,
+    REALTIME_MESSAGES,
+];
+
+/// Which limit rejected an invocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitKind {
+    /// The bucket of the command
+    Command,
+    /// The global ceiling
+    Global,
+}
+
+/// Error returned for a rate limited invocation
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimited {
+    /// Always `rate_limited`
+    pub error: &'static str,
+    /// Rejected command
+    pub command: String,
+    /// Which limit was reached
+    pub limit: LimitKind,
+    /// Delay before the command can succeed again, in milliseconds
+    pub retry_after_ms: u64,
+}
+
+/// Effective limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Refill window
+    pub window: Duration,
+    /// Keychain bucket size
+    pub keychain_max: u32,
+    /// Global bucket size
+    pub global_max: u32,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(constants::RATE_LIMIT_WINDOW_SECS),
+            keychain_max: KEYCHAIN.max_requests,
+            global_max: constants::RATE_LIMIT_GLOBAL_MAX_REQUESTS,
+        }
+    }
+}
+
+impl Limits {
+    /// Bucket name and size of a command
+    fn bucket_of<'a>(&self, command: &'a str) -> (&'a str, u32) {
+        match BUCKETS.iter().find(|bucket| bucket.commands.contains(&command)) {
+            Some(bucket) if bucket.name == KEYCHAIN.name => (bucket.name, self.keychain_max),
+            Some(bucket) => (bucket.name, bucket.max_requests),
+            None => (command, constants::RATE_LIMIT_DEFAULT_MAX_REQUESTS),
+        }
+    }
+}
+
+/// Token bucket refilled continuously
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn full(capacity: u32, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(capacity),
+            updated_at: now,
+        }
+    }
+
+    /// Refill the bucket, then return the delay until a token is available
+    fn refill(&mut self, capacity: u32, window: Duration, now: Instant) -> Duration {
+        let capacity = f64::from(capacity.max(1));
+        let per_sec = capacity / window.as_secs_f64().max(1.0);
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * per_sec).min(capacity);
+        self.updated_at = now;
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / per_sec)
+        }
+    }
+}
+
+/// Rejection counters
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitMetrics {
+    /// Rejected invocations by command
+    pub rejected: BTreeMap<String, u64>,
+    /// Invocations rejected by the global ceiling
+    pub global_rejected: u64,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    buckets: HashMap<String, TokenBucket>,
+    global: Option<TokenBucket>,
+    metrics: RateLimitMetrics,
+}
+
+/// Rate limiter, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    state: Mutex<LimiterState>,
+}
+
+impl RateLimiter {
+    /// Draw a token for an invocation
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the command may run, or the limit that was reached.
+    /// No token is drawn from either bucket when the invocation is rejected.
+    pub fn acquire(&self, command: &str, limits: &Limits, now: Instant) -> Result<(), RateLimited> {
+        let Ok(mut state) = self.state.lock() else {
+            return Ok(());
+        };
+        let (name, capacity) = limits.bucket_of(command);
+        let state = &mut *state;
+
+        let bucket = state
+            .buckets
+            .entry(name.to_string())
+            .or_insert_with(|| TokenBucket::full(capacity, now));
+        let command_wait = bucket.refill(capacity, limits.window, now);
+        let global = state
+            .global
+            .get_or_insert_with(|| TokenBucket::full(limits.global_max, now));
+        let global_wait = global.refill(limits.global_max, limits.window, now);
+
+        let rejected = if !command_wait.is_zero() {
+            Some((LimitKind::Command, command_wait))
+        } else if !global_wait.is_zero() {
+            Some((LimitKind::Global, global_wait))
+        } else {
+            None
+        };
+        if let Some((limit, wait)) = rejected {
+            *state.metrics.rejected.entry(command.to_string()).or_default() += 1;
+            if limit == LimitKind::Global {
+                state.metrics.global_rejected += 1;
+            }
+            return Err(RateLimited {
+                error: "rate_limited",
+                command: command.to_string(),
+                limit,
+                retry_after_ms: (wait.as_secs_f64() * 1000.0).ceil().max(1.0) as u64,
+            });
+        }
+
+        bucket.tokens -= 1.0;
+        global.tokens -= 1.0;
+        Ok(())
+    }
+
+    /// Rejection counters since launch
+    pub fn metrics(&self) -> RateLimitMetrics {
+        self.state
+            .lock()
+            .map(|state| state.metrics.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Limits with remote overrides applied
+pub fn limits(config: &ConfigStore) -> Limits {
+    let default = Limits::default();
+    Limits {
+        window: config
+            .get_typed(keys::RATE_LIMIT_WINDOW_SECS)
+            .map(Duration::from_secs)
+            .unwrap_or(default.window),
+        keychain_max: config.get_or(keys::RATE_LIMIT_MAX_REQUESTS, default.keychain_max),
+        global_max: config.get_or(keys::RATE_LIMIT_GLOBAL_MAX_REQUESTS, default.global_max),
+    }
+}
+
+impl RateLimited {
+    /// JSON the invocation is rejected with
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "rate_limited".to_string())
+    }
+}
+
+/// Validate an IPC invocation against the rate limits
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command may run, or the `RateLimited` error to
+/// reject the invocation with (`RateLimited::to_json`).
+pub fn check_invoke<R: Runtime>(invoke: &Invoke<R>) -> Result<(), RateLimited> {
+    let app = invoke.message.webview_ref().app_handle();
+    let limits = limits(&app.state::<ConfigStore>());
+    app.state::<RateLimiter>()
+        .acquire(invoke.message.command(), &limits, Instant::now())
+        .map_err(|rejected| {
+            tracing::warn!(
+                "Command {} rate limited ({:?}), retry after {}ms",
+                rejected.command,
+                rejected.limit,
+                rejected.retry_after_ms
+            );
+            rejected
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(keychain_max: u32, global_max: u32) -> Limits {
+        Limits {
+            window: Duration::from_secs(60),
+            keychain_max,
+            global_max,
+        }
+    }
+
+    #[test]
+    fn test_keychain_commands_share_a_bucket() {
+        let limiter = RateLimiter::default();
+        let limits = limits(2, 100);
+        let now = Instant::now();
+        assert!(limiter.acquire("keychain_store", &limits, now).is_ok());
+        assert!(limiter.acquire("keychain_retrieve", &limits, now).is_ok());
+
+        let rejected = limiter.acquire("keychain_exists", &limits, now).unwrap_err();
+        assert_eq!(rejected.limit, LimitKind::Command);
+        // Two tokens per minute: one token every 30 seconds
+        assert!((30_000..=30_001).contains(&rejected.retry_after_ms));
+
+        assert!(limiter.acquire("device_info", &limits, now).is_ok(), "Other buckets are unaffected");
+        let retry_at = now + Duration::from_millis(rejected.retry_after_ms);
+        assert!(limiter.acquire("keychain_exists", &limits, retry_at).is_ok());
+    }
+
+    #[test]
+    fn test_high_frequency_commands_have_explicit_limits() {
+        let limits = Limits::default();
+        assert_eq!(limits.bucket_of("db_query"), ("database_queries", constants::RATE_LIMIT_DB_QUERY_MAX_REQUESTS));
+        assert_eq!(limits.bucket_of("db_query_packed").0, "database_queries");
+        assert_eq!(limits.bucket_of("db_execute").0, "database_writes");
+        assert_eq!(limits.bucket_of("session_activity").0, "session_activity");
+        assert_eq!(limits.bucket_of("ws_send").0, "realtime_messages");
+        assert_eq!(limits.bucket_of("device_info"), ("device_info", constants::RATE_LIMIT_DEFAULT_MAX_REQUESTS));
+
+        let explicit: u32 = BUCKETS
+            .iter()
+            .filter(|bucket| bucket.max_requests > constants::RATE_LIMIT_DEFAULT_MAX_REQUESTS)
+            .map(|bucket| bucket.max_requests)
+            .sum();
+        assert!(explicit <= limits.global_max, "Global ceiling above the high-frequency buckets");
+    }
+
+    #[test]
+    fn test_global_ceiling() {
+        let limiter = RateLimiter::default();
+        let limits = limits(10, 2);
+        let now = Instant::now();
+        assert!(limiter.acquire("device_info", &limits, now).is_ok());
+        assert!(limiter.acquire("storage_info", &limits, now).is_ok());
+
+        let rejected = limiter.acquire("carrier_info", &limits, now).unwrap_err();
+        assert_eq!(rejected.limit, LimitKind::Global);
+
+        let metrics = limiter.metrics();
+        assert_eq!(metrics.rejected.get("carrier_info"), Some(&1));
+        assert_eq!(metrics.global_rejected, 1);
+    }
+
+    #[test]
+    fn test_rejection_does_not_draw_tokens() {
+        let limiter = RateLimiter::default();
+        let limits = limits(1, 1);
+        let now = Instant::now();
+        assert!(limiter.acquire("keychain_store", &limits, now).is_ok());
+        for _ in 0..5 {
+            assert!(limiter.acquire("keychain_store", &limits, now).is_err());
+        }
+        assert!(limiter.acquire("keychain_store", &limits, now + Duration::from_secs(61)).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limited_json() {
+        let rejected = RateLimited {
+            error: "rate_limited",
+            command: "auth_login".to_string(),
+            limit: LimitKind::Command,
+            retry_after_ms: 1500,
+        };
+        assert_eq!(
+            rejected.to_json(),
+            r#"{"error":"rate_limited","command":"auth_login","limit":"command","retryAfterMs":1500}"#
+        );
+    }
+}
@@ -0,0 +1,469 @@
+/// Remote configuration module
+///
+/// This module fetches a signed JSON configuration from the backend
+/// (`REMOTE_CONFIG_ENDPOINT`) and lets native subsystems and the frontend
+/// read values from it instead of compile-time constants.
+///
+/// The backend returns an envelope `{ "payload": "<base64>", "signature": "<base64>" }`
+/// where `payload` is a JSON object and `signature` its Ed25519 signature:
+///
+/// ```json
+/// { "version": 42, "expires_at": 1767225600, "values": { "connectivity.port": 443 } }
+/// ```
+///
+/// `version` increases with every config published by the backend, and a
+/// config older than the current one is rejected, so a captured signed config
+/// cannot be replayed to roll back a newer one. A config past `expires_at`
+/// (seconds since Unix epoch) is rejected as well. The envelope is cached
+/// as-is in the app data directory, and verified again every time it is
+/// loaded.
+///
+/// Config lifecycle:
+/// - at startup, the cached config is loaded synchronously (`load_cached`)
+/// - a refresh is fetched when the cached config is older than
+///   `REMOTE_CONFIG_TTL_SECS` (`spawn_refresh`), at startup and on foreground
+/// - every loaded config is applied to the subsystems reading it (`apply`)
+///
+/// Remote config is disabled when the app is built without the
+/// `ELULIB_REMOTE_CONFIG_PUBLIC_KEY` environment variable.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::connectivity::ConnectivityTarget;
+use crate::constants;
+use crate::device_policy;
+use crate::feature_flags;
+use crate::http;
+use crate::tls_pinning;
+use crate::{AppError, AppResult};
+
+/// Config keys read by native subsystems
+pub mod keys {
+    /// Host name targeted by connectivity checks (string)
+    pub const CONNECTIVITY_HOST: &str = "connectivity.host";
+    /// TCP port targeted by connectivity checks (integer)
+    pub const CONNECTIVITY_PORT: &str = "connectivity.port";
+    /// Timeout of each connectivity attempt, in seconds (integer)
+    pub const CONNECTIVITY_TIMEOUT_SECS: &str = "connectivity.timeout_secs";
+    /// Inactivity delay before the session locks, in seconds (integer)
+    pub const SESSION_LOCK_TIMEOUT_SECS: &str = "session_lock.timeout_secs";
+    /// Reload the webview when the system locale changes (boolean)
+    pub const LOCALE_RELOAD_ON_CHANGE: &str = "locale.reload_on_change";
+    /// Maximum number of keychain operations per window (integer)
+    pub const RATE_LIMIT_MAX_REQUESTS: &str = "rate_limit.max_requests";
+    /// Command rate limiting window, in seconds (integer)
+    pub const RATE_LIMIT_WINDOW_SECS: &str = "rate_limit.window_secs";
+    /// Maximum number of invocations of all commands per window (integer)
+    pub const RATE_LIMIT_GLOBAL_MAX_REQUESTS: &str = "rate_limit.global_max_requests";
+    /// Enabled command scopes (list of names, see the `command_scope` module)
+    pub const COMMAND_SCOPES: &str = "command_scopes";
+    /// Feature flag rules (see the `feature_flags` module)
+    pub const FEATURE_FLAGS: &str = "feature_flags";
+    /// Webview TLS pins, replacing the build pins (list of base64 SPKI hashes)
+    pub const TLS_PINS: &str = "tls.pins";
+    /// Response to a failed binary integrity self-check (`warn` or `block`)
+    pub const TAMPER_RESPONSE: &str = "integrity.tamper_response";
+    /// Delay before a sensitive clipboard copy is cleared, in seconds (integer)
+    pub const CLIPBOARD_CLEAR_DELAY_SECS: &str = "clipboard.clear_delay_secs";
+    /// Device policy rules (see the `device_policy` module)
+    pub const DEVICE_POLICY: &str = "device_policy";
+    /// Collections synced into the local database (`[{ "name", "endpoint" }]`)
+    pub const SYNC_COLLECTIONS: &str = "sync.collections";
+    /// Queued uploads wait for an unmetered network unless the upload says
+    /// otherwise (boolean)
+    pub const UPLOAD_DEFER_ON_METERED: &str = "uploads.defer_on_metered";
+    /// Expiry policy of the document vault (`{ "maxAgeSecs", "maxIdleSecs" }`)
+    pub const VAULT_POLICY: &str = "vault.policy";
+}
+
+/// Signed config, as returned by the backend and stored in the cache
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SignedConfig {
+    /// Base64-encoded JSON object of config values
+    pub payload: String,
+    /// Base64-encoded Ed25519 signature of the decoded payload
+    pub signature: String,
+}
+
+/// Cached config file contents
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedConfig {
+    config: SignedConfig,
+    fetched_at: u64,
+}
+
+/// Signed contents of a config envelope
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct ConfigPayload {
+    /// Version of the config, increasing with every published config
+    pub version: u64,
+    /// When the config expires (seconds since Unix epoch)
+    pub expires_at: u64,
+    /// Config values by key
+    pub values: Map<String, Value>,
+}
+
+impl ConfigPayload {
+    /// Check that the config can replace the current one
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current time (seconds since Unix epoch)
+    /// * `current_version` - Version of the current config, if any
+    ///
+    /// # Returns
+    ///
+    /// Returns a configuration error if the config expired or is older than
+    /// the current one.
+    pub fn check(&self, now: u64, current_version: Option<u64>) -> AppResult<()> {
+        if now >= self.expires_at {
+            return Err(AppError::Config(format!("Remote config {} expired", self.version)));
+        }
+        match current_version {
+            Some(current) if self.version < current => Err(AppError::Config(format!(
+                "Remote config downgrade rejected: {} is older than {}",
+                self.version, current
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A verified remote config
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteConfig {
+    /// Version of the config
+    pub version: u64,
+    /// Config values by key
+    pub values: Map<String, Value>,
+    /// When the config was fetched (seconds since Unix epoch)
+    pub fetched_at: u64,
+}
+
+/// Verify a signed config and decode its payload
+///
+/// # Arguments
+///
+/// * `config` - The signed config envelope
+/// * `public_key` - Raw 32-byte Ed25519 public key of the backend
+///
+/// # Returns
+///
+/// Returns the config payload, or a configuration error if the envelope is
+/// malformed or the signature does not match. Expiry and version are checked
+/// separately (`ConfigPayload::check`).
+pub fn verify(config: &SignedConfig, public_key: &[u8]) -> AppResult<ConfigPayload> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let payload = engine
+        .decode(&config.payload)
+        .map_err(|e| AppError::Config(format!("Invalid config payload encoding: {}", e)))?;
+    let signature = engine
+        .decode(&config.signature)
+        .map_err(|e| AppError::Config(format!("Invalid config signature encoding: {}", e)))?;
+
+    ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, public_key)
+        .verify(&payload, &signature)
+        .map_err(|_| AppError::Config("Invalid config signature".to_string()))?;
+
+    serde_json::from_slice(&payload).map_err(|e| AppError::Config(format!("Invalid config payload: {}", e)))
+}
+
+/// Backend config signing key, from the build environment
+fn public_key() -> AppResult<Vec<u8>> {
+    let key = constants::REMOTE_CONFIG_PUBLIC_KEY.ok_or_else(|| {
+        AppError::Config("Remote config disabled: no public key configured at build time".to_string())
+    })?;
+    base64::engine::general_purpose::STANDARD
+        .decode(key)
+        .map_err(|e| AppError::Config(format!("Invalid remote config public key: {}", e)))
+}
+
+/// Remote config state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct ConfigStore {
+    config: Mutex<Option<RemoteConfig>>,
+}
+
+impl ConfigStore {
+    /// Replace the current config
+    pub fn set(&self, config: RemoteConfig) {
+        if let Ok(mut current) = self.config.lock() {
+            *current = Some(config);
+        }
+    }
+
+    /// Version of the current config, if any
+    pub fn version(&self) -> Option<u64> {
+        self.config.lock().ok()?.as_ref().map(|config| config.version)
+    }
+
+    /// Raw value of a config key
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.config
+            .lock()
+            .ok()?
+            .as_ref()
+            .and_then(|config| config.values.get(key).cloned())
+    }
+
+    /// Typed value of a config key
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the key is missing or its value does not have the
+    /// expected type (logged as a warning).
+    pub fn get_typed<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.get(key)?;
+        serde_json::from_value(value)
+            .map_err(|e| tracing::warn!("Ignoring remote config key {}: {}", key, e))
+            .ok()
+    }
+
+    /// Typed value of a config key, or `default` if unavailable
+    pub fn get_or<T: DeserializeOwned>(&self, key: &str, default: T) -> T {
+        self.get_typed(key).unwrap_or(default)
+    }
+
+    /// Returns `true` if the current config is older than `ttl` (or missing)
+    pub fn is_stale(&self, now: u64, ttl: Duration) -> bool {
+        match self.config.lock() {
+            Ok(config) => config
+                .as_ref()
+                .is_none_or(|config| now.saturating_sub(config.fetched_at) >= ttl.as_secs()),
+            Err(_) => true,
+        }
+    }
+
+    /// Connectivity target, with remote overrides applied
+    pub fn connectivity_target(&self) -> ConnectivityTarget {
+        let default = ConnectivityTarget::default();
+        ConnectivityTarget {
+            host: self.get_or(keys::CONNECTIVITY_HOST, default.host),
+            port: self.get_or(keys::CONNECTIVITY_PORT, default.port),
+            timeout: self
+                .get_typed(keys::CONNECTIVITY_TIMEOUT_SECS)
+                .map(Duration::from_secs)
+                .unwrap_or(default.timeout),
+        }
+    }
+}
+
+fn cache_path(app: &AppHandle) -> AppResult<PathBuf> {
+    Ok(app.path().app_data_dir()?.join(constants::REMOTE_CONFIG_CACHE_FILE))
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn read_cache(path: &Path) -> Option<CachedConfig> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents)
+        .map_err(|e| tracing::warn!("Ignoring invalid remote config cache: {}", e))
+        .ok()
+}
+
+fn write_cache(path: &Path, cached: &CachedConfig) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string(cached)?)?;
+    Ok(())
+}
+
+/// Apply the current config to the native subsystems reading it
+pub fn apply(app: &AppHandle) {
+    // Also applies the session lock timeout
+    device_policy::evaluate(app);
+    feature_flags::refresh(app);
+    tls_pinning::refresh(app);
+}
+
+/// Load the cached config, if any, and apply it
+///
+/// Without a cache (fresh install), feature flags are still evaluated so the
+/// webview receives their default values.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if there is no cache, or a configuration error if the
+/// cached config could not be verified.
+pub fn load_cached(app: &AppHandle) -> AppResult<()> {
+    let Some(cached) = read_cache(&cache_path(app)?) else {
+        feature_flags::refresh(app);
+        return Ok(());
+    };
+    let payload = verify(&cached.config, &public_key()?)?;
+    payload.check(now_secs(), app.state::<ConfigStore>().version())?;
+
+    app.state::<ConfigStore>().set(RemoteConfig {
+        version: payload.version,
+        values: payload.values,
+        fetched_at: cached.fetched_at,
+    });
+    apply(app);
+    tracing::info!("Loaded cached remote config");
+    Ok(())
+}
+
+/// Fetch, verify, cache and apply the remote config
+async fn refresh(app: &AppHandle) -> AppResult<()> {
+    let public_key = public_key()?;
+    let config: SignedConfig = http::client()?
+        .get(http::endpoint(constants::REMOTE_CONFIG_ENDPOINT))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| AppError::Config(format!("Remote config fetch failed: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| AppError::Config(format!("Invalid remote config response: {}", e)))?;
+    let payload = verify(&config, &public_key)?;
+    payload.check(now_secs(), app.state::<ConfigStore>().version())?;
+
+    let cached = CachedConfig { config, fetched_at: now_secs() };
+    if let Err(e) = write_cache(&cache_path(app)?, &cached) {
+        tracing::warn!("Failed to write remote config cache: {}", e);
+    }
+
+    app.state::<ConfigStore>().set(RemoteConfig {
+        version: payload.version,
+        values: payload.values,
+        fetched_at: cached.fetched_at,
+    });
+    apply(app);
+    tracing::info!("Remote config {} refreshed", payload.version);
+    Ok(())
+}
+
+/// Spawn a remote config refresh if the current config is stale
+pub fn spawn_refresh(app: AppHandle) {
+    let ttl = Duration::from_secs(constants::REMOTE_CONFIG_TTL_SECS);
+    if !app.state::<ConfigStore>().is_stale(now_secs(), ttl) {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = refresh(&app).await {
+            tracing::warn!("{}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn key_pair() -> Ed25519KeyPair {
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    fn sign(key_pair: &Ed25519KeyPair, payload: &str) -> SignedConfig {
+        let engine = base64::engine::general_purpose::STANDARD;
+        SignedConfig {
+            payload: engine.encode(payload),
+            signature: engine.encode(key_pair.sign(payload.as_bytes())),
+        }
+    }
+
+    fn payload(values: &str) -> String {
+        format!(r#"{{"version": 2, "expires_at": 2000000000, "values": {}}}"#, values)
+    }
+
+    fn store_with(values: Value) -> ConfigStore {
+        let store = ConfigStore::default();
+        store.set(RemoteConfig {
+            version: 1,
+            values: serde_json::from_value(values).unwrap(),
+            fetched_at: 1_000,
+        });
+        store
+    }
+
+    #[test]
+    fn test_verify_valid_signature() {
+        let key_pair = key_pair();
+        let config = sign(&key_pair, &payload(r#"{"connectivity.port": 8443}"#));
+        let payload = verify(&config, key_pair.public_key().as_ref()).unwrap();
+        assert_eq!(payload.version, 2);
+        assert_eq!(payload.values.get("connectivity.port"), Some(&Value::from(8443)));
+    }
+
+    #[test]
+    fn test_verify_rejects_payload_without_version() {
+        let key_pair = key_pair();
+        let config = sign(&key_pair, r#"{"connectivity.port": 8443}"#);
+        assert!(verify(&config, key_pair.public_key().as_ref()).is_err());
+    }
+
+    #[test]
+    fn test_check_rejects_expired_and_older_configs() {
+        let payload = ConfigPayload { version: 2, expires_at: 2_000, values: Map::new() };
+        assert!(payload.check(1_000, None).is_ok());
+        assert!(payload.check(1_000, Some(2)).is_ok(), "Same config fetched again");
+        assert!(payload.check(1_000, Some(1)).is_ok());
+        assert!(payload.check(1_000, Some(3)).is_err(), "Downgrade");
+        assert!(payload.check(2_000, None).is_err(), "Expired");
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_payload() {
+        let key_pair = key_pair();
+        let mut config = sign(&key_pair, &payload(r#"{"connectivity.port": 8443}"#));
+        config.payload =
+            base64::engine::general_purpose::STANDARD.encode(payload(r#"{"connectivity.port": 80}"#));
+        assert!(verify(&config, key_pair.public_key().as_ref()).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_other_key() {
+        let config = sign(&key_pair(), &payload("{}"));
+        assert!(verify(&config, key_pair().public_key().as_ref()).is_err());
+    }
+
+    #[test]
+    fn test_get_typed() {
+        let store = store_with(serde_json::json!({ "a": 5, "b": "text" }));
+        assert_eq!(store.get_typed::<u64>("a"), Some(5));
+        assert_eq!(store.get_typed::<u64>("b"), None, "Wrong type is ignored");
+        assert_eq!(store.get_or("missing", 7u64), 7);
+    }
+
+    #[test]
+    fn test_connectivity_target_overrides() {
+        let store = store_with(serde_json::json!({
+            keys::CONNECTIVITY_HOST: "staging.elulib.com",
+            keys::CONNECTIVITY_TIMEOUT_SECS: 5,
+        }));
+        let target = store.connectivity_target();
+        assert_eq!(target.host, "staging.elulib.com");
+        assert_eq!(target.port, constants::CONNECTIVITY_PORT);
+        assert_eq!(target.timeout, Duration::from_secs(5));
+
+        assert_eq!(ConfigStore::default().connectivity_target(), ConnectivityTarget::default());
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let ttl = Duration::from_secs(100);
+        assert!(ConfigStore::default().is_stale(0, ttl));
+
+        let store = store_with(serde_json::json!({}));
+        assert!(!store.is_stale(1_050, ttl));
+        assert!(store.is_stale(1_100, ttl));
+    }
+}
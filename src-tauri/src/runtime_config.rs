@@ -0,0 +1,437 @@
+/// Runtime configuration file module
+///
+/// This module loads an optional TOML file overriding some constants at
+/// startup, so QA and on-prem deployments do not need a recompile for every
+/// environment tweak:
+///
+/// ```toml
+/// app_url = "https://staging.elulib.com"
+/// log_level = "debug"
+///
+/// [connectivity]
+/// host = "staging.elulib.com"
+/// port = 443
+/// timeout_secs = 5
+///
+/// [http]
+/// timeout_secs = 20
+///
+/// [log_redaction]
+/// patterns = ["\\bFR76(?: ?[0-9]){23}"]
+/// fields = ["iban"]
+/// ```
+///
+/// The defaults come from the environment profile (see `environment`). The
+/// file (`RUNTIME_CONFIG_FILE`) is looked up in the bundled resources and
+/// in the app config directory; values from the config directory take
+/// precedence. All fields are optional. An invalid file is rejected as a whole
+/// with an error naming the file and the field, and the built-in constants are
+/// used instead. Remote config values still take precedence over this file.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, Url};
+
+use crate::constants;
+use crate::environment::{self, Environment};
+use crate::{AppError, AppResult};
+
+/// Connectivity overrides
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectivityOverrides {
+    /// Host name targeted by connectivity checks
+    pub host: Option<String>,
+    /// TCP port targeted by connectivity checks
+    pub port: Option<u16>,
+    /// Timeout of each connectivity attempt, in seconds
+    pub timeout_secs: Option<u64>,
+}
+
+/// HTTP client overrides
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HttpOverrides {
+    /// Timeout of backend requests, in seconds
+    pub timeout_secs: Option<u64>,
+}
+
+/// Log redaction additions, on top of the built-in rules
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LogRedactionOverrides {
+    /// Additional regular expressions masked in log messages
+    pub patterns: Option<Vec<String>>,
+    /// Additional fields whose values are masked in log messages
+    pub fields: Option<Vec<String>>,
+}
+
+/// Contents of a runtime configuration file
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeConfigFile {
+    /// URL of the web application (also the base of backend endpoints)
+    pub app_url: Option<String>,
+    /// Base log level (`off`, `error`, `warn`, `info`, `debug`, `trace`)
+    pub log_level: Option<String>,
+    /// Connectivity check overrides
+    #[serde(default)]
+    pub connectivity: ConnectivityOverrides,
+    /// HTTP client overrides
+    #[serde(default)]
+    pub http: HttpOverrides,
+    /// Log redaction additions
+    #[serde(default)]
+    pub log_redaction: LogRedactionOverrides,
+}
+
+impl RuntimeConfigFile {
+    /// Parse a TOML configuration file
+    pub fn parse(contents: &str) -> AppResult<Self> {
+        toml::from_str(contents).map_err(|e| AppError::Config(e.to_string()))
+    }
+
+    /// Overlay `other` on top of this file, field by field
+    pub fn merge(self, other: RuntimeConfigFile) -> Self {
+        RuntimeConfigFile {
+            app_url: other.app_url.or(self.app_url),
+            log_level: other.log_level.or(self.log_level),
+            connectivity: ConnectivityOverrides {
+                host: other.connectivity.host.or(self.connectivity.host),
+                port: other.connectivity.port.or(self.connectivity.port),
+                timeout_secs: other.connectivity.timeout_secs.or(self.connectivity.timeout_secs),
+            },
+            http: HttpOverrides {
+                timeout_secs: other.http.timeout_secs.or(self.http.timeout_secs),
+            },
+            log_redaction: LogRedactionOverrides {
+                patterns: other.log_redaction.patterns.or(self.log_redaction.patterns),
+                fields: other.log_redaction.fields.or(self.log_redaction.fields),
+            },
+        }
+    }
+}
+
+/// Effective runtime configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeConfig {
+    /// Backend environment of the defaults
+    pub environment: Environment,
+    /// URL of the web application, without trailing slash
+    pub app_url: String,
+    /// Base log level, until changed with `set_log_level`
+    pub log_level: log::LevelFilter,
+    /// Host name targeted by connectivity checks
+    pub connectivity_host: String,
+    /// TCP port targeted by connectivity checks
+    pub connectivity_port: u16,
+    /// Timeout of each connectivity attempt
+    pub connectivity_timeout: Duration,
+    /// Timeout of backend requests
+    pub http_timeout: Duration,
+    /// Regular expressions masked in log messages, on top of the built-in ones
+    pub redaction_patterns: Vec<String>,
+    /// Fields whose values are masked in log messages, on top of the built-in ones
+    pub redaction_fields: Vec<String>,
+    /// Push sender identifier: the FCM sender the Android app registers for
+    /// push with (`sync::start`); APNs has no sender
+    pub push_sender_id: String,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        RuntimeConfig::for_environment(Environment::build())
+    }
+}
+
+impl RuntimeConfig {
+    /// Built-in configuration of an environment
+    pub fn for_environment(environment: Environment) -> Self {
+        let profile = environment.profile();
+        RuntimeConfig {
+            environment,
+            app_url: profile.app_url.to_string(),
+            log_level: constants::LOG_DEFAULT_LEVEL,
+            connectivity_host: profile.connectivity_host.to_string(),
+            connectivity_port: constants::CONNECTIVITY_PORT,
+            connectivity_timeout: Duration::from_secs(constants::CONNECTIVITY_TIMEOUT_SECS),
+            http_timeout: Duration::from_secs(constants::HTTP_TIMEOUT_SECS),
+            redaction_patterns: Vec::new(),
+            redaction_fields: Vec::new(),
+            push_sender_id: profile.push_sender_id.to_string(),
+        }
+    }
+}
+
+/// Validate a timeout value, in seconds
+fn timeout(field: &str, secs: u64) -> AppResult<Duration> {
+    if !(1..=constants::RUNTIME_CONFIG_MAX_TIMEOUT_SECS).contains(&secs) {
+        return Err(AppError::Config(format!(
+            "{} must be between 1 and {} seconds, got {}",
+            field,
+            constants::RUNTIME_CONFIG_MAX_TIMEOUT_SECS,
+            secs
+        )));
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+impl RuntimeConfig {
+    /// Validate a configuration file and apply it over the built-in constants
+    ///
+    /// # Returns
+    ///
+    /// Returns the effective configuration, or a configuration error naming
+    /// the first invalid field.
+    pub fn from_file(file: RuntimeConfigFile) -> AppResult<Self> {
+        RuntimeConfig::from_file_in(Environment::build(), file)
+    }
+
+    /// Validate a configuration file and apply it over the built-in
+    /// configuration of `environment`
+    pub fn from_file_in(environment: Environment, file: RuntimeConfigFile) -> AppResult<Self> {
+        let mut config = RuntimeConfig::for_environment(environment);
+
+        if let Some(app_url) = file.app_url {
+            let url = Url::parse(&app_url).map_err(|e| AppError::Config(format!("app_url is not a valid URL ({}): {}", e, app_url)))?;
+            if url.scheme() != "https" || url.host_str().is_none() {
+                return Err(AppError::Config(format!("app_url must be an https:// URL, got {}", app_url)));
+            }
+            config.app_url = app_url.trim_end_matches('/').to_string();
+        }
+        if let Some(level) = file.log_level {
+            config.log_level = level.parse().map_err(|_| {
+                AppError::Config(format!(
+                    "log_level must be one of off, error, warn, info, debug, trace, got {}",
+                    level
+                ))
+            })?;
+        }
+        if let Some(host) = file.connectivity.host {
+            if host.trim().is_empty() {
+                return Err(AppError::Config("connectivity.host must not be empty".to_string()));
+            }
+            config.connectivity_host = host;
+        }
+        if let Some(port) = file.connectivity.port {
+            if port == 0 {
+                return Err(AppError::Config("connectivity.port must be between 1 and 65535, got 0".to_string()));
+            }
+            config.connectivity_port = port;
+        }
+        if let Some(secs) = file.connectivity.timeout_secs {
+            config.connectivity_timeout = timeout("connectivity.timeout_secs", secs)?;
+        }
+        if let Some(secs) = file.http.timeout_secs {
+            config.http_timeout = timeout("http.timeout_secs", secs)?;
+        }
+        if let Some(patterns) = file.log_redaction.patterns {
+            for pattern in &patterns {
+                regex::Regex::new(pattern)
+                    .map_err(|e| AppError::Config(format!("log_redaction.patterns contains an invalid pattern {}: {}", pattern, e)))?;
+            }
+            config.redaction_patterns = patterns;
+        }
+        if let Some(fields) = file.log_redaction.fields {
+            if let Some(field) = fields.iter().find(|field| {
+                field.is_empty() || !field.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+            }) {
+                return Err(AppError::Config(format!("log_redaction.fields must be field names, got {:?}", field)));
+            }
+            config.redaction_fields = fields;
+        }
+        Ok(config)
+    }
+
+    /// Rebase a built-in URL (`https://app.elulib.com/...`) on the configured
+    /// application URL
+    pub fn rebase(&self, url: &str) -> String {
+        match url.strip_prefix(constants::APP_URL) {
+            Some(path) => format!("{}{}", self.app_url, path),
+            None => url.to_string(),
+        }
+    }
+}
+
+/// Runtime configuration of the current process
+static CONFIG: OnceLock<RuntimeConfig> = OnceLock::new();
+
+/// Effective runtime configuration (built-in constants until `load` is called)
+pub fn get() -> &'static RuntimeConfig {
+    CONFIG.get_or_init(RuntimeConfig::default)
+}
+
+/// Read a configuration file, if present
+fn read_file(path: &Path) -> AppResult<Option<RuntimeConfigFile>> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => toml::from_str(&contents)
+            .map(Some)
+            .map_err(|e| AppError::Config(format!("Invalid runtime config {}: {}", path.display(), e))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(AppError::Config(format!("Failed to read runtime config {}: {}", path.display(), e))),
+    }
+}
+
+/// Read, merge and validate the configuration files
+///
+/// # Returns
+///
+/// Returns the effective configuration (defaults of `environment` if there is
+/// no file), or a configuration error if a file is invalid.
+pub fn load_files(environment: Environment, paths: &[PathBuf]) -> AppResult<RuntimeConfig> {
+    let mut merged = RuntimeConfigFile::default();
+    for path in paths {
+        if let Some(file) = read_file(path)? {
+            tracing::info!("Applying runtime config {}", path.display());
+            merged = merged.merge(file);
+        }
+    }
+    RuntimeConfig::from_file_in(environment, merged)
+}
+
+/// Load the runtime configuration; must be called first in setup
+///
+/// Invalid files are logged and ignored. When the application URL differs
+/// from the production one (environment or file override), the main webview
+/// is navigated to it.
+pub fn load(app: &AppHandle) {
+    let environment = app
+        .path()
+        .app_config_dir()
+        .ok()
+        .and_then(|dir| environment::read_override(&dir))
+        .unwrap_or(Environment::build());
+    if environment != Environment::Prod {
+        tracing::info!("Using {} environment", environment.as_str());
+    }
+
+    let paths: Vec<PathBuf> = [app.path().resource_dir(), app.path().app_config_dir()]
+        .into_iter()
+        .filter_map(Result::ok)
+        .map(|dir| dir.join(constants::RUNTIME_CONFIG_FILE))
+        .collect();
+
+    let config = load_files(environment, &paths).unwrap_or_else(|e| {
+        tracing::error!("{}; using built-in configuration", e);
+        RuntimeConfig::for_environment(environment)
+    });
+    log::set_max_level(config.log_level);
+
+    if CONFIG.set(config).is_err() {
+        tracing::warn!("Runtime config already in use, ignoring configuration files");
+        return;
+    }
+
+    let config = get();
+    if config.app_url != constants::APP_URL {
+        tracing::info!("Using application URL {}", config.app_url);
+        let window = app.get_webview_window(constants::MAIN_WINDOW_LABEL);
+        let navigated = Url::parse(&config.app_url)
+            .map_err(|e| e.to_string())
+            .and_then(|url| match window {
+                Some(window) => window.navigate(url).map_err(|e| e.to_string()),
+                None => Err("main window not found".to_string()),
+            });
+        if let Err(e) = navigated {
+            tracing::error!("Failed to open {}: {}", config.app_url, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_file_keeps_defaults() {
+        let file = RuntimeConfigFile::parse("").unwrap();
+        assert_eq!(RuntimeConfig::from_file(file).unwrap(), RuntimeConfig::default());
+    }
+
+    #[test]
+    fn test_overrides_are_applied() {
+        let file = RuntimeConfigFile::parse(
+            r#"
+            app_url = "https://elulib.example.org/"
+            log_level = "debug"
+
+            [connectivity]
+            host = "elulib.example.org"
+            timeout_secs = 5
+
+            [http]
+            timeout_secs = 30
+
+            [log_redaction]
+            fields = ["iban"]
+            "#,
+        )
+        .unwrap();
+        let config = RuntimeConfig::from_file(file).unwrap();
+        assert_eq!(config.app_url, "https://elulib.example.org");
+        assert_eq!(config.log_level, log::LevelFilter::Debug);
+        assert_eq!(config.connectivity_host, "elulib.example.org");
+        assert_eq!(config.connectivity_port, constants::CONNECTIVITY_PORT);
+        assert_eq!(config.connectivity_timeout, Duration::from_secs(5));
+        assert_eq!(config.http_timeout, Duration::from_secs(30));
+        assert_eq!(config.redaction_fields, ["iban"]);
+        assert!(config.redaction_patterns.is_empty());
+        assert_eq!(
+            config.rebase(constants::ANALYTICS_ENDPOINT),
+            "https://elulib.example.org/api/mobile/events"
+        );
+    }
+
+    #[test]
+    fn test_invalid_values_are_rejected() {
+        let invalid = [
+            ("app_url = \"http://elulib.example.org\"", "app_url"),
+            ("log_level = \"verbose\"", "log_level"),
+            ("[connectivity]\nport = 0", "connectivity.port"),
+            ("[http]\ntimeout_secs = 0", "http.timeout_secs"),
+            ("[log_redaction]\npatterns = [\"(\"]", "log_redaction.patterns"),
+            ("[log_redaction]\nfields = [\"home address\"]", "log_redaction.fields"),
+        ];
+        for (contents, field) in invalid {
+            let file = RuntimeConfigFile::parse(contents).unwrap();
+            let error = RuntimeConfig::from_file(file).unwrap_err();
+            assert!(matches!(error, AppError::Config(_)));
+            assert!(error.to_string().contains(field), "{} should name {}", error, field);
+        }
+        assert!(RuntimeConfigFile::parse("app_ur = \"https://x\"").is_err(), "Unknown keys are rejected");
+        assert!(RuntimeConfigFile::parse("[connectivity]\nport = 70000").is_err());
+    }
+
+    #[test]
+    fn test_environment_defaults_and_overrides() {
+        let config = RuntimeConfig::from_file_in(Environment::Staging, RuntimeConfigFile::default()).unwrap();
+        assert_eq!(config.environment, Environment::Staging);
+        assert_eq!(config.app_url, constants::STAGING_APP_URL);
+        assert_eq!(config.connectivity_host, constants::STAGING_CONNECTIVITY_HOST);
+        assert_eq!(config.push_sender_id, constants::STAGING_PUSH_SENDER_ID);
+        assert_eq!(
+            config.rebase(constants::ANALYTICS_ENDPOINT),
+            format!("{}/api/mobile/events", constants::STAGING_APP_URL)
+        );
+
+        let file = RuntimeConfigFile::parse("app_url = \"https://elulib.example.org\"").unwrap();
+        let config = RuntimeConfig::from_file_in(Environment::Preprod, file).unwrap();
+        assert_eq!(config.app_url, "https://elulib.example.org", "The file takes precedence");
+        assert_eq!(config.connectivity_host, constants::PREPROD_CONNECTIVITY_HOST);
+    }
+
+    #[test]
+    fn test_config_dir_file_takes_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundled = dir.path().join("bundled.toml");
+        let local = dir.path().join("local.toml");
+        std::fs::write(&bundled, "log_level = \"warn\"\n[http]\ntimeout_secs = 20").unwrap();
+        std::fs::write(&local, "log_level = \"debug\"").unwrap();
+
+        let config = load_files(Environment::Prod, &[bundled, local, dir.path().join("missing.toml")]).unwrap();
+        assert_eq!(config.log_level, log::LevelFilter::Debug);
+        assert_eq!(config.http_timeout, Duration::from_secs(20));
+    }
+}
@@ -0,0 +1,136 @@
+/// Session token handoff module
+///
+/// The session token is stored in the keychain (`SESSION_TOKEN_KEY`) and is
+/// no longer readable through `keychain_retrieve`. Instead, the native layer
+/// hands it off to the webview through a one-time-use channel:
+/// - a handoff is opened whenever a page starts loading
+/// - the trusted frontend claims the token once with the
+///   `claim_session_token` command, first thing when its bundle runs; the
+///   native layer reads it from the keychain at that moment
+/// - later claims are rejected until the next page load, so scripts running
+///   after the frontend bundle cannot pull the token
+///
+/// The frontend still writes the token with `keychain_store` after login and
+/// removes it with `keychain_remove` on logout.
+
+use std::sync::Mutex;
+
+use tauri::AppHandle;
+use tauri_plugin_keystore::RetrieveRequest;
+
+use crate::constants::{self, helpers};
+use crate::plugins::{self, KeystoreExt};
+use crate::redact;
+use crate::{AppError, AppResult};
+
+/// Handoff state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct SessionHandoff {
+    open: Mutex<bool>,
+}
+
+impl SessionHandoff {
+    /// Open the handoff for a new page
+    pub fn open(&self) {
+        if let Ok(mut open) = self.open.lock() {
+            *open = true;
+        }
+    }
+
+    /// Consume the handoff of the current page
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` the first time after `open`, or an error.
+    pub fn take(&self) -> AppResult<()> {
+        let mut open = self.open.lock().map_err(|e| AppError::Keychain(e.to_string()))?;
+        if !*open {
+            return Err(AppError::Keychain("Session token already handed off for this page".to_string()));
+        }
+        *open = false;
+        Ok(())
+    }
+
+    /// Hand the session token off to the current page
+    ///
+    /// # Returns
+    ///
+    /// Returns the session token (`None` if no session is stored), or an error
+    /// message if it was already claimed or the keychain is unavailable.
+    pub fn claim(&self, app: &AppHandle) -> AppResult<Option<String>> {
+        self.take()?;
+        session_token(app)
+    }
+}
+
+/// Read the session token from the keychain
+///
+/// Only for native use (handoff, background sync): never return it to the
+/// frontend outside of the handoff.
+///
+/// # Returns
+///
+/// Returns the session token (`None` if no session is stored), or an error
+/// message if the keychain is unavailable.
+pub fn session_token(app: &AppHandle) -> AppResult<Option<String>> {
+    keychain_secret(app, constants::SESSION_TOKEN_KEY)
+}
+
+/// Read a secret from the keychain, for native use only
+///
+/// The value is registered with the log redaction.
+///
+/// # Returns
+///
+/// Returns the value stored for `key` (`None` if nothing or an empty value
+/// is stored), or an error if the keychain is unavailable.
+pub fn keychain_secret(app: &AppHandle, key: &str) -> AppResult<Option<String>> {
+    plugins::ensure_keystore(app)?;
+    let secret = app
+        .keystore()
+        .retrieve(RetrieveRequest {
+            service: key.to_string(),
+            user: key.to_string(),
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_retrieve_error(&e)))?
+        .value
+        .filter(|secret| !secret.is_empty());
+    if let Some(secret) = &secret {
+        redact::register_secret(secret);
+    }
+    Ok(secret)
+}
+
+/// Returns `true` if the frontend may read the key with `keychain_retrieve`
+///
+/// The session token, native login tokens (`auth` module) and the other
+/// reserved keys (`RESERVED_KEYCHAIN_KEYS`) are only used by the native layer.
+pub fn is_retrievable(key: &str) -> bool {
+    !helpers::is_reserved_keychain_key(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handoff_once_per_page() {
+        let handoff = SessionHandoff::default();
+        assert!(handoff.take().is_err(), "No page loaded yet");
+
+        handoff.open();
+        assert!(handoff.take().is_ok());
+        assert!(handoff.take().is_err(), "Already claimed");
+
+        handoff.open();
+        assert!(handoff.take().is_ok(), "A new page can claim again");
+    }
+
+    #[test]
+    fn test_session_token_is_not_retrievable() {
+        assert!(!is_retrievable(constants::SESSION_TOKEN_KEY));
+        assert!(!is_retrievable("auth.session.h1"));
+        assert!(!is_retrievable(constants::DATABASE_KEY_NAME));
+        assert!(is_retrievable("refresh_hint"));
+    }
+}
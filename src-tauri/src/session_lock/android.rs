@@ -0,0 +1,51 @@
+/// Android-specific session lock screen implementation
+///
+/// This module covers the webview with an opaque native view while the
+/// session is locked, so no content is visible until the user re-authenticates.
+///
+/// Note: This implementation provides the structure for the Android lock screen.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Show the lock screen above the webview
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn show_lock_screen() -> AppResult<()> {
+    tracing::info!("[Android] Showing lock screen");
+
+    // TODO: Implement native Android lock screen
+    // Example Kotlin implementation:
+    // ```kotlin
+    // activity.window.setFlags(WindowManager.LayoutParams.FLAG_SECURE, WindowManager.LayoutParams.FLAG_SECURE)
+    // lockView = LockScreenView(activity) // logo + "Unlock" button
+    // (activity.window.decorView as ViewGroup).addView(lockView)
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
+
+/// Remove the lock screen
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn hide_lock_screen() -> AppResult<()> {
+    tracing::info!("[Android] Hiding lock screen");
+
+    // TODO: Implement native Android lock screen removal
+    // Example Kotlin implementation:
+    // ```kotlin
+    // (activity.window.decorView as ViewGroup).removeView(lockView)
+    // activity.window.clearFlags(WindowManager.LayoutParams.FLAG_SECURE)
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
@@ -0,0 +1,50 @@
+/// iOS-specific session lock screen implementation
+///
+/// This module covers the webview with an opaque native view while the
+/// session is locked, so no content is visible until the user re-authenticates.
+///
+/// Note: This implementation provides the structure for the iOS lock screen.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Show the lock screen above the webview
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn show_lock_screen() -> AppResult<()> {
+    tracing::info!("[iOS] Showing lock screen");
+
+    // TODO: Implement native iOS lock screen
+    // Example Swift implementation:
+    // ```swift
+    // let lockView = LockScreenView(frame: window.bounds) // logo + "Unlock" button
+    // lockView.tag = LOCK_VIEW_TAG
+    // window.addSubview(lockView)
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
+
+/// Remove the lock screen
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn hide_lock_screen() -> AppResult<()> {
+    tracing::info!("[iOS] Hiding lock screen");
+
+    // TODO: Implement native iOS lock screen removal
+    // Example Swift implementation:
+    // ```swift
+    // window.viewWithTag(LOCK_VIEW_TAG)?.removeFromSuperview()
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
@@ -0,0 +1,393 @@
+/// Inactivity auto-lock with re-authentication
+///
+/// After `SESSION_LOCK_TIMEOUT_SECS` of inactivity in foreground, or when the
+/// app returns from a background period at least that long, the session is
+/// locked: the webview is covered by a native lock screen and a
+/// `session://locked` event is emitted. Content is revealed again only after
+/// the user re-authenticates with biometrics or the device passcode
+/// (`unlock_session` command).
+///
+/// While the session is locked, the secrets protecting cached content are not
+/// released: the encrypted database is closed (its key dropped from memory),
+/// the ephemeral secrets are wiped and commands reading keychain-held secrets (`LOCK_GATED_COMMANDS`) are
+/// rejected until a successful biometric unlock.
+///
+/// The frontend reports user activity with the `session_activity` command.
+/// The lock is disabled on desktop builds, which have no lock screen, and can
+/// be turned off remotely with the `session_lock` feature flag.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::ipc::Invoke;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::audit_log::{self, AuthMethod, SecurityEvent};
+use crate::biometrics;
+use crate::breadcrumbs;
+use crate::constants;
+use crate::database::Database;
+use crate::ephemeral;
+use crate::events::{self, SessionLocked, SessionUnlocked};
+use crate::feature_flags;
+use crate::locale;
+#[cfg(feature = "module-sse")]
+use crate::sse;
+use crate::vault;
+#[cfg(feature = "module-watch")]
+use crate::watch;
+#[cfg(feature = "module-realtime")]
+use crate::websocket;
+use crate::{AppError, AppResult};
+
+/// Commands releasing keychain-held secrets, rejected while the session is locked
+pub const LOCK_GATED_COMMANDS: &[&str] = &[
+    "keychain_retrieve",
+    "claim_session_token",
+    "db_execute",
+    "db_query",
+    "db_query_packed",
+    "ephemeral_get",
+    "vault_read",
+    "vault_open",
+    "draft_save",
+    "draft_load",
+    "draft_delete",
+    "outbox_ready",
+    "outbox_ack",
+    "export_user_data",
+    "upload_enqueue",
+    "upload_retry",
+];
+
+/// Why the session locked, sent with the `session://locked` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum LockReason {
+    /// No user activity in foreground for the timeout
+    Idle,
+    /// The app spent longer than the timeout in background
+    Background,
+}
+
+/// Mutable state of the session lock
+#[derive(Debug)]
+struct LockState {
+    enabled: bool,
+    locked: bool,
+    timeout: Duration,
+    last_activity: Instant,
+}
+
+/// Session lock state, managed by the Tauri application
+#[derive(Debug)]
+pub struct SessionLock {
+    state: Mutex<LockState>,
+}
+
+impl Default for SessionLock {
+    fn default() -> Self {
+        SessionLock::new(
+            cfg!(any(target_os = "ios", target_os = "android")),
+            Duration::from_secs(constants::SESSION_LOCK_TIMEOUT_SECS),
+            Instant::now(),
+        )
+    }
+}
+
+impl SessionLock {
+    /// Create a session lock
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether the session can lock at all
+    /// * `timeout` - Inactivity / background delay before locking
+    /// * `now` - Time of the last user activity
+    pub fn new(enabled: bool, timeout: Duration, now: Instant) -> Self {
+        SessionLock {
+            state: Mutex::new(LockState {
+                enabled,
+                locked: false,
+                timeout,
+                last_activity: now,
+            }),
+        }
+    }
+
+    /// Returns `true` if the session is locked
+    pub fn is_locked(&self) -> bool {
+        self.state.lock().map(|state| state.locked).unwrap_or(false)
+    }
+
+    /// Change the inactivity / background delay before locking
+    pub fn set_timeout(&self, timeout: Duration) {
+        if let Ok(mut state) = self.state.lock() {
+            state.timeout = timeout;
+        }
+    }
+
+    /// Record user activity at `now`
+    pub fn record_activity(&self, now: Instant) {
+        if let Ok(mut state) = self.state.lock() {
+            state.last_activity = now;
+        }
+    }
+
+    /// Lock the session if the user has been inactive for longer than the timeout
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if this call locked the session.
+    pub fn lock_if_idle(&self, now: Instant) -> bool {
+        self.lock_if(|state| now.saturating_duration_since(state.last_activity) >= state.timeout)
+    }
+
+    /// Lock the session if the app spent longer than the timeout in background
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if this call locked the session.
+    pub fn lock_after_background(&self, away: Duration) -> bool {
+        self.lock_if(|state| away >= state.timeout)
+    }
+
+    /// Mark the session as unlocked, counting the unlock as activity at `now`
+    pub fn unlock(&self, now: Instant) {
+        if let Ok(mut state) = self.state.lock() {
+            state.locked = false;
+            state.last_activity = now;
+        }
+    }
+
+    fn lock_if(&self, condition: impl FnOnce(&LockState) -> bool) -> bool {
+        match self.state.lock() {
+            Ok(mut state) if state.enabled && !state.locked && condition(&state) => {
+                state.locked = true;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Cover the webview with the native lock screen, drop the cached content
+/// key and the ephemeral secrets, close the realtime connection and the
+/// event streams, empty the watch context, and notify the frontend
+fn on_locked(app: &AppHandle, reason: LockReason) {
+    tracing::info!("Session locked ({:?})", reason);
+    breadcrumbs::breadcrumb("session", &format!("locked ({:?})", reason));
+    if let Err(e) = show_lock_screen() {
+        tracing::error!("Failed to show lock screen: {}", e);
+    }
+    app.state::<Database>().close();
+    ephemeral::wipe(app);
+    vault::clear_views(app);
+    #[cfg(feature = "module-realtime")]
+    if let Err(e) = websocket::close(app) {
+        tracing::warn!("Failed to close the realtime connection: {}", e);
+    }
+    #[cfg(feature = "module-sse")]
+    sse::unsubscribe_all(app);
+    #[cfg(feature = "module-watch")]
+    watch::clear(app);
+    events::emit(app, &SessionLocked { reason });
+}
+
+/// Check that a command may run given the lock state
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command does not release secrets or the session is
+/// unlocked, or an error.
+pub fn check(command: &str, locked: bool) -> AppResult<()> {
+    if locked && LOCK_GATED_COMMANDS.contains(&command) {
+        return Err(AppError::Denied(format!("Command {} rejected: session locked", command)));
+    }
+    Ok(())
+}
+
+/// Validate an IPC invocation against the lock state
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the command may run, or an error to reject the
+/// invocation with.
+pub fn check_invoke<R: Runtime>(invoke: &Invoke<R>) -> AppResult<()> {
+    let command = invoke.message.command();
+    if !LOCK_GATED_COMMANDS.contains(&command) {
+        return Ok(());
+    }
+    let locked = invoke.message.webview_ref().app_handle().state::<SessionLock>().is_locked();
+    let result = check(command, locked);
+    if let Err(e) = &result {
+        tracing::warn!("{}", e);
+    }
+    result
+}
+
+/// Lock the session after a background period of `away`, if long enough
+///
+/// Called by the lifecycle module when the app returns to the foreground.
+pub fn on_resume(app: &AppHandle, away: Duration) {
+    if !feature_flags::is_enabled(app, feature_flags::SESSION_LOCK) {
+        return;
+    }
+    if app.state::<SessionLock>().lock_after_background(away) {
+        on_locked(app, LockReason::Background);
+    }
+}
+
+/// Spawn a background task locking the session after inactivity
+pub fn spawn_idle_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            constants::SESSION_LOCK_CHECK_INTERVAL_SECS,
+        ));
+        loop {
+            interval.tick().await;
+            if !feature_flags::is_enabled(&app, feature_flags::SESSION_LOCK) {
+                continue;
+            }
+            if app.state::<SessionLock>().lock_if_idle(Instant::now()) {
+                on_locked(&app, LockReason::Idle);
+            }
+        }
+    });
+}
+
+/// Re-authenticate the user and unlock the session
+///
+/// # Returns
+///
+/// Returns `true` if the session is unlocked (or was not locked), `false` if
+/// authentication failed, or an error if authentication is unavailable.
+pub fn unlock(app: &AppHandle) -> AppResult<bool> {
+    let lock = app.state::<SessionLock>();
+    if !lock.is_locked() {
+        return Ok(true);
+    }
+
+    let authenticated = biometrics::authenticate(locale::strings().session_unlock_reason)?;
+    audit_log::record(
+        app,
+        SecurityEvent::AuthAttempt {
+            method: AuthMethod::SessionUnlock,
+            success: authenticated,
+        },
+    );
+    if !authenticated {
+        tracing::warn!("Session unlock failed: authentication rejected");
+        return Ok(false);
+    }
+
+    lock.unlock(Instant::now());
+    hide_lock_screen()?;
+    tracing::info!("Session unlocked");
+    breadcrumbs::breadcrumb("session", "unlocked");
+    events::emit(app, &SessionUnlocked);
+    Ok(true)
+}
+
+/// Show the native lock screen above the webview
+fn show_lock_screen() -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::show_lock_screen()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::show_lock_screen()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(())
+    }
+}
+
+/// Remove the native lock screen
+fn hide_lock_screen() -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::hide_lock_screen()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::hide_lock_screen()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(300);
+
+    #[test]
+    fn test_lock_if_idle() {
+        let start = Instant::now();
+        let lock = SessionLock::new(true, TIMEOUT, start);
+
+        assert!(!lock.lock_if_idle(start + Duration::from_secs(299)));
+        lock.record_activity(start + Duration::from_secs(200));
+        assert!(!lock.lock_if_idle(start + Duration::from_secs(400)), "Activity should postpone the lock");
+        assert!(lock.lock_if_idle(start + Duration::from_secs(500)));
+        assert!(lock.is_locked());
+        assert!(!lock.lock_if_idle(start + Duration::from_secs(900)), "Already locked");
+    }
+
+    #[test]
+    fn test_lock_after_background() {
+        let lock = SessionLock::new(true, TIMEOUT, Instant::now());
+        assert!(!lock.lock_after_background(Duration::from_secs(30)));
+        assert!(lock.lock_after_background(Duration::from_secs(300)));
+    }
+
+    #[test]
+    fn test_unlock_resets_activity() {
+        let start = Instant::now();
+        let lock = SessionLock::new(true, TIMEOUT, start);
+        assert!(lock.lock_after_background(TIMEOUT));
+
+        lock.unlock(start + Duration::from_secs(1000));
+        assert!(!lock.is_locked());
+        assert!(!lock.lock_if_idle(start + Duration::from_secs(1100)));
+    }
+
+    #[test]
+    fn test_disabled_lock_never_locks() {
+        let start = Instant::now();
+        let lock = SessionLock::new(false, TIMEOUT, start);
+        assert!(!lock.lock_if_idle(start + Duration::from_secs(10_000)));
+        assert!(!lock.lock_after_background(Duration::from_secs(10_000)));
+    }
+
+    #[test]
+    fn test_locked_session_gates_secrets() {
+        assert!(check("claim_session_token", true).is_err());
+        assert!(check("db_query", true).is_err());
+        assert!(check("db_query", false).is_ok());
+        assert!(check("unlock_session", true).is_ok());
+        assert!(check("keychain_store", true).is_ok(), "Writing does not release secrets");
+    }
+
+    #[test]
+    fn test_set_timeout() {
+        let lock = SessionLock::new(true, TIMEOUT, Instant::now());
+        lock.set_timeout(Duration::from_secs(60));
+        assert!(lock.lock_after_background(Duration::from_secs(60)));
+    }
+}
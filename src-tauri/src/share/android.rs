@@ -0,0 +1,64 @@
+/// Android-specific share sheet
+///
+/// This module starts an `ACTION_SEND` chooser for a file. The file is exposed
+/// through a `content://` URI of the app `FileProvider`, with a temporary read
+/// permission granted to the receiving app.
+///
+/// The manifest must declare the provider, covering the `exports` directory of
+/// the cache:
+///
+/// ```xml
+/// <provider
+///     android:name="androidx.core.content.FileProvider"
+///     android:authorities="${applicationId}.fileprovider"
+///     android:exported="false"
+///     android:grantUriPermissions="true">
+///     <meta-data
+///         android:name="android.support.FILE_PROVIDER_PATHS"
+///         android:resource="@xml/file_paths" />
+/// </provider>
+/// <!-- res/xml/file_paths.xml: <paths><cache-path name="exports" path="exports/" /></paths> -->
+/// ```
+///
+/// Note: This implementation provides the structure for the Android share sheet.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use std::path::Path;
+
+use crate::constants;
+use crate::AppResult;
+
+/// Start the share chooser for a file
+///
+/// # Arguments
+///
+/// * `path` - File to share, in the `exports` directory of the cache
+/// * `mime_type` - MIME type of the file
+/// * `subject` - Subject used by email clients
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the chooser is started, or an error if the
+/// operation fails.
+pub fn share_file(path: &Path, mime_type: &str, subject: &str) -> AppResult<()> {
+    tracing::info!("[Android] Starting share chooser ({})", mime_type);
+
+    // TODO: Implement native Android share chooser via JNI
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val uri = FileProvider.getUriForFile(activity, SHARE_FILE_PROVIDER_AUTHORITY, File(path))
+    // val send = Intent(Intent.ACTION_SEND).apply {
+    //     type = mimeType
+    //     putExtra(Intent.EXTRA_STREAM, uri)
+    //     putExtra(Intent.EXTRA_SUBJECT, subject)
+    //     addFlags(Intent.FLAG_GRANT_READ_URI_PERMISSION)
+    // }
+    // activity.startActivity(Intent.createChooser(send, null))
+    // ```
+
+    // Placeholder: Log the operation
+    // Replace this with actual native implementation
+    let _ = (path, subject, constants::SHARE_FILE_PROVIDER_AUTHORITY);
+    Ok(())
+}
@@ -0,0 +1,48 @@
+/// Native share sheet
+///
+/// This module hands a file to the system share sheet (`UIActivityViewController`
+/// on iOS, an `ACTION_SEND` chooser on Android), so the user can send it by
+/// email, messaging or save it to files.
+///
+/// The file must live in the app cache directory: on Android it is exposed to
+/// the receiving app through the `FileProvider` declared in the manifest
+/// (`SHARE_FILE_PROVIDER_AUTHORITY`), with a temporary read grant.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::path::Path;
+use crate::{AppError, AppResult};
+
+/// Present the share sheet for a file
+///
+/// # Arguments
+///
+/// * `path` - File to share, in the app cache directory
+/// * `mime_type` - MIME type of the file
+/// * `subject` - Subject used by email clients
+///
+/// # Returns
+///
+/// Returns `Ok(())` once the share sheet is presented, or an error if
+/// sharing is unavailable.
+pub fn share_file(path: &Path, mime_type: &str, subject: &str) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::share_file(path, mime_type, subject)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::share_file(path, mime_type, subject)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = (path, mime_type, subject);
+        Err(AppError::Platform("Sharing not supported on this platform".to_string()))
+    }
+}
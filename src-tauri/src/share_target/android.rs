@@ -0,0 +1,62 @@
+/// Android-specific share target plumbing
+///
+/// The main activity declares intent filters for the files it accepts, so
+/// the app is listed in the share sheet and in the "Open with" choosers:
+///
+/// ```xml
+/// <intent-filter>
+///     <action android:name="android.intent.action.SEND" />
+///     <category android:name="android.intent.category.DEFAULT" />
+///     <data android:mimeType="application/pdf" />
+///     <data android:mimeType="image/*" />
+/// </intent-filter>
+/// <intent-filter>
+///     <action android:name="android.intent.action.VIEW" />
+///     <category android:name="android.intent.category.DEFAULT" />
+///     <data android:scheme="content" />
+///     <data android:mimeType="application/pdf" />
+///     <data android:mimeType="image/*" />
+/// </intent-filter>
+/// ```
+///
+/// Note: This implementation provides the structure for the Android share target.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use tauri::AppHandle;
+use crate::AppResult;
+
+/// Start forwarding shared files on Android
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start(app: AppHandle) -> AppResult<()> {
+    tracing::info!("[Android] Starting share target intent forwarding");
+
+    // TODO: Implement native Android share intent forwarding
+    // Each shared file must be copied to the `shared-inbox` directory of the
+    // cache and forwarded to `share_target::on_shared_file` (off the main
+    // thread), which deletes the copy.
+    // Example Kotlin implementation (called from onCreate and onNewIntent):
+    // ```kotlin
+    // private fun forwardShare(intent: Intent) {
+    //     val uri = when (intent.action) {
+    //         Intent.ACTION_SEND -> intent.getParcelableExtra(Intent.EXTRA_STREAM, Uri::class.java)
+    //         Intent.ACTION_VIEW -> intent.data?.takeIf { it.scheme == "content" }
+    //         else -> null
+    //     } ?: return
+    //     val name = contentResolver.query(uri, arrayOf(OpenableColumns.DISPLAY_NAME), null, null, null)
+    //         ?.use { if (it.moveToFirst()) it.getString(0) else null } ?: "shared"
+    //     val inbox = File(cacheDir, "shared-inbox").apply { mkdirs() }
+    //     val copy = File.createTempFile("shared", null, inbox)
+    //     contentResolver.openInputStream(uri)?.use { input -> copy.outputStream().use { input.copyTo(it) } }
+    //     onSharedFile(copy.absolutePath, name)
+    // }
+    // ```
+
+    // Placeholder: Return success without forwarding intents
+    // Replace this with actual native implementation
+    let _ = app;
+    Ok(())
+}
@@ -0,0 +1,20 @@
+/// iOS-specific share target plumbing
+///
+/// The app declares the document types it accepts in `Info.plist`
+/// (`CFBundleDocumentTypes`: `com.adobe.pdf` and `public.image`, with
+/// `LSSupportsOpeningDocumentsInPlace` disabled), so it is listed in the
+/// "Copy to" actions of the share sheet. The system copies the file into the
+/// `Documents/Inbox` directory of the app and opens its `file://` URL, which
+/// Tauri delivers as `RunEvent::Opened`.
+
+use crate::AppResult;
+
+/// Start receiving shared files on iOS
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start() -> AppResult<()> {
+    tracing::info!("[iOS] Shared files are received through RunEvent::Opened");
+    Ok(())
+}
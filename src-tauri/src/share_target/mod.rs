@@ -0,0 +1,306 @@
+/// Share target
+///
+/// The app is registered as a target for PDF documents and images shared
+/// from other apps (Files, Mail, Photos), so the frontend can attach them to
+/// a dossier:
+/// - iOS: document types of `Info.plist` ("Copy to élulib"); the system
+///   copies the file into the app inbox (`Documents/Inbox`) and opens its
+///   `file://` URL, delivered by `RunEvent::Opened`
+/// - Android: `ACTION_SEND` / `ACTION_VIEW` intent filters; the activity
+///   copies the shared `content://` stream to the inbox of the cache
+///   directory (`SHARED_INBOX_DIR`) and forwards the copy (see `android`)
+///
+/// Only files inside the inbox are accepted, and deleted once handled: a
+/// `file://` URL pointing anywhere else (a file of the user opened with the
+/// app on a desktop build, ...) is left alone. The copy runs on a blocking
+/// thread, off the run-event loop.
+///
+/// Each received file is:
+/// - checked: only PDF documents and images are accepted, the type being
+///   sniffed from the first bytes (`attachments::sniff`), and at most
+///   `FILE_STORAGE_MAX_FILE_BYTES`
+/// - copied into the app file storage, under `SHARED_FILES_DIR` (with its
+///   quota), then the inbox / cache copy is deleted
+/// - announced with a `share://received` event carrying the storage path
+///   (the handle for `file_read`, `open_attachment` and `file_delete`),
+///   posted through the `outbox` so a file shared while the app was not
+///   running reaches the page once it is ready
+///
+/// The frontend deletes the file once attached to the dossier, or dismissed.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Url};
+
+use crate::attachments::{self, AttachmentKind};
+use crate::constants;
+use crate::file_storage;
+use crate::outbox;
+use crate::{AppError, AppResult};
+
+/// File shared with the app, payload of `share://received`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedFile {
+    /// Path in the app file storage (`shared/1700000000000-minutes.pdf`)
+    pub path: String,
+    /// Name of the file, as shared
+    pub name: String,
+    /// Detected MIME type
+    pub mime_type: String,
+    /// Size in bytes
+    pub size: u64,
+    /// When the file was received (milliseconds since the Unix epoch)
+    pub received_at: u64,
+}
+
+/// File name safe for the app file storage
+///
+/// Keeps letters, digits, `.`, `-` and `_` (other characters are replaced
+/// with `_`), at most `SHARED_FILE_MAX_NAME_LENGTH` characters.
+pub fn sanitize_name(name: &str) -> String {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect();
+    let sanitized = sanitized.trim_start_matches('.');
+    let len = sanitized.chars().count();
+    let sanitized: String = sanitized
+        .chars()
+        .skip(len.saturating_sub(constants::SHARED_FILE_MAX_NAME_LENGTH))
+        .collect();
+    if sanitized.is_empty() {
+        "shared".to_string()
+    } else {
+        sanitized
+    }
+}
+
+/// Copy a shared file into the app file storage
+///
+/// # Arguments
+///
+/// * `root` - Storage directory
+/// * `source` - File received from the system
+/// * `name` - Name of the file, as shared
+/// * `received_at` - Reception time (milliseconds since the Unix epoch),
+///   prefixed to the stored name
+///
+/// # Returns
+///
+/// Returns the `SharedFile`, or an error if the file is not a PDF
+/// document or an image, is too large or exceeds the quota.
+pub fn receive(root: &Path, source: &Path, name: &str, received_at: u64) -> AppResult<SharedFile> {
+    let size = std::fs::metadata(source)
+        .map_err(|e| AppError::Platform(format!("Failed to read shared file: {}", e)))?
+        .len();
+    if size > constants::FILE_STORAGE_MAX_FILE_BYTES {
+        return Err(AppError::InvalidArgument(format!(
+            "Shared files must be at most {} bytes, got {}",
+            constants::FILE_STORAGE_MAX_FILE_BYTES,
+            size
+        )));
+    }
+    let name = sanitize_name(name);
+    let extension = Path::new(&name).extension().and_then(|extension| extension.to_str());
+    let attachment_type = attachments::sniff(&attachments::read_header(source)?, extension)
+        .filter(|detected| matches!(detected.kind, AttachmentKind::Pdf | AttachmentKind::Image))
+        .ok_or_else(|| AppError::Platform("Only PDF documents and images can be shared with the app".to_string()))?;
+
+    let path = format!("{}/{}-{}", constants::SHARED_FILES_DIR, received_at, name);
+    let target = file_storage::prepare_write(root, &path, size)?;
+    let parent = target.parent().ok_or_else(|| AppError::InvalidArgument("Invalid file path".to_string()))?;
+    std::fs::create_dir_all(parent).map_err(|e| AppError::Platform(format!("Failed to create directory: {}", e)))?;
+    let temp = file_storage::temp_path(&target);
+    std::fs::copy(source, &temp)
+        .and_then(|_| std::fs::rename(&temp, &target))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&temp);
+            AppError::Platform(format!("Failed to copy shared file: {}", e))
+        })?;
+
+    Ok(SharedFile {
+        path,
+        name,
+        mime_type: attachment_type.mime_type.to_string(),
+        size,
+        received_at,
+    })
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Directory the system copies shared files into
+///
+/// # Returns
+///
+/// Returns `None` on platforms without a share target.
+pub fn inbox_dir(app: &AppHandle) -> Option<PathBuf> {
+    #[cfg(target_os = "ios")]
+    {
+        use tauri::Manager;
+        app.path().document_dir().ok().map(|dir| dir.join("Inbox"))
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        use tauri::Manager;
+        app.path()
+            .app_cache_dir()
+            .ok()
+            .map(|dir| dir.join(constants::SHARED_INBOX_DIR))
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = app;
+        None
+    }
+}
+
+/// Whether `path` is a file inside `inbox` (symbolic links resolved)
+pub fn is_in_inbox(path: &Path, inbox: &Path) -> bool {
+    match (path.canonicalize(), inbox.canonicalize()) {
+        (Ok(path), Ok(inbox)) => path != inbox && path.starts_with(inbox),
+        _ => false,
+    }
+}
+
+/// Handle a file shared with the app
+///
+/// The file is copied into the app file storage and announced to the page;
+/// `source`, a copy made by the system in the inbox, is deleted in any case.
+/// Files outside the inbox are rejected, and never deleted.
+///
+/// # Arguments
+///
+/// * `app` - The app handle
+/// * `source` - File received from the system, in `inbox_dir`
+/// * `name` - Name of the file, as shared (the name of `source` if `None`)
+pub fn on_shared_file(app: &AppHandle, source: &Path, name: Option<&str>) -> AppResult<SharedFile> {
+    if !inbox_dir(app).is_some_and(|inbox| is_in_inbox(source, &inbox)) {
+        tracing::warn!("Ignoring shared file outside of the inbox");
+        return Err(AppError::Platform("Shared file outside of the inbox".to_string()));
+    }
+    let name = name
+        .map(str::to_string)
+        .unwrap_or_else(|| source.file_name().unwrap_or_default().to_string_lossy().into_owned());
+    let received = file_storage::root(app).and_then(|root| receive(&root, source, &name, now_millis()));
+    if let Err(e) = std::fs::remove_file(source) {
+        tracing::debug!("Failed to delete received file: {}", e);
+    }
+    let shared = received.map_err(|e| {
+        tracing::warn!("Rejected shared file: {}", e);
+        e
+    })?;
+    tracing::info!("Received shared file ({}, {} bytes)", shared.mime_type, shared.size);
+    outbox::post(app, &shared);
+    Ok(shared)
+}
+
+/// Handle a `file://` URL opened by the system (iOS document types)
+///
+/// The file is handled on a blocking thread.
+///
+/// # Returns
+///
+/// Returns `false` if the URL is not a file URL of the inbox.
+pub fn on_open_url(app: &AppHandle, url: &Url) -> bool {
+    if url.scheme() != "file" {
+        return false;
+    }
+    let Ok(path) = url.to_file_path() else {
+        return false;
+    };
+    if !inbox_dir(app).is_some_and(|inbox| is_in_inbox(&path, &inbox)) {
+        return false;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let _ = on_shared_file(&app, &path, None);
+    });
+    true
+}
+
+/// Start receiving files shared with the app
+pub fn start(app: &AppHandle) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        // Files are delivered through `RunEvent::Opened`
+        let _ = app;
+        ios::start()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::start(app.clone())
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = app;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(sanitize_name("Procès-verbal 12.pdf"), "Procès-verbal_12.pdf");
+        assert_eq!(sanitize_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_name(".hidden"), "hidden");
+        assert_eq!(sanitize_name(""), "shared");
+        let long = format!("{}.pdf", "a".repeat(constants::SHARED_FILE_MAX_NAME_LENGTH));
+        assert_eq!(sanitize_name(&long).chars().count(), constants::SHARED_FILE_MAX_NAME_LENGTH);
+        assert!(sanitize_name(&long).ends_with(".pdf"), "Extension kept");
+    }
+
+    #[test]
+    fn test_only_inbox_files_are_accepted() {
+        let dir = tempfile::tempdir().unwrap();
+        let inbox = dir.path().join("Inbox");
+        std::fs::create_dir_all(&inbox).unwrap();
+        let shared = inbox.join("minutes.pdf");
+        let own = dir.path().join("minutes.pdf");
+        std::fs::write(&shared, b"%PDF-1.7").unwrap();
+        std::fs::write(&own, b"%PDF-1.7").unwrap();
+
+        assert!(is_in_inbox(&shared, &inbox));
+        assert!(!is_in_inbox(&own, &inbox), "User file outside of the inbox");
+        assert!(!is_in_inbox(&inbox.join("../minutes.pdf"), &inbox));
+        assert!(!is_in_inbox(&inbox, &inbox));
+        assert!(!is_in_inbox(&inbox.join("missing.pdf"), &inbox));
+    }
+
+    #[test]
+    fn test_receive_copies_pdf_and_images_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("files");
+        let source = dir.path().join("inbox.pdf");
+        std::fs::write(&source, b"%PDF-1.7\n...").unwrap();
+
+        let shared = receive(&root, &source, "Ordre du jour.pdf", 1_700_000_000_000).unwrap();
+        assert_eq!(shared.path, "shared/1700000000000-Ordre_du_jour.pdf");
+        assert_eq!(shared.mime_type, "application/pdf");
+        assert_eq!(file_storage::read(&root, &shared.path).unwrap(), b"%PDF-1.7\n...");
+
+        let text = dir.path().join("notes.txt");
+        std::fs::write(&text, b"plain text").unwrap();
+        assert!(receive(&root, &text, "notes.txt", 1).is_err());
+    }
+}
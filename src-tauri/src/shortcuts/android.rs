@@ -0,0 +1,72 @@
+/// Android-specific App Actions plumbing
+///
+/// The shortcuts are declared in `res/xml/shortcuts.xml` (referenced by the
+/// main activity `<meta-data android:name="android.app.shortcuts">`) and
+/// bound to the `actions.intent.OPEN_APP_FEATURE` capability, so Google
+/// Assistant can open them; donations push the matching dynamic shortcut:
+///
+/// ```xml
+/// <shortcut android:shortcutId="next_meeting"
+///     android:shortcutShortLabel="@string/shortcut_next_meeting">
+///     <intent android:action="android.intent.action.VIEW"
+///         android:data="elulib://agenda/next" />
+///     <capability-binding android:key="actions.intent.OPEN_APP_FEATURE">
+///         <parameter-binding android:key="feature"
+///             android:value="@string/shortcut_next_meeting" />
+///     </capability-binding>
+/// </shortcut>
+/// ```
+///
+/// Note: This implementation provides the structure for App Actions.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use tauri::AppHandle;
+
+use super::Shortcut;
+use crate::AppResult;
+
+/// Start forwarding shortcut invocations on Android
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start(app: AppHandle) -> AppResult<()> {
+    tracing::info!("[Android] Starting App Actions forwarding");
+
+    // TODO: Implement native shortcut intent forwarding
+    // The shortcut id must be forwarded to `shortcuts::on_shortcut`.
+    // Example Kotlin implementation (in the existing intent forwarding):
+    // ```kotlin
+    // // Checked before the deep link, which the shortcut intent also carries
+    // intent.getStringExtra(ShortcutManagerCompat.EXTRA_SHORTCUT_ID)?.let { onShortcut(it); return }
+    // ```
+
+    // Placeholder: Return success without forwarding intents
+    // Replace this with actual native implementation
+    let _ = app;
+    Ok(())
+}
+
+/// Donate a shortcut on Android
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn donate(shortcut: Shortcut) -> AppResult<()> {
+    // TODO: Implement native dynamic shortcut donation
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val info = ShortcutInfoCompat.Builder(context, shortcutId)
+    //     .setShortLabel(title)
+    //     .setIntent(Intent(Intent.ACTION_VIEW, Uri.parse(link)))
+    //     .addCapabilityBinding("actions.intent.OPEN_APP_FEATURE", "feature", listOf(title))
+    //     .build()
+    // ShortcutManagerCompat.pushDynamicShortcut(context, info)
+    // ```
+
+    // Placeholder: Log the donation
+    // Replace this with actual native implementation
+    tracing::debug!("[Android] Donating shortcut {} ({})", shortcut.id(), shortcut.link());
+    Ok(())
+}
@@ -0,0 +1,61 @@
+/// iOS-specific Siri Shortcuts plumbing
+///
+/// Each shortcut is an `NSUserActivity` whose type is listed under
+/// `NSUserActivityTypes` in `Info.plist`, made eligible for search and
+/// prediction so Siri suggests it and it can be added to Shortcuts. The app
+/// delegate continues the activity and forwards its type.
+///
+/// Note: This implementation provides the structure for Siri Shortcuts.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::Shortcut;
+use crate::AppResult;
+
+/// Start continuing shortcut activities on iOS
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start() -> AppResult<()> {
+    tracing::info!("[iOS] Starting Siri Shortcuts listener");
+
+    // TODO: Implement native activity continuation
+    // The activity type must be forwarded to `shortcuts::on_shortcut`.
+    // Example Swift implementation (in the existing continue handler, before
+    // universal links):
+    // ```swift
+    // if userActivity.activityType.hasPrefix("com.elulib.mobile.shortcut.") {
+    //     return onShortcut(userActivity.activityType)
+    // }
+    // ```
+
+    // Placeholder: Return success without continuing activities
+    // Replace this with actual native implementation
+    Ok(())
+}
+
+/// Donate a shortcut on iOS
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn donate(shortcut: Shortcut) -> AppResult<()> {
+    // TODO: Implement native NSUserActivity donation
+    // Example Swift implementation:
+    // ```swift
+    // let activity = NSUserActivity(activityType: activityType)
+    // activity.title = title
+    // activity.suggestedInvocationPhrase = title
+    // activity.isEligibleForSearch = true
+    // activity.isEligibleForPrediction = true
+    // activity.persistentIdentifier = activityType
+    // rootViewController.userActivity = activity
+    // activity.becomeCurrent()
+    // ```
+
+    // Placeholder: Log the donation
+    // Replace this with actual native implementation
+    tracing::debug!("[iOS] Donating activity {} ({})", shortcut.activity_type(), shortcut.title());
+    Ok(())
+}
@@ -0,0 +1,223 @@
+/// Siri Shortcuts and App Actions
+///
+/// Two screens of the app can be opened from the system assistant and
+/// suggestions ("Ouvrir la prochaine séance", "Voir mes documents"):
+/// - the frontend reports that one of these screens is displayed with
+///   `donate_shortcut`, and the native layer donates it to the system: an
+///   `NSUserActivity` on iOS (activity types declared in `Info.plist`), a
+///   pushed dynamic shortcut on Android (App Actions, `shortcuts.xml`)
+/// - each shortcut is donated at most once per launch
+/// - invoking a shortcut opens its deep link (`elulib://agenda/next`,
+///   `elulib://documents`) through the `deep_link` module, with the
+///   `shortcut` source, so it is routed like any other link (kept during a
+///   cold start, `deeplink://open` while running)
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::deep_link::{self, DeepLinkSource};
+use crate::native_module::NativeModule;
+use crate::AppResult;
+
+/// Screen that can be opened from a shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Shortcut {
+    /// Next meeting ("Ouvrir la prochaine séance")
+    NextMeeting,
+    /// Documents list ("Voir mes documents")
+    Documents,
+}
+
+impl Shortcut {
+    /// Every shortcut
+    pub const ALL: [Shortcut; 2] = [Shortcut::NextMeeting, Shortcut::Documents];
+
+    /// Identifier of the shortcut (Android shortcut id, suffix of the iOS
+    /// activity type)
+    pub fn id(self) -> &'static str {
+        match self {
+            Shortcut::NextMeeting => "next_meeting",
+            Shortcut::Documents => "documents",
+        }
+    }
+
+    /// Title shown by the system, also the suggested invocation phrase
+    pub fn title(self) -> &'static str {
+        match self {
+            Shortcut::NextMeeting => "Ouvrir la prochaine séance",
+            Shortcut::Documents => "Voir mes documents",
+        }
+    }
+
+    /// Deep link opened when the shortcut is invoked
+    pub fn link(self) -> String {
+        let path = match self {
+            Shortcut::NextMeeting => "agenda/next",
+            Shortcut::Documents => "documents",
+        };
+        format!("{}://{}", constants::DEEP_LINK_SCHEME, path)
+    }
+
+    /// `NSUserActivity` type of the shortcut (`com.elulib.mobile.shortcut.<id>`)
+    pub fn activity_type(self) -> String {
+        format!("{}{}", constants::SHORTCUT_ACTIVITY_TYPE_PREFIX, self.id())
+    }
+
+    /// Shortcut of an iOS activity type or Android shortcut id
+    pub fn from_identifier(identifier: &str) -> Option<Shortcut> {
+        let id = identifier
+            .strip_prefix(constants::SHORTCUT_ACTIVITY_TYPE_PREFIX)
+            .unwrap_or(identifier);
+        Shortcut::ALL.into_iter().find(|shortcut| shortcut.id() == id)
+    }
+}
+
+/// Shortcuts donated since launch, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct ShortcutState {
+    donated: Mutex<HashSet<Shortcut>>,
+}
+
+impl ShortcutState {
+    /// Returns `true` if the shortcut was donated since launch
+    pub fn is_donated(&self, shortcut: Shortcut) -> bool {
+        self.donated
+            .lock()
+            .map(|donated| donated.contains(&shortcut))
+            .unwrap_or(false)
+    }
+
+    /// Record a donation, once the platform accepted it
+    ///
+    /// # Returns
+    ///
+    /// Returns `false` if the shortcut was already donated since launch.
+    pub fn record(&self, shortcut: Shortcut) -> bool {
+        self.donated
+            .lock()
+            .map(|mut donated| donated.insert(shortcut))
+            .unwrap_or(false)
+    }
+}
+
+/// Donate a shortcut, when the frontend displays its screen
+///
+/// # Returns
+///
+/// Returns `true` if the shortcut was donated, `false` if it already was
+/// since launch, or an error if the platform rejected it.
+pub fn donate(app: &AppHandle, shortcut: Shortcut) -> AppResult<bool> {
+    let state = app.state::<ShortcutState>();
+    if state.is_donated(shortcut) {
+        return Ok(false);
+    }
+
+    #[cfg(target_os = "ios")]
+    ios::donate(shortcut)?;
+
+    #[cfg(target_os = "android")]
+    android::donate(shortcut)?;
+
+    // Recorded only once donated, so a rejected donation is retried
+    state.record(shortcut);
+    tracing::debug!("Shortcut {} donated", shortcut.id());
+    Ok(true)
+}
+
+/// Open the screen of an invoked shortcut
+///
+/// # Arguments
+///
+/// * `app` - The app handle
+/// * `identifier` - iOS activity type or Android shortcut id
+///
+/// # Returns
+///
+/// Returns `false` if the identifier is not a known shortcut.
+pub fn on_shortcut(app: &AppHandle, identifier: &str) -> bool {
+    let Some(shortcut) = Shortcut::from_identifier(identifier) else {
+        tracing::warn!("Unknown shortcut invoked: {}", identifier);
+        return false;
+    };
+    tracing::info!("Shortcut {} invoked", shortcut.id());
+    deep_link::on_open_url(app, &shortcut.link(), DeepLinkSource::Shortcut);
+    true
+}
+
+/// Start listening for shortcut invocations
+pub fn start(app: &AppHandle) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        // Activities are continued by the app delegate
+        let _ = app;
+        ios::start()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::start(app.clone())
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = app;
+        Ok(())
+    }
+}
+
+/// Siri Shortcuts and App Actions, as a native module
+pub struct ShortcutsModule;
+
+impl NativeModule for ShortcutsModule {
+    fn name(&self) -> &'static str {
+        "shortcuts"
+    }
+
+    fn commands(&self) -> &'static [&'static str] {
+        &["donate_shortcut"]
+    }
+
+    fn init(&self, app: &AppHandle) -> AppResult<()> {
+        start(app)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortcut_links_are_deep_links() {
+        for shortcut in Shortcut::ALL {
+            let link = deep_link::parse(&shortcut.link(), DeepLinkSource::Shortcut).unwrap();
+            assert_eq!(link.source, DeepLinkSource::Shortcut);
+            assert_eq!(Shortcut::from_identifier(&shortcut.activity_type()), Some(shortcut));
+            assert_eq!(Shortcut::from_identifier(shortcut.id()), Some(shortcut));
+        }
+        assert_eq!(
+            deep_link::parse(&Shortcut::NextMeeting.link(), DeepLinkSource::Shortcut).unwrap().path,
+            "/agenda/next"
+        );
+        assert_eq!(Shortcut::from_identifier("com.elulib.mobile.shortcut.unknown"), None);
+    }
+
+    #[test]
+    fn test_shortcuts_are_donated_once() {
+        let state = ShortcutState::default();
+        assert!(!state.is_donated(Shortcut::Documents));
+        assert!(state.record(Shortcut::Documents));
+        assert!(state.is_donated(Shortcut::Documents));
+        assert!(!state.record(Shortcut::Documents));
+        assert!(state.record(Shortcut::NextMeeting));
+    }
+}
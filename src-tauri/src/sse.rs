@@ -0,0 +1,618 @@
+/// Native Server-Sent Events subscriber
+///
+/// The live-session feed is an SSE stream, which the webview `EventSource`
+/// loses when the screen locks (the page is throttled, then the connection
+/// dropped). Subscriptions are kept natively instead:
+/// - `subscribe` opens a stream on the application origin (`https` only),
+///   authenticated with the session token when one is stored; at most
+///   `SSE_MAX_SUBSCRIPTIONS` streams are open at once
+/// - every event of the stream is emitted as a `sse://message` event with the
+///   subscription id, state changes as `sse://state` events
+/// - after a disconnection, the subscriber reconnects with the id of the last
+///   event received (`Last-Event-ID`), so the server replays the missed
+///   events; the delay is the `retry` sent by the server, or
+///   `SSE_RECONNECT_BASE_DELAY_MS`, doubled at each failed attempt up to
+///   `SSE_RECONNECT_MAX_DELAY_MS`, and skipped when the app returns to the
+///   foreground
+/// - a stream silent for `SSE_IDLE_TIMEOUT_SECS` is considered lost (the
+///   server is expected to send keep-alive comments)
+/// - the server ends a subscription for good by answering `204 No Content`
+///   or a client error
+///
+/// `unsubscribe` closes a stream; every stream is closed on logout, wipe and
+/// session lock. The commands form the `sse` native module (`SseModule`),
+/// which closes every stream when the app exits.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, Url};
+use tokio::sync::mpsc;
+
+use crate::constants;
+use crate::events;
+use crate::http;
+use crate::native_module::NativeModule;
+use crate::runtime_config;
+use crate::session_handoff;
+use crate::{AppError, AppResult};
+
+/// State of a subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SseState {
+    /// Opening the stream
+    Connecting,
+    /// Receiving events
+    Open,
+    /// Waiting before the next connection attempt
+    Reconnecting,
+    /// Closed by `unsubscribe` or by the server
+    Closed,
+}
+
+/// Payload of `sse://state`, also returned by `sse_subscribe`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SseStatus {
+    /// Subscription id
+    pub id: u32,
+    /// Endpoint of the stream
+    pub url: String,
+    /// Subscription state
+    pub state: SseState,
+    /// Id of the last event received, sent back when reconnecting
+    pub last_event_id: Option<String>,
+    /// Failed connection attempts since the last successful one
+    pub attempt: u32,
+}
+
+/// Payload of `sse://message`, emitted for every event of a stream
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct SseMessage {
+    /// Subscription id
+    pub subscription: u32,
+    /// Event type (`message` unless named by the server)
+    pub event: String,
+    /// Event data, lines joined with `\n`
+    pub data: String,
+    /// Event id, if the server sent one
+    pub id: Option<String>,
+}
+
+/// Event parsed from a stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Event type
+    pub event: String,
+    /// Event data
+    pub data: String,
+    /// Last event id when the event was dispatched
+    pub id: Option<String>,
+}
+
+/// Incremental parser of the `text/event-stream` format
+///
+/// Chunks may split lines (and UTF-8 sequences) anywhere.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    line: Vec<u8>,
+    after_cr: bool,
+    event: String,
+    data: String,
+    last_event_id: Option<String>,
+    retry: Option<u64>,
+}
+
+impl SseParser {
+    /// Parser for a new connection, keeping the last event id of the previous one
+    pub fn resume(last_event_id: Option<String>) -> Self {
+        SseParser {
+            last_event_id,
+            ..Default::default()
+        }
+    }
+
+    /// Id of the last event received
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// Reconnection delay requested by the server (milliseconds)
+    pub fn retry(&self) -> Option<u64> {
+        self.retry
+    }
+
+    /// Parse a chunk of the stream
+    ///
+    /// # Returns
+    ///
+    /// Returns the events completed by the chunk, or an error if a line or an
+    /// event exceeds `SSE_MAX_EVENT_BYTES`.
+    pub fn feed(&mut self, chunk: &[u8]) -> AppResult<Vec<SseEvent>> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            let after_cr = std::mem::take(&mut self.after_cr);
+            match byte {
+                b'\n' if after_cr => {}
+                b'\n' | b'\r' => {
+                    self.after_cr = byte == b'\r';
+                    let line = String::from_utf8_lossy(&std::mem::take(&mut self.line)).into_owned();
+                    if let Some(event) = self.process_line(&line) {
+                        events.push(event);
+                    }
+                }
+                _ => self.line.push(byte),
+            }
+            if self.line.len() + self.data.len() > constants::SSE_MAX_EVENT_BYTES {
+                return Err(AppError::Network(format!("Event larger than {} bytes", constants::SSE_MAX_EVENT_BYTES)));
+            }
+        }
+        Ok(events)
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => {
+                self.last_event_id = Some(value.to_string()).filter(|id| !id.is_empty());
+            }
+            "retry" => {
+                if let Ok(retry) = value.parse() {
+                    self.retry = Some(retry);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = std::mem::take(&mut self.event);
+        let mut data = std::mem::take(&mut self.data);
+        if data.is_empty() {
+            return None;
+        }
+        data.pop();
+        Some(SseEvent {
+            event: if event.is_empty() { "message".to_string() } else { event },
+            data,
+            id: self.last_event_id.clone(),
+        })
+    }
+}
+
+/// Delay before the connection attempt following `attempt` failures
+///
+/// # Arguments
+///
+/// * `attempt` - Failed attempts, from 1
+/// * `retry` - Reconnection delay requested by the server, if any
+pub fn reconnect_delay(attempt: u32, retry: Option<u64>) -> Duration {
+    let base = retry.unwrap_or(constants::SSE_RECONNECT_BASE_DELAY_MS);
+    let exponent = attempt.saturating_sub(1).min(16);
+    Duration::from_millis(base.saturating_mul(1 << exponent).min(constants::SSE_RECONNECT_MAX_DELAY_MS.max(base)))
+}
+
+/// Validate a stream endpoint: `https`, on the host of the application URL
+pub fn validate_url(url: &str, app_url: &str) -> AppResult<Url> {
+    let parsed = Url::parse(url).map_err(|e| AppError::InvalidArgument(format!("Invalid stream URL: {}", e)))?;
+    if parsed.scheme() != "https" {
+        return Err(AppError::InvalidArgument(format!("Unsupported stream scheme: {}", parsed.scheme())));
+    }
+    let app_origin = Url::parse(app_url).ok().map(|app| app.origin());
+    if !parsed.origin().is_tuple() || app_origin != Some(parsed.origin()) {
+        return Err(AppError::InvalidArgument("Stream endpoint must be on the application origin".to_string()));
+    }
+    Ok(parsed)
+}
+
+/// Instruction to a subscription task
+#[derive(Debug)]
+enum Control {
+    Resume,
+    Close,
+}
+
+/// Subscription task and its shared status
+struct Subscription {
+    control: mpsc::UnboundedSender<Control>,
+    status: Arc<Mutex<SseStatus>>,
+}
+
+/// SSE subscriber, managed by the Tauri application
+#[derive(Default)]
+pub struct SseClient {
+    subscriptions: Mutex<HashMap<u32, Subscription>>,
+    next_id: AtomicU32,
+}
+
+impl SseClient {
+    /// Whether `status` belongs to an open subscription
+    fn is_current(&self, status: &Arc<Mutex<SseStatus>>) -> bool {
+        self.subscriptions
+            .lock()
+            .map(|subscriptions| {
+                subscriptions
+                    .values()
+                    .any(|subscription| Arc::ptr_eq(&subscription.status, status))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Status of the open subscriptions, by id
+    pub fn statuses(&self) -> Vec<SseStatus> {
+        let mut statuses: Vec<SseStatus> = self
+            .subscriptions
+            .lock()
+            .map(|subscriptions| {
+                subscriptions
+                    .values()
+                    .filter_map(|subscription| subscription.status.lock().ok().map(|status| status.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        statuses.sort_by_key(|status| status.id);
+        statuses
+    }
+}
+
+/// Subscribe to a stream
+///
+/// # Returns
+///
+/// Returns the status of the new subscription, or an error if the URL is not
+/// accepted or `SSE_MAX_SUBSCRIPTIONS` streams are already open.
+pub fn subscribe(app: &AppHandle, url: &str) -> AppResult<SseStatus> {
+    let url = validate_url(url, &runtime_config::get().app_url)?;
+    let client = app.state::<SseClient>();
+    let mut subscriptions = client.subscriptions.lock().map_err(|e| AppError::Network(e.to_string()))?;
+    if subscriptions.len() >= constants::SSE_MAX_SUBSCRIPTIONS {
+        return Err(AppError::Network(format!(
+            "At most {} streams can be subscribed at once",
+            constants::SSE_MAX_SUBSCRIPTIONS
+        )));
+    }
+    let id = client.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+    let status = SseStatus {
+        id,
+        url: url.to_string(),
+        state: SseState::Connecting,
+        last_event_id: None,
+        attempt: 0,
+    };
+    let shared = Arc::new(Mutex::new(status.clone()));
+    let (control, receiver) = mpsc::unbounded_channel();
+    subscriptions.insert(
+        id,
+        Subscription {
+            control,
+            status: shared.clone(),
+        },
+    );
+    tracing::info!("Subscribing to stream {} ({})", id, url);
+    tauri::async_runtime::spawn(run(app.clone(), url, receiver, shared));
+    Ok(status)
+}
+
+/// Close a subscription
+///
+/// # Returns
+///
+/// Returns `true` if the subscription was open.
+pub fn unsubscribe(app: &AppHandle, id: u32) -> AppResult<bool> {
+    let subscription = app
+        .state::<SseClient>()
+        .subscriptions
+        .lock()
+        .map_err(|e| AppError::Network(e.to_string()))?
+        .remove(&id);
+    Ok(match subscription {
+        Some(subscription) => {
+            close(app, subscription);
+            true
+        }
+        None => false,
+    })
+}
+
+/// Close every subscription
+pub fn unsubscribe_all(app: &AppHandle) {
+    let subscriptions = match app.state::<SseClient>().subscriptions.lock() {
+        Ok(mut subscriptions) => std::mem::take(&mut *subscriptions),
+        Err(_) => return,
+    };
+    for subscription in subscriptions.into_values() {
+        close(app, subscription);
+    }
+}
+
+/// Stop the task of a removed subscription and notify the webview
+fn close(app: &AppHandle, subscription: Subscription) {
+    let _ = subscription.control.send(Control::Close);
+    let closed = subscription.status.lock().map(|mut status| {
+        status.state = SseState::Closed;
+        status.clone()
+    });
+    if let Ok(closed) = closed {
+        events::emit(app, &closed);
+    }
+}
+
+/// Reconnect the waiting subscriptions when the app returns to the foreground
+pub fn on_foreground(app: &AppHandle) {
+    if let Ok(subscriptions) = app.state::<SseClient>().subscriptions.lock() {
+        for subscription in subscriptions.values() {
+            let _ = subscription.control.send(Control::Resume);
+        }
+    }
+}
+
+/// Update the shared status and notify the webview, unless the subscription
+/// was closed
+fn set_state(app: &AppHandle, status: &Arc<Mutex<SseStatus>>, state: SseState, attempt: u32, last_event_id: Option<&str>) {
+    if !app.state::<SseClient>().is_current(status) {
+        return;
+    }
+    let current = match status.lock() {
+        Ok(mut current) => {
+            current.state = state;
+            current.attempt = attempt;
+            current.last_event_id = last_event_id.map(str::to_string);
+            current.clone()
+        }
+        Err(_) => return,
+    };
+    events::emit(app, &current);
+}
+
+/// Why a stream ended
+enum Ended {
+    /// Lost or refused: reconnect
+    Lost,
+    /// `unsubscribe`, or ended by the server: stop
+    Closed,
+}
+
+/// Subscription task: connect, forward events, reconnect until closed
+async fn run(app: AppHandle, url: Url, mut control: mpsc::UnboundedReceiver<Control>, status: Arc<Mutex<SseStatus>>) {
+    let id = status.lock().map(|status| status.id).unwrap_or_default();
+    let mut parser = SseParser::default();
+    let mut attempt = 0;
+    loop {
+        set_state(&app, &status, SseState::Connecting, attempt, parser.last_event_id());
+        let connecting = open(&app, &url, parser.last_event_id());
+        let response = tokio::select! {
+            response = connecting => response,
+            received = control.recv() => match received {
+                Some(Control::Resume) => continue,
+                Some(Control::Close) | None => break,
+            },
+        };
+        parser = SseParser {
+            retry: parser.retry(),
+            ..SseParser::resume(parser.last_event_id().map(str::to_string))
+        };
+        let ended = match response {
+            Ok(Some(response)) => {
+                tracing::info!("Stream {} open", id);
+                attempt = 0;
+                set_state(&app, &status, SseState::Open, attempt, parser.last_event_id());
+                serve(&app, id, response, &mut parser, &mut control).await
+            }
+            Ok(None) => Ended::Closed,
+            Err(e) => {
+                tracing::warn!("Stream {} connection failed: {}", id, e);
+                Ended::Lost
+            }
+        };
+        if let Ended::Closed = ended {
+            break;
+        }
+
+        attempt += 1;
+        let delay = reconnect_delay(attempt, parser.retry());
+        tracing::info!("Reconnecting stream {} in {}ms (attempt {})", id, delay.as_millis(), attempt);
+        set_state(&app, &status, SseState::Reconnecting, attempt, parser.last_event_id());
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            received = control.recv() => match received {
+                Some(Control::Resume) => {}
+                Some(Control::Close) | None => break,
+            },
+        }
+    }
+    tracing::info!("Stream {} closed", id);
+    set_state(&app, &status, SseState::Closed, attempt, parser.last_event_id());
+    // Drop a subscription ended by the server
+    if let Ok(mut subscriptions) = app.state::<SseClient>().subscriptions.lock() {
+        subscriptions.retain(|_, subscription| !Arc::ptr_eq(&subscription.status, &status));
+    }
+}
+
+/// Open a stream, with the session token if one is stored
+///
+/// # Returns
+///
+/// Returns the response, `None` if the server ended the subscription, or an
+/// error to retry.
+async fn open(app: &AppHandle, url: &Url, last_event_id: Option<&str>) -> AppResult<Option<reqwest::Response>> {
+    let mut request = http::stream_client(Duration::from_secs(constants::SSE_IDLE_TIMEOUT_SECS))?
+        .get(url.clone())
+        .header(reqwest::header::ACCEPT, "text/event-stream")
+        .header(reqwest::header::CACHE_CONTROL, "no-cache");
+    if let Some(last_event_id) = last_event_id {
+        request = request.header("Last-Event-ID", last_event_id);
+    }
+    if let Some(token) = session_handoff::session_token(app)? {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await.map_err(|e| AppError::Network(e.to_string()))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NO_CONTENT {
+        tracing::info!("Stream ended by the server");
+        return Ok(None);
+    }
+    if status.is_client_error() && !matches!(status.as_u16(), 408 | 429) {
+        tracing::warn!("Stream refused by the server: {}", status);
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(AppError::Network(format!("Server answered {}", status)));
+    }
+    let event_stream = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+    if !event_stream {
+        tracing::warn!("Stream endpoint did not answer with text/event-stream");
+        return Ok(None);
+    }
+    Ok(Some(response))
+}
+
+/// Forward events until the stream ends
+async fn serve(
+    app: &AppHandle,
+    id: u32,
+    mut response: reqwest::Response,
+    parser: &mut SseParser,
+    control: &mut mpsc::UnboundedReceiver<Control>,
+) -> Ended {
+    loop {
+        tokio::select! {
+            received = response.chunk() => match received {
+                Ok(Some(chunk)) => match parser.feed(&chunk) {
+                    Ok(parsed) => {
+                        for event in parsed {
+                            events::emit(app, &SseMessage {
+                                subscription: id,
+                                event: event.event,
+                                data: event.data,
+                                id: event.id,
+                            });
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("Stream {} dropped: {}", id, e);
+                        return Ended::Lost;
+                    }
+                },
+                Ok(None) => {
+                    tracing::info!("Stream {} ended", id);
+                    return Ended::Lost;
+                }
+                Err(e) => {
+                    tracing::warn!("Stream {} lost: {}", id, e);
+                    return Ended::Lost;
+                }
+            },
+            received = control.recv() => match received {
+                Some(Control::Resume) => {}
+                Some(Control::Close) | None => return Ended::Closed,
+            },
+        }
+    }
+}
+
+/// Server-Sent Events native module (`module-sse`)
+pub struct SseModule;
+
+impl NativeModule for SseModule {
+    fn name(&self) -> &'static str {
+        "sse"
+    }
+
+    fn commands(&self) -> &'static [&'static str] {
+        &["sse_subscribe", "sse_unsubscribe", "sse_subscriptions"]
+    }
+
+    fn events(&self) -> &'static [&'static str] {
+        &[constants::SSE_STATE_EVENT, constants::SSE_MESSAGE_EVENT]
+    }
+
+    fn shutdown(&self, app: &AppHandle) {
+        unsubscribe_all(app);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event: &str, data: &str, id: Option<&str>) -> SseEvent {
+        SseEvent {
+            event: event.to_string(),
+            data: data.to_string(),
+            id: id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parser_handles_split_chunks_and_line_endings() {
+        let mut parser = SseParser::default();
+        assert_eq!(parser.feed(b": keep-alive\n\nda").unwrap(), vec![]);
+        assert_eq!(parser.feed(b"ta: first\r").unwrap(), vec![]);
+        assert_eq!(
+            parser.feed(b"\ndata:second\r\n\r\n").unwrap(),
+            vec![event("message", "first\nsecond", None)],
+            "CRLF split across chunks counts as one line ending"
+        );
+        let parsed = parser.feed("event: vote\rid: 42\rdata: {\"é\":1}\r\r".as_bytes()).unwrap();
+        assert_eq!(parsed, vec![event("vote", "{\"é\":1}", Some("42"))]);
+        assert_eq!(parser.last_event_id(), Some("42"));
+    }
+
+    #[test]
+    fn test_parser_fields() {
+        let mut parser = SseParser::resume(Some("7".to_string()));
+        let parsed = parser.feed(b"retry: 5000\nid: 8\n\ndata\nunknown: x\n\n").unwrap();
+        assert_eq!(parser.retry(), Some(5000));
+        assert_eq!(parser.last_event_id(), Some("8"), "Id kept without data");
+        assert_eq!(parsed, vec![event("message", "", Some("8"))]);
+        parser.feed(b"retry: soon\nid\n\n").unwrap();
+        assert_eq!(parser.retry(), Some(5000), "Invalid retry ignored");
+        assert_eq!(parser.last_event_id(), None, "Empty id resets the last event id");
+    }
+
+    #[test]
+    fn test_parser_rejects_oversized_events() {
+        let mut parser = SseParser::default();
+        let line = format!("data: {}\n", "x".repeat(constants::SSE_MAX_EVENT_BYTES));
+        assert!(parser.feed(line.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_reconnect_delay_uses_server_retry() {
+        assert_eq!(reconnect_delay(1, None), Duration::from_millis(constants::SSE_RECONNECT_BASE_DELAY_MS));
+        assert_eq!(reconnect_delay(2, Some(500)), Duration::from_millis(1000));
+        assert_eq!(reconnect_delay(100, None), Duration::from_millis(constants::SSE_RECONNECT_MAX_DELAY_MS));
+    }
+
+    #[test]
+    fn test_url_must_be_https_on_app_origin() {
+        let app_url = "https://app.elulib.com";
+        assert!(validate_url("https://app.elulib.com/live/42/events", app_url).is_ok());
+        assert!(validate_url("http://app.elulib.com/live/42/events", app_url).is_err());
+        assert!(validate_url("https://evil.example/events", app_url).is_err());
+        assert!(validate_url("https://app.elulib.com:8443/events", app_url).is_err());
+        assert!(validate_url("not a url", app_url).is_err());
+    }
+}
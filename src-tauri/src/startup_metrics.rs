@@ -0,0 +1,193 @@
+/// Startup performance metrics module
+///
+/// This module timestamps the cold start phases, so cold-start regressions can
+/// be quantified and tracked:
+/// - process start (entry of `run()`)
+/// - builder ready (Tauri builder configured, before the runtime is built)
+/// - webview created (application setup, main webview available)
+/// - first page load (remote frontend finished loading)
+///
+/// Durations are reported in milliseconds since process start through the
+/// `startup_metrics` command, and sent once as an `app_startup` analytics
+/// event when the first page load completes.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use tauri::AppHandle;
+
+use crate::analytics::{self, PropValue};
+
+/// Cold start phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupPhase {
+    /// Tauri builder configured
+    BuilderReady,
+    /// Application setup, main webview created
+    WebviewCreated,
+    /// Remote frontend finished loading
+    FirstPageLoad,
+}
+
+/// Startup durations, in milliseconds since process start
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupMetrics {
+    /// Time to configure the Tauri builder
+    pub builder_ready_ms: Option<u64>,
+    /// Time to create the main webview
+    pub webview_created_ms: Option<u64>,
+    /// Time to the first page load
+    pub first_page_load_ms: Option<u64>,
+}
+
+/// Timestamps of the cold start phases
+#[derive(Debug, Default)]
+pub struct StartupTimeline {
+    process_start: Option<Instant>,
+    metrics: StartupMetrics,
+}
+
+impl StartupTimeline {
+    /// Create an empty timeline
+    pub const fn new() -> Self {
+        StartupTimeline {
+            process_start: None,
+            metrics: StartupMetrics {
+                builder_ready_ms: None,
+                webview_created_ms: None,
+                first_page_load_ms: None,
+            },
+        }
+    }
+
+    /// Record the process start
+    pub fn start(&mut self, now: Instant) {
+        self.process_start.get_or_insert(now);
+    }
+
+    /// Record a phase, keeping the first occurrence only
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the phase was recorded by this call.
+    pub fn mark(&mut self, phase: StartupPhase, now: Instant) -> bool {
+        let Some(start) = self.process_start else {
+            return false;
+        };
+        let slot = match phase {
+            StartupPhase::BuilderReady => &mut self.metrics.builder_ready_ms,
+            StartupPhase::WebviewCreated => &mut self.metrics.webview_created_ms,
+            StartupPhase::FirstPageLoad => &mut self.metrics.first_page_load_ms,
+        };
+        if slot.is_some() {
+            return false;
+        }
+        *slot = Some(now.saturating_duration_since(start).as_millis() as u64);
+        true
+    }
+
+    /// Durations recorded so far
+    pub fn metrics(&self) -> StartupMetrics {
+        self.metrics
+    }
+}
+
+/// Timeline of the current process
+static TIMELINE: Mutex<StartupTimeline> = Mutex::new(StartupTimeline::new());
+
+/// Record the process start; must be called first thing in `run()`
+pub fn record_process_start() {
+    if let Ok(mut timeline) = TIMELINE.lock() {
+        timeline.start(Instant::now());
+    }
+}
+
+/// Record a cold start phase
+///
+/// When the first page load is recorded, the metrics are logged and tracked
+/// as an `app_startup` analytics event.
+pub fn mark(app: Option<&AppHandle>, phase: StartupPhase) {
+    let (recorded, metrics) = match TIMELINE.lock() {
+        Ok(mut timeline) => (timeline.mark(phase, Instant::now()), timeline.metrics()),
+        Err(_) => return,
+    };
+    if !recorded || phase != StartupPhase::FirstPageLoad {
+        return;
+    }
+
+    tracing::info!("Startup metrics: {:?}", metrics);
+    if let Some(app) = app {
+        if let Err(e) = analytics::track(app, "app_startup", analytics_props(&metrics)) {
+            tracing::warn!("Failed to track startup metrics: {}", e);
+        }
+    }
+}
+
+/// Startup durations recorded so far
+pub fn metrics() -> StartupMetrics {
+    TIMELINE
+        .lock()
+        .map(|timeline| timeline.metrics())
+        .unwrap_or_default()
+}
+
+/// Analytics properties of the `app_startup` event
+fn analytics_props(metrics: &StartupMetrics) -> BTreeMap<String, PropValue> {
+    [
+        ("builder_ready_ms", metrics.builder_ready_ms),
+        ("webview_created_ms", metrics.webview_created_ms),
+        ("first_page_load_ms", metrics.first_page_load_ms),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| Some((key.to_string(), PropValue::Number(value? as f64))))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_timeline_records_durations_since_start() {
+        let start = Instant::now();
+        let mut timeline = StartupTimeline::default();
+        assert!(!timeline.mark(StartupPhase::BuilderReady, start), "No start recorded yet");
+
+        timeline.start(start);
+        assert!(timeline.mark(StartupPhase::BuilderReady, start + Duration::from_millis(120)));
+        assert!(timeline.mark(StartupPhase::WebviewCreated, start + Duration::from_millis(450)));
+        assert_eq!(
+            timeline.metrics(),
+            StartupMetrics {
+                builder_ready_ms: Some(120),
+                webview_created_ms: Some(450),
+                first_page_load_ms: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_timeline_keeps_first_occurrence() {
+        let start = Instant::now();
+        let mut timeline = StartupTimeline::default();
+        timeline.start(start);
+        assert!(timeline.mark(StartupPhase::FirstPageLoad, start + Duration::from_millis(900)));
+        assert!(!timeline.mark(StartupPhase::FirstPageLoad, start + Duration::from_millis(5000)));
+        assert_eq!(timeline.metrics().first_page_load_ms, Some(900));
+    }
+
+    #[test]
+    fn test_analytics_props_match_schema() {
+        let metrics = StartupMetrics {
+            builder_ready_ms: Some(120),
+            webview_created_ms: None,
+            first_page_load_ms: Some(900),
+        };
+        let props = analytics_props(&metrics);
+        assert_eq!(props.len(), 2);
+        assert!(analytics::validate("app_startup", &props).is_ok());
+    }
+}
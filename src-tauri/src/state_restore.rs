@@ -0,0 +1,218 @@
+/// Persisted app state snapshot and restore
+///
+/// The frontend keeps the native side informed of its current state (last
+/// route, scroll hints, pending draft ids) with `update_app_state`. The
+/// snapshot is written to disk when the app goes to the background, which is
+/// the last reliable moment before the OS may kill the process (Android
+/// low-memory kills, iOS memory pressure).
+///
+/// On the next launch, the snapshot is loaded and exposed through the
+/// `get_restored_state` command so the frontend can bring the user back to
+/// where they were.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::{AppError, AppResult};
+
+/// Snapshot of the frontend state
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStateSnapshot {
+    /// Last route displayed by the frontend
+    pub last_route: Option<String>,
+    /// Scroll offsets, keyed by route or element identifier
+    #[serde(default)]
+    pub scroll_hints: BTreeMap<String, f64>,
+    /// Identifiers of drafts that were being edited
+    #[serde(default)]
+    pub pending_draft_ids: Vec<String>,
+    /// Time the snapshot was saved (seconds since the Unix epoch)
+    #[serde(default)]
+    pub saved_at: u64,
+}
+
+/// App state snapshots, managed by the Tauri application
+#[derive(Default)]
+pub struct StateStore {
+    current: Mutex<Option<AppStateSnapshot>>,
+    restored: Mutex<Option<AppStateSnapshot>>,
+}
+
+impl StateStore {
+    /// Replace the current snapshot
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if the snapshot
+    /// exceeds `MAX_STATE_SNAPSHOT_BYTES` once serialized.
+    pub fn update(&self, snapshot: AppStateSnapshot) -> AppResult<()> {
+        validate_snapshot(&snapshot)?;
+        let mut current = self.current.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        *current = Some(snapshot);
+        Ok(())
+    }
+
+    /// Snapshot restored at launch, if any
+    pub fn restored(&self) -> Option<AppStateSnapshot> {
+        self.restored.lock().ok().and_then(|restored| restored.clone())
+    }
+
+    /// Current snapshot, if the frontend provided one
+    pub fn current(&self) -> Option<AppStateSnapshot> {
+        self.current.lock().ok().and_then(|current| current.clone())
+    }
+
+    /// Forget the current and restored snapshots (local data wipe)
+    pub fn clear(&self) {
+        if let Ok(mut current) = self.current.lock() {
+            *current = None;
+        }
+        if let Ok(mut restored) = self.restored.lock() {
+            *restored = None;
+        }
+    }
+
+    /// Current snapshot, without blocking if the store is locked
+    ///
+    /// Used by the panic hook, which may run while the lock is held.
+    pub fn try_current(&self) -> Option<AppStateSnapshot> {
+        self.current.try_lock().ok().and_then(|current| current.clone())
+    }
+}
+
+/// Check that a snapshot fits within `MAX_STATE_SNAPSHOT_BYTES`
+pub fn validate_snapshot(snapshot: &AppStateSnapshot) -> AppResult<()> {
+    let len = serde_json::to_vec(snapshot)
+        .map_err(|e| AppError::Storage(format!("Failed to serialize app state: {}", e)))?
+        .len();
+    if len > constants::MAX_STATE_SNAPSHOT_BYTES {
+        return Err(AppError::InvalidArgument(format!(
+            "App state must be at most {} bytes, got {}",
+            constants::MAX_STATE_SNAPSHOT_BYTES, len
+        )));
+    }
+    Ok(())
+}
+
+/// Returns `true` if a snapshot saved at `saved_at` can still be restored at `now`
+pub fn is_fresh(snapshot: &AppStateSnapshot, now: u64) -> bool {
+    now.saturating_sub(snapshot.saved_at) <= constants::STATE_SNAPSHOT_MAX_AGE_SECS
+}
+
+/// Path of the snapshot file
+fn snapshot_path(app: &AppHandle) -> AppResult<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(constants::STATE_SNAPSHOT_FILE))
+        .map_err(|e| AppError::Storage(format!("Failed to resolve data directory: {}", e)))
+}
+
+/// Current time in seconds since the Unix epoch
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Read a snapshot file, ignoring missing or corrupted files
+pub fn read_snapshot(path: &Path) -> Option<AppStateSnapshot> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents)
+        .map_err(|e| tracing::warn!("Ignoring invalid app state snapshot: {}", e))
+        .ok()
+}
+
+/// Write a snapshot file
+pub fn write_snapshot(path: &Path, snapshot: &AppStateSnapshot) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| AppError::Storage(format!("Failed to create data directory: {}", e)))?;
+    }
+    let contents = serde_json::to_string(snapshot)
+        .map_err(|e| AppError::Storage(format!("Failed to serialize app state: {}", e)))?;
+    std::fs::write(path, contents).map_err(|e| AppError::Storage(format!("Failed to write app state: {}", e)))
+}
+
+/// Load the snapshot persisted by the previous session
+///
+/// Must be called once at startup. Stale snapshots (older than
+/// `STATE_SNAPSHOT_MAX_AGE_SECS`) are discarded.
+pub fn load_on_launch(app: &AppHandle) -> AppResult<()> {
+    let path = snapshot_path(app)?;
+    let snapshot = read_snapshot(&path).filter(|snapshot| is_fresh(snapshot, now_secs()));
+    if snapshot.is_some() {
+        tracing::info!("Restored app state snapshot from previous session");
+    }
+
+    let store = app.state::<StateStore>();
+    let mut restored = store.restored.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+    *restored = snapshot;
+    Ok(())
+}
+
+/// Persist the current snapshot to disk
+///
+/// Called when the app goes to the background. Does nothing if the frontend
+/// never provided a snapshot.
+pub fn persist(app: &AppHandle) -> AppResult<()> {
+    let mut snapshot = match app.state::<StateStore>().current() {
+        Some(snapshot) => snapshot,
+        None => return Ok(()),
+    };
+    snapshot.saved_at = now_secs();
+    write_snapshot(&snapshot_path(app)?, &snapshot)?;
+    tracing::debug!("App state snapshot persisted");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_snapshot() -> AppStateSnapshot {
+        AppStateSnapshot {
+            last_route: Some("/dossiers/42".to_string()),
+            scroll_hints: BTreeMap::from([("/dossiers/42".to_string(), 1280.0)]),
+            pending_draft_ids: vec!["draft-1".to_string()],
+            saved_at: 1000,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let path = dir.path().join(constants::STATE_SNAPSHOT_FILE);
+
+        assert_eq!(read_snapshot(&path), None);
+        write_snapshot(&path, &sample_snapshot()).expect("write_snapshot should succeed");
+        assert_eq!(read_snapshot(&path), Some(sample_snapshot()));
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let snapshot = sample_snapshot();
+        assert!(is_fresh(&snapshot, 1000));
+        assert!(is_fresh(&snapshot, 1000 + constants::STATE_SNAPSHOT_MAX_AGE_SECS));
+        assert!(!is_fresh(&snapshot, 1001 + constants::STATE_SNAPSHOT_MAX_AGE_SECS));
+    }
+
+    #[test]
+    fn test_update_rejects_oversized_snapshot() {
+        let store = StateStore::default();
+        assert!(store.update(sample_snapshot()).is_ok());
+        assert_eq!(store.current(), Some(sample_snapshot()));
+
+        let oversized = AppStateSnapshot {
+            last_route: Some("a".repeat(constants::MAX_STATE_SNAPSHOT_BYTES)),
+            ..AppStateSnapshot::default()
+        };
+        assert!(store.update(oversized).is_err());
+        assert_eq!(store.current(), Some(sample_snapshot()), "Rejected snapshot should not replace the current one");
+    }
+}
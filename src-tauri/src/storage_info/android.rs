@@ -0,0 +1,34 @@
+/// Android-specific storage and memory implementation
+///
+/// This module queries volume capacity with `StatFs` and memory with
+/// `ActivityManager.MemoryInfo`.
+///
+/// Note: This implementation provides the structure for Android storage info.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::DeviceCapacity;
+use crate::AppResult;
+
+/// Get the device storage and memory capacity on Android
+///
+/// # Returns
+///
+/// Returns the `DeviceCapacity` of the device.
+pub fn device_capacity() -> AppResult<DeviceCapacity> {
+    // TODO: Implement native Android capacity lookup
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val stat = StatFs(context.filesDir.path)
+    // val storageTotal = stat.totalBytes
+    // val storageFree = stat.availableBytes
+    // val memoryInfo = ActivityManager.MemoryInfo()
+    // (context.getSystemService(Context.ACTIVITY_SERVICE) as ActivityManager).getMemoryInfo(memoryInfo)
+    // val memoryTotal = memoryInfo.totalMem
+    // val memoryAvailable = memoryInfo.availMem
+    // ```
+
+    // Placeholder: Report unknown capacity
+    // Replace this with actual native implementation
+    Ok(DeviceCapacity::default())
+}
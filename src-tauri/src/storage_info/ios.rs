@@ -0,0 +1,34 @@
+/// iOS-specific storage and memory implementation
+///
+/// This module queries volume capacity with `URLResourceValues` and memory
+/// with `ProcessInfo` / `os_proc_available_memory`.
+///
+/// Note: This implementation provides the structure for iOS storage info.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::DeviceCapacity;
+use crate::AppResult;
+
+/// Get the device storage and memory capacity on iOS
+///
+/// # Returns
+///
+/// Returns the `DeviceCapacity` of the device.
+pub fn device_capacity() -> AppResult<DeviceCapacity> {
+    // TODO: Implement native iOS capacity lookup
+    // Example Swift implementation:
+    // ```swift
+    // let values = try URL(fileURLWithPath: NSHomeDirectory()).resourceValues(forKeys: [
+    //     .volumeTotalCapacityKey, .volumeAvailableCapacityForImportantUsageKey,
+    // ])
+    // let storageTotal = values.volumeTotalCapacity
+    // let storageFree = values.volumeAvailableCapacityForImportantUsage
+    // let memoryTotal = ProcessInfo.processInfo.physicalMemory
+    // let memoryAvailable = os_proc_available_memory()
+    // ```
+
+    // Placeholder: Report unknown capacity
+    // Replace this with actual native implementation
+    Ok(DeviceCapacity::default())
+}
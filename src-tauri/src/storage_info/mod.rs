@@ -0,0 +1,139 @@
+/// Storage and memory information
+///
+/// This module reports the free and total device storage, the device memory,
+/// and the disk footprint of the application itself (cache and data
+/// directories), so the download manager and offline cache can avoid filling
+/// the disk and support can diagnose "app is slow" reports.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::path::Path;
+
+use tauri::{AppHandle, Manager};
+use crate::{AppError, AppResult};
+
+/// Device storage and memory capacity, as reported by the platform
+///
+/// Values are `None` when the platform does not expose them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DeviceCapacity {
+    /// Total storage of the data volume (bytes)
+    pub storage_total: Option<u64>,
+    /// Free storage of the data volume, usable by the app (bytes)
+    pub storage_free: Option<u64>,
+    /// Total physical memory (bytes)
+    pub memory_total: Option<u64>,
+    /// Memory currently available to the app (bytes)
+    pub memory_available: Option<u64>,
+}
+
+/// Storage and memory information reported to the frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageInfo {
+    /// Total storage of the data volume (bytes)
+    pub total_bytes: Option<u64>,
+    /// Free storage of the data volume (bytes)
+    pub free_bytes: Option<u64>,
+    /// Size of the app cache directory (bytes)
+    pub app_cache_bytes: u64,
+    /// Size of the app data directory (bytes)
+    pub app_data_bytes: u64,
+    /// Total physical memory (bytes)
+    pub memory_total_bytes: Option<u64>,
+    /// Memory currently available to the app (bytes)
+    pub memory_available_bytes: Option<u64>,
+}
+
+/// Get the device storage and memory capacity from the platform
+///
+/// # Returns
+///
+/// Returns the `DeviceCapacity`, or an error if the platform query fails.
+pub fn device_capacity() -> AppResult<DeviceCapacity> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::device_capacity()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::device_capacity()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(DeviceCapacity::default())
+    }
+}
+
+/// Collect storage and memory information for the app
+///
+/// # Returns
+///
+/// Returns the `StorageInfo`, or an error if the app directories
+/// cannot be resolved or the platform query fails.
+pub fn collect(app: &AppHandle) -> AppResult<StorageInfo> {
+    let paths = app.path();
+    let cache_dir = paths
+        .app_cache_dir()
+        .map_err(|e| AppError::Platform(format!("Failed to resolve cache directory: {}", e)))?;
+    let data_dir = paths
+        .app_data_dir()
+        .map_err(|e| AppError::Platform(format!("Failed to resolve data directory: {}", e)))?;
+    let capacity = device_capacity()?;
+
+    Ok(StorageInfo {
+        total_bytes: capacity.storage_total,
+        free_bytes: capacity.storage_free,
+        app_cache_bytes: dir_size(&cache_dir),
+        app_data_bytes: dir_size(&data_dir),
+        memory_total_bytes: capacity.memory_total,
+        memory_available_bytes: capacity.memory_available,
+    })
+}
+
+/// Compute the total size of the files below `path` (bytes)
+///
+/// Symbolic links are not followed and unreadable entries are skipped.
+/// Returns `0` if `path` does not exist.
+pub fn dir_size(path: &Path) -> u64 {
+    let entries = match std::fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(_) => return 0,
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            Ok(file_type) if file_type.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dir_size() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        std::fs::write(dir.path().join("a.bin"), vec![0u8; 100]).unwrap();
+        std::fs::create_dir_all(dir.path().join("nested/deeper")).unwrap();
+        std::fs::write(dir.path().join("nested/b.bin"), vec![0u8; 20]).unwrap();
+        std::fs::write(dir.path().join("nested/deeper/c.bin"), vec![0u8; 3]).unwrap();
+
+        assert_eq!(dir_size(dir.path()), 123);
+    }
+
+    #[test]
+    fn test_dir_size_missing_directory() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        assert_eq!(dir_size(&dir.path().join("missing")), 0);
+    }
+}
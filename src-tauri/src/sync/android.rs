@@ -0,0 +1,78 @@
+/// Android-specific background sync handlers
+///
+/// This module registers the handlers running a sync while the app is not in
+/// foreground: FCM messages and a periodic `WorkManager` job. Every FCM
+/// message goes through the push pipeline (`push::on_push`, which starts the
+/// sync of `sync` pushes); the periodic job calls
+/// `sync::on_background_refresh`.
+///
+/// Note: This implementation provides the structure for Android background sync.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Register the silent push and periodic work handlers
+///
+/// # Arguments
+///
+/// * `interval_secs` - Interval of the periodic sync work (at least 15 minutes)
+/// * `sender_id` - FCM sender of the environment (`RuntimeConfig::push_sender_id`),
+///   which the app registers for push with
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn register_handlers(interval_secs: u64, sender_id: &str) -> AppResult<()> {
+    tracing::info!(
+        "[Android] Registering background sync handlers (every {}s, sender {})",
+        interval_secs,
+        sender_id
+    );
+
+    // TODO: Implement native Android background sync
+    // Example Kotlin implementation:
+    // ```kotlin
+    // // Push registration for the sender of the environment, so staging and
+    // // preprod builds receive the pushes of their own Firebase project
+    // FirebaseApp.initializeApp(context, FirebaseOptions.Builder(FirebaseOptions.fromResource(context)!!)
+    //     .setGcmSenderId(senderId)
+    //     .build())
+    //
+    // class SyncMessagingService : FirebaseMessagingService() {
+    //     override fun onMessageReceived(message: RemoteMessage) {
+    //         val payload = JSONObject(message.data as Map<*, *>)
+    //         message.data["collections"]?.let { payload.put("collections", JSONArray(it)) }
+    //         message.notification?.let {
+    //             payload.put("notification", JSONObject(mapOf("title" to it.title, "body" to it.body)))
+    //         }
+    //         // High priority messages, so expedited work is allowed
+    //         WorkManager.getInstance(this).enqueueUniqueWork("push", ExistingWorkPolicy.APPEND_OR_REPLACE,
+    //             OneTimeWorkRequestBuilder<PushWorker>()
+    //                 .setExpedited(OutOfQuotaPolicy.RUN_AS_NON_EXPEDITED_WORK_REQUEST)
+    //                 .setInputData(workDataOf("payload" to payload.toString()))
+    //                 .build())
+    //     }
+    // }
+    //
+    // class PushWorker(context: Context, params: WorkerParameters) : Worker(context, params) {
+    //     // push::on_push (not tapped)
+    //     override fun doWork(): Result =
+    //         if (rustOnPush(inputData.getString("payload") ?: "{}", false)) Result.success() else Result.retry()
+    // }
+    //
+    // class SyncWorker(context: Context, params: WorkerParameters) : Worker(context, params) {
+    //     // sync::on_background_refresh
+    //     override fun doWork(): Result = if (rustOnBackgroundRefresh()) Result.success() else Result.retry()
+    // }
+    //
+    // WorkManager.getInstance(context).enqueueUniquePeriodicWork("sync", ExistingPeriodicWorkPolicy.KEEP,
+    //     PeriodicWorkRequestBuilder<SyncWorker>(max(intervalSecs, 900), TimeUnit.SECONDS)
+    //         .setConstraints(Constraints(requiredNetworkType = NetworkType.CONNECTED))
+    //         .build())
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
@@ -0,0 +1,59 @@
+/// iOS-specific background sync handlers
+///
+/// This module registers the handlers running a sync while the app is not in
+/// foreground: silent push notifications (`content-available: 1`) and a
+/// `BGAppRefreshTask`. Remote notifications go through the push pipeline
+/// (`push::on_push`, which starts the sync of `sync` pushes); refreshes call
+/// `sync::on_background_refresh`.
+///
+/// Note: This implementation provides the structure for iOS background sync.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Register the silent push and background refresh handlers
+///
+/// # Arguments
+///
+/// * `interval_secs` - Earliest delay between two background refreshes
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn register_handlers(interval_secs: u64) -> AppResult<()> {
+    tracing::info!("[iOS] Registering background sync handlers (every {}s)", interval_secs);
+
+    // TODO: Implement native iOS background sync
+    // Requires the `remote-notification` and `fetch` background modes, and
+    // `com.elulib.mobile.sync` in `BGTaskSchedulerPermittedIdentifiers`.
+    // Example Swift implementation:
+    // ```swift
+    // func application(_ application: UIApplication, didReceiveRemoteNotification userInfo: [AnyHashable: Any],
+    //                  fetchCompletionHandler completionHandler: @escaping (UIBackgroundFetchResult) -> Void) {
+    //     // push::on_push, completes when the (coalesced) sync is done
+    //     rustOnPush(jsonString(userInfo), tapped: false) { routing in
+    //         switch routing {
+    //         case .synced: completionHandler(.newData)
+    //         case .failed: completionHandler(.failed)
+    //         default: completionHandler(.noData)
+    //         }
+    //     }
+    // }
+    //
+    // BGTaskScheduler.shared.register(forTaskWithIdentifier: "com.elulib.mobile.sync", using: nil) { task in
+    //     scheduleRefresh()
+    //     // sync::on_background_refresh
+    //     rustOnBackgroundRefresh { ok in task.setTaskCompleted(success: ok) }
+    // }
+    // func scheduleRefresh() {
+    //     let request = BGAppRefreshTaskRequest(identifier: "com.elulib.mobile.sync")
+    //     request.earliestBeginDate = Date(timeIntervalSinceNow: TimeInterval(intervalSecs))
+    //     try? BGTaskScheduler.shared.submit(request)
+    // }
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
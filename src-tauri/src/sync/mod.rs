@@ -0,0 +1,752 @@
+/// Background data sync engine
+///
+/// This module pulls a configurable set of collections (agenda, documents
+/// list) from the backend into the encrypted local database, so the web
+/// application can render cached data offline:
+/// - collections are read from the `sync.collections` remote config key
+///   (`[{ "name": "agenda", "endpoint": "https://app.elulib.com/api/..." }]`),
+///   defaulting to `SYNC_DEFAULT_COLLECTIONS`; endpoints must be on the
+///   `app_url` origin, since requests carry the session token
+/// - each collection keeps a sync cursor (`sync_cursors` table); a sync
+///   requests the changes since the cursor, page by page
+///   (`GET <endpoint>?cursor=<cursor>`, answered with
+///   `{ items, deleted, cursor, hasMore }`, as JSON or MessagePack, see
+///   `payload_encoding`), and stores the items as JSON in the `sync_records`
+///   table
+/// - a sync runs every `SYNC_INTERVAL_SECS` while the app is in foreground,
+///   when a silent push asks for it (`on_silent_push`), when the OS runs a
+///   background refresh task (`on_background_refresh`), and on demand
+///   (`sync_now` command)
+/// - a sync push is a data-only push of type `sync`
+///   (`{ "type": "sync", "collections": ["agenda"] }`, all collections if
+///   none are named); pushes received within `SYNC_PUSH_DEBOUNCE_MS` of
+///   each other, or while a sync is running, are coalesced into a single run
+///   syncing the union of their collections, which must complete within
+///   `SYNC_PUSH_TIME_BUDGET_SECS` (the time the OS grants to a background
+///   push)
+/// - each run emits a `sync://completed` event with the per-collection
+///   results (queued in the `outbox` while no page is ready); the web
+///   application then reads the records with `db_query`, and the watch
+///   companion context is published again (`watch`)
+///
+/// Syncs only run when a session token is stored, and can be disabled
+/// remotely with the `background_sync` feature flag. Synced records and
+/// cursors are deleted on logout (`discard`), so the next account starts
+/// from an empty cache.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Url};
+use tokio::sync::oneshot;
+
+use crate::breadcrumbs;
+use crate::constants;
+use crate::database::Database;
+use crate::feature_flags;
+use crate::http;
+use crate::lifecycle::LifecycleTracker;
+use crate::outbox;
+use crate::payload_encoding;
+use crate::remote_config::{keys, ConfigStore};
+use crate::runtime_config;
+use crate::session_handoff;
+#[cfg(feature = "module-watch")]
+use crate::watch;
+use crate::{AppError, AppResult};
+
+/// Collection synced into the local database
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SyncCollection {
+    /// Collection name, as stored in the `collection` column
+    pub name: String,
+    /// Endpoint returning the changes of the collection
+    pub endpoint: String,
+}
+
+/// What started a sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncTrigger {
+    /// Periodic sync while in foreground
+    Schedule,
+    /// Silent push notification
+    Push,
+    /// Background refresh task run by the OS
+    Background,
+    /// `sync_now` command
+    Manual,
+}
+
+/// Page of changes returned by a sync endpoint
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncPage {
+    /// Created or updated records, each with a string `id` and an optional
+    /// `updatedAt` timestamp
+    #[serde(default)]
+    pub items: Vec<Value>,
+    /// Identifiers of deleted records
+    #[serde(default)]
+    pub deleted: Vec<String>,
+    /// Cursor to send with the next request
+    pub cursor: Option<String>,
+    /// More changes are available after this page
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+/// Outcome of the sync of a collection
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionResult {
+    /// Collection name
+    pub name: String,
+    /// Number of created or updated records
+    pub upserted: usize,
+    /// Number of deleted records
+    pub deleted: usize,
+    /// Error message, if the sync of the collection failed
+    pub error: Option<String>,
+}
+
+/// Payload of the `sync://completed` event, also returned by `sync_now`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    /// What started the sync
+    pub trigger: SyncTrigger,
+    /// Outcome of each synced collection
+    pub collections: Vec<CollectionResult>,
+    /// Time the sync completed (seconds since the Unix epoch)
+    pub completed_at: u64,
+}
+
+/// Sync pushes waiting for the same run
+#[derive(Debug, Default)]
+struct PushBatch {
+    /// Collections to sync, all if `None`
+    collections: Option<BTreeSet<String>>,
+    /// Pushes waiting for the report, other than the first one
+    waiters: Vec<oneshot::Sender<Result<SyncReport, String>>>,
+}
+
+/// Sync state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct SyncState {
+    running: AtomicBool,
+    /// Set by the local data wipe: no sync runs for the rest of the process
+    stopped: AtomicBool,
+    /// Sync pushes waiting to be coalesced, if any
+    push_batch: Mutex<Option<PushBatch>>,
+}
+
+/// Clears the running flag when a sync ends, even on error
+struct RunningGuard<'a>(&'a AtomicBool);
+
+impl Drop for RunningGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Default collections (`SYNC_DEFAULT_COLLECTIONS`)
+pub fn default_collections() -> Vec<SyncCollection> {
+    constants::SYNC_DEFAULT_COLLECTIONS
+        .iter()
+        .map(|(name, endpoint)| SyncCollection {
+            name: name.to_string(),
+            endpoint: http::endpoint(endpoint),
+        })
+        .collect()
+}
+
+/// Keep the collections whose endpoint is on the web application origin
+///
+/// The session token is sent with each request: it must never leave the
+/// backend origin, whatever the remote config says.
+pub fn trusted_collections(collections: Vec<SyncCollection>, app_url: &str) -> Vec<SyncCollection> {
+    let origin = Url::parse(app_url).map(|url| url.origin()).ok();
+    collections
+        .into_iter()
+        .filter(|collection| {
+            let trusted = Url::parse(&collection.endpoint)
+                .is_ok_and(|url| url.scheme() == "https" && Some(url.origin()) == origin);
+            if !trusted {
+                tracing::warn!("Ignoring sync collection {} with untrusted endpoint", collection.name);
+            }
+            trusted
+        })
+        .collect()
+}
+
+/// Collections to sync, from the remote config or the defaults
+pub fn collections(app: &AppHandle) -> Vec<SyncCollection> {
+    let configured = app
+        .state::<ConfigStore>()
+        .get_typed::<Vec<SyncCollection>>(keys::SYNC_COLLECTIONS)
+        .unwrap_or_else(default_collections);
+    trusted_collections(configured, &runtime_config::get().app_url)
+}
+
+/// Sync cursor of a collection, if it was synced before
+pub fn cursor(conn: &Connection, collection: &str) -> AppResult<Option<String>> {
+    conn.query_row(
+        "SELECT cursor FROM sync_cursors WHERE collection = ?",
+        params![collection],
+        |row| row.get(0),
+    )
+    .optional()
+    .map(Option::flatten)
+    .map_err(|e| AppError::Database(format!("Failed to read sync cursor: {}", e)))
+}
+
+/// Apply a page of changes to a collection, and store its cursor
+///
+/// The page is applied in a single transaction, so the cursor never gets
+/// ahead of the stored records.
+///
+/// # Returns
+///
+/// Returns the number of upserted and deleted records, or an error
+/// if a record has no `id` or the database update failed.
+pub fn apply_page(conn: &Connection, collection: &str, page: &SyncPage, now: u64) -> AppResult<(usize, usize)> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| AppError::Database(format!("Failed to start sync transaction: {}", e)))?;
+    for item in &page.items {
+        let id = item
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| AppError::Database(format!("Sync record of {} without id", collection)))?;
+        let updated_at = item.get("updatedAt").and_then(Value::as_i64).unwrap_or(now as i64);
+        tx.execute(
+            "INSERT INTO sync_records (collection, id, updated_at, data) VALUES (?, ?, ?, ?)
+             ON CONFLICT (collection, id) DO UPDATE SET updated_at = excluded.updated_at, data = excluded.data",
+            params![collection, id, updated_at, item.to_string()],
+        )
+        .map_err(|e| AppError::Database(format!("Failed to store sync record: {}", e)))?;
+    }
+    let mut deleted = 0;
+    for id in &page.deleted {
+        deleted += tx
+            .execute(
+                "DELETE FROM sync_records WHERE collection = ? AND id = ?",
+                params![collection, id],
+            )
+            .map_err(|e| AppError::Database(format!("Failed to delete sync record: {}", e)))?;
+    }
+    tx.execute(
+        "INSERT INTO sync_cursors (collection, cursor, synced_at) VALUES (?, ?, ?)
+         ON CONFLICT (collection) DO UPDATE SET cursor = excluded.cursor, synced_at = excluded.synced_at",
+        params![collection, page.cursor, now as i64],
+    )
+    .map_err(|e| AppError::Database(format!("Failed to store sync cursor: {}", e)))?;
+    tx.commit().map_err(|e| AppError::Database(format!("Failed to commit sync: {}", e)))?;
+    Ok((page.items.len(), deleted))
+}
+
+/// Delete every synced record and cursor
+///
+/// # Returns
+///
+/// Returns the number of deleted records.
+pub fn clear(conn: &Connection) -> AppResult<usize> {
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| AppError::Database(format!("Failed to start sync transaction: {}", e)))?;
+    let deleted = tx
+        .execute("DELETE FROM sync_records", [])
+        .map_err(|e| AppError::Database(format!("Failed to clear sync records: {}", e)))?;
+    tx.execute("DELETE FROM sync_cursors", [])
+        .map_err(|e| AppError::Database(format!("Failed to clear sync cursors: {}", e)))?;
+    tx.commit().map_err(|e| AppError::Database(format!("Failed to commit sync: {}", e)))?;
+    Ok(deleted)
+}
+
+/// Delete the synced data of the signed out account
+///
+/// # Returns
+///
+/// Returns the number of deleted records.
+pub fn discard(app: &AppHandle) -> AppResult<usize> {
+    app.state::<Database>().with_connection(app, clear)
+}
+
+/// Sync a collection, page by page (at most `SYNC_MAX_PAGES`)
+async fn sync_collection(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    token: &str,
+    collection: &SyncCollection,
+) -> AppResult<(usize, usize)> {
+    let database = app.state::<Database>();
+    let mut totals = (0, 0);
+
+    for _ in 0..constants::SYNC_MAX_PAGES {
+        let cursor = database.with_connection(app, |conn| cursor(conn, &collection.name))?;
+        let mut request = client
+            .get(&collection.endpoint)
+            .bearer_auth(token)
+            .header(reqwest::header::ACCEPT, constants::MSGPACK_ACCEPT);
+        if let Some(cursor) = &cursor {
+            request = request.query(&[("cursor", cursor)]);
+        }
+        let response = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::Database(format!("Sync request failed: {}", e)))?;
+        let page: SyncPage = payload_encoding::read_response(response)
+            .await
+            .map_err(|e| AppError::Network(format!("Invalid sync response: {}", e)))?;
+        if app.state::<SyncState>().stopped.load(Ordering::SeqCst) {
+            return Err(AppError::Database("Sync stopped".to_string()));
+        }
+
+        let (upserted, deleted) =
+            database.with_connection(app, |conn| apply_page(conn, &collection.name, &page, now_secs()))?;
+        totals = (totals.0 + upserted, totals.1 + deleted);
+        if !page.has_more {
+            return Ok(totals);
+        }
+    }
+    tracing::warn!("Sync of {} stopped after {} pages", collection.name, constants::SYNC_MAX_PAGES);
+    Ok(totals)
+}
+
+/// Run a sync and emit `sync://completed`
+///
+/// # Arguments
+///
+/// * `trigger` - What started the sync
+/// * `only` - Names of the collections to sync, all if `None`
+///
+/// # Returns
+///
+/// Returns the sync report (collections that failed carry their error), or
+/// an error if the sync is disabled, already running, or no session
+/// is stored.
+pub async fn run(app: &AppHandle, trigger: SyncTrigger, only: Option<&[String]>) -> AppResult<SyncReport> {
+    let state = app.state::<SyncState>();
+    if state.running.swap(true, Ordering::SeqCst) {
+        return Err(AppError::Database("Sync already running".to_string()));
+    }
+    let _running = RunningGuard(&state.running);
+    run_claimed(app, trigger, only).await
+}
+
+/// Run a sync, with the running flag already set by the caller
+async fn run_claimed(app: &AppHandle, trigger: SyncTrigger, only: Option<&[String]>) -> AppResult<SyncReport> {
+    if !feature_flags::is_enabled(app, feature_flags::BACKGROUND_SYNC) {
+        return Err(AppError::Database("Background sync is disabled".to_string()));
+    }
+    if app.state::<SyncState>().stopped.load(Ordering::SeqCst) {
+        return Err(AppError::Database("Sync stopped".to_string()));
+    }
+
+    let token = session_handoff::session_token(app)?.ok_or_else(|| AppError::Database("No session to sync".to_string()))?;
+    let client = http::client()?;
+    let mut results = Vec::new();
+    for collection in collections(app) {
+        if only.is_some_and(|only| !only.contains(&collection.name)) {
+            continue;
+        }
+        let result = match sync_collection(app, &client, &token, &collection).await {
+            Ok((upserted, deleted)) => CollectionResult {
+                name: collection.name,
+                upserted,
+                deleted,
+                error: None,
+            },
+            Err(e) => {
+                tracing::warn!("Sync of {} failed: {}", collection.name, e);
+                CollectionResult {
+                    name: collection.name,
+                    error: Some(e.to_string()),
+                    ..Default::default()
+                }
+            }
+        };
+        results.push(result);
+    }
+
+    let report = SyncReport {
+        trigger,
+        collections: results,
+        completed_at: now_secs(),
+    };
+    let failed = report.collections.iter().filter(|result| result.error.is_some()).count();
+    tracing::info!("Sync ({:?}) completed: {} collection(s), {} failed", trigger, report.collections.len(), failed);
+    breadcrumbs::breadcrumb("sync", &format!("{:?} sync, {} failed", trigger, failed));
+    outbox::post(app, &report);
+    #[cfg(feature = "module-watch")]
+    watch::publish(app);
+    Ok(report)
+}
+
+/// Returns `true` if a push payload is a data-only push of type `sync`
+///
+/// Pushes displaying an alert (`aps.alert` on iOS, `notification` on
+/// Android) are user-visible notifications and never start a sync.
+pub fn is_sync_push(payload: &Value) -> bool {
+    let visible = payload.pointer("/aps/alert").is_some() || payload.get("notification").is_some();
+    !visible && payload.get("type").and_then(Value::as_str) == Some(constants::SYNC_PUSH_TYPE)
+}
+
+/// Collections named by a sync push payload
+/// (`{ "type": "sync", "collections": ["agenda"] }`), all if the payload does
+/// not name any
+pub fn push_collections(payload: &Value) -> Option<Vec<String>> {
+    let names: Vec<String> = payload
+        .get("collections")?
+        .as_array()?
+        .iter()
+        .filter_map(|name| name.as_str().map(str::to_string))
+        .collect();
+    (!names.is_empty()).then_some(names)
+}
+
+/// Merge the collections of a push into those of a batch (`None` for all)
+pub fn merge_collections(batch: Option<BTreeSet<String>>, push: Option<Vec<String>>) -> Option<BTreeSet<String>> {
+    let mut batch = batch?;
+    batch.extend(push?);
+    Some(batch)
+}
+
+/// Claim the running flag and take the push batch, if no sync is running
+///
+/// Both happen under the batch lock, so no other sync starts in between and
+/// no push joins a batch already taken.
+fn claim_batch(state: &SyncState) -> AppResult<Option<(PushBatch, RunningGuard<'_>)>> {
+    let mut pending = state.push_batch.lock().map_err(|e| AppError::Database(e.to_string()))?;
+    if state.running.swap(true, Ordering::SeqCst) {
+        return Ok(None);
+    }
+    Ok(Some((pending.take().unwrap_or_default(), RunningGuard(&state.running))))
+}
+
+/// Run the sync of a push batch, once no other sync is running
+async fn run_batch(app: &AppHandle) -> AppResult<SyncReport> {
+    let state = app.state::<SyncState>();
+    let (batch, _running) = loop {
+        tokio::time::sleep(Duration::from_millis(constants::SYNC_PUSH_DEBOUNCE_MS)).await;
+        if let Some(claimed) = claim_batch(&state)? {
+            break claimed;
+        }
+    };
+
+    let only: Option<Vec<String>> = batch.collections.map(|names| names.into_iter().collect());
+    let result = run_claimed(app, SyncTrigger::Push, only.as_deref()).await;
+    if !batch.waiters.is_empty() {
+        tracing::debug!("Coalesced {} sync push(es) into one sync", batch.waiters.len());
+    }
+    for waiter in batch.waiters {
+        let _ = waiter.send(result.as_ref().map(Clone::clone).map_err(ToString::to_string));
+    }
+    result
+}
+
+/// Handle a silent push notification
+///
+/// Called by the native push handlers, which must wait for the sync to
+/// complete before reporting the end of the background work to the OS. The
+/// first push of a burst runs the sync; the following ones wait for its
+/// report.
+///
+/// # Arguments
+///
+/// * `payload` - Data of the push notification
+///
+/// # Returns
+///
+/// Returns the sync report, `None` if the push is not a sync push, or an
+/// error if the sync failed or did not complete in time.
+pub async fn on_silent_push(app: &AppHandle, payload: &Value) -> AppResult<Option<SyncReport>> {
+    if !is_sync_push(payload) {
+        return Ok(None);
+    }
+    let collections = push_collections(payload);
+    let state = app.state::<SyncState>();
+
+    let waiter = {
+        let mut pending = state.push_batch.lock().map_err(|e| AppError::Database(e.to_string()))?;
+        match pending.as_mut() {
+            Some(batch) => {
+                batch.collections = merge_collections(batch.collections.take(), collections);
+                let (sender, receiver) = oneshot::channel();
+                batch.waiters.push(sender);
+                Some(receiver)
+            }
+            None => {
+                *pending = Some(PushBatch {
+                    collections: collections.map(BTreeSet::from_iter),
+                    waiters: Vec::new(),
+                });
+                None
+            }
+        }
+    };
+
+    let budget = Duration::from_secs(constants::SYNC_PUSH_TIME_BUDGET_SECS);
+    let result = match waiter {
+        Some(receiver) => tokio::time::timeout(budget, receiver)
+            .await
+            .map_err(|_| AppError::Database("Push sync did not complete in time".to_string()))?
+            .map_err(|_| AppError::Database("Push sync did not complete".to_string()))?
+            .map_err(|e| AppError::Database(format!("Coalesced sync failed: {}", e))),
+        None => {
+            let result = tokio::time::timeout(budget, run_batch(app)).await;
+            if result.is_err() {
+                // Pushes still waiting get their sender dropped
+                if let Ok(mut pending) = state.push_batch.lock() {
+                    pending.take();
+                }
+            }
+            result.map_err(|_| AppError::Database("Push sync did not complete in time".to_string()))?
+        }
+    };
+    result.map(Some)
+}
+
+/// Handle a background refresh task
+///
+/// Called by the native background task handlers, which must wait for the
+/// sync to complete before reporting the end of the task to the OS.
+pub async fn on_background_refresh(app: &AppHandle) -> AppResult<SyncReport> {
+    run(app, SyncTrigger::Background, None).await
+}
+
+/// Start the sync scheduler
+///
+/// Syncs every `SYNC_INTERVAL_SECS` while the app is in foreground, and
+/// registers the native silent push and background refresh handlers.
+pub fn start(app: &AppHandle) {
+    if let Err(e) = register_native_handlers() {
+        tracing::warn!("Failed to register background sync handlers: {}", e);
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(constants::SYNC_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if app.state::<SyncState>().stopped.load(Ordering::SeqCst) {
+                break;
+            }
+            if app.state::<LifecycleTracker>().is_background()
+                || !feature_flags::is_enabled(&app, feature_flags::BACKGROUND_SYNC)
+            {
+                continue;
+            }
+            if let Err(e) = run(&app, SyncTrigger::Schedule, None).await {
+                tracing::debug!("Scheduled sync skipped: {}", e);
+            }
+        }
+    });
+}
+
+/// Stop syncing for the rest of the process (local data wipe)
+///
+/// A sync in progress stops before writing its next page.
+pub fn stop(app: &AppHandle) {
+    app.state::<SyncState>().stopped.store(true, Ordering::SeqCst);
+}
+
+/// Register the native silent push and background refresh handlers
+fn register_native_handlers() -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::register_handlers(constants::SYNC_INTERVAL_SECS)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::register_handlers(constants::SYNC_INTERVAL_SECS, &runtime_config::get().push_sender_id)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{self, MIGRATIONS};
+    use serde_json::json;
+
+    fn connection(dir: &tempfile::TempDir) -> Connection {
+        let mut conn = database::open(
+            &dir.path().join("test.db"),
+            "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff",
+        )
+        .unwrap();
+        database::migrate(&mut conn, MIGRATIONS).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_apply_pages_and_cursor() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = connection(&dir);
+        assert_eq!(cursor(&conn, "agenda").unwrap(), None);
+
+        let first: SyncPage = serde_json::from_value(json!({
+            "items": [
+                { "id": "evt-1", "updatedAt": 100, "title": "Conseil municipal" },
+                { "id": "evt-2", "updatedAt": 110, "title": "Commission" }
+            ],
+            "cursor": "c1",
+            "hasMore": true
+        }))
+        .unwrap();
+        assert_eq!(apply_page(&conn, "agenda", &first, 1000).unwrap(), (2, 0));
+        assert_eq!(cursor(&conn, "agenda").unwrap().as_deref(), Some("c1"));
+
+        let second = SyncPage {
+            items: vec![json!({ "id": "evt-1", "updatedAt": 120, "title": "Conseil municipal (reporté)" })],
+            deleted: vec!["evt-2".to_string(), "unknown".to_string()],
+            cursor: Some("c2".to_string()),
+            has_more: false,
+        };
+        assert_eq!(apply_page(&conn, "agenda", &second, 1001).unwrap(), (1, 1));
+
+        let rows = database::query(&conn, "SELECT id, updated_at, data FROM sync_records WHERE collection = 'agenda'", &[]).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["updated_at"], json!(120));
+        assert!(rows[0]["data"].as_str().unwrap().contains("reporté"));
+        assert_eq!(cursor(&conn, "agenda").unwrap().as_deref(), Some("c2"));
+        assert_eq!(cursor(&conn, "documents").unwrap(), None, "Cursors are per collection");
+    }
+
+    #[test]
+    fn test_clear_deletes_records_and_cursors() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = connection(&dir);
+        let page = SyncPage {
+            items: vec![json!({ "id": "evt-1" }), json!({ "id": "evt-2" })],
+            cursor: Some("c1".to_string()),
+            ..Default::default()
+        };
+        apply_page(&conn, "agenda", &page, 1000).unwrap();
+
+        assert_eq!(clear(&conn).unwrap(), 2);
+        assert!(database::query(&conn, "SELECT * FROM sync_records", &[]).unwrap().is_empty());
+        assert_eq!(cursor(&conn, "agenda").unwrap(), None, "The next sync starts over");
+    }
+
+    #[test]
+    fn test_msgpack_pages_match_json_pages() {
+        let page = json!({
+            "items": [{ "id": "doc-1", "updatedAt": 100, "title": "Budget primitif" }],
+            "deleted": ["doc-0"],
+            "cursor": "c1",
+            "hasMore": true
+        });
+        let packed = payload_encoding::encode(&page).unwrap();
+        let decoded: SyncPage = payload_encoding::decode_body(Some(constants::MSGPACK_CONTENT_TYPE), &packed).unwrap();
+        assert_eq!(decoded, serde_json::from_value::<SyncPage>(page).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_page_is_rolled_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = connection(&dir);
+        let page = SyncPage {
+            items: vec![json!({ "id": "doc-1" }), json!({ "title": "No id" })],
+            cursor: Some("c1".to_string()),
+            ..Default::default()
+        };
+        assert!(apply_page(&conn, "documents", &page, 1000).is_err());
+        assert_eq!(cursor(&conn, "documents").unwrap(), None);
+        assert!(database::query(&conn, "SELECT * FROM sync_records", &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_untrusted_endpoints_are_ignored() {
+        let collections = vec![
+            SyncCollection {
+                name: "agenda".to_string(),
+                endpoint: "https://app.elulib.com/api/mobile/sync/agenda".to_string(),
+            },
+            SyncCollection {
+                name: "leak".to_string(),
+                endpoint: "https://attacker.example.com/collect".to_string(),
+            },
+            SyncCollection {
+                name: "plain".to_string(),
+                endpoint: "http://app.elulib.com/api/mobile/sync/documents".to_string(),
+            },
+        ];
+        let trusted = trusted_collections(collections, "https://app.elulib.com");
+        assert_eq!(trusted.len(), 1);
+        assert_eq!(trusted[0].name, "agenda");
+    }
+
+    #[test]
+    fn test_push_collections() {
+        let push = json!({ "type": "sync", "collections": ["agenda"] });
+        assert_eq!(push_collections(&push), Some(vec!["agenda".to_string()]));
+        assert_eq!(push_collections(&json!({ "type": "sync", "collections": [] })), None);
+        assert_eq!(push_collections(&json!({ "type": "sync" })), None);
+    }
+
+    #[test]
+    fn test_only_data_only_sync_pushes_start_a_sync() {
+        assert!(is_sync_push(&json!({ "type": "sync" })));
+        assert!(is_sync_push(&json!({ "type": "sync", "aps": { "content-available": 1 } })));
+        assert!(!is_sync_push(&json!({ "type": "sync", "aps": { "alert": "Nouvelle séance" } })));
+        assert!(!is_sync_push(&json!({ "type": "sync", "notification": { "title": "Nouvelle séance" } })));
+        assert!(!is_sync_push(&json!({ "type": "message" })));
+        assert!(!is_sync_push(&json!({})));
+    }
+
+    #[test]
+    fn test_merge_push_collections() {
+        let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        let batch = Some(BTreeSet::from_iter(names(&["agenda"])));
+        assert_eq!(
+            merge_collections(batch.clone(), Some(names(&["documents", "agenda"]))),
+            Some(BTreeSet::from_iter(names(&["agenda", "documents"])))
+        );
+        assert_eq!(merge_collections(batch, None), None, "A push for all collections widens the batch");
+        assert_eq!(merge_collections(None, Some(names(&["agenda"]))), None);
+    }
+
+    #[test]
+    fn test_batch_is_taken_only_when_no_sync_runs() {
+        let state = SyncState::default();
+        *state.push_batch.lock().unwrap() = Some(PushBatch::default());
+
+        state.running.store(true, Ordering::SeqCst);
+        assert!(claim_batch(&state).unwrap().is_none());
+        assert!(state.push_batch.lock().unwrap().is_some(), "Batch is left for later");
+
+        state.running.store(false, Ordering::SeqCst);
+        let claimed = claim_batch(&state).unwrap();
+        assert!(claimed.is_some());
+        assert!(state.running.load(Ordering::SeqCst), "Claimed with the batch");
+        assert!(state.push_batch.lock().unwrap().is_none());
+        drop(claimed);
+        assert!(!state.running.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,67 @@
+/// Android-specific binary integrity checks
+///
+/// This module reads the SHA-256 fingerprint of the APK signing certificate,
+/// detects an attached Java debugger (JDWP) and shows the "app modified"
+/// screen. Native debuggers are detected from `/proc/self/status` in the
+/// parent module.
+///
+/// Note: This implementation provides the structure for the Android self-check.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Get the SHA-256 fingerprint of the signing certificate
+///
+/// # Returns
+///
+/// Returns the hex fingerprint, `None` if unavailable, or an error if
+/// the lookup fails.
+pub fn signing_identity() -> AppResult<Option<String>> {
+    // TODO: Implement native Android lookup
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val info = context.packageManager.getPackageInfo(
+    //     context.packageName, PackageManager.GET_SIGNING_CERTIFICATES)
+    // val cert = info.signingInfo.apkContentsSigners.first().toByteArray()
+    // return MessageDigest.getInstance("SHA-256").digest(cert).joinToString("") { "%02x".format(it) }
+    // ```
+
+    // Placeholder: Report the identity as unavailable
+    // Replace this with actual native implementation
+    Ok(None)
+}
+
+/// Returns `true` if a Java debugger is connected
+pub fn java_debugger_connected() -> bool {
+    // TODO: Implement native Android check
+    // Example Kotlin implementation:
+    // ```kotlin
+    // return Debug.isDebuggerConnected() || Debug.waitingForDebugger()
+    // ```
+
+    // Placeholder: Report no debugger
+    // Replace this with actual native implementation
+    false
+}
+
+/// Show the "app modified" screen above the webview
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn show_tampered() -> AppResult<()> {
+    tracing::info!("[Android] Showing app modified screen");
+
+    // TODO: Implement native Android tampered screen
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val screen = TamperedView(activity) // "Application modifiée" + store button
+    // screen.onReinstall = { activity.startActivity(Intent(Intent.ACTION_VIEW, playStoreUri)) }
+    // (activity.window.decorView as ViewGroup).addView(screen)
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
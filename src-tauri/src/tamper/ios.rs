@@ -0,0 +1,75 @@
+/// iOS-specific binary integrity checks
+///
+/// This module reads the team identifier the app is signed with, detects an
+/// attached debugger (`P_TRACED` flag from `sysctl`) and shows the "app
+/// modified" screen.
+///
+/// Note: This implementation provides the structure for the iOS self-check.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Get the team identifier of the app signature
+///
+/// # Returns
+///
+/// Returns the team identifier, `None` if unavailable, or an error if
+/// the lookup fails.
+pub fn signing_identity() -> AppResult<Option<String>> {
+    // TODO: Implement native iOS lookup
+    // The team identifier is the prefix of the application identifier
+    // entitlement, read from the app's own code signature.
+    // Example Swift implementation:
+    // ```swift
+    // var code: SecCode?
+    // SecCodeCopySelf([], &code)
+    // var staticCode: SecStaticCode?
+    // SecCodeCopyStaticCode(code!, [], &staticCode)
+    // var info: CFDictionary?
+    // SecCodeCopySigningInformation(staticCode!, SecCSFlags(rawValue: kSecCSSigningInformation), &info)
+    // return (info as? [String: Any])?[kSecCodeInfoTeamIdentifier as String] as? String
+    // ```
+
+    // Placeholder: Report the identity as unavailable
+    // Replace this with actual native implementation
+    Ok(None)
+}
+
+/// Returns `true` if a debugger is attached to the process
+pub fn debugger_attached() -> bool {
+    // TODO: Implement native iOS check
+    // Example Swift implementation:
+    // ```swift
+    // var info = kinfo_proc()
+    // var mib: [Int32] = [CTL_KERN, KERN_PROC, KERN_PROC_PID, getpid()]
+    // var size = MemoryLayout<kinfo_proc>.stride
+    // sysctl(&mib, UInt32(mib.count), &info, &size, nil, 0)
+    // return (info.kp_proc.p_flag & P_TRACED) != 0
+    // ```
+
+    // Placeholder: Report no debugger
+    // Replace this with actual native implementation
+    false
+}
+
+/// Show the "app modified" screen above the webview
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn show_tampered() -> AppResult<()> {
+    tracing::info!("[iOS] Showing app modified screen");
+
+    // TODO: Implement native iOS tampered screen
+    // Example Swift implementation:
+    // ```swift
+    // let screen = TamperedView(frame: window.bounds) // "Application modifiée" + store button
+    // screen.onReinstall = { UIApplication.shared.open(appStoreURL) }
+    // window.addSubview(screen)
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
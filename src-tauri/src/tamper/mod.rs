@@ -0,0 +1,278 @@
+/// Binary integrity self-check (anti-tampering)
+///
+/// At startup, release builds check that the running app has not been
+/// modified or instrumented:
+/// - the signing identity (Android signing certificate SHA-256, iOS team
+///   identifier) matches the one expected by the build (`ELULIB_SIGNING_IDENTITY`)
+/// - the iOS bundle still carries its code signature resources
+/// - no debugger is attached (`TracerPid` on Android, `P_TRACED` on iOS,
+///   plus the Java debugger on Android)
+///
+/// Findings are reported with the device integrity findings (`integrity`
+/// module), sent along with attestation tokens and emitted as an
+/// `integrity://tampered` event. Depending on the tamper response (build
+/// profile `ELULIB_TAMPER_RESPONSE`, `integrity.tamper_response` remote config
+/// key), the app only warns or blocks the webview behind a native screen.
+///
+/// Like the device integrity checks, these checks can be bypassed by a
+/// determined attacker and are a signal, not proof.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use tauri::{AppHandle, Manager};
+
+use crate::audit_log::{self, SecurityEvent};
+use crate::constants;
+use crate::events::{self, IntegrityTampered};
+use crate::integrity::{IntegrityFinding, IntegrityIndicator};
+use crate::remote_config::{keys, ConfigStore};
+use crate::AppResult;
+
+/// Code signature resources of an iOS app bundle
+const CODE_SIGNATURE_RESOURCES: &str = "_CodeSignature/CodeResources";
+
+/// Findings of the startup self-check
+static FINDINGS: OnceLock<Vec<IntegrityFinding>> = OnceLock::new();
+
+/// Response to a failed self-check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TamperResponse {
+    /// Log and report the findings, keep the app usable
+    #[default]
+    Warn,
+    /// Also block the webview behind a native screen
+    Block,
+}
+
+impl TamperResponse {
+    /// Parse a response name (`warn`, `block`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(name.trim().to_ascii_lowercase())).ok()
+    }
+}
+
+/// Tamper response, with the remote override applied
+pub fn response(app: &AppHandle) -> TamperResponse {
+    app.state::<ConfigStore>()
+        .get_typed::<String>(keys::TAMPER_RESPONSE)
+        .or_else(|| constants::TAMPER_RESPONSE.map(str::to_string))
+        .and_then(|name| TamperResponse::from_name(&name))
+        .unwrap_or_default()
+}
+
+/// Normalize a signing identity for comparison (case, `:` separators)
+fn normalize_identity(identity: &str) -> String {
+    identity
+        .chars()
+        .filter(|c| *c != ':' && !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Compare the signing identity of the running app with the expected one
+///
+/// # Arguments
+///
+/// * `actual` - Identity reported by the platform, if available
+/// * `expected` - Identity expected by the build, if configured
+pub fn signing_findings(actual: Option<&str>, expected: Option<&str>) -> Vec<IntegrityFinding> {
+    let (Some(actual), Some(expected)) = (actual, expected) else {
+        return Vec::new();
+    };
+    if normalize_identity(actual) == normalize_identity(expected) {
+        return Vec::new();
+    }
+    vec![IntegrityFinding {
+        indicator: IntegrityIndicator::SigningMismatch,
+        detail: actual.to_string(),
+    }]
+}
+
+/// Check that an iOS app bundle still carries its code signature resources
+pub fn bundle_findings(bundle: &Path) -> Vec<IntegrityFinding> {
+    if bundle.join(CODE_SIGNATURE_RESOURCES).is_file() {
+        return Vec::new();
+    }
+    vec![IntegrityFinding {
+        indicator: IntegrityIndicator::BundleModified,
+        detail: CODE_SIGNATURE_RESOURCES.to_string(),
+    }]
+}
+
+/// PID of the process tracing this one, from a `/proc/<pid>/status` listing
+pub fn tracer_pid(status: &str) -> Option<u32> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("TracerPid:"))
+        .and_then(|pid| pid.trim().parse().ok())
+        .filter(|pid| *pid != 0)
+}
+
+/// Returns `true` if a debugger is attached to the app
+pub fn debugger_attached() -> bool {
+    #[cfg(target_os = "android")]
+    {
+        let traced = std::fs::read_to_string("/proc/self/status")
+            .ok()
+            .and_then(|status| tracer_pid(&status))
+            .is_some();
+        traced || android::java_debugger_connected()
+    }
+
+    #[cfg(target_os = "ios")]
+    {
+        ios::debugger_attached()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        false
+    }
+}
+
+/// Signing identity of the running app, if the platform reports it
+fn signing_identity() -> Option<String> {
+    #[cfg(target_os = "ios")]
+    let identity = ios::signing_identity();
+
+    #[cfg(target_os = "android")]
+    let identity = android::signing_identity();
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    let identity: AppResult<Option<String>> = Ok(None);
+
+    identity
+        .map_err(|e| tracing::warn!("Failed to read signing identity: {}", e))
+        .ok()
+        .flatten()
+}
+
+/// Run the self-checks for the current platform
+fn run_checks(app: &AppHandle) -> Vec<IntegrityFinding> {
+    let mut findings = signing_findings(signing_identity().as_deref(), constants::SIGNING_IDENTITY);
+
+    if cfg!(target_os = "ios") {
+        match app.path().resource_dir() {
+            Ok(bundle) => findings.extend(bundle_findings(&bundle)),
+            Err(e) => tracing::warn!("Failed to resolve app bundle: {}", e),
+        }
+    }
+    if debugger_attached() {
+        findings.push(IntegrityFinding {
+            indicator: IntegrityIndicator::DebuggerAttached,
+            detail: "debugger".to_string(),
+        });
+    }
+    findings
+}
+
+/// Findings of the startup self-check (empty until it ran, and in debug builds)
+pub fn findings() -> Vec<IntegrityFinding> {
+    FINDINGS.get().cloned().unwrap_or_default()
+}
+
+/// Run the startup self-check (release builds only)
+///
+/// Findings are logged and emitted as an `integrity://tampered` event; with
+/// the `block` response, the native tampered screen is also shown.
+pub fn check_on_launch(app: &AppHandle) {
+    if cfg!(debug_assertions) {
+        tracing::debug!("Skipping binary integrity self-check in debug build");
+        return;
+    }
+
+    let findings = FINDINGS.get_or_init(|| run_checks(app));
+    if findings.is_empty() {
+        tracing::debug!("Binary integrity self-check passed");
+        return;
+    }
+
+    let response = response(app);
+    tracing::warn!(
+        "Binary integrity self-check found {} indicator(s), response: {:?}",
+        findings.len(),
+        response
+    );
+    audit_log::record(
+        app,
+        SecurityEvent::IntegrityFailure {
+            indicators: findings.iter().map(|finding| finding.indicator).collect(),
+        },
+    );
+    if response == TamperResponse::Block {
+        if let Err(e) = show_tampered() {
+            tracing::error!("Failed to show tampered screen: {}", e);
+        }
+    }
+    events::emit(app, &IntegrityTampered(findings.to_vec()));
+}
+
+/// Show the native "app modified" screen above the webview
+fn show_tampered() -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::show_tampered()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::show_tampered()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_findings() {
+        let expected = "AB:CD:EF:01";
+        assert!(signing_findings(Some("abcdef01"), Some(expected)).is_empty());
+        assert!(signing_findings(None, Some(expected)).is_empty(), "Unavailable identity");
+        assert!(signing_findings(Some("abcdef01"), None).is_empty(), "No expected identity");
+
+        let findings = signing_findings(Some("12:34:56:78"), Some(expected));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].indicator, IntegrityIndicator::SigningMismatch);
+    }
+
+    #[test]
+    fn test_bundle_findings() {
+        let bundle = tempfile::tempdir().expect("Failed to create temp dir");
+        let findings = bundle_findings(bundle.path());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].indicator, IntegrityIndicator::BundleModified);
+
+        std::fs::create_dir_all(bundle.path().join("_CodeSignature")).unwrap();
+        std::fs::write(bundle.path().join(CODE_SIGNATURE_RESOURCES), b"").unwrap();
+        assert!(bundle_findings(bundle.path()).is_empty());
+    }
+
+    #[test]
+    fn test_tracer_pid() {
+        let status = "Name:\telulib\nState:\tS (sleeping)\nTracerPid:\t4242\nUid:\t10123\n";
+        assert_eq!(tracer_pid(status), Some(4242));
+        assert_eq!(tracer_pid("Name:\telulib\nTracerPid:\t0\n"), None);
+        assert_eq!(tracer_pid("Name:\telulib\n"), None);
+    }
+
+    #[test]
+    fn test_tamper_response_from_name() {
+        assert_eq!(TamperResponse::from_name("Block"), Some(TamperResponse::Block));
+        assert_eq!(TamperResponse::from_name(" warn "), Some(TamperResponse::Warn));
+        assert_eq!(TamperResponse::from_name("ignore"), None);
+    }
+}
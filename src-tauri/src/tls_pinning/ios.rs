@@ -0,0 +1,71 @@
+/// iOS-specific webview TLS pinning
+///
+/// This module installs the pin set in the `WKNavigationDelegate` of the
+/// webview, which validates the server trust of the pinned host in
+/// `webView(_:didReceive:completionHandler:)`, and shows the pin-failure
+/// screen.
+///
+/// Note: This implementation provides the structure for iOS webview pinning.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use super::PinSet;
+use crate::AppResult;
+
+/// Install the pin set in the webview navigation delegate
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn install(pin_set: &PinSet) -> AppResult<()> {
+    tracing::info!("[iOS] Installing {} webview TLS pins for {}", pin_set.pins.len(), pin_set.host);
+
+    // TODO: Implement native iOS server trust validation
+    // Example Swift implementation:
+    // ```swift
+    // func webView(_ webView: WKWebView, didReceive challenge: URLAuthenticationChallenge,
+    //              completionHandler: @escaping (URLSession.AuthChallengeDisposition, URLCredential?) -> Void) {
+    //     guard challenge.protectionSpace.authenticationMethod == NSURLAuthenticationMethodServerTrust,
+    //           let trust = challenge.protectionSpace.serverTrust,
+    //           SecTrustEvaluateWithError(trust, nil) else {
+    //         return completionHandler(.performDefaultHandling, nil)
+    //     }
+    //     let chain = (SecTrustCopyCertificateChain(trust) as? [SecCertificate]) ?? []
+    //     let spkis = chain.compactMap { spkiDER(of: $0) } // ASN.1 header + SecKeyCopyExternalRepresentation
+    //     if rustEvaluateTrust(challenge.protectionSpace.host, spkis) { // tls_pinning::evaluate
+    //         completionHandler(.useCredential, URLCredential(trust: trust))
+    //     } else {
+    //         completionHandler(.cancelAuthenticationChallenge, nil)
+    //     }
+    // }
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
+
+/// Show the pin-failure screen above the webview
+///
+/// # Arguments
+///
+/// * `host` - Host whose certificate did not match
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn show_pin_failure(host: &str) -> AppResult<()> {
+    tracing::info!("[iOS] Showing pin failure screen for {}", host);
+
+    // TODO: Implement native iOS pin failure screen
+    // Example Swift implementation:
+    // ```swift
+    // let screen = PinFailureView(frame: window.bounds) // "Connexion non sécurisée" + "Réessayer" button
+    // screen.onRetry = { screen.removeFromSuperview(); webView.reload() }
+    // window.addSubview(screen)
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
@@ -0,0 +1,275 @@
+/// Webview TLS pinning module
+///
+/// This module pins the TLS certificates of the web application host (the host
+/// of `app_url`, `app.elulib.com` in production) inside the webview, so an
+/// enterprise MITM proxy with a user-installed root certificate cannot
+/// silently intercept sessions:
+/// - pins are base64 SHA-256 hashes of a certificate's SubjectPublicKeyInfo;
+///   a connection is accepted if any certificate of its chain matches a pin
+/// - the pin set is provided at build time (`ELULIB_TLS_PINS`) and can be
+///   replaced by the `tls.pins` remote config key, to rotate certificates
+///   without an app release; remote config can never disable pinning
+/// - the native hook (iOS navigation delegate, Android request interception)
+///   asks `evaluate` to validate each server trust of the pinned host
+/// - on a pin failure, the page is not loaded, a native pin-failure screen is
+///   shown and a `tls://pin-failure` event is emitted
+///
+/// Pinning is disabled when no pin set is configured (development builds).
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use base64::Engine;
+use tauri::{AppHandle, Manager};
+
+use crate::audit_log::{self, SecurityEvent};
+use crate::constants;
+use crate::events;
+use crate::remote_config::{keys, ConfigStore};
+use crate::runtime_config;
+use crate::AppResult;
+
+/// Pin set applied to the webview
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinSet {
+    /// Pinned host
+    pub host: String,
+    /// Base64 SHA-256 hashes of accepted SubjectPublicKeyInfo
+    pub pins: Vec<String>,
+}
+
+/// Pin validation failure, sent with the pin-failure event
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PinFailure {
+    /// Host whose certificate chain did not match
+    pub host: String,
+    /// SPKI hashes of the presented chain, leaf first
+    pub presented: Vec<String>,
+}
+
+/// Returns `true` if the value is a base64 SHA-256 hash
+pub fn is_valid_pin(pin: &str) -> bool {
+    base64::engine::general_purpose::STANDARD
+        .decode(pin.trim())
+        .is_ok_and(|hash| hash.len() == 32)
+}
+
+/// Parse a list of pins, ignoring invalid entries
+pub fn parse_pins<'a>(pins: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    pins.into_iter()
+        .map(str::trim)
+        .filter(|pin| !pin.is_empty())
+        .filter_map(|pin| {
+            if is_valid_pin(pin) {
+                Some(pin.to_string())
+            } else {
+                tracing::warn!("Ignoring invalid TLS pin: {}", pin);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Pin hash of a DER-encoded SubjectPublicKeyInfo
+pub fn spki_hash(spki_der: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(ring::digest::digest(&ring::digest::SHA256, spki_der))
+}
+
+/// Effective pins
+///
+/// # Arguments
+///
+/// * `build` - Pins provided at build time
+/// * `remote` - Pins from remote config, if set
+///
+/// A remote pin set replaces the build pins, unless it has no valid pin.
+pub fn effective_pins(build: Vec<String>, remote: Option<Vec<String>>) -> Vec<String> {
+    match remote.map(|remote| parse_pins(remote.iter().map(String::as_str))) {
+        Some(remote) if !remote.is_empty() => remote,
+        _ => build,
+    }
+}
+
+/// Check the certificate chain presented by a host
+///
+/// # Arguments
+///
+/// * `pin_set` - Pins of the pinned host
+/// * `host` - Host of the connection
+/// * `chain` - SPKI hashes of the presented chain
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the host is not pinned, pinning is not configured, or
+/// a certificate of the chain matches a pin; otherwise the failure.
+pub fn check_chain(pin_set: &PinSet, host: &str, chain: &[String]) -> Result<(), PinFailure> {
+    if !host.eq_ignore_ascii_case(&pin_set.host) || pin_set.pins.is_empty() {
+        return Ok(());
+    }
+    if chain.iter().any(|hash| pin_set.pins.contains(hash)) {
+        return Ok(());
+    }
+    Err(PinFailure {
+        host: host.to_string(),
+        presented: chain.to_vec(),
+    })
+}
+
+/// Current pin set, with remote overrides applied
+pub fn pin_set(app: &AppHandle) -> PinSet {
+    let build = parse_pins(constants::TLS_PINS.unwrap_or_default().split(','));
+    let remote = app.state::<ConfigStore>().get_typed::<Vec<String>>(keys::TLS_PINS);
+    let host = tauri::Url::parse(&runtime_config::get().app_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default();
+
+    PinSet {
+        host,
+        pins: effective_pins(build, remote),
+    }
+}
+
+/// Install (or update) the pin set in the native webview hook
+pub fn refresh(app: &AppHandle) {
+    let pin_set = pin_set(app);
+    if pin_set.pins.is_empty() {
+        tracing::warn!("Webview TLS pinning disabled: no pins configured");
+    }
+    if let Err(e) = install(&pin_set) {
+        tracing::error!("Failed to install webview TLS pins: {}", e);
+    }
+}
+
+/// Validate a server trust, called by the native webview hook
+///
+/// # Arguments
+///
+/// * `host` - Host of the connection
+/// * `chain` - DER-encoded SubjectPublicKeyInfo of each certificate, leaf first
+///
+/// # Returns
+///
+/// Returns `true` if the connection may proceed. On a pin failure, the
+/// pin-failure screen is shown and the event emitted.
+pub fn evaluate(app: &AppHandle, host: &str, chain: &[Vec<u8>]) -> bool {
+    let hashes: Vec<String> = chain.iter().map(|spki| spki_hash(spki)).collect();
+    match check_chain(&pin_set(app), host, &hashes) {
+        Ok(()) => true,
+        Err(failure) => {
+            on_pin_failure(app, &failure);
+            false
+        }
+    }
+}
+
+/// Block the app behind the pin-failure screen
+fn on_pin_failure(app: &AppHandle, failure: &PinFailure) {
+    tracing::error!(
+        "TLS pin validation failed for {} (presented: {})",
+        failure.host,
+        failure.presented.join(", ")
+    );
+    audit_log::record(
+        app,
+        SecurityEvent::PinFailure {
+            host: failure.host.clone(),
+        },
+    );
+    if let Err(e) = show_pin_failure(&failure.host) {
+        tracing::error!("Failed to show pin failure screen: {}", e);
+    }
+    events::emit(app, failure);
+}
+
+/// Pass the pin set to the native webview hook
+fn install(pin_set: &PinSet) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::install(pin_set)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::install(pin_set)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        tracing::debug!("Webview TLS pinning not supported on this platform ({} pins)", pin_set.pins.len());
+        Ok(())
+    }
+}
+
+/// Show the native pin-failure screen above the webview
+fn show_pin_failure(host: &str) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::show_pin_failure(host)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::show_pin_failure(host)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = host;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIN_A: &str = "r/mIkG3eEpVdm+u/ko/cwxzOMo1bk4TyHIlByibiA5E=";
+    const PIN_B: &str = "YLh1dUR9y6Kja30RrAn7JKnbQG/uEtLMkBgFF2Fuihg=";
+
+    fn pin_set() -> PinSet {
+        PinSet {
+            host: "app.elulib.com".to_string(),
+            pins: vec![PIN_A.to_string()],
+        }
+    }
+
+    #[test]
+    fn test_parse_pins() {
+        assert_eq!(parse_pins([PIN_A, " ", "not-a-pin", "AAAA"]), vec![PIN_A.to_string()]);
+    }
+
+    #[test]
+    fn test_spki_hash() {
+        assert_eq!(spki_hash(b""), "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=");
+        assert!(is_valid_pin(&spki_hash(b"spki")));
+    }
+
+    #[test]
+    fn test_remote_pins_replace_build_pins() {
+        let build = vec![PIN_A.to_string()];
+        assert_eq!(effective_pins(build.clone(), None), build);
+        assert_eq!(effective_pins(build.clone(), Some(vec![PIN_B.to_string()])), vec![PIN_B.to_string()]);
+        assert_eq!(effective_pins(build.clone(), Some(vec![])), build, "Cannot disable pinning");
+        assert_eq!(effective_pins(build.clone(), Some(vec!["bad".to_string()])), build);
+    }
+
+    #[test]
+    fn test_check_chain() {
+        let pins = pin_set();
+        assert!(check_chain(&pins, "app.elulib.com", &[PIN_B.to_string(), PIN_A.to_string()]).is_ok());
+        assert!(check_chain(&pins, "APP.elulib.com", &[PIN_A.to_string()]).is_ok());
+
+        let failure = check_chain(&pins, "app.elulib.com", &[PIN_B.to_string()]).unwrap_err();
+        assert_eq!(failure.presented, vec![PIN_B.to_string()]);
+
+        assert!(check_chain(&pins, "cdn.example.org", &[PIN_B.to_string()]).is_ok(), "Host not pinned");
+        let unpinned = PinSet { pins: vec![], ..pin_set() };
+        assert!(check_chain(&unpinned, "app.elulib.com", &[PIN_B.to_string()]).is_ok());
+    }
+}
@@ -0,0 +1,451 @@
+/// Tracing to log bridge
+///
+/// The app logs with `tracing`, so work can be grouped in spans: each Tauri
+/// command runs in a `command` span, each connectivity attempt in a
+/// `connectivity_attempt` span. This module installs the global `tracing`
+/// subscriber (`install`, first thing in `run()`), which forwards events to
+/// the `log` facade, so they reach the existing log plugin targets (stdout,
+/// webview, log files, shipping, crash breadcrumbs) with the usual level
+/// filter and redaction:
+/// - events are prefixed with their span scope, outermost first, so causality
+///   is visible (`command{name=keychain_store}: Keychain unavailable`)
+/// - closing a span logs its duration at debug level
+///   (`command{name=keychain_store}: closed after 12ms`)
+/// - closing a `command` span records the invocation in `command_metrics`,
+///   as failed if the command resolved with an error (`fail_command`)
+/// - the spans of Tauri itself (`tauri::*` targets) only link a command to
+///   the future running it: their fields, which hold IPC payloads, are
+///   dropped, and they appear neither in scopes nor in close logs
+///
+/// Spans are tracked per thread while entered; futures instrumented with
+/// `tracing::Instrument` enter their span each time they are polled.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+
+thread_local! {
+    /// Spans entered on this thread, innermost last
+    static ENTERED: RefCell<Vec<Id>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Name of the span each Tauri command runs in
+pub const COMMAND_SPAN: &str = "command";
+
+/// Open span
+#[derive(Debug)]
+struct SpanData {
+    name: &'static str,
+    target: &'static str,
+    /// Recorded fields, formatted as `name=value` pairs
+    fields: String,
+    /// Name of the command, for a `command` span
+    command: Option<String>,
+    /// Whether a `command` span resolved with an error
+    failed: bool,
+    parent: Option<Id>,
+    opened: Instant,
+    /// Handles to the span
+    refs: usize,
+}
+
+/// Formats recorded fields as `name=value` pairs, keeping the message apart
+#[derive(Debug, Default)]
+struct FieldFormatter {
+    message: String,
+    fields: String,
+}
+
+impl Visit for FieldFormatter {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.push(field, format_args!("{}", value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            self.push(field, format_args!("{:?}", value));
+        }
+    }
+}
+
+impl FieldFormatter {
+    fn push(&mut self, field: &Field, value: std::fmt::Arguments<'_>) {
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        let _ = write!(self.fields, "{}={}", field.name(), value);
+    }
+}
+
+/// Reads the `name` field of a `command` span
+#[derive(Debug, Default)]
+struct CommandName(Option<String>);
+
+impl Visit for CommandName {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+/// Whether a span or event comes from Tauri itself
+fn is_tauri(target: &str) -> bool {
+    target == "tauri" || target.starts_with("tauri::")
+}
+
+/// Convert a tracing level to a log level
+pub fn log_level(level: &Level) -> log::Level {
+    match *level {
+        Level::ERROR => log::Level::Error,
+        Level::WARN => log::Level::Warn,
+        Level::INFO => log::Level::Info,
+        Level::DEBUG => log::Level::Debug,
+        Level::TRACE => log::Level::Trace,
+    }
+}
+
+/// Install the bridge as the global `tracing` subscriber
+///
+/// Called first thing in `run()`; events recorded before are dropped.
+pub fn install() {
+    if tracing::subscriber::set_global_default(LogBridge::default()).is_err() {
+        eprintln!("A tracing subscriber is already installed");
+    }
+}
+
+/// Record the command of the current scope as failed
+///
+/// Called when a command resolves with an error (`AppError` rejection,
+/// failed `CommandResponse`), which Tauri does in the span running the
+/// command.
+pub fn fail_command() {
+    tracing::dispatcher::get_default(|dispatch| {
+        if let Some(bridge) = dispatch.downcast_ref::<LogBridge>() {
+            bridge.mark_failed(bridge.current());
+        }
+    });
+}
+
+/// Subscriber forwarding tracing events to the `log` facade
+#[derive(Debug, Default)]
+pub struct LogBridge {
+    spans: Mutex<HashMap<u64, SpanData>>,
+    next_id: AtomicU64,
+}
+
+impl LogBridge {
+    /// Scope of a span, outermost first (`command{name=x}:inner`)
+    fn scope(&self, id: Option<&Id>) -> String {
+        let Ok(spans) = self.spans.lock() else {
+            return String::new();
+        };
+        let mut names = Vec::new();
+        let mut current = id.cloned();
+        while let Some(span) = current.and_then(|id| spans.get(&id.into_u64())) {
+            current = span.parent.clone();
+            if is_tauri(span.target) {
+                continue;
+            }
+            names.push(if span.fields.is_empty() {
+                span.name.to_string()
+            } else {
+                format!("{}{{{}}}", span.name, span.fields)
+            });
+        }
+        names.reverse();
+        names.join(":")
+    }
+
+    /// Mark the innermost `command` span of a scope as failed
+    fn mark_failed(&self, id: Option<Id>) {
+        let Ok(mut spans) = self.spans.lock() else {
+            return;
+        };
+        let mut current = id;
+        while let Some(span) = current.and_then(|id| spans.get_mut(&id.into_u64())) {
+            if span.command.is_some() {
+                span.failed = true;
+                return;
+            }
+            current = span.parent.clone();
+        }
+    }
+
+    fn current(&self) -> Option<Id> {
+        ENTERED.with(|entered| entered.borrow().last().cloned())
+    }
+
+    fn log(&self, level: log::Level, target: &str, args: std::fmt::Arguments<'_>) {
+        let logger = log::logger();
+        let record = log::Record::builder().args(args).level(level).target(target).build();
+        if logger.enabled(record.metadata()) {
+            logger.log(&record);
+        }
+    }
+}
+
+impl Subscriber for LogBridge {
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        // The log level changes at runtime: never cache the decision
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        // Spans are cheap and give events their scope, even at a lower level
+        if metadata.is_span() {
+            return true;
+        }
+        let level = log_level(metadata.level());
+        level <= log::max_level()
+            && log::logger().enabled(&log::Metadata::builder().level(level).target(metadata.target()).build())
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let metadata = attributes.metadata();
+        let mut fields = FieldFormatter::default();
+        if !is_tauri(metadata.target()) {
+            attributes.record(&mut fields);
+        }
+        let mut command = CommandName::default();
+        if metadata.name() == COMMAND_SPAN {
+            attributes.record(&mut command);
+        }
+        let parent = if attributes.is_root() {
+            None
+        } else {
+            attributes.parent().cloned().or_else(|| self.current())
+        };
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Ok(mut spans) = self.spans.lock() {
+            // The parent stays open while it has children
+            if let Some(parent) = parent.as_ref().and_then(|parent| spans.get_mut(&parent.into_u64())) {
+                parent.refs += 1;
+            }
+            spans.insert(
+                id,
+                SpanData {
+                    name: metadata.name(),
+                    target: metadata.target(),
+                    fields: fields.fields,
+                    command: command.0,
+                    failed: false,
+                    parent,
+                    opened: Instant::now(),
+                    refs: 1,
+                },
+            );
+        }
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut fields = FieldFormatter::default();
+        values.record(&mut fields);
+        if let Some(data) = self.spans.lock().ok().as_mut().and_then(|spans| spans.get_mut(&span.into_u64())) {
+            if is_tauri(data.target) {
+                return;
+            }
+            if !data.fields.is_empty() && !fields.fields.is_empty() {
+                data.fields.push(' ');
+            }
+            data.fields.push_str(&fields.fields);
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = FieldFormatter::default();
+        event.record(&mut fields);
+        let parent = if event.is_root() {
+            None
+        } else {
+            event.parent().cloned().or_else(|| self.current())
+        };
+        let scope = self.scope(parent.as_ref());
+
+        let metadata = event.metadata();
+        let separator = if fields.message.is_empty() || fields.fields.is_empty() { "" } else { " " };
+        let message = format!("{}{}{}", fields.message, separator, fields.fields);
+        let level = log_level(metadata.level());
+        if scope.is_empty() {
+            self.log(level, metadata.target(), format_args!("{}", message));
+        } else {
+            self.log(level, metadata.target(), format_args!("{}: {}", scope, message));
+        }
+    }
+
+    fn enter(&self, span: &Id) {
+        ENTERED.with(|entered| entered.borrow_mut().push(span.clone()));
+    }
+
+    fn exit(&self, span: &Id) {
+        ENTERED.with(|entered| {
+            let mut entered = entered.borrow_mut();
+            if let Some(position) = entered.iter().rposition(|id| id == span) {
+                entered.remove(position);
+            }
+        });
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Some(data) = self.spans.lock().ok().as_mut().and_then(|spans| spans.get_mut(&span.into_u64())) {
+            data.refs += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        let closed = {
+            let Ok(mut spans) = self.spans.lock() else {
+                return false;
+            };
+            match spans.get_mut(&span.into_u64()) {
+                Some(data) if data.refs > 1 => {
+                    data.refs -= 1;
+                    None
+                }
+                Some(_) => spans.remove(&span.into_u64()),
+                None => None,
+            }
+        };
+        let Some(data) = closed else {
+            return false;
+        };
+
+        if let Some(command) = &data.command {
+            crate::command_metrics::record(command, data.opened.elapsed(), !data.failed);
+        }
+        if is_tauri(data.target) {
+            if let Some(parent) = data.parent {
+                self.try_close(parent);
+            }
+            return true;
+        }
+        let scope = self.scope(data.parent.as_ref());
+        let name = if data.fields.is_empty() {
+            data.name.to_string()
+        } else {
+            format!("{}{{{}}}", data.name, data.fields)
+        };
+        let full_name = if scope.is_empty() { name } else { format!("{}:{}", scope, name) };
+        self.log(
+            log::Level::Debug,
+            data.target,
+            format_args!("{}: closed after {}ms", full_name, data.opened.elapsed().as_millis()),
+        );
+        if let Some(parent) = data.parent {
+            self.try_close(parent);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tracing::Dispatch;
+
+    #[test]
+    fn test_scope_and_close() {
+        let dispatch = Dispatch::new(LogBridge::default());
+        tracing::dispatcher::with_default(&dispatch, || {
+            let bridge = dispatch.downcast_ref::<LogBridge>().unwrap();
+            let command = tracing::info_span!("command", name = "keychain_store");
+            let attempt = command.in_scope(|| tracing::debug_span!("connectivity_attempt", attempt = 1u32));
+            {
+                let _attempt = attempt.enter();
+                assert_eq!(
+                    bridge.scope(bridge.current().as_ref()),
+                    "command{name=keychain_store}:connectivity_attempt{attempt=1}"
+                );
+            }
+            assert_eq!(bridge.current(), None);
+
+            drop(command);
+            assert_eq!(bridge.spans.lock().unwrap().len(), 2, "Kept open by its child");
+            drop(attempt);
+            assert!(bridge.spans.lock().unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_command_span_is_measured_once_its_children_close() {
+        let dispatch = Dispatch::new(LogBridge::default());
+        tracing::dispatcher::with_default(&dispatch, || {
+            let bridge = dispatch.downcast_ref::<LogBridge>().unwrap();
+            let command = tracing::info_span!(COMMAND_SPAN, name = "test_bridge_command");
+            // Stands for the span Tauri runs an async command in
+            let run = command.in_scope(|| tracing::debug_span!(target: "tauri::ipc", "ipc::request::run", request = "{}"));
+            drop(command);
+            {
+                let _run = run.enter();
+                assert_eq!(bridge.scope(bridge.current().as_ref()), "command{name=test_bridge_command}");
+                // What Tauri does with the `Err` returned by the command
+                let _ = tauri::ipc::InvokeError::from(crate::AppError::Platform("Failed".to_string()));
+            }
+            assert!(!crate::command_metrics::snapshot().commands.contains_key("test_bridge_command"));
+            drop(run);
+            let stats = &crate::command_metrics::snapshot().commands["test_bridge_command"];
+            assert_eq!((stats.invocations, stats.failures), (1, 1));
+        });
+    }
+
+    #[test]
+    fn test_command_fails_on_its_result_not_its_logs() {
+        use tauri::ipc::IpcResponse;
+
+        use crate::command_response::{CommandError, CommandResponse, ErrorCode};
+
+        let dispatch = Dispatch::new(LogBridge::default());
+        tracing::dispatcher::with_default(&dispatch, || {
+            tracing::info_span!(COMMAND_SPAN, name = "test_bridge_logged").in_scope(|| {
+                tracing::error!("Recovered from a failure");
+                CommandResponse::from(Ok::<_, CommandError>(true)).body().unwrap();
+            });
+            tracing::info_span!(COMMAND_SPAN, name = "test_bridge_rejected").in_scope(|| {
+                let error = CommandError::new(ErrorCode::InvalidArgument, "Invalid key");
+                CommandResponse::<()>::from(Err(error)).body().unwrap();
+            });
+            let metrics = crate::command_metrics::snapshot();
+            let logged = &metrics.commands["test_bridge_logged"];
+            assert_eq!((logged.invocations, logged.failures), (1, 0));
+            let rejected = &metrics.commands["test_bridge_rejected"];
+            assert_eq!((rejected.invocations, rejected.failures), (1, 1), "Failed without logging");
+        });
+    }
+
+    #[test]
+    fn test_field_formatter() {
+        let dispatch = Dispatch::new(LogBridge::default());
+        tracing::dispatcher::with_default(&dispatch, || {
+            let bridge = dispatch.downcast_ref::<LogBridge>().unwrap();
+            let span = tracing::info_span!("request", host = "app.elulib.com", port = 443u16, status = tracing::field::Empty);
+            span.record("status", "ok");
+            let _entered = span.enter();
+            assert_eq!(
+                bridge.scope(bridge.current().as_ref()),
+                "request{host=app.elulib.com port=443 status=ok}"
+            );
+        });
+    }
+}
@@ -0,0 +1,35 @@
+/// Android-specific "update required" screen
+///
+/// This module covers the webview with a native screen explaining that the
+/// installed version is no longer supported, with a button opening the store.
+///
+/// Note: This implementation provides the structure for the Android update screen.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Show the "update required" screen above the webview
+///
+/// # Arguments
+///
+/// * `store_url` - Store page opened by the update button, if known
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn show_update_required(store_url: Option<&str>) -> AppResult<()> {
+    tracing::info!("[Android] Showing update required screen: {:?}", store_url);
+
+    // TODO: Implement native Android update required screen
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val gate = UpdateRequiredView(activity) // message + "Mettre à jour" button
+    // gate.onUpdate = { activity.startActivity(Intent(Intent.ACTION_VIEW, Uri.parse(storeUrl))) }
+    // (activity.window.decorView as ViewGroup).addView(gate)
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
@@ -0,0 +1,35 @@
+/// iOS-specific "update required" screen
+///
+/// This module covers the webview with a native screen explaining that the
+/// installed version is no longer supported, with a button opening the store.
+///
+/// Note: This implementation provides the structure for the iOS update screen.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use crate::AppResult;
+
+/// Show the "update required" screen above the webview
+///
+/// # Arguments
+///
+/// * `store_url` - Store page opened by the update button, if known
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn show_update_required(store_url: Option<&str>) -> AppResult<()> {
+    tracing::info!("[iOS] Showing update required screen: {:?}", store_url);
+
+    // TODO: Implement native iOS update required screen
+    // Example Swift implementation:
+    // ```swift
+    // let gate = UpdateRequiredView(frame: window.bounds) // message + "Mettre à jour" button
+    // gate.onUpdate = { UIApplication.shared.open(storeURL ?? appStoreFallbackURL) }
+    // window.addSubview(gate)
+    // ```
+
+    // Placeholder: Return success
+    // Replace this with actual native implementation
+    Ok(())
+}
@@ -0,0 +1,209 @@
+/// Force-upgrade gate
+///
+/// At startup, this module fetches the minimum supported version policy
+/// (see `app_update`) and, if the installed build is below it, blocks the
+/// webview behind a native "update required" screen with a link to the store.
+///
+/// The last fetched policy is cached in the app data directory, so the gate
+/// still applies when the device is offline for up to
+/// `VERSION_POLICY_CACHE_MAX_AGE_SECS`.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Manager};
+
+use crate::app_update::{self, UpdateInfo, VersionPolicy};
+use crate::constants;
+use crate::events;
+use crate::{AppError, AppResult};
+
+/// Version policy cached on disk
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedPolicy {
+    /// The version policy
+    pub policy: VersionPolicy,
+    /// When the policy was fetched (seconds since Unix epoch)
+    pub fetched_at: u64,
+}
+
+/// Pick the version policy to enforce
+///
+/// A freshly fetched policy always wins; otherwise the cached policy is used
+/// if it is recent enough.
+///
+/// # Arguments
+///
+/// * `fetched` - Policy fetched from the backend, if the request succeeded
+/// * `cached` - Policy cached by a previous launch
+/// * `now` - Current time (seconds since Unix epoch)
+pub fn resolve_policy(
+    fetched: Option<VersionPolicy>,
+    cached: Option<CachedPolicy>,
+    now: u64,
+) -> Option<VersionPolicy> {
+    fetched.or_else(|| {
+        cached
+            .filter(|cached| {
+                now.saturating_sub(cached.fetched_at) <= constants::VERSION_POLICY_CACHE_MAX_AGE_SECS
+            })
+            .map(|cached| cached.policy)
+    })
+}
+
+fn cache_path(app: &AppHandle) -> AppResult<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(constants::VERSION_POLICY_CACHE_FILE))
+        .map_err(|e| AppError::Platform(format!("Failed to resolve app data directory: {}", e)))
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Read the cached version policy, if any
+pub fn read_cache(path: &Path) -> Option<CachedPolicy> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents)
+        .map_err(|e| tracing::warn!("Ignoring invalid version policy cache: {}", e))
+        .ok()
+}
+
+/// Write the version policy cache
+pub fn write_cache(path: &Path, cached: &CachedPolicy) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| AppError::Platform(format!("Failed to create data directory: {}", e)))?;
+    }
+    let contents = serde_json::to_string(cached)
+        .map_err(|e| AppError::Platform(format!("Failed to serialize version policy: {}", e)))?;
+    std::fs::write(path, contents)
+        .map_err(|e| AppError::Platform(format!("Failed to write version policy cache: {}", e)))
+}
+
+/// Fetch (or load from cache) the version policy and evaluate the installed build
+///
+/// # Returns
+///
+/// Returns the `UpdateInfo`, or `None` if no usable policy is available.
+async fn evaluate(app: &AppHandle) -> Option<UpdateInfo> {
+    let path = cache_path(app)
+        .map_err(|e| tracing::warn!("Version policy cache unavailable: {}", e))
+        .ok();
+    let now = now_secs();
+
+    let fetched = match app_update::fetch_policy().await {
+        Ok(policy) => {
+            if let Some(path) = &path {
+                let cached = CachedPolicy { policy: policy.clone(), fetched_at: now };
+                if let Err(e) = write_cache(path, &cached) {
+                    tracing::warn!("{}", e);
+                }
+            }
+            Some(policy)
+        }
+        Err(e) => {
+            tracing::warn!("Falling back to cached version policy: {}", e);
+            None
+        }
+    };
+    let cached = path.as_deref().and_then(read_cache);
+
+    let policy = resolve_policy(fetched, cached, now)?;
+    app_update::evaluate(env!("CARGO_PKG_VERSION"), &policy)
+        .map_err(|e| tracing::warn!("Invalid version policy: {}", e))
+        .ok()
+}
+
+/// Spawn the startup upgrade check
+///
+/// If the installed build is below the minimum supported version, the native
+/// "update required" screen is shown and an `update://required` event is
+/// emitted with the `UpdateInfo`.
+pub fn spawn_check(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let Some(info) = evaluate(&app).await else {
+            return;
+        };
+        if !info.update_required {
+            return;
+        }
+
+        tracing::warn!(
+            "Installed version {} is below the minimum supported version, blocking the app",
+            info.current_version
+        );
+        if let Err(e) = show_update_required(info.store_url.as_deref()) {
+            tracing::error!("Failed to show update required screen: {}", e);
+        }
+        events::emit(&app, &info);
+    });
+}
+
+/// Show the native "update required" screen above the webview
+fn show_update_required(store_url: Option<&str>) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::show_update_required(store_url)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::show_update_required(store_url)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = store_url;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(minimum: &str) -> VersionPolicy {
+        VersionPolicy {
+            latest_version: "2.0.0".to_string(),
+            minimum_version: minimum.to_string(),
+            store_url: None,
+        }
+    }
+
+    #[test]
+    fn test_resolve_policy_prefers_fetched() {
+        let cached = CachedPolicy { policy: policy("1.0.0"), fetched_at: 1_000 };
+        let resolved = resolve_policy(Some(policy("1.5.0")), Some(cached), 1_000);
+        assert_eq!(resolved, Some(policy("1.5.0")));
+    }
+
+    #[test]
+    fn test_resolve_policy_uses_fresh_cache_offline() {
+        let cached = CachedPolicy { policy: policy("1.0.0"), fetched_at: 1_000 };
+        let now = 1_000 + constants::VERSION_POLICY_CACHE_MAX_AGE_SECS;
+        assert_eq!(resolve_policy(None, Some(cached.clone()), now), Some(policy("1.0.0")));
+        assert_eq!(resolve_policy(None, Some(cached), now + 1), None);
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(constants::VERSION_POLICY_CACHE_FILE);
+        assert!(read_cache(&path).is_none());
+
+        let cached = CachedPolicy { policy: policy("1.2.0"), fetched_at: 42 };
+        write_cache(&path, &cached).unwrap();
+        assert_eq!(read_cache(&path), Some(cached));
+    }
+}
@@ -0,0 +1,707 @@
+/// Upload queue
+///
+/// The frontend enqueues files of the app file storage (`file_storage`
+/// module) with their target URL, and the native side uploads them in
+/// background, one at a time:
+/// - the authorization header is given by reference, never by value: either
+///   the session token (only for the application server), or a keychain
+///   entry readable by the frontend; the secret is read from the keychain at
+///   upload time
+/// - network errors, timeouts and `408`, `425`, `429` and `5xx` responses
+///   are retried with exponential backoff (honouring `Retry-After`), up to
+///   `UPLOAD_MAX_ATTEMPTS`; other responses fail the upload
+/// - uploads wait while the device is offline and, when configured (per
+///   upload, or with the `uploads.defer_on_metered` remote config), while it
+///   is on cellular data
+/// - the queue is persisted (`UPLOADS_FILE`); an upload interrupted by an app
+///   restart is retried
+/// - status changes are emitted as `upload://state` events
+///
+/// The file is sent as the raw request body (no multipart encoding).
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use ring::rand::SecureRandom;
+use tauri::{AppHandle, Manager, Url};
+
+use crate::constants::{self, helpers};
+use crate::events;
+use crate::file_storage;
+use crate::http;
+use crate::network_info::{self, NativeNetworkState, NetworkTransport};
+use crate::remote_config::{keys, ConfigStore};
+use crate::runtime_config;
+use crate::session_handoff;
+use crate::{AppError, AppResult};
+
+/// Reference to the authorization sent with an upload
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UploadAuth {
+    /// Bearer session token (application server only)
+    Session,
+    /// Bearer token stored in the keychain under `key` (application server only)
+    Keychain {
+        /// Keychain key of the token
+        key: String,
+    },
+}
+
+/// Status of an upload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadStatus {
+    /// Waiting to be uploaded
+    Queued,
+    /// Uploading
+    Uploading,
+    /// Failed, retried at `nextAttemptAt`
+    Retrying,
+    /// Uploaded
+    Completed,
+    /// Failed permanently (see `error`)
+    Failed,
+}
+
+/// Upload, as persisted and sent to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Upload {
+    /// Upload identifier
+    pub id: String,
+    /// File to upload, relative to the app file storage
+    pub path: String,
+    /// Target URL
+    pub url: String,
+    /// HTTP method (`POST` or `PUT`)
+    pub method: String,
+    /// Content type of the request body
+    pub content_type: String,
+    /// Authorization sent with the upload
+    pub auth: Option<UploadAuth>,
+    /// Wait for an unmetered network
+    pub defer_on_metered: bool,
+    /// Status of the upload
+    pub status: UploadStatus,
+    /// Failed attempts so far
+    pub attempts: u32,
+    /// Earliest time of the next attempt (seconds since the Unix epoch)
+    pub next_attempt_at: u64,
+    /// Status code of the last response
+    pub response_status: Option<u16>,
+    /// Error of the last failed attempt
+    pub error: Option<String>,
+    /// Time the upload was enqueued (seconds since the Unix epoch)
+    pub created_at: u64,
+}
+
+/// Result of an upload attempt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Attempt {
+    /// Uploaded, with the response status
+    Completed(u16),
+    /// Transient failure, with the delay requested by the server
+    /// (`Retry-After`), if any
+    Retry {
+        error: String,
+        status: Option<u16>,
+        retry_after: Option<u64>,
+    },
+    /// Permanent failure
+    Failed { error: String, status: Option<u16> },
+}
+
+/// Upload queue, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct UploadQueue {
+    uploads: Mutex<BTreeMap<String, Upload>>,
+    /// Persisted queue
+    file: Mutex<Option<PathBuf>>,
+    /// Set while the queue is being processed
+    processing: AtomicBool,
+}
+
+/// Clears the processing flag when the processing ends
+struct ProcessingGuard<'a>(&'a AtomicBool);
+
+impl Drop for ProcessingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+impl UploadQueue {
+    /// Load the queue persisted in `file`, queuing again the uploads that
+    /// were interrupted
+    pub fn load(&self, file: PathBuf) {
+        let uploads: BTreeMap<String, Upload> = std::fs::read_to_string(&file)
+            .ok()
+            .and_then(|contents| {
+                serde_json::from_str(&contents)
+                    .map_err(|e| tracing::warn!("Ignoring invalid upload queue: {}", e))
+                    .ok()
+            })
+            .unwrap_or_default();
+        if let Ok(mut current) = self.uploads.lock() {
+            *current = uploads
+                .into_iter()
+                .map(|(id, mut upload)| {
+                    if upload.status == UploadStatus::Uploading {
+                        upload.status = UploadStatus::Queued;
+                    }
+                    (id, upload)
+                })
+                .collect();
+        }
+        if let Ok(mut current) = self.file.lock() {
+            *current = Some(file);
+        }
+    }
+
+    /// Persist the queue
+    pub fn persist(&self) -> AppResult<()> {
+        let Some(file) = self.file.lock().map_err(|e| AppError::Network(e.to_string()))?.clone() else {
+            return Ok(());
+        };
+        let contents = {
+            let uploads = self.uploads.lock().map_err(|e| AppError::Network(e.to_string()))?;
+            serde_json::to_vec(&*uploads).map_err(|e| AppError::Network(format!("Failed to serialize upload queue: {}", e)))?
+        };
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AppError::Network(format!("Failed to create data directory: {}", e)))?;
+        }
+        std::fs::write(&file, contents).map_err(|e| AppError::Network(format!("Failed to write upload queue: {}", e)))
+    }
+
+    /// All uploads, oldest first
+    pub fn list(&self) -> Vec<Upload> {
+        let mut uploads: Vec<Upload> = self
+            .uploads
+            .lock()
+            .map(|uploads| uploads.values().cloned().collect())
+            .unwrap_or_default();
+        uploads.sort_by_key(|upload| upload.created_at);
+        uploads
+    }
+
+    /// Add an upload
+    pub fn add(&self, upload: Upload) -> AppResult<()> {
+        self.uploads
+            .lock()
+            .map_err(|e| AppError::Network(e.to_string()))?
+            .insert(upload.id.clone(), upload);
+        Ok(())
+    }
+
+    /// Update an upload
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated upload, or `None` if it no longer exists.
+    pub fn update(&self, id: &str, f: impl FnOnce(&mut Upload)) -> Option<Upload> {
+        let mut uploads = self.uploads.lock().ok()?;
+        let upload = uploads.get_mut(id)?;
+        f(upload);
+        Some(upload.clone())
+    }
+
+    /// Remove an upload
+    pub fn remove(&self, id: &str) -> Option<Upload> {
+        self.uploads.lock().ok()?.remove(id)
+    }
+
+    /// Remove all uploads
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of removed uploads.
+    pub fn clear(&self) -> usize {
+        self.uploads
+            .lock()
+            .map(|mut uploads| std::mem::take(&mut *uploads).len())
+            .unwrap_or(0)
+    }
+
+    /// Oldest upload due at `now` that the network allows, marked as
+    /// uploading
+    pub fn claim_next(&self, now: u64, network: &NativeNetworkState) -> Option<Upload> {
+        let mut uploads = self.uploads.lock().ok()?;
+        let upload = uploads
+            .values_mut()
+            .filter(|upload| matches!(upload.status, UploadStatus::Queued | UploadStatus::Retrying))
+            .filter(|upload| upload.next_attempt_at <= now && network_allows(network, upload.defer_on_metered))
+            .min_by_key(|upload| upload.created_at)?;
+        upload.status = UploadStatus::Uploading;
+        Some(upload.clone())
+    }
+
+    /// Start processing the queue
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the queue is already being processed.
+    fn start_processing(&self) -> Option<ProcessingGuard<'_>> {
+        if self.processing.swap(true, Ordering::SeqCst) {
+            return None;
+        }
+        Some(ProcessingGuard(&self.processing))
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn random_id() -> AppResult<String> {
+    let mut bytes = [0u8; 16];
+    ring::rand::SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| AppError::Network("Failed to generate random value".to_string()))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Returns `true` if an upload may run on the network
+pub fn network_allows(network: &NativeNetworkState, defer_on_metered: bool) -> bool {
+    match network.transport {
+        NetworkTransport::None => false,
+        NetworkTransport::Cellular => !defer_on_metered,
+        _ => !(network.metered && defer_on_metered),
+    }
+}
+
+/// Returns `true` if a response status is worth retrying
+pub fn is_retryable(status: u16) -> bool {
+    matches!(status, 408 | 425 | 429) || (500..600).contains(&status)
+}
+
+/// Delay before the next attempt after `attempts` failed attempts (seconds)
+pub fn backoff_secs(attempts: u32) -> u64 {
+    constants::UPLOAD_RETRY_BASE_SECS
+        .saturating_mul(2u64.saturating_pow(attempts.saturating_sub(1)))
+        .min(constants::UPLOAD_RETRY_MAX_SECS)
+}
+
+/// Record the result of an attempt on an upload
+pub fn apply_attempt(upload: &mut Upload, attempt: Attempt, now: u64) {
+    match attempt {
+        Attempt::Completed(status) => {
+            upload.status = UploadStatus::Completed;
+            upload.response_status = Some(status);
+            upload.error = None;
+        }
+        Attempt::Retry { error, status, retry_after } => {
+            upload.attempts += 1;
+            upload.response_status = status;
+            upload.error = Some(error);
+            if upload.attempts >= constants::UPLOAD_MAX_ATTEMPTS {
+                upload.status = UploadStatus::Failed;
+            } else {
+                let delay = backoff_secs(upload.attempts)
+                    .max(retry_after.unwrap_or(0).min(constants::UPLOAD_RETRY_MAX_SECS));
+                upload.status = UploadStatus::Retrying;
+                upload.next_attempt_at = now + delay;
+            }
+        }
+        Attempt::Failed { error, status } => {
+            upload.attempts += 1;
+            upload.status = UploadStatus::Failed;
+            upload.response_status = status;
+            upload.error = Some(error);
+        }
+    }
+}
+
+/// Validate the authorization reference of an upload to `url`
+///
+/// Authorizations are only sent to the application server, and only
+/// keychain entries the frontend may read itself can be referenced.
+pub fn validate_auth(auth: &UploadAuth, url: &Url, app_url: &str) -> AppResult<()> {
+    if let UploadAuth::Keychain { key } = auth {
+        helpers::validate_keychain_key(key)?;
+        if !session_handoff::is_retrievable(key) {
+            return Err(AppError::Network("This keychain entry cannot be used for uploads".to_string()));
+        }
+    }
+    let same_origin = Url::parse(app_url).is_ok_and(|app_url| app_url.origin() == url.origin());
+    if !same_origin {
+        return Err(AppError::Network("Authorizations are only sent to the application server".to_string()));
+    }
+    Ok(())
+}
+
+fn emit_state(app: &AppHandle, upload: &Upload) {
+    events::emit(app, upload);
+}
+
+fn persist(app: &AppHandle) {
+    if let Err(e) = app.state::<UploadQueue>().persist() {
+        tracing::warn!("{}", e);
+    }
+}
+
+/// Read the file of an upload, bounded by `UPLOAD_MAX_FILE_BYTES`
+fn read_body(file: &std::path::Path) -> AppResult<Vec<u8>> {
+    let metadata = std::fs::metadata(file)?;
+    if metadata.len() > constants::UPLOAD_MAX_FILE_BYTES {
+        return Err(AppError::Storage(format!("File exceeds {} bytes", constants::UPLOAD_MAX_FILE_BYTES)));
+    }
+    Ok(std::fs::read(file)?)
+}
+
+/// Upload a file once
+async fn attempt(app: &AppHandle, upload: &Upload) -> Attempt {
+    let failed = |error: String| Attempt::Failed { error, status: None };
+
+    let file = match file_storage::root(app).and_then(|root| file_storage::resolve(&root, &upload.path)) {
+        Ok(file) => file,
+        Err(e) => return failed(e.to_string()),
+    };
+    let body = match tauri::async_runtime::spawn_blocking(move || read_body(&file)).await {
+        Ok(Ok(body)) => body,
+        Ok(Err(e)) => return failed(format!("Failed to read {}: {}", upload.path, e)),
+        Err(e) => return failed(format!("Failed to read {}: {}", upload.path, e)),
+    };
+
+    // The application URL may have changed since the upload was queued
+    if let Some(auth) = &upload.auth {
+        let allowed = Url::parse(&upload.url)
+            .map_err(|e| AppError::InvalidArgument(format!("Invalid upload URL: {}", e)))
+            .and_then(|url| validate_auth(auth, &url, &runtime_config::get().app_url));
+        if let Err(e) = allowed {
+            return failed(e.to_string());
+        }
+    }
+
+    let token = match &upload.auth {
+        None => None,
+        Some(UploadAuth::Session) => session_handoff::session_token(app).transpose(),
+        Some(UploadAuth::Keychain { key }) => session_handoff::keychain_secret(app, key).transpose(),
+    };
+    let token = match token {
+        Some(Ok(token)) => Some(token),
+        // Keychain unavailable, e.g. locked device
+        Some(Err(e)) => return Attempt::Retry { error: e.to_string(), status: None, retry_after: None },
+        None if upload.auth.is_some() => return failed("Authorization not found in the keychain".to_string()),
+        None => None,
+    };
+
+    let client = match http::download_client() {
+        Ok(client) => client,
+        Err(e) => return failed(e.to_string()),
+    };
+    let method = reqwest::Method::from_bytes(upload.method.as_bytes()).unwrap_or(reqwest::Method::POST);
+    let mut request = client
+        .request(method, &upload.url)
+        .header(reqwest::header::CONTENT_TYPE, &upload.content_type)
+        .body(body);
+    if let Some(token) = &token {
+        request = request.bearer_auth(token);
+    }
+
+    match request.send().await {
+        Ok(response) if response.status().is_success() => Attempt::Completed(response.status().as_u16()),
+        Ok(response) => {
+            let status = response.status().as_u16();
+            let error = format!("Upload rejected with status {}", status);
+            if is_retryable(status) {
+                let retry_after = response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.trim().parse().ok());
+                Attempt::Retry { error, status: Some(status), retry_after }
+            } else {
+                Attempt::Failed { error, status: Some(status) }
+            }
+        }
+        Err(e) => Attempt::Retry {
+            error: format!("Upload failed: {}", e),
+            status: None,
+            retry_after: None,
+        },
+    }
+}
+
+/// Upload the due uploads, one at a time, while the network allows
+pub async fn process(app: &AppHandle) {
+    let queue = app.state::<UploadQueue>();
+    let Some(_guard) = queue.start_processing() else {
+        return;
+    };
+
+    loop {
+        let network = network_info::native_state().unwrap_or_default();
+        let Some(upload) = queue.claim_next(now_secs(), &network) else {
+            return;
+        };
+        emit_state(app, &upload);
+        persist(app);
+
+        let result = attempt(app, &upload).await;
+        let updated = queue.update(&upload.id, |upload| apply_attempt(upload, result, now_secs()));
+        // Removed (cancelled) while uploading
+        if let Some(updated) = updated {
+            match updated.status {
+                UploadStatus::Completed => tracing::info!("Upload {} completed", updated.id),
+                _ => tracing::warn!(
+                    "Upload {} attempt {} failed: {}",
+                    updated.id,
+                    updated.attempts,
+                    updated.error.as_deref().unwrap_or_default()
+                ),
+            }
+            emit_state(app, &updated);
+        }
+        persist(app);
+    }
+}
+
+fn spawn_process(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move { process(&app).await });
+}
+
+/// Enqueue an upload
+///
+/// # Arguments
+///
+/// * `path` - File to upload, relative to the app file storage
+/// * `url` - `https` target URL
+/// * `method` - `POST` (default) or `PUT`
+/// * `content_type` - Content type of the body (default
+///   `application/octet-stream`)
+/// * `auth` - Authorization sent with the upload
+/// * `defer_on_metered` - Wait for an unmetered network (default from the
+///   `uploads.defer_on_metered` remote config)
+///
+/// # Returns
+///
+/// Returns the queued upload, or an error if the arguments are
+/// invalid.
+pub fn enqueue(
+    app: &AppHandle,
+    path: &str,
+    url: &str,
+    method: Option<&str>,
+    content_type: Option<&str>,
+    auth: Option<UploadAuth>,
+    defer_on_metered: Option<bool>,
+) -> AppResult<Upload> {
+    let parsed = Url::parse(url).map_err(|e| AppError::InvalidArgument(format!("Invalid upload URL: {}", e)))?;
+    if parsed.scheme() != "https" {
+        return Err(AppError::InvalidArgument("Upload URL must be https".to_string()));
+    }
+    let method = method.unwrap_or("POST").to_ascii_uppercase();
+    if method != "POST" && method != "PUT" {
+        return Err(AppError::InvalidArgument(format!("Unsupported upload method: {}", method)));
+    }
+    if let Some(auth) = &auth {
+        validate_auth(auth, &parsed, &runtime_config::get().app_url)?;
+    }
+    let file = file_storage::resolve(&file_storage::root(app)?, path)?;
+    if !file.is_file() {
+        return Err(AppError::Network(format!("File not found: {}", path)));
+    }
+    let defer_on_metered = defer_on_metered.unwrap_or_else(|| {
+        app.state::<ConfigStore>()
+            .get_typed::<bool>(keys::UPLOAD_DEFER_ON_METERED)
+            .unwrap_or(false)
+    });
+
+    let now = now_secs();
+    let upload = Upload {
+        id: random_id()?,
+        path: path.to_string(),
+        url: parsed.to_string(),
+        method,
+        content_type: content_type.unwrap_or("application/octet-stream").to_string(),
+        auth,
+        defer_on_metered,
+        status: UploadStatus::Queued,
+        attempts: 0,
+        next_attempt_at: now,
+        response_status: None,
+        error: None,
+        created_at: now,
+    };
+    app.state::<UploadQueue>().add(upload.clone())?;
+    persist(app);
+    emit_state(app, &upload);
+    spawn_process(app);
+    Ok(upload)
+}
+
+/// Retry a failed upload now
+pub fn retry(app: &AppHandle, id: &str) -> AppResult<Upload> {
+    let upload = app
+        .state::<UploadQueue>()
+        .update(id, |upload| {
+            if matches!(upload.status, UploadStatus::Failed | UploadStatus::Retrying) {
+                upload.status = UploadStatus::Queued;
+                upload.attempts = 0;
+                upload.next_attempt_at = now_secs();
+            }
+        })
+        .ok_or_else(|| AppError::InvalidArgument(format!("Unknown upload: {}", id)))?;
+    emit_state(app, &upload);
+    persist(app);
+    spawn_process(app);
+    Ok(upload)
+}
+
+/// Remove an upload from the queue (an upload in progress still completes)
+pub fn cancel(app: &AppHandle, id: &str) -> AppResult<()> {
+    app.state::<UploadQueue>()
+        .remove(id)
+        .ok_or_else(|| AppError::InvalidArgument(format!("Unknown upload: {}", id)))?;
+    persist(app);
+    Ok(())
+}
+
+/// Restore the persisted queue and process it periodically, to retry the
+/// failed uploads and pick up the deferred ones once the network allows
+pub fn start(app: &AppHandle) -> AppResult<()> {
+    let file = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Network(format!("Failed to resolve data directory: {}", e)))?
+        .join(constants::UPLOADS_FILE);
+    app.state::<UploadQueue>().load(file);
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(constants::UPLOAD_CHECK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            process(&app).await;
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upload(id: &str, created_at: u64) -> Upload {
+        Upload {
+            id: id.to_string(),
+            path: format!("annexes/{}.pdf", id),
+            url: "https://app.elulib.com/api/annexes".to_string(),
+            method: "POST".to_string(),
+            content_type: "application/pdf".to_string(),
+            auth: Some(UploadAuth::Session),
+            defer_on_metered: false,
+            status: UploadStatus::Queued,
+            attempts: 0,
+            next_attempt_at: created_at,
+            response_status: None,
+            error: None,
+            created_at,
+        }
+    }
+
+    fn network(transport: NetworkTransport) -> NativeNetworkState {
+        NativeNetworkState {
+            transport,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_claim_next_respects_schedule_and_network() {
+        let queue = UploadQueue::default();
+        let mut later = upload("later", 1);
+        later.status = UploadStatus::Retrying;
+        later.next_attempt_at = 500;
+        queue.add(later).unwrap();
+        let mut metered = upload("metered", 2);
+        metered.defer_on_metered = true;
+        queue.add(metered).unwrap();
+        queue.add(upload("due", 3)).unwrap();
+
+        assert_eq!(queue.claim_next(100, &network(NetworkTransport::None)), None);
+        let claimed = queue.claim_next(100, &network(NetworkTransport::Cellular)).unwrap();
+        assert_eq!(claimed.id, "due");
+        assert_eq!(claimed.status, UploadStatus::Uploading);
+        assert_eq!(queue.claim_next(100, &network(NetworkTransport::Cellular)), None);
+        assert_eq!(queue.claim_next(100, &network(NetworkTransport::Wifi)).unwrap().id, "metered");
+        assert_eq!(queue.claim_next(500, &network(NetworkTransport::Wifi)).unwrap().id, "later");
+    }
+
+    #[test]
+    fn test_retries_back_off_then_fail() {
+        let mut upload = upload("a", 0);
+        let retry = || Attempt::Retry {
+            error: "Upload rejected with status 503".to_string(),
+            status: Some(503),
+            retry_after: None,
+        };
+
+        apply_attempt(&mut upload, retry(), 1000);
+        assert_eq!(upload.status, UploadStatus::Retrying);
+        assert_eq!(upload.next_attempt_at, 1000 + constants::UPLOAD_RETRY_BASE_SECS);
+
+        apply_attempt(
+            &mut upload,
+            Attempt::Retry { error: "busy".to_string(), status: Some(429), retry_after: Some(600) },
+            2000,
+        );
+        assert_eq!(upload.next_attempt_at, 2600, "Retry-After beyond the backoff is honoured");
+
+        while upload.status == UploadStatus::Retrying {
+            apply_attempt(&mut upload, retry(), 3000);
+        }
+        assert_eq!(upload.status, UploadStatus::Failed);
+        assert_eq!(upload.attempts, constants::UPLOAD_MAX_ATTEMPTS);
+        assert_eq!(backoff_secs(40), constants::UPLOAD_RETRY_MAX_SECS);
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(is_retryable(503));
+        assert!(is_retryable(429));
+        assert!(is_retryable(408));
+        assert!(!is_retryable(400));
+        assert!(!is_retryable(401));
+        assert!(!is_retryable(413));
+    }
+
+    #[test]
+    fn test_auth_references_are_restricted() {
+        let app_url = "https://app.elulib.com";
+        let own = Url::parse("https://app.elulib.com/api/annexes").unwrap();
+        let other = Url::parse("https://storage.example.com/bucket").unwrap();
+
+        assert!(validate_auth(&UploadAuth::Session, &own, app_url).is_ok());
+        assert!(validate_auth(&UploadAuth::Session, &other, app_url).is_err());
+
+        let storage_token = UploadAuth::Keychain { key: "storage_token".to_string() };
+        assert!(validate_auth(&storage_token, &own, app_url).is_ok());
+        assert!(validate_auth(&storage_token, &other, app_url).is_err());
+        let session_token = UploadAuth::Keychain { key: constants::SESSION_TOKEN_KEY.to_string() };
+        assert!(validate_auth(&session_token, &own, app_url).is_err());
+    }
+
+    #[test]
+    fn test_interrupted_uploads_are_queued_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(constants::UPLOADS_FILE);
+        let queue = UploadQueue::default();
+        queue.load(file.clone());
+        queue.add(upload("a", 1)).unwrap();
+        queue.claim_next(1, &network(NetworkTransport::Wifi)).unwrap();
+        queue.persist().unwrap();
+
+        let restarted = UploadQueue::default();
+        restarted.load(file);
+        assert_eq!(restarted.list()[0].status, UploadStatus::Queued);
+    }
+}
@@ -0,0 +1,671 @@
+/// Encrypted file vault for offline documents
+///
+/// Documents kept for offline reading (meeting packets, annexes) are moved
+/// out of the plaintext file storage into the vault (`VAULT_DIR`, in the app
+/// data directory):
+/// - each file is encrypted with its own random AES-256-GCM key, in chunks of
+///   `VAULT_CHUNK_BYTES`, so large files never need to fit in memory; the
+///   chunk index and a final-chunk flag are authenticated, so chunks cannot
+///   be reordered or the file truncated
+/// - the file key is wrapped with a key derived with HKDF-SHA256 from the
+///   keychain master key (`database::master_key`) and stored in the vault
+///   index (`VAULT_INDEX_FILE`); the master key never touches the disk
+/// - files are only decrypted while viewed: to memory (`vault_read`), or to a
+///   temporary file in the cache (`VAULT_VIEW_DIR`) handed to the native
+///   viewer (`vault_open`), deleted after `VAULT_VIEW_TTL_SECS`, when the
+///   session locks and at the next launch
+/// - files are purged when the vault policy expires them: past their own
+///   expiry date, `maxAgeSecs` after being stored or `maxIdleSecs` after
+///   being last opened (`vault.policy` remote config key, defaulting to
+///   `VAULT_MAX_AGE_SECS` and `VAULT_MAX_IDLE_SECS`); the policy is applied
+///   at launch and before every vault operation
+///
+/// On Android, the `FileProvider` paths must cover `VAULT_VIEW_DIR` of the
+/// cache (`<cache-path name="vault" path="vault-view/" />`).
+
+use std::collections::BTreeMap;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::hkdf::{Salt, HKDF_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use tauri::{AppHandle, Manager};
+
+use crate::attachments::{self, AttachmentType};
+use crate::constants;
+use crate::database;
+use crate::file_storage;
+use crate::remote_config::{keys, ConfigStore};
+use crate::{AppError, AppResult};
+
+/// Length of the AES-GCM tag appended to each chunk
+const TAG_LEN: usize = 16;
+
+/// Document stored in the vault, as sent to the frontend
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultItem {
+    /// Vault identifier
+    pub id: String,
+    /// File name of the document
+    pub name: String,
+    /// Size of the document (bytes)
+    pub size: u64,
+    /// Time the document was stored (seconds since the Unix epoch)
+    pub stored_at: u64,
+    /// Time the document was last opened (seconds since the Unix epoch)
+    pub last_opened_at: Option<u64>,
+    /// Time after which the document is purged (seconds since the Unix epoch)
+    pub expires_at: Option<u64>,
+}
+
+/// Entry of the vault index
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Record {
+    #[serde(flatten)]
+    item: VaultItem,
+    /// File key wrapped with the vault key (base64)
+    wrapped_key: String,
+}
+
+/// Expiry policy of the vault (`vault.policy` remote config key)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct VaultPolicy {
+    /// Maximum time a document is kept after being stored (seconds)
+    pub max_age_secs: u64,
+    /// Maximum time a document is kept without being opened (seconds)
+    pub max_idle_secs: u64,
+}
+
+impl Default for VaultPolicy {
+    fn default() -> Self {
+        Self {
+            max_age_secs: constants::VAULT_MAX_AGE_SECS,
+            max_idle_secs: constants::VAULT_MAX_IDLE_SECS,
+        }
+    }
+}
+
+impl VaultPolicy {
+    /// Returns `true` if the policy expires a document at `now`
+    pub fn is_expired(&self, item: &VaultItem, now: u64) -> bool {
+        let last_used = item.last_opened_at.unwrap_or(item.stored_at).max(item.stored_at);
+        item.expires_at.is_some_and(|expires_at| expires_at <= now)
+            || now.saturating_sub(item.stored_at) >= self.max_age_secs
+            || now.saturating_sub(last_used) >= self.max_idle_secs
+    }
+}
+
+/// Key wrapping the file keys, derived from the master key
+pub struct VaultKey(LessSafeKey);
+
+impl std::fmt::Debug for VaultKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("VaultKey(..)")
+    }
+}
+
+impl VaultKey {
+    /// Derive the vault key from the master key
+    pub fn derive(master_key: &[u8]) -> AppResult<Self> {
+        let info = [constants::VAULT_KEY_INFO.as_bytes()];
+        let prk = Salt::new(HKDF_SHA256, &[]).extract(master_key);
+        let okm = prk
+            .expand(&info, &AES_256_GCM)
+            .map_err(|_| AppError::Storage("Failed to derive vault key".to_string()))?;
+        Ok(Self(LessSafeKey::new(UnboundKey::from(okm))))
+    }
+
+    /// Wrap the key of a file, bound to its identifier
+    pub fn wrap(&self, id: &str, file_key: &[u8]) -> AppResult<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| AppError::Storage("Failed to generate vault nonce".to_string()))?;
+        let mut in_out = file_key.to_vec();
+        self.0
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::from(id.as_bytes()), &mut in_out)
+            .map_err(|_| AppError::Storage("Failed to wrap file key".to_string()))?;
+        Ok([&nonce[..], &in_out].concat())
+    }
+
+    /// Unwrap the key of a file wrapped with `wrap`
+    pub fn unwrap(&self, id: &str, wrapped: &[u8]) -> AppResult<Vec<u8>> {
+        if wrapped.len() < NONCE_LEN {
+            return Err(AppError::Storage("Truncated file key".to_string()));
+        }
+        let (nonce, ciphertext) = wrapped.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| AppError::Storage("Invalid file key nonce".to_string()))?;
+        let mut in_out = ciphertext.to_vec();
+        let file_key = self
+            .0
+            .open_in_place(nonce, Aad::from(id.as_bytes()), &mut in_out)
+            .map_err(|_| AppError::Storage("Failed to unwrap file key".to_string()))?;
+        Ok(file_key.to_vec())
+    }
+}
+
+/// Key of a single file
+fn file_key(key: &[u8]) -> AppResult<LessSafeKey> {
+    UnboundKey::new(&AES_256_GCM, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| AppError::Storage("Invalid file key".to_string()))
+}
+
+/// Nonce of a chunk: file keys encrypt a single file, so the chunk index is
+/// a unique nonce
+fn chunk_nonce(index: u64) -> Nonce {
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce[NONCE_LEN - 8..].copy_from_slice(&index.to_be_bytes());
+    Nonce::assume_unique_for_key(nonce)
+}
+
+/// Authenticated data of a chunk
+fn chunk_aad(id: &str, index: u64, last: bool) -> Vec<u8> {
+    [id.as_bytes(), &index.to_be_bytes(), &[u8::from(last)]].concat()
+}
+
+/// Read up to `size` bytes, fewer only at the end of the input
+fn read_chunk(reader: &mut impl Read, size: usize) -> AppResult<Vec<u8>> {
+    let mut chunk = Vec::with_capacity(size);
+    reader
+        .take(size as u64)
+        .read_to_end(&mut chunk)
+        .map_err(|e| AppError::Storage(format!("Failed to read vault file: {}", e)))?;
+    Ok(chunk)
+}
+
+/// Encrypt a file in chunks
+///
+/// # Returns
+///
+/// Returns the size of the plaintext (bytes).
+pub fn seal_stream(key: &[u8], id: &str, mut reader: impl Read, mut writer: impl Write) -> AppResult<u64> {
+    let key = file_key(key)?;
+    let write_error = |e: std::io::Error| AppError::Storage(format!("Failed to write vault file: {}", e));
+    writer.write_all(constants::VAULT_MAGIC).map_err(write_error)?;
+
+    let mut size = 0;
+    let mut index = 0;
+    let mut chunk = read_chunk(&mut reader, constants::VAULT_CHUNK_BYTES)?;
+    loop {
+        let next = if chunk.len() == constants::VAULT_CHUNK_BYTES {
+            read_chunk(&mut reader, constants::VAULT_CHUNK_BYTES)?
+        } else {
+            Vec::new()
+        };
+        let last = next.is_empty();
+        size += chunk.len() as u64;
+        key.seal_in_place_append_tag(chunk_nonce(index), Aad::from(chunk_aad(id, index, last)), &mut chunk)
+            .map_err(|_| AppError::Storage("Failed to encrypt vault file".to_string()))?;
+        writer.write_all(&chunk).map_err(write_error)?;
+        if last {
+            break;
+        }
+        chunk = next;
+        index += 1;
+    }
+    writer.flush().map_err(write_error)?;
+    Ok(size)
+}
+
+/// Decrypt a file encrypted with `seal_stream`
+pub fn open_stream(key: &[u8], id: &str, mut reader: impl Read, mut writer: impl Write) -> AppResult<()> {
+    let key = file_key(key)?;
+    let magic = read_chunk(&mut reader, constants::VAULT_MAGIC.len())?;
+    if magic != constants::VAULT_MAGIC {
+        return Err(AppError::Storage("Not a vault file".to_string()));
+    }
+
+    let sealed_size = constants::VAULT_CHUNK_BYTES + TAG_LEN;
+    let mut index = 0;
+    let mut chunk = read_chunk(&mut reader, sealed_size)?;
+    loop {
+        let next = if chunk.len() == sealed_size {
+            read_chunk(&mut reader, sealed_size)?
+        } else {
+            Vec::new()
+        };
+        let last = next.is_empty();
+        let plaintext = key
+            .open_in_place(chunk_nonce(index), Aad::from(chunk_aad(id, index, last)), &mut chunk)
+            .map_err(|_| AppError::Storage("Failed to decrypt vault file".to_string()))?;
+        writer
+            .write_all(plaintext)
+            .map_err(|e| AppError::Storage(format!("Failed to write decrypted file: {}", e)))?;
+        if last {
+            break;
+        }
+        chunk = next;
+        index += 1;
+    }
+    writer.flush().map_err(|e| AppError::Storage(format!("Failed to write decrypted file: {}", e)))
+}
+
+/// Vault index, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct Vault {
+    records: Mutex<BTreeMap<String, Record>>,
+    /// Vault directory, once opened
+    dir: Mutex<Option<PathBuf>>,
+}
+
+impl Vault {
+    /// Open the vault stored in `dir`, loading its index
+    pub fn open(&self, dir: PathBuf) {
+        let records: BTreeMap<String, Record> = std::fs::read_to_string(dir.join(constants::VAULT_INDEX_FILE))
+            .ok()
+            .and_then(|contents| {
+                serde_json::from_str(&contents)
+                    .map_err(|e| tracing::warn!("Ignoring invalid vault index: {}", e))
+                    .ok()
+            })
+            .unwrap_or_default();
+        if let Ok(mut current) = self.records.lock() {
+            *current = records;
+        }
+        if let Ok(mut current) = self.dir.lock() {
+            *current = Some(dir);
+        }
+    }
+
+    fn dir(&self) -> AppResult<PathBuf> {
+        self.dir
+            .lock()
+            .map_err(|e| AppError::Storage(e.to_string()))?
+            .clone()
+            .ok_or_else(|| AppError::Storage("Vault not open".to_string()))
+    }
+
+    fn persist(&self, records: &BTreeMap<String, Record>) -> AppResult<()> {
+        let dir = self.dir()?;
+        std::fs::create_dir_all(&dir).map_err(|e| AppError::Storage(format!("Failed to create vault directory: {}", e)))?;
+        let contents = serde_json::to_vec(records).map_err(|e| AppError::Storage(format!("Failed to serialize vault index: {}", e)))?;
+        let index = dir.join(constants::VAULT_INDEX_FILE);
+        let temp = index.with_extension("tmp");
+        std::fs::write(&temp, contents).map_err(|e| AppError::Storage(format!("Failed to write vault index: {}", e)))?;
+        std::fs::rename(&temp, &index).map_err(|e| AppError::Storage(format!("Failed to write vault index: {}", e)))
+    }
+
+    /// Path of the encrypted file of a document
+    fn file(&self, id: &str) -> AppResult<PathBuf> {
+        Ok(self.dir()?.join(format!("{}.{}", id, constants::VAULT_FILE_EXTENSION)))
+    }
+
+    /// Documents in the vault, oldest first
+    pub fn list(&self) -> Vec<VaultItem> {
+        let mut items: Vec<VaultItem> = self
+            .records
+            .lock()
+            .map(|records| records.values().map(|record| record.item.clone()).collect())
+            .unwrap_or_default();
+        items.sort_by_key(|item| item.stored_at);
+        items
+    }
+
+    /// Returns `true` if the document is in the vault
+    pub fn contains(&self, id: &str) -> bool {
+        self.records.lock().is_ok_and(|records| records.contains_key(id))
+    }
+
+    fn record(&self, id: &str) -> AppResult<Record> {
+        self.records
+            .lock()
+            .map_err(|e| AppError::Storage(e.to_string()))?
+            .get(id)
+            .cloned()
+            .ok_or_else(|| AppError::Storage(format!("Document not in vault: {}", id)))
+    }
+
+    fn insert(&self, record: Record) -> AppResult<()> {
+        let mut records = self.records.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        records.insert(record.item.id.clone(), record);
+        self.persist(&records)
+    }
+
+    fn touch(&self, id: &str, now: u64) -> AppResult<()> {
+        let mut records = self.records.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        if let Some(record) = records.get_mut(id) {
+            record.item.last_opened_at = Some(now);
+        }
+        self.persist(&records)
+    }
+
+    /// Remove a document and its encrypted file
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the document was in the vault.
+    pub fn remove(&self, id: &str) -> AppResult<bool> {
+        let mut records = self.records.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        if records.remove(id).is_none() {
+            return Ok(false);
+        }
+        match std::fs::remove_file(self.file(id)?) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(AppError::Storage(format!("Failed to delete vault file: {}", e))),
+        }
+        self.persist(&records)?;
+        Ok(true)
+    }
+
+    /// Identifiers of the documents expired by `policy` at `now`
+    pub fn expired(&self, policy: &VaultPolicy, now: u64) -> Vec<String> {
+        self.records
+            .lock()
+            .map(|records| {
+                records
+                    .values()
+                    .filter(|record| policy.is_expired(&record.item, now))
+                    .map(|record| record.item.id.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Remove all documents and the vault directory (local data wipe)
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of removed documents.
+    pub fn clear(&self) -> AppResult<usize> {
+        let mut records = self.records.lock().map_err(|e| AppError::Storage(e.to_string()))?;
+        let count = records.len();
+        records.clear();
+        match std::fs::remove_dir_all(self.dir()?) {
+            Ok(()) => Ok(count),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(count),
+            Err(e) => Err(AppError::Storage(format!("Failed to delete vault: {}", e))),
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn random_id() -> AppResult<String> {
+    let mut bytes = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| AppError::Storage("Failed to generate vault identifier".to_string()))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Expiry policy, from the remote config
+pub fn policy(app: &AppHandle) -> VaultPolicy {
+    app.state::<ConfigStore>()
+        .get_typed::<VaultPolicy>(keys::VAULT_POLICY)
+        .unwrap_or_default()
+}
+
+/// Purge the documents expired by the vault policy
+///
+/// # Returns
+///
+/// Returns the number of purged documents.
+pub fn purge(app: &AppHandle) -> AppResult<usize> {
+    let vault = app.state::<Vault>();
+    let expired = vault.expired(&policy(app), now_secs());
+    for id in &expired {
+        vault.remove(id)?;
+    }
+    if !expired.is_empty() {
+        tracing::info!("Purged {} expired vault document(s)", expired.len());
+    }
+    Ok(expired.len())
+}
+
+fn vault_key(app: &AppHandle) -> AppResult<VaultKey> {
+    VaultKey::derive(&database::master_key(app)?)
+}
+
+/// Key of a document
+fn document_key(app: &AppHandle, record: &Record) -> AppResult<Vec<u8>> {
+    let wrapped = base64::engine::general_purpose::STANDARD
+        .decode(&record.wrapped_key)
+        .map_err(|_| AppError::Storage("Invalid wrapped file key".to_string()))?;
+    vault_key(app)?.unwrap(&record.item.id, &wrapped)
+}
+
+/// Move a file of the app file storage into the vault
+///
+/// The plaintext file is deleted once encrypted.
+///
+/// # Arguments
+///
+/// * `app` - The app handle
+/// * `path` - Relative `/`-separated path in the app file storage
+/// * `expires_at` - Time after which the document is purged, if earlier
+///   than the vault policy
+pub fn import(app: &AppHandle, path: &str, expires_at: Option<u64>) -> AppResult<VaultItem> {
+    purge(app)?;
+    let source = file_storage::resolve(&file_storage::root(app)?, path)?;
+    if !source.is_file() {
+        return Err(AppError::Storage(format!("File not found: {}", path)));
+    }
+    let name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let id = random_id()?;
+    let mut key = [0u8; 32];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| AppError::Storage("Failed to generate file key".to_string()))?;
+    let wrapped_key = vault_key(app)?.wrap(&id, &key)?;
+
+    let vault = app.state::<Vault>();
+    let target = vault.file(&id)?;
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| AppError::Storage(format!("Failed to create vault directory: {}", e)))?;
+    }
+    let temp = file_storage::temp_path(&target);
+    let reader = std::fs::File::open(&source).map_err(|e| AppError::Storage(format!("Failed to read file: {}", e)))?;
+    let writer = std::fs::File::create(&temp).map_err(|e| AppError::Storage(format!("Failed to write vault file: {}", e)))?;
+    let size = match seal_stream(&key, &id, BufReader::new(reader), BufWriter::new(writer)) {
+        Ok(size) => size,
+        Err(e) => {
+            let _ = std::fs::remove_file(&temp);
+            return Err(e);
+        }
+    };
+    std::fs::rename(&temp, &target).map_err(|e| AppError::Storage(format!("Failed to write vault file: {}", e)))?;
+
+    let item = VaultItem {
+        id,
+        name,
+        size,
+        stored_at: now_secs(),
+        last_opened_at: None,
+        expires_at,
+    };
+    vault.insert(Record {
+        item: item.clone(),
+        wrapped_key: base64::engine::general_purpose::STANDARD.encode(wrapped_key),
+    })?;
+    std::fs::remove_file(&source).map_err(|e| AppError::Storage(format!("Failed to delete plaintext file: {}", e)))?;
+    tracing::info!("Stored document in vault ({} bytes)", size);
+    Ok(item)
+}
+
+/// Decrypt a document to memory
+///
+/// Documents larger than `FILE_STORAGE_MAX_FILE_BYTES` must be opened with
+/// `open` instead.
+pub fn read(app: &AppHandle, id: &str) -> AppResult<Vec<u8>> {
+    purge(app)?;
+    let vault = app.state::<Vault>();
+    let record = vault.record(id)?;
+    if record.item.size > constants::FILE_STORAGE_MAX_FILE_BYTES {
+        return Err(AppError::Storage(format!("{} is too large to be read in memory: open it instead", record.item.name)));
+    }
+    let key = document_key(app, &record)?;
+    let file = std::fs::File::open(vault.file(id)?).map_err(|e| AppError::Storage(format!("Failed to read vault file: {}", e)))?;
+    let mut contents = Vec::with_capacity(record.item.size as usize);
+    open_stream(&key, id, BufReader::new(file), &mut contents)?;
+    vault.touch(id, now_secs())?;
+    Ok(contents)
+}
+
+/// Directory of the decrypted files being viewed
+fn view_dir(app: &AppHandle) -> AppResult<PathBuf> {
+    app.path()
+        .app_cache_dir()
+        .map(|dir| dir.join(constants::VAULT_VIEW_DIR))
+        .map_err(|e| AppError::Storage(format!("Failed to resolve cache directory: {}", e)))
+}
+
+/// Delete the decrypted files being viewed
+pub fn clear_views(app: &AppHandle) {
+    let Ok(dir) = view_dir(app) else {
+        return;
+    };
+    remove_view(&dir);
+}
+
+fn remove_view(path: &Path) {
+    match std::fs::remove_dir_all(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => tracing::warn!("Failed to delete decrypted vault files: {}", e),
+    }
+}
+
+/// Decrypt a document to a temporary file and open it in the native viewer
+///
+/// The temporary file is deleted after `VAULT_VIEW_TTL_SECS`.
+pub fn open(app: &AppHandle, id: &str) -> AppResult<AttachmentType> {
+    purge(app)?;
+    let vault = app.state::<Vault>();
+    let record = vault.record(id)?;
+    let key = document_key(app, &record)?;
+
+    let dir = view_dir(app)?.join(id);
+    std::fs::create_dir_all(&dir).map_err(|e| AppError::Storage(format!("Failed to create view directory: {}", e)))?;
+    let target = dir.join(&record.item.name);
+    let opened = (|| {
+        let file = std::fs::File::open(vault.file(id)?).map_err(|e| AppError::Storage(format!("Failed to read vault file: {}", e)))?;
+        let writer = std::fs::File::create(&target).map_err(|e| AppError::Storage(format!("Failed to write decrypted file: {}", e)))?;
+        open_stream(&key, id, BufReader::new(file), BufWriter::new(writer))?;
+        attachments::open_file(&target)
+    })();
+    let attachment_type = match opened {
+        Ok(attachment_type) => attachment_type,
+        Err(e) => {
+            remove_view(&dir);
+            return Err(e);
+        }
+    };
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(constants::VAULT_VIEW_TTL_SECS)).await;
+        remove_view(&dir);
+    });
+    vault.touch(id, now_secs())?;
+    Ok(attachment_type)
+}
+
+/// Open the vault and apply its policy
+///
+/// Called at startup; also deletes the files decrypted for a viewer before
+/// the app was killed.
+pub fn init(app: &AppHandle) -> AppResult<()> {
+    clear_views(app);
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve data directory: {}", e)))?
+        .join(constants::VAULT_DIR);
+    app.state::<Vault>().open(dir);
+    purge(app).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [7; 32];
+
+    fn seal(id: &str, plaintext: &[u8]) -> Vec<u8> {
+        let mut sealed = Vec::new();
+        assert_eq!(seal_stream(&KEY, id, plaintext, &mut sealed).unwrap(), plaintext.len() as u64);
+        sealed
+    }
+
+    fn open(id: &str, sealed: &[u8]) -> AppResult<Vec<u8>> {
+        let mut plaintext = Vec::new();
+        open_stream(&KEY, id, sealed, &mut plaintext).map(|_| plaintext)
+    }
+
+    #[test]
+    fn test_stream_roundtrip_across_chunks() {
+        let chunk = constants::VAULT_CHUNK_BYTES;
+        for size in [0, 10, chunk, chunk + 1, 3 * chunk] {
+            let plaintext: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+            let sealed = seal("doc-1", &plaintext);
+            assert_eq!(open("doc-1", &sealed).unwrap(), plaintext, "size {}", size);
+        }
+    }
+
+    #[test]
+    fn test_tampered_streams_are_rejected() {
+        let chunk = constants::VAULT_CHUNK_BYTES;
+        let plaintext = vec![1u8; 2 * chunk + 5];
+        let sealed = seal("doc-1", &plaintext);
+
+        assert!(open("doc-2", &sealed).is_err(), "Bound to the identifier");
+        let truncated = &sealed[..constants::VAULT_MAGIC.len() + chunk + TAG_LEN];
+        assert!(open("doc-1", truncated).is_err(), "Truncated after a full chunk");
+        let mut flipped = sealed.clone();
+        let last = flipped.len() - 1;
+        flipped[last] ^= 1;
+        assert!(open("doc-1", &flipped).is_err());
+        assert!(open("doc-1", b"PK\x03\x04").is_err());
+    }
+
+    #[test]
+    fn test_file_keys_are_wrapped_per_document() {
+        let key = VaultKey::derive(&[3u8; 32]).unwrap();
+        let wrapped = key.wrap("doc-1", &KEY).unwrap();
+        assert_eq!(key.unwrap("doc-1", &wrapped).unwrap(), KEY);
+        assert!(key.unwrap("doc-2", &wrapped).is_err());
+        assert!(VaultKey::derive(&[4u8; 32]).unwrap().unwrap("doc-1", &wrapped).is_err());
+    }
+
+    #[test]
+    fn test_policy_expiry() {
+        let policy = VaultPolicy {
+            max_age_secs: 1000,
+            max_idle_secs: 100,
+        };
+        let item = VaultItem {
+            id: "doc-1".to_string(),
+            name: "budget.pdf".to_string(),
+            size: 10,
+            stored_at: 5000,
+            last_opened_at: None,
+            expires_at: None,
+        };
+        assert!(!policy.is_expired(&item, 5050));
+        assert!(policy.is_expired(&item, 5100), "Never opened");
+
+        let opened = VaultItem { last_opened_at: Some(5900), ..item.clone() };
+        assert!(!policy.is_expired(&opened, 5950));
+        assert!(policy.is_expired(&opened, 6000), "Maximum age");
+
+        let expiring = VaultItem { expires_at: Some(5010), ..item };
+        assert!(policy.is_expired(&expiring, 5010), "Own expiry date");
+    }
+}
@@ -0,0 +1,68 @@
+/// Android-specific watch companion plumbing
+///
+/// The phone app uses the Wear OS Data Layer: the watch context is stored as
+/// a data item at `WATCH_DATA_PATH` (synced to the watch, and kept while the
+/// phone app is not running), and watch requests arrive as messages on
+/// `WATCH_MESSAGE_PATH`, answered through the `MessageClient`.
+///
+/// Note: This implementation provides the structure for the Wear Data Layer.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use tauri::AppHandle;
+
+use super::WatchContext;
+use crate::constants;
+use crate::AppResult;
+
+/// Start listening for watch messages on Android
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start(app: AppHandle) -> AppResult<()> {
+    tracing::info!("[Android] Starting Wear Data Layer listener on {}", constants::WATCH_MESSAGE_PATH);
+
+    // TODO: Implement native Wear Data Layer listener
+    // Each watch message must be forwarded to `watch::on_message`, whose
+    // result (or error) is sent back to the node.
+    // Example Kotlin implementation:
+    // ```kotlin
+    // Wearable.getMessageClient(context).addListener { event ->
+    //     if (event.path == "/elulib/request") {
+    //         val reply = onWatchMessage(String(event.data))
+    //         Wearable.getMessageClient(context).sendMessage(event.sourceNodeId, "/elulib/reply", reply.toByteArray())
+    //     }
+    // }
+    // ```
+
+    // Placeholder: Return success without listening
+    // Replace this with actual native implementation
+    let _ = app;
+    Ok(())
+}
+
+/// Store the watch context as a Data Layer item on Android
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn publish(context: &WatchContext) -> AppResult<()> {
+    // TODO: Implement native data item update
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val request = PutDataMapRequest.create("/elulib/next-meeting").apply {
+    //     dataMap.putString("context", contextJson)
+    // }.asPutDataRequest().setUrgent()
+    // Wearable.getDataClient(context).putDataItem(request)
+    // ```
+
+    // Placeholder: Log the update
+    // Replace this with actual native implementation
+    tracing::debug!(
+        "[Android] Watch context updated at {} (next meeting: {})",
+        constants::WATCH_DATA_PATH,
+        context.next_meeting.is_some()
+    );
+    Ok(())
+}
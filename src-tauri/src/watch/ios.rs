@@ -0,0 +1,64 @@
+/// iOS-specific watch companion plumbing
+///
+/// The phone app activates a `WCSession`: the watch context is sent with
+/// `updateApplicationContext` (only the latest context is kept and delivered
+/// to the watch app, even if it is not running), and watch requests arrive
+/// through `session(_:didReceiveMessage:replyHandler:)`.
+///
+/// Note: This implementation provides the structure for WatchConnectivity.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use tauri::AppHandle;
+
+use super::WatchContext;
+use crate::AppResult;
+
+/// Activate the WatchConnectivity session on iOS
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn start(app: AppHandle) -> AppResult<()> {
+    tracing::info!("[iOS] Activating WatchConnectivity session");
+
+    // TODO: Implement native WatchConnectivity session
+    // Each watch message must be forwarded to `watch::on_message`, whose
+    // result (or error) is the reply.
+    // Example Swift implementation:
+    // ```swift
+    // guard WCSession.isSupported() else { return }
+    // WCSession.default.delegate = self
+    // WCSession.default.activate()
+    //
+    // func session(_ session: WCSession, didReceiveMessage message: [String: Any],
+    //              replyHandler: @escaping ([String: Any]) -> Void) {
+    //     replyHandler(onWatchMessage(message))
+    // }
+    // ```
+
+    // Placeholder: Return success without activating the session
+    // Replace this with actual native implementation
+    let _ = app;
+    Ok(())
+}
+
+/// Send the watch context on iOS
+///
+/// # Returns
+///
+/// Returns `Ok(())` on success, or an error if the operation fails.
+pub fn publish(context: &WatchContext) -> AppResult<()> {
+    // TODO: Implement native application context update
+    // Example Swift implementation:
+    // ```swift
+    // guard WCSession.default.activationState == .activated,
+    //       WCSession.default.isWatchAppInstalled else { return }
+    // try WCSession.default.updateApplicationContext(["context": contextJson])
+    // ```
+
+    // Placeholder: Log the update
+    // Replace this with actual native implementation
+    tracing::debug!("[iOS] Watch context updated (next meeting: {})", context.next_meeting.is_some());
+    Ok(())
+}
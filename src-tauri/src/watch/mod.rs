@@ -0,0 +1,378 @@
+/// Watch companion data channel
+///
+/// The watch apps (watchOS, Wear OS) show the next meeting and can mark it as
+/// read, without a webview: they talk to the native layer, which answers from
+/// the encrypted local database kept up to date by the `sync` engine.
+/// - the data sent to the watch is a `WatchContext`: the next meeting of the
+///   `agenda` collection (`sync_records`), its `readAt` mark included
+/// - it is published when the module starts and after each sync
+///   (`publish`): `WCSession.updateApplicationContext` on iOS, a Data Layer
+///   item at `WATCH_DATA_PATH` on Android, so the watch shows it even while
+///   the phone app is not running
+/// - nothing is read from the database while the session is locked or no
+///   session is stored: the watch then gets an empty context, and logout,
+///   wipe and lock replace the published context with an empty one (`clear`)
+/// - the watch sends requests as JSON (`{ "type": "nextMeeting" }`,
+///   `{ "type": "markRead", "id": "..." }`), handled by `on_message`, which
+///   answers with the updated context
+/// - marking a meeting as read (refused while locked or logged out) stores `readAt` in the local record and posts
+///   a `watch://mark-read` event through the `outbox`, so the frontend
+///   reports it to the backend once a page is ready
+///
+/// Agenda records are the backend sync items: `id`, `title`, `startsAt`
+/// (seconds since the Unix epoch) and an optional `location`.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::constants;
+use crate::database::Database;
+use crate::native_module::NativeModule;
+use crate::outbox;
+use crate::session_handoff;
+use crate::session_lock::SessionLock;
+use crate::{AppError, AppResult};
+
+/// Summary of a meeting, as shown on the watch
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeetingSummary {
+    /// Identifier of the agenda record
+    pub id: String,
+    /// Meeting title
+    pub title: String,
+    /// Start time (seconds since the Unix epoch)
+    pub starts_at: i64,
+    /// Meeting place, if known
+    pub location: Option<String>,
+    /// When the meeting was marked as read (seconds since the Unix epoch)
+    pub read_at: Option<i64>,
+}
+
+impl MeetingSummary {
+    /// Summary of an agenda record, `None` if it lacks a title or start time
+    pub fn from_record(id: &str, data: &Value) -> Option<Self> {
+        Some(MeetingSummary {
+            id: id.to_string(),
+            title: data.get("title")?.as_str()?.to_string(),
+            starts_at: data.get("startsAt")?.as_i64()?,
+            location: data.get("location").and_then(Value::as_str).map(str::to_string),
+            read_at: data.get("readAt").and_then(Value::as_i64),
+        })
+    }
+}
+
+/// Data published to the watch
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchContext {
+    /// Next meeting, if any is scheduled
+    pub next_meeting: Option<MeetingSummary>,
+    /// When the context was computed (seconds since the Unix epoch)
+    pub updated_at: u64,
+}
+
+/// Request sent by the watch
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WatchRequest {
+    /// Get the current context
+    NextMeeting,
+    /// Mark a meeting as read
+    MarkRead {
+        /// Identifier of the agenda record
+        id: String,
+    },
+}
+
+/// Payload of `watch://mark-read`, posted when a meeting is marked as read
+/// from the watch
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchMarkRead {
+    /// Identifier of the agenda record
+    pub meeting_id: String,
+    /// When the meeting was marked as read (seconds since the Unix epoch)
+    pub read_at: u64,
+}
+
+/// Watch channel state, managed by the Tauri application
+#[derive(Debug, Default)]
+pub struct WatchState {
+    active: AtomicBool,
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Next meeting of the agenda, starting at or after `now`
+pub fn next_meeting(conn: &Connection, now: u64) -> AppResult<Option<MeetingSummary>> {
+    let mut statement = conn
+        .prepare("SELECT id, data FROM sync_records WHERE collection = ?")
+        .map_err(|e| AppError::Database(format!("Failed to read agenda: {}", e)))?;
+    let rows = statement
+        .query_map(params![constants::WATCH_AGENDA_COLLECTION], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| AppError::Database(format!("Failed to read agenda: {}", e)))?;
+
+    let mut next: Option<MeetingSummary> = None;
+    for row in rows {
+        let (id, data) = row.map_err(|e| AppError::Database(format!("Failed to read agenda: {}", e)))?;
+        let meeting = serde_json::from_str(&data)
+            .ok()
+            .and_then(|data| MeetingSummary::from_record(&id, &data))
+            .filter(|meeting| meeting.starts_at >= now as i64);
+        if let Some(meeting) = meeting {
+            if next.as_ref().is_none_or(|next| meeting.starts_at < next.starts_at) {
+                next = Some(meeting);
+            }
+        }
+    }
+    Ok(next)
+}
+
+/// Store `readAt` in an agenda record
+///
+/// # Returns
+///
+/// Returns `false` if the agenda has no record with this identifier.
+pub fn mark_read(conn: &Connection, id: &str, now: u64) -> AppResult<bool> {
+    let data: Option<String> = conn
+        .query_row(
+            "SELECT data FROM sync_records WHERE collection = ? AND id = ?",
+            params![constants::WATCH_AGENDA_COLLECTION, id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| AppError::Database(format!("Failed to read agenda: {}", e)))?;
+    let Some(data) = data else {
+        return Ok(false);
+    };
+    let mut data: Value = serde_json::from_str(&data).map_err(|e| AppError::Database(format!("Invalid agenda record: {}", e)))?;
+    let record = data.as_object_mut().ok_or_else(|| AppError::Database("Invalid agenda record".to_string()))?;
+    record.insert("readAt".to_string(), Value::from(now));
+    conn.execute(
+        "UPDATE sync_records SET data = ? WHERE collection = ? AND id = ?",
+        params![data.to_string(), constants::WATCH_AGENDA_COLLECTION, id],
+    )
+    .map_err(|e| AppError::Database(format!("Failed to mark meeting as read: {}", e)))?;
+    Ok(true)
+}
+
+/// Whether the agenda may be read: the session is unlocked and stored
+fn agenda_available(app: &AppHandle) -> bool {
+    if app.state::<SessionLock>().is_locked() {
+        return false;
+    }
+    matches!(session_handoff::session_token(app), Ok(Some(_)))
+}
+
+/// Context without any meeting, published when the agenda is not available
+fn empty_context() -> WatchContext {
+    WatchContext {
+        next_meeting: None,
+        updated_at: now_secs(),
+    }
+}
+
+/// Current watch context, from the local database
+pub fn context(app: &AppHandle) -> AppResult<WatchContext> {
+    let now = now_secs();
+    let next_meeting = app
+        .state::<Database>()
+        .with_connection(app, |conn| next_meeting(conn, now))?;
+    Ok(WatchContext {
+        next_meeting,
+        updated_at: now,
+    })
+}
+
+/// Handle a request sent by the watch
+///
+/// # Arguments
+///
+/// * `app` - The app handle
+/// * `message` - JSON request (`{ "type": "markRead", "id": "..." }`)
+///
+/// # Returns
+///
+/// Returns the updated context, sent back to the watch, or an error
+/// if the request is invalid, the meeting unknown, or a meeting is marked as
+/// read while the session is locked or logged out.
+pub fn on_message(app: &AppHandle, message: &Value) -> AppResult<WatchContext> {
+    let request: WatchRequest =
+        serde_json::from_value(message.clone()).map_err(|e| AppError::InvalidArgument(format!("Invalid watch request: {}", e)))?;
+    if !agenda_available(app) {
+        return match request {
+            WatchRequest::MarkRead { .. } => Err(AppError::Database("Session locked or logged out".to_string())),
+            WatchRequest::NextMeeting => Ok(empty_context()),
+        };
+    }
+    if let WatchRequest::MarkRead { id } = request {
+        let read_at = now_secs();
+        let found = app
+            .state::<Database>()
+            .with_connection(app, |conn| mark_read(conn, &id, read_at))?;
+        if !found {
+            return Err(AppError::InvalidArgument("Unknown meeting".to_string()));
+        }
+        tracing::info!("Meeting marked as read from the watch");
+        outbox::post(app, &WatchMarkRead { meeting_id: id, read_at });
+        publish(app);
+    }
+    context(app)
+}
+
+/// Send a context to the watch, through the platform channel
+fn send(context: &WatchContext) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::publish(context)
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::publish(context)
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        let _ = context;
+        Ok(())
+    }
+}
+
+/// Publish the current context to the watch, once the channel is started
+///
+/// Skipped while the session is locked or no session is stored.
+pub fn publish(app: &AppHandle) {
+    if !app.state::<WatchState>().active.load(Ordering::SeqCst) || !agenda_available(app) {
+        return;
+    }
+    if let Err(e) = context(app).and_then(|context| send(&context)) {
+        tracing::warn!("Failed to publish watch context: {}", e);
+    }
+}
+
+/// Replace the published context with an empty one, on logout, wipe and
+/// session lock
+pub fn clear(app: &AppHandle) {
+    if !app.state::<WatchState>().active.load(Ordering::SeqCst) {
+        return;
+    }
+    if let Err(e) = send(&empty_context()) {
+        tracing::warn!("Failed to clear watch context: {}", e);
+    }
+}
+
+/// Start the watch channel
+pub fn start(app: &AppHandle) -> AppResult<()> {
+    #[cfg(target_os = "ios")]
+    ios::start(app.clone())?;
+
+    #[cfg(target_os = "android")]
+    android::start(app.clone())?;
+
+    app.state::<WatchState>().active.store(true, Ordering::SeqCst);
+    publish(app);
+    Ok(())
+}
+
+/// Watch companion channel, as a native module
+pub struct WatchModule;
+
+impl NativeModule for WatchModule {
+    fn name(&self) -> &'static str {
+        "watch"
+    }
+
+    fn events(&self) -> &'static [&'static str] {
+        &[constants::WATCH_MARK_READ_EVENT]
+    }
+
+    fn init(&self, app: &AppHandle) -> AppResult<()> {
+        start(app)
+    }
+
+    fn shutdown(&self, app: &AppHandle) {
+        app.state::<WatchState>().active.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{migrate, open, MIGRATIONS};
+    use crate::sync::{apply_page, SyncPage};
+    use serde_json::json;
+
+    const KEY: &str = "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff";
+
+    fn agenda(dir: &tempfile::TempDir, items: Vec<Value>) -> Connection {
+        let mut conn = open(&dir.path().join("test.db"), KEY).unwrap();
+        migrate(&mut conn, MIGRATIONS).unwrap();
+        let page = SyncPage {
+            items,
+            ..Default::default()
+        };
+        apply_page(&conn, constants::WATCH_AGENDA_COLLECTION, &page, 0).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_next_meeting_is_the_first_upcoming_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = agenda(
+            &dir,
+            vec![
+                json!({ "id": "past", "title": "Conseil de janvier", "startsAt": 900 }),
+                json!({ "id": "later", "title": "Commission finances", "startsAt": 3_000 }),
+                json!({ "id": "next", "title": "Conseil municipal", "startsAt": 2_000, "location": "Mairie" }),
+                json!({ "id": "invalid", "title": "Sans date" }),
+            ],
+        );
+
+        let next = next_meeting(&conn, 1_000).unwrap().unwrap();
+        assert_eq!(next.id, "next");
+        assert_eq!(next.location.as_deref(), Some("Mairie"));
+        assert_eq!(next.read_at, None);
+        assert_eq!(next_meeting(&conn, 3_001).unwrap(), None);
+    }
+
+    #[test]
+    fn test_mark_read_updates_the_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = agenda(&dir, vec![json!({ "id": "next", "title": "Conseil municipal", "startsAt": 2_000 })]);
+
+        assert_eq!(mark_read(&conn, "next", 1_500).unwrap(), true);
+        assert_eq!(next_meeting(&conn, 1_000).unwrap().unwrap().read_at, Some(1_500));
+        assert_eq!(mark_read(&conn, "unknown", 1_500).unwrap(), false);
+    }
+
+    #[test]
+    fn test_watch_requests() {
+        assert_eq!(
+            serde_json::from_value::<WatchRequest>(json!({ "type": "nextMeeting" })).unwrap(),
+            WatchRequest::NextMeeting
+        );
+        assert_eq!(
+            serde_json::from_value::<WatchRequest>(json!({ "type": "markRead", "id": "next" })).unwrap(),
+            WatchRequest::MarkRead { id: "next".to_string() }
+        );
+        assert!(serde_json::from_value::<WatchRequest>(json!({ "type": "markRead" })).is_err());
+    }
+}
@@ -0,0 +1,26 @@
+/// Android-specific watchdog support
+///
+/// This module captures the stack trace of the main (looper) thread while it
+/// is stalled, so hang reports show what the UI thread was doing.
+///
+/// Note: This implementation provides the structure for the Android watchdog.
+/// The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+/// Capture the stack trace of the main thread
+///
+/// # Returns
+///
+/// Returns the stack trace, or `None` if it cannot be captured.
+pub fn main_thread_backtrace() -> Option<String> {
+    // TODO: Implement native Android capture
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val mainThread = Looper.getMainLooper().thread
+    // return mainThread.stackTrace.joinToString("\n") { "    at $it" }
+    // ```
+
+    // Placeholder: Report the backtrace as unavailable
+    // Replace this with actual native implementation
+    None
+}
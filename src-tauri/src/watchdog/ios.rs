@@ -0,0 +1,34 @@
+/// iOS-specific watchdog support
+///
+/// This module captures the backtrace of the main thread while it is
+/// stalled, so hang reports show what the UI thread was doing.
+///
+/// Note: This implementation provides the structure for the iOS watchdog.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+/// Capture the backtrace of the main thread
+///
+/// # Returns
+///
+/// Returns the symbolicated backtrace, or `None` if it cannot be captured.
+pub fn main_thread_backtrace() -> Option<String> {
+    // TODO: Implement native iOS capture
+    // The main thread must be sampled from another thread: suspend it, read
+    // its registers and walk the frame pointers (as PLCrashReporter does).
+    // Example Objective-C implementation:
+    // ```objc
+    // PLCrashReporterConfig *config = [[PLCrashReporterConfig alloc]
+    //     initWithSignalHandlerType:PLCrashReporterSignalHandlerTypeBSD
+    //     symbolicationStrategy:PLCrashReporterSymbolicationStrategyAll];
+    // PLCrashReporter *reporter = [[PLCrashReporter alloc] initWithConfiguration:config];
+    // NSData *data = [reporter generateLiveReportWithThread:pthread_mach_thread_np(mainThread)];
+    // PLCrashReport *report = [[PLCrashReport alloc] initWithData:data error:nil];
+    // return [PLCrashReportTextFormatter stringValueForCrashReport:report
+    //     withTextFormat:PLCrashReportTextFormatiOS];
+    // ```
+
+    // Placeholder: Report the backtrace as unavailable
+    // Replace this with actual native implementation
+    None
+}
@@ -0,0 +1,273 @@
+/// Main thread watchdog ("app not responding" detection)
+///
+/// A background thread pings the main (UI) thread every
+/// `WATCHDOG_INTERVAL_MS` by posting a closure to it
+/// (`AppHandle::run_on_main_thread`). When a ping is not acknowledged within
+/// `WATCHDOG_HANG_THRESHOLD_MS`, the stall is reported once:
+/// - a warning is logged and a `watchdog` breadcrumb recorded
+/// - a hang report (`crash` module) is written with the main thread
+///   backtrace where the platform can capture it, the breadcrumbs and the
+///   app state, so it shows up as the last crash in diagnostics and is
+///   uploaded like the other crash reports
+///
+/// Recovery is logged with the approximate stall duration. The watchdog is
+/// paused while the app is in background, where the OS may suspend the main
+/// thread, and ignores gaps where the watchdog thread itself was suspended.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tauri::AppHandle;
+
+use crate::breadcrumbs;
+use crate::constants;
+use crate::crash;
+use crate::{AppError, AppResult};
+
+/// Set while the app is in background
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// Set once the watchdog thread is started
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Main thread responsiveness change detected by the watchdog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogEvent {
+    /// The pending ping is not acknowledged after the threshold
+    Stalled(Duration),
+    /// The main thread acknowledged the ping after a reported stall
+    Recovered(Duration),
+}
+
+/// Ping bookkeeping of the watchdog, independent of the threads
+#[derive(Debug)]
+pub struct HangDetector {
+    threshold: Duration,
+    /// Sequence number of the last ping
+    last_seq: u64,
+    /// Pending ping and the time it was sent
+    sent: Option<(u64, Instant)>,
+    /// Time of the last check, to detect suspensions of the watchdog thread
+    last_check: Option<Instant>,
+    /// The pending ping was already reported as a stall
+    reported: bool,
+}
+
+impl HangDetector {
+    /// Create a detector reporting stalls beyond `threshold`
+    pub fn new(threshold: Duration) -> Self {
+        HangDetector {
+            threshold,
+            last_seq: 0,
+            sent: None,
+            last_check: None,
+            reported: false,
+        }
+    }
+
+    /// Send a new ping at `now`
+    ///
+    /// # Returns
+    ///
+    /// Returns the sequence number the main thread must acknowledge.
+    pub fn ping(&mut self, now: Instant) -> u64 {
+        self.last_seq += 1;
+        self.sent = Some((self.last_seq, now));
+        self.last_seq
+    }
+
+    /// Returns `true` if the last ping is not acknowledged yet
+    pub fn pending(&self, acked: u64) -> bool {
+        self.sent.is_some_and(|(seq, _)| acked < seq)
+    }
+
+    /// Forget the pending ping (app paused, watchdog thread suspended)
+    pub fn reset(&mut self) {
+        self.sent = None;
+        self.last_check = None;
+        self.reported = false;
+    }
+
+    /// Check the pending ping at `now`, given the last acknowledged sequence
+    /// number
+    ///
+    /// # Returns
+    ///
+    /// Returns `Stalled` once per ping exceeding the threshold, `Recovered`
+    /// when a reported ping is acknowledged, `None` otherwise.
+    pub fn check(&mut self, now: Instant, acked: u64) -> Option<WatchdogEvent> {
+        // The watchdog thread did not run for a while: the whole process was
+        // suspended, not just the main thread
+        let suspended = self
+            .last_check
+            .is_some_and(|last| now.saturating_duration_since(last) >= self.threshold);
+        if suspended {
+            self.reset();
+            return None;
+        }
+        self.last_check = Some(now);
+
+        let (_, sent_at) = self.sent?;
+        let elapsed = now.saturating_duration_since(sent_at);
+        if self.pending(acked) {
+            if !self.reported && elapsed >= self.threshold {
+                self.reported = true;
+                return Some(WatchdogEvent::Stalled(elapsed));
+            }
+            None
+        } else if self.reported {
+            self.reported = false;
+            Some(WatchdogEvent::Recovered(elapsed))
+        } else {
+            None
+        }
+    }
+}
+
+/// Pause the watchdog (app in background)
+pub fn pause() {
+    PAUSED.store(true, Ordering::SeqCst);
+}
+
+/// Resume the watchdog (app back in foreground)
+pub fn resume() {
+    PAUSED.store(false, Ordering::SeqCst);
+}
+
+/// Start the watchdog thread
+///
+/// Called in `setup()` once crash reporting is installed, so hang reports
+/// can be written.
+pub fn start(app: &AppHandle) -> AppResult<()> {
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return Err(AppError::Platform("Watchdog already started".to_string()));
+    }
+    let app = app.clone();
+    let acked = Arc::new(AtomicU64::new(0));
+    let interval = Duration::from_millis(constants::WATCHDOG_INTERVAL_MS);
+    let threshold = Duration::from_millis(constants::WATCHDOG_HANG_THRESHOLD_MS);
+
+    std::thread::Builder::new()
+        .name("watchdog".to_string())
+        .spawn(move || {
+            let mut detector = HangDetector::new(threshold);
+            loop {
+                std::thread::sleep(interval);
+                if PAUSED.load(Ordering::SeqCst) {
+                    detector.reset();
+                    continue;
+                }
+
+                let now = Instant::now();
+                let acked_seq = acked.load(Ordering::SeqCst);
+                match detector.check(now, acked_seq) {
+                    Some(WatchdogEvent::Stalled(stalled)) => on_stall(stalled),
+                    Some(WatchdogEvent::Recovered(stalled)) => {
+                        tracing::warn!("Main thread responsive again after about {}ms", stalled.as_millis());
+                        breadcrumbs::breadcrumb("watchdog", &format!("main thread recovered after {}ms", stalled.as_millis()));
+                    }
+                    None => {}
+                }
+
+                if !detector.pending(acked_seq) {
+                    let seq = detector.ping(now);
+                    let acked = acked.clone();
+                    if let Err(e) = app.run_on_main_thread(move || acked.store(seq, Ordering::SeqCst)) {
+                        tracing::warn!("Failed to ping the main thread: {}", e);
+                        detector.reset();
+                    }
+                }
+            }
+        })
+        .map_err(|e| AppError::Platform(format!("Failed to start watchdog thread: {}", e)))?;
+    Ok(())
+}
+
+/// Report a main thread stall, while the main thread is still stalled
+fn on_stall(stalled: Duration) {
+    let stalled_ms = stalled.as_millis() as u64;
+    tracing::warn!("App not responding: main thread stalled for {}ms", stalled_ms);
+    breadcrumbs::breadcrumb("watchdog", &format!("main thread stalled for {}ms", stalled_ms));
+
+    match crash::record_hang(stalled_ms, main_thread_backtrace()) {
+        Ok(path) => tracing::info!("Hang report written: {}", path.display()),
+        Err(e) => tracing::warn!("Failed to write hang report: {}", e),
+    }
+}
+
+/// Backtrace of the main thread, where the platform can capture it
+///
+/// Rust can only capture the backtrace of the current thread, so the main
+/// thread is sampled natively.
+fn main_thread_backtrace() -> Option<String> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::main_thread_backtrace()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::main_thread_backtrace()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_acknowledged_pings_are_not_reported() {
+        let mut detector = HangDetector::new(THRESHOLD);
+        let start = Instant::now();
+        assert_eq!(detector.check(start, 0), None);
+        let seq = detector.ping(start);
+        assert!(detector.pending(0));
+        assert!(!detector.pending(seq));
+        assert_eq!(detector.check(start + Duration::from_secs(1), seq), None);
+    }
+
+    #[test]
+    fn test_stall_is_reported_once_then_recovers() {
+        let mut detector = HangDetector::new(THRESHOLD);
+        let start = Instant::now();
+        let seq = detector.ping(start);
+
+        let mut events = Vec::new();
+        for secs in 1..=8 {
+            events.extend(detector.check(start + Duration::from_secs(secs), 0));
+        }
+        assert_eq!(events, [WatchdogEvent::Stalled(Duration::from_secs(5))]);
+
+        assert_eq!(
+            detector.check(start + Duration::from_secs(9), seq),
+            Some(WatchdogEvent::Recovered(Duration::from_secs(9)))
+        );
+        assert_eq!(detector.check(start + Duration::from_secs(10), seq), None);
+    }
+
+    #[test]
+    fn test_suspended_watchdog_is_not_a_stall() {
+        let mut detector = HangDetector::new(THRESHOLD);
+        let start = Instant::now();
+        assert_eq!(detector.check(start, 0), None);
+        detector.ping(start);
+
+        // No check for 30s: the process was suspended
+        assert_eq!(detector.check(start + Duration::from_secs(30), 0), None);
+        assert!(!detector.pending(0), "Pending ping is forgotten");
+    }
+}
@@ -0,0 +1,569 @@
+/// Native WebSocket client
+///
+/// The realtime connection to the backend is kept natively instead of in the
+/// webview, whose WebSocket dies when iOS suspends the page:
+/// - `connect` opens the connection to an endpoint of the application
+///   origin (`wss` only), authenticated with the session token when one is
+///   stored; a connection replaces the previous one
+/// - messages from the server are emitted as `ws://message` events, state
+///   changes as `ws://state` events
+/// - after an unexpected disconnection, the client reconnects with an
+///   exponential backoff (`WS_RECONNECT_BASE_DELAY_MS` doubling up to
+///   `WS_RECONNECT_MAX_DELAY_MS`), at most `WS_MAX_RECONNECT_ATTEMPTS` times
+///   in a row; a ping every `WS_PING_INTERVAL_SECS` detects dead connections
+/// - the client stops for good when the session token cannot be read, or when
+///   the server refuses the credentials (HTTP 401/403 on the handshake, close
+///   codes `WS_AUTH_CLOSE_CODES`)
+/// - when the app goes to the background, the connection is closed cleanly
+///   before the OS suspends the process, and reopened at once on foreground
+/// - messages sent while disconnected are queued (at most
+///   `WS_MAX_QUEUED_MESSAGES`, the oldest dropped first) and flushed once
+///   connected
+///
+/// `close` ends the connection for good, until the next `connect`; it is
+/// called on logout, wipe and session lock, so the connection never outlives
+/// the session it was opened with. The commands form the `realtime` native module (`RealtimeModule`), which closes
+/// the connection when the app exits.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use base64::Engine;
+use futures_util::{SinkExt, StreamExt};
+use tauri::{AppHandle, Manager, Url};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+use tokio_tungstenite::tungstenite::{self, Message};
+
+use crate::constants;
+use crate::events;
+use crate::native_module::NativeModule;
+use crate::runtime_config;
+use crate::session_handoff;
+use crate::{AppError, AppResult};
+
+/// State of the realtime connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WebSocketState {
+    /// Opening the connection
+    Connecting,
+    /// Connected
+    Open,
+    /// Waiting before the next connection attempt
+    Reconnecting,
+    /// Closed while the app is in background
+    Suspended,
+    /// Closed by `close`, or never opened
+    Closed,
+}
+
+/// Payload of `ws://state`, also returned by `ws_status`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WebSocketStatus {
+    /// Connection state
+    pub state: WebSocketState,
+    /// Endpoint of the connection, if any
+    pub url: Option<String>,
+    /// Failed connection attempts since the last successful one
+    pub attempt: u32,
+    /// Messages waiting to be sent
+    pub queued: usize,
+}
+
+/// Payload of `ws://message`, emitted for every message from the server
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+pub struct WebSocketMessage {
+    /// Text of the message, or its base64 encoding for a binary message
+    pub data: String,
+    /// Whether the message was binary
+    pub binary: bool,
+}
+
+/// Delay before the connection attempt following `attempt` failures
+pub fn reconnect_delay(attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    Duration::from_millis(
+        (constants::WS_RECONNECT_BASE_DELAY_MS << exponent).min(constants::WS_RECONNECT_MAX_DELAY_MS),
+    )
+}
+
+/// Validate a WebSocket endpoint: `wss`, on the host of the application URL
+pub fn validate_url(url: &str, app_url: &str) -> AppResult<Url> {
+    let parsed = Url::parse(url).map_err(|e| AppError::InvalidArgument(format!("Invalid WebSocket URL: {}", e)))?;
+    if parsed.scheme() != "wss" {
+        return Err(AppError::InvalidArgument(format!("Unsupported WebSocket scheme: {}", parsed.scheme())));
+    }
+    let app_host = Url::parse(app_url).ok().and_then(|app| app.host_str().map(str::to_string));
+    if parsed.host_str().is_none() || parsed.host_str() != app_host.as_deref() {
+        return Err(AppError::InvalidArgument("WebSocket endpoint must be on the application origin".to_string()));
+    }
+    Ok(parsed)
+}
+
+/// Messages waiting for a connection, the oldest dropped beyond
+/// `WS_MAX_QUEUED_MESSAGES`
+#[derive(Debug, Default)]
+pub struct OutgoingQueue(VecDeque<String>);
+
+impl OutgoingQueue {
+    /// Queue a message
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the oldest message was dropped to make room.
+    pub fn push(&mut self, message: String) -> bool {
+        self.0.push_back(message);
+        if self.0.len() > constants::WS_MAX_QUEUED_MESSAGES {
+            self.0.pop_front();
+            return true;
+        }
+        false
+    }
+
+    /// Next message to send
+    pub fn pop(&mut self) -> Option<String> {
+        self.0.pop_front()
+    }
+
+    /// Put back a message that could not be sent
+    pub fn requeue(&mut self, message: String) {
+        self.0.push_front(message);
+    }
+
+    /// Number of queued messages
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no message is queued
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Instruction to the connection task
+#[derive(Debug)]
+enum Control {
+    Send(String),
+    Suspend,
+    Resume,
+    Close,
+}
+
+/// Connection task and its shared status
+struct Connection {
+    control: mpsc::UnboundedSender<Control>,
+    status: Arc<Mutex<WebSocketStatus>>,
+}
+
+/// Realtime client, managed by the Tauri application
+#[derive(Default)]
+pub struct WebSocketClient {
+    connection: Mutex<Option<Connection>>,
+}
+
+impl WebSocketClient {
+    fn control(&self, control: Control) -> AppResult<()> {
+        let connection = self.connection.lock().map_err(|e| AppError::Network(e.to_string()))?;
+        let connection = connection.as_ref().ok_or_else(|| AppError::Network("WebSocket is not connected".to_string()))?;
+        connection
+            .control
+            .send(control)
+            .map_err(|_| AppError::Network("WebSocket is closed".to_string()))
+    }
+
+    /// Whether `status` belongs to the current connection
+    fn is_current(&self, status: &Arc<Mutex<WebSocketStatus>>) -> bool {
+        self.connection
+            .lock()
+            .map(|connection| connection.as_ref().is_some_and(|current| Arc::ptr_eq(&current.status, status)))
+            .unwrap_or(false)
+    }
+
+    /// Current status
+    pub fn status(&self) -> WebSocketStatus {
+        self.connection
+            .lock()
+            .ok()
+            .and_then(|connection| connection.as_ref()?.status.lock().ok().map(|status| status.clone()))
+            .unwrap_or(WebSocketStatus {
+                state: WebSocketState::Closed,
+                url: None,
+                attempt: 0,
+                queued: 0,
+            })
+    }
+}
+
+/// Open the realtime connection, replacing the current one
+pub fn connect(app: &AppHandle, url: &str) -> AppResult<WebSocketStatus> {
+    let url = validate_url(url, &runtime_config::get().app_url)?;
+    let status = Arc::new(Mutex::new(WebSocketStatus {
+        state: WebSocketState::Connecting,
+        url: Some(url.to_string()),
+        attempt: 0,
+        queued: 0,
+    }));
+    let (control, receiver) = mpsc::unbounded_channel();
+    let client = app.state::<WebSocketClient>();
+    {
+        let mut connection = client.connection.lock().map_err(|e| AppError::Network(e.to_string()))?;
+        if let Some(previous) = connection.replace(Connection {
+            control,
+            status: status.clone(),
+        }) {
+            let _ = previous.control.send(Control::Close);
+        }
+    }
+    tracing::info!("Opening realtime connection to {}", url);
+    tauri::async_runtime::spawn(run(app.clone(), url, receiver, status));
+    Ok(client.status())
+}
+
+/// Send a text message, queued until connected
+pub fn send(app: &AppHandle, message: String) -> AppResult<()> {
+    app.state::<WebSocketClient>().control(Control::Send(message))
+}
+
+/// Close the realtime connection
+pub fn close(app: &AppHandle) -> AppResult<()> {
+    let connection = app
+        .state::<WebSocketClient>()
+        .connection
+        .lock()
+        .map_err(|e| AppError::Network(e.to_string()))?
+        .take();
+    if let Some(connection) = connection {
+        let _ = connection.control.send(Control::Close);
+        events::emit(app, &app.state::<WebSocketClient>().status());
+    }
+    Ok(())
+}
+
+/// Realtime native module (`module-realtime`)
+pub struct RealtimeModule;
+
+impl NativeModule for RealtimeModule {
+    fn name(&self) -> &'static str {
+        "realtime"
+    }
+
+    fn commands(&self) -> &'static [&'static str] {
+        &["ws_connect", "ws_send", "ws_close", "ws_status"]
+    }
+
+    fn events(&self) -> &'static [&'static str] {
+        &[constants::WS_STATE_EVENT, constants::WS_MESSAGE_EVENT]
+    }
+
+    fn shutdown(&self, app: &AppHandle) {
+        if let Err(e) = close(app) {
+            tracing::warn!("Failed to close the realtime connection: {}", e);
+        }
+    }
+}
+
+/// Close the connection before the OS suspends the app
+pub fn on_background(app: &AppHandle) {
+    let _ = app.state::<WebSocketClient>().control(Control::Suspend);
+}
+
+/// Reopen the connection when the app returns to the foreground
+pub fn on_foreground(app: &AppHandle) {
+    let _ = app.state::<WebSocketClient>().control(Control::Resume);
+}
+
+/// Update the shared status and notify the webview, unless the connection
+/// was closed or replaced
+fn set_state(app: &AppHandle, status: &Arc<Mutex<WebSocketStatus>>, state: WebSocketState, attempt: u32, queued: usize) {
+    if !app.state::<WebSocketClient>().is_current(status) {
+        return;
+    }
+    let current = match status.lock() {
+        Ok(mut current) => {
+            current.state = state;
+            current.attempt = attempt;
+            current.queued = queued;
+            current.clone()
+        }
+        Err(_) => return,
+    };
+    events::emit(app, &current);
+}
+
+/// Why a connection ended
+enum Ended {
+    /// Lost or refused: reconnect
+    Lost,
+    /// App in background: wait for the foreground
+    Suspended,
+    /// `close` or a replacing `connect`: stop
+    Closed,
+    /// Credentials refused or unreadable: stop
+    Rejected,
+}
+
+/// Why a connection could not be opened
+enum OpenError {
+    /// Network or server error: reconnect
+    Failed(String),
+    /// Session token unreadable or refused by the server: stop
+    Rejected(String),
+}
+
+/// Whether a close frame from the server refuses the session credentials
+pub fn is_auth_close(frame: Option<&CloseFrame>) -> bool {
+    frame.is_some_and(|frame| constants::WS_AUTH_CLOSE_CODES.contains(&u16::from(frame.code)))
+}
+
+/// Connection task: connect, forward messages, reconnect until closed
+async fn run(
+    app: AppHandle,
+    url: Url,
+    mut control: mpsc::UnboundedReceiver<Control>,
+    status: Arc<Mutex<WebSocketStatus>>,
+) {
+    let mut queue = OutgoingQueue::default();
+    let mut attempt = 0;
+    let mut suspended = false;
+    loop {
+        if suspended {
+            set_state(&app, &status, WebSocketState::Suspended, attempt, queue.len());
+            match control.recv().await {
+                Some(Control::Send(message)) => {
+                    queue.push(message);
+                    continue;
+                }
+                Some(Control::Resume) => {
+                    suspended = false;
+                    attempt = 0;
+                }
+                Some(Control::Suspend) => continue,
+                Some(Control::Close) | None => break,
+            }
+        }
+
+        set_state(&app, &status, WebSocketState::Connecting, attempt, queue.len());
+        let ended = match open(&app, &url).await {
+            Ok(socket) => {
+                tracing::info!("Realtime connection open");
+                attempt = 0;
+                set_state(&app, &status, WebSocketState::Open, attempt, queue.len());
+                serve(&app, socket, &mut control, &mut queue).await
+            }
+            Err(OpenError::Failed(e)) => {
+                tracing::warn!("Realtime connection failed: {}", e);
+                Ended::Lost
+            }
+            Err(OpenError::Rejected(e)) => {
+                tracing::warn!("Realtime connection rejected: {}", e);
+                Ended::Rejected
+            }
+        };
+        match ended {
+            Ended::Closed | Ended::Rejected => break,
+            Ended::Suspended => {
+                suspended = true;
+                continue;
+            }
+            Ended::Lost => {}
+        }
+
+        attempt += 1;
+        if attempt > constants::WS_MAX_RECONNECT_ATTEMPTS {
+            tracing::warn!("Realtime connection given up after {} attempts", attempt - 1);
+            break;
+        }
+        let delay = reconnect_delay(attempt);
+        tracing::info!("Reconnecting in {}ms (attempt {})", delay.as_millis(), attempt);
+        set_state(&app, &status, WebSocketState::Reconnecting, attempt, queue.len());
+        let wait = tokio::time::sleep(delay);
+        tokio::pin!(wait);
+        loop {
+            tokio::select! {
+                _ = &mut wait => break,
+                received = control.recv() => match received {
+                    Some(Control::Send(message)) => {
+                        queue.push(message);
+                    }
+                    Some(Control::Resume) => break,
+                    Some(Control::Suspend) => {
+                        suspended = true;
+                        break;
+                    }
+                    Some(Control::Close) | None => {
+                        set_state(&app, &status, WebSocketState::Closed, attempt, queue.len());
+                        return;
+                    }
+                },
+            }
+        }
+    }
+    tracing::info!("Realtime connection closed");
+    set_state(&app, &status, WebSocketState::Closed, attempt, queue.len());
+}
+
+type Socket = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Open a connection, with the session token if one is stored
+async fn open(app: &AppHandle, url: &Url) -> Result<Socket, OpenError> {
+    let mut request = url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| OpenError::Rejected(format!("Invalid WebSocket request: {}", e)))?;
+    if let Some(token) = session_handoff::session_token(app).map_err(|e| OpenError::Rejected(e.to_string()))? {
+        let value = format!("Bearer {}", token)
+            .parse()
+            .map_err(|_| OpenError::Rejected("Invalid session token".to_string()))?;
+        request.headers_mut().insert("Authorization", value);
+    }
+    let connecting = tokio_tungstenite::connect_async(request);
+    match tokio::time::timeout(runtime_config::get().http_timeout, connecting).await {
+        Ok(Ok((socket, _))) => Ok(socket),
+        Ok(Err(tungstenite::Error::Http(response)))
+            if matches!(response.status().as_u16(), 401 | 403) =>
+        {
+            Err(OpenError::Rejected(format!("Handshake refused ({})", response.status())))
+        }
+        Ok(Err(e)) => Err(OpenError::Failed(e.to_string())),
+        Err(_) => Err(OpenError::Failed("Timed out".to_string())),
+    }
+}
+
+/// Forward messages until the connection ends
+async fn serve(
+    app: &AppHandle,
+    socket: Socket,
+    control: &mut mpsc::UnboundedReceiver<Control>,
+    queue: &mut OutgoingQueue,
+) -> Ended {
+    let (mut sink, mut stream) = socket.split();
+    while let Some(message) = queue.pop() {
+        if let Err(e) = sink.send(Message::Text(message.clone())).await {
+            tracing::warn!("Failed to flush queued message: {}", e);
+            queue.requeue(message);
+            return Ended::Lost;
+        }
+    }
+
+    let mut ping = tokio::time::interval(Duration::from_secs(constants::WS_PING_INTERVAL_SECS));
+    ping.tick().await;
+    let mut awaiting_pong = false;
+    loop {
+        tokio::select! {
+            received = stream.next() => match received {
+                Some(Ok(Message::Text(data))) => {
+                    events::emit(app, &WebSocketMessage { data, binary: false });
+                }
+                Some(Ok(Message::Binary(data))) => {
+                    let data = base64::engine::general_purpose::STANDARD.encode(data);
+                    events::emit(app, &WebSocketMessage { data, binary: true });
+                }
+                Some(Ok(Message::Pong(_))) => awaiting_pong = false,
+                Some(Ok(Message::Close(frame))) => {
+                    tracing::info!("Realtime connection closed by the server: {:?}", frame);
+                    if is_auth_close(frame.as_ref()) {
+                        return Ended::Rejected;
+                    }
+                    return Ended::Lost;
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    tracing::warn!("Realtime connection lost: {}", e);
+                    return Ended::Lost;
+                }
+                None => return Ended::Lost,
+            },
+            _ = ping.tick() => {
+                if awaiting_pong {
+                    tracing::warn!("Realtime connection unresponsive");
+                    return Ended::Lost;
+                }
+                awaiting_pong = true;
+                if sink.send(Message::Ping(Vec::new())).await.is_err() {
+                    return Ended::Lost;
+                }
+            }
+            received = control.recv() => match received {
+                Some(Control::Send(message)) => {
+                    if let Err(e) = sink.send(Message::Text(message.clone())).await {
+                        tracing::warn!("Failed to send message: {}", e);
+                        queue.push(message);
+                        return Ended::Lost;
+                    }
+                }
+                Some(Control::Resume) => {}
+                Some(Control::Suspend) => {
+                    going_away(&mut sink, "App in background").await;
+                    return Ended::Suspended;
+                }
+                Some(Control::Close) | None => {
+                    going_away(&mut sink, "Closed by the app").await;
+                    return Ended::Closed;
+                }
+            },
+        }
+    }
+}
+
+/// Close the connection cleanly
+async fn going_away<S>(sink: &mut S, reason: &'static str)
+where
+    S: futures_util::Sink<Message> + Unpin,
+{
+    let frame = CloseFrame {
+        code: CloseCode::Away,
+        reason: reason.into(),
+    };
+    let _ = sink.send(Message::Close(Some(frame))).await;
+    let _ = sink.close().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_delay_backs_off_to_cap() {
+        assert_eq!(reconnect_delay(1), Duration::from_millis(constants::WS_RECONNECT_BASE_DELAY_MS));
+        assert_eq!(reconnect_delay(2), Duration::from_millis(constants::WS_RECONNECT_BASE_DELAY_MS * 2));
+        assert_eq!(reconnect_delay(100), Duration::from_millis(constants::WS_RECONNECT_MAX_DELAY_MS));
+    }
+
+    #[test]
+    fn test_url_must_be_wss_on_app_origin() {
+        let app_url = "https://app.elulib.com";
+        assert!(validate_url("wss://app.elulib.com/realtime", app_url).is_ok());
+        assert!(validate_url("ws://app.elulib.com/realtime", app_url).is_err());
+        assert!(validate_url("wss://evil.example/realtime", app_url).is_err());
+        assert!(validate_url("not a url", app_url).is_err());
+    }
+
+    #[test]
+    fn test_auth_close_codes_stop_the_client() {
+        let frame = |code: u16| CloseFrame {
+            code: CloseCode::from(code),
+            reason: "".into(),
+        };
+        assert!(is_auth_close(Some(&frame(4401))));
+        assert!(is_auth_close(Some(&frame(1008))));
+        assert!(!is_auth_close(Some(&frame(1001))));
+        assert!(!is_auth_close(None));
+    }
+
+    #[test]
+    fn test_queue_drops_oldest() {
+        let mut queue = OutgoingQueue::default();
+        for index in 0..constants::WS_MAX_QUEUED_MESSAGES {
+            assert!(!queue.push(index.to_string()));
+        }
+        assert!(queue.push("last".to_string()));
+        assert_eq!(queue.len(), constants::WS_MAX_QUEUED_MESSAGES);
+        assert_eq!(queue.pop().as_deref(), Some("1"));
+    }
+}
@@ -0,0 +1,32 @@
+/// Android-specific managed wipe request
+///
+/// This module reads the wipe request from the app restrictions set by the
+/// device policy controller (`RestrictionsManager`).
+///
+/// Note: This implementation provides the structure for Android managed
+/// wipes. The actual native implementation should be done in Java/Kotlin
+/// and connected via JNI or Tauri's native bridge.
+
+use crate::constants;
+use crate::AppResult;
+
+/// Read the wipe request from the app restrictions
+///
+/// # Returns
+///
+/// Returns the request identifier, or `None` if no wipe is requested.
+pub fn managed_wipe_request() -> AppResult<Option<String>> {
+    tracing::debug!("[Android] Reading app restriction {}", constants::WIPE_MANAGED_CONFIG_KEY);
+
+    // TODO: Implement native Android app restrictions lookup
+    // Example Kotlin implementation:
+    // ```kotlin
+    // val manager = context.getSystemService(Context.RESTRICTIONS_SERVICE) as RestrictionsManager
+    // return manager.applicationRestrictions.getString(key)
+    // ```
+    // The key must also be declared in res/xml/app_restrictions.xml.
+
+    // Placeholder: No app restrictions
+    // Replace this with actual native implementation
+    Ok(None)
+}
@@ -0,0 +1,31 @@
+/// iOS-specific managed wipe request
+///
+/// This module reads the wipe request from the managed app configuration
+/// pushed by the MDM (`com.apple.configuration.managed` user defaults).
+///
+/// Note: This implementation provides the structure for iOS managed wipes.
+/// The actual native implementation should be done in Objective-C/Swift
+/// and connected via FFI or Tauri's native bridge.
+
+use crate::constants;
+use crate::AppResult;
+
+/// Read the wipe request from the managed app configuration
+///
+/// # Returns
+///
+/// Returns the request identifier, or `None` if no wipe is requested.
+pub fn managed_wipe_request() -> AppResult<Option<String>> {
+    tracing::debug!("[iOS] Reading managed config key {}", constants::WIPE_MANAGED_CONFIG_KEY);
+
+    // TODO: Implement native iOS managed app config lookup
+    // Example Swift implementation:
+    // ```swift
+    // let config = UserDefaults.standard.dictionary(forKey: "com.apple.configuration.managed")
+    // return config?[key] as? String
+    // ```
+
+    // Placeholder: No managed configuration
+    // Replace this with actual native implementation
+    Ok(None)
+}
@@ -0,0 +1,488 @@
+/// Complete local data wipe
+///
+/// Decommissioning a device (or an MDM-triggered wipe) must leave nothing of
+/// the user's data on it. `wipe_all_local_data` first stops, for the rest of
+/// the process, the background loops that would write data back (sync,
+/// prefetch, log shipping) and closes the realtime connections (WebSocket,
+/// SSE), then runs the steps of `WIPE_ORDER` one after the other, continuing
+/// when a step fails:
+/// 1. `pending_queues`: uploads, downloads, prefetch list, analytics events,
+///    log records waiting to be shipped and ephemeral secrets, so nothing is
+///    sent or written back while the rest is wiped
+/// 2. `database`: the encrypted database is closed and its files deleted
+/// 3. `file_storage`: the app file storage and the document vault
+/// 4. `caches`: asset and image caches, then the whole cache directory
+/// 5. `preferences`: preferences and the restored app state
+/// 6. `keychain`: session token, database key, security log anchor, the
+///    native login sessions (`auth::session_keys`), the keys given by the
+///    frontend and the device-binding key; the database is deleted first so its key is never removed while
+///    the database is still readable
+/// 7. `app_data`: whatever is left in the data and log directories
+///
+/// The wipe ends with a verification: the data and cache directories must be
+/// empty, the stores and queues empty, and the known keychain keys absent.
+/// The report lists the failed steps and what remains. Log lines written
+/// after the wipe go to a new log file.
+///
+/// MDM: when the managed app configuration (iOS managed app config, Android
+/// app restrictions) sets `WIPE_MANAGED_CONFIG_KEY` to a request identifier,
+/// the wipe runs at launch and the frontend is notified with the
+/// `wipe://completed` event. The identifier of the last handled request is
+/// kept in `WIPE_MARKER_FILE`, which the wipe leaves in place, so a request
+/// is only handled once.
+
+#[cfg(target_os = "ios")]
+mod ios;
+
+#[cfg(target_os = "android")]
+mod android;
+
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_keystore::RemoveRequest;
+
+use crate::analytics::Analytics;
+use crate::asset_cache;
+use crate::auth;
+use crate::constants::{self, helpers};
+use crate::database::Database;
+use crate::device_binding;
+use crate::downloads::DownloadManager;
+use crate::ephemeral;
+use crate::events;
+use crate::file_storage;
+use crate::image_cache;
+use crate::log_shipping;
+use crate::plugins::{self, KeystoreExt};
+use crate::prefetch::{self, Prefetcher};
+use crate::prefs::Prefs;
+use crate::session_handoff;
+#[cfg(feature = "module-sse")]
+use crate::sse;
+use crate::state_restore::StateStore;
+use crate::sync;
+use crate::uploads::UploadQueue;
+use crate::vault::Vault;
+#[cfg(feature = "module-watch")]
+use crate::watch;
+#[cfg(feature = "module-realtime")]
+use crate::websocket;
+use crate::{AppError, AppResult};
+
+/// Step of the wipe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WipeStep {
+    /// Upload, download, analytics and log shipping queues, ephemeral secrets
+    PendingQueues,
+    /// Encrypted database files
+    Database,
+    /// App file storage and document vault
+    FileStorage,
+    /// Asset cache, image cache and cache directory
+    Caches,
+    /// Preferences and restored app state
+    Preferences,
+    /// Keychain entries and device-binding key
+    Keychain,
+    /// Data and log directories
+    AppData,
+}
+
+/// Order in which the steps run
+pub const WIPE_ORDER: [WipeStep; 7] = [
+    WipeStep::PendingQueues,
+    WipeStep::Database,
+    WipeStep::FileStorage,
+    WipeStep::Caches,
+    WipeStep::Preferences,
+    WipeStep::Keychain,
+    WipeStep::AppData,
+];
+
+/// Result of a step
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StepResult {
+    /// Step
+    pub step: WipeStep,
+    /// Error of a failed step
+    pub error: Option<String>,
+}
+
+/// Verification report of the wipe
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WipeReport {
+    /// Steps, in the order they ran
+    pub steps: Vec<StepResult>,
+    /// What remains after the wipe (files, stores, keychain keys)
+    pub remaining: Vec<String>,
+    /// Whether every step succeeded and nothing remains
+    pub verified: bool,
+    /// Time the wipe ended (seconds since the Unix epoch)
+    pub completed_at: u64,
+}
+
+/// Delete the contents of a directory, keeping the directory and the files
+/// named in `keep`
+///
+/// A missing directory is already clear. Every entry is attempted even if one
+/// fails.
+pub fn clear_dir(dir: &Path, keep: &[&str]) -> AppResult<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(AppError::Storage(format!("Failed to read {}: {}", dir.display(), e))),
+    };
+    let mut errors = Vec::new();
+    for entry in entries.filter_map(Result::ok).filter(|entry| !is_kept(entry, keep)) {
+        let path = entry.path();
+        let removed = match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => std::fs::remove_dir_all(&path),
+            _ => std::fs::remove_file(&path),
+        };
+        if let Err(e) = removed {
+            errors.push(format!("{}: {}", path.display(), e));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Storage(format!("Failed to delete {}", errors.join(", "))))
+    }
+}
+
+/// Entries left in a directory, by path, except the files named in `keep`
+pub fn remaining_entries(dir: &Path, keep: &[&str]) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut remaining: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| !is_kept(entry, keep))
+        .map(|entry| entry.path().display().to_string())
+        .collect();
+    remaining.sort();
+    remaining
+}
+
+fn is_kept(entry: &std::fs::DirEntry, keep: &[&str]) -> bool {
+    entry.file_name().to_str().is_some_and(|name| keep.contains(&name))
+}
+
+/// Files of the encrypted database, in `dir`
+pub fn database_files(dir: &Path) -> Vec<PathBuf> {
+    ["", "-wal", "-shm", "-journal"]
+        .iter()
+        .map(|suffix| dir.join(format!("{}{}", constants::DATABASE_FILE, suffix)))
+        .collect()
+}
+
+fn remove_file(path: &Path) -> AppResult<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(AppError::Storage(format!("Failed to delete {}: {}", path.display(), e))),
+    }
+}
+
+fn data_dir(app: &AppHandle) -> AppResult<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve data directory: {}", e)))
+}
+
+fn cache_dir(app: &AppHandle) -> AppResult<PathBuf> {
+    app.path()
+        .app_cache_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve cache directory: {}", e)))
+}
+
+fn log_dir(app: &AppHandle) -> AppResult<PathBuf> {
+    app.path()
+        .app_log_dir()
+        .map_err(|e| AppError::Storage(format!("Failed to resolve log directory: {}", e)))
+}
+
+/// Keychain keys removed by the wipe
+///
+/// # Arguments
+///
+/// * `sessions` - Keys of the native login sessions (`auth::session_keys`)
+/// * `extra` - Keys given by the frontend
+fn keychain_keys(sessions: &[String], extra: &[String]) -> Vec<String> {
+    let mut keys: Vec<String> = [
+        constants::SESSION_TOKEN_KEY,
+        constants::DATABASE_KEY_NAME,
+        constants::AUDIT_LOG_ANCHOR_KEY,
+    ]
+    .iter()
+    .map(|key| key.to_string())
+    .chain(sessions.iter().cloned())
+    .chain(extra.iter().cloned())
+    .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// Stop what would write data back during or after the wipe, and empty the
+/// watch context
+fn halt(app: &AppHandle) {
+    sync::stop(app);
+    prefetch::stop(app);
+    log_shipping::stop();
+    #[cfg(feature = "module-realtime")]
+    if let Err(e) = websocket::close(app) {
+        tracing::warn!("Failed to close the realtime connection: {}", e);
+    }
+    #[cfg(feature = "module-sse")]
+    sse::unsubscribe_all(app);
+    #[cfg(feature = "module-watch")]
+    watch::clear(app);
+}
+
+fn remove_secret(app: &AppHandle, key: &str) -> AppResult<()> {
+    app.keystore()
+        .remove(RemoveRequest {
+            service: key.to_string(),
+            user: key.to_string(),
+        })
+        .map_err(|e| AppError::Keychain(helpers::keychain_remove_error(&e)))
+}
+
+fn run_step(app: &AppHandle, step: WipeStep, keys: &[String]) -> AppResult<()> {
+    match step {
+        WipeStep::PendingQueues => {
+            app.state::<UploadQueue>().clear();
+            app.state::<DownloadManager>().clear();
+            let prefetch = app.state::<Prefetcher>().set(Vec::new()).map(|_| ());
+            app.state::<Analytics>().discard_queued();
+            log_shipping::discard_queued();
+            ephemeral::wipe(app);
+            prefetch
+        }
+        WipeStep::Database => {
+            app.state::<Database>().close();
+            let dir = data_dir(app)?;
+            database_files(&dir).iter().try_for_each(|file| remove_file(file))
+        }
+        WipeStep::FileStorage => {
+            let vault = app.state::<Vault>().clear().map(|_| ());
+            let root = file_storage::root(app)?;
+            let storage = match std::fs::remove_dir_all(&root) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(e) => Err(AppError::Storage(format!("Failed to delete file storage: {}", e))),
+            };
+            vault.and(storage)
+        }
+        WipeStep::Caches => {
+            let assets = asset_cache::clear(app).map(|_| ());
+            let images = image_cache::clear(app).map(|_| ());
+            assets.and(images).and(clear_dir(&cache_dir(app)?, &[]))
+        }
+        WipeStep::Preferences => {
+            app.state::<StateStore>().clear();
+            app.state::<Prefs>().clear().map(|_| ())
+        }
+        WipeStep::Keychain => {
+            plugins::ensure_keystore(app)?;
+            let mut errors: Vec<String> = keys
+                .iter()
+                .filter_map(|key| remove_secret(app, key).err())
+                .map(|e| e.to_string())
+                .collect();
+            if let Err(e) = device_binding::delete_key() {
+                errors.push(e.to_string());
+            }
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(AppError::Keychain(errors.join("; ")))
+            }
+        }
+        WipeStep::AppData => {
+            clear_dir(&data_dir(app)?, &[constants::WIPE_MARKER_FILE]).and(clear_dir(&log_dir(app)?, &[]))
+        }
+    }
+}
+
+/// What remains after the wipe
+fn verify(app: &AppHandle, keys: &[String]) -> Vec<String> {
+    let mut remaining = Vec::new();
+    match data_dir(app) {
+        Ok(dir) => remaining.extend(remaining_entries(&dir, &[constants::WIPE_MARKER_FILE])),
+        Err(e) => remaining.push(e.to_string()),
+    }
+    match cache_dir(app) {
+        Ok(dir) => remaining.extend(remaining_entries(&dir, &[])),
+        Err(e) => remaining.push(e.to_string()),
+    }
+    if !app.state::<Prefs>().all().is_empty() {
+        remaining.push("preferences".to_string());
+    }
+    if !app.state::<UploadQueue>().list().is_empty() {
+        remaining.push("upload queue".to_string());
+    }
+    if !app.state::<Vault>().list().is_empty() {
+        remaining.push("document vault".to_string());
+    }
+    if !app.state::<DownloadManager>().list().is_empty() {
+        remaining.push("download list".to_string());
+    }
+    if !app.state::<Analytics>().queued().is_empty() {
+        remaining.push("analytics queue".to_string());
+    }
+    for key in keys {
+        match session_handoff::keychain_secret(app, key) {
+            Ok(None) => {}
+            Ok(Some(_)) => remaining.push(format!("keychain: {}", key)),
+            Err(e) => remaining.push(format!("keychain: {} ({})", key, e)),
+        }
+    }
+    remaining
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Wipe all local data
+///
+/// # Arguments
+///
+/// * `app` - The app handle
+/// * `extra_keys` - Keychain keys written by the frontend, removed along
+///   with the native ones
+///
+/// # Returns
+///
+/// Returns the `WipeReport`; failed steps do not stop the wipe.
+pub fn wipe_all(app: &AppHandle, extra_keys: &[String]) -> WipeReport {
+    tracing::warn!("Wiping all local data");
+    halt(app);
+    let sessions = auth::session_keys(app);
+    if let Err(e) = &sessions {
+        tracing::error!("Local data wipe: failed to list the login sessions: {}", e);
+    }
+    let keys = keychain_keys(sessions.as_deref().unwrap_or(&[]), extra_keys);
+
+    let steps: Vec<StepResult> = WIPE_ORDER
+        .iter()
+        .map(|&step| {
+            let error = run_step(app, step, &keys).err().map(|e| e.to_string());
+            if let Some(e) = &error {
+                tracing::error!("Local data wipe: {:?} failed: {}", step, e);
+            }
+            StepResult { step, error }
+        })
+        .collect();
+
+    let mut remaining = verify(app, &keys);
+    if let Err(e) = sessions {
+        remaining.push(format!("keychain: login sessions ({})", e));
+    }
+    let verified = remaining.is_empty() && steps.iter().all(|step| step.error.is_none());
+    if verified {
+        tracing::info!("Local data wipe verified");
+    } else {
+        tracing::error!("Local data wipe incomplete: {} item(s) remain", remaining.len());
+    }
+    WipeReport {
+        steps,
+        remaining,
+        verified,
+        completed_at: now_secs(),
+    }
+}
+
+/// Read the wipe request set by the MDM, if any
+fn managed_wipe_request() -> AppResult<Option<String>> {
+    #[cfg(target_os = "ios")]
+    {
+        ios::managed_wipe_request()
+    }
+
+    #[cfg(target_os = "android")]
+    {
+        android::managed_wipe_request()
+    }
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        Ok(None)
+    }
+}
+
+/// Run the wipe requested by the MDM, once per request
+///
+/// Called at startup.
+pub fn start(app: &AppHandle) -> AppResult<()> {
+    let Some(request) = managed_wipe_request()?.filter(|request| !request.is_empty()) else {
+        return Ok(());
+    };
+    let marker = data_dir(app)?.join(constants::WIPE_MARKER_FILE);
+    if std::fs::read_to_string(&marker).ok().as_deref() == Some(request.as_str()) {
+        return Ok(());
+    }
+
+    tracing::warn!("Local data wipe requested by the device management");
+    let report = wipe_all(app, &[]);
+    if let Some(parent) = marker.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| AppError::Storage(format!("Failed to create data directory: {}", e)))?;
+    }
+    std::fs::write(&marker, &request).map_err(|e| AppError::Storage(format!("Failed to record wipe request: {}", e)))?;
+    events::emit(app, &report);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wipe_order() {
+        let position = |step| WIPE_ORDER.iter().position(|&s| s == step).unwrap();
+        assert_eq!(WIPE_ORDER[0], WipeStep::PendingQueues);
+        assert!(position(WipeStep::Database) < position(WipeStep::Keychain));
+        assert!(position(WipeStep::Preferences) < position(WipeStep::AppData));
+        assert_eq!(WIPE_ORDER[WIPE_ORDER.len() - 1], WipeStep::AppData);
+    }
+
+    #[test]
+    fn test_clear_dir_keeps_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("storage/annexes")).unwrap();
+        std::fs::write(dir.path().join("storage/annexes/budget.pdf"), b"pdf").unwrap();
+        std::fs::write(dir.path().join(constants::PREFS_FILE), b"{}").unwrap();
+        std::fs::write(dir.path().join(constants::WIPE_MARKER_FILE), b"request-1").unwrap();
+        assert_eq!(remaining_entries(dir.path(), &[constants::WIPE_MARKER_FILE]).len(), 2);
+
+        clear_dir(dir.path(), &[constants::WIPE_MARKER_FILE]).unwrap();
+        assert!(dir.path().join(constants::WIPE_MARKER_FILE).exists());
+        assert!(remaining_entries(dir.path(), &[constants::WIPE_MARKER_FILE]).is_empty());
+        assert_eq!(remaining_entries(dir.path(), &[]).len(), 1);
+        assert!(clear_dir(&dir.path().join("missing"), &[]).is_ok());
+    }
+
+    #[test]
+    fn test_keychain_keys_include_native_keys() {
+        let sessions = ["auth.session.h1".to_string(), constants::AUTH_SESSION_INDEX_KEY.to_string()];
+        let keys = keychain_keys(&sessions, &["device_id".to_string(), constants::SESSION_TOKEN_KEY.to_string()]);
+        assert!(keys.iter().any(|key| key == constants::DATABASE_KEY_NAME));
+        assert!(keys.iter().any(|key| key == "auth.session.h1"), "Login sessions are removed");
+        assert!(keys.iter().any(|key| key == constants::AUTH_SESSION_INDEX_KEY));
+        assert!(keys.iter().any(|key| key == "device_id"));
+        assert_eq!(keys.iter().filter(|key| *key == constants::SESSION_TOKEN_KEY).count(), 1);
+
+        let dir = Path::new("/data");
+        let files = database_files(dir);
+        assert!(files.contains(&dir.join(format!("{}-wal", constants::DATABASE_FILE))));
+    }
+}
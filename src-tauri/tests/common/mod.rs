@@ -4,16 +4,100 @@
 /// This module can be used by integration tests in this directory.
 
 
-/// Mock Tauri context for testing (placeholder for future use)
+/// Command invocation on the mock runtime (`create_test_app()`)
+#[cfg(feature = "test-harness")]
 pub mod mock_context {
-    use std::sync::Arc;
-    
-    /// Creates a mock Tauri context for testing
+    use serde_json::Value;
+    use tauri::test::MockRuntime;
+    use tauri::WebviewWindow;
+
+    /// Creates the test app and its main webview
+    #[allow(dead_code)]
+    pub fn create_test_webview() -> (tauri::App<MockRuntime>, WebviewWindow<MockRuntime>) {
+        let app = elulib_mobile::create_test_app();
+        // Sensitive commands are only accepted from the application origin
+        let url = elulib_mobile::runtime_config::get()
+            .app_url
+            .parse()
+            .expect("Invalid application URL");
+        let webview = tauri::WebviewWindowBuilder::new(&app, "main", tauri::WebviewUrl::External(url))
+            .build()
+            .expect("Failed to create test webview");
+        (app, webview)
+    }
+
+    /// Invokes a command from the webview, as the frontend would
+    ///
+    /// Returns the command response (`{ ok, data, error }` for commands
+    /// returning a `CommandResponse`), or the rejection of the invoke.
     #[allow(dead_code)]
-    pub fn create_mock_context() -> Arc<()> {
-        // Placeholder for mock context creation
-        // In the future, this could create a proper mock Tauri context
-        Arc::new(())
+    pub fn invoke(webview: &WebviewWindow<MockRuntime>, command: &str, args: Value) -> Result<Value, Value> {
+        elulib_mobile::invoke_test_command(webview, command, args)
+    }
+}
+
+/// Command-level test framework: invokes registered commands by name with
+/// JSON payloads and checks the standardized response envelope
+/// (`{ ok, data, error: { code, message, retryable } }`)
+#[cfg(feature = "test-harness")]
+pub mod commands {
+    use serde_json::Value;
+    use tauri::test::MockRuntime;
+    use tauri::WebviewWindow;
+
+    use super::assertions::assert_envelope;
+    use super::mock_context::{create_test_webview, invoke};
+
+    /// Mock app with its main webview
+    pub struct TestApp {
+        _app: tauri::App<MockRuntime>,
+        webview: WebviewWindow<MockRuntime>,
+    }
+
+    impl TestApp {
+        /// Creates the mock app (`create_test_app()`)
+        #[allow(dead_code)]
+        pub fn new() -> Self {
+            let (app, webview) = create_test_webview();
+            TestApp { _app: app, webview }
+        }
+
+        /// Invokes a command, returning its raw response or rejection
+        #[allow(dead_code)]
+        pub fn invoke(&self, command: &str, args: Value) -> Result<Value, Value> {
+            invoke(&self.webview, command, args)
+        }
+
+        /// Invokes a command and checks its response envelope
+        ///
+        /// Panics if the invoke was rejected (unknown command, invalid
+        /// arguments) or the envelope is malformed.
+        #[allow(dead_code)]
+        pub fn invoke_response(&self, command: &str, args: Value) -> Value {
+            let response = self
+                .invoke(command, args)
+                .unwrap_or_else(|e| panic!("{} was rejected: {}", command, e));
+            assert_envelope(&response);
+            response
+        }
+
+        /// Invokes a command expected to succeed, returning its data
+        #[allow(dead_code)]
+        pub fn invoke_ok(&self, command: &str, args: Value) -> Value {
+            let response = self.invoke_response(command, args);
+            assert_eq!(response["ok"], Value::Bool(true), "{} failed: {}", command, response["error"]);
+            response["data"].clone()
+        }
+
+        /// Invokes a command expected to fail with an error code, returning
+        /// the error
+        #[allow(dead_code)]
+        pub fn invoke_err(&self, command: &str, args: Value, code: &str) -> Value {
+            let response = self.invoke_response(command, args);
+            assert_eq!(response["ok"], Value::Bool(false), "{} should have failed", command);
+            assert_eq!(response["error"]["code"], Value::from(code), "{}: {}", command, response["error"]);
+            response["error"].clone()
+        }
     }
 }
 
@@ -53,4 +137,23 @@ pub mod assertions {
     {
         assert!(result.is_err(), "{}: Expected error but got: {:?}", message, result);
     }
+    
+    /// Asserts that a command response has the standardized envelope:
+    /// `data` on success, an `error` with a code, a message and a retry hint
+    /// on failure
+    #[allow(dead_code)]
+    pub fn assert_envelope(response: &serde_json::Value) {
+        let ok = response["ok"]
+            .as_bool()
+            .unwrap_or_else(|| panic!("Response without ok flag: {}", response));
+        if ok {
+            assert!(response["error"].is_null(), "Successful response with an error: {}", response);
+        } else {
+            let error = &response["error"];
+            assert!(response["data"].is_null(), "Failed response with data: {}", response);
+            assert!(error["code"].is_string(), "Error without code: {}", response);
+            assert!(error["message"].is_string(), "Error without message: {}", response);
+            assert!(error["retryable"].is_boolean(), "Error without retry hint: {}", response);
+        }
+    }
 }
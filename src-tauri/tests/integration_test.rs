@@ -4,171 +4,178 @@
 /// Integration tests run in a separate crate, so they can only access
 /// public APIs of the library.
 /// 
+/// The tests invoking commands run on the Tauri mock runtime with the mock
+/// backends (`create_test_app()`), and need the `test-harness` feature:
+/// ```bash
+/// cargo test --features test-harness --test integration_test
+/// ```
+/// 
 /// Test helpers are available in the `common` module.
 
 mod common;
 
-use elulib_mobile::create_app;
-
-#[test]
-#[ignore] // Ignore by default as it requires Tauri runtime
-fn test_app_initialization_with_context() {
-    // This test would require a test Tauri context
-    // It's marked as ignore because it needs special setup
-    // To run: cargo test -- --ignored
-    let _builder = create_app();
-    // Future: test builder.run() with mock context
-    assert!(true, "App initialization test placeholder");
-}
+#[cfg(feature = "test-harness")]
+mod keystore {
+    use serde_json::json;
 
-/// Integration test for keystore operations via Tauri commands (requires runtime)
-///
-/// This test is ignored by default because it requires:
-/// - A Tauri runtime environment
-/// - Platform-specific keychain access (iOS Keychain or Android Keystore)
-/// - May require running on a physical device
-///
-/// This test would verify that the keychain commands (`keychain_store`,
-/// `keychain_retrieve`, `keychain_remove`, `keychain_exists`) work correctly
-/// when invoked from the frontend.
-///
-/// To run this test:
-/// ```bash
-/// cargo test --test integration_test -- --ignored
-/// ```
-#[test]
-#[ignore]
-fn test_keystore_store_and_retrieve() {
-    // This test would require a running Tauri application with a test context
-    // Example of what the test would do:
-    //
-    // // Create app with test context
-    // let app = create_test_app().await;
-    //
-    // // Test keychain_store command
-    // let key = "test_key";
-    // let value = "test_value";
-    // app.tauri_scope().invoke("keychain_store", json!({ "key": key, "value": value }))
-    //     .await
-    //     .expect("Failed to store value");
-    //
-    // // Test keychain_retrieve command
-    // let retrieved: String = app.tauri_scope().invoke("keychain_retrieve", json!({ "key": key }))
-    //     .await
-    //     .expect("Failed to retrieve value");
-    // assert_eq!(retrieved, value);
-    //
-    // // Test keychain_remove command
-    // app.tauri_scope().invoke("keychain_remove", json!({ "key": key }))
-    //     .await
-    //     .expect("Failed to remove value");
-    //
-    // // Test keychain_exists command
-    // let exists: bool = app.tauri_scope().invoke("keychain_exists", json!({ "key": key }))
-    //     .await
-    //     .expect("Failed to check existence");
-    // assert_eq!(exists, false);
-    
-    // Placeholder assertion
-    assert!(true, "Keystore integration test placeholder - requires runtime");
-}
+    use crate::common::commands::TestApp;
 
-/// Integration test for keystore error handling (requires runtime)
-///
-/// Tests that retrieving a non-existent key returns an error.
-#[test]
-#[ignore]
-fn test_keystore_retrieve_nonexistent_key() {
-    // This test would require a running Tauri application
-    // Example:
-    //
-    // use tauri_plugin_keystore::Keystore;
-    //
-    // let result = Keystore::get("nonexistent_key");
-    // assert!(result.is_err(), "Retrieving non-existent key should return an error");
-    
-    assert!(true, "Keystore error handling test placeholder - requires runtime");
-}
+    #[test]
+    fn test_app_initialization_with_context() {
+        let app = TestApp::new();
+        assert_eq!(app.invoke_ok("keychain_exists", json!({ "key": "init_test_key" })), json!(false));
+    }
 
-/// Integration test for keystore key removal (requires runtime)
-///
-/// Tests that keys can be removed from the keystore.
-#[test]
-#[ignore]
-fn test_keystore_remove_key() {
-    // This test would require a running Tauri application
-    // Example:
-    //
-    // use tauri_plugin_keystore::Keystore;
-    //
-    // let key = "test_remove_key";
-    // let value = "test_value";
-    //
-    // // Store a value
-    // Keystore::set(key, value).expect("Failed to store value");
-    //
-    // // Verify it exists
-    // let retrieved = Keystore::get(key).expect("Failed to retrieve value");
-    // assert_eq!(retrieved, value);
-    //
-    // // Remove the value
-    // Keystore::remove(key).expect("Failed to remove value");
-    //
-    // // Verify it no longer exists
-    // let result = Keystore::get(key);
-    // assert!(result.is_err(), "Key should no longer exist after removal");
-    
-    assert!(true, "Keystore removal test placeholder - requires runtime");
-}
+    /// Unknown commands are rejected by the IPC, not answered with an envelope
+    #[test]
+    fn test_unknown_command_is_rejected() {
+        let app = TestApp::new();
+        assert!(app.invoke("not_a_command", json!({})).is_err());
+    }
 
-/// Integration test for keystore overwrite behavior (requires runtime)
-///
-/// Tests that storing a value with an existing key overwrites the old value.
-#[test]
-#[ignore]
-fn test_keystore_overwrite_existing_key() {
-    // This test would require a running Tauri application
-    // Example:
-    //
-    // use tauri_plugin_keystore::Keystore;
-    //
-    // let key = "test_overwrite_key";
-    // let old_value = "old_value";
-    // let new_value = "new_value";
-    //
-    // // Store initial value
-    // Keystore::set(key, old_value).expect("Failed to store initial value");
-    // let retrieved = Keystore::get(key).expect("Failed to retrieve initial value");
-    // assert_eq!(retrieved, old_value);
-    //
-    // // Overwrite with new value
-    // Keystore::set(key, new_value).expect("Failed to overwrite value");
-    // let retrieved = Keystore::get(key).expect("Failed to retrieve new value");
-    // assert_eq!(retrieved, new_value);
-    //
-    // // Clean up
-    // Keystore::remove(key).expect("Failed to remove key");
-    
-    assert!(true, "Keystore overwrite test placeholder - requires runtime");
-}
+    /// Store, retrieve, remove and check a key through the keychain commands
+    #[test]
+    fn test_keystore_store_and_retrieve() {
+        let app = TestApp::new();
+        let key = "roundtrip_test_key";
+
+        app.invoke_ok("keychain_store", json!({ "key": key, "value": "test_value" }));
+        assert_eq!(app.invoke_ok("keychain_retrieve", json!({ "key": key })), json!("test_value"));
+        assert_eq!(app.invoke_ok("keychain_exists", json!({ "key": key })), json!(true));
+
+        app.invoke_ok("keychain_remove", json!({ "key": key }));
+        assert_eq!(app.invoke_ok("keychain_exists", json!({ "key": key })), json!(false));
+    }
+
+    /// Tests that retrieving a non-existent key returns an error
+    #[test]
+    fn test_keystore_retrieve_nonexistent_key() {
+        let app = TestApp::new();
+        let error = app.invoke_err("keychain_retrieve", json!({ "key": "nonexistent_key" }), "KEYCHAIN_NOT_FOUND");
+        assert_eq!(error["retryable"], json!(false));
+    }
+
+    /// Tests that keys can be removed from the keystore
+    #[test]
+    fn test_keystore_remove_key() {
+        let app = TestApp::new();
+        let key = "remove_test_key";
 
-/// Integration test for keystore concurrent access (requires runtime)
-///
-/// Tests that the keystore handles concurrent access correctly.
-/// Note: This test would require a running Tauri application and actual
-/// keystore implementation to test properly.
-#[test]
-#[ignore]
-fn test_keystore_concurrent_access() {
-    // This test would require a running Tauri application
-    // Example:
-    //
-    // use tauri_plugin_keystore::Keystore;
-    // use std::thread;
-    //
-    // // Test concurrent store/retrieve operations
-    // // Implementation would depend on actual plugin API
-    //
-    assert!(true, "Keystore concurrent access test placeholder - requires runtime");
+        app.invoke_ok("keychain_store", json!({ "key": key, "value": "test_value" }));
+        app.invoke_ok("keychain_remove", json!({ "key": key }));
+        app.invoke_err("keychain_retrieve", json!({ "key": key }), "KEYCHAIN_NOT_FOUND");
+    }
+
+    /// Tests that storing a value with an existing key overwrites the old value
+    #[test]
+    fn test_keystore_overwrite_existing_key() {
+        let app = TestApp::new();
+        let key = "overwrite_test_key";
+
+        app.invoke_ok("keychain_store", json!({ "key": key, "value": "old_value" }));
+        app.invoke_ok("keychain_store", json!({ "key": key, "value": "new_value" }));
+        assert_eq!(app.invoke_ok("keychain_retrieve", json!({ "key": key })), json!("new_value"));
+        app.invoke_ok("keychain_remove", json!({ "key": key }));
+    }
+
+    /// Tests invalid arguments are rejected before reaching the keystore
+    #[test]
+    fn test_keystore_invalid_key() {
+        let app = TestApp::new();
+        app.invoke_err("keychain_store", json!({ "key": "", "value": "test_value" }), "INVALID_ARGUMENT");
+    }
+
+    /// Tests that the session token is only released by claim_session_token
+    #[test]
+    fn test_keystore_session_token_is_not_retrievable() {
+        let app = TestApp::new();
+        app.invoke_err("keychain_retrieve", json!({ "key": "session_token" }), "FORBIDDEN");
+    }
+
+    /// Tests that keys reserved to the native layer cannot be overwritten or removed
+    #[test]
+    fn test_keystore_reserved_keys() {
+        let app = TestApp::new();
+        app.invoke_err("keychain_store", json!({ "key": "session_token", "value": "forged" }), "FORBIDDEN");
+        app.invoke_err("keychain_remove", json!({ "key": "elulib.database.key" }), "FORBIDDEN");
+        app.invoke_err("keychain_retrieve", json!({ "key": "auth.session.index" }), "FORBIDDEN");
+    }
+
+    /// Tests that the keystore handles concurrent access correctly
+    #[test]
+    fn test_keystore_concurrent_access() {
+        let handles: Vec<_> = (0..8)
+            .map(|index| {
+                std::thread::spawn(move || {
+                    let app = TestApp::new();
+                    let key = format!("concurrent_test_key_{}", index);
+                    let value = format!("value_{}", index);
+                    app.invoke_ok("keychain_store", json!({ "key": key, "value": value }));
+                    assert_eq!(app.invoke_ok("keychain_retrieve", json!({ "key": key })), json!(value));
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("Concurrent keystore access failed");
+        }
+    }
 }
 
+#[cfg(feature = "test-harness")]
+mod replay {
+    use serde_json::json;
+
+    use elulib_mobile::invoke_recorder::{self, InvokeOutcome};
+
+    use crate::common::mock_context::create_test_webview;
+
+    /// A recording replays against the mock app with the same outcomes
+    #[test]
+    fn test_replay_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.jsonl");
+        let lines = [
+            json!({ "seq": 1, "command": "keychain_store", "args": { "key": "replay_test_key", "value": "[REDACTED]" },
+                    "invokedAt": 0, "outcome": "ok", "durationMs": 2 }),
+            json!({ "seq": 2, "command": "keychain_exists", "args": { "key": "replay_test_key" },
+                    "invokedAt": 1, "outcome": "ok", "durationMs": 1 }),
+            json!({ "seq": 3, "command": "keychain_retrieve", "args": { "key": "replay_missing_key" },
+                    "invokedAt": 2, "outcome": "error", "durationMs": 1 }),
+            json!({ "seq": 4, "command": "db_query", "args": { "sql": "SELECT 1" },
+                    "invokedAt": 3, "outcome": "rejected", "durationMs": 0 }),
+            json!({ "seq": 5, "command": "sync_now", "args": {},
+                    "invokedAt": 4, "outcome": "ok", "durationMs": 40 }),
+        ];
+        let contents: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        std::fs::write(&path, contents.join("\n")).unwrap();
+
+        let entries = invoke_recorder::read_recording(&path).unwrap();
+        let (_app, webview) = create_test_webview();
+        let report = invoke_recorder::replay(&webview, &entries);
+        assert_eq!(report.replayed, 3);
+        assert_eq!(report.skipped, 2);
+        assert!(report.mismatches.is_empty(), "Unexpected mismatches: {:?}", report.mismatches);
+    }
+
+    /// Outcome changes are reported
+    #[test]
+    fn test_replay_reports_mismatches() {
+        let mut entries = vec![serde_json::from_value(json!({
+            "seq": 1, "command": "keychain_retrieve", "args": { "key": "replay_never_stored" },
+            "invokedAt": 0, "outcome": "ok", "durationMs": 1,
+        }))
+        .unwrap()];
+        entries.push(serde_json::from_value(json!({
+            "seq": 2, "command": "keychain_store", "args": { "key": "", "value": "x" },
+            "invokedAt": 1, "outcome": "ok", "durationMs": 1,
+        }))
+        .unwrap());
+
+        let (_app, webview) = create_test_webview();
+        let report = invoke_recorder::replay(&webview, &entries);
+        assert_eq!(report.mismatches.len(), 2);
+        assert_eq!(report.mismatches[0].expected, InvokeOutcome::Ok);
+        assert_eq!(report.mismatches[0].actual, InvokeOutcome::Error);
+        assert_eq!(report.mismatches[1].response["error"]["code"], json!("INVALID_ARGUMENT"));
+    }
+}
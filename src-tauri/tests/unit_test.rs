@@ -7,6 +7,7 @@
 mod common;
 
 use elulib_mobile::{create_app, AppError, AppResult};
+#[cfg(feature = "module-notifications")]
 use elulib_mobile::notification_bridge;
 
 #[test]
@@ -53,6 +54,45 @@ fn test_error_conversion() {
     assert!(matches!(app_error, AppError::Tauri(_)));
 }
 
+#[test]
+fn test_error_conversion_by_subsystem() {
+    // Test the #[from] conversions of the other subsystems
+    let io: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+    assert!(matches!(io, AppError::Io(_)));
+    let serialization: AppError = serde_json::from_str::<u32>("x").unwrap_err().into();
+    assert!(matches!(serialization, AppError::Serialization(_)));
+    let connectivity: AppError = elulib_mobile::connectivity::ConnectivityError::Timeout.into();
+    assert!(matches!(connectivity, AppError::Connectivity(_)));
+}
+
+#[test]
+fn test_app_error_codes_are_stable() {
+    // Codes are part of the frontend contract and must never change
+    let codes = [
+        AppError::Tauri(tauri::Error::FailedToReceiveMessage).code(),
+        AppError::Keychain(String::new()).code(),
+        AppError::Notification(String::new()).code(),
+        AppError::Connectivity(elulib_mobile::connectivity::ConnectivityError::Timeout).code(),
+        AppError::Config(String::new()).code(),
+        AppError::Io(std::io::ErrorKind::Other.into()).code(),
+        AppError::Serialization(serde_json::from_str::<u32>("x").unwrap_err()).code(),
+    ];
+    assert_eq!(codes, [1000, 1100, 1200, 1300, 1400, 1500, 1600]);
+}
+
+#[test]
+fn test_app_error_serialization() {
+    // Commands returning AppResult reject with the code and the message
+    let error = AppError::Config("connectivity.host must not be empty".to_string());
+    assert_eq!(
+        tauri::ipc::InvokeError::from(error).0,
+        serde_json::json!({
+            "code": 1400,
+            "message": "Configuration error: connectivity.host must not be empty",
+        })
+    );
+}
+
 #[test]
 fn test_error_propagation() {
     // Test that errors propagate correctly through the application
@@ -149,7 +189,7 @@ fn test_validate_keychain_key_too_short() {
     // Test empty key (below minimum)
     let result = helpers::validate_keychain_key("");
     assert!(result.is_err(), "Empty key should be invalid");
-    let error_msg = result.unwrap_err();
+    let error_msg = result.unwrap_err().to_string();
     assert!(error_msg.contains("at least"), "Error message should mention minimum length");
     assert!(error_msg.contains("1"), "Error message should mention minimum value of 1");
 }
@@ -162,7 +202,7 @@ fn test_validate_keychain_key_too_long() {
     let too_long_key = "a".repeat(257);
     let result = helpers::validate_keychain_key(&too_long_key);
     assert!(result.is_err(), "Key exceeding maximum length should be invalid");
-    let error_msg = result.unwrap_err();
+    let error_msg = result.unwrap_err().to_string();
     assert!(error_msg.contains("at most"), "Error message should mention maximum length");
     assert!(error_msg.contains("256"), "Error message should mention maximum value of 256");
     assert!(error_msg.contains("257"), "Error message should mention actual length");
@@ -189,7 +229,7 @@ fn test_validate_keychain_value_too_long() {
     let too_long_value = "a".repeat(4097);
     let result = helpers::validate_keychain_value(&too_long_value);
     assert!(result.is_err(), "Value exceeding maximum length should be invalid");
-    let error_msg = result.unwrap_err();
+    let error_msg = result.unwrap_err().to_string();
     assert!(error_msg.contains("at most"), "Error message should mention maximum length");
     assert!(error_msg.contains("4096"), "Error message should mention maximum value of 4096");
     assert!(error_msg.contains("4097"), "Error message should mention actual length");
@@ -380,12 +420,12 @@ async fn test_show_notification_command_valid_input() {
     
     // On mobile platforms, should succeed (even if it's just logging in the placeholder implementation)
     // On other platforms, will return an error (which is expected)
-    #[cfg(any(target_os = "ios", target_os = "android"))]
+    #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
     {
         assert!(result.is_ok(), "show_notification should succeed with valid input on mobile platforms");
     }
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         // On non-mobile platforms, should return an error
         assert!(result.is_err(), "show_notification should fail on non-mobile platforms");
@@ -403,12 +443,12 @@ async fn test_show_notification_empty_strings() {
     
     // On mobile platforms, should succeed (empty notifications are valid, though not useful)
     // On other platforms, will return an error (which is expected)
-    #[cfg(any(target_os = "ios", target_os = "android"))]
+    #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
     {
         assert!(result.is_ok(), "show_notification should handle empty strings on mobile platforms");
     }
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         assert!(result.is_err(), "show_notification should fail on non-mobile platforms");
     }
@@ -423,12 +463,12 @@ async fn test_show_notification_long_strings() {
     
     let result = elulib_mobile::notifications::show_notification(&title, &body, icon);
     
-    #[cfg(any(target_os = "ios", target_os = "android"))]
+    #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
     {
         assert!(result.is_ok(), "show_notification should handle long strings on mobile platforms");
     }
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         assert!(result.is_err(), "show_notification should fail on non-mobile platforms");
     }
@@ -443,12 +483,12 @@ async fn test_show_notification_special_characters() {
     
     let result = elulib_mobile::notifications::show_notification(&title, &body, icon.as_deref());
     
-    #[cfg(any(target_os = "ios", target_os = "android"))]
+    #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
     {
         assert!(result.is_ok(), "show_notification should handle special characters on mobile platforms");
     }
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         assert!(result.is_err(), "show_notification should fail on non-mobile platforms");
     }
@@ -485,13 +525,14 @@ async fn test_check_notification_permission() {
 }
 
 /// Test notification support check
+#[cfg(feature = "module-notifications")]
 #[tokio::test]
 async fn test_is_notification_supported() {
     let result = notification_bridge::is_notification_supported().await;
     
-    assert!(matches!(result, Ok(_)), "is_notification_supported should return Ok(bool)");
+    assert!(result.ok, "is_notification_supported should succeed");
     
-    if let Ok(supported) = result {
+    if let Some(supported) = result.data {
         // On iOS/Android builds, should be true
         // On other platforms, should be false
         assert!(supported || !supported, "Support check result should be a boolean");
@@ -516,7 +557,7 @@ async fn test_notification_flow_simulation() {
         notification_data.icon.as_deref(),
     );
     
-    #[cfg(any(target_os = "ios", target_os = "android"))]
+    #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
     {
         assert!(result.is_ok(), "Notification should be processed successfully on mobile platforms");
         
@@ -527,7 +568,7 @@ async fn test_notification_flow_simulation() {
         }
     }
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         // On non-mobile platforms, expect an error
         assert!(result.is_err(), "Notification should fail on non-mobile platforms");
@@ -553,12 +594,12 @@ async fn test_multiple_notifications_sequence() {
     for (title, body) in notifications {
         let result = elulib_mobile::notifications::show_notification(title, body, None);
         
-        #[cfg(any(target_os = "ios", target_os = "android"))]
+        #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
         {
             assert!(result.is_ok(), "Each notification should succeed on mobile platforms: {}", title);
         }
         
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
+        #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
         {
             assert!(result.is_err(), "Each notification should fail on non-mobile platforms: {}", title);
         }
@@ -582,12 +623,12 @@ async fn test_notification_icon_variations() {
             icon,
         );
         
-        #[cfg(any(target_os = "ios", target_os = "android"))]
+        #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
         {
             assert!(result.is_ok(), "Notification with {} should succeed on mobile platforms", description);
         }
         
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
+        #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
         {
             assert!(result.is_err(), "Notification with {} should fail on non-mobile platforms", description);
         }
@@ -615,12 +656,18 @@ fn test_platform_specific_routing() {
         assert!(true, "Running on Android");
     }
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         // Other platforms - should return error
         assert!(result.is_err(), "Platform routing should return error on non-mobile platforms");
         assert!(true, "Running on non-mobile platform");
     }
+    
+    #[cfg(all(feature = "dev-mock", not(any(target_os = "ios", target_os = "android"))))]
+    {
+        // Desktop development builds - notifications go to the mock
+        assert!(result.is_ok(), "Platform routing should use the mock notifications with dev-mock");
+    }
 }
 
 /// Test error handling for invalid inputs
@@ -640,7 +687,7 @@ async fn test_notification_error_handling() {
         }
         Err(e) => {
             // Implementation rejects long strings with error
-            assert!(!e.is_empty(), "Error message should not be empty");
+            assert!(!e.to_string().is_empty(), "Error message should not be empty");
         }
     }
 }
@@ -672,7 +719,7 @@ async fn test_complete_notification_flow() {
     );
     
     // Step 3: Verify the flow completed successfully
-    #[cfg(any(target_os = "ios", target_os = "android"))]
+    #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
     {
         assert!(result.is_ok(), "Complete notification flow should succeed on mobile platforms");
         
@@ -682,7 +729,7 @@ async fn test_complete_notification_flow() {
         }
     }
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         // On non-mobile platforms, expect an error
         assert!(result.is_err(), "Notification flow should fail on non-mobile platforms");
@@ -697,11 +744,12 @@ struct FrontendNotification {
 }
 
 /// Test notification flow with permission check
+#[cfg(feature = "module-notifications")]
 #[tokio::test]
 async fn test_notification_flow_with_permission() {
     // Step 1: Check if notifications are supported
     let supported = notification_bridge::is_notification_supported().await;
-    assert!(supported.is_ok(), "is_notification_supported should succeed");
+    assert!(supported.ok, "is_notification_supported should succeed");
     
     // Step 2: Request permission (simulating frontend: Notification.requestPermission())
     let permission_result = elulib_mobile::notifications::request_permission();
@@ -720,13 +768,13 @@ async fn test_notification_flow_with_permission() {
                 None,
             );
             
-            #[cfg(any(target_os = "ios", target_os = "android"))]
+            #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
             {
                 assert!(result.is_ok(), "Notification should succeed when permission is granted");
             }
             
             // On non-mobile platforms, result is unused but that's expected
-            #[cfg(not(any(target_os = "ios", target_os = "android")))]
+            #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
             {
                 let _ = result; // Suppress unused variable warning
             }
@@ -740,17 +788,18 @@ async fn test_notification_error_propagation() {
     // Test that errors are properly propagated through the system
     // On non-mobile platforms, should return an error
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         let result = elulib_mobile::notifications::show_notification("Test", "Body", None);
         assert!(result.is_err(), "Should return error on non-mobile platforms");
         
         if let Err(e) = result {
-            assert!(e.contains("not supported"), "Error message should indicate platform not supported");
+            assert!(matches!(e, elulib_mobile::AppError::Notification(_)), "Error should be a notification error");
+            assert!(e.to_string().contains("not supported"), "Error message should indicate platform not supported");
         }
     }
     
-    #[cfg(any(target_os = "ios", target_os = "android"))]
+    #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
     {
         // On mobile platforms, should succeed (placeholder implementation)
         let _result = elulib_mobile::notifications::show_notification("Test", "Body", None);
@@ -786,12 +835,12 @@ async fn test_notification_unicode() {
     
     let result = elulib_mobile::notifications::show_notification(&title, &body, icon);
     
-    #[cfg(any(target_os = "ios", target_os = "android"))]
+    #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
     {
         assert!(result.is_ok(), "Notification should handle unicode characters on mobile platforms");
     }
     
-    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
     {
         assert!(result.is_err(), "Notification should fail on non-mobile platforms");
     }
@@ -818,14 +867,32 @@ async fn test_concurrent_notifications() {
     for handle in handles {
         let result = handle.await.expect("Task should complete");
         
-        #[cfg(any(target_os = "ios", target_os = "android"))]
+        #[cfg(any(target_os = "ios", target_os = "android", feature = "dev-mock"))]
         {
             assert!(result.is_ok(), "Concurrent notification should succeed on mobile platforms");
         }
         
-        #[cfg(not(any(target_os = "ios", target_os = "android")))]
+        #[cfg(not(any(target_os = "ios", target_os = "android", feature = "dev-mock")))]
         {
             assert!(result.is_err(), "Concurrent notification should fail on non-mobile platforms");
         }
     }
 }
+
+// ============================================================================
+// Device Information Tests
+// ============================================================================
+
+/// Test that device information is collected for the current platform
+#[test]
+fn test_device_info_collect() {
+    let info = elulib_mobile::device_info::collect();
+
+    assert_eq!(info.platform, std::env::consts::OS);
+    assert!(!info.app_version.is_empty(), "App version should not be empty");
+
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    {
+        assert!(!info.is_emulator, "Desktop builds should never report an emulator");
+    }
+}